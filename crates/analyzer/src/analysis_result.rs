@@ -9,11 +9,14 @@ use mago_reporting::IssueCollection;
 use crate::code::IssueCode;
 use crate::statement::class_like::unused_members::UnusedMemberSpans;
 use crate::statement::class_like::unused_members::find_unused_member_spans;
+use crate::taint::TaintGraph;
 
 #[derive(Clone, Debug)]
 pub struct AnalysisResult {
     pub issues: IssueCollection,
     pub symbol_references: SymbolReferences,
+    /// Data flows recorded when taint analysis is enabled, solved once every file is analyzed.
+    pub taint_graph: TaintGraph,
     deferred_pragmas: Vec<DeferredPragmas>,
     #[cfg(not(target_arch = "wasm32"))]
     pub time_in_analysis: Duration,
@@ -25,6 +28,7 @@ impl AnalysisResult {
         Self {
             issues: IssueCollection::default(),
             symbol_references,
+            taint_graph: TaintGraph::new(),
             deferred_pragmas: Vec::new(),
             #[cfg(not(target_arch = "wasm32"))]
             time_in_analysis: Duration::default(),
@@ -34,6 +38,7 @@ impl AnalysisResult {
     pub fn extend(&mut self, other: Self) {
         self.issues.extend(other.issues);
         self.symbol_references.extend(other.symbol_references);
+        self.taint_graph.extend(other.taint_graph);
         self.deferred_pragmas.extend(other.deferred_pragmas);
     }
 
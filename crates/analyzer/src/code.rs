@@ -120,9 +120,11 @@ pub enum IssueCode {
     InvalidConstantSelector,
     InvalidConstantValue,
     InvalidContinue,
+    InvalidDataProvider,
     InvalidDestructuringSource,
     InvalidDocblock,
     InvalidEnumCaseValue,
+    InvalidEventListener,
     InvalidExtend,
     InvalidForeachKey,
     InvalidForeachValue,
@@ -133,6 +135,7 @@ pub enum IssueCode {
     InvalidIterator,
     InvalidMemberSelector,
     InvalidMethodAccess,
+    InvalidModelCast,
     InvalidNamedArgument,
     InvalidOperand,
     InvalidOverrideAttribute,
@@ -213,9 +216,14 @@ pub enum IssueCode {
     NonExistentClassConstant,
     NonExistentClassLike,
     NonExistentConstant,
+    NonExistentEntity,
+    NonExistentEntityField,
     NonExistentFunction,
     NonExistentMethod,
+    NonExistentMockedMethod,
     NonExistentProperty,
+    NonExistentTemplate,
+    NonExistentTemplateAttribute,
     NonExistentUseImport,
     NonIterableObjectIteration,
     NonStaticAbstractImplementation,
@@ -285,6 +293,11 @@ pub enum IssueCode {
     StaticOutsideClassScope,
     StringConstantSelector,
     StringMemberSelector,
+    TaintedHtml,
+    TaintedInclude,
+    TaintedShell,
+    TaintedSql,
+    TaintedUnserialize,
     TemplateConstraintViolation,
     TooFewArguments,
     TooManyArguments,
@@ -301,6 +314,7 @@ pub enum IssueCode {
     UnavailableProperty,
     UndefinedIntArrayIndex,
     UndefinedStringArrayIndex,
+    UndefinedTemplateVariable,
     UndefinedVariable,
     UndefinedVariableInClosureUse,
     UnevaluatedCode,
@@ -314,9 +328,13 @@ pub enum IssueCode {
     UnknownMatchSubjectType,
     UnknownMemberSelectorType,
     UnknownYieldFromIteratorType,
+    UnreachableClass,
+    UnreachableConstant,
     UnreachableElseClause,
     UnreachableMatchArm,
     UnreachableMatchDefaultArm,
+    UnreachableMethod,
+    UnreachableProperty,
     UnreachableSwitchCase,
     UnreachableSwitchDefault,
     UnresolvableClassConstant,
@@ -452,9 +470,11 @@ impl IssueCode {
             Self::InvalidConstantSelector => "invalid-constant-selector",
             Self::InvalidConstantValue => "invalid-constant-value",
             Self::InvalidContinue => "invalid-continue",
+            Self::InvalidDataProvider => "invalid-data-provider",
             Self::InvalidDestructuringSource => "invalid-destructuring-source",
             Self::InvalidDocblock => "invalid-docblock",
             Self::InvalidEnumCaseValue => "invalid-enum-case-value",
+            Self::InvalidEventListener => "invalid-event-listener",
             Self::InvalidExtend => "invalid-extend",
             Self::InvalidForeachKey => "invalid-foreach-key",
             Self::InvalidForeachValue => "invalid-foreach-value",
@@ -465,6 +485,7 @@ impl IssueCode {
             Self::InvalidIterator => "invalid-iterator",
             Self::InvalidMemberSelector => "invalid-member-selector",
             Self::InvalidMethodAccess => "invalid-method-access",
+            Self::InvalidModelCast => "invalid-model-cast",
             Self::InvalidNamedArgument => "invalid-named-argument",
             Self::InvalidOperand => "invalid-operand",
             Self::InvalidOverrideAttribute => "invalid-override-attribute",
@@ -545,9 +566,14 @@ impl IssueCode {
             Self::NonExistentClassConstant => "non-existent-class-constant",
             Self::NonExistentClassLike => "non-existent-class-like",
             Self::NonExistentConstant => "non-existent-constant",
+            Self::NonExistentEntity => "non-existent-entity",
+            Self::NonExistentEntityField => "non-existent-entity-field",
             Self::NonExistentFunction => "non-existent-function",
             Self::NonExistentMethod => "non-existent-method",
+            Self::NonExistentMockedMethod => "non-existent-mocked-method",
             Self::NonExistentProperty => "non-existent-property",
+            Self::NonExistentTemplate => "non-existent-template",
+            Self::NonExistentTemplateAttribute => "non-existent-template-attribute",
             Self::NonExistentUseImport => "non-existent-use-import",
             Self::NonIterableObjectIteration => "non-iterable-object-iteration",
             Self::NonStaticAbstractImplementation => "non-static-abstract-implementation",
@@ -617,6 +643,11 @@ impl IssueCode {
             Self::StaticOutsideClassScope => "static-outside-class-scope",
             Self::StringConstantSelector => "string-constant-selector",
             Self::StringMemberSelector => "string-member-selector",
+            Self::TaintedHtml => "tainted-html",
+            Self::TaintedInclude => "tainted-include",
+            Self::TaintedShell => "tainted-shell",
+            Self::TaintedSql => "tainted-sql",
+            Self::TaintedUnserialize => "tainted-unserialize",
             Self::TemplateConstraintViolation => "template-constraint-violation",
             Self::TooFewArguments => "too-few-arguments",
             Self::TooManyArguments => "too-many-arguments",
@@ -633,6 +664,7 @@ impl IssueCode {
             Self::UnavailableProperty => "unavailable-property",
             Self::UndefinedIntArrayIndex => "undefined-int-array-index",
             Self::UndefinedStringArrayIndex => "undefined-string-array-index",
+            Self::UndefinedTemplateVariable => "undefined-template-variable",
             Self::UndefinedVariable => "undefined-variable",
             Self::UndefinedVariableInClosureUse => "undefined-variable-in-closure-use",
             Self::UnevaluatedCode => "unevaluated-code",
@@ -646,9 +678,13 @@ impl IssueCode {
             Self::UnknownMatchSubjectType => "unknown-match-subject-type",
             Self::UnknownMemberSelectorType => "unknown-member-selector-type",
             Self::UnknownYieldFromIteratorType => "unknown-yield-from-iterator-type",
+            Self::UnreachableClass => "unreachable-class",
+            Self::UnreachableConstant => "unreachable-constant",
             Self::UnreachableElseClause => "unreachable-else-clause",
             Self::UnreachableMatchArm => "unreachable-match-arm",
             Self::UnreachableMatchDefaultArm => "unreachable-match-default-arm",
+            Self::UnreachableMethod => "unreachable-method",
+            Self::UnreachableProperty => "unreachable-property",
             Self::UnreachableSwitchCase => "unreachable-switch-case",
             Self::UnreachableSwitchDefault => "unreachable-switch-default",
             Self::UnresolvableClassConstant => "unresolvable-class-constant",
@@ -789,9 +825,11 @@ impl IssueCode {
             Self::InvalidConstantSelector,
             Self::InvalidConstantValue,
             Self::InvalidContinue,
+            Self::InvalidDataProvider,
             Self::InvalidDestructuringSource,
             Self::InvalidDocblock,
             Self::InvalidEnumCaseValue,
+            Self::InvalidEventListener,
             Self::InvalidExtend,
             Self::InvalidForeachKey,
             Self::InvalidForeachValue,
@@ -802,6 +840,7 @@ impl IssueCode {
             Self::InvalidIterator,
             Self::InvalidMemberSelector,
             Self::InvalidMethodAccess,
+            Self::InvalidModelCast,
             Self::InvalidNamedArgument,
             Self::InvalidOperand,
             Self::InvalidOverrideAttribute,
@@ -882,9 +921,14 @@ impl IssueCode {
             Self::NonExistentClassConstant,
             Self::NonExistentClassLike,
             Self::NonExistentConstant,
+            Self::NonExistentEntity,
+            Self::NonExistentEntityField,
             Self::NonExistentFunction,
             Self::NonExistentMethod,
+            Self::NonExistentMockedMethod,
             Self::NonExistentProperty,
+            Self::NonExistentTemplate,
+            Self::NonExistentTemplateAttribute,
             Self::NonExistentUseImport,
             Self::NonIterableObjectIteration,
             Self::NonStaticAbstractImplementation,
@@ -954,6 +998,11 @@ impl IssueCode {
             Self::StaticOutsideClassScope,
             Self::StringConstantSelector,
             Self::StringMemberSelector,
+            Self::TaintedHtml,
+            Self::TaintedInclude,
+            Self::TaintedShell,
+            Self::TaintedSql,
+            Self::TaintedUnserialize,
             Self::TemplateConstraintViolation,
             Self::TooFewArguments,
             Self::TooManyArguments,
@@ -970,6 +1019,7 @@ impl IssueCode {
             Self::UnavailableProperty,
             Self::UndefinedIntArrayIndex,
             Self::UndefinedStringArrayIndex,
+            Self::UndefinedTemplateVariable,
             Self::UndefinedVariable,
             Self::UndefinedVariableInClosureUse,
             Self::UnevaluatedCode,
@@ -983,9 +1033,13 @@ impl IssueCode {
             Self::UnknownMatchSubjectType,
             Self::UnknownMemberSelectorType,
             Self::UnknownYieldFromIteratorType,
+            Self::UnreachableClass,
+            Self::UnreachableConstant,
             Self::UnreachableElseClause,
             Self::UnreachableMatchArm,
             Self::UnreachableMatchDefaultArm,
+            Self::UnreachableMethod,
+            Self::UnreachableProperty,
             Self::UnreachableSwitchCase,
             Self::UnreachableSwitchDefault,
             Self::UnresolvableClassConstant,
@@ -1124,9 +1178,11 @@ impl std::str::FromStr for IssueCode {
             "invalid-constant-selector" => Ok(Self::InvalidConstantSelector),
             "invalid-constant-value" => Ok(Self::InvalidConstantValue),
             "invalid-continue" => Ok(Self::InvalidContinue),
+            "invalid-data-provider" => Ok(Self::InvalidDataProvider),
             "invalid-destructuring-source" => Ok(Self::InvalidDestructuringSource),
             "invalid-docblock" => Ok(Self::InvalidDocblock),
             "invalid-enum-case-value" => Ok(Self::InvalidEnumCaseValue),
+            "invalid-event-listener" => Ok(Self::InvalidEventListener),
             "invalid-extend" => Ok(Self::InvalidExtend),
             "invalid-foreach-key" => Ok(Self::InvalidForeachKey),
             "invalid-foreach-value" => Ok(Self::InvalidForeachValue),
@@ -1137,6 +1193,7 @@ impl std::str::FromStr for IssueCode {
             "invalid-iterator" => Ok(Self::InvalidIterator),
             "invalid-member-selector" => Ok(Self::InvalidMemberSelector),
             "invalid-method-access" => Ok(Self::InvalidMethodAccess),
+            "invalid-model-cast" => Ok(Self::InvalidModelCast),
             "invalid-named-argument" => Ok(Self::InvalidNamedArgument),
             "invalid-operand" => Ok(Self::InvalidOperand),
             "invalid-override-attribute" => Ok(Self::InvalidOverrideAttribute),
@@ -1217,9 +1274,14 @@ impl std::str::FromStr for IssueCode {
             "non-existent-class-constant" => Ok(Self::NonExistentClassConstant),
             "non-existent-class-like" => Ok(Self::NonExistentClassLike),
             "non-existent-constant" => Ok(Self::NonExistentConstant),
+            "non-existent-entity" => Ok(Self::NonExistentEntity),
+            "non-existent-entity-field" => Ok(Self::NonExistentEntityField),
             "non-existent-function" => Ok(Self::NonExistentFunction),
             "non-existent-method" => Ok(Self::NonExistentMethod),
+            "non-existent-mocked-method" => Ok(Self::NonExistentMockedMethod),
             "non-existent-property" => Ok(Self::NonExistentProperty),
+            "non-existent-template" => Ok(Self::NonExistentTemplate),
+            "non-existent-template-attribute" => Ok(Self::NonExistentTemplateAttribute),
             "non-existent-use-import" => Ok(Self::NonExistentUseImport),
             "non-iterable-object-iteration" => Ok(Self::NonIterableObjectIteration),
             "non-static-abstract-implementation" => Ok(Self::NonStaticAbstractImplementation),
@@ -1289,6 +1351,11 @@ impl std::str::FromStr for IssueCode {
             "static-outside-class-scope" => Ok(Self::StaticOutsideClassScope),
             "string-constant-selector" => Ok(Self::StringConstantSelector),
             "string-member-selector" => Ok(Self::StringMemberSelector),
+            "tainted-html" => Ok(Self::TaintedHtml),
+            "tainted-include" => Ok(Self::TaintedInclude),
+            "tainted-shell" => Ok(Self::TaintedShell),
+            "tainted-sql" => Ok(Self::TaintedSql),
+            "tainted-unserialize" => Ok(Self::TaintedUnserialize),
             "template-constraint-violation" => Ok(Self::TemplateConstraintViolation),
             "too-few-arguments" => Ok(Self::TooFewArguments),
             "too-many-arguments" => Ok(Self::TooManyArguments),
@@ -1305,6 +1372,7 @@ impl std::str::FromStr for IssueCode {
             "unavailable-property" => Ok(Self::UnavailableProperty),
             "undefined-int-array-index" => Ok(Self::UndefinedIntArrayIndex),
             "undefined-string-array-index" => Ok(Self::UndefinedStringArrayIndex),
+            "undefined-template-variable" => Ok(Self::UndefinedTemplateVariable),
             "undefined-variable" => Ok(Self::UndefinedVariable),
             "undefined-variable-in-closure-use" => Ok(Self::UndefinedVariableInClosureUse),
             "unevaluated-code" => Ok(Self::UnevaluatedCode),
@@ -1318,9 +1386,13 @@ impl std::str::FromStr for IssueCode {
             "unknown-match-subject-type" => Ok(Self::UnknownMatchSubjectType),
             "unknown-member-selector-type" => Ok(Self::UnknownMemberSelectorType),
             "unknown-yield-from-iterator-type" => Ok(Self::UnknownYieldFromIteratorType),
+            "unreachable-class" => Ok(Self::UnreachableClass),
+            "unreachable-constant" => Ok(Self::UnreachableConstant),
             "unreachable-else-clause" => Ok(Self::UnreachableElseClause),
             "unreachable-match-arm" => Ok(Self::UnreachableMatchArm),
             "unreachable-match-default-arm" => Ok(Self::UnreachableMatchDefaultArm),
+            "unreachable-method" => Ok(Self::UnreachableMethod),
+            "unreachable-property" => Ok(Self::UnreachableProperty),
             "unreachable-switch-case" => Ok(Self::UnreachableSwitchCase),
             "unreachable-switch-default" => Ok(Self::UnreachableSwitchDefault),
             "unresolvable-class-constant" => Ok(Self::UnresolvableClassConstant),
@@ -0,0 +1,241 @@
+//! Summaries of a Symfony project's compiled service container.
+//!
+//! Symfony dumps the compiled container as XML in debug mode (for example to
+//! `var/cache/dev/App_KernelDevDebugContainer.xml`). Plugins use the [`ServiceContainer`] read
+//! from that dump to type the services and parameters fetched from the container by name.
+//!
+//! The dump is read from the
+//! [`Settings::container_xml_path`](crate::settings::Settings::container_xml_path) file.
+
+use foldhash::HashMap;
+
+use crate::container::reader::XmlEvent;
+use crate::container::reader::XmlReader;
+
+mod reader;
+
+/// How many aliases are followed before giving up on resolving a service.
+const MAX_ALIAS_DEPTH: usize = 16;
+
+/// The services and parameters of a compiled container.
+#[derive(Debug, Clone, Default)]
+pub struct ServiceContainer {
+    services: HashMap<String, Service>,
+    parameters: HashMap<String, Parameter>,
+}
+
+/// A service definition, or an alias to another service.
+#[derive(Debug, Clone, Default)]
+struct Service {
+    class: Option<String>,
+    alias: Option<String>,
+}
+
+/// The kind of value a container parameter holds.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Parameter {
+    Null,
+    Bool,
+    Int,
+    Float,
+    String,
+    /// An array, dumped as a `collection`.
+    Collection,
+    /// A value only known at runtime, like an environment variable placeholder or a constant.
+    Unknown,
+}
+
+impl ServiceContainer {
+    #[must_use]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Reads the services and parameters of a container dumped by Symfony's `XmlDumper`.
+    ///
+    /// The dump is read leniently: elements that are not understood are skipped, and a
+    /// malformed document yields whatever was read before the error.
+    #[must_use]
+    pub fn from_xml(xml: &str) -> Self {
+        let mut container = Self::new();
+        let mut reader = XmlReader::new(xml);
+        let mut path: Vec<String> = Vec::new();
+        let mut pending_parameter: Option<(String, Option<String>, String)> = None;
+
+        while let Some(event) = reader.next_event() {
+            match event {
+                XmlEvent::Start { name, attributes, is_empty } => {
+                    let parent = path.last().map(String::as_str);
+                    let attribute =
+                        |key: &str| attributes.iter().find(|(name, _)| *name == key).map(|(_, value)| value.clone());
+
+                    match (name, parent) {
+                        ("service", Some("services")) => {
+                            if let Some(id) = attribute("id") {
+                                container
+                                    .services
+                                    .insert(id, Service { class: attribute("class"), alias: attribute("alias") });
+                            }
+                        }
+                        ("parameter", Some("parameters")) => {
+                            if let Some(key) = attribute("key") {
+                                let kind = attribute("type");
+                                if is_empty {
+                                    let parameter = parameter_kind(kind.as_deref(), "");
+                                    container.parameters.insert(key, parameter);
+                                } else {
+                                    pending_parameter = Some((key, kind, String::new()));
+                                }
+                            }
+                        }
+                        _ => {}
+                    }
+
+                    if !is_empty {
+                        path.push(name.to_string());
+                    }
+                }
+                XmlEvent::Text(text) => {
+                    if let Some((_, _, value)) = pending_parameter.as_mut()
+                        && path.len() >= 2
+                        && path[path.len() - 2] == "parameters"
+                    {
+                        value.push_str(&text);
+                    }
+                }
+                XmlEvent::End { name } => {
+                    path.pop();
+
+                    if name == "parameter"
+                        && path.last().is_some_and(|parent| parent == "parameters")
+                        && let Some((key, kind, value)) = pending_parameter.take()
+                    {
+                        container.parameters.insert(key, parameter_kind(kind.as_deref(), &value));
+                    }
+                }
+            }
+        }
+
+        container
+    }
+
+    /// The class of the service with the given id, following aliases.
+    #[must_use]
+    pub fn service_class(&self, id: &str) -> Option<&str> {
+        let mut service = self.services.get(id)?;
+        for _ in 0..MAX_ALIAS_DEPTH {
+            match &service.alias {
+                Some(alias) => service = self.services.get(alias.as_str())?,
+                None => return service.class.as_deref(),
+            }
+        }
+
+        None
+    }
+
+    /// The kind of value of the parameter with the given name.
+    #[must_use]
+    pub fn parameter(&self, name: &str) -> Option<Parameter> {
+        self.parameters.get(name).copied()
+    }
+
+    #[must_use]
+    pub fn len(&self) -> usize {
+        self.services.len() + self.parameters.len()
+    }
+
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.services.is_empty() && self.parameters.is_empty()
+    }
+}
+
+/// Classifies a dumped parameter value the way Symfony's `XmlUtils::phpize()` reads it back.
+fn parameter_kind(kind: Option<&str>, value: &str) -> Parameter {
+    match kind {
+        Some("collection") => Parameter::Collection,
+        Some("string" | "binary") => Parameter::String,
+        Some(_) => Parameter::Unknown,
+        None if value.contains("%env(") => Parameter::Unknown,
+        None if value.eq_ignore_ascii_case("null") => Parameter::Null,
+        None if value.eq_ignore_ascii_case("true") || value.eq_ignore_ascii_case("false") => Parameter::Bool,
+        None if is_integer(value) => Parameter::Int,
+        None if value.parse::<f64>().is_ok_and(f64::is_finite) => Parameter::Float,
+        None => Parameter::String,
+    }
+}
+
+fn is_integer(value: &str) -> bool {
+    let digits = value.strip_prefix('-').unwrap_or(value);
+    if let Some(hexadecimal) = digits.strip_prefix("0x") {
+        return !hexadecimal.is_empty() && hexadecimal.bytes().all(|byte| byte.is_ascii_hexdigit());
+    }
+
+    !digits.is_empty() && digits.bytes().all(|byte| byte.is_ascii_digit())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const CONTAINER: &str = r#"<?xml version="1.0" encoding="utf-8"?>
+<container xmlns="http://symfony.com/schema/dic/services">
+  <parameters>
+    <parameter key="kernel.debug">true</parameter>
+    <parameter key="kernel.project_dir">/srv/app</parameter>
+    <parameter key="kernel.bundles" type="collection">
+      <parameter key="FrameworkBundle">Symfony\Bundle\FrameworkBundle\FrameworkBundle</parameter>
+    </parameter>
+    <parameter key="app.page_size">25</parameter>
+    <parameter key="app.ratio">0.5</parameter>
+    <parameter key="app.version" type="string">2</parameter>
+    <parameter key="app.secret">%env(APP_SECRET)%</parameter>
+    <parameter key="app.empty" type="collection"/>
+    <parameter key="app.nothing">null</parameter>
+    <parameter key="app.title">Tom &amp; Jerry</parameter>
+  </parameters>
+  <services>
+    <!-- <service id="commented.out" class="App\Commented"/> -->
+    <service id="service_container" class="Symfony\Component\DependencyInjection\ContainerInterface" public="true" synthetic="true"/>
+    <service id="App\Mailer" class="App\Mailer" autowire="true" public="true">
+      <argument type="service" id="mailer.transport"/>
+    </service>
+    <service id="app.mailer" alias="App\Mailer" public="true"/>
+    <service id="mailer" alias="app.mailer" public="true"/>
+    <service id="loop.a" alias="loop.b"/>
+    <service id="loop.b" alias="loop.a"/>
+  </services>
+</container>
+"#;
+
+    #[test]
+    fn test_services() {
+        let container = ServiceContainer::from_xml(CONTAINER);
+
+        assert_eq!(container.service_class("App\\Mailer"), Some("App\\Mailer"));
+        assert_eq!(container.service_class("mailer"), Some("App\\Mailer"));
+        assert_eq!(
+            container.service_class("service_container"),
+            Some("Symfony\\Component\\DependencyInjection\\ContainerInterface")
+        );
+        assert_eq!(container.service_class("commented.out"), None);
+        assert_eq!(container.service_class("loop.a"), None);
+    }
+
+    #[test]
+    fn test_parameters() {
+        let container = ServiceContainer::from_xml(CONTAINER);
+
+        assert_eq!(container.parameter("kernel.debug"), Some(Parameter::Bool));
+        assert_eq!(container.parameter("kernel.project_dir"), Some(Parameter::String));
+        assert_eq!(container.parameter("kernel.bundles"), Some(Parameter::Collection));
+        assert_eq!(container.parameter("FrameworkBundle"), None);
+        assert_eq!(container.parameter("app.page_size"), Some(Parameter::Int));
+        assert_eq!(container.parameter("app.ratio"), Some(Parameter::Float));
+        assert_eq!(container.parameter("app.version"), Some(Parameter::String));
+        assert_eq!(container.parameter("app.secret"), Some(Parameter::Unknown));
+        assert_eq!(container.parameter("app.empty"), Some(Parameter::Collection));
+        assert_eq!(container.parameter("app.nothing"), Some(Parameter::Null));
+        assert_eq!(container.parameter("app.title"), Some(Parameter::String));
+    }
+}
@@ -0,0 +1,152 @@
+//! A minimal XML reader for container dumps.
+//!
+//! Only the subset of XML that Symfony's `XmlDumper` produces is supported: elements,
+//! attributes, text, the predefined and numeric character references, comments, CDATA
+//! sections, and processing instructions (which are skipped). Namespaces are not resolved.
+
+pub(super) enum XmlEvent<'xml> {
+    Start { name: &'xml str, attributes: Vec<(&'xml str, String)>, is_empty: bool },
+    End { name: &'xml str },
+    Text(String),
+}
+
+pub(super) struct XmlReader<'xml> {
+    xml: &'xml str,
+    position: usize,
+}
+
+impl<'xml> XmlReader<'xml> {
+    pub(super) fn new(xml: &'xml str) -> Self {
+        Self { xml, position: 0 }
+    }
+
+    /// Reads the next event, or `None` at the end of the document or on malformed input.
+    pub(super) fn next_event(&mut self) -> Option<XmlEvent<'xml>> {
+        loop {
+            let rest = &self.xml[self.position..];
+            if rest.is_empty() {
+                return None;
+            }
+
+            if !rest.starts_with('<') {
+                let length = rest.find('<').unwrap_or(rest.len());
+                self.position += length;
+
+                return Some(XmlEvent::Text(unescape(&rest[..length])));
+            }
+
+            if rest.starts_with("<!--") {
+                self.skip_past("-->")?;
+            } else if let Some(data) = rest.strip_prefix("<![CDATA[") {
+                let length = data.find("]]>")?;
+                self.position += "<![CDATA[".len() + length + "]]>".len();
+
+                return Some(XmlEvent::Text(data[..length].to_string()));
+            } else if rest.starts_with("<?") || rest.starts_with("<!") {
+                self.skip_past(">")?;
+            } else if let Some(tag) = rest.strip_prefix("</") {
+                let length = tag.find('>')?;
+                self.position += "</".len() + length + 1;
+
+                return Some(XmlEvent::End { name: tag[..length].trim() });
+            } else {
+                return self.read_start_tag();
+            }
+        }
+    }
+
+    fn read_start_tag(&mut self) -> Option<XmlEvent<'xml>> {
+        let xml = self.xml;
+        let tag_start = self.position + 1;
+        let tag_length = find_tag_end(&xml[tag_start..])?;
+        let mut tag = &xml[tag_start..tag_start + tag_length];
+        self.position = tag_start + tag_length + 1;
+
+        let is_empty = tag.ends_with('/');
+        if is_empty {
+            tag = &tag[..tag.len() - 1];
+        }
+
+        let name_length = tag.find(|character: char| character.is_ascii_whitespace()).unwrap_or(tag.len());
+        let name = &tag[..name_length];
+        let mut rest = tag[name_length..].trim_start();
+        let mut attributes = Vec::new();
+
+        while let Some(equals) = rest.find('=') {
+            let attribute = rest[..equals].trim();
+            let value = rest[equals + 1..].trim_start();
+            let quote = value.chars().next().filter(|quote| *quote == '"' || *quote == '\'')?;
+            let value_length = value[1..].find(quote)?;
+
+            attributes.push((attribute, unescape(&value[1..=value_length])));
+            rest = value[value_length + 2..].trim_start();
+        }
+
+        Some(XmlEvent::Start { name, attributes, is_empty })
+    }
+
+    fn skip_past(&mut self, terminator: &str) -> Option<()> {
+        let length = self.xml[self.position..].find(terminator)?;
+        self.position += length + terminator.len();
+
+        Some(())
+    }
+}
+
+/// Finds the `>` closing a start tag, ignoring any inside quoted attribute values.
+fn find_tag_end(tag: &str) -> Option<usize> {
+    let mut quote = None;
+    for (index, character) in tag.char_indices() {
+        match (quote, character) {
+            (None, '"' | '\'') => quote = Some(character),
+            (Some(open), _) if open == character => quote = None,
+            (None, '>') => return Some(index),
+            _ => {}
+        }
+    }
+
+    None
+}
+
+fn unescape(text: &str) -> String {
+    let mut result = String::with_capacity(text.len());
+    let mut rest = text;
+
+    while let Some(ampersand) = rest.find('&') {
+        result.push_str(&rest[..ampersand]);
+        rest = &rest[ampersand..];
+
+        let Some(semicolon) = rest.find(';') else {
+            break;
+        };
+
+        let entity = &rest[1..semicolon];
+        let character = match entity {
+            "amp" => Some('&'),
+            "lt" => Some('<'),
+            "gt" => Some('>'),
+            "quot" => Some('"'),
+            "apos" => Some('\''),
+            _ => entity
+                .strip_prefix("#x")
+                .map(|hexadecimal| u32::from_str_radix(hexadecimal, 16))
+                .or_else(|| entity.strip_prefix('#').map(str::parse))
+                .and_then(Result::ok)
+                .and_then(char::from_u32),
+        };
+
+        match character {
+            Some(character) => {
+                result.push(character);
+                rest = &rest[semicolon + 1..];
+            }
+            None => {
+                result.push('&');
+                rest = &rest[1..];
+            }
+        }
+    }
+
+    result.push_str(rest);
+    result
+}
@@ -25,11 +25,15 @@ use mago_syntax::cst::Trivia;
 use crate::analysis_result::AnalysisResult;
 use crate::artifacts::AnalysisArtifacts;
 use crate::code::IssueCode;
+use crate::container::ServiceContainer;
 use crate::context::assertion::AssertionContext;
 use crate::context::block::BlockContext;
 use crate::external::ExternalAnalysisSession;
+use crate::form::FormIndex;
 use crate::plugin::PluginRegistry;
+use crate::schema::DatabaseSchema;
 use crate::settings::Settings;
+use crate::template::TemplateIndex;
 
 pub mod assertion;
 pub mod block;
@@ -57,6 +61,10 @@ where
     pub(super) plugin_registry: &'ctx PluginRegistry,
     pub(super) external_analysis_session: Option<&'ctx ExternalAnalysisSession>,
     pub(super) additional_symbol_references: Option<&'ctx SymbolReferences>,
+    pub(super) templates: Option<&'ctx TemplateIndex>,
+    pub(super) schema: Option<&'ctx DatabaseSchema>,
+    pub(super) container: Option<&'ctx ServiceContainer>,
+    pub(super) forms: Option<&'ctx FormIndex>,
     class_initializers: WordMap<WordSet>,
 }
 
@@ -76,6 +84,10 @@ where
         plugin_registry: &'ctx PluginRegistry,
         external_analysis_session: Option<&'ctx ExternalAnalysisSession>,
         additional_symbol_references: Option<&'ctx SymbolReferences>,
+        templates: Option<&'ctx TemplateIndex>,
+        schema: Option<&'ctx DatabaseSchema>,
+        container: Option<&'ctx ServiceContainer>,
+        forms: Option<&'ctx FormIndex>,
     ) -> Self {
         Self {
             arena,
@@ -91,6 +103,10 @@ where
             plugin_registry,
             external_analysis_session,
             additional_symbol_references,
+            templates,
+            schema,
+            container,
+            forms,
             class_initializers: WordMap::default(),
         }
     }
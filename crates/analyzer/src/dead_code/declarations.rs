@@ -0,0 +1,106 @@
+use foldhash::HashMap;
+
+use mago_span::HasSpan;
+use mago_span::Span;
+use mago_syntax::cst::ClassLikeConstant;
+use mago_syntax::cst::HookedProperty;
+use mago_syntax::cst::PlainProperty;
+use mago_syntax::walker::Walker;
+
+/// Maps the span recorded in the metadata of a class constant or property to the span of
+/// the declaration holding it.
+///
+/// Declarations holding several items (`const A = 1, B = 2;`) map to `None`, as removing
+/// one of their items is not a matter of deleting the declaration.
+pub(super) type MemberDeclarations = HashMap<Span, Option<Span>>;
+
+#[derive(Debug, Clone, Copy)]
+pub(super) struct MemberDeclarationWalker;
+
+impl<'ast, 'arena> Walker<'ast, 'arena, MemberDeclarations> for MemberDeclarationWalker {
+    fn walk_in_class_like_constant(
+        &self,
+        class_like_constant: &'ast ClassLikeConstant<'arena>,
+        declarations: &mut MemberDeclarations,
+    ) {
+        let declaration = (class_like_constant.items.len() == 1).then(|| class_like_constant.span());
+        for item in &class_like_constant.items {
+            declarations.insert(item.span(), declaration);
+        }
+    }
+
+    fn walk_in_plain_property(
+        &self,
+        plain_property: &'ast PlainProperty<'arena>,
+        declarations: &mut MemberDeclarations,
+    ) {
+        let declaration = (plain_property.items.len() == 1).then(|| plain_property.span());
+        for item in &plain_property.items {
+            declarations.insert(item.variable().span, declaration);
+        }
+    }
+
+    fn walk_in_hooked_property(
+        &self,
+        hooked_property: &'ast HookedProperty<'arena>,
+        declarations: &mut MemberDeclarations,
+    ) {
+        declarations.insert(hooked_property.item.variable().span, Some(hooked_property.span()));
+    }
+}
+
+/// Widens the span of a declaration to the lines it occupies, including its docblock and
+/// one of the blank lines around it, so deleting it leaves no stray comment, indentation, or
+/// doubled blank line behind.
+pub(super) fn deletion_range(contents: &[u8], span: Span) -> (u32, u32) {
+    let mut start = span.start.offset as usize;
+    let mut end = (span.end.offset as usize).min(contents.len());
+
+    let before = trim_trailing_whitespace(&contents[..start]);
+    if before.ends_with(b"*/")
+        && let Some(comment_start) = memchr::memmem::rfind(&before[..before.len() - 2], b"/*")
+        && contents[comment_start..].starts_with(b"/**")
+    {
+        start = comment_start;
+    }
+
+    let first_line_start = line_start(contents, start);
+    if !is_blank(&contents[first_line_start..start]) {
+        return (start as u32, end as u32);
+    }
+
+    let last_line_end = line_end(contents, end);
+    if !is_blank(&contents[end..last_line_end]) {
+        return (first_line_start as u32, end as u32);
+    }
+
+    start = first_line_start;
+    end = last_line_end;
+    if start > 0 && is_blank(&contents[line_start(contents, start - 1)..start]) {
+        start = line_start(contents, start - 1);
+    } else if end < contents.len() && is_blank(&contents[end..line_end(contents, end)]) {
+        end = line_end(contents, end);
+    }
+
+    (start as u32, end as u32)
+}
+
+/// The offset at which the line holding `offset` starts.
+fn line_start(contents: &[u8], offset: usize) -> usize {
+    contents[..offset].iter().rposition(|byte| *byte == b'\n').map_or(0, |position| position + 1)
+}
+
+/// The offset just past the newline ending the line holding `offset`.
+fn line_end(contents: &[u8], offset: usize) -> usize {
+    contents[offset..].iter().position(|byte| *byte == b'\n').map_or(contents.len(), |position| offset + position + 1)
+}
+
+fn is_blank(bytes: &[u8]) -> bool {
+    bytes.iter().all(u8::is_ascii_whitespace)
+}
+
+fn trim_trailing_whitespace(bytes: &[u8]) -> &[u8] {
+    let end = bytes.iter().rposition(|byte| !byte.is_ascii_whitespace()).map_or(0, |position| position + 1);
+
+    &bytes[..end]
+}
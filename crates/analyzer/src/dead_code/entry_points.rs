@@ -0,0 +1,128 @@
+/// Classes whose public methods are invoked by a framework rather than by the code itself.
+pub const DEFAULT_ENTRY_POINT_CLASSES: &[&str] = &[
+    "Symfony\\Bundle\\FrameworkBundle\\Controller\\AbstractController",
+    "Symfony\\Component\\Console\\Command\\Command",
+    "Symfony\\Component\\EventDispatcher\\EventSubscriberInterface",
+    "Illuminate\\Routing\\Controller",
+    "Illuminate\\Console\\Command",
+    "PHPUnit\\Framework\\TestCase",
+];
+
+/// Attributes marking a class or method as invoked by a framework.
+pub const DEFAULT_ENTRY_POINT_ATTRIBUTES: &[&str] = &[
+    "Symfony\\Component\\Routing\\Attribute\\Route",
+    "Symfony\\Component\\Routing\\Annotation\\Route",
+    "Symfony\\Component\\HttpKernel\\Attribute\\AsController",
+    "Symfony\\Component\\Console\\Attribute\\AsCommand",
+    "Symfony\\Component\\EventDispatcher\\Attribute\\AsEventListener",
+    "Symfony\\Component\\Messenger\\Attribute\\AsMessageHandler",
+];
+
+/// The symbols that are reached from outside the analyzed code.
+///
+/// Frameworks call controllers, console commands, and event subscribers without the code
+/// ever referencing them, so the dead code report starts its walk from these symbols in
+/// addition to top-level code.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct EntryPoints {
+    /// Classes whose public methods are entry points.
+    ///
+    /// A name matches the class itself and every class extending or implementing it, while
+    /// a pattern ending in `*` matches class names by prefix, e.g. `App\Controller\*`.
+    pub classes: Vec<String>,
+
+    /// Attributes marking the class or method they are applied to as an entry point.
+    pub attributes: Vec<String>,
+
+    /// Individual `Class` or `Class::member` names that are entry points, such as the
+    /// services and controllers referenced from configuration files.
+    pub symbols: Vec<String>,
+}
+
+impl Default for EntryPoints {
+    fn default() -> Self {
+        Self {
+            classes: DEFAULT_ENTRY_POINT_CLASSES.iter().map(|class| (*class).to_string()).collect(),
+            attributes: DEFAULT_ENTRY_POINT_ATTRIBUTES.iter().map(|attribute| (*attribute).to_string()).collect(),
+            symbols: Vec::new(),
+        }
+    }
+}
+
+/// Extracts the namespaced class names, and `Class::member` references, mentioned in the
+/// contents of a configuration file.
+///
+/// The file is not parsed: any run of name characters containing a namespace separator is
+/// taken as a class name, which covers service definitions, routes, and event listeners in
+/// YAML, XML, and PHP configuration. Escaped separators (`App\\Service`) are unescaped.
+#[must_use]
+pub fn extract_symbol_names(contents: &[u8]) -> Vec<String> {
+    let mut names = Vec::new();
+    let mut offset = 0;
+    while offset < contents.len() {
+        if !is_name_byte(contents[offset]) {
+            offset += 1;
+            continue;
+        }
+
+        let start = offset;
+        while offset < contents.len() && is_name_byte(contents[offset]) {
+            offset += 1;
+        }
+
+        let Some(mut name) = normalize_class_name(&contents[start..offset]) else {
+            continue;
+        };
+
+        if contents[offset..].starts_with(b"::") {
+            let member_start = offset + 2;
+            let mut member_end = member_start;
+            while member_end < contents.len() && is_identifier_byte(contents[member_end]) {
+                member_end += 1;
+            }
+
+            if member_end > member_start && !contents[member_start].is_ascii_digit() {
+                name.push_str("::");
+                name.push_str(&String::from_utf8_lossy(&contents[member_start..member_end]));
+                offset = member_end;
+            }
+        }
+
+        names.push(name);
+    }
+
+    names.sort_unstable();
+    names.dedup();
+    names
+}
+
+fn normalize_class_name(raw: &[u8]) -> Option<String> {
+    let mut segments = Vec::new();
+    for segment in raw.split(|byte| *byte == b'\\') {
+        if segment.is_empty() {
+            continue;
+        }
+
+        if segment[0].is_ascii_digit() || !segment.iter().copied().all(is_identifier_byte) {
+            return None;
+        }
+
+        segments.push(String::from_utf8_lossy(segment));
+    }
+
+    if segments.len() < 2 {
+        return None;
+    }
+
+    Some(segments.join("\\"))
+}
+
+#[inline]
+const fn is_identifier_byte(byte: u8) -> bool {
+    byte.is_ascii_alphanumeric() || byte == b'_' || byte >= 0x80
+}
+
+#[inline]
+const fn is_name_byte(byte: u8) -> bool {
+    is_identifier_byte(byte) || byte == b'\\'
+}
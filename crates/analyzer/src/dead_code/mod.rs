@@ -0,0 +1,557 @@
+//! Whole-program dead code detection.
+//!
+//! When [`Settings::find_unreachable_symbols`](crate::settings::Settings::find_unreachable_symbols)
+//! is enabled, [`find_unreachable_symbols`] walks the [`SymbolReferences`] recorded across the
+//! whole program, starting from top-level code, functions, `@api` symbols, code outside the
+//! analyzed sources, and the configured [`EntryPoints`], and reports every user-defined class,
+//! method, class constant, and property that is never reached.
+
+use std::borrow::Borrow;
+
+use foldhash::HashMap;
+use foldhash::HashSet;
+
+use mago_allocator::LocalArena;
+use mago_codex::metadata::CodebaseMetadata;
+use mago_codex::metadata::class_like::ClassLikeMetadata;
+use mago_codex::reference::ReferenceOrigin;
+use mago_codex::reference::SymbolReferences;
+use mago_codex::symbol::SymbolIdentifier;
+use mago_database::file::File;
+use mago_database::file::FileId;
+use mago_reporting::Annotation;
+use mago_reporting::Issue;
+use mago_reporting::IssueCollection;
+use mago_span::Span;
+use mago_syntax::parser::parse_file;
+use mago_syntax::walker::Walker;
+use mago_text_edit::Safety;
+use mago_text_edit::TextEdit;
+use mago_word::Word;
+use mago_word::ascii_lowercase_word;
+use mago_word::empty_word;
+use mago_word::word;
+
+use crate::code::IssueCode;
+use crate::dead_code::declarations::MemberDeclarationWalker;
+use crate::dead_code::declarations::MemberDeclarations;
+use crate::dead_code::declarations::deletion_range;
+use crate::external::class_pattern_matches;
+use crate::external::pattern_matches;
+
+mod declarations;
+mod entry_points;
+
+pub use entry_points::DEFAULT_ENTRY_POINT_ATTRIBUTES;
+pub use entry_points::DEFAULT_ENTRY_POINT_CLASSES;
+pub use entry_points::EntryPoints;
+pub use entry_points::extract_symbol_names;
+
+/// The codes of the issues reported by [`find_unreachable_symbols`].
+pub const UNREACHABLE_SYMBOL_CODES: [IssueCode; 4] = [
+    IssueCode::UnreachableClass,
+    IssueCode::UnreachableMethod,
+    IssueCode::UnreachableConstant,
+    IssueCode::UnreachableProperty,
+];
+
+/// Reports the user-defined symbols that can not be reached from any entry point.
+///
+/// Issues are ordered by namespace, then by the symbol they report, and carry an edit
+/// deleting the unreachable declaration. The `files` are the sources the codebase was
+/// built from; they are only read to find the extent of the declarations to delete.
+pub fn find_unreachable_symbols<F>(
+    codebase: &CodebaseMetadata,
+    symbol_references: &SymbolReferences,
+    entry_points: &EntryPoints,
+    files: impl IntoIterator<Item = F>,
+) -> IssueCollection
+where
+    F: Borrow<File>,
+{
+    let mut reachability = Reachability::new(codebase, symbol_references);
+    reachability.seed_entry_points(entry_points);
+    reachability.propagate();
+
+    let mut reporter = UnreachableSymbolReporter::new(files);
+    let mut class_likes = codebase
+        .class_likes
+        .values()
+        .filter(|class_like| reachability.is_tracked_class_like(class_like))
+        .collect::<Vec<_>>();
+    class_likes.sort_by_cached_key(|class_like| {
+        let name = class_like.original_name.as_bytes().to_ascii_lowercase();
+        let namespace_length = name.iter().rposition(|byte| *byte == b'\\').unwrap_or(0);
+
+        (name[..namespace_length].to_vec(), name)
+    });
+
+    for class_like in class_likes {
+        if !reachability.is_reachable(class_like.name, empty_word()) {
+            reporter.report_class_like(class_like);
+            continue;
+        }
+
+        let mut members = Vec::new();
+        for method_name in &class_like.methods {
+            if is_magic_method(*method_name)
+                || !declares_method(class_like, *method_name)
+                || reachability.is_reachable(class_like.name, *method_name)
+            {
+                continue;
+            }
+
+            if let Some(method) = codebase.function_likes.get(&(class_like.name, *method_name)) {
+                members.push((method.span, UnreachableMember::Method(*method_name)));
+            }
+        }
+
+        for (constant_name, constant) in &class_like.constants {
+            if is_declared_in(class_like, constant.span) && !reachability.is_reachable(class_like.name, *constant_name)
+            {
+                members.push((constant.span, UnreachableMember::Constant(*constant_name)));
+            }
+        }
+
+        for (property_name, property) in &class_like.properties {
+            if class_like.declaring_property_ids.get(property_name) != Some(&class_like.name)
+                || reachability.is_reachable(class_like.name, *property_name)
+            {
+                continue;
+            }
+
+            if let Some(span) = property.name_span.or(property.span) {
+                members.push((span, UnreachableMember::Property(*property_name)));
+            }
+        }
+
+        members.sort_by_key(|(span, _)| span.start.offset);
+        for (_, member) in members {
+            reporter.report_member(codebase, class_like, member);
+        }
+    }
+
+    reporter.issues
+}
+
+/// The set of symbols reachable from the entry points of the program.
+struct Reachability<'ctx> {
+    codebase: &'ctx CodebaseMetadata,
+    references: HashMap<SymbolIdentifier, Vec<SymbolIdentifier>>,
+    reachable: HashSet<SymbolIdentifier>,
+    queue: Vec<SymbolIdentifier>,
+}
+
+impl<'ctx> Reachability<'ctx> {
+    /// Indexes the references made by the tracked symbols, and marks everything referenced
+    /// from elsewhere (top-level code, functions, plugins, and vendor code) as reachable.
+    fn new(codebase: &'ctx CodebaseMetadata, symbol_references: &SymbolReferences) -> Self {
+        let mut reachability =
+            Self { codebase, references: HashMap::default(), reachable: HashSet::default(), queue: Vec::new() };
+
+        let mut roots = Vec::new();
+        symbol_references.for_each_reference(|origin, target, _| match origin {
+            ReferenceOrigin::Symbol(source) if reachability.is_tracked(source) => {
+                reachability.references.entry(source).or_default().push(target);
+            }
+            _ => roots.push(target),
+        });
+
+        for root in roots {
+            reachability.mark(root);
+        }
+
+        reachability
+    }
+
+    fn seed_entry_points(&mut self, entry_points: &EntryPoints) {
+        let class_patterns = entry_points.classes.iter().map(|class| normalize_name(class)).collect::<Vec<_>>();
+        let attribute_patterns =
+            entry_points.attributes.iter().map(|attribute| normalize_name(attribute)).collect::<Vec<_>>();
+        let matches_attribute = |name: Word| {
+            let name = normalize_name(&name.as_str_lossy());
+            attribute_patterns.iter().any(|pattern| pattern_matches(pattern.as_bytes(), name.as_bytes()))
+        };
+
+        let codebase = self.codebase;
+        for class_like in codebase.class_likes.values() {
+            if !self.is_tracked_class_like(class_like) {
+                continue;
+            }
+
+            let is_api = class_like.flags.is_public_api();
+            let is_entry_point = class_patterns
+                .iter()
+                .any(|pattern| class_pattern_matches(codebase, pattern.as_bytes(), class_like.name.as_bytes()));
+
+            if is_api
+                || is_entry_point
+                || class_like.attributes.iter().any(|attribute| matches_attribute(attribute.name))
+            {
+                self.mark((class_like.name, empty_word()));
+            }
+
+            for (method_name, declaring_method) in &class_like.declaring_method_ids {
+                let Some(method) = codebase.get_method_by_id(declaring_method) else {
+                    continue;
+                };
+
+                let is_public = method.method_metadata.as_ref().is_some_and(|metadata| metadata.visibility.is_public());
+                let is_declared_here = declaring_method.get_class_name() == class_like.name;
+                if (is_public && (is_entry_point || (is_api && is_declared_here)))
+                    || (is_declared_here
+                        && (method.flags.is_public_api()
+                            || method.attributes.iter().any(|attribute| matches_attribute(attribute.name))))
+                {
+                    self.mark((declaring_method.get_class_name(), *method_name));
+                }
+            }
+
+            if is_api {
+                for (constant_name, constant) in &class_like.constants {
+                    if constant.visibility.is_public() {
+                        self.mark((class_like.name, *constant_name));
+                    }
+                }
+
+                for (property_name, property) in &class_like.properties {
+                    if property.read_visibility.is_public() {
+                        self.mark((class_like.name, *property_name));
+                    }
+                }
+            }
+        }
+
+        for symbol in &entry_points.symbols {
+            let symbol = normalize_name(symbol);
+            let (class_name, member_name) = match symbol.split_once("::") {
+                Some((class_name, member_name)) => (class_name, Some(member_name)),
+                None => (symbol.as_str(), None),
+            };
+
+            let Some(class_like) = codebase.get_class_like(class_name.as_bytes()) else {
+                continue;
+            };
+
+            let member = match member_name {
+                None => empty_word(),
+                Some(member_name) => {
+                    let method_name = ascii_lowercase_word(member_name.as_bytes());
+                    if class_like.methods.contains(&method_name) {
+                        method_name
+                    } else if member_name.starts_with('$') {
+                        word(member_name.as_bytes())
+                    } else {
+                        let property_name = word(format!("${member_name}").as_bytes());
+                        if class_like.properties.contains_key(&property_name) {
+                            property_name
+                        } else {
+                            word(member_name.as_bytes())
+                        }
+                    }
+                }
+            };
+
+            self.mark((class_like.name, member));
+        }
+    }
+
+    fn propagate(&mut self) {
+        while let Some(symbol) = self.queue.pop() {
+            for target in self.references.remove(&symbol).unwrap_or_default() {
+                self.mark(target);
+            }
+
+            let (class_name, member_name) = symbol;
+            let Some(class_like) = self.codebase.class_likes.get(&class_name) else {
+                continue;
+            };
+
+            if member_name.is_empty() {
+                self.reach_class_like(class_like);
+            } else {
+                self.mark((class_name, empty_word()));
+                self.reach_member(class_like, member_name);
+            }
+        }
+    }
+
+    /// A reachable class-like keeps its ancestors and magic methods alive, along with the
+    /// methods overriding an ancestor method that is itself reachable.
+    fn reach_class_like(&mut self, class_like: &ClassLikeMetadata) {
+        for ancestor in
+            class_like.all_parent_classes.iter().chain(&class_like.all_parent_interfaces).chain(&class_like.used_traits)
+        {
+            self.mark((*ancestor, empty_word()));
+        }
+
+        for (method_name, declaring_method) in &class_like.declaring_method_ids {
+            if is_magic_method(*method_name) {
+                self.mark((declaring_method.get_class_name(), *method_name));
+            } else if declaring_method.get_class_name() == class_like.name
+                && self.overrides_reachable_method(class_like, *method_name)
+            {
+                self.mark((class_like.name, *method_name));
+            }
+        }
+    }
+
+    fn reach_member(&mut self, class_like: &ClassLikeMetadata, member_name: Word) {
+        if class_like.methods.contains(&member_name) {
+            if let Some(declaring_method) = class_like.declaring_method_ids.get(&member_name) {
+                self.mark((declaring_method.get_class_name(), declaring_method.get_method_name()));
+            }
+
+            // A reachable method keeps the declarations it implements alive, and, as calls
+            // dispatch dynamically, the overrides of it in reachable descendants.
+            if let Some(overridden_methods) = class_like.overridden_method_ids.get(&member_name) {
+                for overridden_method in overridden_methods.values() {
+                    self.mark((overridden_method.get_class_name(), overridden_method.get_method_name()));
+                }
+            }
+
+            for descendant in self.codebase.get_all_descendants(class_like.name.as_bytes()) {
+                if self.reachable.contains(&(descendant, empty_word()))
+                    && self
+                        .codebase
+                        .class_likes
+                        .get(&descendant)
+                        .is_some_and(|descendant| declares_method(descendant, member_name))
+                {
+                    self.mark((descendant, member_name));
+                }
+            }
+        } else if let Some(declaring_class) = class_like.declaring_property_ids.get(&member_name) {
+            self.mark((*declaring_class, member_name));
+        } else {
+            for ancestor in class_like
+                .all_parent_classes
+                .iter()
+                .chain(&class_like.all_parent_interfaces)
+                .chain(&class_like.used_traits)
+            {
+                self.mark((*ancestor, member_name));
+            }
+        }
+    }
+
+    fn overrides_reachable_method(&self, class_like: &ClassLikeMetadata, method_name: Word) -> bool {
+        class_like.overridden_method_ids.get(&method_name).is_some_and(|overridden_methods| {
+            overridden_methods.values().any(|overridden_method| {
+                let class_name = overridden_method.get_class_name();
+
+                !self.codebase.class_likes.get(&class_name).is_some_and(|parent| self.is_tracked_class_like(parent))
+                    || self.reachable.contains(&(class_name, overridden_method.get_method_name()))
+            })
+        })
+    }
+
+    fn mark(&mut self, symbol: SymbolIdentifier) {
+        if self.reachable.insert(symbol) {
+            self.queue.push(symbol);
+        }
+    }
+
+    fn is_reachable(&self, class_name: Word, member_name: Word) -> bool {
+        self.reachable.contains(&(class_name, member_name))
+    }
+
+    /// Whether the reachability of a symbol is decided by this analysis; references made
+    /// by any other symbol are treated as made from an entry point.
+    fn is_tracked(&self, (symbol, member): SymbolIdentifier) -> bool {
+        if member.is_empty() && self.codebase.function_likes.contains_key(&(empty_word(), symbol)) {
+            return false;
+        }
+
+        self.codebase.class_likes.get(&symbol).is_some_and(|class_like| self.is_tracked_class_like(class_like))
+    }
+
+    fn is_tracked_class_like(&self, class_like: &ClassLikeMetadata) -> bool {
+        class_like.flags.is_user_defined() && !class_like.name.as_bytes().starts_with(b"{")
+    }
+}
+
+#[derive(Debug, Clone, Copy)]
+enum UnreachableMember {
+    Method(Word),
+    Constant(Word),
+    Property(Word),
+}
+
+struct UnreachableSymbolReporter<F> {
+    files: HashMap<FileId, F>,
+    member_declarations: HashMap<FileId, MemberDeclarations>,
+    arena: LocalArena,
+    issues: IssueCollection,
+}
+
+impl<F> UnreachableSymbolReporter<F>
+where
+    F: Borrow<File>,
+{
+    fn new(files: impl IntoIterator<Item = F>) -> Self {
+        Self {
+            files: files.into_iter().map(|file| (file.borrow().id, file)).collect(),
+            member_declarations: HashMap::default(),
+            arena: LocalArena::new(),
+            issues: IssueCollection::new(),
+        }
+    }
+
+    fn report_class_like(&mut self, class_like: &ClassLikeMetadata) {
+        let kind = class_like.kind.as_str();
+        let name = class_like.original_name;
+        let mut issue = Issue::help(format!("{} `{name}` is never reached from an entry point.", capitalize(kind)))
+            .with_code(IssueCode::UnreachableClass)
+            .with_annotation(
+                Annotation::primary(class_like.name_span.unwrap_or(class_like.span))
+                    .with_message(format!("{} `{name}` is declared here.", capitalize(kind))),
+            )
+            .with_note(namespace_note(name))
+            .with_note(format!(
+                "No entry point, top-level code, or function references this {kind}, directly or through other code."
+            ))
+            .with_help(format!("Remove the {kind}, or configure it as an entry point if a framework uses it."));
+
+        if let Some(edit) = self.deletion(class_like.span) {
+            issue = issue.with_edit(class_like.span.file_id, edit);
+        }
+
+        self.issues.push(issue);
+    }
+
+    fn report_member(
+        &mut self,
+        codebase: &CodebaseMetadata,
+        class_like: &ClassLikeMetadata,
+        member: UnreachableMember,
+    ) {
+        let class_name = class_like.original_name;
+        let (issue, declaration) = match member {
+            UnreachableMember::Method(method_name) => {
+                let Some(method) = codebase.function_likes.get(&(class_like.name, method_name)) else {
+                    return;
+                };
+
+                let display_name = format!("{class_name}::{}()", method.original_name);
+                let issue = Issue::help(format!("Method `{display_name}` is never reached from an entry point."))
+                    .with_code(IssueCode::UnreachableMethod)
+                    .with_annotation(
+                        Annotation::primary(method.name_span.unwrap_or(method.span))
+                            .with_message(format!("Method `{display_name}` is declared here.")),
+                    );
+
+                (issue, Some(method.span))
+            }
+            UnreachableMember::Constant(constant_name) => {
+                let Some(constant) = class_like.constants.get(&constant_name) else {
+                    return;
+                };
+
+                let display_name = format!("{class_name}::{constant_name}");
+                let issue = Issue::help(format!("Constant `{display_name}` is never reached from an entry point."))
+                    .with_code(IssueCode::UnreachableConstant)
+                    .with_annotation(
+                        Annotation::primary(constant.span)
+                            .with_message(format!("Constant `{display_name}` is declared here.")),
+                    );
+
+                (issue, self.member_declaration(constant.span))
+            }
+            UnreachableMember::Property(property_name) => {
+                let Some(property) = class_like.properties.get(&property_name) else {
+                    return;
+                };
+                let Some(name_span) = property.name_span.or(property.span) else {
+                    return;
+                };
+
+                let display_name = format!("{class_name}::{property_name}");
+                let issue = Issue::help(format!("Property `{display_name}` is never reached from an entry point."))
+                    .with_code(IssueCode::UnreachableProperty)
+                    .with_annotation(
+                        Annotation::primary(name_span)
+                            .with_message(format!("Property `{display_name}` is declared here.")),
+                    );
+
+                // Promoted properties are also constructor parameters, which can not be deleted
+                // without updating every call to the constructor.
+                let declaration =
+                    if property.flags.is_promoted_property() { None } else { self.member_declaration(name_span) };
+
+                (issue, declaration)
+            }
+        };
+
+        let mut issue = issue
+            .with_annotation(Annotation::secondary(class_like.name_span.unwrap_or(class_like.span)))
+            .with_note(namespace_note(class_name))
+            .with_note("The member is not referenced by any code reachable from an entry point.")
+            .with_help("Remove the member, or configure it as an entry point if a framework uses it.");
+
+        if let Some(declaration) = declaration
+            && let Some(edit) = self.deletion(declaration)
+        {
+            issue = issue.with_edit(declaration.file_id, edit);
+        }
+
+        self.issues.push(issue);
+    }
+
+    /// Finds the declaration of the class constant or property whose item spans `span`.
+    fn member_declaration(&mut self, span: Span) -> Option<Span> {
+        if !self.member_declarations.contains_key(&span.file_id) {
+            let mut declarations = MemberDeclarations::default();
+            if let Some(file) = self.files.get(&span.file_id) {
+                let program = parse_file(&self.arena, file.borrow());
+                MemberDeclarationWalker.walk_program(program, &mut declarations);
+                self.arena.reset();
+            }
+
+            self.member_declarations.insert(span.file_id, declarations);
+        }
+
+        self.member_declarations.get(&span.file_id).and_then(|declarations| declarations.get(&span).copied().flatten())
+    }
+
+    fn deletion(&self, declaration: Span) -> Option<TextEdit> {
+        let file = self.files.get(&declaration.file_id)?;
+        let (start, end) = deletion_range(&file.borrow().contents, declaration);
+
+        Some(TextEdit::delete(start..end).with_safety(Safety::PotentiallyUnsafe))
+    }
+}
+
+fn declares_method(class_like: &ClassLikeMetadata, method_name: Word) -> bool {
+    class_like
+        .declaring_method_ids
+        .get(&method_name)
+        .is_some_and(|declaring_method| declaring_method.get_class_name() == class_like.name)
+}
+
+/// Whether a member span lies within the class-like, rather than in an ancestor it was
+/// inherited from.
+fn is_declared_in(class_like: &ClassLikeMetadata, span: Span) -> bool {
+    span.file_id == class_like.span.file_id && class_like.span.contains(&span.start)
+}
+
+fn is_magic_method(method_name: Word) -> bool {
+    method_name.as_bytes().starts_with(b"__")
+}
+
+fn normalize_name(name: &str) -> String {
+    name.trim().trim_start_matches('\\').to_string()
+}
+
+fn namespace_note(name: Word) -> String {
+    match name.as_str_lossy().rsplit_once('\\') {
+        Some((namespace, _)) => format!("Declared in the `{namespace}` namespace."),
+        None => "Declared in the global namespace.".to_string(),
+    }
+}
+
+fn capitalize(kind: &str) -> String {
+    let mut characters = kind.chars();
+
+    characters.next().map(|first| format!("{}{}", first.to_ascii_uppercase(), characters.as_str())).unwrap_or_default()
+}
@@ -39,7 +39,8 @@ impl<'ast, 'arena> Analyzable<'ast, 'arena> for FunctionCall<'arena> {
         A: Arena,
     {
         if context.plugin_registry.has_function_call_hooks() {
-            let mut hook_context = HookContext::new(context.codebase, context.source_file, block_context, artifacts);
+            let mut hook_context =
+                HookContext::new(context.codebase, context.source_file, context.templates, block_context, artifacts);
             let result = context.plugin_registry.before_function_call(self, &mut hook_context)?;
             for reported in hook_context.take_issues() {
                 context.collector.report_with_code(reported.code, reported.issue);
@@ -77,7 +78,8 @@ impl<'ast, 'arena> Analyzable<'ast, 'arena> for FunctionCall<'arena> {
         )?;
 
         if context.plugin_registry.has_function_call_hooks() {
-            let mut hook_context = HookContext::new(context.codebase, context.source_file, block_context, artifacts);
+            let mut hook_context =
+                HookContext::new(context.codebase, context.source_file, context.templates, block_context, artifacts);
             context.plugin_registry.after_function_call(self, &mut hook_context)?;
             for reported in hook_context.take_issues() {
                 context.collector.report_with_code(reported.code, reported.issue);
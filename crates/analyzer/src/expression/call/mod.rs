@@ -80,7 +80,7 @@ pub(super) fn record_external_method_call_targets<A>(
 ) where
     A: Arena,
 {
-    if !context.plugin_registry.has_external_method_call_analysis_hooks() {
+    if !context.plugin_registry.has_external_method_call_analysis_hooks() && !context.settings.check_taints {
         return;
     }
 
@@ -3391,7 +3391,7 @@ impl<T> Drop for ExternalAnalyzer<T> {
     }
 }
 
-fn pattern_matches(pattern: &[u8], value: &[u8]) -> bool {
+pub(crate) fn pattern_matches(pattern: &[u8], value: &[u8]) -> bool {
     if pattern == b"*" {
         return true;
     }
@@ -3403,7 +3403,7 @@ fn pattern_matches(pattern: &[u8], value: &[u8]) -> bool {
     }
 }
 
-fn class_pattern_matches(codebase: &CodebaseMetadata, pattern: &[u8], class: &[u8]) -> bool {
+pub(crate) fn class_pattern_matches(codebase: &CodebaseMetadata, pattern: &[u8], class: &[u8]) -> bool {
     pattern_matches(pattern, class) || (!pattern.contains(&b'*') && codebase.is_instance_of(class, pattern))
 }
 
@@ -0,0 +1,187 @@
+//! Summaries of the Symfony form types in a project.
+//!
+//! Plugins use the [`FormIndex`] to type the data of a form from the `data_class` option its
+//! type declares in `configureOptions()`, either through `$resolver->setDefaults([...])` or
+//! `$resolver->setDefault('data_class', ...)`.
+
+use std::borrow::Borrow;
+
+use foldhash::HashMap;
+
+use mago_allocator::LocalArena;
+use mago_database::file::File;
+use mago_database::file::FileType;
+use mago_names::resolver::NameResolver;
+use mago_syntax::parser::parse_file;
+use mago_syntax::walker::Walker;
+
+use crate::form::scanner::FormScanner;
+use crate::form::scanner::FormScannerState;
+
+mod scanner;
+
+/// The `data_class` of the form types in a project, keyed by the lowercase name of the form type.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct FormIndex {
+    data_classes: HashMap<String, String>,
+}
+
+impl FormIndex {
+    #[must_use]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Builds an index from the form types declared in the project files among `files`.
+    ///
+    /// Only the files for which [`may_declare_forms`](Self::may_declare_forms) holds are parsed.
+    #[must_use]
+    pub fn from_files<F>(files: impl IntoIterator<Item = F>) -> Self
+    where
+        F: Borrow<File>,
+    {
+        let mut index = Self::new();
+        let mut arena = LocalArena::new();
+
+        for file in files {
+            let file = file.borrow();
+            if !Self::may_declare_forms(file) {
+                continue;
+            }
+
+            let program = parse_file(&arena, file);
+            if !program.has_errors() {
+                let resolved_names = NameResolver::new(&arena).resolve(program);
+                let mut state = FormScannerState::new(&mut index, &resolved_names);
+
+                FormScanner.walk_program(program, &mut state);
+            }
+
+            arena.reset();
+        }
+
+        index
+    }
+
+    /// Returns `true` if `file` is a PHP file of the project mentioning `data_class`.
+    ///
+    /// Vendored and built-in files are never indexed, and files that do not mention the option
+    /// cannot declare a data class, so this is enough to tell which changes affect the index.
+    #[must_use]
+    pub fn may_declare_forms(file: &File) -> bool {
+        file.file_type == FileType::Host
+            && file.language().is_php()
+            && memchr::memmem::find(&file.contents, b"data_class").is_some()
+    }
+
+    /// The `data_class` of the given form type.
+    #[must_use]
+    pub fn data_class(&self, form_type: &[u8]) -> Option<&str> {
+        self.data_classes.get(String::from_utf8_lossy(form_type).to_ascii_lowercase().as_str()).map(String::as_str)
+    }
+
+    /// The form types and their data classes, sorted by form type.
+    #[must_use]
+    pub fn entries(&self) -> Vec<(&str, &str)> {
+        let mut entries: Vec<_> =
+            self.data_classes.iter().map(|(form_type, data_class)| (form_type.as_str(), data_class.as_str())).collect();
+        entries.sort_unstable();
+        entries
+    }
+
+    #[must_use]
+    pub fn len(&self) -> usize {
+        self.data_classes.len()
+    }
+
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.data_classes.is_empty()
+    }
+
+    fn insert(&mut self, form_type: &str, data_class: &str) {
+        self.data_classes.insert(form_type.to_ascii_lowercase(), data_class.to_string());
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::borrow::Cow;
+
+    use super::*;
+
+    fn index(sources: &[(&str, &str)]) -> FormIndex {
+        let files: Vec<File> = sources
+            .iter()
+            .map(|(name, content)| {
+                File::ephemeral(Cow::Owned(name.as_bytes().to_vec()), Cow::Owned(content.as_bytes().to_vec()))
+            })
+            .collect();
+
+        FormIndex::from_files(&files)
+    }
+
+    #[test]
+    fn test_set_defaults() {
+        let index = index(&[(
+            "src/Form/UserType.php",
+            "<?php
+            namespace App\\Form;
+
+            use App\\Entity\\User;
+            use Symfony\\Component\\Form\\AbstractType;
+
+            final class UserType extends AbstractType
+            {
+                public function configureOptions(OptionsResolver $resolver): void
+                {
+                    $resolver->setDefaults([
+                        'data_class' => User::class,
+                        'csrf_protection' => true,
+                    ]);
+                }
+            }",
+        )]);
+
+        assert_eq!(index.data_class(b"App\\Form\\UserType"), Some("App\\Entity\\User"));
+        assert_eq!(index.data_class(b"app\\form\\usertype"), Some("App\\Entity\\User"));
+    }
+
+    #[test]
+    fn test_set_default() {
+        let index = index(&[(
+            "src/Form/AddressType.php",
+            "<?php
+            namespace App\\Form;
+
+            final class AddressType extends AbstractType
+            {
+                public function configureOptions(OptionsResolver $resolver): void
+                {
+                    $resolver->setDefault('data_class', \\App\\Model\\Address::class);
+                }
+            }",
+        )]);
+
+        assert_eq!(index.data_class(b"App\\Form\\AddressType"), Some("App\\Model\\Address"));
+    }
+
+    #[test]
+    fn test_ignores_other_methods() {
+        let index = index(&[(
+            "src/Form/SearchType.php",
+            "<?php
+            namespace App\\Form;
+
+            final class SearchType extends AbstractType
+            {
+                public function buildForm(FormBuilderInterface $builder, array $options): void
+                {
+                    $builder->add('query', null, ['data_class' => Query::class]);
+                }
+            }",
+        )]);
+
+        assert!(index.is_empty());
+    }
+}
@@ -0,0 +1,119 @@
+use mago_names::ResolvedNames;
+use mago_syntax::cst::Access;
+use mago_syntax::cst::Argument;
+use mago_syntax::cst::Class;
+use mago_syntax::cst::ClassLikeConstantSelector;
+use mago_syntax::cst::ClassLikeMemberSelector;
+use mago_syntax::cst::Expression;
+use mago_syntax::cst::KeyValueArrayElement;
+use mago_syntax::cst::Literal;
+use mago_syntax::cst::Method;
+use mago_syntax::cst::MethodCall;
+use mago_syntax::walker::Walker;
+
+use crate::form::FormIndex;
+
+/// The option naming the class a form maps its data to.
+const DATA_CLASS: &[u8] = b"data_class";
+
+pub(super) struct FormScannerState<'index, 'names, 'arena> {
+    index: &'index mut FormIndex,
+    resolved_names: &'names ResolvedNames<'arena>,
+    /// The class being walked, if it is a named class.
+    class: Option<String>,
+    /// Whether the walker is inside the `configureOptions()` method of that class.
+    in_configure_options: bool,
+}
+
+impl<'index, 'names, 'arena> FormScannerState<'index, 'names, 'arena> {
+    pub(super) fn new(index: &'index mut FormIndex, resolved_names: &'names ResolvedNames<'arena>) -> Self {
+        Self { index, resolved_names, class: None, in_configure_options: false }
+    }
+
+    fn record(&mut self, data_class: &Expression<'_>) {
+        let Some(class) = &self.class else {
+            return;
+        };
+
+        if let Some(data_class) = class_name(self.resolved_names, data_class) {
+            self.index.insert(class, &data_class);
+        }
+    }
+}
+
+/// Walks a file and records the `data_class` set by the `configureOptions()` method of each class.
+pub(super) struct FormScanner;
+
+impl<'ast, 'arena> Walker<'ast, 'arena, FormScannerState<'_, '_, 'arena>> for FormScanner {
+    fn walk_in_class(&self, class: &'ast Class<'arena>, state: &mut FormScannerState<'_, '_, 'arena>) {
+        state.class = Some(String::from_utf8_lossy(state.resolved_names.get(&class.name)).into_owned());
+    }
+
+    fn walk_out_class(&self, _class: &'ast Class<'arena>, state: &mut FormScannerState<'_, '_, 'arena>) {
+        state.class = None;
+    }
+
+    fn walk_in_method(&self, method: &'ast Method<'arena>, state: &mut FormScannerState<'_, '_, 'arena>) {
+        state.in_configure_options = method.name.value.eq_ignore_ascii_case(b"configureOptions");
+    }
+
+    fn walk_out_method(&self, _method: &'ast Method<'arena>, state: &mut FormScannerState<'_, '_, 'arena>) {
+        state.in_configure_options = false;
+    }
+
+    fn walk_in_key_value_array_element(
+        &self,
+        element: &'ast KeyValueArrayElement<'arena>,
+        state: &mut FormScannerState<'_, '_, 'arena>,
+    ) {
+        if state.in_configure_options && is_data_class(element.key) {
+            state.record(element.value);
+        }
+    }
+
+    fn walk_in_method_call(&self, method_call: &'ast MethodCall<'arena>, state: &mut FormScannerState<'_, '_, 'arena>) {
+        if !state.in_configure_options {
+            return;
+        }
+
+        let ClassLikeMemberSelector::Identifier(method) = &method_call.method else {
+            return;
+        };
+
+        if !method.value.eq_ignore_ascii_case(b"setDefault") {
+            return;
+        }
+
+        let mut arguments = method_call.argument_list.arguments.iter().map(Argument::value);
+        if let (Some(option), Some(value)) = (arguments.next(), arguments.next())
+            && is_data_class(option)
+        {
+            state.record(value);
+        }
+    }
+}
+
+fn is_data_class(expression: &Expression<'_>) -> bool {
+    matches!(expression, Expression::Literal(Literal::String(string)) if string.value == Some(DATA_CLASS))
+}
+
+/// The class named by a `Foo::class` expression.
+fn class_name(resolved_names: &ResolvedNames<'_>, expression: &Expression<'_>) -> Option<String> {
+    let Expression::Access(Access::ClassConstant(access)) = expression else {
+        return None;
+    };
+
+    let ClassLikeConstantSelector::Identifier(constant) = &access.constant else {
+        return None;
+    };
+
+    let Expression::Identifier(class) = access.class else {
+        return None;
+    };
+
+    if !constant.value.eq_ignore_ascii_case(b"class") {
+        return None;
+    }
+
+    Some(String::from_utf8_lossy(resolved_names.resolve(class)?).into_owned())
+}
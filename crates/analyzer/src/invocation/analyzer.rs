@@ -300,6 +300,25 @@ where
 
         let parameter_type = if let Some(mut pt) = parameter_type {
             filter_array_filter_callback_type(&invocation.target, &mut pt, &analyzed_argument_types);
+
+            if context.plugin_registry.has_function_parameter_providers()
+                && let Some(FunctionLikeIdentifier::Function(function_name)) =
+                    invocation.target.get_function_like_identifier()
+                && let Some(parameter_name) = parameter.and_then(|(_, parameter)| parameter.get_name())
+                && let Some(refined_type) = context.plugin_registry.get_function_parameter_type(
+                    context.codebase,
+                    context.source_file,
+                    block_context,
+                    artifacts,
+                    function_name.as_bytes(),
+                    invocation,
+                    parameter_name.0.as_bytes(),
+                    &pt,
+                )
+            {
+                pt = refined_type;
+            }
+
             Some(pt)
         } else {
             None
@@ -68,6 +68,8 @@ where
         artifacts,
         identifier,
         invocation,
+        context.container,
+        context.forms,
         context.external_analysis_session,
     ) {
         for reported_issue in result.issues {
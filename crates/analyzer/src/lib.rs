@@ -20,25 +20,35 @@ use mago_word::word;
 
 use crate::analysis_result::AnalysisResult;
 use crate::artifacts::AnalysisArtifacts;
+use crate::container::ServiceContainer;
 use crate::context::Context;
 use crate::context::block::BlockContext;
 use crate::error::AnalysisError;
 use crate::external::ExternalAnalysisSession;
+use crate::form::FormIndex;
 use crate::plugin::PluginRegistry;
 use crate::plugin::context::HookContext;
 use crate::plugin::hook::HookAction;
+use crate::schema::DatabaseSchema;
 use crate::settings::Settings;
 use crate::statement::analyze_statements;
+use crate::template::TemplateIndex;
 
 pub mod analysis_result;
 pub mod artifacts;
 pub mod code;
+pub mod container;
+pub mod dead_code;
 pub mod error;
 pub mod external;
+pub mod form;
 pub mod plugin;
+pub mod schema;
 pub mod settings;
+pub mod taint;
 #[cfg(not(target_arch = "wasm32"))]
 pub mod telemetry;
+pub mod template;
 
 mod analyzable;
 mod assertion;
@@ -69,6 +79,10 @@ where
     pub plugin_registry: &'ctx PluginRegistry,
     pub external_analysis_session: Option<&'ctx ExternalAnalysisSession>,
     pub additional_symbol_references: Option<&'ctx SymbolReferences>,
+    pub templates: Option<&'ctx TemplateIndex>,
+    pub schema: Option<&'ctx DatabaseSchema>,
+    pub container: Option<&'ctx ServiceContainer>,
+    pub forms: Option<&'ctx FormIndex>,
     defer_pragmas: bool,
 }
 
@@ -93,6 +107,10 @@ where
             plugin_registry,
             external_analysis_session: None,
             additional_symbol_references: None,
+            templates: None,
+            schema: None,
+            container: None,
+            forms: None,
             defer_pragmas: false,
         }
     }
@@ -109,6 +127,34 @@ where
         self
     }
 
+    /// Makes the project's Twig templates available to plugin hooks.
+    #[must_use]
+    pub fn with_templates(mut self, templates: &'ctx TemplateIndex) -> Self {
+        self.templates = Some(templates);
+        self
+    }
+
+    /// Makes the database schema declared by the project's migrations available to plugin providers.
+    #[must_use]
+    pub fn with_schema(mut self, schema: &'ctx DatabaseSchema) -> Self {
+        self.schema = Some(schema);
+        self
+    }
+
+    /// Makes the project's compiled service container available to plugin providers.
+    #[must_use]
+    pub fn with_container(mut self, container: &'ctx ServiceContainer) -> Self {
+        self.container = Some(container);
+        self
+    }
+
+    /// Makes the project's form types available to plugin providers.
+    #[must_use]
+    pub fn with_forms(mut self, forms: &'ctx FormIndex) -> Self {
+        self.forms = Some(forms);
+        self
+    }
+
     /// Defers unused and unfulfilled pragma reporting until external lifecycle
     /// diagnostics have been collected.
     #[must_use]
@@ -179,6 +225,10 @@ where
             self.plugin_registry,
             self.external_analysis_session,
             self.additional_symbol_references,
+            self.templates,
+            self.schema,
+            self.container,
+            self.forms,
         );
 
         let mut block_context = BlockContext::new(
@@ -192,8 +242,13 @@ where
         }
 
         if self.plugin_registry.has_program_hooks() {
-            let mut hook_context =
-                HookContext::new(context.codebase, context.source_file, &mut block_context, &mut artifacts);
+            let mut hook_context = HookContext::new(
+                context.codebase,
+                context.source_file,
+                context.templates,
+                &mut block_context,
+                &mut artifacts,
+            );
 
             if self.plugin_registry.before_program(self.source_file, program, &mut hook_context)? == HookAction::Skip {
                 for reported in hook_context.take_issues() {
@@ -226,14 +281,29 @@ where
 
         // Call after_program hooks
         if self.plugin_registry.has_program_hooks() {
-            let mut hook_context =
-                HookContext::new(context.codebase, context.source_file, &mut block_context, &mut artifacts);
+            let mut hook_context = HookContext::new(
+                context.codebase,
+                context.source_file,
+                context.templates,
+                &mut block_context,
+                &mut artifacts,
+            );
             self.plugin_registry.after_program(self.source_file, program, &mut hook_context)?;
             for reported in hook_context.take_issues() {
                 context.collector.report_with_code(reported.code, reported.issue);
             }
         }
 
+        if self.settings.check_taints {
+            analysis_result.taint_graph.extend(taint::build_graph(
+                program,
+                self.codebase,
+                self.resolved_names,
+                &artifacts,
+                self.source_file,
+            ));
+        }
+
         #[cfg(not(target_arch = "wasm32"))]
         let finish_start = trace_enabled.then(std::time::Instant::now);
         analysis_result.symbol_references.extend(std::mem::take(&mut artifacts.symbol_references));
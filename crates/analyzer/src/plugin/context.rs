@@ -26,10 +26,13 @@ use mago_word::word;
 
 use crate::artifacts::AnalysisArtifacts;
 use crate::code::IssueCode;
+use crate::container::ServiceContainer;
 use crate::context::block::BlockContext;
+use crate::form::FormIndex;
 use crate::invocation::Invocation;
 use crate::invocation::InvocationArgument;
 use crate::invocation::InvocationArgumentsSource;
+use crate::template::TemplateIndex;
 
 pub struct ReportedIssue {
     pub code: IssueCode,
@@ -42,6 +45,8 @@ pub struct ProviderContext<'codebase, 'artifacts, 'block> {
     pub(crate) source_file: &'codebase File,
     pub(crate) artifacts: &'artifacts AnalysisArtifacts,
     pub(crate) block_context: &'block BlockContext<'codebase>,
+    pub(crate) container: Option<&'codebase ServiceContainer>,
+    pub(crate) forms: Option<&'codebase FormIndex>,
     pub(crate) reported_issues: RefCell<Vec<ReportedIssue>>,
 }
 
@@ -52,7 +57,27 @@ impl<'codebase, 'artifacts, 'block> ProviderContext<'codebase, 'artifacts, 'bloc
         block_context: &'block BlockContext<'codebase>,
         artifacts: &'artifacts AnalysisArtifacts,
     ) -> Self {
-        Self { codebase, source_file, artifacts, block_context, reported_issues: RefCell::new(Vec::new()) }
+        Self {
+            codebase,
+            source_file,
+            artifacts,
+            block_context,
+            container: None,
+            forms: None,
+            reported_issues: RefCell::new(Vec::new()),
+        }
+    }
+
+    #[must_use]
+    pub(crate) fn with_container(mut self, container: Option<&'codebase ServiceContainer>) -> Self {
+        self.container = container;
+        self
+    }
+
+    #[must_use]
+    pub(crate) fn with_forms(mut self, forms: Option<&'codebase FormIndex>) -> Self {
+        self.forms = forms;
+        self
     }
 
     pub fn report(&self, code: IssueCode, issue: Issue) {
@@ -68,6 +93,18 @@ impl<'codebase, 'artifacts, 'block> ProviderContext<'codebase, 'artifacts, 'bloc
         self.codebase
     }
 
+    /// Get the project's compiled service container, if the analyzer was given one.
+    #[inline]
+    pub fn container(&self) -> Option<&'codebase ServiceContainer> {
+        self.container
+    }
+
+    /// Get the project's form types, if the analyzer was given them.
+    #[inline]
+    pub fn forms(&self) -> Option<&'codebase FormIndex> {
+        self.forms
+    }
+
     #[inline]
     pub fn get_expression_type<T>(&self, expr: &T) -> Option<&TUnion>
     where
@@ -210,6 +247,7 @@ impl<'codebase, 'artifacts, 'block> ProviderContext<'codebase, 'artifacts, 'bloc
 pub struct HookContext<'ctx, 'block> {
     pub(crate) codebase: &'ctx CodebaseMetadata,
     pub(crate) source_file: &'ctx File,
+    pub(crate) templates: Option<&'ctx TemplateIndex>,
     pub(crate) block_context: &'block mut BlockContext<'ctx>,
     pub(crate) artifacts: &'block mut AnalysisArtifacts,
     pub(crate) reported_issues: RefCell<Vec<ReportedIssue>>,
@@ -219,10 +257,11 @@ impl<'ctx, 'block> HookContext<'ctx, 'block> {
     pub(crate) fn new(
         codebase: &'ctx CodebaseMetadata,
         source_file: &'ctx File,
+        templates: Option<&'ctx TemplateIndex>,
         block_context: &'block mut BlockContext<'ctx>,
         artifacts: &'block mut AnalysisArtifacts,
     ) -> Self {
-        Self { codebase, source_file, artifacts, block_context, reported_issues: RefCell::new(Vec::new()) }
+        Self { codebase, source_file, templates, artifacts, block_context, reported_issues: RefCell::new(Vec::new()) }
     }
 
     /// Report an issue from a hook.
@@ -240,6 +279,18 @@ impl<'ctx, 'block> HookContext<'ctx, 'block> {
         self.codebase
     }
 
+    /// Get the project's Twig templates, if the analyzer was given them.
+    #[inline]
+    pub fn templates(&self) -> Option<&'ctx TemplateIndex> {
+        self.templates
+    }
+
+    /// Get the file being analyzed.
+    #[inline]
+    pub fn source_file(&self) -> &'ctx File {
+        self.source_file
+    }
+
     /// Get the type of an expression.
     #[inline]
     pub fn get_expression_type<T>(&self, expr: &T) -> Option<&TUnion>
@@ -0,0 +1,139 @@
+//! Checks of the criteria passed to repository finders.
+
+use mago_codex::ttype::union::TUnion;
+use mago_reporting::Annotation;
+use mago_reporting::Issue;
+use mago_span::HasSpan;
+use mago_syntax::cst::Argument;
+use mago_syntax::cst::ArrayElement;
+use mago_syntax::cst::ClassLikeMemberSelector;
+use mago_syntax::cst::Expression;
+use mago_syntax::cst::MethodCall;
+use mago_word::Word;
+
+use crate::code::IssueCode;
+use crate::plugin::context::HookContext;
+use crate::plugin::hook::HookResult;
+use crate::plugin::hook::MethodCallHook;
+use crate::plugin::libraries::doctrine::is_doctrine_method;
+use crate::plugin::libraries::doctrine::mapping::field_names;
+use crate::plugin::libraries::doctrine::mapping::is_entity;
+use crate::plugin::libraries::doctrine::mapping::unmapped_segment;
+use crate::plugin::libraries::doctrine::repositories;
+use crate::plugin::libraries::doctrine::repository_entity;
+use crate::plugin::provider::Provider;
+use crate::plugin::provider::ProviderMeta;
+
+/// Repository methods taking a criteria array, and whether they also take an `orderBy` array.
+const FINDERS: [(&[u8], bool); 3] = [(b"findBy", true), (b"findOneBy", true), (b"count", false)];
+
+/// Checks the keys of the criteria and `orderBy` arrays passed to `findBy()`, `findOneBy()`, and
+/// `count()` against the fields mapped on the repository's entity.
+#[derive(Default)]
+pub struct RepositoryCriteriaHook;
+
+impl Provider for RepositoryCriteriaHook {
+    fn meta() -> &'static ProviderMeta {
+        static META: ProviderMeta = ProviderMeta::new(
+            "doctrine::repository::criteria",
+            "Repository criteria",
+            "Checks findBy(), findOneBy(), and count() criteria against the entity's mapped fields.",
+        );
+
+        &META
+    }
+}
+
+impl MethodCallHook for RepositoryCriteriaHook {
+    fn after_method_call(&self, call: &MethodCall<'_>, context: &mut HookContext<'_, '_>) -> HookResult<()> {
+        let ClassLikeMemberSelector::Identifier(method) = &call.method else {
+            return Ok(());
+        };
+
+        let Some(&(_, has_order_by)) = FINDERS.iter().find(|(name, _)| name.eq_ignore_ascii_case(method.value)) else {
+            return Ok(());
+        };
+
+        let Some(entity) = find_entity(context, call, method.value) else {
+            return Ok(());
+        };
+
+        if let Some(criteria) = get_argument(call, 0, &[b"criteria"]) {
+            check_keys(context, entity, criteria);
+        }
+
+        if has_order_by && let Some(order_by) = get_argument(call, 1, &[b"orderBy"]) {
+            check_keys(context, entity, order_by);
+        }
+
+        Ok(())
+    }
+}
+
+/// The attribute-mapped entity of the repository `call` is made on.
+fn find_entity(context: &HookContext<'_, '_>, call: &MethodCall<'_>, method: &[u8]) -> Option<Word> {
+    let codebase = context.codebase();
+    let object_type = context.get_expression_type(call.object)?;
+    let repository = repositories(codebase, object_type).next()?;
+    if !is_doctrine_method(codebase, repository.get_name().as_bytes(), method) {
+        return None;
+    }
+
+    let entity = codebase.get_class_like(repository_entity(codebase, repository)?.as_bytes())?;
+
+    is_entity(entity).then_some(entity.name)
+}
+
+fn check_keys(context: &HookContext<'_, '_>, entity: Word, array: &Expression<'_>) {
+    let elements = match array {
+        Expression::Array(array) => &array.elements,
+        Expression::LegacyArray(array) => &array.elements,
+        _ => return,
+    };
+
+    let codebase = context.codebase();
+    for element in elements.iter() {
+        let ArrayElement::KeyValue(element) = element else {
+            continue;
+        };
+
+        let Some(key) = context.get_expression_type(element.key).and_then(TUnion::get_single_literal_string_value)
+        else {
+            continue;
+        };
+
+        let key = String::from_utf8_lossy(key);
+        let Some(segment) = unmapped_segment(codebase, entity.as_bytes(), &key) else {
+            continue;
+        };
+
+        let entity_name = codebase.get_class_like(entity.as_bytes()).map_or(entity, |entity| entity.original_name);
+        let fields = field_names(codebase, entity.as_bytes());
+
+        let mut issue = Issue::error(format!("Entity `{entity_name}` has no mapped field `{segment}`."))
+            .with_annotation(Annotation::primary(element.key.span()).with_message("This field is not mapped"))
+            .with_note("Doctrine builds its query from the entity's mapping, and throws for fields it does not know.");
+
+        if !fields.is_empty() {
+            issue = issue.with_help(format!("Use one of the mapped fields: `{}`.", fields.join("`, `")));
+        }
+
+        context.report(IssueCode::NonExistentEntityField, issue);
+    }
+}
+
+fn get_argument<'arena>(
+    call: &MethodCall<'arena>,
+    position: usize,
+    names: &[&[u8]],
+) -> Option<&'arena Expression<'arena>> {
+    for (index, argument) in call.argument_list.arguments.iter().enumerate() {
+        match argument {
+            Argument::Positional(argument) if index == position => return Some(argument.value),
+            Argument::Named(argument) if names.contains(&argument.name.value) => return Some(argument.value),
+            _ => {}
+        }
+    }
+
+    None
+}
@@ -0,0 +1,416 @@
+//! Checks of the DQL passed to `EntityManagerInterface::createQuery()`.
+//!
+//! The query is not fully parsed: only the entities it selects from or joins, the aliases it
+//! binds them to, and the `alias.field` paths it uses are read from it. This is enough to catch
+//! misspelled entities and fields, without having to follow the whole DQL grammar.
+
+use std::collections::HashMap;
+
+use mago_codex::metadata::CodebaseMetadata;
+use mago_codex::ttype::atomic::TAtomic;
+use mago_codex::ttype::atomic::object::TObject;
+use mago_codex::ttype::union::TUnion;
+use mago_reporting::Annotation;
+use mago_reporting::Issue;
+use mago_span::HasSpan;
+use mago_span::Span;
+use mago_syntax::cst::Argument;
+use mago_syntax::cst::ClassLikeMemberSelector;
+use mago_syntax::cst::Expression;
+use mago_syntax::cst::Literal;
+use mago_syntax::cst::MethodCall;
+use mago_word::Word;
+
+use crate::code::IssueCode;
+use crate::plugin::context::HookContext;
+use crate::plugin::hook::HookResult;
+use crate::plugin::hook::MethodCallHook;
+use crate::plugin::libraries::doctrine::ENTITY_MANAGER;
+use crate::plugin::libraries::doctrine::mapping::Field;
+use crate::plugin::libraries::doctrine::mapping::field;
+use crate::plugin::libraries::doctrine::mapping::field_names;
+use crate::plugin::libraries::doctrine::mapping::is_entity;
+use crate::plugin::libraries::doctrine::mapping::unmapped_segment;
+use crate::plugin::provider::Provider;
+use crate::plugin::provider::ProviderMeta;
+
+/// Keywords that may follow an entity or a join in place of an alias.
+const CLAUSE_KEYWORDS: [&str; 15] = [
+    "where", "join", "left", "inner", "outer", "on", "with", "index", "order", "group", "having", "set", "and", "or",
+    "by",
+];
+
+/// Checks the entities and fields used in DQL queries against the entity mappings.
+#[derive(Default)]
+pub struct DqlHook;
+
+impl Provider for DqlHook {
+    fn meta() -> &'static ProviderMeta {
+        static META: ProviderMeta = ProviderMeta::new(
+            "doctrine::dql",
+            "DQL queries",
+            "Checks the entities and fields used in createQuery() DQL against the entity mappings.",
+        );
+
+        &META
+    }
+}
+
+impl MethodCallHook for DqlHook {
+    fn after_method_call(&self, call: &MethodCall<'_>, context: &mut HookContext<'_, '_>) -> HookResult<()> {
+        let ClassLikeMemberSelector::Identifier(method) = &call.method else {
+            return Ok(());
+        };
+
+        if !method.value.eq_ignore_ascii_case(b"createQuery") || !is_entity_manager(context, call) {
+            return Ok(());
+        }
+
+        let Some(argument) = get_argument(call, 0, &[b"dql"]) else {
+            return Ok(());
+        };
+
+        let Some(dql) = context.get_expression_type(argument).and_then(TUnion::get_single_literal_string_value) else {
+            return Ok(());
+        };
+
+        let dql = String::from_utf8_lossy(dql).into_owned();
+        check_query(context, &Query::parse(&dql), argument);
+
+        Ok(())
+    }
+}
+
+/// What a `FROM`, `JOIN`, `UPDATE`, or `DELETE` clause reads from.
+#[derive(Debug, PartialEq, Eq)]
+enum Target<'query> {
+    /// An entity class, like `App\Entity\User`.
+    Entity(&'query str),
+    /// An association of an aliased entity, like `u.posts`.
+    Association { parent: &'query str, field: &'query str },
+}
+
+/// An entity or association read by the query, and the alias it is bound to.
+#[derive(Debug, PartialEq, Eq)]
+struct Declaration<'query> {
+    target: Target<'query>,
+    alias: Option<&'query str>,
+    offset: usize,
+    length: usize,
+}
+
+/// A field path, like `u.email` or `u.address.city`.
+#[derive(Debug, PartialEq, Eq)]
+struct FieldPath<'query> {
+    alias: &'query str,
+    path: &'query str,
+    offset: usize,
+    length: usize,
+}
+
+/// The entities, aliases, and field paths used by a DQL query.
+#[derive(Debug, Default)]
+struct Query<'query> {
+    declarations: Vec<Declaration<'query>>,
+    paths: Vec<FieldPath<'query>>,
+}
+
+impl<'query> Query<'query> {
+    fn parse(dql: &'query str) -> Self {
+        let words = words(dql);
+        let mut query = Query::default();
+        let mut index = 0;
+
+        while index < words.len() {
+            let (word, offset) = words[index];
+            index += 1;
+
+            let is_declaring = match word.to_ascii_lowercase().as_str() {
+                "from" | "join" => true,
+                // `DELETE FROM ...` is handled by the `FROM` that follows.
+                "update" | "delete" => words.get(index).is_some_and(|(next, _)| !next.eq_ignore_ascii_case("from")),
+                _ => false,
+            };
+
+            if is_declaring {
+                let Some(&(target, target_offset)) = words.get(index) else {
+                    break;
+                };
+
+                index += 1;
+                if words.get(index).is_some_and(|(next, _)| next.eq_ignore_ascii_case("as")) {
+                    index += 1;
+                }
+
+                let alias = match words.get(index) {
+                    Some(&(alias, _)) if is_identifier(alias) && !is_clause_keyword(alias) => {
+                        index += 1;
+                        Some(alias)
+                    }
+                    _ => None,
+                };
+
+                let length = target.len();
+                let target = match target.split_once('.') {
+                    Some((parent, field)) if !target.contains('\\') => Target::Association { parent, field },
+                    _ => Target::Entity(target.trim_start_matches('\\')),
+                };
+
+                query.declarations.push(Declaration { target, alias, offset: target_offset, length });
+
+                continue;
+            }
+
+            if let Some((alias, path)) = word.split_once('.')
+                && is_identifier(alias)
+                && !word.contains('\\')
+                && path.split('.').all(is_identifier)
+            {
+                query.paths.push(FieldPath { alias, path, offset, length: word.len() });
+            }
+        }
+
+        query
+    }
+}
+
+fn check_query(context: &HookContext<'_, '_>, query: &Query<'_>, argument: &Expression<'_>) {
+    let codebase = context.codebase();
+    let mut aliases: HashMap<&str, Word> = HashMap::new();
+
+    for declaration in &query.declarations {
+        let span = query_span(argument, declaration.offset, declaration.length);
+        let entity = match declaration.target {
+            // `Bundle:Entity` aliases are resolved by the bundle's configuration.
+            Target::Entity(class) if class.contains(':') => None,
+            Target::Entity(class) => {
+                let Some(metadata) = codebase.get_class_like(class.as_bytes()) else {
+                    context.report(
+                        IssueCode::NonExistentEntity,
+                        Issue::error(format!("Entity `{class}` used in DQL does not exist."))
+                            .with_annotation(Annotation::primary(span).with_message("This class is not found"))
+                            .with_help("Check the class name, and use its fully qualified name."),
+                    );
+
+                    continue;
+                };
+
+                // Entities mapped with XML or PHP files are not known, so they are not checked.
+                is_entity(metadata).then_some(metadata.name)
+            }
+            Target::Association { parent, field: association } => {
+                let Some(&parent_entity) = aliases.get(parent) else {
+                    continue;
+                };
+
+                match field(codebase, parent_entity.as_bytes(), association) {
+                    Some(Field::Association(Some(target))) => codebase
+                        .get_class_like(target.as_bytes())
+                        .filter(|metadata| is_entity(metadata))
+                        .map(|metadata| metadata.name),
+                    Some(_) => None,
+                    None => {
+                        report_unmapped_field(context, codebase, parent_entity, association, span);
+
+                        None
+                    }
+                }
+            }
+        };
+
+        if let (Some(alias), Some(entity)) = (declaration.alias, entity) {
+            aliases.insert(alias, entity);
+        }
+    }
+
+    for path in &query.paths {
+        let Some(&entity) = aliases.get(path.alias) else {
+            continue;
+        };
+
+        if let Some(segment) = unmapped_segment(codebase, entity.as_bytes(), path.path) {
+            report_unmapped_field(context, codebase, entity, segment, query_span(argument, path.offset, path.length));
+        }
+    }
+}
+
+fn report_unmapped_field(
+    context: &HookContext<'_, '_>,
+    codebase: &CodebaseMetadata,
+    entity: Word,
+    name: &str,
+    span: Span,
+) {
+    let entity_name = codebase.get_class_like(entity.as_bytes()).map_or(entity, |entity| entity.original_name);
+    let fields = field_names(codebase, entity.as_bytes());
+
+    let mut issue = Issue::error(format!("Entity `{entity_name}` has no mapped field `{name}`."))
+        .with_annotation(Annotation::primary(span).with_message("This field is not mapped"))
+        .with_note("DQL refers to the fields of the entity mapping, not to columns or to unmapped properties.");
+
+    if !fields.is_empty() {
+        issue = issue.with_help(format!("Use one of the mapped fields: `{}`.", fields.join("`, `")));
+    }
+
+    context.report(IssueCode::NonExistentEntityField, issue);
+}
+
+/// The span of `length` bytes at `offset` in the DQL, when the argument is a string literal
+/// whose content is the query as written; the span of the whole argument otherwise.
+fn query_span(argument: &Expression<'_>, offset: usize, length: usize) -> Span {
+    if let Expression::Literal(Literal::String(literal)) = argument
+        && let Some(value) = literal.value
+        && literal.raw.len() == value.len() + 2
+    {
+        return literal.span.subspan((offset + 1) as u32, (offset + length + 1) as u32);
+    }
+
+    argument.span()
+}
+
+/// Splits a query into words (identifiers, class names, and dotted paths) with their offsets,
+/// skipping string literals and parameters.
+fn words(dql: &str) -> Vec<(&str, usize)> {
+    let bytes = dql.as_bytes();
+    let mut words = Vec::new();
+    let mut index = 0;
+
+    while index < bytes.len() {
+        let byte = bytes[index];
+        if byte == b'\'' {
+            index += 1;
+            while index < bytes.len() {
+                if bytes[index] == b'\'' {
+                    // A quote is escaped by doubling it.
+                    if bytes.get(index + 1) == Some(&b'\'') {
+                        index += 2;
+                        continue;
+                    }
+
+                    break;
+                }
+
+                index += 1;
+            }
+
+            index += 1;
+        } else if byte == b':' || byte == b'?' {
+            index += 1;
+            while index < bytes.len() && is_word_byte(bytes[index]) {
+                index += 1;
+            }
+        } else if is_word_byte(byte) {
+            let start = index;
+            // `Bundle:Entity` aliases are kept as a single word.
+            while index < bytes.len() && (is_word_byte(bytes[index]) || bytes[index] == b':') {
+                index += 1;
+            }
+
+            words.push((&dql[start..index], start));
+        } else {
+            index += 1;
+        }
+    }
+
+    words
+}
+
+fn is_word_byte(byte: u8) -> bool {
+    byte.is_ascii_alphanumeric() || matches!(byte, b'_' | b'\\' | b'.') || !byte.is_ascii()
+}
+
+fn is_identifier(word: &str) -> bool {
+    word.chars().next().is_some_and(|first| first.is_alphabetic() || first == '_')
+        && word.chars().all(|character| character.is_alphanumeric() || character == '_')
+}
+
+fn is_clause_keyword(word: &str) -> bool {
+    CLAUSE_KEYWORDS.iter().any(|keyword| keyword.eq_ignore_ascii_case(word))
+}
+
+fn is_entity_manager(context: &HookContext<'_, '_>, call: &MethodCall<'_>) -> bool {
+    let Some(object_type) = context.get_expression_type(call.object) else {
+        return false;
+    };
+
+    object_type.types.iter().any(|atomic| match atomic {
+        TAtomic::Object(TObject::Named(named)) => context.is_instance_of(named.name.as_bytes(), ENTITY_MANAGER),
+        _ => false,
+    })
+}
+
+fn get_argument<'arena>(
+    call: &MethodCall<'arena>,
+    position: usize,
+    names: &[&[u8]],
+) -> Option<&'arena Expression<'arena>> {
+    for (index, argument) in call.argument_list.arguments.iter().enumerate() {
+        match argument {
+            Argument::Positional(argument) if index == position => return Some(argument.value),
+            Argument::Named(argument) if names.contains(&argument.name.value) => return Some(argument.value),
+            _ => {}
+        }
+    }
+
+    None
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_select_with_join() {
+        let query = Query::parse(
+            "SELECT u, p FROM App\\Entity\\User u LEFT JOIN u.posts AS p WHERE u.email = :email AND p.title LIKE '%u.x%'",
+        );
+
+        assert_eq!(
+            query.declarations,
+            vec![
+                Declaration { target: Target::Entity("App\\Entity\\User"), alias: Some("u"), offset: 17, length: 15 },
+                Declaration {
+                    target: Target::Association { parent: "u", field: "posts" },
+                    alias: Some("p"),
+                    offset: 45,
+                    length: 7,
+                },
+            ]
+        );
+
+        assert_eq!(
+            query.paths,
+            vec![
+                FieldPath { alias: "u", path: "email", offset: 64, length: 7 },
+                FieldPath { alias: "p", path: "title", offset: 85, length: 7 },
+            ]
+        );
+    }
+
+    #[test]
+    fn test_entity_without_alias() {
+        let query = Query::parse("DELETE FROM \\App\\Entity\\User WHERE id = ?1");
+
+        assert_eq!(
+            query.declarations,
+            vec![Declaration { target: Target::Entity("App\\Entity\\User"), alias: None, offset: 12, length: 16 }]
+        );
+        assert!(query.paths.is_empty());
+    }
+
+    #[test]
+    fn test_update_and_functions() {
+        let query = Query::parse("UPDATE App\\Entity\\User u SET u.name = UPPER(u.address.city)");
+
+        assert_eq!(query.declarations.len(), 1);
+        assert_eq!(query.declarations[0].alias, Some("u"));
+        assert_eq!(query.paths.iter().map(|path| path.path).collect::<Vec<_>>(), vec!["name", "address.city"],);
+    }
+
+    #[test]
+    fn test_skips_numbers_and_partial_paths() {
+        let query = Query::parse("SELECT PARTIAL u.{id} FROM User u WHERE u.score > 1.5");
+
+        assert_eq!(query.paths.iter().map(|path| path.path).collect::<Vec<_>>(), vec!["score"]);
+    }
+}
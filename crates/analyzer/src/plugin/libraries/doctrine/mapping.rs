@@ -0,0 +1,151 @@
+//! Entity mappings, read from Doctrine's mapping attributes.
+
+use mago_codex::metadata::CodebaseMetadata;
+use mago_codex::metadata::attribute::AttributeMetadata;
+use mago_codex::metadata::class_like::ClassLikeMetadata;
+use mago_codex::metadata::property::PropertyMetadata;
+use mago_codex::ttype::atomic::TAtomic;
+use mago_codex::ttype::atomic::object::TObject;
+use mago_word::Word;
+
+const ENTITY: &[u8] = b"Doctrine\\ORM\\Mapping\\Entity";
+const EMBEDDABLE: &[u8] = b"Doctrine\\ORM\\Mapping\\Embeddable";
+
+/// Attributes mapping a property to a column.
+const COLUMNS: [&[u8]; 2] = [b"Doctrine\\ORM\\Mapping\\Column", b"Doctrine\\ORM\\Mapping\\Id"];
+
+/// Attributes mapping a property to an association with another entity.
+const ASSOCIATIONS: [&[u8]; 4] = [
+    b"Doctrine\\ORM\\Mapping\\ManyToOne",
+    b"Doctrine\\ORM\\Mapping\\OneToOne",
+    b"Doctrine\\ORM\\Mapping\\OneToMany",
+    b"Doctrine\\ORM\\Mapping\\ManyToMany",
+];
+
+const EMBEDDED: &[u8] = b"Doctrine\\ORM\\Mapping\\Embedded";
+
+/// How an entity property is mapped.
+pub(super) enum Field {
+    /// A column, mapped with `#[ORM\Column]` or `#[ORM\Id]`.
+    Column,
+    /// An association, with the entity it targets when it is known.
+    Association(Option<Word>),
+    /// An embeddable, with its class when it is known.
+    Embedded(Option<Word>),
+}
+
+/// Whether `class` is an entity mapped with attributes.
+pub(super) fn is_entity(class: &ClassLikeMetadata) -> bool {
+    find_attribute(&class.attributes, &[ENTITY]).is_some()
+}
+
+/// The repository class declared by `#[ORM\Entity(repositoryClass: ...)]`.
+pub(super) fn repository_class(class: &ClassLikeMetadata) -> Option<Word> {
+    let entity = find_attribute(&class.attributes, &[ENTITY])?;
+
+    class_argument(entity, b"repositoryClass", true)
+}
+
+/// The mapping of the `name` property of `class`, if it is a mapped field.
+pub(super) fn field(codebase: &CodebaseMetadata, class: &[u8], name: &str) -> Option<Field> {
+    let property = codebase.get_declaring_property(class, format!("${name}").as_bytes())?;
+
+    field_of(property)
+}
+
+/// Resolves a dotted path like `address.city` through the embeddables of `class`.
+///
+/// Returns the segment that is not a mapped field, if any.
+pub(super) fn unmapped_segment<'path>(
+    codebase: &CodebaseMetadata,
+    class: &[u8],
+    path: &'path str,
+) -> Option<&'path str> {
+    let mut class = class.to_vec();
+    let mut segments = path.split('.').peekable();
+    while let Some(segment) = segments.next() {
+        match field(codebase, &class, segment) {
+            None => return Some(segment),
+            Some(Field::Embedded(Some(embeddable))) if segments.peek().is_some() => {
+                let embeddable = codebase.get_class_like(embeddable.as_bytes())?;
+                // Paths into classes that are not mapped embeddables are not checked.
+                find_attribute(&embeddable.attributes, &[EMBEDDABLE])?;
+
+                class = embeddable.name.as_bytes().to_vec();
+            }
+            Some(_) => return None,
+        }
+    }
+
+    None
+}
+
+/// The mapped fields of `class`, sorted by name.
+pub(super) fn field_names(codebase: &CodebaseMetadata, class: &[u8]) -> Vec<String> {
+    let Some(class) = codebase.get_class_like(class) else {
+        return Vec::new();
+    };
+
+    let mut names: Vec<String> = class
+        .declaring_property_ids
+        .iter()
+        .filter(|(name, declaring_class)| {
+            codebase
+                .get_property(declaring_class.as_bytes(), name.as_bytes())
+                .is_some_and(|property| field_of(property).is_some())
+        })
+        .map(|(name, _)| String::from_utf8_lossy(&name.as_bytes()[1..]).into_owned())
+        .collect();
+
+    names.sort_unstable();
+    names
+}
+
+fn field_of(property: &PropertyMetadata) -> Option<Field> {
+    if find_attribute(&property.attributes, &COLUMNS).is_some() {
+        return Some(Field::Column);
+    }
+
+    if let Some(association) = find_attribute(&property.attributes, &ASSOCIATIONS) {
+        let target = class_argument(association, b"targetEntity", false).or_else(|| property_class(property));
+
+        return Some(Field::Association(target));
+    }
+
+    let embedded = find_attribute(&property.attributes, &[EMBEDDED])?;
+
+    Some(Field::Embedded(class_argument(embedded, b"class", false).or_else(|| property_class(property))))
+}
+
+fn find_attribute<'metadata>(
+    attributes: &'metadata [AttributeMetadata],
+    names: &[&[u8]],
+) -> Option<&'metadata AttributeMetadata> {
+    attributes.iter().find(|attribute| names.iter().any(|name| attribute.name.as_bytes().eq_ignore_ascii_case(name)))
+}
+
+/// The class named by an attribute argument, passed by name or, unless `named_only`, as any
+/// positional `Foo::class` argument.
+fn class_argument(attribute: &AttributeMetadata, name: &[u8], named_only: bool) -> Option<Word> {
+    attribute
+        .arguments
+        .iter()
+        .filter(|argument| match argument.name {
+            Some(argument_name) => argument_name.as_bytes() == name,
+            None => !named_only,
+        })
+        .find_map(|argument| argument.value_type.as_ref()?.get_single_class_string_value())
+}
+
+/// The class of a property declared as `private Foo $foo` or `private ?Foo $foo`.
+fn property_class(property: &PropertyMetadata) -> Option<Word> {
+    let property_type = &property.type_declaration_metadata.as_ref()?.type_union;
+    let mut classes = property_type.types.iter().filter_map(|atomic| match atomic {
+        TAtomic::Object(TObject::Named(named)) => Some(named.get_name()),
+        _ => None,
+    });
+
+    let class = classes.next()?;
+
+    classes.next().is_none().then_some(class)
+}
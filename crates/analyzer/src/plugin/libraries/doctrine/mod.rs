@@ -0,0 +1,94 @@
+//! Doctrine ORM providers.
+//!
+//! Entities are read from their mapping attributes (`#[ORM\Entity]`, `#[ORM\Column]`,
+//! `#[ORM\ManyToOne]`, ...). Entities mapped with XML or PHP files are not known to the
+//! analyzer, so they are typed but never checked.
+
+mod criteria;
+mod dql;
+mod mapping;
+mod repository;
+
+pub use criteria::RepositoryCriteriaHook;
+pub use dql::DqlHook;
+pub use repository::GetRepositoryProvider;
+pub use repository::RepositoryMethodProvider;
+
+use mago_codex::metadata::CodebaseMetadata;
+use mago_codex::ttype::atomic::TAtomic;
+use mago_codex::ttype::atomic::object::TObject;
+use mago_codex::ttype::atomic::object::named::TNamedObject;
+use mago_codex::ttype::union::TUnion;
+use mago_word::Word;
+use mago_word::ascii_lowercase_word;
+
+use crate::plugin::Plugin;
+use crate::plugin::PluginMeta;
+use crate::plugin::PluginRegistry;
+
+const ENTITY_REPOSITORY: &[u8] = b"Doctrine\\ORM\\EntityRepository";
+const OBJECT_REPOSITORY: &[u8] = b"Doctrine\\Persistence\\ObjectRepository";
+const ENTITY_MANAGER: &[u8] = b"Doctrine\\ORM\\EntityManagerInterface";
+
+/// Plugin providing type inference and checks for Doctrine ORM entities and repositories.
+pub struct DoctrinePlugin;
+
+static META: PluginMeta = PluginMeta::new(
+    "doctrine",
+    "Doctrine",
+    "Types Doctrine repositories and checks criteria and DQL against entity mappings",
+    &["doctrine-orm"],
+    false,
+);
+
+impl Plugin for DoctrinePlugin {
+    fn meta(&self) -> &'static PluginMeta {
+        &META
+    }
+
+    fn register(&self, registry: &mut PluginRegistry) {
+        registry.register_method_provider(GetRepositoryProvider);
+        registry.register_method_provider(RepositoryMethodProvider);
+        registry.register_method_call_hook(RepositoryCriteriaHook);
+        registry.register_method_call_hook(DqlHook);
+    }
+}
+
+/// The entity managed by a repository: `Foo` for `EntityRepository<Foo>`, or for a repository
+/// class that extends `ServiceEntityRepository<Foo>`.
+fn repository_entity(codebase: &CodebaseMetadata, repository: &TNamedObject) -> Option<Word> {
+    let name = repository.get_name();
+    let entity_type = if name.as_bytes().eq_ignore_ascii_case(ENTITY_REPOSITORY)
+        || name.as_bytes().eq_ignore_ascii_case(OBJECT_REPOSITORY)
+    {
+        repository.get_type_parameters()?.first()?
+    } else {
+        let extended_parameters = &codebase.get_class_like(name.as_bytes())?.template_extended_parameters;
+
+        [ENTITY_REPOSITORY, OBJECT_REPOSITORY]
+            .into_iter()
+            .find_map(|repository| extended_parameters.get(&ascii_lowercase_word(repository))?.values().next())?
+    };
+
+    entity_type.get_single_named_object().map(TNamedObject::get_name)
+}
+
+/// The repositories among the objects of `object_type`.
+fn repositories<'ty>(codebase: &CodebaseMetadata, object_type: &'ty TUnion) -> impl Iterator<Item = &'ty TNamedObject> {
+    object_type.types.iter().filter_map(move |atomic| match atomic {
+        TAtomic::Object(TObject::Named(named)) if is_repository(codebase, named.get_name().as_bytes()) => Some(named),
+        _ => None,
+    })
+}
+
+/// Whether `class` is a Doctrine repository.
+fn is_repository(codebase: &CodebaseMetadata, class: &[u8]) -> bool {
+    codebase.is_instance_of(class, OBJECT_REPOSITORY) || codebase.is_instance_of(class, ENTITY_REPOSITORY)
+}
+
+/// Whether `method` is one of Doctrine's own methods, rather than one the project overrides.
+fn is_doctrine_method(codebase: &CodebaseMetadata, class: &[u8], method: &[u8]) -> bool {
+    codebase
+        .get_declaring_method_class(class, method)
+        .is_none_or(|declaring_class| mago_word::starts_with_ignore_case(declaring_class.as_bytes(), b"Doctrine\\"))
+}
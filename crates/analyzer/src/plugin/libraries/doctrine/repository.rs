@@ -0,0 +1,144 @@
+//! Doctrine repository return type providers.
+
+use mago_codex::ttype::atomic::TAtomic;
+use mago_codex::ttype::atomic::object::TObject;
+use mago_codex::ttype::atomic::object::named::TNamedObject;
+use mago_codex::ttype::expander::StaticClassType;
+use mago_codex::ttype::get_list;
+use mago_codex::ttype::get_non_negative_int;
+use mago_codex::ttype::union::TUnion;
+use mago_word::Word;
+use mago_word::word;
+
+use crate::plugin::context::InvocationInfo;
+use crate::plugin::context::ProviderContext;
+use crate::plugin::libraries::doctrine::ENTITY_REPOSITORY;
+use crate::plugin::libraries::doctrine::is_doctrine_method;
+use crate::plugin::libraries::doctrine::is_repository;
+use crate::plugin::libraries::doctrine::mapping::repository_class;
+use crate::plugin::libraries::doctrine::repository_entity;
+use crate::plugin::provider::Provider;
+use crate::plugin::provider::ProviderMeta;
+use crate::plugin::provider::method::MethodReturnTypeProvider;
+use crate::plugin::provider::method::MethodTarget;
+
+/// Classes whose `getRepository()` method takes an entity class and returns its repository.
+const REPOSITORY_FACTORIES: [&[u8]; 2] =
+    [b"Doctrine\\Persistence\\ObjectManager", b"Doctrine\\Persistence\\ManagerRegistry"];
+
+static GET_REPOSITORY_META: ProviderMeta = ProviderMeta::new(
+    "doctrine::get-repository",
+    "getRepository",
+    "Returns the repository of the entity class argument",
+);
+
+static GET_REPOSITORY_TARGETS: [MethodTarget; 1] = [MethodTarget::any_class(b"getRepository")];
+
+static REPOSITORY_META: ProviderMeta = ProviderMeta::new(
+    "doctrine::repository",
+    "Repository finders",
+    "Returns the entities found by find(), findOneBy(), findBy(), and findAll()",
+);
+
+static REPOSITORY_TARGETS: [MethodTarget; 5] = [
+    MethodTarget::any_class(b"find"),
+    MethodTarget::any_class(b"findOneBy"),
+    MethodTarget::any_class(b"findBy"),
+    MethodTarget::any_class(b"findAll"),
+    MethodTarget::any_class(b"count"),
+];
+
+/// Provider for `EntityManagerInterface::getRepository()` and `ManagerRegistry::getRepository()`.
+///
+/// `getRepository(User::class)` returns the `repositoryClass` declared by the entity's
+/// `#[ORM\Entity]` attribute, or `EntityRepository<User>` when it does not declare one.
+#[derive(Default)]
+pub struct GetRepositoryProvider;
+
+impl Provider for GetRepositoryProvider {
+    fn meta() -> &'static ProviderMeta {
+        &GET_REPOSITORY_META
+    }
+}
+
+impl MethodReturnTypeProvider for GetRepositoryProvider {
+    fn targets() -> &'static [MethodTarget] {
+        &GET_REPOSITORY_TARGETS
+    }
+
+    fn get_return_type(
+        &self,
+        context: &ProviderContext<'_, '_, '_>,
+        class_name: &[u8],
+        _method_name: &[u8],
+        invocation: &InvocationInfo<'_, '_, '_>,
+    ) -> Option<TUnion> {
+        if !REPOSITORY_FACTORIES.iter().any(|factory| context.is_instance_of(class_name, factory)) {
+            return None;
+        }
+
+        let entity_argument = invocation.get_argument(0, &[b"className", b"persistentObject", b"entityName"])?;
+        let entity = context.get_expression_type(entity_argument)?.get_single_class_string_value()?;
+        let entity_metadata = context.get_class_like(entity)?;
+
+        let repository = match repository_class(entity_metadata) {
+            Some(repository) => TNamedObject::new(repository),
+            None => TNamedObject::new(word(ENTITY_REPOSITORY))
+                .with_type_parameters(Some(vec![entity_object(entity_metadata.original_name)])),
+        };
+
+        Some(TUnion::from_atomic(TAtomic::Object(TObject::Named(repository))))
+    }
+}
+
+/// Provider for the finder methods of Doctrine repositories.
+///
+/// On a repository of `User`, `find()` and `findOneBy()` return `User|null`, `findBy()` and
+/// `findAll()` return `list<User>`, and `count()` returns `int<0, max>`.
+#[derive(Default)]
+pub struct RepositoryMethodProvider;
+
+impl Provider for RepositoryMethodProvider {
+    fn meta() -> &'static ProviderMeta {
+        &REPOSITORY_META
+    }
+}
+
+impl MethodReturnTypeProvider for RepositoryMethodProvider {
+    fn targets() -> &'static [MethodTarget] {
+        &REPOSITORY_TARGETS
+    }
+
+    fn get_return_type(
+        &self,
+        context: &ProviderContext<'_, '_, '_>,
+        _class_name: &[u8],
+        method_name: &[u8],
+        invocation: &InvocationInfo<'_, '_, '_>,
+    ) -> Option<TUnion> {
+        let method_context = invocation.inner().target.get_method_context()?;
+        let StaticClassType::Object(TObject::Named(repository)) = &method_context.class_type else {
+            return None;
+        };
+
+        let codebase = context.codebase();
+        if !is_repository(codebase, repository.get_name().as_bytes())
+            || !is_doctrine_method(codebase, repository.get_name().as_bytes(), method_name)
+        {
+            return None;
+        }
+
+        let entity = entity_object(repository_entity(codebase, repository)?);
+
+        Some(match method_name.to_ascii_lowercase().as_slice() {
+            b"find" | b"findoneby" => entity.as_nullable(),
+            b"findby" | b"findall" => get_list(entity),
+            b"count" => get_non_negative_int(),
+            _ => return None,
+        })
+    }
+}
+
+fn entity_object(entity: Word) -> TUnion {
+    TUnion::from_atomic(TAtomic::Object(TObject::Named(TNamedObject::new(entity))))
+}
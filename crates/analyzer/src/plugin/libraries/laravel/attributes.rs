@@ -0,0 +1,247 @@
+//! Eloquent model attribute types.
+
+use mago_codex::metadata::CodebaseMetadata;
+use mago_codex::metadata::class_like::ClassLikeMetadata;
+use mago_codex::ttype::atomic::TAtomic;
+use mago_codex::ttype::atomic::array::TArray;
+use mago_codex::ttype::atomic::array::key::ArrayKey;
+use mago_codex::ttype::get_bool;
+use mago_codex::ttype::get_float;
+use mago_codex::ttype::get_int;
+use mago_codex::ttype::get_mixed;
+use mago_codex::ttype::get_numeric_string;
+use mago_codex::ttype::get_string;
+use mago_codex::ttype::union::TUnion;
+use mago_word::word;
+
+use crate::plugin::libraries::laravel::MODEL;
+use crate::plugin::libraries::laravel::RELATION;
+use crate::plugin::libraries::laravel::casts::Cast;
+use crate::plugin::libraries::laravel::casts::resolve_cast;
+use crate::plugin::libraries::laravel::collection_of;
+use crate::plugin::libraries::laravel::model_object;
+use crate::plugin::libraries::laravel::model_parameter;
+use crate::plugin::libraries::laravel::named_object;
+use crate::plugin::provider::Provider;
+use crate::plugin::provider::ProviderMeta;
+use crate::plugin::provider::property::PropertyTypeProvider;
+use crate::schema::Column;
+use crate::schema::ColumnKind;
+use crate::schema::DatabaseSchema;
+use crate::schema::snake_case;
+
+static META: ProviderMeta = ProviderMeta::new(
+    "laravel::eloquent::attributes",
+    "Eloquent model attributes",
+    "Types model attributes from casts, accessors, relations, and the migrations' columns",
+);
+
+const ATTRIBUTE: &[u8] = b"Illuminate\\Database\\Eloquent\\Casts\\Attribute";
+const CARBON: &[u8] = b"Illuminate\\Support\\Carbon";
+
+/// Relations that load a collection of models rather than a single one.
+const TO_MANY_RELATIONS: [&[u8]; 5] = [
+    b"Illuminate\\Database\\Eloquent\\Relations\\HasMany",
+    b"Illuminate\\Database\\Eloquent\\Relations\\HasManyThrough",
+    b"Illuminate\\Database\\Eloquent\\Relations\\BelongsToMany",
+    b"Illuminate\\Database\\Eloquent\\Relations\\MorphMany",
+    b"Illuminate\\Database\\Eloquent\\Relations\\MorphToMany",
+];
+
+/// Provider for the attributes of Eloquent models.
+///
+/// An attribute that the model does not declare is typed, in order of precedence, from:
+///
+/// 1. its entry in the model's `$casts` property,
+/// 2. an accessor, either a `getFooAttribute()` method or a `foo(): Attribute` method,
+/// 3. a relation method of the same name, which Eloquent loads when the attribute is read,
+/// 4. the column of the model's table declared by the project's migrations.
+#[derive(Default)]
+pub struct ModelAttributeProvider;
+
+impl Provider for ModelAttributeProvider {
+    fn meta() -> &'static ProviderMeta {
+        &META
+    }
+}
+
+impl PropertyTypeProvider for ModelAttributeProvider {
+    fn get_property_type(
+        &self,
+        codebase: &CodebaseMetadata,
+        schema: Option<&DatabaseSchema>,
+        class_metadata: &ClassLikeMetadata,
+        property_name: &[u8],
+        for_assignment: bool,
+    ) -> Option<TUnion> {
+        let class = class_metadata.name.as_bytes();
+        if !codebase.class_extends(class, MODEL) {
+            return None;
+        }
+
+        let variable = [b"$", property_name].concat();
+        if codebase.get_declaring_property(class, &variable).is_some()
+            || codebase.get_declaring_magic_property(class, &variable).is_some()
+        {
+            return None;
+        }
+
+        let attribute = std::str::from_utf8(property_name).ok()?;
+        let column = schema.and_then(|schema| schema.table(&table_name(codebase, class_metadata))?.column(attribute));
+
+        if let Some(cast) = cast_of(codebase, class, property_name) {
+            // Eloquent accepts anything it can serialize into a cast attribute.
+            if for_assignment {
+                return Some(get_mixed());
+            }
+
+            return match resolve_cast(codebase, &cast) {
+                Cast::Typed(cast_type) if column.is_some_and(|column| column.nullable) => Some(cast_type.as_nullable()),
+                Cast::Typed(cast_type) => Some(cast_type),
+                Cast::Untyped | Cast::Unknown => Some(get_mixed()),
+            };
+        }
+
+        if !for_assignment {
+            if let Some(accessor_type) = accessor_type(codebase, class, attribute) {
+                return Some(accessor_type);
+            }
+
+            if let Some(relation_type) = relation_type(codebase, class, attribute) {
+                return Some(relation_type);
+            }
+        }
+
+        column.map(|column| column_type(attribute, column))
+    }
+}
+
+/// The table a model maps to: its `$table` property, or the plural snake case of its short name.
+fn table_name(codebase: &CodebaseMetadata, class_metadata: &ClassLikeMetadata) -> String {
+    if let Some(table) = codebase
+        .get_declaring_property(class_metadata.name.as_bytes(), b"$table")
+        .and_then(|property| property.default_type_metadata.as_ref())
+        .and_then(|default| default.type_union.get_single_literal_string_value())
+    {
+        return String::from_utf8_lossy(table).into_owned();
+    }
+
+    let name = class_metadata.original_name.as_bytes();
+    let short_name = name.rsplit(|byte| *byte == b'\\').next().unwrap_or(name);
+
+    plural(&snake_case(&String::from_utf8_lossy(short_name)))
+}
+
+/// English pluralization, covering the regular forms Laravel's pluralizer produces for table names.
+fn plural(word: &str) -> String {
+    if let Some(stem) = word.strip_suffix('y')
+        && !stem.ends_with(['a', 'e', 'i', 'o', 'u'])
+    {
+        return format!("{stem}ies");
+    }
+
+    if word.ends_with(['s', 'x', 'z']) || word.ends_with("ch") || word.ends_with("sh") {
+        return format!("{word}es");
+    }
+
+    format!("{word}s")
+}
+
+/// The cast declared for `attribute` by the default value of the model's `$casts` property.
+fn cast_of(codebase: &CodebaseMetadata, class: &[u8], attribute: &[u8]) -> Option<Vec<u8>> {
+    let casts = codebase.get_declaring_property(class, b"$casts")?.default_type_metadata.as_ref()?;
+    let casts = &casts.type_union;
+    let (true, TAtomic::Array(TArray::Keyed(casts))) = (casts.is_single(), casts.get_single()) else {
+        return None;
+    };
+
+    let (_, cast) = casts.known_items.as_ref()?.iter().find_map(|(key, value)| match key {
+        ArrayKey::String(key) if key.as_bytes() == attribute => Some(value),
+        _ => None,
+    })?;
+
+    if let Some(class) = cast.get_single_class_string_value() {
+        return Some(class.as_bytes().to_vec());
+    }
+
+    cast.get_single_literal_string_value().map(<[u8]>::to_vec)
+}
+
+/// The type returned by the accessor of `attribute`, if the model declares one.
+fn accessor_type(codebase: &CodebaseMetadata, class: &[u8], attribute: &str) -> Option<TUnion> {
+    let studly = studly_case(attribute);
+    if let Some(accessor) = codebase.get_declaring_method(class, format!("get{studly}Attribute").as_bytes()) {
+        return Some(accessor.return_type_metadata.as_ref().map_or_else(get_mixed, |r| r.type_union.clone()));
+    }
+
+    let return_type = &codebase.get_declaring_method(class, studly.as_bytes())?.return_type_metadata;
+    let attribute_type = return_type.as_ref()?.type_union.get_single_named_object()?;
+    if !attribute_type.get_name().as_bytes().eq_ignore_ascii_case(ATTRIBUTE) {
+        return None;
+    }
+
+    Some(attribute_type.get_type_parameters().and_then(<[TUnion]>::first).cloned().unwrap_or_else(get_mixed))
+}
+
+/// The type Eloquent loads for `attribute` when it names one of the model's relation methods.
+fn relation_type(codebase: &CodebaseMetadata, class: &[u8], attribute: &str) -> Option<TUnion> {
+    let method = codebase.get_declaring_method(class, attribute.as_bytes())?;
+    let relation = method.return_type_metadata.as_ref()?.type_union.get_single_named_object()?;
+    let relation_name = relation.get_name();
+    if !relation_name.as_bytes().starts_with(b"Illuminate\\Database\\Eloquent\\Relations\\")
+        && !codebase.class_extends(relation_name.as_bytes(), RELATION)
+    {
+        return None;
+    }
+
+    let related = relation
+        .get_type_parameters()
+        .and_then(model_parameter)
+        .map_or_else(|| named_object(MODEL, None), model_object);
+
+    let is_to_many = TO_MANY_RELATIONS.iter().any(|to_many| {
+        relation_name.as_bytes().eq_ignore_ascii_case(to_many)
+            || codebase.is_instance_of(relation_name.as_bytes(), to_many)
+    });
+
+    if is_to_many {
+        return Some(collection_of(related.get_single_named_object().map_or_else(|| word(MODEL), |r| r.get_name())));
+    }
+
+    Some(related.as_nullable())
+}
+
+/// The type Eloquent reads a column as, when the model does not cast it.
+fn column_type(attribute: &str, column: &Column) -> TUnion {
+    let column_type = match column.kind {
+        ColumnKind::Integer => get_int(),
+        ColumnKind::Float => get_float(),
+        ColumnKind::Decimal => get_numeric_string(),
+        ColumnKind::Boolean => get_bool(),
+        ColumnKind::DateTime if matches!(attribute, "created_at" | "updated_at" | "deleted_at") => {
+            named_object(CARBON, None)
+        }
+        ColumnKind::String
+        | ColumnKind::Json
+        | ColumnKind::Binary
+        | ColumnKind::Date
+        | ColumnKind::DateTime
+        | ColumnKind::Time => get_string(),
+    };
+
+    if column.nullable { column_type.as_nullable() } else { column_type }
+}
+
+/// Converts a `snake_case` attribute name to `StudlyCase`, the way Laravel names accessors.
+fn studly_case(attribute: &str) -> String {
+    let mut studly = String::with_capacity(attribute.len());
+    for segment in attribute.split(['_', '-']) {
+        let mut characters = segment.chars();
+        if let Some(first) = characters.next() {
+            studly.push(first.to_ascii_uppercase());
+            studly.push_str(characters.as_str());
+        }
+    }
+
+    studly
+}
@@ -0,0 +1,218 @@
+//! Eloquent attribute casts.
+//!
+//! A model's `$casts` property maps attributes to the cast Eloquent applies when they are read:
+//! either a built-in cast name like `datetime` or `decimal:2`, or the name of an enum or a
+//! custom cast class. [`resolve_cast`] turns a cast into the type of the attribute, and
+//! [`ModelCastsHook`] reports the casts Eloquent does not know.
+
+use mago_codex::metadata::CodebaseMetadata;
+use mago_codex::metadata::class_like::ClassLikeMetadata;
+use mago_codex::ttype::get_arraykey;
+use mago_codex::ttype::get_bool;
+use mago_codex::ttype::get_float;
+use mago_codex::ttype::get_int;
+use mago_codex::ttype::get_mixed;
+use mago_codex::ttype::get_mixed_keyed_array;
+use mago_codex::ttype::get_numeric_string;
+use mago_codex::ttype::get_string;
+use mago_codex::ttype::union::TUnion;
+use mago_reporting::Annotation;
+use mago_reporting::Issue;
+use mago_span::HasSpan;
+use mago_syntax::cst::ArrayElement;
+use mago_syntax::cst::Class;
+use mago_syntax::cst::ClassLikeMember;
+use mago_syntax::cst::Expression;
+use mago_syntax::cst::Literal;
+use mago_syntax::cst::Property;
+use mago_syntax::cst::PropertyItem;
+
+use crate::code::IssueCode;
+use crate::plugin::context::HookContext;
+use crate::plugin::hook::ClassDeclarationHook;
+use crate::plugin::hook::HookResult;
+use crate::plugin::libraries::laravel::MODEL;
+use crate::plugin::libraries::laravel::SUPPORT_COLLECTION;
+use crate::plugin::libraries::laravel::named_object;
+use crate::plugin::provider::Provider;
+use crate::plugin::provider::ProviderMeta;
+
+static META: ProviderMeta =
+    ProviderMeta::new("laravel::eloquent::casts", "Eloquent casts", "Reports model casts that Eloquent does not know");
+
+const CARBON: &[u8] = b"Illuminate\\Support\\Carbon";
+const CARBON_IMMUTABLE: &[u8] = b"Carbon\\CarbonImmutable";
+const ARRAY_OBJECT: &[u8] = b"Illuminate\\Database\\Eloquent\\Casts\\ArrayObject";
+const STRINGABLE: &[u8] = b"Illuminate\\Support\\Stringable";
+const CASTS_ATTRIBUTES: &[u8] = b"Illuminate\\Contracts\\Database\\Eloquent\\CastsAttributes";
+
+/// What a cast makes of the attribute it is applied to.
+pub(super) enum Cast {
+    /// The attribute is read as this type.
+    Typed(TUnion),
+    /// The cast exists, but the type it produces is not known.
+    Untyped,
+    /// Eloquent does not know the cast.
+    Unknown,
+}
+
+/// Resolves a cast, as written in `$casts`, to the type of the attribute it is applied to.
+pub(super) fn resolve_cast(codebase: &CodebaseMetadata, cast: &[u8]) -> Cast {
+    let (name, arguments) = match cast.iter().position(|byte| *byte == b':') {
+        Some(position) => (&cast[..position], Some(&cast[position + 1..])),
+        None => (cast, None),
+    };
+
+    let cast_type = match name.to_ascii_lowercase().as_slice() {
+        b"int" | b"integer" | b"timestamp" => get_int(),
+        b"real" | b"float" | b"double" => get_float(),
+        b"decimal" => get_numeric_string(),
+        b"string" | b"hashed" => get_string(),
+        b"bool" | b"boolean" => get_bool(),
+        b"object" => named_object(b"stdClass", None),
+        b"array" | b"json" => get_mixed_keyed_array(),
+        b"collection" => collection(get_arraykey(), get_mixed()),
+        b"date" | b"datetime" | b"custom_datetime" => named_object(CARBON, None),
+        b"immutable_date" | b"immutable_datetime" | b"immutable_custom_datetime" => {
+            named_object(CARBON_IMMUTABLE, None)
+        }
+        b"encrypted" => match arguments.map(<[u8]>::to_ascii_lowercase).as_deref() {
+            None => get_string(),
+            Some(b"array" | b"json") => get_mixed_keyed_array(),
+            Some(b"collection") => collection(get_arraykey(), get_mixed()),
+            Some(b"object") => named_object(b"stdClass", None),
+            Some(_) => return Cast::Unknown,
+        },
+        _ => return resolve_class_cast(codebase, name, arguments),
+    };
+
+    Cast::Typed(cast_type)
+}
+
+/// Resolves a cast naming a class: an enum, one of Eloquent's castables, or a custom cast.
+fn resolve_class_cast(codebase: &CodebaseMetadata, name: &[u8], arguments: Option<&[u8]>) -> Cast {
+    let Some(class) = codebase.get_class_like(name) else {
+        return Cast::Unknown;
+    };
+
+    if class.kind.is_enum() {
+        return Cast::Typed(named_object(class.original_name.as_bytes(), None));
+    }
+
+    let enum_argument = || {
+        let enum_class = codebase.get_class_like(arguments?)?;
+
+        enum_class.kind.is_enum().then(|| named_object(enum_class.original_name.as_bytes(), None))
+    };
+
+    let cast_type = match class.original_name.as_bytes() {
+        b"Illuminate\\Database\\Eloquent\\Casts\\AsCollection"
+        | b"Illuminate\\Database\\Eloquent\\Casts\\AsEncryptedCollection" => collection(get_arraykey(), get_mixed()),
+        b"Illuminate\\Database\\Eloquent\\Casts\\AsArrayObject"
+        | b"Illuminate\\Database\\Eloquent\\Casts\\AsEncryptedArrayObject" => {
+            named_object(ARRAY_OBJECT, Some(vec![get_arraykey(), get_mixed()]))
+        }
+        b"Illuminate\\Database\\Eloquent\\Casts\\AsEnumCollection" => match enum_argument() {
+            Some(enum_type) => collection(get_int(), enum_type),
+            None => return Cast::Untyped,
+        },
+        b"Illuminate\\Database\\Eloquent\\Casts\\AsEnumArrayObject" => match enum_argument() {
+            Some(enum_type) => named_object(ARRAY_OBJECT, Some(vec![get_int(), enum_type])),
+            None => return Cast::Untyped,
+        },
+        b"Illuminate\\Database\\Eloquent\\Casts\\AsStringable" => named_object(STRINGABLE, None),
+        _ => return resolve_custom_cast(codebase, class),
+    };
+
+    Cast::Typed(cast_type)
+}
+
+/// A custom cast is read as whatever its `get()` method returns; any other class is read as itself.
+fn resolve_custom_cast(codebase: &CodebaseMetadata, class: &ClassLikeMetadata) -> Cast {
+    if !codebase.class_implements(class.name.as_bytes(), CASTS_ATTRIBUTES) {
+        return Cast::Typed(named_object(class.original_name.as_bytes(), None));
+    }
+
+    codebase
+        .get_declaring_method(class.name.as_bytes(), b"get")
+        .and_then(|method| method.return_type_metadata.as_ref())
+        .map_or(Cast::Untyped, |return_type| Cast::Typed(return_type.type_union.clone()))
+}
+
+fn collection(key: TUnion, value: TUnion) -> TUnion {
+    named_object(SUPPORT_COLLECTION, Some(vec![key, value]))
+}
+
+/// Reports the entries of a model's `$casts` property that Eloquent does not know.
+#[derive(Default)]
+pub struct ModelCastsHook;
+
+impl Provider for ModelCastsHook {
+    fn meta() -> &'static ProviderMeta {
+        &META
+    }
+}
+
+impl ClassDeclarationHook for ModelCastsHook {
+    fn on_enter_class(
+        &self,
+        class: &Class<'_>,
+        metadata: &ClassLikeMetadata,
+        context: &mut HookContext<'_, '_>,
+    ) -> HookResult<()> {
+        if !context.codebase().class_extends(metadata.name.as_bytes(), MODEL) {
+            return Ok(());
+        }
+
+        for member in class.members.iter() {
+            let ClassLikeMember::Property(Property::Plain(property)) = member else {
+                continue;
+            };
+
+            for item in property.items.iter() {
+                let PropertyItem::Concrete(item) = item else {
+                    continue;
+                };
+
+                if item.variable.name != b"$casts" {
+                    continue;
+                }
+
+                if let Expression::Array(array) = item.value {
+                    for element in array.elements.iter() {
+                        if let ArrayElement::KeyValue(element) = element {
+                            check_cast(context, element.value);
+                        }
+                    }
+                }
+            }
+        }
+
+        Ok(())
+    }
+}
+
+fn check_cast(context: &HookContext<'_, '_>, value: &Expression<'_>) {
+    let Expression::Literal(Literal::String(string)) = value else {
+        return;
+    };
+
+    let Some(cast) = string.value else {
+        return;
+    };
+
+    if !matches!(resolve_cast(context.codebase(), cast), Cast::Unknown) {
+        return;
+    }
+
+    let cast = String::from_utf8_lossy(cast);
+    context.report(
+        IssueCode::InvalidModelCast,
+        Issue::error(format!("Unknown Eloquent cast `{cast}`."))
+            .with_annotation(Annotation::primary(value.span()).with_message("This cast is not known to Eloquent"))
+            .with_note(
+                "Casts are either one of Eloquent's built-in casts, like `integer`, `datetime`, or `decimal:2`, or the name of an enum or a cast class.",
+            )
+            .with_help("Fix the name of the cast, or make sure the cast class exists."),
+    );
+}
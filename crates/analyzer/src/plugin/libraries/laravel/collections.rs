@@ -0,0 +1,141 @@
+//! Laravel collection return type provider.
+
+use mago_codex::ttype::atomic::TAtomic;
+use mago_codex::ttype::get_int;
+use mago_codex::ttype::get_keyed_array;
+use mago_codex::ttype::union::TUnion;
+
+use crate::plugin::context::InvocationInfo;
+use crate::plugin::context::ProviderContext;
+use crate::plugin::libraries::laravel::ELOQUENT_COLLECTION;
+use crate::plugin::libraries::laravel::MODEL;
+use crate::plugin::libraries::laravel::SUPPORT_COLLECTION;
+use crate::plugin::libraries::laravel::is_framework_method;
+use crate::plugin::libraries::laravel::named_object;
+use crate::plugin::libraries::laravel::receiver;
+use crate::plugin::provider::Provider;
+use crate::plugin::provider::ProviderMeta;
+use crate::plugin::provider::method::MethodReturnTypeProvider;
+use crate::plugin::provider::method::MethodTarget;
+
+static META: ProviderMeta = ProviderMeta::new(
+    "laravel::collections",
+    "Laravel collections",
+    "Tracks the key and value types of collections through map, filter, and friends",
+);
+
+static TARGETS: [MethodTarget; 8] = [
+    MethodTarget::any_class(b"map"),
+    MethodTarget::any_class(b"filter"),
+    MethodTarget::any_class(b"reject"),
+    MethodTarget::any_class(b"first"),
+    MethodTarget::any_class(b"last"),
+    MethodTarget::any_class(b"values"),
+    MethodTarget::any_class(b"keys"),
+    MethodTarget::any_class(b"all"),
+];
+
+/// Provider for `Illuminate\Support\Collection` and its subclasses.
+///
+/// `map()` returns a collection of whatever its callback returns; mapping an Eloquent collection
+/// to models keeps it an Eloquent collection, as Eloquent does at runtime, while mapping it to
+/// anything else gives a `Illuminate\Support\Collection`.
+#[derive(Default)]
+pub struct CollectionProvider;
+
+impl Provider for CollectionProvider {
+    fn meta() -> &'static ProviderMeta {
+        &META
+    }
+}
+
+impl MethodReturnTypeProvider for CollectionProvider {
+    fn targets() -> &'static [MethodTarget] {
+        &TARGETS
+    }
+
+    fn get_return_type(
+        &self,
+        context: &ProviderContext<'_, '_, '_>,
+        _class_name: &[u8],
+        method_name: &[u8],
+        invocation: &InvocationInfo<'_, '_, '_>,
+    ) -> Option<TUnion> {
+        let (class, type_parameters) = receiver(invocation)?;
+        let [key_type, value_type] = type_parameters else {
+            return None;
+        };
+
+        if !context.is_instance_of(class.as_bytes(), SUPPORT_COLLECTION)
+            || !is_framework_method(context.codebase(), class.as_bytes(), method_name)
+        {
+            return None;
+        }
+
+        let same_collection =
+            |key_type: TUnion, value_type: TUnion| named_object(class.as_bytes(), Some(vec![key_type, value_type]));
+
+        Some(match method_name.to_ascii_lowercase().as_slice() {
+            b"map" => {
+                let mapped_type = callback_return_type(context, invocation)?;
+                let maps_to_models = !mapped_type.types.is_empty()
+                    && mapped_type.types.iter().all(|atomic| match atomic {
+                        TAtomic::Object(object) => {
+                            object.get_name().is_some_and(|name| context.is_instance_of(name.as_bytes(), MODEL))
+                        }
+                        _ => false,
+                    });
+
+                if maps_to_models && context.is_instance_of(class.as_bytes(), ELOQUENT_COLLECTION) {
+                    same_collection(key_type.clone(), mapped_type)
+                } else {
+                    named_object(SUPPORT_COLLECTION, Some(vec![key_type.clone(), mapped_type]))
+                }
+            }
+            b"filter" if invocation.has_no_arguments() => {
+                let truthy_types: Vec<TAtomic> = value_type
+                    .types
+                    .iter()
+                    .filter(|atomic| {
+                        !atomic.is_null() && !matches!(atomic, TAtomic::Scalar(scalar) if scalar.is_false())
+                    })
+                    .cloned()
+                    .collect();
+
+                if truthy_types.is_empty() {
+                    same_collection(key_type.clone(), value_type.clone())
+                } else {
+                    same_collection(key_type.clone(), TUnion::from_vec(truthy_types))
+                }
+            }
+            b"filter" | b"reject" => same_collection(key_type.clone(), value_type.clone()),
+            b"first" | b"last" => value_type.clone().as_nullable(),
+            b"values" => same_collection(get_int(), value_type.clone()),
+            b"keys" => named_object(SUPPORT_COLLECTION, Some(vec![get_int(), key_type.clone()])),
+            b"all" => get_keyed_array(key_type.clone(), value_type.clone()),
+            _ => return None,
+        })
+    }
+}
+
+/// The declared return type of the callback passed to `map()`, or the one inferred from its body.
+fn callback_return_type(
+    context: &ProviderContext<'_, '_, '_>,
+    invocation: &InvocationInfo<'_, '_, '_>,
+) -> Option<TUnion> {
+    let callback = invocation.get_argument(0, &[b"callback"])?;
+    if let Some(return_type) =
+        context.get_callable_metadata(callback).and_then(|metadata| metadata.return_type_metadata.as_ref())
+    {
+        return Some(return_type.type_union.clone());
+    }
+
+    let callback_type = context.get_expression_type(callback)?;
+    if callback_type.is_single()
+        && let TAtomic::Callable(callable) = callback_type.get_single()
+    {
+        return callable.get_signature()?.get_return_type().cloned();
+    }
+
+    None
+}
@@ -0,0 +1,110 @@
+//! Laravel facade return type provider.
+
+use mago_codex::ttype::expander::StaticClassType;
+use mago_codex::ttype::expander::TypeExpansionOptions;
+use mago_codex::ttype::expander::expand_union;
+use mago_codex::ttype::union::TUnion;
+use mago_word::word;
+
+use crate::plugin::context::InvocationInfo;
+use crate::plugin::context::ProviderContext;
+use crate::plugin::provider::Provider;
+use crate::plugin::provider::ProviderMeta;
+use crate::plugin::provider::method::MethodReturnTypeProvider;
+use crate::plugin::provider::method::MethodTarget;
+
+static META: ProviderMeta = ProviderMeta::new(
+    "laravel::facades",
+    "Laravel facades",
+    "Returns the type returned by the service behind the facade",
+);
+
+static TARGETS: [MethodTarget; 1] = [MethodTarget::all_methods(b"Illuminate\\Support\\Facades\\*")];
+
+/// The service each of Laravel's facades resolves to.
+const FACADE_SERVICES: [(&[u8], &[u8]); 32] = [
+    (b"App", b"Illuminate\\Foundation\\Application"),
+    (b"Artisan", b"Illuminate\\Contracts\\Console\\Kernel"),
+    (b"Auth", b"Illuminate\\Auth\\AuthManager"),
+    (b"Blade", b"Illuminate\\View\\Compilers\\BladeCompiler"),
+    (b"Bus", b"Illuminate\\Contracts\\Bus\\Dispatcher"),
+    (b"Cache", b"Illuminate\\Cache\\CacheManager"),
+    (b"Config", b"Illuminate\\Config\\Repository"),
+    (b"Cookie", b"Illuminate\\Cookie\\CookieJar"),
+    (b"Crypt", b"Illuminate\\Encryption\\Encrypter"),
+    (b"DB", b"Illuminate\\Database\\DatabaseManager"),
+    (b"Event", b"Illuminate\\Events\\Dispatcher"),
+    (b"File", b"Illuminate\\Filesystem\\Filesystem"),
+    (b"Gate", b"Illuminate\\Contracts\\Auth\\Access\\Gate"),
+    (b"Hash", b"Illuminate\\Hashing\\HashManager"),
+    (b"Http", b"Illuminate\\Http\\Client\\Factory"),
+    (b"Lang", b"Illuminate\\Translation\\Translator"),
+    (b"Log", b"Illuminate\\Log\\LogManager"),
+    (b"Mail", b"Illuminate\\Mail\\MailManager"),
+    (b"Notification", b"Illuminate\\Notifications\\ChannelManager"),
+    (b"Password", b"Illuminate\\Auth\\Passwords\\PasswordBrokerManager"),
+    (b"Queue", b"Illuminate\\Queue\\QueueManager"),
+    (b"RateLimiter", b"Illuminate\\Cache\\RateLimiter"),
+    (b"Redirect", b"Illuminate\\Routing\\Redirector"),
+    (b"Request", b"Illuminate\\Http\\Request"),
+    (b"Response", b"Illuminate\\Contracts\\Routing\\ResponseFactory"),
+    (b"Route", b"Illuminate\\Routing\\Router"),
+    (b"Schema", b"Illuminate\\Database\\Schema\\Builder"),
+    (b"Session", b"Illuminate\\Session\\SessionManager"),
+    (b"Storage", b"Illuminate\\Filesystem\\FilesystemManager"),
+    (b"URL", b"Illuminate\\Routing\\UrlGenerator"),
+    (b"Validator", b"Illuminate\\Validation\\Factory"),
+    (b"View", b"Illuminate\\View\\Factory"),
+];
+
+/// Provider for static calls on Laravel's facades.
+///
+/// `Cache::get('key')` is forwarded to `Illuminate\Cache\CacheManager::get()`, so it returns
+/// whatever that method is declared to return.
+#[derive(Default)]
+pub struct FacadeProvider;
+
+impl Provider for FacadeProvider {
+    fn meta() -> &'static ProviderMeta {
+        &META
+    }
+}
+
+impl MethodReturnTypeProvider for FacadeProvider {
+    fn targets() -> &'static [MethodTarget] {
+        &TARGETS
+    }
+
+    fn get_return_type(
+        &self,
+        context: &ProviderContext<'_, '_, '_>,
+        class_name: &[u8],
+        method_name: &[u8],
+        _invocation: &InvocationInfo<'_, '_, '_>,
+    ) -> Option<TUnion> {
+        // Targets only match classes in the facades namespace, so the short name is the facade.
+        let facade = class_name.rsplit(|byte| *byte == b'\\').next()?;
+        let (_, service) = FACADE_SERVICES.iter().find(|(name, _)| name.eq_ignore_ascii_case(facade))?;
+
+        // Methods the facade declares itself, like `fake()`, are not forwarded.
+        let codebase = context.codebase();
+        if codebase.get_declaring_method_class(class_name, method_name).is_some() {
+            return None;
+        }
+
+        let method = codebase.get_declaring_method(service, method_name)?;
+        let mut return_type = method.return_type_metadata.as_ref()?.type_union.clone();
+        let service = word(service);
+        expand_union(
+            codebase,
+            &mut return_type,
+            &TypeExpansionOptions {
+                self_class: Some(service),
+                static_class_type: StaticClassType::Name(service),
+                ..Default::default()
+            },
+        );
+
+        Some(return_type)
+    }
+}
@@ -0,0 +1,117 @@
+//! Laravel framework providers.
+//!
+//! Eloquent resolves most of a model's API at runtime: attributes are read from the table the
+//! model maps to, relations are exposed as properties, and static calls on the model are
+//! forwarded to its query builder. These providers give those calls and properties the types
+//! Eloquent gives them at runtime, and do the same for facades and collections.
+
+mod attributes;
+mod casts;
+mod collections;
+mod facades;
+mod query;
+mod relations;
+
+pub use attributes::ModelAttributeProvider;
+pub use casts::ModelCastsHook;
+pub use collections::CollectionProvider;
+pub use facades::FacadeProvider;
+pub use query::ModelQueryProvider;
+pub use relations::RelationMethodProvider;
+
+use mago_codex::metadata::CodebaseMetadata;
+use mago_codex::ttype::atomic::TAtomic;
+use mago_codex::ttype::atomic::object::TObject;
+use mago_codex::ttype::atomic::object::named::TNamedObject;
+use mago_codex::ttype::expander::StaticClassType;
+use mago_codex::ttype::get_int;
+use mago_codex::ttype::union::TUnion;
+use mago_word::Word;
+use mago_word::starts_with_ignore_case;
+use mago_word::word;
+
+use crate::plugin::Plugin;
+use crate::plugin::PluginMeta;
+use crate::plugin::PluginRegistry;
+use crate::plugin::context::InvocationInfo;
+
+const MODEL: &[u8] = b"Illuminate\\Database\\Eloquent\\Model";
+const BUILDER: &[u8] = b"Illuminate\\Database\\Eloquent\\Builder";
+const RELATION: &[u8] = b"Illuminate\\Database\\Eloquent\\Relations\\Relation";
+const ELOQUENT_COLLECTION: &[u8] = b"Illuminate\\Database\\Eloquent\\Collection";
+const SUPPORT_COLLECTION: &[u8] = b"Illuminate\\Support\\Collection";
+
+/// Plugin providing type inference for Laravel's Eloquent models, facades, and collections.
+pub struct LaravelPlugin;
+
+static META: PluginMeta = PluginMeta::new(
+    "laravel",
+    "Laravel",
+    "Type providers for Eloquent models, query builders, relations, facades, and collections",
+    &["laravel-framework", "eloquent"],
+    false,
+);
+
+impl Plugin for LaravelPlugin {
+    fn meta(&self) -> &'static PluginMeta {
+        &META
+    }
+
+    fn register(&self, registry: &mut PluginRegistry) {
+        registry.register_property_type_provider(ModelAttributeProvider);
+        registry.register_method_provider(RelationMethodProvider);
+        registry.register_method_provider(ModelQueryProvider);
+        registry.register_method_provider(FacadeProvider);
+        registry.register_method_provider(CollectionProvider);
+        registry.register_class_hook(ModelCastsHook);
+    }
+}
+
+/// The class a method is called on, with its type parameters when it is called on an object.
+///
+/// Static calls (`User::where(...)`) have no type parameters.
+fn receiver<'ctx>(invocation: &InvocationInfo<'ctx, '_, '_>) -> Option<(Word, &'ctx [TUnion])> {
+    let method_context = invocation.inner().target.get_method_context()?;
+
+    match &method_context.class_type {
+        StaticClassType::Exact(name) | StaticClassType::Name(name) => Some((*name, &[])),
+        StaticClassType::Object(TObject::Named(named)) => {
+            Some((named.get_name(), named.get_type_parameters().unwrap_or_default()))
+        }
+        _ => None,
+    }
+}
+
+/// Whether `method` is one of Laravel's own methods, or is not declared on `class` at all.
+///
+/// Providers leave methods a project declares itself to their declared return types.
+fn is_framework_method(codebase: &CodebaseMetadata, class: &[u8], method: &[u8]) -> bool {
+    codebase
+        .get_declaring_method_class(class, method)
+        .is_none_or(|declaring_class| starts_with_ignore_case(declaring_class.as_bytes(), b"Illuminate\\"))
+}
+
+/// The model named by the first type parameter of a builder, relation, or collection.
+fn model_parameter(type_parameters: &[TUnion]) -> Option<Word> {
+    type_parameters.first()?.get_single_named_object().map(TNamedObject::get_name)
+}
+
+fn named_object(name: &[u8], type_parameters: Option<Vec<TUnion>>) -> TUnion {
+    TUnion::from_atomic(TAtomic::Object(TObject::Named(
+        TNamedObject::new(word(name)).with_type_parameters(type_parameters),
+    )))
+}
+
+fn model_object(model: Word) -> TUnion {
+    named_object(model.as_bytes(), None)
+}
+
+/// `Illuminate\Database\Eloquent\Builder<TModel>`.
+fn builder_of(model: Word) -> TUnion {
+    named_object(BUILDER, Some(vec![model_object(model)]))
+}
+
+/// `Illuminate\Database\Eloquent\Collection<int, TModel>`.
+fn collection_of(model: Word) -> TUnion {
+    named_object(ELOQUENT_COLLECTION, Some(vec![get_int(), model_object(model)]))
+}
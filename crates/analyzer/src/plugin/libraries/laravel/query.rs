@@ -0,0 +1,183 @@
+//! Eloquent query builder return type provider.
+
+use mago_codex::ttype::union::TUnion;
+use mago_word::Word;
+
+use crate::plugin::context::InvocationInfo;
+use crate::plugin::context::ProviderContext;
+use crate::plugin::libraries::laravel::BUILDER;
+use crate::plugin::libraries::laravel::MODEL;
+use crate::plugin::libraries::laravel::RELATION;
+use crate::plugin::libraries::laravel::builder_of;
+use crate::plugin::libraries::laravel::collection_of;
+use crate::plugin::libraries::laravel::is_framework_method;
+use crate::plugin::libraries::laravel::model_object;
+use crate::plugin::libraries::laravel::model_parameter;
+use crate::plugin::libraries::laravel::named_object;
+use crate::plugin::libraries::laravel::receiver;
+use crate::plugin::provider::Provider;
+use crate::plugin::provider::ProviderMeta;
+use crate::plugin::provider::method::MethodReturnTypeProvider;
+use crate::plugin::provider::method::MethodTarget;
+
+static META: ProviderMeta = ProviderMeta::new(
+    "laravel::eloquent::query",
+    "Eloquent queries",
+    "Returns builders, models, and collections of the queried model",
+);
+
+// Models forward unknown static and instance calls to their query builder, so any class may be a model.
+static TARGETS: [MethodTarget; 1] = [MethodTarget::any_class(b"*")];
+
+/// What a query method returns, in terms of the model being queried.
+enum QueryResult {
+    /// The query itself: a `Builder<TModel>`, or the relation the method was called on.
+    Query,
+    /// `TModel|null`.
+    NullableModel,
+    /// `TModel`.
+    Model,
+    /// `Illuminate\Database\Eloquent\Collection<int, TModel>`.
+    Collection,
+}
+
+/// Provider for queries made on Eloquent models, builders, and relations.
+///
+/// `User::where(...)`, `$query->latest()` and `$user->posts()->published()` (through a
+/// `scopePublished()` method) keep track of the model being queried, so `first()` returns
+/// `User|null`, `get()` returns a collection of users, and `create()` returns a `User`.
+#[derive(Default)]
+pub struct ModelQueryProvider;
+
+impl Provider for ModelQueryProvider {
+    fn meta() -> &'static ProviderMeta {
+        &META
+    }
+}
+
+impl MethodReturnTypeProvider for ModelQueryProvider {
+    fn targets() -> &'static [MethodTarget] {
+        &TARGETS
+    }
+
+    fn get_return_type(
+        &self,
+        context: &ProviderContext<'_, '_, '_>,
+        _class_name: &[u8],
+        method_name: &[u8],
+        invocation: &InvocationInfo<'_, '_, '_>,
+    ) -> Option<TUnion> {
+        let (class, type_parameters) = receiver(invocation)?;
+        let is_relation = context.is_instance_of(class.as_bytes(), RELATION);
+        let model = if is_relation || context.is_instance_of(class.as_bytes(), BUILDER) {
+            model_parameter(type_parameters)?
+        } else if context.is_instance_of(class.as_bytes(), MODEL) {
+            class
+        } else {
+            return None;
+        };
+
+        let codebase = context.codebase();
+        if !is_framework_method(codebase, class.as_bytes(), method_name) {
+            return None;
+        }
+
+        let result = match query_result(method_name) {
+            Some(result) => result,
+            None if has_scope(context, model, method_name) => QueryResult::Query,
+            None => return None,
+        };
+
+        Some(match result {
+            QueryResult::Query if is_relation => named_object(class.as_bytes(), Some(type_parameters.to_vec())),
+            QueryResult::Query => builder_of(model),
+            QueryResult::NullableModel | QueryResult::Model
+                if method_name.to_ascii_lowercase().starts_with(b"find") && has_array_argument(context, invocation) =>
+            {
+                collection_of(model)
+            }
+            QueryResult::NullableModel => model_object(model).as_nullable(),
+            QueryResult::Model => model_object(model),
+            QueryResult::Collection => collection_of(model),
+        })
+    }
+}
+
+fn query_result(method_name: &[u8]) -> Option<QueryResult> {
+    let method_name = method_name.to_ascii_lowercase();
+
+    // `where()`, `orWhere()`, `whereHas()`, and dynamic wheres like `whereEmail()`.
+    if method_name.starts_with(b"where") || method_name.starts_with(b"orwhere") {
+        return Some(QueryResult::Query);
+    }
+
+    Some(match method_name.as_slice() {
+        b"query"
+        | b"newquery"
+        | b"newmodelquery"
+        | b"has"
+        | b"orhas"
+        | b"doesnthave"
+        | b"ordoesnthave"
+        | b"withwherehas"
+        | b"with"
+        | b"without"
+        | b"withonly"
+        | b"withcount"
+        | b"withsum"
+        | b"withavg"
+        | b"withmin"
+        | b"withmax"
+        | b"withexists"
+        | b"withtrashed"
+        | b"onlytrashed"
+        | b"withouttrashed"
+        | b"withoutglobalscope"
+        | b"withoutglobalscopes"
+        | b"select"
+        | b"addselect"
+        | b"distinct"
+        | b"orderby"
+        | b"orderbydesc"
+        | b"latest"
+        | b"oldest"
+        | b"inrandomorder"
+        | b"reorder"
+        | b"groupby"
+        | b"having"
+        | b"limit"
+        | b"take"
+        | b"offset"
+        | b"skip"
+        | b"forpage"
+        | b"join"
+        | b"leftjoin"
+        | b"rightjoin"
+        | b"crossjoin"
+        | b"when"
+        | b"unless"
+        | b"lockforupdate"
+        | b"sharedlock"
+        | b"scopes" => QueryResult::Query,
+        b"find" | b"first" | b"firstwhere" => QueryResult::NullableModel,
+        b"findorfail" | b"findornew" | b"firstorfail" | b"sole" | b"create" | b"forcecreate" | b"createorfirst"
+        | b"firstorcreate" | b"firstornew" | b"updateorcreate" | b"make" => QueryResult::Model,
+        b"get" | b"all" | b"findmany" => QueryResult::Collection,
+        _ => return None,
+    })
+}
+
+/// Whether `model` declares a local scope for `method_name`, e.g. `scopePublished()` for `published()`.
+fn has_scope(context: &ProviderContext<'_, '_, '_>, model: Word, method_name: &[u8]) -> bool {
+    let scope = [b"scope", method_name].concat();
+
+    context.codebase().get_declaring_method(model.as_bytes(), &scope).is_some()
+}
+
+/// Whether the first argument of `find()` or `findOrFail()` is an array of keys.
+fn has_array_argument(context: &ProviderContext<'_, '_, '_>, invocation: &InvocationInfo<'_, '_, '_>) -> bool {
+    invocation
+        .get_argument(0, &[b"id"])
+        .and_then(|argument| context.get_expression_type(argument))
+        .is_some_and(|argument_type| argument_type.is_array())
+}
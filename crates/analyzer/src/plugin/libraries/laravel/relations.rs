@@ -0,0 +1,93 @@
+//! Eloquent relation method return type provider.
+
+use mago_codex::ttype::union::TUnion;
+
+use crate::plugin::context::InvocationInfo;
+use crate::plugin::context::ProviderContext;
+use crate::plugin::libraries::laravel::MODEL;
+use crate::plugin::libraries::laravel::model_object;
+use crate::plugin::libraries::laravel::named_object;
+use crate::plugin::libraries::laravel::receiver;
+use crate::plugin::provider::Provider;
+use crate::plugin::provider::ProviderMeta;
+use crate::plugin::provider::method::MethodReturnTypeProvider;
+use crate::plugin::provider::method::MethodTarget;
+
+static META: ProviderMeta = ProviderMeta::new(
+    "laravel::eloquent::relations",
+    "Eloquent relations",
+    "Returns the relation type, parameterized by the related and declaring models",
+);
+
+static TARGETS: [MethodTarget; 11] = [
+    MethodTarget::any_class(b"hasOne"),
+    MethodTarget::any_class(b"hasMany"),
+    MethodTarget::any_class(b"hasOneThrough"),
+    MethodTarget::any_class(b"hasManyThrough"),
+    MethodTarget::any_class(b"belongsTo"),
+    MethodTarget::any_class(b"belongsToMany"),
+    MethodTarget::any_class(b"morphOne"),
+    MethodTarget::any_class(b"morphMany"),
+    MethodTarget::any_class(b"morphTo"),
+    MethodTarget::any_class(b"morphToMany"),
+    MethodTarget::any_class(b"morphedByMany"),
+];
+
+/// Provider for the relation methods of `Illuminate\Database\Eloquent\Model`.
+///
+/// `$this->hasMany(Post::class)` in `User` returns `HasMany<Post, User>`, so relation methods
+/// declared without generics still type the models they load.
+#[derive(Default)]
+pub struct RelationMethodProvider;
+
+impl Provider for RelationMethodProvider {
+    fn meta() -> &'static ProviderMeta {
+        &META
+    }
+}
+
+impl MethodReturnTypeProvider for RelationMethodProvider {
+    fn targets() -> &'static [MethodTarget] {
+        &TARGETS
+    }
+
+    fn get_return_type(
+        &self,
+        context: &ProviderContext<'_, '_, '_>,
+        _class_name: &[u8],
+        method_name: &[u8],
+        invocation: &InvocationInfo<'_, '_, '_>,
+    ) -> Option<TUnion> {
+        let (declaring_model, _) = receiver(invocation)?;
+        if !context.is_instance_of(declaring_model.as_bytes(), MODEL) {
+            return None;
+        }
+
+        let relation = relation_class(method_name)?;
+        let related = if method_name.eq_ignore_ascii_case(b"morphTo") {
+            named_object(MODEL, None)
+        } else {
+            let related_argument = invocation.get_argument(0, &[b"related"])?;
+
+            model_object(context.get_expression_type(related_argument)?.get_single_class_string_value()?)
+        };
+
+        Some(named_object(relation, Some(vec![related, model_object(declaring_model)])))
+    }
+}
+
+fn relation_class(method_name: &[u8]) -> Option<&'static [u8]> {
+    Some(match method_name.to_ascii_lowercase().as_slice() {
+        b"hasone" => b"Illuminate\\Database\\Eloquent\\Relations\\HasOne",
+        b"hasmany" => b"Illuminate\\Database\\Eloquent\\Relations\\HasMany",
+        b"hasonethrough" => b"Illuminate\\Database\\Eloquent\\Relations\\HasOneThrough",
+        b"hasmanythrough" => b"Illuminate\\Database\\Eloquent\\Relations\\HasManyThrough",
+        b"belongsto" => b"Illuminate\\Database\\Eloquent\\Relations\\BelongsTo",
+        b"belongstomany" => b"Illuminate\\Database\\Eloquent\\Relations\\BelongsToMany",
+        b"morphone" => b"Illuminate\\Database\\Eloquent\\Relations\\MorphOne",
+        b"morphmany" => b"Illuminate\\Database\\Eloquent\\Relations\\MorphMany",
+        b"morphto" => b"Illuminate\\Database\\Eloquent\\Relations\\MorphTo",
+        b"morphtomany" | b"morphedbymany" => b"Illuminate\\Database\\Eloquent\\Relations\\MorphToMany",
+        _ => return None,
+    })
+}
@@ -1,16 +1,33 @@
-//! Library-specific providers for PHP stdlib, PSL, Flow-PHP, and PSR-11 Container.
+//! Library-specific providers for PHP stdlib, PSL, Flow-PHP, PSR-11 Container, Symfony, Laravel, Doctrine, and PHPUnit.
 
+pub mod doctrine;
 pub mod flow_php;
+pub mod laravel;
+pub mod phpunit;
 pub mod psl;
 pub mod psr_container;
 pub mod stdlib;
+pub mod symfony;
 
 use crate::plugin::Plugin;
 
+pub use doctrine::DoctrinePlugin;
 pub use flow_php::FlowPhpPlugin;
+pub use laravel::LaravelPlugin;
+pub use phpunit::PhpUnitPlugin;
 pub use psl::PslPlugin;
 pub use psr_container::PsrContainerPlugin;
 pub use stdlib::StdlibPlugin;
+pub use symfony::SymfonyPlugin;
 
 /// All available analyzer plugins.
-pub static ALL_PLUGINS: &[&dyn Plugin] = &[&StdlibPlugin, &PslPlugin, &FlowPhpPlugin, &PsrContainerPlugin];
+pub static ALL_PLUGINS: &[&dyn Plugin] = &[
+    &StdlibPlugin,
+    &PslPlugin,
+    &FlowPhpPlugin,
+    &PsrContainerPlugin,
+    &SymfonyPlugin,
+    &LaravelPlugin,
+    &DoctrinePlugin,
+    &PhpUnitPlugin,
+];
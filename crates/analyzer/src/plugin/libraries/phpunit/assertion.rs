@@ -0,0 +1,79 @@
+//! `Assert` assertion provider.
+
+use mago_codex::assertion::Assertion;
+use mago_codex::ttype::atomic::TAtomic;
+use mago_codex::ttype::atomic::object::TObject;
+use mago_codex::ttype::atomic::object::named::TNamedObject;
+use mago_codex::ttype::get_mixed_keyed_array;
+use mago_word::word;
+
+use crate::plugin::context::InvocationInfo;
+use crate::plugin::context::ProviderContext;
+use crate::plugin::libraries::phpunit::ASSERT;
+use crate::plugin::provider::Provider;
+use crate::plugin::provider::ProviderMeta;
+use crate::plugin::provider::assertion::InvocationAssertions;
+use crate::plugin::provider::assertion::MethodAssertionProvider;
+use crate::plugin::provider::method::MethodTarget;
+
+static META: ProviderMeta = ProviderMeta::new(
+    "phpunit::assert",
+    "Assert",
+    "Narrows the value passed to assertInstanceOf(), assertNotNull(), and assertIsArray()",
+);
+
+static TARGETS: [MethodTarget; 3] = [
+    MethodTarget::any_class(b"assertInstanceOf"),
+    MethodTarget::any_class(b"assertNotNull"),
+    MethodTarget::any_class(b"assertIsArray"),
+];
+
+/// Provider for the assertions of `PHPUnit\Framework\Assert`, called on the test case or on
+/// `Assert` itself.
+///
+/// A failed assertion ends the test, so after `$this->assertInstanceOf(Foo::class, $value)`,
+/// `$value` is a `Foo`. Likewise, `assertNotNull($value)` removes `null` from `$value`, and
+/// `assertIsArray($value)` narrows it to an array.
+#[derive(Default)]
+pub struct AssertionProvider;
+
+impl Provider for AssertionProvider {
+    fn meta() -> &'static ProviderMeta {
+        &META
+    }
+}
+
+impl MethodAssertionProvider for AssertionProvider {
+    fn targets() -> &'static [MethodTarget] {
+        &TARGETS
+    }
+
+    fn get_assertions(
+        &self,
+        context: &ProviderContext<'_, '_, '_>,
+        class_name: &[u8],
+        method_name: &[u8],
+        invocation: &InvocationInfo<'_, '_, '_>,
+    ) -> Option<InvocationAssertions> {
+        if !context.is_instance_of(class_name, ASSERT) {
+            return None;
+        }
+
+        let assertion = match method_name.to_ascii_lowercase().as_slice() {
+            b"assertinstanceof" => {
+                let expected = invocation.get_argument(0, &[b"expected"])?;
+                let class = context.get_expression_type(expected)?.get_single_class_string_value()?;
+
+                Assertion::IsType(TAtomic::Object(TObject::Named(TNamedObject::new(class))))
+            }
+            b"assertnotnull" => Assertion::IsNotType(TAtomic::Null),
+            b"assertisarray" => Assertion::IsType(get_mixed_keyed_array().get_single_owned()),
+            _ => return None,
+        };
+
+        let mut assertions = InvocationAssertions::new();
+        assertions.add_immediate(word("$actual"), vec![assertion]);
+
+        Some(assertions)
+    }
+}
@@ -0,0 +1,367 @@
+//! Data provider checks for `#[DataProvider]`, `#[DataProviderExternal]`, and `@dataProvider`.
+//!
+//! PHPUnit calls a data provider before the test it feeds, and calls the test once with each
+//! row the provider yields. The provider must exist and be public, and the items of each row
+//! are passed to the test's parameters by position, or by name when the row has string keys.
+//! Rows are checked against the parameters when the provider declares their shape, like
+//! `iterable<string, array{int, string}>`.
+
+use foldhash::HashSet;
+
+use mago_codex::metadata::CodebaseMetadata;
+use mago_codex::metadata::attribute::AttributeArgumentMetadata;
+use mago_codex::metadata::attribute::AttributeMetadata;
+use mago_codex::metadata::class_like::ClassLikeMetadata;
+use mago_codex::metadata::function_like::FunctionLikeMetadata;
+use mago_codex::metadata::parameter::FunctionLikeParameterMetadata;
+use mago_codex::ttype::TType;
+use mago_codex::ttype::atomic::TAtomic;
+use mago_codex::ttype::atomic::array::TArray;
+use mago_codex::ttype::atomic::array::key::ArrayKey;
+use mago_codex::ttype::comparator::ComparisonResult;
+use mago_codex::ttype::comparator::union_comparator::is_contained_by;
+use mago_codex::ttype::get_iterable_parameters;
+use mago_codex::ttype::union::TUnion;
+use mago_reporting::Annotation;
+use mago_reporting::Issue;
+use mago_span::HasSpan;
+use mago_span::Span;
+use mago_syntax::cst::Class;
+use mago_syntax::cst::ClassLikeMember;
+use mago_syntax::cst::Method;
+use mago_word::Word;
+
+use crate::code::IssueCode;
+use crate::plugin::context::HookContext;
+use crate::plugin::hook::ClassDeclarationHook;
+use crate::plugin::hook::HookResult;
+use crate::plugin::libraries::phpunit::TEST_CASE;
+use crate::plugin::provider::Provider;
+use crate::plugin::provider::ProviderMeta;
+
+static META: ProviderMeta = ProviderMeta::new(
+    "phpunit::data-provider",
+    "Data providers",
+    "Checks data providers exist, are public, and yield rows the test accepts",
+);
+
+const DATA_PROVIDER: &[u8] = b"PHPUnit\\Framework\\Attributes\\DataProvider";
+const DATA_PROVIDER_EXTERNAL: &[u8] = b"PHPUnit\\Framework\\Attributes\\DataProviderExternal";
+
+/// A data provider, as referenced by a test.
+struct DataProvider {
+    /// The class declaring the provider.
+    class: Word,
+    /// The name of the provider method.
+    method: String,
+    /// Where the test references the provider.
+    span: Span,
+}
+
+/// An item of a row yielded by a data provider.
+enum RowItem<'row> {
+    /// An item passed by position.
+    Positional(usize, &'row TUnion),
+    /// An item passed by name.
+    Named(Word, &'row TUnion),
+}
+
+/// Checks the data providers referenced by the tests of a test case.
+#[derive(Default)]
+pub struct DataProviderHook;
+
+impl Provider for DataProviderHook {
+    fn meta() -> &'static ProviderMeta {
+        &META
+    }
+}
+
+impl ClassDeclarationHook for DataProviderHook {
+    fn on_enter_class(
+        &self,
+        class: &Class<'_>,
+        metadata: &ClassLikeMetadata,
+        context: &mut HookContext<'_, '_>,
+    ) -> HookResult<()> {
+        let codebase = context.codebase();
+        if !codebase.is_instance_of(metadata.name.as_bytes(), TEST_CASE) {
+            return Ok(());
+        }
+
+        for member in class.members.iter() {
+            let ClassLikeMember::Method(method) = member else {
+                continue;
+            };
+
+            let Some(test) = codebase.get_method(metadata.name.as_bytes(), method.name.value) else {
+                continue;
+            };
+
+            for provider in providers(context, metadata, method, test) {
+                check_provider(context, test, &provider);
+            }
+        }
+
+        Ok(())
+    }
+}
+
+/// The data providers referenced by the attributes and the docblock of a test.
+///
+/// Providers referenced as `@dataProvider Foo::provider` are not resolved, as the docblock
+/// names the class relative to the imports of the file.
+fn providers(
+    context: &HookContext<'_, '_>,
+    metadata: &ClassLikeMetadata,
+    method: &Method<'_>,
+    test: &FunctionLikeMetadata,
+) -> Vec<DataProvider> {
+    let mut providers = Vec::new();
+
+    for attribute in &test.attributes {
+        let name = attribute.name.as_bytes();
+        if name.eq_ignore_ascii_case(DATA_PROVIDER) {
+            if let Some(provider) = string_argument(attribute, b"methodName", 0) {
+                providers.push(DataProvider { class: metadata.name, method: provider, span: attribute.span });
+            }
+        } else if name.eq_ignore_ascii_case(DATA_PROVIDER_EXTERNAL)
+            && let Some(class) = class_string_argument(attribute, b"className", 0)
+            && let Some(provider) = string_argument(attribute, b"methodName", 1)
+        {
+            providers.push(DataProvider { class, method: provider, span: attribute.span });
+        }
+    }
+
+    let contents = &context.source_file().contents;
+    let Some(docblock) = docblock_before(contents, method.span().start.offset as usize) else {
+        return providers;
+    };
+
+    for tag in docblock.split(|byte| *byte == b'\n').filter_map(|line| tag_value(line, b"@dataProvider")) {
+        if !tag.contains(&b':') {
+            providers.push(DataProvider {
+                class: metadata.name,
+                method: String::from_utf8_lossy(tag).into_owned(),
+                span: method.name.span,
+            });
+        }
+    }
+
+    providers
+}
+
+fn check_provider(context: &HookContext<'_, '_>, test: &FunctionLikeMetadata, provider: &DataProvider) {
+    let codebase = context.codebase();
+    let Some(class) = codebase.get_class_like(provider.class.as_bytes()) else {
+        return;
+    };
+
+    let Some(provider_method) = codebase.get_declaring_method(provider.class.as_bytes(), provider.method.as_bytes())
+    else {
+        context.report(
+            IssueCode::InvalidDataProvider,
+            Issue::error(format!(
+                "Data provider `{}::{}()` of test `{}()` does not exist.",
+                class.original_name, provider.method, test.original_name
+            ))
+            .with_annotation(Annotation::primary(provider.span).with_message("The data provider is referenced here"))
+            .with_note("PHPUnit reports the test as errored when its data provider does not exist.")
+            .with_help("Add the data provider method, or reference an existing one."),
+        );
+
+        return;
+    };
+
+    if provider_method.method_metadata.as_ref().is_some_and(|method_metadata| !method_metadata.visibility.is_public()) {
+        context.report(
+            IssueCode::InvalidDataProvider,
+            Issue::error(format!(
+                "Data provider `{}::{}()` is not public.",
+                class.original_name, provider_method.original_name
+            ))
+            .with_annotation(Annotation::primary(provider.span).with_message("The data provider is referenced here"))
+            .with_annotation(
+                Annotation::secondary(provider_method.name_span.unwrap_or(provider_method.span))
+                    .with_message("The data provider is declared here"),
+            )
+            .with_help("Make the data provider public, as PHPUnit calls it from outside the test case."),
+        );
+
+        return;
+    }
+
+    let Some(return_type) =
+        provider_method.return_type_metadata.as_ref().or(provider_method.return_type_declaration_metadata.as_ref())
+    else {
+        return;
+    };
+
+    let mut reported = HashSet::default();
+    for atomic in return_type.type_union.types.iter() {
+        let Some((_, row_type)) = get_iterable_parameters(atomic, codebase) else {
+            continue;
+        };
+
+        for row in row_type.types.iter() {
+            for item in row_items(row) {
+                let (parameter, item_type) = match item {
+                    RowItem::Positional(position, item_type) => {
+                        let parameter = test
+                            .parameters
+                            .get(position)
+                            .or_else(|| test.parameters.last().filter(|parameter| parameter.flags.is_variadic()));
+
+                        (parameter, item_type)
+                    }
+                    RowItem::Named(name, item_type) => {
+                        let parameter = test
+                            .parameters
+                            .iter()
+                            .find(|parameter| &parameter.get_name().0.as_bytes()[1..] == name.as_bytes());
+
+                        (parameter, item_type)
+                    }
+                };
+
+                let Some(parameter) = parameter else {
+                    continue;
+                };
+
+                if reported.contains(&parameter.get_name().0) || accepts(codebase, parameter, item_type) {
+                    continue;
+                }
+
+                reported.insert(parameter.get_name().0);
+                report_mismatch(context, test, provider_method, provider, parameter, item_type);
+            }
+        }
+    }
+}
+
+fn report_mismatch(
+    context: &HookContext<'_, '_>,
+    test: &FunctionLikeMetadata,
+    provider_method: &FunctionLikeMetadata,
+    provider: &DataProvider,
+    parameter: &FunctionLikeParameterMetadata,
+    item_type: &TUnion,
+) {
+    let parameter_name = parameter.get_name().0;
+    let parameter_type = parameter
+        .get_type_metadata()
+        .map_or_else(|| "mixed".to_string(), |type_metadata| type_metadata.type_union.get_id().to_string());
+
+    context.report(
+        IssueCode::InvalidDataProvider,
+        Issue::error(format!(
+            "Data provider `{}()` yields `{}` for parameter `{parameter_name}` of test `{}()`, which expects `{parameter_type}`.",
+            provider_method.original_name,
+            item_type.get_id(),
+            test.original_name,
+        ))
+        .with_annotation(
+            Annotation::primary(parameter.get_span()).with_message(format!("This parameter is `{parameter_type}`")),
+        )
+        .with_annotation(
+            Annotation::secondary(provider.span).with_message(format!("The data provider yields `{}`", item_type.get_id())),
+        )
+        .with_note("PHPUnit passes each row of the data provider to the test, which fails when an item does not match its parameter.")
+        .with_help("Change the rows yielded by the data provider, or widen the parameter type."),
+    );
+}
+
+/// The items of a row whose shape is known.
+fn row_items(row: &TAtomic) -> Vec<RowItem<'_>> {
+    match row {
+        TAtomic::Array(TArray::List(list)) => list
+            .known_elements
+            .iter()
+            .flatten()
+            .map(|(position, (_, item_type))| RowItem::Positional(*position, item_type))
+            .collect(),
+        TAtomic::Array(TArray::Keyed(keyed)) => keyed
+            .known_items
+            .iter()
+            .flatten()
+            .filter_map(|(key, (_, item_type))| match key {
+                ArrayKey::Integer(position) => Some(RowItem::Positional(usize::try_from(*position).ok()?, item_type)),
+                ArrayKey::String(name) => Some(RowItem::Named(*name, item_type)),
+                _ => None,
+            })
+            .collect(),
+        _ => Vec::new(),
+    }
+}
+
+/// Whether `parameter` accepts an item of `item_type`.
+///
+/// Untyped parameters and items of unknown type are accepted.
+fn accepts(codebase: &CodebaseMetadata, parameter: &FunctionLikeParameterMetadata, item_type: &TUnion) -> bool {
+    let Some(parameter_type) = parameter.get_type_metadata() else {
+        return true;
+    };
+
+    if item_type.is_mixed() || item_type.has_template_types() || parameter_type.type_union.has_template_types() {
+        return true;
+    }
+
+    is_contained_by(
+        codebase,
+        item_type,
+        &parameter_type.type_union,
+        false,
+        false,
+        false,
+        &mut ComparisonResult::default(),
+    )
+}
+
+/// The docblock directly preceding `offset` in `contents`.
+fn docblock_before(contents: &[u8], offset: usize) -> Option<&[u8]> {
+    let before = contents.get(..offset)?.trim_ascii_end();
+    if !before.ends_with(b"*/") {
+        return None;
+    }
+
+    let start = before.windows(3).rposition(|window| window == b"/**")?;
+
+    Some(&before[start..])
+}
+
+/// The value of `tag` on a docblock line, like `provider` for ` * @dataProvider provider`.
+fn tag_value<'line>(line: &'line [u8], tag: &[u8]) -> Option<&'line [u8]> {
+    let start = line.iter().position(|byte| !byte.is_ascii_whitespace() && !matches!(byte, b'/' | b'*'))?;
+    let value = line[start..].strip_prefix(tag)?;
+    if !value.first().is_some_and(u8::is_ascii_whitespace) {
+        return None;
+    }
+
+    let value = value.trim_ascii_start();
+    let end = value.iter().position(|byte| byte.is_ascii_whitespace() || *byte == b'*').unwrap_or(value.len());
+
+    (end > 0).then(|| &value[..end])
+}
+
+/// The literal string passed to an attribute, by name or at the given position.
+fn string_argument(attribute: &AttributeMetadata, name: &[u8], position: usize) -> Option<String> {
+    let value_type = argument(attribute, name, position)?.value_type.as_ref()?;
+
+    Some(String::from_utf8_lossy(value_type.get_single_literal_string_value()?).into_owned())
+}
+
+/// The class named by a `Foo::class` argument of an attribute, by name or at the given position.
+fn class_string_argument(attribute: &AttributeMetadata, name: &[u8], position: usize) -> Option<Word> {
+    argument(attribute, name, position)?.value_type.as_ref()?.get_single_class_string_value()
+}
+
+fn argument<'attribute>(
+    attribute: &'attribute AttributeMetadata,
+    name: &[u8],
+    position: usize,
+) -> Option<&'attribute AttributeArgumentMetadata> {
+    attribute
+        .arguments
+        .iter()
+        .find(|argument| argument.name.is_some_and(|argument_name| argument_name.as_bytes() == name))
+        .or_else(|| attribute.arguments.iter().filter(|argument| argument.name.is_none()).nth(position))
+}
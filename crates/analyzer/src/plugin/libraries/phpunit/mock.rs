@@ -0,0 +1,226 @@
+//! Test double return type provider and mocked method checks.
+
+use mago_codex::metadata::CodebaseMetadata;
+use mago_codex::ttype::TType;
+use mago_codex::ttype::atomic::TAtomic;
+use mago_codex::ttype::atomic::object::TObject;
+use mago_codex::ttype::atomic::object::named::TNamedObject;
+use mago_codex::ttype::union::TUnion;
+use mago_reporting::Annotation;
+use mago_reporting::Issue;
+use mago_span::HasSpan;
+use mago_syntax::cst::Argument;
+use mago_syntax::cst::Call;
+use mago_syntax::cst::ClassLikeMemberSelector;
+use mago_syntax::cst::Expression;
+use mago_syntax::cst::MethodCall;
+use mago_word::Word;
+use mago_word::ascii_lowercase_word;
+use mago_word::word;
+
+use crate::code::IssueCode;
+use crate::plugin::context::HookContext;
+use crate::plugin::context::InvocationInfo;
+use crate::plugin::context::ProviderContext;
+use crate::plugin::hook::HookResult;
+use crate::plugin::hook::MethodCallHook;
+use crate::plugin::libraries::phpunit::MOCK_OBJECT;
+use crate::plugin::libraries::phpunit::STUB;
+use crate::plugin::libraries::phpunit::TEST_CASE;
+use crate::plugin::libraries::phpunit::is_test_double;
+use crate::plugin::provider::Provider;
+use crate::plugin::provider::ProviderMeta;
+use crate::plugin::provider::method::MethodReturnTypeProvider;
+use crate::plugin::provider::method::MethodTarget;
+
+static CREATE_MOCK_META: ProviderMeta = ProviderMeta::new(
+    "phpunit::create-mock",
+    "createMock",
+    "Returns the doubled class intersected with MockObject or Stub",
+);
+
+static CREATE_MOCK_TARGETS: [MethodTarget; 5] = [
+    MethodTarget::any_class(b"createMock"),
+    MethodTarget::any_class(b"createConfiguredMock"),
+    MethodTarget::any_class(b"createPartialMock"),
+    MethodTarget::any_class(b"createStub"),
+    MethodTarget::any_class(b"createConfiguredStub"),
+];
+
+/// Provider for the test double factories of `TestCase`.
+///
+/// `createMock(Foo::class)`, `createConfiguredMock()`, and `createPartialMock()` return
+/// `Foo&MockObject`, while `createStub(Foo::class)` and `createConfiguredStub()` return
+/// `Foo&Stub`.
+#[derive(Default)]
+pub struct CreateMockProvider;
+
+impl Provider for CreateMockProvider {
+    fn meta() -> &'static ProviderMeta {
+        &CREATE_MOCK_META
+    }
+}
+
+impl MethodReturnTypeProvider for CreateMockProvider {
+    fn targets() -> &'static [MethodTarget] {
+        &CREATE_MOCK_TARGETS
+    }
+
+    fn get_return_type(
+        &self,
+        context: &ProviderContext<'_, '_, '_>,
+        class_name: &[u8],
+        method_name: &[u8],
+        invocation: &InvocationInfo<'_, '_, '_>,
+    ) -> Option<TUnion> {
+        if !context.is_instance_of(class_name, TEST_CASE) {
+            return None;
+        }
+
+        let class_argument = invocation.get_argument(0, &[b"type", b"originalClassName", b"className"])?;
+        let class = context.get_expression_type(class_argument)?.get_single_class_string_value()?;
+        let class = context.get_class_like(class)?.original_name;
+
+        let double = if method_name.eq_ignore_ascii_case(b"createStub")
+            || method_name.eq_ignore_ascii_case(b"createConfiguredStub")
+        {
+            STUB
+        } else {
+            MOCK_OBJECT
+        };
+
+        let mut object = TNamedObject::new(class);
+        object.add_intersection_type(TAtomic::Object(TObject::Named(TNamedObject::new(word(double)))));
+
+        Some(TUnion::from_atomic(TAtomic::Object(TObject::Named(object))))
+    }
+}
+
+/// Checks the methods configured with `->method('name')` on test doubles against the classes
+/// they double.
+///
+/// PHPUnit only allows configuring methods the doubled class declares, so a double of a
+/// renamed method throws once the test runs.
+#[derive(Default)]
+pub struct MockedMethodHook;
+
+impl Provider for MockedMethodHook {
+    fn meta() -> &'static ProviderMeta {
+        static META: ProviderMeta = ProviderMeta::new(
+            "phpunit::mock::method",
+            "Mocked methods",
+            "Checks the methods configured on test doubles against the doubled class.",
+        );
+
+        &META
+    }
+}
+
+impl MethodCallHook for MockedMethodHook {
+    fn after_method_call(&self, call: &MethodCall<'_>, context: &mut HookContext<'_, '_>) -> HookResult<()> {
+        let ClassLikeMemberSelector::Identifier(method) = &call.method else {
+            return Ok(());
+        };
+
+        if !method.value.eq_ignore_ascii_case(b"method") {
+            return Ok(());
+        }
+
+        let Some(argument) = call.argument_list.arguments.iter().find(|argument| match argument {
+            Argument::Positional(_) => true,
+            Argument::Named(argument) => argument.name.value == b"constraint",
+        }) else {
+            return Ok(());
+        };
+
+        let Some(method_name) =
+            context.get_expression_type(argument.value()).and_then(TUnion::get_single_literal_string_value)
+        else {
+            return Ok(());
+        };
+
+        let codebase = context.codebase();
+        for class in doubled_classes(context, call.object) {
+            let Some(metadata) = codebase.get_class_like(class.as_bytes()) else {
+                continue;
+            };
+
+            if has_method(codebase, class, method_name) {
+                continue;
+            }
+
+            let method_name = String::from_utf8_lossy(method_name);
+
+            context.report(
+                IssueCode::NonExistentMockedMethod,
+                Issue::error(format!(
+                    "Cannot configure method `{method_name}()` on a test double of `{}`, which does not declare it.",
+                    metadata.original_name
+                ))
+                .with_annotation(
+                    Annotation::primary(argument.value().span()).with_message("This method does not exist"),
+                )
+                .with_note(
+                    "PHPUnit throws when a test double is configured with a method the doubled class does not declare.",
+                )
+                .with_help(
+                    "Configure a method the doubled class declares, or update the test after renaming the method.",
+                ),
+            );
+        }
+
+        Ok(())
+    }
+}
+
+/// The classes doubled by the test double `object`, following `->expects(...)` to the double
+/// it was called on.
+fn doubled_classes(context: &HookContext<'_, '_>, object: &Expression<'_>) -> Vec<Word> {
+    let codebase = context.codebase();
+    let mut classes = Vec::new();
+
+    if let Some(object_type) = context.get_expression_type(object) {
+        for atomic in object_type.types.iter() {
+            let TAtomic::Object(TObject::Named(named)) = atomic else {
+                continue;
+            };
+
+            let parts = std::iter::once(named.get_name()).chain(
+                named.get_intersection_types().into_iter().flatten().filter_map(|intersection| match intersection {
+                    TAtomic::Object(TObject::Named(intersection)) => Some(intersection.get_name()),
+                    _ => None,
+                }),
+            );
+
+            let (double_interfaces, doubled): (Vec<Word>, Vec<Word>) =
+                parts.partition(|part| is_test_double(codebase, part.as_bytes()));
+
+            if !double_interfaces.is_empty() {
+                classes.extend(doubled);
+            }
+        }
+    }
+
+    if classes.is_empty()
+        && let Expression::Call(Call::Method(expects)) = object
+        && let ClassLikeMemberSelector::Identifier(method) = &expects.method
+        && method.value.eq_ignore_ascii_case(b"expects")
+    {
+        return doubled_classes(context, expects.object);
+    }
+
+    classes
+}
+
+/// Whether `class` declares `method`, either as a real method or with a `@method` tag.
+fn has_method(codebase: &CodebaseMetadata, class: Word, method: &[u8]) -> bool {
+    if codebase.method_exists(class.as_bytes(), method) {
+        return true;
+    }
+
+    let method = ascii_lowercase_word(method);
+
+    codebase.get_class_like(class.as_bytes()).is_some_and(|metadata| {
+        metadata.pseudo_methods.contains(&method) || metadata.static_pseudo_methods.contains(&method)
+    })
+}
@@ -0,0 +1,56 @@
+//! PHPUnit providers.
+//!
+//! Test doubles created by `TestCase` are typed as the intersection of the doubled class and
+//! PHPUnit's `MockObject` or `Stub`, so the methods they configure can be checked against the
+//! doubled class. Data providers are checked against the tests they feed, and the common
+//! `Assert` methods narrow the values they assert on.
+
+mod assertion;
+mod data_provider;
+mod mock;
+
+pub use assertion::AssertionProvider;
+pub use data_provider::DataProviderHook;
+pub use mock::CreateMockProvider;
+pub use mock::MockedMethodHook;
+
+use mago_codex::metadata::CodebaseMetadata;
+
+use crate::plugin::Plugin;
+use crate::plugin::PluginMeta;
+use crate::plugin::PluginRegistry;
+
+const TEST_CASE: &[u8] = b"PHPUnit\\Framework\\TestCase";
+const ASSERT: &[u8] = b"PHPUnit\\Framework\\Assert";
+const MOCK_OBJECT: &[u8] = b"PHPUnit\\Framework\\MockObject\\MockObject";
+const STUB: &[u8] = b"PHPUnit\\Framework\\MockObject\\Stub";
+
+/// Plugin providing type inference and checks for PHPUnit test doubles, data providers, and
+/// assertions.
+pub struct PhpUnitPlugin;
+
+static META: PluginMeta = PluginMeta::new(
+    "phpunit",
+    "PHPUnit",
+    "Types test doubles and assertions, and checks mocked methods and data providers",
+    &["php-unit"],
+    false,
+);
+
+impl Plugin for PhpUnitPlugin {
+    fn meta(&self) -> &'static PluginMeta {
+        &META
+    }
+
+    fn register(&self, registry: &mut PluginRegistry) {
+        registry.register_method_provider(CreateMockProvider);
+        registry.register_method_call_hook(MockedMethodHook);
+        registry.register_class_hook(DataProviderHook);
+        registry.register_method_assertion_provider(AssertionProvider);
+    }
+}
+
+/// Whether `class` is one of PHPUnit's test double interfaces, or extends one.
+fn is_test_double(codebase: &CodebaseMetadata, class: &[u8]) -> bool {
+    codebase.is_instance_of(class, STUB) || codebase.is_instance_of(class, MOCK_OBJECT)
+}
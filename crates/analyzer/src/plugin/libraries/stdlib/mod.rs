@@ -10,6 +10,7 @@ pub mod math;
 pub mod object;
 pub mod random;
 pub mod reflection;
+pub mod regex;
 pub mod session;
 pub mod spl;
 pub mod string;
@@ -25,7 +26,7 @@ pub struct StdlibPlugin;
 static META: PluginMeta = PluginMeta::new(
     "stdlib",
     "PHP Standard Library",
-    "Type providers for PHP built-in functions (strlen, array_*, json_*, etc.)",
+    "Type providers for PHP built-in functions (strlen, array_*, json_*, preg_*, etc.)",
     &["standard", "std", "php-stdlib"],
     true,
 );
@@ -61,6 +62,10 @@ impl Plugin for StdlibPlugin {
         registry.register_function_provider(array::RangeProvider);
 
         registry.register_function_assertion_provider(array::ArrayAllAssertionProvider);
+        registry.register_function_assertion_provider(regex::PregMatchAssertionProvider);
+        registry.register_function_assertion_provider(regex::PregMatchAllAssertionProvider);
+
+        registry.register_function_parameter_provider(regex::PregReplaceCallbackParameterProvider);
 
         registry.register_function_call_hook(cookie::SetCookieHook);
         registry.register_function_call_hook(session::SessionSetSaveHandlerHook);
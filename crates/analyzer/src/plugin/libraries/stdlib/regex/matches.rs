@@ -0,0 +1,127 @@
+//! Array shapes of the matches of a literal pattern.
+//!
+//! `preg_match()` leaves out optional groups at the end of the pattern that did not
+//! participate in the match, and sets optional groups before a participating group to
+//! `""`. With `PREG_UNMATCHED_AS_NULL`, every group is set, and unmatched groups are
+//! `null`. With `PREG_OFFSET_CAPTURE`, every value is a `list{string, int}` pair of the
+//! matched string and its offset.
+
+use std::collections::BTreeMap;
+use std::sync::Arc;
+
+use mago_codex::ttype::atomic::TAtomic;
+use mago_codex::ttype::atomic::array::TArray;
+use mago_codex::ttype::atomic::array::key::ArrayKey;
+use mago_codex::ttype::atomic::array::keyed::TKeyedArray;
+use mago_codex::ttype::atomic::array::list::TList;
+use mago_codex::ttype::get_int;
+use mago_codex::ttype::get_nullable_string;
+use mago_codex::ttype::get_string;
+use mago_codex::ttype::union::TUnion;
+
+use crate::plugin::context::InvocationInfo;
+use crate::plugin::context::ProviderContext;
+use crate::plugin::libraries::stdlib::regex::pattern::CaptureGroup;
+use crate::plugin::libraries::stdlib::regex::pattern::parse_capture_groups;
+
+pub const PREG_PATTERN_ORDER: i64 = 1;
+pub const PREG_SET_ORDER: i64 = 2;
+pub const PREG_OFFSET_CAPTURE: i64 = 256;
+pub const PREG_UNMATCHED_AS_NULL: i64 = 512;
+
+/// Returns the capture groups of the literal `$pattern` argument.
+pub fn get_pattern_capture_groups(
+    context: &ProviderContext<'_, '_, '_>,
+    invocation: &InvocationInfo<'_, '_, '_>,
+) -> Option<Vec<CaptureGroup>> {
+    let pattern = invocation.get_argument(0, &[b"pattern"])?;
+    let pattern = context.get_expression_type(pattern)?.get_single_literal_string_value()?;
+
+    parse_capture_groups(pattern)
+}
+
+/// Returns the literal `$flags` argument at `index`, or `0` if it is not passed.
+pub fn get_flags(
+    context: &ProviderContext<'_, '_, '_>,
+    invocation: &InvocationInfo<'_, '_, '_>,
+    index: usize,
+) -> Option<i64> {
+    match invocation.get_argument(index, &[b"flags"]) {
+        Some(flags) => context.get_expression_type(flags)?.get_single_literal_int_value(),
+        None => Some(0),
+    }
+}
+
+/// Returns the shape of the matches of a single match, as set by `preg_match()` or
+/// passed to a `preg_replace_callback()` callback.
+pub fn get_match_shape(groups: &[CaptureGroup], flags: i64) -> TAtomic {
+    let unmatched_as_null = flags & PREG_UNMATCHED_AS_NULL != 0;
+    let offset_capture = flags & PREG_OFFSET_CAPTURE != 0;
+
+    let mut known_items = BTreeMap::from([(ArrayKey::Integer(0), (false, get_group_type(false, offset_capture)))]);
+    for ((number, group), position) in (1..).zip(groups).zip(0..) {
+        let possibly_unset = !unmatched_as_null && groups[position..].iter().all(|group| group.optional);
+        let group_type = get_group_type(group.optional && unmatched_as_null, offset_capture);
+
+        insert_group(&mut known_items, number, group, possibly_unset, group_type);
+    }
+
+    get_keyed_array(known_items)
+}
+
+/// Returns the shape of the matches set by `preg_match_all()` with `PREG_PATTERN_ORDER`,
+/// when there is at least one match.
+pub fn get_pattern_order_shape(groups: &[CaptureGroup], flags: i64) -> TAtomic {
+    let unmatched_as_null = flags & PREG_UNMATCHED_AS_NULL != 0;
+    let offset_capture = flags & PREG_OFFSET_CAPTURE != 0;
+
+    let get_list_type = |nullable| {
+        TUnion::from_atomic(TAtomic::Array(TArray::List(TList::new_non_empty(Arc::new(get_group_type(
+            nullable,
+            offset_capture,
+        ))))))
+    };
+
+    let mut known_items = BTreeMap::from([(ArrayKey::Integer(0), (false, get_list_type(false)))]);
+    for (number, group) in (1..).zip(groups) {
+        insert_group(&mut known_items, number, group, false, get_list_type(group.optional && unmatched_as_null));
+    }
+
+    get_keyed_array(known_items)
+}
+
+/// Returns the shape of the matches set by `preg_match_all()` with `PREG_SET_ORDER`,
+/// when there is at least one match.
+pub fn get_set_order_shape(groups: &[CaptureGroup], flags: i64) -> TAtomic {
+    TAtomic::Array(TArray::List(TList::new_non_empty(Arc::new(TUnion::from_atomic(get_match_shape(groups, flags))))))
+}
+
+fn get_group_type(nullable: bool, offset_capture: bool) -> TUnion {
+    let string_type = if nullable { get_nullable_string() } else { get_string() };
+    if !offset_capture {
+        return string_type;
+    }
+
+    TUnion::from_atomic(TAtomic::Array(TArray::List(TList::from_known_elements(BTreeMap::from([
+        (0, (false, string_type)),
+        (1, (false, get_int())),
+    ])))))
+}
+
+fn insert_group(
+    known_items: &mut BTreeMap<ArrayKey, (bool, TUnion)>,
+    number: i64,
+    group: &CaptureGroup,
+    possibly_unset: bool,
+    group_type: TUnion,
+) {
+    if let Some(name) = group.name {
+        known_items.insert(ArrayKey::String(name), (possibly_unset, group_type.clone()));
+    }
+
+    known_items.insert(ArrayKey::Integer(number), (possibly_unset, group_type));
+}
+
+fn get_keyed_array(known_items: BTreeMap<ArrayKey, (bool, TUnion)>) -> TAtomic {
+    TAtomic::Array(TArray::Keyed(TKeyedArray::new().with_known_items(known_items).with_non_empty(true)))
+}
@@ -0,0 +1,11 @@
+//! PHP PCRE function providers.
+
+mod matches;
+mod pattern;
+mod preg_match;
+mod preg_match_all;
+mod preg_replace_callback;
+
+pub use preg_match::PregMatchAssertionProvider;
+pub use preg_match_all::PregMatchAllAssertionProvider;
+pub use preg_replace_callback::PregReplaceCallbackParameterProvider;
@@ -0,0 +1,421 @@
+//! Capture group parsing for literal PCRE patterns.
+//!
+//! Only the structure of a pattern that decides which groups exist and whether
+//! they always participate in a match is understood. Patterns whose groups cannot
+//! be numbered statically, such as branch resets (`(?|...)`), conditionals or
+//! duplicate group names, are rejected so callers fall back to the stub types.
+
+use mago_word::Word;
+use mago_word::word;
+
+/// A capturing group of a pattern.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CaptureGroup {
+    /// The name of the group, for `(?<name>...)`, `(?'name'...)` and `(?P<name>...)`.
+    pub name: Option<Word>,
+    /// Whether the group may not participate in a match, e.g. `(a)?` or `(a)|(b)`.
+    pub optional: bool,
+}
+
+/// A group that is open while parsing.
+#[derive(Default)]
+struct Frame {
+    /// The index of the capturing group this frame opens, if it captures.
+    capture: Option<usize>,
+    /// The indices of every capturing group nested in this frame.
+    members: Vec<usize>,
+    /// Whether this frame contains a top-level `|`.
+    alternation: bool,
+    /// Whether groups in this frame never capture, as in a negative lookaround.
+    negative: bool,
+}
+
+/// Parses the capturing groups of a pattern as passed to the `preg_*` functions,
+/// with its delimiters and modifiers.
+///
+/// Group `n` is returned at index `n - 1`; group `0`, the whole match, is implied.
+/// Returns `None` if the pattern is invalid or not understood.
+#[must_use]
+pub fn parse_capture_groups(pattern: &[u8]) -> Option<Vec<CaptureGroup>> {
+    let (body, modifiers) = split_delimiters(pattern)?;
+
+    let mut extended = false;
+    let mut no_auto_capture = false;
+    for modifier in modifiers {
+        match modifier {
+            b'x' => extended = true,
+            b'n' => no_auto_capture = true,
+            b'i' | b'm' | b's' | b'A' | b'D' | b'S' | b'U' | b'X' | b'J' | b'u' | b'r' | b' ' | b'\n' | b'\r' => {}
+            _ => return None,
+        }
+    }
+
+    let mut groups: Vec<CaptureGroup> = Vec::new();
+    let mut stack = vec![Frame::default()];
+    // The groups closed by the last atom, which a following quantifier applies to.
+    let mut quantifiable: Option<Vec<usize>> = None;
+    let mut i = 0;
+
+    while i < body.len() {
+        let byte = body[i];
+
+        if extended && byte.is_ascii_whitespace() {
+            i += 1;
+            continue;
+        }
+
+        if extended && byte == b'#' {
+            i = body[i..].iter().position(|&b| b == b'\n').map_or(body.len(), |offset| i + offset + 1);
+            continue;
+        }
+
+        match byte {
+            b'\\' => {
+                let escaped = *body.get(i + 1)?;
+                i = if escaped == b'Q' { find(body, i + 2, b"\\E").map_or(body.len(), |end| end + 2) } else { i + 2 };
+                quantifiable = None;
+            }
+            b'[' => {
+                i = skip_class(body, i)?;
+                quantifiable = None;
+            }
+            b'(' => {
+                let frame = match (body.get(i + 1), body.get(i + 2)) {
+                    (Some(b'*'), next) => {
+                        // Backtracking verbs such as `(*SKIP)`; alpha assertions are not supported.
+                        if next.is_some_and(u8::is_ascii_lowercase) {
+                            return None;
+                        }
+
+                        i = skip_to_closing(body, i)?;
+                        quantifiable = None;
+                        continue;
+                    }
+                    (Some(b'?'), Some(kind)) => match kind {
+                        b':' | b'>' | b'=' => {
+                            i += 3;
+                            Frame::default()
+                        }
+                        b'!' => {
+                            i += 3;
+                            Frame { negative: true, ..Frame::default() }
+                        }
+                        b'<' if body.get(i + 3) == Some(&b'=') => {
+                            i += 4;
+                            Frame::default()
+                        }
+                        b'<' if body.get(i + 3) == Some(&b'!') => {
+                            i += 4;
+                            Frame { negative: true, ..Frame::default() }
+                        }
+                        b'<' | b'\'' => {
+                            let (name, end) = read_name(body, i + 3, if *kind == b'<' { b'>' } else { b'\'' })?;
+                            i = end;
+                            open_capture(&mut groups, Some(name))?
+                        }
+                        b'P' if body.get(i + 3) == Some(&b'<') => {
+                            let (name, end) = read_name(body, i + 4, b'>')?;
+                            i = end;
+                            open_capture(&mut groups, Some(name))?
+                        }
+                        // Comments, callouts, back references and recursion do not open a group.
+                        b'#' | b'C' | b'P' | b'R' | b'&' | b'+' | b'0'..=b'9' => {
+                            i = skip_to_closing(body, i)?;
+                            quantifiable = None;
+                            continue;
+                        }
+                        b'-' if body.get(i + 3).is_some_and(u8::is_ascii_digit) => {
+                            i = skip_to_closing(body, i)?;
+                            quantifiable = None;
+                            continue;
+                        }
+                        _ => {
+                            // Inline options, such as `(?i)` or `(?i:...)`. Options that change
+                            // how the rest of the pattern is parsed are not supported.
+                            let mut end = i + 2;
+                            while let Some(&option) = body.get(end) {
+                                match option {
+                                    b')' | b':' => break,
+                                    b'i' | b'm' | b's' | b'U' | b'-' | b'^' => end += 1,
+                                    _ => return None,
+                                }
+                            }
+
+                            let closing = *body.get(end)?;
+                            i = end + 1;
+                            if closing == b')' {
+                                continue;
+                            }
+
+                            Frame::default()
+                        }
+                    },
+                    (Some(b'?'), None) => return None,
+                    _ => {
+                        i += 1;
+                        if no_auto_capture { Frame::default() } else { open_capture(&mut groups, None)? }
+                    }
+                };
+
+                stack.push(frame);
+                quantifiable = None;
+            }
+            b')' => {
+                if stack.len() < 2 {
+                    return None;
+                }
+
+                let frame = stack.pop()?;
+                if frame.alternation || frame.negative {
+                    for &member in &frame.members {
+                        groups[member].optional = true;
+                    }
+                }
+
+                let mut closed = frame.members;
+                closed.extend(frame.capture);
+
+                stack.last_mut()?.members.extend_from_slice(&closed);
+                quantifiable = Some(closed);
+                i += 1;
+            }
+            b'|' => {
+                stack.last_mut()?.alternation = true;
+                quantifiable = None;
+                i += 1;
+            }
+            b'?' | b'*' | b'+' | b'{' => {
+                let (minimum, end) = if byte == b'{' {
+                    match read_braces_minimum(body, i) {
+                        Some(quantifier) => quantifier,
+                        None => {
+                            // Not a quantifier, so a literal `{`.
+                            quantifiable = None;
+                            i += 1;
+                            continue;
+                        }
+                    }
+                } else {
+                    (usize::from(byte == b'+'), i + 1)
+                };
+
+                if minimum == 0
+                    && let Some(closed) = quantifiable.take()
+                {
+                    for member in closed {
+                        groups[member].optional = true;
+                    }
+                }
+
+                // Lazy and possessive quantifiers, such as `*?` or `++`.
+                i = if matches!(body.get(end), Some(b'?' | b'+')) { end + 1 } else { end };
+                quantifiable = None;
+            }
+            _ => {
+                quantifiable = None;
+                i += 1;
+            }
+        }
+    }
+
+    let [root] = <[Frame; 1]>::try_from(stack).ok()?;
+    if root.alternation {
+        for group in &mut groups {
+            group.optional = true;
+        }
+    }
+
+    Some(groups)
+}
+
+/// Splits a pattern into its body and modifiers, the way PHP does.
+fn split_delimiters(pattern: &[u8]) -> Option<(&[u8], &[u8])> {
+    let start = pattern.iter().position(|byte| !byte.is_ascii_whitespace())?;
+    let delimiter = pattern[start];
+    if delimiter.is_ascii_alphanumeric() || delimiter == b'\\' {
+        return None;
+    }
+
+    let closing = match delimiter {
+        b'(' => b')',
+        b'[' => b']',
+        b'{' => b'}',
+        b'<' => b'>',
+        _ => delimiter,
+    };
+
+    let mut depth = 1usize;
+    let mut i = start + 1;
+    while i < pattern.len() {
+        let byte = pattern[i];
+        if byte == b'\\' {
+            i += 2;
+            continue;
+        }
+
+        if byte == closing {
+            depth -= 1;
+            if depth == 0 {
+                return Some((&pattern[start + 1..i], &pattern[i + 1..]));
+            }
+        } else if byte == delimiter {
+            depth += 1;
+        }
+
+        i += 1;
+    }
+
+    None
+}
+
+fn open_capture(groups: &mut Vec<CaptureGroup>, name: Option<Word>) -> Option<Frame> {
+    if let Some(name) = name
+        && groups.iter().any(|group| group.name == Some(name))
+    {
+        return None;
+    }
+
+    groups.push(CaptureGroup { name, optional: false });
+
+    Some(Frame { capture: Some(groups.len() - 1), ..Frame::default() })
+}
+
+/// Reads a group name starting at `start` up to `terminator`, returning the name and the
+/// offset after the terminator.
+fn read_name(body: &[u8], start: usize, terminator: u8) -> Option<(Word, usize)> {
+    let length = body[start..].iter().position(|&byte| byte == terminator)?;
+    let name = &body[start..start + length];
+
+    let valid = name.first().is_some_and(|byte| !byte.is_ascii_digit())
+        && name.iter().all(|byte| byte.is_ascii_alphanumeric() || *byte == b'_');
+    if !valid {
+        return None;
+    }
+
+    Some((word(name), start + length + 1))
+}
+
+/// Skips the character class starting at `start`, returning the offset after it.
+fn skip_class(body: &[u8], start: usize) -> Option<usize> {
+    let mut i = start + 1;
+    if body.get(i) == Some(&b'^') {
+        i += 1;
+    }
+
+    // A `]` right after the opening bracket is a literal.
+    if body.get(i) == Some(&b']') {
+        i += 1;
+    }
+
+    while i < body.len() {
+        match body[i] {
+            b'\\' => i += 2,
+            b'[' if body.get(i + 1) == Some(&b':') => {
+                i = find(body, i + 2, b":]").map_or(i + 1, |end| end + 2);
+            }
+            b']' => return Some(i + 1),
+            _ => i += 1,
+        }
+    }
+
+    None
+}
+
+/// Skips to after the `)` closing the construct starting at `start`.
+fn skip_to_closing(body: &[u8], start: usize) -> Option<usize> {
+    body[start..].iter().position(|&byte| byte == b')').map(|offset| start + offset + 1)
+}
+
+/// Reads a `{n}`, `{n,}`, `{n,m}` or `{,m}` quantifier starting at `start`, returning its
+/// minimum and the offset after it.
+fn read_braces_minimum(body: &[u8], start: usize) -> Option<(usize, usize)> {
+    let length = body[start..].iter().position(|&byte| byte == b'}')?;
+    let inner = &body[start + 1..start + length];
+
+    let (minimum, maximum) = match inner.iter().position(|&byte| byte == b',') {
+        Some(comma) => (&inner[..comma], Some(&inner[comma + 1..])),
+        None => (inner, None),
+    };
+
+    let is_number = |digits: &[u8]| !digits.is_empty() && digits.iter().all(u8::is_ascii_digit);
+    let valid = match maximum {
+        None => is_number(minimum),
+        Some(maximum) => (is_number(minimum) || minimum.is_empty()) && (is_number(maximum) || maximum.is_empty()),
+    } && !(minimum.is_empty() && maximum.is_none_or(<[u8]>::is_empty));
+
+    if !valid {
+        return None;
+    }
+
+    let minimum = minimum.iter().try_fold(0usize, |value, digit| {
+        value.checked_mul(10).and_then(|value| value.checked_add(usize::from(digit - b'0')))
+    })?;
+
+    Some((minimum, start + length + 1))
+}
+
+fn find(haystack: &[u8], start: usize, needle: &[u8]) -> Option<usize> {
+    haystack.get(start..)?.windows(needle.len()).position(|window| window == needle).map(|offset| start + offset)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn groups(pattern: &str) -> Option<Vec<(Option<String>, bool)>> {
+        parse_capture_groups(pattern.as_bytes()).map(|groups| {
+            groups.into_iter().map(|group| (group.name.map(|name| name.to_string()), group.optional)).collect()
+        })
+    }
+
+    #[test]
+    fn test_numbered_and_named_groups() {
+        assert_eq!(groups("/(\\d+)-(?<year>\\d{4})/"), Some(vec![(None, false), (Some("year".to_owned()), false)]));
+        assert_eq!(
+            groups("#(?P<a>x)(?'b'y)(?:z)(?=w)(?>v)#i"),
+            Some(vec![(Some("a".to_owned()), false), (Some("b".to_owned()), false)])
+        );
+        assert_eq!(groups("{a(b)}"), Some(vec![(None, false)]));
+    }
+
+    #[test]
+    fn test_optional_groups() {
+        assert_eq!(
+            groups("/(a)?(b)*(c)+(d){0,2}(e){1}/"),
+            Some(vec![(None, true), (None, true), (None, false), (None, true), (None, false),])
+        );
+        assert_eq!(groups("/(a)|(b)/"), Some(vec![(None, true), (None, true)]));
+        assert_eq!(groups("/(x(a)|(b))/"), Some(vec![(None, false), (None, true), (None, true)]));
+        assert_eq!(groups("/((a))?/"), Some(vec![(None, true), (None, true)]));
+        assert_eq!(groups("/(?!(a))b/"), Some(vec![(None, true)]));
+        assert_eq!(groups("/(a)+?(b)??/"), Some(vec![(None, false), (None, true)]));
+    }
+
+    #[test]
+    fn test_escapes_and_classes_do_not_open_groups() {
+        assert_eq!(groups("/\\(a\\)[(|)]\\Q(b)|\\E(c)/"), Some(vec![(None, false)]));
+        assert_eq!(groups("/[]()][^]|][[:alpha:]](d)/"), Some(vec![(None, false)]));
+        assert_eq!(groups("/(a)(?1)(?P=x)(?#(b|)/"), Some(vec![(None, false)]));
+        assert_eq!(groups("/a{,}(b)/"), Some(vec![(None, false)]));
+    }
+
+    #[test]
+    fn test_modifiers() {
+        assert_eq!(groups("/ (a) # (b)\n | c /x"), Some(vec![(None, true)]));
+        assert_eq!(groups("/(a)(?<b>c)/n"), Some(vec![(Some("b".to_owned()), false)]));
+        assert_eq!(groups("/(?i)(a)(?i:b)/"), Some(vec![(None, false)]));
+    }
+
+    #[test]
+    fn test_unsupported_patterns() {
+        assert_eq!(groups("/(?|(a)|(b))/"), None);
+        assert_eq!(groups("/(?<a>x)(?<a>y)/J"), None);
+        assert_eq!(groups("/(?(1)a|b)/"), None);
+        assert_eq!(groups("/(?x)(a)/"), None);
+        assert_eq!(groups("/(a/"), None);
+        assert_eq!(groups("/a)/"), None);
+        assert_eq!(groups("/a/e"), None);
+        assert_eq!(groups("abc"), None);
+        assert_eq!(groups("/a"), None);
+    }
+}
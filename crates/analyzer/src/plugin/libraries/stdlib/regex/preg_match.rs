@@ -0,0 +1,61 @@
+//! `preg_match()` assertion provider.
+
+use mago_codex::assertion::Assertion;
+use mago_word::word;
+
+use crate::plugin::context::InvocationInfo;
+use crate::plugin::context::ProviderContext;
+use crate::plugin::libraries::stdlib::regex::matches::PREG_OFFSET_CAPTURE;
+use crate::plugin::libraries::stdlib::regex::matches::PREG_UNMATCHED_AS_NULL;
+use crate::plugin::libraries::stdlib::regex::matches::get_flags;
+use crate::plugin::libraries::stdlib::regex::matches::get_match_shape;
+use crate::plugin::libraries::stdlib::regex::matches::get_pattern_capture_groups;
+use crate::plugin::provider::Provider;
+use crate::plugin::provider::ProviderMeta;
+use crate::plugin::provider::assertion::FunctionAssertionProvider;
+use crate::plugin::provider::assertion::InvocationAssertions;
+use crate::plugin::provider::function::FunctionTarget;
+
+static META: ProviderMeta = ProviderMeta::new(
+    "php::regex::preg_match",
+    "preg_match",
+    "Narrows `$matches` to the capture groups of a literal pattern when there is a match",
+);
+
+/// Provider for the `preg_match()` function.
+///
+/// When the call returns a truthy value, `$matches` is narrowed to an array shape with
+/// a key for each capture group of the pattern.
+#[derive(Default)]
+pub struct PregMatchAssertionProvider;
+
+impl Provider for PregMatchAssertionProvider {
+    fn meta() -> &'static ProviderMeta {
+        &META
+    }
+}
+
+impl FunctionAssertionProvider for PregMatchAssertionProvider {
+    fn targets() -> FunctionTarget {
+        FunctionTarget::Exact(b"preg_match")
+    }
+
+    fn get_assertions(
+        &self,
+        context: &ProviderContext<'_, '_, '_>,
+        invocation: &InvocationInfo<'_, '_, '_>,
+    ) -> Option<InvocationAssertions> {
+        invocation.get_argument(2, &[b"matches"])?;
+
+        let flags = get_flags(context, invocation, 3)?;
+        if flags & !(PREG_OFFSET_CAPTURE | PREG_UNMATCHED_AS_NULL) != 0 {
+            return None;
+        }
+
+        let groups = get_pattern_capture_groups(context, invocation)?;
+
+        let mut result = InvocationAssertions::new();
+        result.add_if_true(word("$matches"), vec![Assertion::IsType(get_match_shape(&groups, flags))]);
+        Some(result)
+    }
+}
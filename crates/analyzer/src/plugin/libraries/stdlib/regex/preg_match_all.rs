@@ -0,0 +1,70 @@
+//! `preg_match_all()` assertion provider.
+
+use mago_codex::assertion::Assertion;
+use mago_word::word;
+
+use crate::plugin::context::InvocationInfo;
+use crate::plugin::context::ProviderContext;
+use crate::plugin::libraries::stdlib::regex::matches::PREG_OFFSET_CAPTURE;
+use crate::plugin::libraries::stdlib::regex::matches::PREG_PATTERN_ORDER;
+use crate::plugin::libraries::stdlib::regex::matches::PREG_SET_ORDER;
+use crate::plugin::libraries::stdlib::regex::matches::PREG_UNMATCHED_AS_NULL;
+use crate::plugin::libraries::stdlib::regex::matches::get_flags;
+use crate::plugin::libraries::stdlib::regex::matches::get_pattern_capture_groups;
+use crate::plugin::libraries::stdlib::regex::matches::get_pattern_order_shape;
+use crate::plugin::libraries::stdlib::regex::matches::get_set_order_shape;
+use crate::plugin::provider::Provider;
+use crate::plugin::provider::ProviderMeta;
+use crate::plugin::provider::assertion::FunctionAssertionProvider;
+use crate::plugin::provider::assertion::InvocationAssertions;
+use crate::plugin::provider::function::FunctionTarget;
+
+static META: ProviderMeta = ProviderMeta::new(
+    "php::regex::preg_match_all",
+    "preg_match_all",
+    "Narrows `$matches` to the capture groups of a literal pattern when there are matches",
+);
+
+/// Provider for the `preg_match_all()` function.
+///
+/// When the call returns a truthy value, `$matches` is narrowed to an array shape with
+/// a non-empty list for each capture group of the pattern, or with `PREG_SET_ORDER`, to
+/// a non-empty list of shapes with a key for each capture group.
+#[derive(Default)]
+pub struct PregMatchAllAssertionProvider;
+
+impl Provider for PregMatchAllAssertionProvider {
+    fn meta() -> &'static ProviderMeta {
+        &META
+    }
+}
+
+impl FunctionAssertionProvider for PregMatchAllAssertionProvider {
+    fn targets() -> FunctionTarget {
+        FunctionTarget::Exact(b"preg_match_all")
+    }
+
+    fn get_assertions(
+        &self,
+        context: &ProviderContext<'_, '_, '_>,
+        invocation: &InvocationInfo<'_, '_, '_>,
+    ) -> Option<InvocationAssertions> {
+        invocation.get_argument(2, &[b"matches"])?;
+
+        let flags = get_flags(context, invocation, 3)?;
+        if flags & !(PREG_PATTERN_ORDER | PREG_SET_ORDER | PREG_OFFSET_CAPTURE | PREG_UNMATCHED_AS_NULL) != 0 {
+            return None;
+        }
+
+        let groups = get_pattern_capture_groups(context, invocation)?;
+        let shape = match flags & (PREG_PATTERN_ORDER | PREG_SET_ORDER) {
+            PREG_SET_ORDER => get_set_order_shape(&groups, flags),
+            0 | PREG_PATTERN_ORDER => get_pattern_order_shape(&groups, flags),
+            _ => return None,
+        };
+
+        let mut result = InvocationAssertions::new();
+        result.add_if_true(word("$matches"), vec![Assertion::IsType(shape)]);
+        Some(result)
+    }
+}
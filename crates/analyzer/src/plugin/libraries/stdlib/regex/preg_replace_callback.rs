@@ -0,0 +1,76 @@
+//! `preg_replace_callback()` parameter type provider.
+
+use mago_codex::ttype::atomic::TAtomic;
+use mago_codex::ttype::atomic::callable::TCallable;
+use mago_codex::ttype::union::TUnion;
+
+use crate::plugin::context::InvocationInfo;
+use crate::plugin::context::ProviderContext;
+use crate::plugin::libraries::stdlib::regex::matches::PREG_OFFSET_CAPTURE;
+use crate::plugin::libraries::stdlib::regex::matches::PREG_UNMATCHED_AS_NULL;
+use crate::plugin::libraries::stdlib::regex::matches::get_flags;
+use crate::plugin::libraries::stdlib::regex::matches::get_match_shape;
+use crate::plugin::libraries::stdlib::regex::matches::get_pattern_capture_groups;
+use crate::plugin::provider::Provider;
+use crate::plugin::provider::ProviderMeta;
+use crate::plugin::provider::function::FunctionTarget;
+use crate::plugin::provider::parameter::FunctionParameterTypeProvider;
+
+static META: ProviderMeta = ProviderMeta::new(
+    "php::regex::preg_replace_callback",
+    "preg_replace_callback",
+    "Types the callback's `$matches` with the capture groups of a literal pattern",
+);
+
+/// Provider for the `preg_replace_callback()` function.
+///
+/// Gives the matches passed to the callback an array shape with a key for each
+/// capture group of the pattern.
+#[derive(Default)]
+pub struct PregReplaceCallbackParameterProvider;
+
+impl Provider for PregReplaceCallbackParameterProvider {
+    fn meta() -> &'static ProviderMeta {
+        &META
+    }
+}
+
+impl FunctionParameterTypeProvider for PregReplaceCallbackParameterProvider {
+    fn targets() -> FunctionTarget {
+        FunctionTarget::Exact(b"preg_replace_callback")
+    }
+
+    fn get_parameter_type(
+        &self,
+        context: &ProviderContext<'_, '_, '_>,
+        invocation: &InvocationInfo<'_, '_, '_>,
+        parameter_name: &[u8],
+        parameter_type: &TUnion,
+    ) -> Option<TUnion> {
+        if parameter_name != b"$callback" {
+            return None;
+        }
+
+        let flags = get_flags(context, invocation, 5)?;
+        if flags & !(PREG_OFFSET_CAPTURE | PREG_UNMATCHED_AS_NULL) != 0 {
+            return None;
+        }
+
+        let groups = get_pattern_capture_groups(context, invocation)?;
+        let matches_type = TUnion::from_atomic(get_match_shape(&groups, flags));
+
+        let mut refined_type = parameter_type.clone();
+        let mut refined = false;
+        for atomic in refined_type.types.to_mut() {
+            if let TAtomic::Callable(TCallable::Signature(signature)) = atomic
+                && let Some(matches_parameter) = signature.get_parameters_mut().first_mut()
+                && let Some(matches_parameter_type) = matches_parameter.get_type_signature_mut()
+            {
+                *matches_parameter_type = matches_type.clone();
+                refined = true;
+            }
+        }
+
+        refined.then_some(refined_type)
+    }
+}
@@ -0,0 +1,139 @@
+//! Service container return type providers.
+//!
+//! Services and parameters are fetched from Symfony's container by name, so their types are
+//! only known from the compiled container. When the analyzer is given its XML dump, these
+//! providers type `$container->get('mailer')` as the class of the `mailer` service, and
+//! `$this->getParameter('kernel.debug')` as the kind of value of the `kernel.debug` parameter.
+
+use mago_codex::ttype::atomic::TAtomic;
+use mago_codex::ttype::atomic::object::TObject;
+use mago_codex::ttype::atomic::object::named::TNamedObject;
+use mago_codex::ttype::get_bool;
+use mago_codex::ttype::get_float;
+use mago_codex::ttype::get_int;
+use mago_codex::ttype::get_mixed_keyed_array;
+use mago_codex::ttype::get_null;
+use mago_codex::ttype::get_string;
+use mago_codex::ttype::union::TUnion;
+use mago_word::word;
+
+use crate::container::Parameter;
+use crate::plugin::context::InvocationInfo;
+use crate::plugin::context::ProviderContext;
+use crate::plugin::libraries::symfony::ABSTRACT_CONTROLLER;
+use crate::plugin::provider::Provider;
+use crate::plugin::provider::ProviderMeta;
+use crate::plugin::provider::method::MethodReturnTypeProvider;
+use crate::plugin::provider::method::MethodTarget;
+
+const CONTAINER_INTERFACE: &[u8] = b"Symfony\\Component\\DependencyInjection\\ContainerInterface";
+const PSR_CONTAINER_INTERFACE: &[u8] = b"Psr\\Container\\ContainerInterface";
+const PARAMETER_BAG_INTERFACE: &[u8] = b"Symfony\\Component\\DependencyInjection\\ParameterBag\\ParameterBagInterface";
+
+static SERVICE_META: ProviderMeta = ProviderMeta::new(
+    "symfony::container::get",
+    "Container services",
+    "Returns the class of the service fetched from the compiled container",
+);
+
+static SERVICE_TARGETS: [MethodTarget; 1] = [MethodTarget::any_class(b"get")];
+
+static PARAMETER_META: ProviderMeta = ProviderMeta::new(
+    "symfony::container::parameter",
+    "Container parameters",
+    "Returns the type of the parameter fetched from the compiled container",
+);
+
+static PARAMETER_TARGETS: [MethodTarget; 2] =
+    [MethodTarget::any_class(b"getParameter"), MethodTarget::any_class(b"get")];
+
+/// Provider for `ContainerInterface::get()`, typed from the compiled container.
+#[derive(Default)]
+pub struct ContainerServiceProvider;
+
+impl Provider for ContainerServiceProvider {
+    fn meta() -> &'static ProviderMeta {
+        &SERVICE_META
+    }
+}
+
+impl MethodReturnTypeProvider for ContainerServiceProvider {
+    fn targets() -> &'static [MethodTarget] {
+        &SERVICE_TARGETS
+    }
+
+    fn get_return_type(
+        &self,
+        context: &ProviderContext<'_, '_, '_>,
+        class_name: &[u8],
+        _method_name: &[u8],
+        invocation: &InvocationInfo<'_, '_, '_>,
+    ) -> Option<TUnion> {
+        let container = context.container()?;
+        if !context.is_instance_of(class_name, CONTAINER_INTERFACE)
+            && !context.is_instance_of(class_name, PSR_CONTAINER_INTERFACE)
+        {
+            return None;
+        }
+
+        let id_type = context.get_expression_type(invocation.get_argument(0, &[b"id"])?)?;
+        let id = match id_type.get_single_literal_string_value() {
+            Some(id) => String::from_utf8_lossy(id).into_owned(),
+            None => id_type.get_single_class_string_value()?.to_string(),
+        };
+
+        let service_class = container.service_class(&id)?;
+
+        Some(TUnion::from_atomic(TAtomic::Object(TObject::Named(TNamedObject::new(word(service_class.as_bytes()))))))
+    }
+}
+
+/// Provider for `getParameter()` on controllers and containers, and `ParameterBagInterface::get()`,
+/// typed from the compiled container.
+#[derive(Default)]
+pub struct ContainerParameterProvider;
+
+impl Provider for ContainerParameterProvider {
+    fn meta() -> &'static ProviderMeta {
+        &PARAMETER_META
+    }
+}
+
+impl MethodReturnTypeProvider for ContainerParameterProvider {
+    fn targets() -> &'static [MethodTarget] {
+        &PARAMETER_TARGETS
+    }
+
+    fn get_return_type(
+        &self,
+        context: &ProviderContext<'_, '_, '_>,
+        class_name: &[u8],
+        method_name: &[u8],
+        invocation: &InvocationInfo<'_, '_, '_>,
+    ) -> Option<TUnion> {
+        let container = context.container()?;
+        let is_parameter_getter = if method_name.eq_ignore_ascii_case(b"get") {
+            context.is_instance_of(class_name, PARAMETER_BAG_INTERFACE)
+        } else {
+            context.is_instance_of(class_name, ABSTRACT_CONTROLLER)
+                || context.is_instance_of(class_name, CONTAINER_INTERFACE)
+        };
+
+        if !is_parameter_getter {
+            return None;
+        }
+
+        let name_argument = invocation.get_argument(0, &[b"name"])?;
+        let name = context.get_expression_type(name_argument)?.get_single_literal_string_value()?;
+
+        Some(match container.parameter(&String::from_utf8_lossy(name))? {
+            Parameter::Null => get_null(),
+            Parameter::Bool => get_bool(),
+            Parameter::Int => get_int(),
+            Parameter::Float => get_float(),
+            Parameter::String => get_string(),
+            Parameter::Collection => get_mixed_keyed_array(),
+            Parameter::Unknown => return None,
+        })
+    }
+}
@@ -0,0 +1,298 @@
+//! Event listener checks for `#[AsEventListener]`.
+//!
+//! A listener registered with `#[AsEventListener]` is called with the event object dispatched
+//! for the event it listens to. On a method, the attribute registers that method. On a class, it
+//! registers the method named by its `method` argument, or `on` followed by the camelized event
+//! name, falling back to `__invoke()`. The listener method must exist, be public, and accept the
+//! dispatched event as its first parameter.
+
+use mago_codex::metadata::CodebaseMetadata;
+use mago_codex::metadata::attribute::AttributeArgumentMetadata;
+use mago_codex::metadata::attribute::AttributeMetadata;
+use mago_codex::metadata::class_like::ClassLikeMetadata;
+use mago_codex::metadata::function_like::FunctionLikeMetadata;
+use mago_codex::ttype::TType;
+use mago_codex::ttype::atomic::TAtomic;
+use mago_codex::ttype::atomic::object::TObject;
+use mago_reporting::Annotation;
+use mago_reporting::Issue;
+use mago_syntax::cst::Class;
+use mago_syntax::cst::ClassLikeMember;
+
+use crate::code::IssueCode;
+use crate::plugin::context::HookContext;
+use crate::plugin::hook::ClassDeclarationHook;
+use crate::plugin::hook::HookResult;
+use crate::plugin::provider::Provider;
+use crate::plugin::provider::ProviderMeta;
+
+static META: ProviderMeta = ProviderMeta::new(
+    "symfony::event-listener",
+    "Event listeners",
+    "Checks #[AsEventListener] listeners against the events they listen to",
+);
+
+const AS_EVENT_LISTENER: &[u8] = b"Symfony\\Component\\EventDispatcher\\Attribute\\AsEventListener";
+
+/// The events dispatched by the HTTP kernel and the console, by name.
+const KNOWN_EVENTS: [(&str, &str); 11] = [
+    ("kernel.request", "Symfony\\Component\\HttpKernel\\Event\\RequestEvent"),
+    ("kernel.controller", "Symfony\\Component\\HttpKernel\\Event\\ControllerEvent"),
+    ("kernel.controller_arguments", "Symfony\\Component\\HttpKernel\\Event\\ControllerArgumentsEvent"),
+    ("kernel.view", "Symfony\\Component\\HttpKernel\\Event\\ViewEvent"),
+    ("kernel.response", "Symfony\\Component\\HttpKernel\\Event\\ResponseEvent"),
+    ("kernel.finish_request", "Symfony\\Component\\HttpKernel\\Event\\FinishRequestEvent"),
+    ("kernel.terminate", "Symfony\\Component\\HttpKernel\\Event\\TerminateEvent"),
+    ("kernel.exception", "Symfony\\Component\\HttpKernel\\Event\\ExceptionEvent"),
+    ("console.command", "Symfony\\Component\\Console\\Event\\ConsoleCommandEvent"),
+    ("console.error", "Symfony\\Component\\Console\\Event\\ConsoleErrorEvent"),
+    ("console.terminate", "Symfony\\Component\\Console\\Event\\ConsoleTerminateEvent"),
+];
+
+/// The event a listener listens to, as read from its attribute.
+struct Event {
+    /// The name of the event, when it is given as a literal.
+    name: Option<String>,
+    /// The class of the event object, when it is known.
+    class: Option<String>,
+    /// Whether the attribute names no event, leaving it to the listener's first parameter.
+    is_inferred: bool,
+}
+
+/// Checks the listeners registered with `#[AsEventListener]` on a class and its methods.
+#[derive(Default)]
+pub struct EventListenerHook;
+
+impl Provider for EventListenerHook {
+    fn meta() -> &'static ProviderMeta {
+        &META
+    }
+}
+
+impl ClassDeclarationHook for EventListenerHook {
+    fn on_enter_class(
+        &self,
+        class: &Class<'_>,
+        metadata: &ClassLikeMetadata,
+        context: &mut HookContext<'_, '_>,
+    ) -> HookResult<()> {
+        let codebase = context.codebase();
+
+        for attribute in metadata.attributes.iter().filter(|attribute| is_listener(attribute)) {
+            let event = event(codebase, attribute);
+            let Some(method) = class_listener(context, metadata, attribute, &event) else {
+                continue;
+            };
+
+            check_listener(context, attribute, method, &event);
+        }
+
+        for member in class.members.iter() {
+            let ClassLikeMember::Method(method) = member else {
+                continue;
+            };
+
+            let Some(method) = codebase.get_method(metadata.name.as_bytes(), method.name.value) else {
+                continue;
+            };
+
+            for attribute in method.attributes.iter().filter(|attribute| is_listener(attribute)) {
+                check_listener(context, attribute, method, &event(codebase, attribute));
+            }
+        }
+
+        Ok(())
+    }
+}
+
+/// Resolves the method registered by a class-level `#[AsEventListener]`, reporting it when it
+/// does not exist.
+fn class_listener<'ctx>(
+    context: &HookContext<'ctx, '_>,
+    metadata: &ClassLikeMetadata,
+    attribute: &AttributeMetadata,
+    event: &Event,
+) -> Option<&'ctx FunctionLikeMetadata> {
+    let codebase = context.codebase();
+    let class = metadata.name.as_bytes();
+
+    let method = match string_argument(attribute, b"method", 1) {
+        Some(method) => method,
+        None => {
+            if let Some(name) = &event.name
+                && let Some(method) = codebase.get_declaring_method(class, listener_method(name).as_bytes())
+            {
+                return Some(method);
+            }
+
+            "__invoke".to_string()
+        }
+    };
+
+    if let Some(method) = codebase.get_declaring_method(class, method.as_bytes()) {
+        return Some(method);
+    }
+
+    context.report(
+        IssueCode::InvalidEventListener,
+        Issue::error(format!("Event listener method `{}::{method}()` does not exist.", metadata.original_name))
+            .with_annotation(Annotation::primary(attribute.span).with_message("This listener is registered here"))
+            .with_note(
+                "Without a `method` argument, the listener is the `on` method named after the event, like `onKernelRequest()`, or `__invoke()`.",
+            )
+            .with_help("Add the listener method, or name an existing method in the `method` argument."),
+    );
+
+    None
+}
+
+fn check_listener(
+    context: &HookContext<'_, '_>,
+    attribute: &AttributeMetadata,
+    method: &FunctionLikeMetadata,
+    event: &Event,
+) {
+    let codebase = context.codebase();
+    let method_name = method.original_name;
+
+    if method.method_metadata.as_ref().is_some_and(|method_metadata| !method_metadata.visibility.is_public()) {
+        context.report(
+            IssueCode::InvalidEventListener,
+            Issue::error(format!("Event listener method `{method_name}()` is not public."))
+                .with_annotation(Annotation::primary(attribute.span).with_message("This listener is registered here"))
+                .with_help("Make the listener method public, as the event dispatcher calls it from outside the class."),
+        );
+
+        return;
+    }
+
+    let parameter = method.parameters.first();
+    let parameter_type = parameter.and_then(|parameter| parameter.type_declaration_metadata.as_ref());
+
+    let event_class = match &event.class {
+        Some(event_class) => event_class.as_str(),
+        None if !event.is_inferred => return,
+        None => {
+            if parameter_type.and_then(|parameter_type| parameter_type.type_union.get_single_named_object()).is_none() {
+                context.report(
+                    IssueCode::InvalidEventListener,
+                    Issue::error(format!("Cannot infer the event listened to by `{method_name}()`."))
+                        .with_annotation(
+                            Annotation::primary(attribute.span).with_message("This listener does not name its event"),
+                        )
+                        .with_help(
+                            "Pass the event to the attribute, or type the first parameter with the event class.",
+                        ),
+                );
+            }
+
+            return;
+        }
+    };
+
+    let (Some(parameter), Some(parameter_type)) = (parameter, parameter_type) else {
+        return;
+    };
+
+    if !codebase.class_like_exists(event_class.as_bytes())
+        || accepts(codebase, &parameter_type.type_union.types, event_class)
+    {
+        return;
+    }
+
+    context.report(
+        IssueCode::InvalidEventListener,
+        Issue::error(format!(
+            "Event listener `{method_name}()` does not accept the `{event_class}` event it listens to."
+        ))
+        .with_annotation(
+            Annotation::primary(parameter.span)
+                .with_message(format!("This parameter is `{}`", parameter_type.type_union.get_id())),
+        )
+        .with_annotation(
+            Annotation::secondary(attribute.span).with_message(format!("The listener is called with `{event_class}`")),
+        )
+        .with_help("Type the first parameter with the event class, or listen to the event it accepts."),
+    );
+}
+
+/// Whether a parameter of the given type accepts an object of `event_class`.
+fn accepts(codebase: &CodebaseMetadata, types: &[TAtomic], event_class: &str) -> bool {
+    types.iter().any(|atomic| match atomic {
+        TAtomic::Object(TObject::Named(named)) => {
+            codebase.is_instance_of(event_class.as_bytes(), named.get_name().as_bytes())
+        }
+        TAtomic::Object(TObject::Any) | TAtomic::Mixed(_) => true,
+        _ => false,
+    })
+}
+
+fn is_listener(attribute: &AttributeMetadata) -> bool {
+    attribute.name.as_bytes().eq_ignore_ascii_case(AS_EVENT_LISTENER)
+}
+
+/// The event named by the `event` argument of an attribute.
+fn event(codebase: &CodebaseMetadata, attribute: &AttributeMetadata) -> Event {
+    if argument(attribute, b"event", 0).is_none() {
+        return Event { name: None, class: None, is_inferred: true };
+    }
+
+    if let Some(class) = class_string_argument(attribute, b"event", 0) {
+        return Event { name: Some(class.clone()), class: Some(class), is_inferred: false };
+    }
+
+    let Some(name) = string_argument(attribute, b"event", 0) else {
+        return Event { name: None, class: None, is_inferred: false };
+    };
+
+    let class = match KNOWN_EVENTS.iter().find(|(event, _)| *event == name) {
+        Some((_, class)) => Some((*class).to_string()),
+        None => codebase.get_class_like(name.as_bytes()).map(|class| class.original_name.to_string()),
+    };
+
+    Event { name: Some(name), class, is_inferred: false }
+}
+
+/// The listener method Symfony derives from an event name: `kernel.request` is listened to by
+/// `onKernelRequest()`.
+fn listener_method(event: &str) -> String {
+    let mut method = String::from("on");
+    let mut previous = None;
+
+    for character in event.chars() {
+        let at_boundary = previous.is_none_or(|previous: char| !previous.is_ascii_alphanumeric());
+        if character.is_ascii_alphanumeric() {
+            method.push(if at_boundary { character.to_ascii_uppercase() } else { character });
+        }
+
+        previous = Some(character);
+    }
+
+    method
+}
+
+/// The literal string passed to an attribute, by name or at the given position.
+fn string_argument(attribute: &AttributeMetadata, name: &[u8], position: usize) -> Option<String> {
+    let value_type = argument(attribute, name, position)?.value_type.as_ref()?;
+
+    Some(String::from_utf8_lossy(value_type.get_single_literal_string_value()?).into_owned())
+}
+
+/// The class named by a `Foo::class` argument of an attribute, by name or at the given position.
+fn class_string_argument(attribute: &AttributeMetadata, name: &[u8], position: usize) -> Option<String> {
+    let value_type = argument(attribute, name, position)?.value_type.as_ref()?;
+
+    Some(value_type.get_single_class_string_value()?.to_string())
+}
+
+fn argument<'attribute>(
+    attribute: &'attribute AttributeMetadata,
+    name: &[u8],
+    position: usize,
+) -> Option<&'attribute AttributeArgumentMetadata> {
+    attribute
+        .arguments
+        .iter()
+        .find(|argument| argument.name.is_some_and(|argument_name| argument_name.as_bytes() == name))
+        .or_else(|| attribute.arguments.iter().filter(|argument| argument.name.is_none()).nth(position))
+}
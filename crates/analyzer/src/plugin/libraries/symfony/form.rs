@@ -0,0 +1,138 @@
+//! Form data return type providers.
+//!
+//! A form type maps its data to the class it declares as `data_class` in `configureOptions()`.
+//! Forms created from such a type are typed as `FormInterface<Data>`, so that `getData()` returns
+//! the data class, or `null` as long as no data was given to the form.
+
+use mago_codex::ttype::atomic::TAtomic;
+use mago_codex::ttype::atomic::object::TObject;
+use mago_codex::ttype::atomic::object::named::TNamedObject;
+use mago_codex::ttype::expander::StaticClassType;
+use mago_codex::ttype::union::TUnion;
+use mago_word::word;
+
+use crate::plugin::context::InvocationInfo;
+use crate::plugin::context::ProviderContext;
+use crate::plugin::libraries::symfony::ABSTRACT_CONTROLLER;
+use crate::plugin::provider::Provider;
+use crate::plugin::provider::ProviderMeta;
+use crate::plugin::provider::method::MethodReturnTypeProvider;
+use crate::plugin::provider::method::MethodTarget;
+
+const FORM_INTERFACE: &[u8] = b"Symfony\\Component\\Form\\FormInterface";
+const FORM_FACTORY_INTERFACE: &[u8] = b"Symfony\\Component\\Form\\FormFactoryInterface";
+
+static CREATE_FORM_META: ProviderMeta = ProviderMeta::new(
+    "symfony::form::create",
+    "Form creation",
+    "Returns a form typed with the data class of its form type",
+);
+
+static CREATE_FORM_TARGETS: [MethodTarget; 3] = [
+    MethodTarget::any_class(b"createForm"),
+    MethodTarget::any_class(b"create"),
+    MethodTarget::any_class(b"createNamed"),
+];
+
+static GET_DATA_META: ProviderMeta =
+    ProviderMeta::new("symfony::form::get-data", "FormInterface::getData", "Returns the data of a typed form");
+
+static GET_DATA_TARGETS: [MethodTarget; 1] = [MethodTarget::any_class(b"getData")];
+
+/// Provider for `AbstractController::createForm()`, and `FormFactoryInterface::create()` and
+/// `createNamed()`.
+///
+/// `createForm(UserType::class, $user)` returns `FormInterface<User>` when `UserType` declares
+/// `User` as its `data_class`, and `FormInterface<User|null>` when no data is passed.
+#[derive(Default)]
+pub struct CreateFormProvider;
+
+impl Provider for CreateFormProvider {
+    fn meta() -> &'static ProviderMeta {
+        &CREATE_FORM_META
+    }
+}
+
+impl MethodReturnTypeProvider for CreateFormProvider {
+    fn targets() -> &'static [MethodTarget] {
+        &CREATE_FORM_TARGETS
+    }
+
+    fn get_return_type(
+        &self,
+        context: &ProviderContext<'_, '_, '_>,
+        class_name: &[u8],
+        method_name: &[u8],
+        invocation: &InvocationInfo<'_, '_, '_>,
+    ) -> Option<TUnion> {
+        let (type_position, data_position) = match method_name.to_ascii_lowercase().as_slice() {
+            b"createform" if context.is_instance_of(class_name, ABSTRACT_CONTROLLER) => (0, 1),
+            b"create" if context.is_instance_of(class_name, FORM_FACTORY_INTERFACE) => (0, 1),
+            b"createnamed" if context.is_instance_of(class_name, FORM_FACTORY_INTERFACE) => (1, 2),
+            _ => return None,
+        };
+
+        let data_type = invocation
+            .get_argument(data_position, &[b"data"])
+            .and_then(|argument| context.get_expression_type(argument))
+            .filter(|data_type| !data_type.has_nullish());
+
+        let data_class = invocation
+            .get_argument(type_position, &[b"type"])
+            .and_then(|argument| context.get_expression_type(argument)?.get_single_class_string_value())
+            .and_then(|form_type| context.forms()?.data_class(form_type.as_bytes()));
+
+        let data = match (data_class, data_type) {
+            (Some(data_class), data_type) => {
+                let data = named_object(data_class.as_bytes());
+
+                if data_type.is_some() { data } else { data.as_nullable() }
+            }
+            (None, Some(data_type)) if data_type.get_single_named_object().is_some() => data_type.clone(),
+            (None, _) => return None,
+        };
+
+        Some(TUnion::from_atomic(TAtomic::Object(TObject::Named(
+            TNamedObject::new(word(FORM_INTERFACE)).with_type_parameters(Some(vec![data])),
+        ))))
+    }
+}
+
+/// Provider for `FormInterface::getData()` on forms typed by [`CreateFormProvider`].
+#[derive(Default)]
+pub struct FormDataProvider;
+
+impl Provider for FormDataProvider {
+    fn meta() -> &'static ProviderMeta {
+        &GET_DATA_META
+    }
+}
+
+impl MethodReturnTypeProvider for FormDataProvider {
+    fn targets() -> &'static [MethodTarget] {
+        &GET_DATA_TARGETS
+    }
+
+    fn get_return_type(
+        &self,
+        context: &ProviderContext<'_, '_, '_>,
+        _class_name: &[u8],
+        _method_name: &[u8],
+        invocation: &InvocationInfo<'_, '_, '_>,
+    ) -> Option<TUnion> {
+        let method_context = invocation.inner().target.get_method_context()?;
+        let StaticClassType::Object(TObject::Named(form)) = &method_context.class_type else {
+            return None;
+        };
+
+        if !context.is_instance_of(form.get_name().as_bytes(), FORM_INTERFACE) {
+            return None;
+        }
+
+        form.get_type_parameters()?.first().cloned()
+    }
+}
+
+fn named_object(name: &[u8]) -> TUnion {
+    TUnion::from_atomic(TAtomic::Object(TObject::Named(TNamedObject::new(word(name)))))
+}
@@ -0,0 +1,51 @@
+//! Symfony framework providers.
+//!
+//! Besides checking controllers against the Twig templates they render, these providers type
+//! the services and parameters fetched from the compiled service container, the data of forms
+//! created from form types declaring a `data_class`, and check the listeners registered with
+//! `#[AsEventListener]` against the events they listen to.
+
+mod container;
+mod event_listener;
+mod form;
+mod render;
+
+pub use container::ContainerParameterProvider;
+pub use container::ContainerServiceProvider;
+pub use event_listener::EventListenerHook;
+pub use form::CreateFormProvider;
+pub use form::FormDataProvider;
+pub use render::TwigRenderHook;
+
+use crate::plugin::Plugin;
+use crate::plugin::PluginMeta;
+use crate::plugin::PluginRegistry;
+
+const ABSTRACT_CONTROLLER: &[u8] = b"Symfony\\Bundle\\FrameworkBundle\\Controller\\AbstractController";
+
+/// Plugin providing type inference and checks for Symfony controllers, containers, forms, and
+/// event listeners.
+pub struct SymfonyPlugin;
+
+static META: PluginMeta = PluginMeta::new(
+    "symfony",
+    "Symfony",
+    "Type providers for the service container and forms, and checks for render() calls and event listeners",
+    &["symfony-framework"],
+    false,
+);
+
+impl Plugin for SymfonyPlugin {
+    fn meta(&self) -> &'static PluginMeta {
+        &META
+    }
+
+    fn register(&self, registry: &mut PluginRegistry) {
+        registry.register_method_call_hook(TwigRenderHook);
+        registry.register_method_provider(ContainerServiceProvider);
+        registry.register_method_provider(ContainerParameterProvider);
+        registry.register_method_provider(CreateFormProvider);
+        registry.register_method_provider(FormDataProvider);
+        registry.register_class_hook(EventListenerHook);
+    }
+}
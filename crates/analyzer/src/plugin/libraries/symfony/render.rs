@@ -0,0 +1,356 @@
+//! Twig template checks for `render()` calls.
+//!
+//! When a controller calls `$this->render('user/show.html.twig', ['user' => $user])`, the
+//! template is looked up in the project's [`TemplateIndex`], and the parameters are checked
+//! against what the template reads: every variable it uses must be passed, and every attribute
+//! it accesses on a passed object (`user.email`) must resolve to a property or method.
+
+use std::collections::BTreeMap;
+
+use mago_codex::metadata::CodebaseMetadata;
+use mago_codex::symbol::SymbolKind;
+use mago_codex::ttype::atomic::TAtomic;
+use mago_codex::ttype::atomic::array::TArray;
+use mago_codex::ttype::atomic::array::key::ArrayKey;
+use mago_codex::ttype::atomic::object::TObject;
+use mago_codex::ttype::union::TUnion;
+use mago_codex::visibility::Visibility;
+use mago_reporting::Annotation;
+use mago_reporting::Issue;
+use mago_span::HasSpan;
+use mago_syntax::cst::Argument;
+use mago_syntax::cst::ClassLikeMemberSelector;
+use mago_syntax::cst::Expression;
+use mago_syntax::cst::MethodCall;
+
+use crate::code::IssueCode;
+use crate::plugin::context::HookContext;
+use crate::plugin::hook::HookResult;
+use crate::plugin::hook::MethodCallHook;
+use crate::plugin::provider::Provider;
+use crate::plugin::provider::ProviderMeta;
+use crate::template::TemplateAttribute;
+use crate::template::TemplateIndex;
+
+/// Classes whose render methods take a template name and a parameter array.
+const RENDERERS: [&[u8]; 2] =
+    [b"Symfony\\Bundle\\FrameworkBundle\\Controller\\AbstractController", b"Twig\\Environment"];
+
+/// Methods that render a template: `AbstractController::render()`, `renderView()`, and `stream()`,
+/// and `Twig\Environment::render()` and `display()`.
+const RENDER_METHODS: [&[u8]; 4] = [b"render", b"renderView", b"stream", b"display"];
+
+/// Forms passed to `AbstractController::render()` reach the template as a `FormView`.
+const FORM_INTERFACE: &[u8] = b"Symfony\\Component\\Form\\FormInterface";
+
+/// Prefixes of the methods Twig tries, after the property, when resolving `object.attribute`.
+const GETTER_PREFIXES: [&str; 4] = ["", "get", "is", "has"];
+
+#[derive(Default)]
+pub struct TwigRenderHook;
+
+impl Provider for TwigRenderHook {
+    fn meta() -> &'static ProviderMeta {
+        static META: ProviderMeta = ProviderMeta::new(
+            "symfony::twig::render",
+            "Twig render",
+            "Checks render() calls against the Twig templates they render.",
+        );
+
+        &META
+    }
+}
+
+impl MethodCallHook for TwigRenderHook {
+    fn after_method_call(&self, call: &MethodCall<'_>, context: &mut HookContext<'_, '_>) -> HookResult<()> {
+        let Some(templates) = context.templates().filter(|templates| !templates.is_empty()) else {
+            return Ok(());
+        };
+
+        let ClassLikeMemberSelector::Identifier(method) = &call.method else {
+            return Ok(());
+        };
+
+        if !RENDER_METHODS.iter().any(|name| name.eq_ignore_ascii_case(method.value)) || !is_renderer(context, call) {
+            return Ok(());
+        }
+
+        let Some(name_argument) = get_argument(call, 0, &[b"view", b"name"]) else {
+            return Ok(());
+        };
+
+        let Some(name) = context.get_expression_type(name_argument).and_then(TUnion::get_single_literal_string_value)
+        else {
+            return Ok(());
+        };
+
+        // Namespaced templates (`@Bundle/...`) live outside of the project's template directories.
+        if name.starts_with(b"@") {
+            return Ok(());
+        }
+
+        let name = String::from_utf8_lossy(name).into_owned();
+        check_render(context, templates, call, name_argument, &name);
+
+        Ok(())
+    }
+}
+
+fn check_render(
+    context: &HookContext<'_, '_>,
+    templates: &TemplateIndex,
+    call: &MethodCall<'_>,
+    name_argument: &Expression<'_>,
+    name: &str,
+) {
+    let Some(template) = templates.resolve_context(name) else {
+        context.report(
+            IssueCode::NonExistentTemplate,
+            Issue::error(format!("Template `{name}` does not exist."))
+                .with_annotation(Annotation::primary(name_argument.span()).with_message("This template is not found"))
+                .with_help(
+                    "Check the template name, and make sure its directory is listed in `template-paths` and part of the source paths.",
+                ),
+        );
+
+        return;
+    };
+
+    let parameters_argument = get_argument(call, 1, &[b"parameters", b"context"]);
+    let parameters_span = parameters_argument.map_or_else(|| call.span(), HasSpan::span);
+    let Some((parameters, is_sealed)) = (match parameters_argument {
+        Some(argument) => context.get_expression_type(argument).and_then(get_parameters),
+        None => Some((BTreeMap::new(), true)),
+    }) else {
+        return;
+    };
+
+    if template.is_complete && is_sealed {
+        for variable in template.required_variables() {
+            if parameters.contains_key(variable.name.as_str()) {
+                continue;
+            }
+
+            context.report(
+                IssueCode::UndefinedTemplateVariable,
+                Issue::error(format!(
+                    "Template `{name}` uses variable `{}`, which is not passed to it.",
+                    variable.name
+                ))
+                .with_annotation(
+                    Annotation::primary(parameters_span)
+                        .with_message(format!("`{}` is missing from these parameters", variable.name)),
+                )
+                .with_annotation(
+                    Annotation::secondary(variable.span).with_message(format!("`{}` is used here", variable.name)),
+                )
+                .with_help(format!(
+                    "Pass `{}` to the template, or guard its use with `is defined` or the `default` filter.",
+                    variable.name
+                )),
+            );
+        }
+    }
+
+    for variable in template.attribute_accesses() {
+        let Some(parameter_type) = parameters.get(variable.name.as_str()) else {
+            continue;
+        };
+
+        if is_form(context.codebase(), parameter_type) {
+            continue;
+        }
+
+        let Some((position, owner)) = find_missing_attribute(context.codebase(), parameter_type, &variable.attributes)
+        else {
+            continue;
+        };
+
+        let attribute = &variable.attributes[position];
+        let path = std::iter::once(variable.name.as_str())
+            .chain(variable.attributes[..=position].iter().map(|attribute| attribute.name.as_str()))
+            .collect::<Vec<_>>()
+            .join(".");
+
+        context.report(
+            IssueCode::NonExistentTemplateAttribute,
+            Issue::error(format!("Template `{name}` reads `{path}`, but `{owner}` has no `{}` attribute.", attribute.name))
+                .with_annotation(
+                    Annotation::primary(parameters_span)
+                        .with_message(format!("`{}` is passed to the template here", variable.name)),
+                )
+                .with_annotation(Annotation::secondary(attribute.span).with_message(format!("`{path}` is read here")))
+                .with_note(format!(
+                    "Twig resolves `{}` to a property named `{0}`, or to a method named `{0}`, `get{0}`, `is{0}`, or `has{0}`.",
+                    attribute.name
+                ))
+                .with_help("Fix the attribute name in the template, or pass a value that provides it."),
+        );
+    }
+}
+
+fn is_renderer(context: &HookContext<'_, '_>, call: &MethodCall<'_>) -> bool {
+    let Some(object_type) = context.get_expression_type(call.object) else {
+        return false;
+    };
+
+    object_type.types.iter().any(|atomic| match atomic {
+        TAtomic::Object(TObject::Named(named)) => {
+            RENDERERS.iter().any(|renderer| context.is_instance_of(named.name.as_bytes(), renderer))
+        }
+        _ => false,
+    })
+}
+
+fn is_form(codebase: &CodebaseMetadata, parameter_type: &TUnion) -> bool {
+    parameter_type.types.iter().any(|atomic| match atomic {
+        TAtomic::Object(TObject::Named(named)) => codebase.is_instance_of(named.name.as_bytes(), FORM_INTERFACE),
+        _ => false,
+    })
+}
+
+fn get_argument<'arena>(
+    call: &MethodCall<'arena>,
+    position: usize,
+    names: &[&[u8]],
+) -> Option<&'arena Expression<'arena>> {
+    for (index, argument) in call.argument_list.arguments.iter().enumerate() {
+        match argument {
+            Argument::Positional(argument) if index == position => return Some(argument.value),
+            Argument::Named(argument) if names.contains(&argument.name.value) => return Some(argument.value),
+            _ => {}
+        }
+    }
+
+    None
+}
+
+/// Returns the string-keyed entries of a parameter array shape, and whether the shape is sealed.
+fn get_parameters(parameters_type: &TUnion) -> Option<(BTreeMap<&str, &TUnion>, bool)> {
+    let [TAtomic::Array(TArray::Keyed(keyed_array))] = parameters_type.types.as_ref() else {
+        return None;
+    };
+
+    let parameters = keyed_array
+        .known_items
+        .iter()
+        .flatten()
+        .filter_map(|(key, (_, value))| match key {
+            ArrayKey::String(key) => Some((key.as_str()?, value)),
+            _ => None,
+        })
+        .collect();
+
+    Some((parameters, keyed_array.parameters.is_none()))
+}
+
+enum Resolution {
+    /// The attribute exists; its type is known if it is a plain object type.
+    Found(Option<TUnion>),
+    /// The attribute does not exist on the named class.
+    Missing(String),
+    /// The attribute cannot be checked, e.g. because the value is not an object.
+    Unknown,
+}
+
+/// Follows an attribute chain, returning the position of the first missing attribute and the class it is missing on.
+fn find_missing_attribute(
+    codebase: &CodebaseMetadata,
+    value_type: &TUnion,
+    attributes: &[TemplateAttribute],
+) -> Option<(usize, String)> {
+    let mut current = value_type.clone();
+    for (position, attribute) in attributes.iter().enumerate() {
+        match resolve_attribute(codebase, &current, attribute) {
+            Resolution::Found(Some(next)) => current = next,
+            Resolution::Found(None) | Resolution::Unknown => return None,
+            Resolution::Missing(owner) => return Some((position, owner)),
+        }
+    }
+
+    None
+}
+
+fn resolve_attribute(codebase: &CodebaseMetadata, value_type: &TUnion, attribute: &TemplateAttribute) -> Resolution {
+    let mut classes = Vec::new();
+    for atomic in value_type.types.iter() {
+        match atomic {
+            TAtomic::Null => {}
+            TAtomic::Object(TObject::Named(named)) => classes.push(named.name),
+            _ => return Resolution::Unknown,
+        }
+    }
+
+    let [class] = classes.as_slice() else {
+        return Resolution::Unknown;
+    };
+
+    resolve_class_attribute(codebase, class.as_bytes(), attribute)
+}
+
+fn resolve_class_attribute(codebase: &CodebaseMetadata, class: &[u8], attribute: &TemplateAttribute) -> Resolution {
+    let Some(class_like) = codebase.get_class_like(class) else {
+        return Resolution::Unknown;
+    };
+
+    if !class_like.mixins.is_empty() || codebase.method_exists(class, b"__call") {
+        return Resolution::Unknown;
+    }
+
+    if !attribute.is_method_call {
+        if codebase.method_exists(class, b"__get") || codebase.is_instance_of(class, b"ArrayAccess") {
+            return Resolution::Unknown;
+        }
+
+        // Twig reads properties from outside of the class, so only public ones are visible to it.
+        let property_name = format!("${}", attribute.name);
+        let property = codebase
+            .get_declaring_property_class(class, property_name.as_bytes())
+            .and_then(|declaring_class| codebase.get_property(declaring_class.as_bytes(), property_name.as_bytes()));
+        if let Some(property) = property.filter(|property| property.read_visibility == Visibility::Public) {
+            return Resolution::Found(
+                property.type_metadata.as_ref().and_then(|metadata| plain_type(&metadata.type_union)),
+            );
+        }
+
+        if codebase.magic_property_exists(class, property_name.as_bytes())
+            || (class_like.kind == SymbolKind::Enum && matches!(attribute.name.as_str(), "name" | "value"))
+        {
+            return Resolution::Found(None);
+        }
+    }
+
+    for prefix in GETTER_PREFIXES {
+        let method_name = format!("{prefix}{}", attribute.name);
+
+        // Twig looks methods up from outside of the class as well, so it skips non-public ones.
+        let method = codebase.get_declaring_method(class, method_name.as_bytes()).filter(|method| {
+            method.method_metadata.as_ref().is_some_and(|metadata| metadata.visibility == Visibility::Public)
+        });
+        if let Some(method) = method {
+            let return_type =
+                method.return_type_metadata.as_ref().and_then(|return_type| plain_type(&return_type.type_union));
+
+            return Resolution::Found(return_type);
+        }
+
+        if class_like
+            .pseudo_methods
+            .iter()
+            .any(|pseudo_method| pseudo_method.as_bytes().eq_ignore_ascii_case(method_name.as_bytes()))
+        {
+            return Resolution::Found(None);
+        }
+    }
+
+    Resolution::Missing(class_like.original_name.to_string())
+}
+
+/// Returns the type if attributes can be resolved on it without expanding templates or `static`.
+fn plain_type(value_type: &TUnion) -> Option<TUnion> {
+    if value_type.has_template_or_static() {
+        return None;
+    }
+
+    Some(value_type.clone())
+}
@@ -59,7 +59,7 @@ pub fn create_registry_with_plugins(enabled_plugins: &[String], disable_defaults
         let default_enabled = !disable_defaults && meta.default_enabled;
 
         if explicitly_enabled || default_enabled {
-            plugin.register(&mut registry);
+            registry.register_plugin(*plugin);
         }
     }
 
@@ -73,7 +73,7 @@ pub fn create_registry_with_plugins(enabled_plugins: &[String], disable_defaults
 pub fn create_registry() -> PluginRegistry {
     let mut registry = PluginRegistry::new();
     for plugin in libraries::ALL_PLUGINS.iter() {
-        plugin.register(&mut registry);
+        registry.register_plugin(*plugin);
     }
 
     registry
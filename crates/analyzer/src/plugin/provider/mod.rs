@@ -3,12 +3,14 @@
 pub mod assertion;
 pub mod function;
 pub mod method;
+pub mod parameter;
 pub mod property;
 pub mod throw;
 
 pub use assertion::*;
 pub use function::*;
 pub use method::*;
+pub use parameter::*;
 pub use property::*;
 pub use throw::*;
 
@@ -0,0 +1,31 @@
+//! Function parameter type provider trait.
+
+use mago_codex::ttype::union::TUnion;
+
+use crate::plugin::context::InvocationInfo;
+use crate::plugin::context::ProviderContext;
+use crate::plugin::provider::Provider;
+use crate::plugin::provider::function::FunctionTarget;
+
+/// Provider for refining the type of a function parameter from the other arguments of a call.
+///
+/// The refined type is the type expected for a closure or arrow function passed to the
+/// parameter, so the parameters of that closure can be inferred from it; for example, the
+/// `$matches` of a `preg_replace_callback()` callback from the pattern.
+pub trait FunctionParameterTypeProvider: Provider {
+    /// The functions this provider handles.
+    fn targets() -> FunctionTarget
+    where
+        Self: Sized;
+
+    /// Get the refined type of the parameter named `parameter_name` (including the `$`).
+    ///
+    /// Returns `None` to keep the declared `parameter_type`.
+    fn get_parameter_type(
+        &self,
+        context: &ProviderContext<'_, '_, '_>,
+        invocation: &InvocationInfo<'_, '_, '_>,
+        parameter_name: &[u8],
+        parameter_type: &TUnion,
+    ) -> Option<TUnion>;
+}
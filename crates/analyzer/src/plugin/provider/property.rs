@@ -1,9 +1,12 @@
 //! Property-related providers for the analyzer plugin system.
 
+use mago_codex::metadata::CodebaseMetadata;
 use mago_codex::metadata::class_like::ClassLikeMetadata;
 use mago_codex::metadata::property::PropertyMetadata;
+use mago_codex::ttype::union::TUnion;
 
 use crate::plugin::provider::Provider;
+use crate::schema::DatabaseSchema;
 
 /// Provider for checking if a property should be considered initialized.
 ///
@@ -18,3 +21,26 @@ pub trait PropertyInitializationProvider: Provider {
     fn is_property_initialized(&self, class_metadata: &ClassLikeMetadata, property_metadata: &PropertyMetadata)
     -> bool;
 }
+
+/// Provider for the types of properties a class resolves at runtime, through `__get()` and `__set()`.
+///
+/// This allows plugins to type the magic attributes of ORM models, whose properties are backed by
+/// database columns rather than declarations. Providers are consulted before the class's own
+/// properties, so they should only answer for properties the class does not declare.
+pub trait PropertyTypeProvider: Provider {
+    /// Get the type of `$property_name` (without the leading `$`) on an instance of the class.
+    ///
+    /// `for_assignment` is `true` when the property is being written to, in which case the
+    /// returned type is the type the property accepts. `schema` is the database schema declared
+    /// by the project's migrations, if the analyzer was given one.
+    ///
+    /// Returns `None` to let other providers or the default resolution decide.
+    fn get_property_type(
+        &self,
+        codebase: &CodebaseMetadata,
+        schema: Option<&DatabaseSchema>,
+        class_metadata: &ClassLikeMetadata,
+        property_name: &[u8],
+        for_assignment: bool,
+    ) -> Option<TUnion>;
+}
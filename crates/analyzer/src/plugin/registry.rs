@@ -32,6 +32,7 @@ use mago_word::ascii_lowercase_word;
 use mago_word::concat_word;
 
 use crate::artifacts::AnalysisArtifacts;
+use crate::container::ServiceContainer;
 use crate::context::block::BlockContext;
 use crate::external::AfterFileAnalysisResult;
 use crate::external::BeforeAnalysisResult;
@@ -46,8 +47,10 @@ use crate::external::ExternalAnalyzerHandle;
 use crate::external::FileAnalysisSnapshot;
 use crate::external::NodeAnalysisRequirements;
 use crate::external::PropertyAccessKind;
+use crate::form::FormIndex;
 use crate::invocation::EffectiveCallableSignature;
 use crate::invocation::Invocation;
+use crate::plugin::Plugin;
 use crate::plugin::PluginError;
 use crate::plugin::context::HookContext;
 use crate::plugin::context::InvocationInfo;
@@ -77,10 +80,13 @@ use crate::plugin::provider::function::FunctionReturnTypeProvider;
 use crate::plugin::provider::function::FunctionTarget;
 use crate::plugin::provider::method::MethodReturnTypeProvider;
 use crate::plugin::provider::method::MethodTarget;
+use crate::plugin::provider::parameter::FunctionParameterTypeProvider;
 use crate::plugin::provider::property::PropertyInitializationProvider;
+use crate::plugin::provider::property::PropertyTypeProvider;
 use crate::plugin::provider::throw::ExpressionThrowTypeProvider;
 use crate::plugin::provider::throw::FunctionThrowTypeProvider;
 use crate::plugin::provider::throw::MethodThrowTypeProvider;
+use crate::schema::DatabaseSchema;
 
 pub struct ProviderResult {
     pub return_type: Option<TUnion>,
@@ -102,6 +108,7 @@ where
 
 #[derive(Default)]
 pub struct PluginRegistry {
+    plugins: Vec<&'static str>,
     external_analyzer: Option<Arc<ExternalAnalyzerHandle>>,
     external_capabilities: OnceLock<ExternalAnalyzerCapabilities>,
     function_exact: WordMap<Vec<usize>>,
@@ -124,6 +131,7 @@ pub struct PluginRegistry {
     enum_hooks: Vec<Box<dyn EnumDeclarationHook>>,
     function_decl_hooks: Vec<Box<dyn FunctionDeclarationHook>>,
     property_initialization_providers: Vec<Box<dyn PropertyInitializationProvider>>,
+    property_type_providers: Vec<Box<dyn PropertyTypeProvider>>,
     issue_filter_hooks: Vec<Box<dyn IssueFilterHook>>,
     function_assertion_exact: WordMap<Vec<usize>>,
     function_assertion_prefix: Vec<(Word, usize)>,
@@ -140,12 +148,17 @@ pub struct PluginRegistry {
     method_throw_exact: WordMap<Vec<usize>>,
     method_throw_wildcard: Vec<(Vec<MethodTarget>, usize)>,
     method_throw_providers: Vec<Box<dyn MethodThrowTypeProvider>>,
+    function_parameter_exact: WordMap<Vec<usize>>,
+    function_parameter_prefix: Vec<(Word, usize)>,
+    function_parameter_namespace: Vec<(Word, usize)>,
+    function_parameter_providers: Vec<Box<dyn FunctionParameterTypeProvider>>,
 }
 
 #[allow(clippy::missing_fields_in_debug)]
 impl std::fmt::Debug for PluginRegistry {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         f.debug_struct("PluginRegistry")
+            .field("plugins", &self.plugins)
             .field("external_analyzer", &self.external_analyzer.is_some())
             .field("external_capabilities", &self.external_capabilities.get())
             .field("function_providers", &self.function_providers.len())
@@ -163,22 +176,46 @@ impl std::fmt::Debug for PluginRegistry {
             .field("enum_hooks", &self.enum_hooks.len())
             .field("function_decl_hooks", &self.function_decl_hooks.len())
             .field("property_initialization_providers", &self.property_initialization_providers.len())
+            .field("property_type_providers", &self.property_type_providers.len())
             .field("issue_filter_hooks", &self.issue_filter_hooks.len())
             .field("function_assertion_providers", &self.function_assertion_providers.len())
             .field("method_assertion_providers", &self.method_assertion_providers.len())
             .field("expression_throw_providers", &self.expression_throw_providers.len())
             .field("function_throw_providers", &self.function_throw_providers.len())
             .field("method_throw_providers", &self.method_throw_providers.len())
+            .field("function_parameter_providers", &self.function_parameter_providers.len())
             .finish()
     }
 }
 
 impl PluginRegistry {
+    /// Registers the providers and hooks of `plugin`, and records it as enabled.
+    pub fn register_plugin(&mut self, plugin: &dyn Plugin) {
+        plugin.register(self);
+        self.plugins.push(plugin.meta().id);
+    }
+
+    /// Returns `true` if `plugin` was registered with [`register_plugin`](Self::register_plugin).
+    ///
+    /// Services use this to skip building the project indexes only a plugin reads.
+    #[inline]
+    #[must_use]
+    pub fn has_plugin(&self, plugin: &dyn Plugin) -> bool {
+        self.plugins.contains(&plugin.meta().id)
+    }
+
     /// Attaches worker-backed analyzer plugins to this registry.
     pub fn set_external_analyzer(&mut self, analyzer: Arc<ExternalAnalyzerHandle>) {
         self.external_analyzer = Some(analyzer);
     }
 
+    /// Returns `true` if worker-backed analyzer plugins are attached to this registry.
+    #[inline]
+    #[must_use]
+    pub fn has_external_analyzer(&self) -> bool {
+        self.external_analyzer.is_some()
+    }
+
     /// Completes concurrent external analyzer initialization before file analysis.
     ///
     /// # Errors
@@ -553,6 +590,13 @@ impl PluginRegistry {
         self.property_initialization_providers.push(Box::new(provider));
     }
 
+    pub fn register_property_type_provider<P>(&mut self, provider: P)
+    where
+        P: PropertyTypeProvider + 'static,
+    {
+        self.property_type_providers.push(Box::new(provider));
+    }
+
     pub fn register_issue_filter_hook<H>(&mut self, hook: H)
     where
         H: IssueFilterHook + 'static,
@@ -683,6 +727,38 @@ impl PluginRegistry {
         self.method_throw_providers.push(Box::new(provider));
     }
 
+    pub fn register_function_parameter_provider<P>(&mut self, provider: P)
+    where
+        P: FunctionParameterTypeProvider + 'static,
+    {
+        let index = self.function_parameter_providers.len();
+
+        match P::targets() {
+            FunctionTarget::Exact(name) => {
+                self.function_parameter_exact.entry(ascii_lowercase_word(name)).or_default().push(index);
+            }
+            FunctionTarget::ExactMultiple(names) => {
+                for name in names {
+                    self.function_parameter_exact.entry(ascii_lowercase_word(name)).or_default().push(index);
+                }
+            }
+            FunctionTarget::Prefix(prefix) => {
+                self.function_parameter_prefix.push((ascii_lowercase_word(prefix), index));
+            }
+            FunctionTarget::Namespace(ns) => {
+                let ns_lower = ascii_lowercase_word(ns);
+                let ns_pattern = if ns_lower.as_bytes().last() == Some(&b'\\') {
+                    ns_lower
+                } else {
+                    concat_word!(ns_lower.as_bytes(), b"\\")
+                };
+                self.function_parameter_namespace.push((ns_pattern, index));
+            }
+        }
+
+        self.function_parameter_providers.push(Box::new(provider));
+    }
+
     #[inline]
     #[must_use]
     pub fn has_program_hooks(&self) -> bool {
@@ -792,6 +868,12 @@ impl PluginRegistry {
         !self.function_throw_providers.is_empty()
     }
 
+    #[inline]
+    #[must_use]
+    pub fn has_function_parameter_providers(&self) -> bool {
+        !self.function_parameter_providers.is_empty()
+    }
+
     #[inline]
     #[must_use]
     pub fn has_method_throw_providers(&self) -> bool {
@@ -1326,6 +1408,8 @@ impl PluginRegistry {
         artifacts: &AnalysisArtifacts,
         function_like: &FunctionLikeIdentifier,
         invocation: &Invocation<'ctx, '_, '_>,
+        container: Option<&'ctx ServiceContainer>,
+        forms: Option<&'ctx FormIndex>,
         external_session: Option<&ExternalAnalysisSession>,
     ) -> Option<ProviderResult> {
         match function_like {
@@ -1346,6 +1430,8 @@ impl PluginRegistry {
                 class_name.as_bytes(),
                 method_name.as_bytes(),
                 invocation,
+                container,
+                forms,
                 external_session,
             )),
             _ => None,
@@ -1415,13 +1501,17 @@ impl PluginRegistry {
         class_name: &[u8],
         method_name: &[u8],
         invocation: &Invocation<'ctx, '_, '_>,
+        container: Option<&'ctx ServiceContainer>,
+        forms: Option<&'ctx FormIndex>,
         external_session: Option<&ExternalAnalysisSession>,
     ) -> ProviderResult {
         let indices = self.get_method_provider_indices(class_name, method_name);
         let mut all_issues = Vec::new();
 
         for idx in indices {
-            let provider_context = ProviderContext::new(codebase, source_file, block_context, artifacts);
+            let provider_context = ProviderContext::new(codebase, source_file, block_context, artifacts)
+                .with_container(container)
+                .with_forms(forms);
             let invocation_info = InvocationInfo::new(invocation);
 
             if let Some(ty) =
@@ -1461,7 +1551,8 @@ impl PluginRegistry {
     #[inline]
     #[must_use]
     pub(crate) fn may_have_property_type_provider(&self) -> bool {
-        self.has_external_capability(|capabilities| capabilities.property_types)
+        !self.property_type_providers.is_empty()
+            || self.has_external_capability(|capabilities| capabilities.property_types)
     }
 
     #[inline]
@@ -1470,12 +1561,14 @@ impl PluginRegistry {
         self.has_external_capability(|capabilities| capabilities.class_initializers)
     }
 
-    /// Requests an external provider's effective magic-property contract.
+    /// Returns the effective magic-property contract of the first provider that types the property.
     ///
-    /// Provider failures are logged and preserve native property resolution.
+    /// Native providers are consulted before external ones. External provider failures are
+    /// logged and preserve native property resolution.
     pub(crate) fn get_property_type(
         &self,
         codebase: &CodebaseMetadata,
+        schema: Option<&DatabaseSchema>,
         class: &[u8],
         property: &[u8],
         access: PropertyAccessKind,
@@ -1483,6 +1576,22 @@ impl PluginRegistry {
         span: Span,
         external_session: Option<&ExternalAnalysisSession>,
     ) -> Option<EffectivePropertyType> {
+        if !self.property_type_providers.is_empty()
+            && let Some(class_metadata) = codebase.get_class_like(class)
+        {
+            let for_assignment = matches!(access, PropertyAccessKind::Write);
+            for provider in &self.property_type_providers {
+                if let Some(property_type) =
+                    provider.get_property_type(codebase, schema, class_metadata, property, for_assignment)
+                {
+                    return Some(EffectivePropertyType {
+                        read_type: Some(property_type.clone()),
+                        write_type: Some(property_type),
+                    });
+                }
+            }
+        }
+
         if !self.has_external_capability(|capabilities| capabilities.property_types) {
             return None;
         }
@@ -1882,6 +1991,70 @@ impl PluginRegistry {
         exceptions
     }
 
+    fn get_function_parameter_provider_indices(&self, name: &[u8]) -> Vec<usize> {
+        if self.function_parameter_exact.is_empty()
+            && self.function_parameter_prefix.is_empty()
+            && self.function_parameter_namespace.is_empty()
+        {
+            return Vec::new();
+        }
+
+        let lower_name = ascii_lowercase_word(name);
+        let mut indices = Vec::new();
+
+        if let Some(idxs) = self.function_parameter_exact.get(&lower_name) {
+            indices.extend(idxs.iter().copied());
+        }
+
+        for (prefix, idx) in &self.function_parameter_prefix {
+            if lower_name.as_bytes().starts_with(prefix.as_bytes()) && !indices.contains(idx) {
+                indices.push(*idx);
+            }
+        }
+
+        for (ns, idx) in &self.function_parameter_namespace {
+            if lower_name.as_bytes().starts_with(ns.as_bytes()) && !indices.contains(idx) {
+                indices.push(*idx);
+            }
+        }
+
+        indices
+    }
+
+    /// Get the refined type of a function parameter from registered providers.
+    ///
+    /// The first provider that refines the type wins.
+    #[must_use]
+    pub fn get_function_parameter_type<'ctx>(
+        &self,
+        codebase: &'ctx CodebaseMetadata,
+        source_file: &'ctx File,
+        block_context: &BlockContext<'ctx>,
+        artifacts: &AnalysisArtifacts,
+        function_name: &[u8],
+        invocation: &Invocation<'ctx, '_, '_>,
+        parameter_name: &[u8],
+        parameter_type: &TUnion,
+    ) -> Option<TUnion> {
+        let indices = self.get_function_parameter_provider_indices(function_name);
+
+        for idx in indices {
+            let provider_context = ProviderContext::new(codebase, source_file, block_context, artifacts);
+            let invocation_info = InvocationInfo::new(invocation);
+
+            if let Some(refined_type) = self.function_parameter_providers[idx].get_parameter_type(
+                &provider_context,
+                &invocation_info,
+                parameter_name,
+                parameter_type,
+            ) {
+                return Some(refined_type);
+            }
+        }
+
+        None
+    }
+
     /// Get thrown exception class names for a method invocation from registered providers.
     #[must_use]
     pub fn get_method_thrown_exceptions<'ctx>(
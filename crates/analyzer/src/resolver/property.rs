@@ -432,7 +432,7 @@ fn resolve_external_property<A>(
 where
     A: Arena,
 {
-    if context.external_analysis_session.is_none() || !context.plugin_registry.may_have_property_type_provider() {
+    if !context.plugin_registry.may_have_property_type_provider() {
         return Ok(None);
     }
 
@@ -440,6 +440,7 @@ where
     let receiver_type = TUnion::from_atomic(TAtomic::Object(object.clone()));
     let Some(effective) = context.plugin_registry.get_property_type(
         context.codebase,
+        context.schema,
         class.as_bytes(),
         property_without_dollar,
         if for_assignment { PropertyAccessKind::Write } else { PropertyAccessKind::Read },
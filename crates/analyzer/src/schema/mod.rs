@@ -0,0 +1,346 @@
+//! Summaries of the database tables declared by a project's migrations.
+//!
+//! Plugins that understand an ORM's magic attributes use the [`DatabaseSchema`] to type them
+//! from the columns of the table they map to. Every migration is scanned once, in file name
+//! order, and the `Schema::create()`, `Schema::table()`, `Schema::rename()` and `Schema::drop()`
+//! calls in its `up()` method are replayed to build the tables the migrations leave behind.
+//!
+//! Migrations are read from the
+//! [`Settings::migration_paths`](crate::settings::Settings::migration_paths) directories.
+
+use std::borrow::Borrow;
+
+use foldhash::HashMap;
+
+use mago_allocator::LocalArena;
+use mago_database::file::File;
+use mago_syntax::parser::parse_file;
+use mago_syntax::walker::Walker;
+
+use crate::schema::scanner::MigrationScanner;
+
+mod scanner;
+
+/// The tables declared by a project's migrations, keyed by table name.
+#[derive(Debug, Clone, Default)]
+pub struct DatabaseSchema {
+    tables: HashMap<String, Table>,
+}
+
+/// The columns of a single table, keyed by column name.
+#[derive(Debug, Clone, Default)]
+pub struct Table {
+    columns: HashMap<String, Column>,
+}
+
+/// A column declared by a migration.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Column {
+    pub kind: ColumnKind,
+    /// Whether the column was declared `nullable()`, or is nullable by definition (e.g. `timestamps()`).
+    pub nullable: bool,
+}
+
+/// The kind of value a column holds, as declared by its schema builder method.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ColumnKind {
+    /// `id()`, `integer()`, `foreignId()`, `year()`, and the other integer columns.
+    Integer,
+    /// `float()` and `double()`.
+    Float,
+    /// `decimal()`, which the database driver returns as a string.
+    Decimal,
+    /// `string()`, `text()`, `uuid()`, `enum()`, and the other text columns.
+    String,
+    /// `boolean()`.
+    Boolean,
+    /// `json()` and `jsonb()`.
+    Json,
+    /// `date()`.
+    Date,
+    /// `dateTime()`, `timestamp()`, and the columns added by `timestamps()` and `softDeletes()`.
+    DateTime,
+    /// `time()`.
+    Time,
+    /// `binary()`.
+    Binary,
+}
+
+impl DatabaseSchema {
+    #[must_use]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Builds a schema from the migrations among `files`, as selected by [`migration_files`](Self::migration_files).
+    #[must_use]
+    pub fn from_files<F>(files: impl IntoIterator<Item = F>, paths: &[String]) -> Self
+    where
+        F: Borrow<File>,
+    {
+        let mut schema = Self::new();
+        let mut arena = LocalArena::new();
+        for file in Self::migration_files(files, paths) {
+            let program = parse_file(&arena, file.borrow());
+            if !program.has_errors() {
+                MigrationScanner.walk_program(program, &mut (&mut schema, false));
+            }
+
+            arena.reset();
+        }
+
+        schema
+    }
+
+    /// Returns the PHP files among `files` that live in one of the `paths` directories, in the
+    /// order they are applied.
+    ///
+    /// Migrations run in file name order, which is the order of their timestamp prefixes, even
+    /// when they are spread over several directories.
+    #[must_use]
+    pub fn migration_files<F>(files: impl IntoIterator<Item = F>, paths: &[String]) -> Vec<F>
+    where
+        F: Borrow<File>,
+    {
+        let prefixes: Vec<String> = paths
+            .iter()
+            .map(|path| path.trim_start_matches("./").trim_end_matches('/'))
+            .filter(|path| !path.is_empty())
+            .map(|path| format!("{path}/"))
+            .collect();
+
+        if prefixes.is_empty() {
+            return Vec::new();
+        }
+
+        let mut migrations: Vec<F> = files
+            .into_iter()
+            .filter(|file| {
+                let file = file.borrow();
+                file.language().is_php() && prefixes.iter().any(|prefix| file.name.starts_with(prefix.as_bytes()))
+            })
+            .collect();
+
+        migrations.sort_by(|a, b| {
+            let (a, b) = (a.borrow(), b.borrow());
+
+            base_name(&a.name).cmp(base_name(&b.name)).then_with(|| a.name.cmp(&b.name))
+        });
+
+        migrations
+    }
+
+    #[must_use]
+    pub fn table(&self, name: &str) -> Option<&Table> {
+        self.tables.get(name)
+    }
+
+    #[must_use]
+    pub fn len(&self) -> usize {
+        self.tables.len()
+    }
+
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.tables.is_empty()
+    }
+
+    fn table_mut(&mut self, name: &str) -> &mut Table {
+        self.tables.entry(name.to_string()).or_default()
+    }
+
+    fn drop_table(&mut self, name: &str) {
+        self.tables.remove(name);
+    }
+
+    fn rename_table(&mut self, from: &str, to: &str) {
+        if let Some(table) = self.tables.remove(from) {
+            self.tables.insert(to.to_string(), table);
+        }
+    }
+}
+
+impl Table {
+    #[must_use]
+    pub fn column(&self, name: &str) -> Option<&Column> {
+        self.columns.get(name)
+    }
+
+    pub fn columns(&self) -> impl Iterator<Item = (&str, &Column)> {
+        self.columns.iter().map(|(name, column)| (name.as_str(), column))
+    }
+
+    fn add(&mut self, name: impl Into<String>, kind: ColumnKind, nullable: bool) {
+        self.columns.insert(name.into(), Column { kind, nullable });
+    }
+
+    fn drop(&mut self, name: &str) {
+        self.columns.remove(name);
+    }
+
+    fn rename(&mut self, from: &str, to: &str) {
+        if let Some(column) = self.columns.remove(from) {
+            self.columns.insert(to.to_string(), column);
+        }
+    }
+}
+
+/// Converts a `StudlyCase` class name to `snake_case`, the way Laravel names tables and foreign keys.
+#[must_use]
+pub(crate) fn snake_case(name: &str) -> String {
+    let mut snake = String::with_capacity(name.len() + 4);
+    for (index, character) in name.char_indices() {
+        if character.is_ascii_uppercase() {
+            if index > 0 {
+                snake.push('_');
+            }
+
+            snake.push(character.to_ascii_lowercase());
+        } else {
+            snake.push(character);
+        }
+    }
+
+    snake
+}
+
+/// The last segment of a file name, e.g. `2024_01_01_000000_create_users_table.php`.
+fn base_name(name: &[u8]) -> &[u8] {
+    name.rsplit(|byte| *byte == b'/').next().unwrap_or(name)
+}
+
+#[cfg(test)]
+#[allow(clippy::expect_used)]
+mod tests {
+    use std::borrow::Cow;
+
+    use super::*;
+
+    fn schema(migrations: &[(&str, &str)]) -> DatabaseSchema {
+        let files: Vec<File> = migrations
+            .iter()
+            .map(|(name, content)| {
+                File::ephemeral(
+                    Cow::Owned(format!("database/migrations/{name}").into_bytes()),
+                    Cow::Owned(content.as_bytes().to_vec()),
+                )
+            })
+            .collect();
+
+        DatabaseSchema::from_files(&files, &["database/migrations".to_string()])
+    }
+
+    #[test]
+    fn test_create_table() {
+        let schema = schema(&[(
+            "2024_01_01_000000_create_users_table.php",
+            "<?php
+            return new class extends Migration {
+                public function up(): void {
+                    Schema::create('users', function (Blueprint $table) {
+                        $table->id();
+                        $table->string('email')->unique();
+                        $table->string('nickname')->nullable();
+                        $table->boolean('is_admin')->default(false);
+                        $table->decimal('balance', 8, 2);
+                        $table->foreignIdFor(Team::class);
+                        $table->timestamps();
+                    });
+                }
+
+                public function down(): void {
+                    Schema::dropIfExists('users');
+                }
+            };",
+        )]);
+
+        let users = schema.table("users").expect("the table should exist");
+        assert_eq!(users.column("id"), Some(&Column { kind: ColumnKind::Integer, nullable: false }));
+        assert_eq!(users.column("email"), Some(&Column { kind: ColumnKind::String, nullable: false }));
+        assert_eq!(users.column("nickname"), Some(&Column { kind: ColumnKind::String, nullable: true }));
+        assert_eq!(users.column("is_admin"), Some(&Column { kind: ColumnKind::Boolean, nullable: false }));
+        assert_eq!(users.column("balance"), Some(&Column { kind: ColumnKind::Decimal, nullable: false }));
+        assert_eq!(users.column("team_id"), Some(&Column { kind: ColumnKind::Integer, nullable: false }));
+        assert_eq!(users.column("created_at"), Some(&Column { kind: ColumnKind::DateTime, nullable: true }));
+        assert_eq!(users.columns().count(), 8);
+    }
+
+    #[test]
+    fn test_migrations_are_applied_in_order() {
+        let schema = schema(&[
+            (
+                "2024_01_02_000000_update_posts_table.php",
+                "<?php
+                return new class extends Migration {
+                    public function up(): void {
+                        Schema::table('posts', function (Blueprint $table) {
+                            $table->dropColumn(['body', 'draft']);
+                            $table->renameColumn('name', 'title');
+                            $table->json('meta')->nullable();
+                        });
+
+                        Schema::rename('posts', 'articles');
+                    }
+                };",
+            ),
+            (
+                "2024_01_01_000000_create_posts_table.php",
+                "<?php
+                return new class extends Migration {
+                    public function up(): void {
+                        Schema::create('posts', static fn (Blueprint $table) => $table->string('name'));
+                        Schema::table('posts', function (Blueprint $t) {
+                            $t->text('body');
+                            $t->boolean('draft');
+                            $t->softDeletes();
+                        });
+                        Schema::create('tags', fn ($table) => $table->id());
+                        Schema::drop('tags');
+                    }
+                };",
+            ),
+        ]);
+
+        assert!(schema.table("posts").is_none());
+        assert!(schema.table("tags").is_none());
+
+        let articles = schema.table("articles").expect("the table should exist");
+        assert_eq!(articles.column("title"), Some(&Column { kind: ColumnKind::String, nullable: false }));
+        assert_eq!(articles.column("meta"), Some(&Column { kind: ColumnKind::Json, nullable: true }));
+        assert_eq!(articles.column("deleted_at"), Some(&Column { kind: ColumnKind::DateTime, nullable: true }));
+        assert!(articles.column("body").is_none());
+        assert!(articles.column("draft").is_none());
+        assert!(articles.column("name").is_none());
+    }
+
+    #[test]
+    fn test_migration_files_are_ordered_by_file_name() {
+        let files: Vec<File> = [
+            "database/migrations/2024_02_01_000000_create_posts_table.php",
+            "database/tenant/2024_01_01_000000_create_users_table.php",
+            "database/migrations/2024_03_01_000000_add_title_to_posts_table.php",
+        ]
+        .iter()
+        .map(|name| File::ephemeral(Cow::Owned(name.as_bytes().to_vec()), Cow::Borrowed(b"<?php\n")))
+        .collect();
+
+        let paths = ["./database/migrations/".to_string(), "database/tenant".to_string()];
+        let names: Vec<&[u8]> =
+            DatabaseSchema::migration_files(&files, &paths).into_iter().map(|file| file.name.as_ref()).collect();
+
+        assert_eq!(
+            names,
+            [
+                b"database/tenant/2024_01_01_000000_create_users_table.php".as_slice(),
+                b"database/migrations/2024_02_01_000000_create_posts_table.php".as_slice(),
+                b"database/migrations/2024_03_01_000000_add_title_to_posts_table.php".as_slice(),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_snake_case() {
+        assert_eq!(snake_case("User"), "user");
+        assert_eq!(snake_case("BlogPost"), "blog_post");
+    }
+}
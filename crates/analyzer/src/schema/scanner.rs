@@ -0,0 +1,293 @@
+use mago_syntax::cst::Access;
+use mago_syntax::cst::Argument;
+use mago_syntax::cst::ArgumentList;
+use mago_syntax::cst::ArrayElement;
+use mago_syntax::cst::Call;
+use mago_syntax::cst::ClassLikeConstantSelector;
+use mago_syntax::cst::ClassLikeMemberSelector;
+use mago_syntax::cst::Expression;
+use mago_syntax::cst::FunctionLikeParameterList;
+use mago_syntax::cst::Literal;
+use mago_syntax::cst::Method;
+use mago_syntax::cst::Statement;
+use mago_syntax::cst::StaticMethodCall;
+use mago_syntax::cst::Variable;
+use mago_syntax::walker::Walker;
+
+use crate::schema::ColumnKind;
+use crate::schema::DatabaseSchema;
+use crate::schema::Table;
+use crate::schema::snake_case;
+
+/// The schema being built, and whether the walker is inside a migration's `up()` method.
+pub(super) type MigrationScannerState<'schema> = (&'schema mut DatabaseSchema, bool);
+
+/// Walks a migration and replays the schema changes made by its `up()` method.
+pub(super) struct MigrationScanner;
+
+impl<'ast, 'arena> Walker<'ast, 'arena, MigrationScannerState<'_>> for MigrationScanner {
+    fn walk_in_method(&self, method: &'ast Method<'arena>, state: &mut MigrationScannerState<'_>) {
+        state.1 = method.name.value.eq_ignore_ascii_case(b"up");
+    }
+
+    fn walk_out_method(&self, _method: &'ast Method<'arena>, state: &mut MigrationScannerState<'_>) {
+        state.1 = false;
+    }
+
+    fn walk_in_static_method_call(
+        &self,
+        static_method_call: &'ast StaticMethodCall<'arena>,
+        state: &mut MigrationScannerState<'_>,
+    ) {
+        if !state.1 || !is_schema_facade(static_method_call.class) {
+            return;
+        }
+
+        let ClassLikeMemberSelector::Identifier(method) = &static_method_call.method else {
+            return;
+        };
+
+        let arguments = &static_method_call.argument_list;
+        let Some(table_name) = string_argument(arguments, 0) else {
+            return;
+        };
+
+        let schema = &mut *state.0;
+        match method.value.to_ascii_lowercase().as_slice() {
+            b"create" | b"table" => {
+                let Some(callback) = arguments.arguments.get(1).map(Argument::value) else {
+                    return;
+                };
+
+                let table = schema.table_mut(&table_name);
+                match callback {
+                    Expression::Closure(closure) => {
+                        let Some(blueprint) = first_parameter(&closure.parameter_list) else {
+                            return;
+                        };
+
+                        for statement in closure.body.statements.iter() {
+                            if let Statement::Expression(statement) = statement {
+                                apply_column_definition(table, blueprint, statement.expression);
+                            }
+                        }
+                    }
+                    Expression::ArrowFunction(arrow_function) => {
+                        let Some(blueprint) = first_parameter(&arrow_function.parameter_list) else {
+                            return;
+                        };
+
+                        apply_column_definition(table, blueprint, arrow_function.expression);
+                    }
+                    _ => {}
+                }
+            }
+            b"rename" => {
+                if let Some(to) = string_argument(arguments, 1) {
+                    schema.rename_table(&table_name, &to);
+                }
+            }
+            b"drop" | b"dropifexists" => {
+                schema.drop_table(&table_name);
+            }
+            _ => {}
+        }
+    }
+}
+
+/// Applies a `$table->column('name')->modifier()` chain to `table`.
+fn apply_column_definition(table: &mut Table, blueprint: &[u8], expression: &Expression<'_>) {
+    let mut nullable = None;
+    let mut current = expression;
+    loop {
+        let Expression::Call(Call::Method(call)) = current else {
+            return;
+        };
+
+        let ClassLikeMemberSelector::Identifier(method) = &call.method else {
+            return;
+        };
+
+        let method = method.value.to_ascii_lowercase();
+        if matches!(call.object, Expression::Variable(Variable::Direct(variable)) if variable.name == blueprint) {
+            apply_blueprint_method(table, &method, &call.argument_list, nullable.unwrap_or(false));
+
+            return;
+        }
+
+        // Modifiers are read outermost first, so the last call in the chain wins.
+        if method == b"nullable" && nullable.is_none() {
+            nullable = Some(!matches!(
+                call.argument_list.arguments.first().map(Argument::value),
+                Some(Expression::Literal(Literal::False(_)))
+            ));
+        }
+
+        current = call.object;
+    }
+}
+
+fn apply_blueprint_method(table: &mut Table, method: &[u8], arguments: &ArgumentList<'_>, nullable: bool) {
+    let name = |default: &str| string_argument(arguments, 0).unwrap_or_else(|| default.to_string());
+
+    match method {
+        b"timestamps" | b"timestampstz" | b"nullabletimestamps" => {
+            table.add("created_at", ColumnKind::DateTime, true);
+            table.add("updated_at", ColumnKind::DateTime, true);
+        }
+        b"softdeletes" | b"softdeletestz" => table.add(name("deleted_at"), ColumnKind::DateTime, true),
+        b"remembertoken" => table.add("remember_token", ColumnKind::String, true),
+        b"id" => table.add(name("id"), ColumnKind::Integer, nullable),
+        b"uuid" => table.add(name("uuid"), ColumnKind::String, nullable),
+        b"ulid" => table.add(name("ulid"), ColumnKind::String, nullable),
+        b"ipaddress" => table.add(name("ip_address"), ColumnKind::String, nullable),
+        b"macaddress" => table.add(name("mac_address"), ColumnKind::String, nullable),
+        b"foreignidfor" => {
+            let column = string_argument(arguments, 1).or_else(|| {
+                let model = class_name_argument(arguments, 0)?;
+
+                Some(format!("{}_id", snake_case(&model)))
+            });
+
+            if let Some(column) = column {
+                table.add(column, ColumnKind::Integer, nullable);
+            }
+        }
+        b"morphs"
+        | b"nullablemorphs"
+        | b"uuidmorphs"
+        | b"nullableuuidmorphs"
+        | b"ulidmorphs"
+        | b"nullableulidmorphs" => {
+            let Some(name) = string_argument(arguments, 0) else {
+                return;
+            };
+
+            let nullable = nullable || method.starts_with(b"nullable");
+            let id_kind = if method == b"morphs" || method == b"nullablemorphs" {
+                ColumnKind::Integer
+            } else {
+                ColumnKind::String
+            };
+
+            table.add(format!("{name}_id"), id_kind, nullable);
+            table.add(format!("{name}_type"), ColumnKind::String, nullable);
+        }
+        b"dropcolumn" | b"dropcolumns" => {
+            for argument in arguments.arguments.iter() {
+                match argument.value() {
+                    Expression::Array(array) => {
+                        for element in array.elements.iter() {
+                            if let ArrayElement::Value(element) = element
+                                && let Some(column) = string_value(element.value)
+                            {
+                                table.drop(&column);
+                            }
+                        }
+                    }
+                    value => {
+                        if let Some(column) = string_value(value) {
+                            table.drop(&column);
+                        }
+                    }
+                }
+            }
+        }
+        b"droptimestamps" | b"droptimestampstz" => {
+            table.drop("created_at");
+            table.drop("updated_at");
+        }
+        b"dropsoftdeletes" | b"dropsoftdeletestz" => table.drop(&name("deleted_at")),
+        b"dropremembertoken" => table.drop("remember_token"),
+        b"renamecolumn" => {
+            if let (Some(from), Some(to)) = (string_argument(arguments, 0), string_argument(arguments, 1)) {
+                table.rename(&from, &to);
+            }
+        }
+        _ => {
+            if let Some(kind) = column_kind(method)
+                && let Some(column) = string_argument(arguments, 0)
+            {
+                table.add(column, kind, nullable);
+            }
+        }
+    }
+}
+
+/// The kind of column added by a blueprint method that takes the column name as its first argument.
+fn column_kind(method: &[u8]) -> Option<ColumnKind> {
+    Some(match method {
+        b"increments"
+        | b"tinyincrements"
+        | b"smallincrements"
+        | b"mediumincrements"
+        | b"bigincrements"
+        | b"integer"
+        | b"tinyinteger"
+        | b"smallinteger"
+        | b"mediuminteger"
+        | b"biginteger"
+        | b"unsignedinteger"
+        | b"unsignedtinyinteger"
+        | b"unsignedsmallinteger"
+        | b"unsignedmediuminteger"
+        | b"unsignedbiginteger"
+        | b"foreignid"
+        | b"year" => ColumnKind::Integer,
+        b"float" | b"double" => ColumnKind::Float,
+        b"decimal" | b"unsigneddecimal" => ColumnKind::Decimal,
+        b"char" | b"string" | b"tinytext" | b"text" | b"mediumtext" | b"longtext" | b"enum" | b"set"
+        | b"foreignuuid" | b"foreignulid" => ColumnKind::String,
+        b"boolean" => ColumnKind::Boolean,
+        b"json" | b"jsonb" => ColumnKind::Json,
+        b"date" => ColumnKind::Date,
+        b"datetime" | b"datetimetz" | b"timestamp" | b"timestamptz" => ColumnKind::DateTime,
+        b"time" | b"timetz" => ColumnKind::Time,
+        b"binary" => ColumnKind::Binary,
+        _ => return None,
+    })
+}
+
+/// Whether `class` names the `Schema` facade, imported or fully qualified.
+fn is_schema_facade(class: &Expression<'_>) -> bool {
+    let Expression::Identifier(identifier) = class else {
+        return false;
+    };
+
+    identifier.last_segment().eq_ignore_ascii_case(b"Schema")
+}
+
+fn first_parameter<'arena>(parameters: &FunctionLikeParameterList<'arena>) -> Option<&'arena [u8]> {
+    parameters.parameters.first().map(|parameter| parameter.variable.name)
+}
+
+fn string_argument(arguments: &ArgumentList<'_>, index: usize) -> Option<String> {
+    string_value(arguments.arguments.get(index)?.value())
+}
+
+fn string_value(expression: &Expression<'_>) -> Option<String> {
+    let Expression::Literal(Literal::String(string)) = expression else {
+        return None;
+    };
+
+    Some(String::from_utf8_lossy(string.value?).into_owned())
+}
+
+/// The short name of the class in a `Foo::class` argument.
+fn class_name_argument(arguments: &ArgumentList<'_>, index: usize) -> Option<String> {
+    let Expression::Access(Access::ClassConstant(access)) = arguments.arguments.get(index)?.value() else {
+        return None;
+    };
+
+    let (Expression::Identifier(class), ClassLikeConstantSelector::Identifier(constant)) =
+        (access.class, &access.constant)
+    else {
+        return None;
+    };
+
+    if !constant.value.eq_ignore_ascii_case(b"class") {
+        return None;
+    }
+
+    Some(String::from_utf8_lossy(class.last_segment()).into_owned())
+}
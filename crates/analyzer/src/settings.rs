@@ -1,3 +1,5 @@
+use std::path::PathBuf;
+
 use mago_algebra::AlgebraThresholds;
 use mago_codex::metadata::class_like::ClassLikeMetadata;
 use mago_codex::ttype::combiner::CombinerOptions;
@@ -6,6 +8,8 @@ use mago_word::Word;
 use mago_word::WordSet;
 use mago_word::ascii_lowercase_word;
 
+use crate::dead_code::EntryPoints;
+
 /// Default maximum logical formula size during conditional analysis.
 pub const DEFAULT_FORMULA_SIZE_THRESHOLD: u16 = 512;
 
@@ -71,6 +75,30 @@ pub struct Settings {
     /// during `check_throws` analysis. Parent classes and subclasses are not affected.
     pub unchecked_exception_classes: WordSet,
 
+    /// Enable interprocedural taint analysis.
+    ///
+    /// When `true`, the analyzer tracks untrusted input (superglobals, `filter_input()`,
+    /// HTTP request objects, and functions annotated with `@mago-taint-source`) through
+    /// assignments, calls, returns, and properties, and reports it when it reaches a
+    /// sensitive sink (HTML output, SQL queries, shell commands, `include`, or `unserialize()`)
+    /// without passing through a sanitizer.
+    ///
+    /// This check is disabled by default (`false`) as it requires a whole-program pass.
+    pub check_taints: bool,
+
+    /// Report classes, methods, class constants, and properties that can not be reached from
+    /// any entry point.
+    ///
+    /// When `true`, the references recorded across the whole program are walked from the
+    /// configured [`entry_points`](Self::entry_points), top-level code, functions, and `@api`
+    /// symbols, and every user-defined symbol that is never reached is reported.
+    ///
+    /// This check is disabled by default (`false`) as it requires a whole-program pass.
+    pub find_unreachable_symbols: bool,
+
+    /// The entry points used by [`find_unreachable_symbols`](Self::find_unreachable_symbols).
+    pub entry_points: EntryPoints,
+
     /// Check for missing `#[Override]` attributes on overriding methods.
     ///
     /// When enabled, the analyzer reports methods that override a parent method without
@@ -236,6 +264,32 @@ pub struct Settings {
     /// Defaults to empty (no additional initializers).
     pub class_initializers: Vec<ClassInitializer>,
 
+    /// Directories holding Twig templates, relative to the workspace.
+    ///
+    /// Templates are named by their path relative to one of these directories, so
+    /// `templates/user/show.html.twig` is rendered as `user/show.html.twig`. Plugins use
+    /// these names to check `render()` calls against the templates they render.
+    ///
+    /// Defaults to `["templates"]`.
+    pub template_paths: Vec<String>,
+
+    /// Directories holding database migrations, relative to the workspace.
+    ///
+    /// The migrations are replayed in file name order to build the
+    /// [`DatabaseSchema`](crate::schema::DatabaseSchema) plugins use to type ORM attributes
+    /// from the columns of the tables they map to.
+    ///
+    /// Defaults to `["database/migrations"]`.
+    pub migration_paths: Vec<String>,
+
+    /// The XML dump of the project's compiled Symfony service container.
+    ///
+    /// The [`ServiceContainer`](crate::container::ServiceContainer) read from it lets plugins
+    /// type the services and parameters fetched from the container by name.
+    ///
+    /// Defaults to `None`.
+    pub container_xml_path: Option<PathBuf>,
+
     /// Enable property initialization checking (`missing-constructor`, `uninitialized-property`).
     ///
     /// When `false`, disables both `missing-constructor` and `uninitialized-property` issues
@@ -402,6 +456,9 @@ impl Settings {
             check_throws: false,
             unchecked_exceptions: WordSet::default(),
             unchecked_exception_classes: WordSet::default(),
+            check_taints: false,
+            find_unreachable_symbols: false,
+            entry_points: EntryPoints::default(),
             use_colors: true,
             check_missing_override: false,
             find_unused_parameters: false,
@@ -419,6 +476,9 @@ impl Settings {
             diff: false,
             trust_existence_checks: true,
             class_initializers: Vec::new(),
+            template_paths: vec!["templates".to_string()],
+            migration_paths: vec!["database/migrations".to_string()],
+            container_xml_path: None,
             check_property_initialization: false,
             check_use_statements: false,
             check_experimental: false,
@@ -365,7 +365,8 @@ impl<'ast, 'arena> Analyzable<'ast, 'arena> for Class<'arena> {
 
         // Call plugin on_enter_class hooks
         if context.plugin_registry.has_class_hooks() {
-            let mut hook_context = HookContext::new(context.codebase, context.source_file, block_context, artifacts);
+            let mut hook_context =
+                HookContext::new(context.codebase, context.source_file, context.templates, block_context, artifacts);
             context.plugin_registry.on_enter_class(self, class_like_metadata, &mut hook_context)?;
             for reported in hook_context.take_issues() {
                 context.collector.report_with_code(reported.code, reported.issue);
@@ -471,7 +472,8 @@ impl<'ast, 'arena> Analyzable<'ast, 'arena> for Class<'arena> {
 
         // Call plugin on_leave_class hooks
         if context.plugin_registry.has_class_hooks() {
-            let mut hook_context = HookContext::new(context.codebase, context.source_file, block_context, artifacts);
+            let mut hook_context =
+                HookContext::new(context.codebase, context.source_file, context.templates, block_context, artifacts);
             context.plugin_registry.on_leave_class(self, class_like_metadata, &mut hook_context)?;
             for reported in hook_context.take_issues() {
                 context.collector.report_with_code(reported.code, reported.issue);
@@ -508,7 +510,8 @@ impl<'ast, 'arena> Analyzable<'ast, 'arena> for Interface<'arena> {
 
         // Call plugin on_enter_interface hooks
         if context.plugin_registry.has_interface_hooks() {
-            let mut hook_context = HookContext::new(context.codebase, context.source_file, block_context, artifacts);
+            let mut hook_context =
+                HookContext::new(context.codebase, context.source_file, context.templates, block_context, artifacts);
             context.plugin_registry.on_enter_interface(self, class_like_metadata, &mut hook_context)?;
             for reported in hook_context.take_issues() {
                 context.collector.report_with_code(reported.code, reported.issue);
@@ -552,7 +555,8 @@ impl<'ast, 'arena> Analyzable<'ast, 'arena> for Interface<'arena> {
 
         // Call plugin on_leave_interface hooks
         if context.plugin_registry.has_interface_hooks() {
-            let mut hook_context = HookContext::new(context.codebase, context.source_file, block_context, artifacts);
+            let mut hook_context =
+                HookContext::new(context.codebase, context.source_file, context.templates, block_context, artifacts);
             context.plugin_registry.on_leave_interface(self, class_like_metadata, &mut hook_context)?;
             for reported in hook_context.take_issues() {
                 context.collector.report_with_code(reported.code, reported.issue);
@@ -589,7 +593,8 @@ impl<'ast, 'arena> Analyzable<'ast, 'arena> for Trait<'arena> {
 
         // Call plugin on_enter_trait hooks
         if context.plugin_registry.has_trait_hooks() {
-            let mut hook_context = HookContext::new(context.codebase, context.source_file, block_context, artifacts);
+            let mut hook_context =
+                HookContext::new(context.codebase, context.source_file, context.templates, block_context, artifacts);
             context.plugin_registry.on_enter_trait(self, class_like_metadata, &mut hook_context)?;
             for reported in hook_context.take_issues() {
                 context.collector.report_with_code(reported.code, reported.issue);
@@ -633,7 +638,8 @@ impl<'ast, 'arena> Analyzable<'ast, 'arena> for Trait<'arena> {
 
         // Call plugin on_leave_trait hooks
         if context.plugin_registry.has_trait_hooks() {
-            let mut hook_context = HookContext::new(context.codebase, context.source_file, block_context, artifacts);
+            let mut hook_context =
+                HookContext::new(context.codebase, context.source_file, context.templates, block_context, artifacts);
             context.plugin_registry.on_leave_trait(self, class_like_metadata, &mut hook_context)?;
             for reported in hook_context.take_issues() {
                 context.collector.report_with_code(reported.code, reported.issue);
@@ -670,7 +676,8 @@ impl<'ast, 'arena> Analyzable<'ast, 'arena> for Enum<'arena> {
 
         // Call plugin on_enter_enum hooks
         if context.plugin_registry.has_enum_hooks() {
-            let mut hook_context = HookContext::new(context.codebase, context.source_file, block_context, artifacts);
+            let mut hook_context =
+                HookContext::new(context.codebase, context.source_file, context.templates, block_context, artifacts);
             context.plugin_registry.on_enter_enum(self, class_like_metadata, &mut hook_context)?;
             for reported in hook_context.take_issues() {
                 context.collector.report_with_code(reported.code, reported.issue);
@@ -711,7 +718,8 @@ impl<'ast, 'arena> Analyzable<'ast, 'arena> for Enum<'arena> {
 
         // Call plugin on_leave_enum hooks
         if context.plugin_registry.has_enum_hooks() {
-            let mut hook_context = HookContext::new(context.codebase, context.source_file, block_context, artifacts);
+            let mut hook_context =
+                HookContext::new(context.codebase, context.source_file, context.templates, block_context, artifacts);
             context.plugin_registry.on_leave_enum(self, class_like_metadata, &mut hook_context)?;
             for reported in hook_context.take_issues() {
                 context.collector.report_with_code(reported.code, reported.issue);
@@ -0,0 +1,316 @@
+use std::collections::BTreeMap;
+use std::collections::VecDeque;
+
+use mago_codex::flags::taint::TaintFlags;
+use mago_reporting::Annotation;
+use mago_reporting::Issue;
+use mago_reporting::IssueCollection;
+use mago_span::Span;
+use mago_word::Word;
+
+use crate::code::IssueCode;
+
+/// A node of the taint graph.
+///
+/// Nodes tied to a source location are identified by their span; nodes shared across files
+/// (parameters, return values, and properties) are identified by the lowercased name of the
+/// symbol they belong to, so graphs built for different files connect when merged.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum TaintNode {
+    /// An expression producing untrusted input.
+    Source(Span),
+    /// An expression whose value reaches a sensitive operation.
+    Sink(Span),
+    /// An assignment to a local variable.
+    Assignment(Span),
+    /// The value returned by a call.
+    Call(Span),
+    /// A parameter of a function or method, identified by class, name, and position.
+    Parameter(Word, Word, u32),
+    /// The return value of a function or method, identified by class and name.
+    Return(Word, Word),
+    /// A property, identified by declaring class and name.
+    Property(Word, Word),
+}
+
+/// How data moves along an edge of the taint graph.
+///
+/// Edges entering a function through its parameters and leaving it through its return value
+/// are tagged with the span of the call, so that a value passed at one call site is not
+/// considered returned at another.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+enum TaintEdge {
+    Flow,
+    Enter(Span),
+    Exit(Span),
+}
+
+/// The maximum number of calls tracked when matching returns to the calls that entered a function.
+///
+/// Deeper call chains forget the outermost call, and may return to any of its call sites.
+const MAX_CALL_DEPTH: usize = 8;
+
+/// A node reached during the search for flows, along with the calls that led into it.
+type TaintState = (TaintNode, Vec<Span>);
+
+/// The location and description of a node, used to render the path of a flow.
+#[derive(Clone, Debug, PartialEq, Eq, PartialOrd, Ord)]
+struct TaintNodeInfo {
+    span: Span,
+    message: String,
+}
+
+/// A whole-program graph of how data flows between taint nodes.
+///
+/// Each analyzed file contributes the edges found in its own code. Once the graphs of every
+/// file are merged with [`TaintGraph::extend`], [`TaintGraph::find_flows`] reports each sink
+/// reachable from a source without passing through an edge that removes the taint.
+#[derive(Clone, Debug, Default)]
+pub struct TaintGraph {
+    nodes: BTreeMap<TaintNode, TaintNodeInfo>,
+    edges: BTreeMap<TaintNode, BTreeMap<(TaintNode, TaintEdge), TaintFlags>>,
+    sources: BTreeMap<TaintNode, TaintFlags>,
+    sinks: BTreeMap<TaintNode, TaintFlags>,
+}
+
+impl TaintGraph {
+    #[must_use]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.edges.is_empty() && self.sources.is_empty() && self.sinks.is_empty()
+    }
+
+    /// Merges another graph into this one.
+    pub fn extend(&mut self, other: TaintGraph) {
+        for (node, info) in other.nodes {
+            self.describe(node, info.span, info.message);
+        }
+
+        for (from, targets) in other.edges {
+            for ((to, edge), removes) in targets {
+                self.insert_edge(from, to, edge, removes);
+            }
+        }
+
+        for (node, kinds) in other.sources {
+            self.add_source(node, kinds);
+        }
+
+        for (node, kinds) in other.sinks {
+            self.add_sink(node, kinds);
+        }
+    }
+
+    /// Records where `node` is located and how it appears in the path of a flow.
+    ///
+    /// When a node is described more than once, e.g. by two files, the description that
+    /// sorts first is kept so that merging is independent of file order.
+    pub(crate) fn describe(&mut self, node: TaintNode, span: Span, message: String) {
+        let info = TaintNodeInfo { span, message };
+
+        match self.nodes.get_mut(&node) {
+            Some(existing) if info < *existing => *existing = info,
+            Some(_) => {}
+            None => {
+                self.nodes.insert(node, info);
+            }
+        }
+    }
+
+    #[must_use]
+    pub(crate) fn is_described(&self, node: &TaintNode) -> bool {
+        self.nodes.contains_key(node)
+    }
+
+    /// Adds a flow from `from` to `to` that strips the `removes` taint kinds.
+    ///
+    /// Adding the same edge twice keeps only the kinds removed by both.
+    pub(crate) fn add_edge(&mut self, from: TaintNode, to: TaintNode, removes: TaintFlags) {
+        self.insert_edge(from, to, TaintEdge::Flow, removes);
+    }
+
+    /// Adds a flow from an argument of the call at `call` into a parameter of the called function.
+    pub(crate) fn add_call_edge(&mut self, from: TaintNode, to: TaintNode, call: Span) {
+        self.insert_edge(from, to, TaintEdge::Enter(call), TaintFlags::empty());
+    }
+
+    /// Adds a flow from the return value of a function to the result of the call at `call`.
+    pub(crate) fn add_return_edge(&mut self, from: TaintNode, to: TaintNode, call: Span, removes: TaintFlags) {
+        self.insert_edge(from, to, TaintEdge::Exit(call), removes);
+    }
+
+    fn insert_edge(&mut self, from: TaintNode, to: TaintNode, edge: TaintEdge, removes: TaintFlags) {
+        if from == to {
+            return;
+        }
+
+        self.edges
+            .entry(from)
+            .or_default()
+            .entry((to, edge))
+            .and_modify(|existing| *existing = existing.intersection(removes))
+            .or_insert(removes);
+    }
+
+    pub(crate) fn add_source(&mut self, node: TaintNode, kinds: TaintFlags) {
+        if !kinds.is_empty() {
+            *self.sources.entry(node).or_default() |= kinds;
+        }
+    }
+
+    pub(crate) fn add_sink(&mut self, node: TaintNode, kinds: TaintFlags) {
+        if !kinds.is_empty() {
+            *self.sinks.entry(node).or_default() |= kinds;
+        }
+    }
+
+    /// Finds every sink reachable from a source carrying one of the sink's taint kinds.
+    ///
+    /// A value that enters a function through a call only leaves it through the same call.
+    /// One issue is reported per sink and taint kind. The primary annotation points at the
+    /// sink; secondary annotations trace the shortest path from a source to it.
+    #[must_use]
+    pub fn find_flows(&self) -> IssueCollection {
+        let mut issues = IssueCollection::new();
+        if self.sources.is_empty() || self.sinks.is_empty() {
+            return issues;
+        }
+
+        for kind in TaintFlags::KINDS {
+            if !self.sinks.values().any(|kinds| kinds.contains(kind)) {
+                continue;
+            }
+
+            let mut parents: BTreeMap<TaintState, Option<TaintState>> = BTreeMap::new();
+            let mut reached: BTreeMap<TaintNode, TaintState> = BTreeMap::new();
+            let mut queue = VecDeque::new();
+            for (node, kinds) in &self.sources {
+                if kinds.contains(kind) {
+                    parents.insert((*node, vec![]), None);
+                    queue.push_back((*node, vec![]));
+                }
+            }
+
+            while let Some(state) = queue.pop_front() {
+                let (node, calls) = &state;
+                if self.sinks.get(node).is_some_and(|kinds| kinds.contains(kind)) {
+                    reached.entry(*node).or_insert_with(|| state.clone());
+                }
+
+                let Some(targets) = self.edges.get(node) else {
+                    continue;
+                };
+
+                for ((target, edge), removes) in targets {
+                    if removes.contains(kind) {
+                        continue;
+                    }
+
+                    let calls = match edge {
+                        // Properties are shared by every caller, so the calls that led to one are forgotten.
+                        TaintEdge::Flow if matches!(target, TaintNode::Property(..)) => vec![],
+                        TaintEdge::Flow => calls.clone(),
+                        TaintEdge::Enter(call) => {
+                            let mut calls = calls.clone();
+                            if calls.len() == MAX_CALL_DEPTH {
+                                calls.remove(0);
+                            }
+
+                            calls.push(*call);
+                            calls
+                        }
+                        TaintEdge::Exit(call) => match calls.split_last() {
+                            Some((last, rest)) if last == call => rest.to_vec(),
+                            Some(_) => continue,
+                            None => vec![],
+                        },
+                    };
+
+                    let next = (*target, calls);
+                    if parents.contains_key(&next) {
+                        continue;
+                    }
+
+                    parents.insert(next.clone(), Some(state.clone()));
+                    queue.push_back(next);
+                }
+            }
+
+            for (sink, state) in reached {
+                let mut path = vec![];
+                let mut current = parents.get(&state).cloned().flatten();
+                while let Some(parent) = current {
+                    current = parents.get(&parent).cloned().flatten();
+                    path.push(parent.0);
+                }
+
+                path.reverse();
+
+                if let Some(issue) = self.build_issue(kind, sink, &path) {
+                    issues.push(issue);
+                }
+            }
+        }
+
+        issues
+    }
+
+    fn build_issue(&self, kind: TaintFlags, sink: TaintNode, path: &[TaintNode]) -> Option<Issue> {
+        let sink_info = self.nodes.get(&sink)?;
+
+        let (code, message, help) = match kind {
+            TaintFlags::HTML => (
+                IssueCode::TaintedHtml,
+                "Untrusted input is rendered as HTML, which allows cross-site scripting.",
+                "Escape the value with `htmlspecialchars()` before output, or mark your own escaping function with `@mago-taint-escape html`.",
+            ),
+            TaintFlags::SQL => (
+                IssueCode::TaintedSql,
+                "Untrusted input is used in an SQL query, which allows SQL injection.",
+                "Use a prepared statement with bound parameters instead of building the query from input.",
+            ),
+            TaintFlags::SHELL => (
+                IssueCode::TaintedShell,
+                "Untrusted input is used in a shell command, which allows command injection.",
+                "Escape each argument with `escapeshellarg()`, or avoid the shell by passing an argument list to `proc_open()`.",
+            ),
+            TaintFlags::INCLUDE => (
+                IssueCode::TaintedInclude,
+                "Untrusted input is used as the path of an include, which allows file inclusion.",
+                "Map the input to a fixed list of allowed files instead of building the path from it.",
+            ),
+            _ => (
+                IssueCode::TaintedUnserialize,
+                "Untrusted input is passed to `unserialize()`, which allows object injection.",
+                "Decode the input with `json_decode()` instead, or pass `['allowed_classes' => false]` to `unserialize()`.",
+            ),
+        };
+
+        let mut issue = Issue::error(message)
+            .with_code(code)
+            .with_annotation(Annotation::primary(sink_info.span).with_message(sink_info.message.clone()));
+
+        let mut previous_span = sink_info.span;
+        for node in path {
+            if let Some(info) = self.nodes.get(node)
+                && info.span != sink_info.span
+                && info.span != previous_span
+            {
+                issue = issue.with_annotation(Annotation::secondary(info.span).with_message(info.message.clone()));
+                previous_span = info.span;
+            }
+        }
+
+        Some(
+            issue
+                .with_note(
+                    "Taint analysis follows untrusted input through assignments, calls, returns, and properties.",
+                )
+                .with_help(help),
+        )
+    }
+}
@@ -0,0 +1,36 @@
+//! Interprocedural taint analysis.
+//!
+//! When [`Settings::check_taints`](crate::settings::Settings::check_taints) is enabled, the
+//! analyzer records, for each file, how untrusted input flows through assignments, calls,
+//! returns, properties, and array entries into a [`TaintGraph`]. The graphs of all files are
+//! merged in [`AnalysisResult::taint_graph`](crate::analysis_result::AnalysisResult::taint_graph),
+//! and [`TaintGraph::find_flows`] reports every path from a source to a sink.
+
+use mago_codex::metadata::CodebaseMetadata;
+use mago_database::file::File;
+use mago_names::ResolvedNames;
+use mago_syntax::cst::Program;
+
+use crate::artifacts::AnalysisArtifacts;
+use crate::taint::walker::TaintWalker;
+
+pub mod graph;
+
+mod specs;
+mod walker;
+
+pub use graph::TaintGraph;
+pub use graph::TaintNode;
+
+/// Builds the taint graph of a single file from the artifacts of its analysis.
+pub(crate) fn build_graph<'arena>(
+    program: &Program<'arena>,
+    codebase: &CodebaseMetadata,
+    resolved_names: &ResolvedNames<'arena>,
+    artifacts: &AnalysisArtifacts,
+    file: &File,
+) -> TaintGraph {
+    let mut walker = TaintWalker::new(codebase, resolved_names, artifacts, file);
+    walker.walk_statements(program.statements.iter());
+    walker.finish()
+}
@@ -0,0 +1,183 @@
+//! Built-in taint sources, sinks, and sanitizers for PHP functions and common libraries.
+
+use mago_codex::flags::taint::TaintFlags;
+
+/// Which arguments of a call a sink applies to.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub(super) enum Arguments {
+    All,
+    Index(usize),
+    Last,
+}
+
+impl Arguments {
+    pub(super) fn matches(self, index: usize, count: usize) -> bool {
+        match self {
+            Arguments::All => true,
+            Arguments::Index(expected) => index == expected,
+            Arguments::Last => index + 1 == count,
+        }
+    }
+}
+
+/// How a function or method interacts with tainted data.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub(super) enum TaintSpec {
+    /// The call returns untrusted input.
+    Source,
+    /// The selected arguments reach a sink of the given kinds.
+    Sink(TaintFlags, Arguments),
+    /// The returned value no longer carries the given kinds.
+    Sanitizer(TaintFlags),
+}
+
+/// Keys of `$_SERVER` whose values are controlled by the client.
+pub(super) fn is_tainted_server_key(key: &[u8]) -> bool {
+    key.starts_with(b"HTTP_")
+        || matches!(
+            key,
+            b"REQUEST_URI"
+                | b"QUERY_STRING"
+                | b"PHP_SELF"
+                | b"PATH_INFO"
+                | b"ORIG_PATH_INFO"
+                | b"PATH_TRANSLATED"
+                | b"REQUEST_METHOD"
+                | b"CONTENT_TYPE"
+                | b"argv"
+        )
+}
+
+/// Superglobals whose whole contents are controlled by the client.
+pub(super) fn is_tainted_superglobal(name: &[u8]) -> bool {
+    matches!(name, b"$_GET" | b"$_POST" | b"$_REQUEST" | b"$_COOKIE" | b"$_FILES")
+}
+
+/// Returns the taint behaviour of a global function, given its lowercased name.
+pub(super) fn function_spec(name: &[u8]) -> Option<TaintSpec> {
+    const HTML: TaintFlags = TaintFlags::HTML;
+    const SQL: TaintFlags = TaintFlags::SQL;
+    const SHELL: TaintFlags = TaintFlags::SHELL;
+
+    Some(match name {
+        b"getallheaders" | b"apache_request_headers" | b"filter_input" | b"filter_input_array" => TaintSpec::Source,
+        b"printf" | b"vprintf" => TaintSpec::Sink(HTML, Arguments::All),
+        b"mysqli_query" | b"mysqli_real_query" | b"mysqli_multi_query" | b"mysqli_prepare" => {
+            TaintSpec::Sink(SQL, Arguments::Index(1))
+        }
+        b"pg_query" | b"pg_send_query" => TaintSpec::Sink(SQL, Arguments::Last),
+        b"exec" | b"shell_exec" | b"system" | b"passthru" | b"popen" | b"proc_open" | b"pcntl_exec" => {
+            TaintSpec::Sink(SHELL, Arguments::Index(0))
+        }
+        b"unserialize" => TaintSpec::Sink(TaintFlags::UNSERIALIZE, Arguments::Index(0)),
+        b"htmlspecialchars" | b"htmlentities" => TaintSpec::Sanitizer(HTML),
+        b"escapeshellarg" | b"escapeshellcmd" => TaintSpec::Sanitizer(SHELL),
+        b"mysqli_real_escape_string"
+        | b"mysqli_escape_string"
+        | b"pg_escape_string"
+        | b"pg_escape_literal"
+        | b"pg_escape_identifier" => TaintSpec::Sanitizer(SQL),
+        b"md5" | b"sha1" | b"hash" | b"hash_hmac" | b"crc32" | b"bin2hex" | b"urlencode" | b"rawurlencode" => {
+            TaintSpec::Sanitizer(TaintFlags::ALL)
+        }
+        _ => return None,
+    })
+}
+
+/// Taint behaviour of methods, keyed by the class or interface declaring them.
+///
+/// A spec applies to calls on any instance of the class, including subclasses and implementations.
+const METHOD_SPECS: &[(&str, &[&str], TaintSpec)] = &[
+    // Sources: HTTP requests.
+    (
+        "Symfony\\Component\\HttpFoundation\\Request",
+        &["get", "getContent", "toArray", "getQueryString", "getRequestUri", "getUri", "getPathInfo", "getPayload"],
+        TaintSpec::Source,
+    ),
+    ("Symfony\\Component\\HttpFoundation\\InputBag", &["get", "all", "getString"], TaintSpec::Source),
+    ("Symfony\\Component\\HttpFoundation\\HeaderBag", &["get", "all"], TaintSpec::Source),
+    (
+        "Psr\\Http\\Message\\ServerRequestInterface",
+        &[
+            "getQueryParams",
+            "getParsedBody",
+            "getCookieParams",
+            "getUploadedFiles",
+            "getServerParams",
+            "getBody",
+            "getHeader",
+            "getHeaderLine",
+            "getHeaders",
+        ],
+        TaintSpec::Source,
+    ),
+    (
+        "Illuminate\\Http\\Request",
+        &[
+            "input",
+            "query",
+            "post",
+            "get",
+            "all",
+            "cookie",
+            "header",
+            "json",
+            "string",
+            "str",
+            "collect",
+            "only",
+            "except",
+            "getContent",
+            "validated",
+            "safe",
+        ],
+        TaintSpec::Source,
+    ),
+    // Sinks: SQL.
+    ("PDO", &["query", "exec", "prepare"], TaintSpec::Sink(TaintFlags::SQL, Arguments::Index(0))),
+    (
+        "mysqli",
+        &["query", "real_query", "multi_query", "prepare"],
+        TaintSpec::Sink(TaintFlags::SQL, Arguments::Index(0)),
+    ),
+    ("SQLite3", &["query", "exec", "querySingle"], TaintSpec::Sink(TaintFlags::SQL, Arguments::Index(0))),
+    (
+        "Doctrine\\DBAL\\Connection",
+        &["executeQuery", "executeStatement", "executeUpdate", "query", "exec", "prepare"],
+        TaintSpec::Sink(TaintFlags::SQL, Arguments::Index(0)),
+    ),
+    ("Doctrine\\ORM\\EntityManagerInterface", &["createQuery"], TaintSpec::Sink(TaintFlags::SQL, Arguments::Index(0))),
+    (
+        "Illuminate\\Database\\ConnectionInterface",
+        &["select", "selectOne", "statement", "unprepared", "insert", "update", "delete", "affectingStatement", "raw"],
+        TaintSpec::Sink(TaintFlags::SQL, Arguments::Index(0)),
+    ),
+    (
+        "Illuminate\\Support\\Facades\\DB",
+        &["select", "selectOne", "statement", "unprepared", "insert", "update", "delete", "affectingStatement", "raw"],
+        TaintSpec::Sink(TaintFlags::SQL, Arguments::Index(0)),
+    ),
+    (
+        "Illuminate\\Database\\Query\\Builder",
+        &["whereRaw", "orWhereRaw", "selectRaw", "orderByRaw", "havingRaw", "orHavingRaw", "groupByRaw", "fromRaw"],
+        TaintSpec::Sink(TaintFlags::SQL, Arguments::Index(0)),
+    ),
+    (
+        "Illuminate\\Database\\Eloquent\\Builder",
+        &["whereRaw", "orWhereRaw", "selectRaw", "orderByRaw", "havingRaw", "orHavingRaw", "groupByRaw", "fromRaw"],
+        TaintSpec::Sink(TaintFlags::SQL, Arguments::Index(0)),
+    ),
+    // Sanitizers.
+    ("PDO", &["quote"], TaintSpec::Sanitizer(TaintFlags::SQL)),
+    ("mysqli", &["real_escape_string", "escape_string"], TaintSpec::Sanitizer(TaintFlags::SQL)),
+    ("SQLite3", &["escapeString"], TaintSpec::Sanitizer(TaintFlags::SQL)),
+];
+
+/// Returns the taint behaviour of `method` called on an instance of a class, given a predicate
+/// telling whether that class is an instance of another.
+pub(super) fn method_spec(method: &[u8], is_instance_of: impl Fn(&str) -> bool) -> Option<TaintSpec> {
+    METHOD_SPECS.iter().find_map(|(class, methods, spec)| {
+        (methods.iter().any(|candidate| candidate.as_bytes().eq_ignore_ascii_case(method)) && is_instance_of(class))
+            .then_some(*spec)
+    })
+}
@@ -0,0 +1,1699 @@
+use std::collections::BTreeMap;
+use std::collections::BTreeSet;
+
+use foldhash::HashMap;
+
+use mago_codex::flags::taint::TaintFlags;
+use mago_codex::get_anonymous_class_name;
+use mago_codex::identifier::method::MethodIdentifier;
+use mago_codex::metadata::CodebaseMetadata;
+use mago_codex::metadata::function_like::FunctionLikeMetadata;
+use mago_codex::ttype::atomic::TAtomic;
+use mago_codex::ttype::atomic::object::TObject;
+use mago_codex::ttype::atomic::scalar::TScalar;
+use mago_database::file::File;
+use mago_names::ResolvedNames;
+use mago_span::HasSpan;
+use mago_span::Span;
+use mago_syntax::cst::Access;
+use mago_syntax::cst::Argument;
+use mago_syntax::cst::ArgumentList;
+use mago_syntax::cst::ArrayAccess;
+use mago_syntax::cst::ArrayElement;
+use mago_syntax::cst::AssignmentOperator;
+use mago_syntax::cst::BinaryOperator;
+use mago_syntax::cst::Call;
+use mago_syntax::cst::ClassLikeMember;
+use mago_syntax::cst::ClassLikeMemberSelector;
+use mago_syntax::cst::Closure;
+use mago_syntax::cst::CompositeString;
+use mago_syntax::cst::Construct;
+use mago_syntax::cst::DeclareBody;
+use mago_syntax::cst::Expression;
+use mago_syntax::cst::ForBody;
+use mago_syntax::cst::ForeachBody;
+use mago_syntax::cst::ForeachTarget;
+use mago_syntax::cst::FunctionLikeParameterList;
+use mago_syntax::cst::IfBody;
+use mago_syntax::cst::Literal;
+use mago_syntax::cst::MatchArm;
+use mago_syntax::cst::Method;
+use mago_syntax::cst::MethodBody;
+use mago_syntax::cst::NamespaceBody;
+use mago_syntax::cst::Statement;
+use mago_syntax::cst::StaticItem;
+use mago_syntax::cst::StringPart;
+use mago_syntax::cst::SwitchBody;
+use mago_syntax::cst::SwitchCase;
+use mago_syntax::cst::UnaryPrefixOperator;
+use mago_syntax::cst::Variable;
+use mago_syntax::cst::WhileBody;
+use mago_syntax::cst::Yield;
+use mago_word::Word;
+use mago_word::ascii_lowercase_word;
+use mago_word::empty_word;
+use mago_word::word;
+
+use crate::artifacts::AnalysisArtifacts;
+use crate::taint::graph::TaintGraph;
+use crate::taint::graph::TaintNode;
+use crate::taint::specs::TaintSpec;
+use crate::taint::specs::function_spec;
+use crate::taint::specs::is_tainted_server_key;
+use crate::taint::specs::is_tainted_superglobal;
+use crate::taint::specs::method_spec;
+
+/// The taint carried by a value: the graph nodes it flows from, plus the taint of
+/// individual array entries whose keys are known.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+struct Value {
+    nodes: BTreeSet<TaintNode>,
+    entries: BTreeMap<Vec<u8>, Value>,
+}
+
+impl Value {
+    fn node(node: TaintNode) -> Self {
+        Self { nodes: BTreeSet::from([node]), entries: BTreeMap::new() }
+    }
+
+    fn from_nodes(nodes: BTreeSet<TaintNode>) -> Self {
+        Self { nodes, entries: BTreeMap::new() }
+    }
+
+    fn is_empty(&self) -> bool {
+        self.nodes.is_empty() && self.entries.values().all(Value::is_empty)
+    }
+
+    fn union(&mut self, other: Value) {
+        self.nodes.extend(other.nodes);
+        for (key, value) in other.entries {
+            self.entries.entry(key).or_default().union(value);
+        }
+    }
+
+    /// Returns every node the value, or any of its entries, flows from.
+    fn flatten(&self) -> BTreeSet<TaintNode> {
+        let mut nodes = self.nodes.clone();
+        for entry in self.entries.values() {
+            nodes.extend(entry.flatten());
+        }
+
+        nodes
+    }
+
+    /// Returns the taint of the entry at `key`, which includes the taint of the whole array.
+    fn get(&self, key: &[u8]) -> Value {
+        let mut value = self.entries.get(key).cloned().unwrap_or_default();
+        value.nodes.extend(self.nodes.iter().copied());
+        value
+    }
+}
+
+/// The taint of every local variable at a point in a function body.
+#[derive(Clone, Debug, Default)]
+struct Scope {
+    variables: BTreeMap<Word, Value>,
+    terminated: bool,
+}
+
+impl Scope {
+    /// Joins the states of two control flow paths; a path that has returned or thrown does not contribute.
+    fn merge(mut self, other: Scope) -> Scope {
+        if self.terminated {
+            return other;
+        }
+
+        if other.terminated {
+            return self;
+        }
+
+        for (name, value) in other.variables {
+            self.variables.entry(name).or_default().union(value);
+        }
+
+        self
+    }
+}
+
+/// An argument of a call, evaluated.
+struct EvaluatedArgument<'ast, 'arena> {
+    name: Option<&'arena [u8]>,
+    expression: &'ast Expression<'arena>,
+    value: Value,
+}
+
+/// A resolved target of a call.
+struct CallTarget<'ctx> {
+    identifier: Option<(Word, Word)>,
+    metadata: Option<&'ctx FunctionLikeMetadata>,
+    spec: Option<TaintSpec>,
+    display: String,
+}
+
+/// Walks a program and records how data flows between taint nodes.
+///
+/// The walk is flow-sensitive within a function body: assignments replace the taint of a
+/// variable, branches are joined, and loops are walked until their effect is visible. Data
+/// crossing a function boundary goes through the shared parameter, return, and property
+/// nodes of the graph, which connect when the graphs of all files are merged.
+pub(super) struct TaintWalker<'ctx, 'arena> {
+    codebase: &'ctx CodebaseMetadata,
+    resolved_names: &'ctx ResolvedNames<'arena>,
+    artifacts: &'ctx AnalysisArtifacts,
+    file: &'ctx File,
+    method_receivers: HashMap<(u32, u32), Vec<Word>>,
+    graph: TaintGraph,
+    class: Option<Word>,
+    function: Option<(Word, Word)>,
+    scope: Scope,
+}
+
+impl<'ctx, 'arena> TaintWalker<'ctx, 'arena> {
+    pub(super) fn new(
+        codebase: &'ctx CodebaseMetadata,
+        resolved_names: &'ctx ResolvedNames<'arena>,
+        artifacts: &'ctx AnalysisArtifacts,
+        file: &'ctx File,
+    ) -> Self {
+        let mut method_receivers: HashMap<(u32, u32), Vec<Word>> = HashMap::default();
+        for call in &artifacts.resolved_method_calls {
+            let receivers = method_receivers.entry(call.span).or_default();
+            if !receivers.contains(&call.class) {
+                receivers.push(call.class);
+            }
+        }
+
+        Self {
+            codebase,
+            resolved_names,
+            artifacts,
+            file,
+            method_receivers,
+            graph: TaintGraph::new(),
+            class: None,
+            function: None,
+            scope: Scope::default(),
+        }
+    }
+
+    pub(super) fn finish(self) -> TaintGraph {
+        self.graph
+    }
+
+    pub(super) fn walk_statements<'ast>(&mut self, statements: impl IntoIterator<Item = &'ast Statement<'arena>>)
+    where
+        'arena: 'ast,
+    {
+        for statement in statements {
+            if self.scope.terminated && !is_declaration(statement) {
+                continue;
+            }
+
+            self.walk_statement(statement);
+        }
+    }
+
+    fn walk_statement(&mut self, statement: &Statement<'arena>) {
+        match statement {
+            Statement::Namespace(namespace) => match &namespace.body {
+                NamespaceBody::Implicit(body) => self.walk_statements(body.statements.iter()),
+                NamespaceBody::BraceDelimited(block) => self.walk_statements(block.statements.iter()),
+            },
+            Statement::Class(class) => {
+                self.walk_class_like(word(self.resolved_names.get(&class.name)), class.members.iter());
+            }
+            Statement::Interface(interface) => {
+                self.walk_class_like(word(self.resolved_names.get(&interface.name)), interface.members.iter());
+            }
+            Statement::Trait(r#trait) => {
+                self.walk_class_like(word(self.resolved_names.get(&r#trait.name)), r#trait.members.iter());
+            }
+            Statement::Enum(r#enum) => {
+                self.walk_class_like(word(self.resolved_names.get(&r#enum.name)), r#enum.members.iter());
+            }
+            Statement::Block(block) => self.walk_statements(block.statements.iter()),
+            Statement::Function(function) => {
+                let name = self.resolved_names.get(&function.name);
+                let identifier = (empty_word(), ascii_lowercase_word(name));
+                let display = format!("{}()", String::from_utf8_lossy(name));
+
+                self.walk_function_like(
+                    identifier,
+                    &display,
+                    function.name.span,
+                    &function.parameter_list,
+                    function.body.statements.iter(),
+                );
+            }
+            Statement::Declare(declare) => match &declare.body {
+                DeclareBody::Statement(statement) => self.walk_statement(statement),
+                DeclareBody::ColonDelimited(body) => self.walk_statements(body.statements.iter()),
+            },
+            Statement::Try(r#try) => {
+                let entry = self.scope.clone();
+                self.walk_statements(r#try.block.statements.iter());
+                let mut result = std::mem::take(&mut self.scope);
+
+                for clause in r#try.catch_clauses.iter() {
+                    self.scope = entry.clone().merge(result.clone());
+                    self.scope.terminated = false;
+                    if let Some(variable) = &clause.variable {
+                        self.scope.variables.remove(&word(variable.name));
+                    }
+
+                    self.walk_statements(clause.block.statements.iter());
+                    result = result.merge(std::mem::take(&mut self.scope));
+                }
+
+                self.scope = result;
+                if let Some(finally) = &r#try.finally_clause {
+                    let terminated = self.scope.terminated;
+                    self.scope.terminated = false;
+                    self.walk_statements(finally.block.statements.iter());
+                    self.scope.terminated |= terminated;
+                }
+            }
+            Statement::Foreach(foreach) => {
+                let iterated = self.eval(foreach.expression);
+                let element = Value::from_nodes(iterated.flatten());
+
+                self.walk_loop(|walker| {
+                    match &foreach.target {
+                        ForeachTarget::Value(target) => {
+                            walker.assign(target.value, element.clone());
+                        }
+                        ForeachTarget::KeyValue(target) => {
+                            walker.assign(target.key, element.clone());
+                            walker.assign(target.value, element.clone());
+                        }
+                    }
+
+                    match &foreach.body {
+                        ForeachBody::Statement(statement) => walker.walk_statement(statement),
+                        ForeachBody::ColonDelimited(body) => walker.walk_statements(body.statements.iter()),
+                    }
+                });
+            }
+            Statement::For(r#for) => {
+                for initialization in r#for.initializations.iter() {
+                    self.eval(initialization);
+                }
+
+                self.walk_loop(|walker| {
+                    for condition in r#for.conditions.iter() {
+                        walker.eval(condition);
+                    }
+
+                    match &r#for.body {
+                        ForBody::Statement(statement) => walker.walk_statement(statement),
+                        ForBody::ColonDelimited(body) => walker.walk_statements(body.statements.iter()),
+                    }
+
+                    for increment in r#for.increments.iter() {
+                        walker.eval(increment);
+                    }
+                });
+            }
+            Statement::While(r#while) => {
+                self.walk_loop(|walker| {
+                    walker.eval(r#while.condition);
+
+                    match &r#while.body {
+                        WhileBody::Statement(statement) => walker.walk_statement(statement),
+                        WhileBody::ColonDelimited(body) => walker.walk_statements(body.statements.iter()),
+                    }
+                });
+            }
+            Statement::DoWhile(do_while) => {
+                self.walk_statement(do_while.statement);
+                self.walk_loop(|walker| {
+                    walker.eval(do_while.condition);
+                    walker.walk_statement(do_while.statement);
+                });
+            }
+            Statement::Switch(switch) => {
+                self.eval(switch.expression);
+
+                let cases = match &switch.body {
+                    SwitchBody::BraceDelimited(body) => &body.cases,
+                    SwitchBody::ColonDelimited(body) => &body.cases,
+                };
+
+                let entry = self.scope.clone();
+                let mut result: Option<Scope> = None;
+                let mut previous: Option<Scope> = None;
+                let mut has_default = false;
+                for case in cases.iter() {
+                    self.scope = match previous.take() {
+                        Some(previous) if !previous.terminated => entry.clone().merge(previous),
+                        _ => entry.clone(),
+                    };
+
+                    let statements = match case {
+                        SwitchCase::Expression(case) => {
+                            self.eval(case.expression);
+                            &case.statements
+                        }
+                        SwitchCase::Default(case) => {
+                            has_default = true;
+                            &case.statements
+                        }
+                    };
+
+                    self.walk_statements(statements.iter());
+                    previous = Some(self.scope.clone());
+                    result = Some(match result {
+                        Some(result) => result.merge(self.scope.clone()),
+                        None => self.scope.clone(),
+                    });
+                }
+
+                let mut result = result.unwrap_or_else(|| entry.clone());
+                if !has_default {
+                    result = result.merge(entry);
+                }
+
+                self.scope = result;
+            }
+            Statement::If(r#if) => {
+                self.eval(r#if.condition);
+                let entry = self.scope.clone();
+
+                let mut branches = vec![];
+                let mut has_else = false;
+                match &r#if.body {
+                    IfBody::Statement(body) => {
+                        self.walk_statement(body.statement);
+                        branches.push(std::mem::replace(&mut self.scope, entry.clone()));
+
+                        for clause in body.else_if_clauses.iter() {
+                            self.eval(clause.condition);
+                            self.walk_statement(clause.statement);
+                            branches.push(std::mem::replace(&mut self.scope, entry.clone()));
+                        }
+
+                        if let Some(clause) = &body.else_clause {
+                            has_else = true;
+                            self.walk_statement(clause.statement);
+                            branches.push(std::mem::replace(&mut self.scope, entry.clone()));
+                        }
+                    }
+                    IfBody::ColonDelimited(body) => {
+                        self.walk_statements(body.statements.iter());
+                        branches.push(std::mem::replace(&mut self.scope, entry.clone()));
+
+                        for clause in body.else_if_clauses.iter() {
+                            self.eval(clause.condition);
+                            self.walk_statements(clause.statements.iter());
+                            branches.push(std::mem::replace(&mut self.scope, entry.clone()));
+                        }
+
+                        if let Some(clause) = &body.else_clause {
+                            has_else = true;
+                            self.walk_statements(clause.statements.iter());
+                            branches.push(std::mem::replace(&mut self.scope, entry.clone()));
+                        }
+                    }
+                }
+
+                if !has_else {
+                    branches.push(entry);
+                }
+
+                self.scope = branches.into_iter().reduce(Scope::merge).unwrap_or_default();
+            }
+            Statement::Return(r#return) => {
+                if let Some(value) = r#return.value {
+                    let value = self.eval(value);
+                    if let Some((class, name)) = self.function {
+                        self.flow(&value.flatten(), TaintNode::Return(class, name), TaintFlags::empty());
+                    }
+                }
+
+                self.scope.terminated = true;
+            }
+            Statement::Expression(statement) => {
+                self.eval(statement.expression);
+                if matches!(
+                    statement.expression,
+                    Expression::Throw(_) | Expression::Construct(Construct::Exit(_) | Construct::Die(_))
+                ) {
+                    self.scope.terminated = true;
+                }
+            }
+            Statement::Echo(echo) => {
+                for value in echo.values.iter() {
+                    self.eval_into_sink(value, TaintFlags::HTML, "`echo`");
+                }
+            }
+            Statement::EchoTag(echo) => {
+                for value in echo.values.iter() {
+                    self.eval_into_sink(value, TaintFlags::HTML, "`<?=`");
+                }
+            }
+            Statement::Global(global) => {
+                for variable in global.variables.iter() {
+                    if let Variable::Direct(variable) = variable {
+                        self.scope.variables.remove(&word(variable.name));
+                    }
+                }
+            }
+            Statement::Static(r#static) => {
+                for item in r#static.items.iter() {
+                    if let StaticItem::Concrete(item) = item {
+                        let value = self.eval(item.value);
+                        self.assign_variable(word(item.variable.name), item.variable.span, value);
+                    }
+                }
+            }
+            Statement::Unset(unset) => {
+                for value in unset.values.iter() {
+                    if let Expression::Variable(Variable::Direct(variable)) = value {
+                        self.scope.variables.remove(&word(variable.name));
+                    }
+                }
+            }
+            _ => {}
+        }
+    }
+
+    /// Walks a loop body until assignments made in one iteration are visible to the next.
+    fn walk_loop(&mut self, mut body: impl FnMut(&mut Self)) {
+        let mut merged = self.scope.clone();
+        for _ in 0..2 {
+            body(self);
+            merged = merged.merge(std::mem::take(&mut self.scope));
+            self.scope = merged.clone();
+        }
+    }
+
+    fn walk_class_like<'ast>(&mut self, name: Word, members: impl Iterator<Item = &'ast ClassLikeMember<'arena>>)
+    where
+        'arena: 'ast,
+    {
+        let previous_class = self.class.replace(ascii_lowercase_word(name.as_bytes()));
+
+        for member in members {
+            if let ClassLikeMember::Method(method) = member {
+                self.walk_method(name, method);
+            }
+        }
+
+        self.class = previous_class;
+    }
+
+    fn walk_method(&mut self, class_name: Word, method: &Method<'arena>) {
+        let class = ascii_lowercase_word(class_name.as_bytes());
+        let name = ascii_lowercase_word(method.name.value);
+        let identifier = (class, name);
+        let parameter_count = method.parameter_list.parameters.len() as u32;
+
+        // Calls resolved against an overridden method may dispatch to this one.
+        if let Some(overridden) =
+            self.codebase.get_class_like(class_name.as_bytes()).and_then(|meta| meta.overridden_method_ids.get(&name))
+        {
+            for parent in overridden.values() {
+                let parent_class = ascii_lowercase_word(parent.get_class_name().as_bytes());
+                let parent_name = ascii_lowercase_word(parent.get_method_name().as_bytes());
+
+                for index in 0..parameter_count {
+                    self.graph.add_edge(
+                        TaintNode::Parameter(parent_class, parent_name, index),
+                        TaintNode::Parameter(class, name, index),
+                        TaintFlags::empty(),
+                    );
+                }
+
+                self.graph.add_edge(
+                    TaintNode::Return(class, name),
+                    TaintNode::Return(parent_class, parent_name),
+                    TaintFlags::empty(),
+                );
+            }
+        }
+
+        // Promoted constructor parameters are stored in properties.
+        if name.as_bytes() == b"__construct" {
+            for (index, parameter) in method.parameter_list.parameters.iter().enumerate() {
+                if parameter.is_promoted_property() {
+                    let property = self.property_node(class_name, parameter.variable.name, parameter.variable.span);
+                    if let Some(property) = property {
+                        self.graph.add_edge(
+                            TaintNode::Parameter(class, name, index as u32),
+                            property,
+                            TaintFlags::empty(),
+                        );
+                    }
+                }
+            }
+        }
+
+        let MethodBody::Concrete(body) = &method.body else {
+            return;
+        };
+
+        let display = format!("{}::{}()", class_name, String::from_utf8_lossy(method.name.value));
+        self.walk_function_like(identifier, &display, method.name.span, &method.parameter_list, body.statements.iter());
+    }
+
+    fn walk_function_like<'ast>(
+        &mut self,
+        identifier: (Word, Word),
+        display: &str,
+        name_span: Span,
+        parameters: &FunctionLikeParameterList<'arena>,
+        statements: impl IntoIterator<Item = &'ast Statement<'arena>>,
+    ) where
+        'arena: 'ast,
+    {
+        let (class, name) = identifier;
+        self.graph.describe(
+            TaintNode::Return(class, name),
+            name_span,
+            format!("`{display}` returns the tainted value"),
+        );
+
+        let mut scope = Scope::default();
+        for (index, parameter) in parameters.parameters.iter().enumerate() {
+            let node = TaintNode::Parameter(class, name, index as u32);
+            self.graph.describe(
+                node,
+                parameter.variable.span,
+                format!(
+                    "The tainted value is passed to parameter `{}` of `{display}`",
+                    String::from_utf8_lossy(parameter.variable.name)
+                ),
+            );
+
+            scope.variables.insert(word(parameter.variable.name), Value::node(node));
+        }
+
+        let previous_scope = std::mem::replace(&mut self.scope, scope);
+        let previous_function = self.function.replace(identifier);
+
+        self.walk_statements(statements);
+
+        self.scope = previous_scope;
+        self.function = previous_function;
+    }
+
+    fn walk_closure(&mut self, closure: &Closure<'arena>) {
+        let mut scope = Scope::default();
+        if let Some(use_clause) = &closure.use_clause {
+            for variable in use_clause.variables.iter() {
+                let name = word(variable.variable.name);
+                if let Some(value) = self.scope.variables.get(&name) {
+                    scope.variables.insert(name, value.clone());
+                }
+            }
+        }
+
+        let previous_scope = std::mem::replace(&mut self.scope, scope);
+        let previous_function = self.function.take();
+
+        self.walk_statements(closure.body.statements.iter());
+
+        self.scope = previous_scope;
+        self.function = previous_function;
+    }
+
+    /// Evaluates an expression, recording the flows it creates, and returns the taint of its value.
+    fn eval(&mut self, expression: &Expression<'arena>) -> Value {
+        let value = self.eval_inner(expression);
+        if !value.is_empty() && self.is_taint_free(expression) {
+            return Value::default();
+        }
+
+        value
+    }
+
+    fn eval_inner(&mut self, expression: &Expression<'arena>) -> Value {
+        match expression {
+            Expression::Binary(binary) => {
+                let mut lhs = self.eval(binary.lhs);
+                let rhs = self.eval(binary.rhs);
+
+                match binary.operator {
+                    BinaryOperator::NullCoalesce(_) => {
+                        lhs.union(rhs);
+                        lhs
+                    }
+                    BinaryOperator::StringConcat(_)
+                    | BinaryOperator::Addition(_)
+                    | BinaryOperator::Subtraction(_)
+                    | BinaryOperator::Multiplication(_)
+                    | BinaryOperator::Division(_)
+                    | BinaryOperator::Modulo(_)
+                    | BinaryOperator::Exponentiation(_)
+                    | BinaryOperator::BitwiseAnd(_)
+                    | BinaryOperator::BitwiseOr(_)
+                    | BinaryOperator::BitwiseXor(_)
+                    | BinaryOperator::LeftShift(_)
+                    | BinaryOperator::RightShift(_) => {
+                        let mut nodes = lhs.flatten();
+                        nodes.extend(rhs.flatten());
+                        Value::from_nodes(nodes)
+                    }
+                    _ => Value::default(),
+                }
+            }
+            Expression::UnaryPrefix(unary) => {
+                let operand = self.eval(unary.operand);
+
+                match unary.operator {
+                    UnaryPrefixOperator::Not(_)
+                    | UnaryPrefixOperator::BoolCast(..)
+                    | UnaryPrefixOperator::BooleanCast(..)
+                    | UnaryPrefixOperator::IntCast(..)
+                    | UnaryPrefixOperator::IntegerCast(..)
+                    | UnaryPrefixOperator::FloatCast(..)
+                    | UnaryPrefixOperator::DoubleCast(..)
+                    | UnaryPrefixOperator::RealCast(..)
+                    | UnaryPrefixOperator::UnsetCast(..)
+                    | UnaryPrefixOperator::VoidCast(..) => Value::default(),
+                    _ => operand,
+                }
+            }
+            Expression::UnaryPostfix(unary) => self.eval(unary.operand),
+            Expression::Parenthesized(parenthesized) => self.eval(parenthesized.expression),
+            Expression::CompositeString(string) => {
+                let mut nodes = BTreeSet::new();
+                for part in string.parts().iter() {
+                    match part {
+                        StringPart::Literal(_) => {}
+                        StringPart::Expression(expression) => nodes.extend(self.eval(expression).flatten()),
+                        StringPart::BracedExpression(part) => nodes.extend(self.eval(part.expression).flatten()),
+                    }
+                }
+
+                if let CompositeString::ShellExecute(_) = string {
+                    self.sink(
+                        string.span(),
+                        TaintFlags::SHELL,
+                        &nodes,
+                        "This command is executed by the shell".to_string(),
+                    );
+                }
+
+                Value::from_nodes(nodes)
+            }
+            Expression::Assignment(assignment) => {
+                let value = self.eval(assignment.rhs);
+                let value = match assignment.operator {
+                    AssignmentOperator::Assign(_) => value,
+                    _ => {
+                        let mut current = self.eval(assignment.lhs);
+                        current.union(value);
+                        current
+                    }
+                };
+
+                self.assign(assignment.lhs, value.clone());
+                value
+            }
+            Expression::Conditional(conditional) => {
+                let condition = self.eval(conditional.condition);
+                let mut value = match conditional.then {
+                    Some(then) => self.eval(then),
+                    None => condition,
+                };
+
+                value.union(self.eval(conditional.r#else));
+                value
+            }
+            Expression::Array(array) => self.eval_array_elements(array.elements.iter()),
+            Expression::LegacyArray(array) => self.eval_array_elements(array.elements.iter()),
+            Expression::ArrayAccess(access) => self.eval_array_access(access),
+            Expression::ArrayAppend(append) => self.eval(append.array),
+            Expression::AnonymousClass(class) => {
+                if let Some(argument_list) = &class.argument_list {
+                    for argument in argument_list.arguments.iter() {
+                        if let Some(value) = argument.value() {
+                            self.eval(value);
+                        }
+                    }
+                }
+
+                let name = get_anonymous_class_name(self.file, class.span());
+                self.walk_class_like(name, class.members.iter());
+
+                Value::default()
+            }
+            Expression::Closure(closure) => {
+                self.walk_closure(closure);
+
+                Value::default()
+            }
+            Expression::ArrowFunction(arrow_function) => {
+                let previous_function = self.function.take();
+                let mut scope = self.scope.clone();
+                for parameter in arrow_function.parameter_list.parameters.iter() {
+                    scope.variables.remove(&word(parameter.variable.name));
+                }
+
+                let previous_scope = std::mem::replace(&mut self.scope, scope);
+                self.eval(arrow_function.expression);
+                self.scope = previous_scope;
+                self.function = previous_function;
+
+                Value::default()
+            }
+            Expression::Variable(variable) => self.eval_variable(variable),
+            Expression::Match(r#match) => {
+                self.eval(r#match.expression);
+
+                let mut value = Value::default();
+                for arm in r#match.arms.iter() {
+                    match arm {
+                        MatchArm::Expression(arm) => {
+                            for condition in arm.conditions.iter() {
+                                self.eval(condition);
+                            }
+
+                            value.union(self.eval(arm.expression));
+                        }
+                        MatchArm::Default(arm) => value.union(self.eval(arm.expression)),
+                    }
+                }
+
+                value
+            }
+            Expression::Yield(r#yield) => {
+                match r#yield {
+                    Yield::Value(r#yield) => {
+                        if let Some(value) = r#yield.value {
+                            self.eval(value);
+                        }
+                    }
+                    Yield::Pair(r#yield) => {
+                        self.eval(r#yield.key);
+                        self.eval(r#yield.value);
+                    }
+                    Yield::From(r#yield) => {
+                        self.eval(r#yield.iterator);
+                    }
+                }
+
+                Value::default()
+            }
+            Expression::Construct(construct) => self.eval_construct(construct),
+            Expression::Throw(throw) => {
+                self.eval(throw.exception);
+
+                Value::default()
+            }
+            Expression::Clone(clone) => self.eval(clone.object),
+            Expression::Call(call) => self.eval_call(call),
+            Expression::Access(access) => match access {
+                Access::Property(access) => self.read_property(access.object, &access.property),
+                Access::NullSafeProperty(access) => self.read_property(access.object, &access.property),
+                Access::StaticProperty(access) => {
+                    let Variable::Direct(property) = &access.property else {
+                        return Value::default();
+                    };
+
+                    let mut nodes = BTreeSet::new();
+                    for class in self.class_names(access.class) {
+                        if let Some(node) = self.property_node(class, property.name, access.span()) {
+                            nodes.insert(node);
+                        }
+                    }
+
+                    Value::from_nodes(nodes)
+                }
+                Access::ClassConstant(_) => Value::default(),
+            },
+            Expression::Instantiation(instantiation) => {
+                let arguments = match &instantiation.argument_list {
+                    Some(argument_list) => self.eval_arguments(argument_list),
+                    None => vec![],
+                };
+
+                let mut value = Value::default();
+                for class in self.class_names(instantiation.class) {
+                    let Some(class_metadata) = self.codebase.get_class_like(class.as_bytes()) else {
+                        continue;
+                    };
+
+                    let display = format!("new {}()", class_metadata.original_name);
+                    let constructor = MethodIdentifier::new(class, word("__construct"));
+                    let declaring = self.codebase.get_declaring_method_identifier(&constructor);
+                    let metadata = self.codebase.get_method_by_id(&declaring);
+                    let target = CallTarget {
+                        identifier: metadata.map(|_| {
+                            (
+                                ascii_lowercase_word(declaring.get_class_name().as_bytes()),
+                                ascii_lowercase_word(declaring.get_method_name().as_bytes()),
+                            )
+                        }),
+                        metadata,
+                        spec: None,
+                        display,
+                    };
+
+                    let user_defined = class_metadata.flags.is_user_defined();
+                    let result = self.invoke(&target, &arguments, instantiation.span(), None);
+                    if !user_defined {
+                        value.union(result);
+                    }
+                }
+
+                value
+            }
+            Expression::Pipe(pipe) => {
+                let input = self.eval(pipe.input);
+                self.eval(pipe.callable);
+
+                input
+            }
+            _ => Value::default(),
+        }
+    }
+
+    fn eval_array_elements<'ast>(&mut self, elements: impl Iterator<Item = &'ast ArrayElement<'arena>>) -> Value
+    where
+        'arena: 'ast,
+    {
+        let mut value = Value::default();
+        let mut next_index = 0u64;
+        for element in elements {
+            match element {
+                ArrayElement::KeyValue(element) => {
+                    let key_value = self.eval(element.key);
+                    let element_value = self.eval(element.value);
+
+                    match literal_key(element.key) {
+                        Some(key) => {
+                            if let Ok(index) = std::str::from_utf8(&key).unwrap_or_default().parse::<u64>() {
+                                next_index = next_index.max(index + 1);
+                            }
+
+                            value.entries.insert(key, element_value);
+                        }
+                        None => {
+                            value.nodes.extend(key_value.flatten());
+                            value.nodes.extend(element_value.flatten());
+                        }
+                    }
+                }
+                ArrayElement::Value(element) => {
+                    let element_value = self.eval(element.value);
+                    value.entries.insert(next_index.to_string().into_bytes(), element_value);
+                    next_index += 1;
+                }
+                ArrayElement::Variadic(element) => {
+                    let element_value = self.eval(element.value);
+                    value.nodes.extend(element_value.flatten());
+                }
+                ArrayElement::Missing(_) => {
+                    next_index += 1;
+                }
+            }
+        }
+
+        value
+    }
+
+    fn eval_array_access(&mut self, access: &ArrayAccess<'arena>) -> Value {
+        let key = literal_key(access.index);
+        self.eval(access.index);
+
+        if let Expression::Variable(Variable::Direct(variable)) = access.array {
+            if variable.name == b"$_SERVER" {
+                return match key {
+                    Some(key) if !is_tainted_server_key(&key) => Value::default(),
+                    _ => self.source(access.span(), "$_SERVER"),
+                };
+            }
+
+            if is_tainted_superglobal(variable.name) {
+                return self.source(access.span(), &String::from_utf8_lossy(variable.name));
+            }
+        }
+
+        let array = self.eval(access.array);
+        match key {
+            Some(key) => array.get(&key),
+            None => Value::from_nodes(array.flatten()),
+        }
+    }
+
+    fn eval_variable(&mut self, variable: &Variable<'arena>) -> Value {
+        match variable {
+            Variable::Direct(variable) => {
+                if variable.name == b"$_SERVER" || is_tainted_superglobal(variable.name) {
+                    return self.source(variable.span, &String::from_utf8_lossy(variable.name));
+                }
+
+                self.scope.variables.get(&word(variable.name)).cloned().unwrap_or_default()
+            }
+            Variable::Indirect(variable) => {
+                self.eval(variable.expression);
+
+                Value::default()
+            }
+            Variable::Nested(_) => Value::default(),
+        }
+    }
+
+    fn eval_construct(&mut self, construct: &Construct<'arena>) -> Value {
+        match construct {
+            Construct::Isset(isset) => {
+                for value in isset.values.iter() {
+                    self.eval(value);
+                }
+            }
+            Construct::Empty(empty) => {
+                self.eval(empty.value);
+            }
+            Construct::Eval(eval) => {
+                self.eval(eval.value);
+            }
+            Construct::Include(include) => {
+                self.eval_into_sink(include.value, TaintFlags::INCLUDE, "`include`");
+            }
+            Construct::IncludeOnce(include) => {
+                self.eval_into_sink(include.value, TaintFlags::INCLUDE, "`include_once`");
+            }
+            Construct::Require(require) => {
+                self.eval_into_sink(require.value, TaintFlags::INCLUDE, "`require`");
+            }
+            Construct::RequireOnce(require) => {
+                self.eval_into_sink(require.value, TaintFlags::INCLUDE, "`require_once`");
+            }
+            Construct::Print(print) => {
+                self.eval_into_sink(print.value, TaintFlags::HTML, "`print`");
+            }
+            Construct::Exit(exit) => {
+                if let Some(argument_list) = &exit.arguments {
+                    self.eval_exit_arguments(argument_list, "`exit`");
+                }
+            }
+            Construct::Die(die) => {
+                if let Some(argument_list) = &die.arguments {
+                    self.eval_exit_arguments(argument_list, "`die`");
+                }
+            }
+        }
+
+        Value::default()
+    }
+
+    fn eval_exit_arguments(&mut self, argument_list: &ArgumentList<'arena>, display: &str) {
+        for argument in argument_list.arguments.iter() {
+            self.eval_into_sink(argument.value(), TaintFlags::HTML, display);
+        }
+    }
+
+    fn eval_arguments<'ast>(
+        &mut self,
+        argument_list: &'ast ArgumentList<'arena>,
+    ) -> Vec<EvaluatedArgument<'ast, 'arena>> {
+        argument_list
+            .arguments
+            .iter()
+            .map(|argument| {
+                let (name, expression) = match argument {
+                    Argument::Positional(argument) => (None, argument.value),
+                    Argument::Named(argument) => (Some(argument.name.value), argument.value),
+                };
+
+                EvaluatedArgument { name, expression, value: self.eval(expression) }
+            })
+            .collect()
+    }
+
+    fn eval_call(&mut self, call: &Call<'arena>) -> Value {
+        let span = call.span();
+
+        match call {
+            Call::Function(call) => {
+                let Expression::Identifier(identifier) = call.function else {
+                    let mut nodes = self.eval(call.function).flatten();
+                    for argument in self.eval_arguments(&call.argument_list) {
+                        nodes.extend(argument.value.flatten());
+                    }
+
+                    return self.pass_through(span, "the called function".to_string(), &nodes, TaintFlags::empty());
+                };
+
+                let name = self.resolved_names.get(identifier);
+                let unqualified = identifier.value();
+                let (name, metadata) = match self.codebase.get_function(name) {
+                    Some(metadata) => (name, Some(metadata)),
+                    None if identifier.is_local() => (unqualified, self.codebase.get_function(unqualified)),
+                    None => (name, None),
+                };
+
+                let lowercase_name = ascii_lowercase_word(name);
+                let arguments = self.eval_arguments(&call.argument_list);
+                let display = format!("{}()", metadata.map_or_else(|| word(name), |metadata| metadata.original_name));
+
+                if let Some(value) = self.eval_special_function(lowercase_name.as_bytes(), span, &arguments) {
+                    return value;
+                }
+
+                let user_defined = metadata.is_some_and(|metadata| metadata.flags.is_user_defined());
+                let target = CallTarget {
+                    identifier: metadata.map(|_| (empty_word(), lowercase_name)),
+                    metadata,
+                    spec: if user_defined { None } else { function_spec(lowercase_name.as_bytes()) },
+                    display,
+                };
+
+                self.invoke(&target, &arguments, span, None)
+            }
+            Call::Method(call) => self.eval_method_call(span, call.object, &call.method, &call.argument_list),
+            Call::NullSafeMethod(call) => self.eval_method_call(span, call.object, &call.method, &call.argument_list),
+            Call::StaticMethod(call) => {
+                let arguments = self.eval_arguments(&call.argument_list);
+                let ClassLikeMemberSelector::Identifier(method) = &call.method else {
+                    return self.pass_through_arguments(span, &arguments, None);
+                };
+
+                let mut classes =
+                    self.method_receivers.get(&(span.start.offset, span.end.offset)).cloned().unwrap_or_default();
+                for class in self.class_names(call.class) {
+                    if !classes.iter().any(|known| known.as_bytes().eq_ignore_ascii_case(class.as_bytes())) {
+                        classes.push(class);
+                    }
+                }
+
+                self.invoke_method(span, &classes, method.value, &arguments, None)
+            }
+        }
+    }
+
+    fn eval_method_call(
+        &mut self,
+        span: Span,
+        object: &Expression<'arena>,
+        method: &ClassLikeMemberSelector<'arena>,
+        argument_list: &ArgumentList<'arena>,
+    ) -> Value {
+        let receiver = self.eval(object);
+        let arguments = self.eval_arguments(argument_list);
+
+        let ClassLikeMemberSelector::Identifier(method) = method else {
+            return self.pass_through_arguments(span, &arguments, Some(&receiver));
+        };
+
+        let classes = match self.method_receivers.get(&(span.start.offset, span.end.offset)) {
+            Some(classes) => classes.clone(),
+            None => self.object_class_names(object),
+        };
+
+        self.invoke_method(span, &classes, method.value, &arguments, Some(&receiver))
+    }
+
+    fn invoke_method(
+        &mut self,
+        span: Span,
+        classes: &[Word],
+        method: &[u8],
+        arguments: &[EvaluatedArgument<'_, 'arena>],
+        receiver: Option<&Value>,
+    ) -> Value {
+        if classes.is_empty() {
+            return self.pass_through_arguments(span, arguments, receiver);
+        }
+
+        let mut value = Value::default();
+        let mut seen = BTreeSet::new();
+        for class in classes {
+            let declaring = self.codebase.get_declaring_method_identifier(&MethodIdentifier::new(*class, word(method)));
+            let identifier = (
+                ascii_lowercase_word(declaring.get_class_name().as_bytes()),
+                ascii_lowercase_word(declaring.get_method_name().as_bytes()),
+            );
+
+            if !seen.insert(identifier) {
+                continue;
+            }
+
+            let metadata = self.codebase.get_method_by_id(&declaring);
+            let user_defined = metadata.is_some_and(|metadata| metadata.flags.is_user_defined());
+            let spec = if user_defined {
+                None
+            } else {
+                method_spec(method, |parent| self.codebase.is_instance_of(class.as_bytes(), parent.as_bytes()))
+            };
+
+            let class_name =
+                self.codebase.get_class_like(class.as_bytes()).map_or(*class, |metadata| metadata.original_name);
+            let target = CallTarget {
+                identifier: metadata.map(|_| identifier),
+                metadata,
+                spec,
+                display: format!("{}::{}()", class_name, String::from_utf8_lossy(method)),
+            };
+
+            value.union(self.invoke(&target, arguments, span, receiver));
+        }
+
+        value
+    }
+
+    /// Handles built-in functions whose behaviour depends on the value of their arguments.
+    fn eval_special_function(
+        &mut self,
+        name: &[u8],
+        span: Span,
+        arguments: &[EvaluatedArgument<'_, 'arena>],
+    ) -> Option<Value> {
+        match name {
+            b"filter_input" | b"filter_input_array" => {
+                let filter_index = if name == b"filter_input" { 2 } else { 1 };
+                let kinds = match arguments.get(filter_index).and_then(|argument| constant_name(argument.expression)) {
+                    Some(filter) => TaintFlags::ALL.difference(filter_removes(filter)),
+                    None => TaintFlags::ALL,
+                };
+
+                if kinds.is_empty() {
+                    return Some(Value::default());
+                }
+
+                let node = TaintNode::Call(span);
+                self.graph.add_source(node, kinds);
+                self.graph.describe(
+                    node,
+                    span,
+                    format!("Untrusted input enters through `{}()` here", String::from_utf8_lossy(name)),
+                );
+
+                Some(Value::node(node))
+            }
+            b"filter_var" => {
+                let removes = arguments
+                    .get(1)
+                    .and_then(|argument| constant_name(argument.expression))
+                    .map_or(TaintFlags::empty(), filter_removes);
+                let nodes = arguments.first().map(|argument| argument.value.flatten()).unwrap_or_default();
+
+                Some(self.pass_through(span, "filter_var()".to_string(), &nodes, removes))
+            }
+            b"file_get_contents" => {
+                let reads_input = arguments.first().is_some_and(|argument| {
+                    matches!(
+                        argument.expression,
+                        Expression::Literal(Literal::String(string)) if string.value == Some(b"php://input")
+                    )
+                });
+
+                reads_input.then(|| self.source(span, "php://input"))
+            }
+            _ => None,
+        }
+    }
+
+    /// Records the flows of a call to `target` and returns the taint of its result.
+    fn invoke(
+        &mut self,
+        target: &CallTarget<'_>,
+        arguments: &[EvaluatedArgument<'_, 'arena>],
+        span: Span,
+        receiver: Option<&Value>,
+    ) -> Value {
+        let parameters = target.metadata.map_or(&[][..], |metadata| metadata.parameters.as_slice());
+        let count = arguments.len();
+
+        for (index, argument) in arguments.iter().enumerate() {
+            let parameter_index = parameter_index(parameters, index, argument.name);
+
+            let mut sink = parameter_index.map_or(TaintFlags::empty(), |index| parameters[index].taint_sink);
+            if let Some(TaintSpec::Sink(kinds, selection)) = target.spec
+                && argument.name.is_none()
+                && selection.matches(index, count)
+            {
+                sink |= kinds;
+            }
+
+            if !sink.is_empty() {
+                self.sink(
+                    argument.expression.span(),
+                    sink,
+                    &argument.value.flatten(),
+                    sink_message(sink, &format!("`{}`", target.display)),
+                );
+            }
+        }
+
+        let node = TaintNode::Call(span);
+        let mut escape = target.metadata.map_or(TaintFlags::empty(), |metadata| metadata.taint_escape);
+        if let Some(TaintSpec::Sanitizer(kinds)) = target.spec {
+            escape |= kinds;
+        }
+
+        let mut source = target.metadata.map_or(TaintFlags::empty(), |metadata| metadata.taint_source);
+        if target.spec == Some(TaintSpec::Source) {
+            source = TaintFlags::ALL;
+        }
+
+        let mut has_flow = false;
+        if let (Some((class, name)), Some(metadata)) = (target.identifier, target.metadata) {
+            let is_abstract = metadata.method_metadata.as_ref().is_some_and(|method| method.is_abstract);
+
+            if metadata.flags.is_user_defined() || is_abstract {
+                for (index, argument) in arguments.iter().enumerate() {
+                    let Some(parameter_index) = parameter_index(parameters, index, argument.name) else {
+                        continue;
+                    };
+
+                    for argument_node in argument.value.flatten() {
+                        self.graph.add_call_edge(
+                            argument_node,
+                            TaintNode::Parameter(class, name, parameter_index as u32),
+                            span,
+                        );
+                    }
+                }
+
+                self.graph.add_return_edge(TaintNode::Return(class, name), node, span, escape);
+                has_flow = true;
+            }
+        }
+
+        if !target.metadata.is_some_and(|metadata| metadata.flags.is_user_defined()) {
+            let mut nodes = receiver.map(Value::flatten).unwrap_or_default();
+            for argument in arguments {
+                nodes.extend(argument.value.flatten());
+            }
+
+            if !nodes.is_empty() {
+                self.flow(&nodes, node, escape);
+                has_flow = true;
+            }
+
+            // Arguments passed by reference to functions we cannot see into receive their other inputs.
+            for (index, argument) in arguments.iter().enumerate() {
+                let by_reference = parameter_index(parameters, index, argument.name)
+                    .is_some_and(|index| parameters[index].flags.is_by_reference());
+
+                if by_reference && !nodes.is_empty() && matches!(argument.expression, Expression::Variable(_)) {
+                    self.assign(argument.expression, Value::node(node));
+                }
+            }
+        }
+
+        if !source.is_empty() {
+            self.graph.add_source(node, source);
+            self.graph.describe(node, span, format!("Untrusted input is returned by `{}` here", target.display));
+
+            return Value::node(node);
+        }
+
+        if !has_flow {
+            return Value::default();
+        }
+
+        self.graph.describe(node, span, format!("The tainted value is returned by `{}` here", target.display));
+
+        Value::node(node)
+    }
+
+    fn pass_through_arguments(
+        &mut self,
+        span: Span,
+        arguments: &[EvaluatedArgument<'_, 'arena>],
+        receiver: Option<&Value>,
+    ) -> Value {
+        let mut nodes = receiver.map(Value::flatten).unwrap_or_default();
+        for argument in arguments {
+            nodes.extend(argument.value.flatten());
+        }
+
+        self.pass_through(span, "the called method".to_string(), &nodes, TaintFlags::empty())
+    }
+
+    fn pass_through(&mut self, span: Span, display: String, nodes: &BTreeSet<TaintNode>, removes: TaintFlags) -> Value {
+        if nodes.is_empty() {
+            return Value::default();
+        }
+
+        let node = TaintNode::Call(span);
+        self.flow(nodes, node, removes);
+        self.graph.describe(node, span, format!("The tainted value is returned by {display} here"));
+
+        Value::node(node)
+    }
+
+    fn read_property(&mut self, object: &Expression<'arena>, selector: &ClassLikeMemberSelector<'arena>) -> Value {
+        let receiver = self.eval(object);
+
+        let ClassLikeMemberSelector::Identifier(property) = selector else {
+            return Value::from_nodes(receiver.flatten());
+        };
+
+        let name = [b"$", property.value].concat();
+        let mut value = Value::default();
+        let mut resolved = false;
+        for class in self.object_class_names(object) {
+            if let Some(node) = self.property_node(class, &name, property.span) {
+                value.nodes.insert(node);
+                resolved = true;
+            }
+        }
+
+        if !resolved {
+            value.nodes.extend(receiver.flatten());
+        }
+
+        value
+    }
+
+    /// Returns the node of a property declared in analyzed code, describing it on first use.
+    fn property_node(&mut self, class: Word, property: &[u8], span: Span) -> Option<TaintNode> {
+        let declaring = self.codebase.get_declaring_property_class(class.as_bytes(), property)?;
+        let declaring_metadata = self.codebase.get_class_like(declaring.as_bytes())?;
+        if !declaring_metadata.flags.is_user_defined() {
+            return None;
+        }
+
+        let node = TaintNode::Property(ascii_lowercase_word(declaring.as_bytes()), word(property));
+        if !self.graph.is_described(&node) {
+            let span = self
+                .codebase
+                .get_property(declaring.as_bytes(), property)
+                .and_then(|metadata| metadata.name_span.or(metadata.span))
+                .unwrap_or(span);
+
+            self.graph.describe(
+                node,
+                span,
+                format!(
+                    "The tainted value is stored in property `{}::{}`",
+                    declaring_metadata.original_name,
+                    String::from_utf8_lossy(property)
+                ),
+            );
+        }
+
+        Some(node)
+    }
+
+    fn assign(&mut self, target: &Expression<'arena>, value: Value) {
+        match target {
+            Expression::Variable(Variable::Direct(variable)) => {
+                self.assign_variable(word(variable.name), variable.span, value);
+            }
+            Expression::ArrayAccess(_) | Expression::ArrayAppend(_) => {
+                let mut keys = vec![];
+                let mut base = target;
+                loop {
+                    match base {
+                        Expression::ArrayAccess(access) => {
+                            self.eval(access.index);
+                            keys.push(literal_key(access.index));
+                            base = access.array;
+                        }
+                        Expression::ArrayAppend(append) => {
+                            keys.push(None);
+                            base = append.array;
+                        }
+                        _ => break,
+                    }
+                }
+
+                keys.reverse();
+
+                let Expression::Variable(Variable::Direct(variable)) = base else {
+                    let nodes = value.flatten();
+                    self.write_to(base, &nodes);
+                    return;
+                };
+
+                let mut current = self.scope.variables.entry(word(variable.name)).or_default();
+                let last = keys.len() - 1;
+                for (index, key) in keys.into_iter().enumerate() {
+                    match key {
+                        Some(key) if index == last => {
+                            current.entries.insert(key, value);
+                            return;
+                        }
+                        Some(key) => {
+                            current = current.entries.entry(key).or_default();
+                        }
+                        None => {
+                            current.nodes.extend(value.flatten());
+                            return;
+                        }
+                    }
+                }
+            }
+            Expression::List(list) => self.assign_destructuring(list.elements.iter(), &value),
+            Expression::Array(array) => self.assign_destructuring(array.elements.iter(), &value),
+            Expression::LegacyArray(array) => self.assign_destructuring(array.elements.iter(), &value),
+            _ => self.write_to(target, &value.flatten()),
+        }
+    }
+
+    fn assign_variable(&mut self, name: Word, span: Span, value: Value) {
+        if !value.entries.is_empty() || value.nodes.is_empty() {
+            self.scope.variables.insert(name, value);
+            return;
+        }
+
+        let node = TaintNode::Assignment(span);
+        self.flow(&value.nodes, node, TaintFlags::empty());
+        self.graph.describe(node, span, format!("The tainted value is assigned to `{name}` here"));
+        self.scope.variables.insert(name, Value::node(node));
+    }
+
+    fn assign_destructuring<'ast>(&mut self, elements: impl Iterator<Item = &'ast ArrayElement<'arena>>, value: &Value)
+    where
+        'arena: 'ast,
+    {
+        let mut next_index = 0u64;
+        for element in elements {
+            match element {
+                ArrayElement::KeyValue(element) => match literal_key(element.key) {
+                    Some(key) => self.assign(element.value, value.get(&key)),
+                    None => self.assign(element.value, Value::from_nodes(value.flatten())),
+                },
+                ArrayElement::Value(element) => {
+                    self.assign(element.value, value.get(next_index.to_string().as_bytes()));
+                    next_index += 1;
+                }
+                ArrayElement::Variadic(element) => {
+                    self.assign(element.value, Value::from_nodes(value.flatten()));
+                }
+                ArrayElement::Missing(_) => {
+                    next_index += 1;
+                }
+            }
+        }
+    }
+
+    /// Records a write into a place that is not a local variable, such as a property.
+    fn write_to(&mut self, target: &Expression<'arena>, nodes: &BTreeSet<TaintNode>) {
+        match target {
+            Expression::Access(Access::Property(access)) => {
+                self.eval(access.object);
+                self.write_property(access.object, &access.property, nodes);
+            }
+            Expression::Access(Access::NullSafeProperty(access)) => {
+                self.eval(access.object);
+                self.write_property(access.object, &access.property, nodes);
+            }
+            Expression::Access(Access::StaticProperty(access)) => {
+                let Variable::Direct(property) = &access.property else {
+                    return;
+                };
+
+                for class in self.class_names(access.class) {
+                    if let Some(node) = self.property_node(class, property.name, access.span()) {
+                        self.flow(nodes, node, TaintFlags::empty());
+                    }
+                }
+            }
+            _ => {
+                self.eval(target);
+            }
+        }
+    }
+
+    fn write_property(
+        &mut self,
+        object: &Expression<'arena>,
+        selector: &ClassLikeMemberSelector<'arena>,
+        nodes: &BTreeSet<TaintNode>,
+    ) {
+        let ClassLikeMemberSelector::Identifier(property) = selector else {
+            return;
+        };
+
+        let name = [b"$", property.value].concat();
+        for class in self.object_class_names(object) {
+            if let Some(node) = self.property_node(class, &name, property.span) {
+                self.flow(nodes, node, TaintFlags::empty());
+            }
+        }
+    }
+
+    fn eval_into_sink(&mut self, expression: &Expression<'arena>, kinds: TaintFlags, display: &str) {
+        let value = self.eval(expression);
+        self.sink(expression.span(), kinds, &value.flatten(), sink_message(kinds, display));
+    }
+
+    fn sink(&mut self, span: Span, kinds: TaintFlags, nodes: &BTreeSet<TaintNode>, message: String) {
+        if nodes.is_empty() {
+            return;
+        }
+
+        let node = TaintNode::Sink(span);
+        self.graph.describe(node, span, message);
+        self.graph.add_sink(node, kinds);
+        self.flow(nodes, node, TaintFlags::empty());
+    }
+
+    fn source(&mut self, span: Span, origin: &str) -> Value {
+        let node = TaintNode::Source(span);
+        self.graph.add_source(node, TaintFlags::ALL);
+        self.graph.describe(node, span, format!("Untrusted input enters from `{origin}` here"));
+
+        Value::node(node)
+    }
+
+    fn flow(&mut self, nodes: &BTreeSet<TaintNode>, target: TaintNode, removes: TaintFlags) {
+        for node in nodes {
+            self.graph.add_edge(*node, target, removes);
+        }
+    }
+
+    /// Returns the classes an expression naming a class, such as `Foo`, `self`, or `static`, refers to.
+    fn class_names(&self, expression: &Expression<'arena>) -> Vec<Word> {
+        let current = || self.class.and_then(|class| self.codebase.get_class_like(class.as_bytes()));
+
+        match expression {
+            Expression::Identifier(identifier) => vec![word(self.resolved_names.get(identifier))],
+            Expression::Self_(_) | Expression::Static(_) => {
+                current().map(|metadata| vec![metadata.original_name]).unwrap_or_default()
+            }
+            Expression::Parent(_) => current().and_then(|metadata| metadata.direct_parent_class).into_iter().collect(),
+            _ => self.object_class_names(expression),
+        }
+    }
+
+    /// Returns the classes of the objects an expression may evaluate to, according to the analyzer.
+    fn object_class_names(&self, expression: &Expression<'arena>) -> Vec<Word> {
+        if let Expression::Variable(Variable::Direct(variable)) = expression
+            && variable.name == b"$this"
+        {
+            return self
+                .class
+                .and_then(|class| self.codebase.get_class_like(class.as_bytes()))
+                .map(|metadata| vec![metadata.original_name])
+                .unwrap_or_default();
+        }
+
+        let Some(union) = self.artifacts.get_expression_type(expression) else {
+            return vec![];
+        };
+
+        let mut names = vec![];
+        for atomic in union.types.iter() {
+            if let TAtomic::Object(object) = atomic
+                && let Some(name) = object.get_name()
+                && !names.contains(&name)
+            {
+                names.push(name);
+            }
+        }
+
+        names
+    }
+
+    /// Returns `true` if the analyzer inferred a type for the expression that cannot carry an attack,
+    /// such as a number, a boolean, or a string known at analysis time.
+    fn is_taint_free(&self, expression: &Expression<'arena>) -> bool {
+        let Some(union) = self.artifacts.get_expression_type(expression) else {
+            return false;
+        };
+
+        !union.types.is_empty()
+            && union.types.iter().all(|atomic| match atomic {
+                TAtomic::Null | TAtomic::Void | TAtomic::Never => true,
+                TAtomic::Scalar(scalar) => match scalar {
+                    TScalar::Bool(_) | TScalar::Integer(_) | TScalar::Float(_) | TScalar::Numeric => true,
+                    TScalar::String(string) => string.is_numeric || string.is_literal_origin(),
+                    _ => false,
+                },
+                TAtomic::Object(TObject::Enum(_)) => true,
+                _ => false,
+            })
+    }
+}
+
+/// Returns `true` for statements that declare symbols and are walked even when unreachable.
+fn is_declaration(statement: &Statement<'_>) -> bool {
+    matches!(
+        statement,
+        Statement::Function(_)
+            | Statement::Class(_)
+            | Statement::Interface(_)
+            | Statement::Trait(_)
+            | Statement::Enum(_)
+            | Statement::Namespace(_)
+    )
+}
+
+/// Returns the key of an array access or array element when it is a literal.
+fn literal_key(expression: &Expression<'_>) -> Option<Vec<u8>> {
+    match expression {
+        Expression::Literal(Literal::String(string)) => string.value.map(<[u8]>::to_vec),
+        Expression::Literal(Literal::Integer(integer)) => integer.value.map(|value| value.to_string().into_bytes()),
+        _ => None,
+    }
+}
+
+/// Returns the name of a global constant, such as `FILTER_VALIDATE_INT`.
+fn constant_name<'arena>(expression: &Expression<'arena>) -> Option<&'arena [u8]> {
+    match expression {
+        Expression::ConstantAccess(access) => Some(access.name.value()),
+        _ => None,
+    }
+}
+
+/// Returns the taint kinds a `filter_*()` filter removes from its input.
+fn filter_removes(filter: &[u8]) -> TaintFlags {
+    let filter = filter.strip_prefix(b"\\").unwrap_or(filter);
+
+    match filter {
+        b"FILTER_VALIDATE_INT"
+        | b"FILTER_VALIDATE_FLOAT"
+        | b"FILTER_VALIDATE_BOOL"
+        | b"FILTER_VALIDATE_BOOLEAN"
+        | b"FILTER_SANITIZE_NUMBER_INT"
+        | b"FILTER_SANITIZE_NUMBER_FLOAT" => TaintFlags::ALL,
+        b"FILTER_SANITIZE_SPECIAL_CHARS" | b"FILTER_SANITIZE_FULL_SPECIAL_CHARS" => TaintFlags::HTML,
+        _ => TaintFlags::empty(),
+    }
+}
+
+/// Maps an argument to the index of the parameter receiving it.
+fn parameter_index(
+    parameters: &[mago_codex::metadata::parameter::FunctionLikeParameterMetadata],
+    index: usize,
+    name: Option<&[u8]>,
+) -> Option<usize> {
+    if let Some(name) = name {
+        return parameters.iter().position(|parameter| &parameter.get_name().0.as_bytes()[1..] == name);
+    }
+
+    if index < parameters.len() {
+        return Some(index);
+    }
+
+    parameters.last().filter(|parameter| parameter.flags.is_variadic()).map(|_| parameters.len() - 1)
+}
+
+fn sink_message(kinds: TaintFlags, display: &str) -> String {
+    match kinds {
+        TaintFlags::HTML => format!("This value is output as HTML by {display}"),
+        TaintFlags::SQL => format!("This value is used as SQL by {display}"),
+        TaintFlags::SHELL => format!("This value is executed as a shell command by {display}"),
+        TaintFlags::INCLUDE => format!("This value is used as a path by {display}"),
+        TaintFlags::UNSERIALIZE => format!("This value is unserialized by {display}"),
+        _ => format!("This value reaches {display}"),
+    }
+}
@@ -0,0 +1,281 @@
+//! Summaries of the Twig templates in a project.
+//!
+//! Plugins that understand a framework's rendering API use the [`TemplateIndex`] to check a
+//! `render()` call against the template it renders: every template is scanned once, and its
+//! [`TemplateSummary`] records the variables it reads from its render context, the attribute
+//! chains accessed on them, and the templates it extends or includes.
+//!
+//! Templates are keyed by the name they are rendered with, which is their path relative to
+//! one of the [`Settings::template_paths`](crate::settings::Settings::template_paths)
+//! directories (e.g. `user/show.html.twig` for `templates/user/show.html.twig`).
+
+use std::borrow::Borrow;
+
+use foldhash::HashMap;
+use foldhash::HashSet;
+
+use mago_allocator::LocalArena;
+use mago_database::file::File;
+use mago_database::file::FileId;
+use mago_span::Span;
+use mago_twig_syntax::parser::parse_file;
+use mago_twig_syntax::walker::Walker;
+
+use crate::template::scanner::TemplateScanner;
+use crate::template::scanner::TemplateScannerState;
+
+mod scanner;
+
+/// Variables Twig provides to every template, regardless of the render context.
+const GLOBAL_VARIABLES: [&str; 5] = ["_self", "_context", "_charset", "app", "loop"];
+
+/// An index of the Twig templates in a project, keyed by template name.
+#[derive(Debug, Clone, Default)]
+pub struct TemplateIndex {
+    templates: HashMap<String, TemplateSummary>,
+}
+
+/// What a single template reads from its render context.
+#[derive(Debug, Clone)]
+pub struct TemplateSummary {
+    /// The file the template was read from.
+    pub file_id: FileId,
+    /// Whether the template parsed without errors; variables are only recorded if it did.
+    pub is_complete: bool,
+    /// Every read of a variable that is not defined by the template itself.
+    pub variables: Vec<TemplateVariable>,
+    /// Names assigned by the template: `set` targets, `for` targets, import aliases, and so on.
+    pub defined: HashSet<String>,
+    /// Names whose presence the template checks with `is defined`, `default`, or `??`.
+    pub optional: HashSet<String>,
+    /// Names of the blocks declared by the template.
+    pub blocks: HashSet<String>,
+    /// The template named by `{% extends %}`, when it is a string literal.
+    pub parent: Option<String>,
+    /// Templates included or embedded with the full render context, when named by a string literal.
+    pub includes: Vec<String>,
+}
+
+/// A read of a render context variable.
+#[derive(Debug, Clone)]
+pub struct TemplateVariable {
+    pub name: String,
+    pub span: Span,
+    /// Whether this read is guarded with `is defined`, `default`, or `??`.
+    pub is_optional: bool,
+    /// The names of the blocks enclosing the read, outermost first.
+    pub blocks: Vec<String>,
+    /// The attributes accessed on the variable, in order (`user.address.city`).
+    pub attributes: Vec<TemplateAttribute>,
+}
+
+/// An attribute accessed on a template variable.
+#[derive(Debug, Clone)]
+pub struct TemplateAttribute {
+    pub name: String,
+    pub span: Span,
+    /// Whether the attribute is called as a method (`user.name()`).
+    pub is_method_call: bool,
+}
+
+/// The render context requirements of a template, including the templates it extends and includes.
+#[derive(Debug, Clone, Default)]
+pub struct TemplateContext<'index> {
+    /// Whether every template involved parsed without errors.
+    pub is_complete: bool,
+    /// Reads of context variables, in the templates that are actually rendered.
+    pub variables: Vec<&'index TemplateVariable>,
+    defined: HashSet<&'index str>,
+    optional: HashSet<&'index str>,
+}
+
+impl TemplateIndex {
+    #[must_use]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Builds an index from the Twig files among `files`.
+    ///
+    /// Files outside of the `paths` directories are ignored, as they cannot be rendered by name.
+    #[must_use]
+    pub fn from_files<F>(files: impl IntoIterator<Item = F>, paths: &[String]) -> Self
+    where
+        F: Borrow<File>,
+    {
+        let prefixes: Vec<String> = paths
+            .iter()
+            .map(|path| path.trim_start_matches("./").trim_end_matches('/'))
+            .filter(|path| !path.is_empty())
+            .map(|path| format!("{path}/"))
+            .collect();
+
+        let mut index = Self::new();
+        let mut arena = LocalArena::new();
+        for file in files {
+            let file = file.borrow();
+            if !file.language().is_twig() {
+                continue;
+            }
+
+            let file_name = String::from_utf8_lossy(&file.name);
+            let Some(name) = prefixes.iter().find_map(|prefix| file_name.strip_prefix(prefix.as_str())) else {
+                continue;
+            };
+
+            let template = parse_file(&arena, file);
+            let summary = if template.errors.is_empty() {
+                let mut state = TemplateScannerState::default();
+                TemplateScanner.walk_template(template, &mut state);
+                state.finish(file.id)
+            } else {
+                TemplateSummary::incomplete(file.id)
+            };
+
+            index.add(name.to_string(), summary);
+            arena.reset();
+        }
+
+        index
+    }
+
+    pub fn add(&mut self, name: String, summary: TemplateSummary) {
+        self.templates.insert(name, summary);
+    }
+
+    #[must_use]
+    pub fn get(&self, name: &str) -> Option<&TemplateSummary> {
+        self.templates.get(name)
+    }
+
+    #[must_use]
+    pub fn contains(&self, name: &str) -> bool {
+        self.templates.contains_key(name)
+    }
+
+    #[must_use]
+    pub fn len(&self) -> usize {
+        self.templates.len()
+    }
+
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.templates.is_empty()
+    }
+
+    /// Collects what rendering the template `name` reads from its render context.
+    ///
+    /// The parents named by `{% extends %}` contribute the reads outside of the blocks their
+    /// children override, and templates included with the full context contribute all of
+    /// theirs. Templates that are not in the index are skipped, and mark the result incomplete.
+    #[must_use]
+    pub fn resolve_context(&self, name: &str) -> Option<TemplateContext<'_>> {
+        let (name, summary) = self.templates.get_key_value(name)?;
+        let mut context = TemplateContext { is_complete: true, ..TemplateContext::default() };
+        let mut visited: HashSet<&str> = HashSet::default();
+        visited.insert(name.as_str());
+
+        let mut overridden_blocks: HashSet<&str> = HashSet::default();
+        let mut current = Some(summary);
+        while let Some(template) = current {
+            context.add(template, &overridden_blocks);
+            overridden_blocks.extend(template.blocks.iter().map(String::as_str));
+            self.add_includes(template, &mut context, &mut visited);
+
+            current = match template.parent.as_deref() {
+                Some(parent) if visited.insert(parent) => {
+                    let parent_summary = self.templates.get(parent);
+                    context.is_complete &= parent_summary.is_some();
+                    parent_summary
+                }
+                _ => None,
+            };
+        }
+
+        Some(context)
+    }
+
+    fn add_includes<'index>(
+        &'index self,
+        template: &'index TemplateSummary,
+        context: &mut TemplateContext<'index>,
+        visited: &mut HashSet<&'index str>,
+    ) {
+        for include in &template.includes {
+            if !visited.insert(include.as_str()) {
+                continue;
+            }
+
+            let Some(included) = self.templates.get(include.as_str()) else {
+                context.is_complete = false;
+                continue;
+            };
+
+            context.add(included, &HashSet::default());
+            self.add_includes(included, context, visited);
+        }
+    }
+}
+
+impl TemplateSummary {
+    /// Creates the summary of a template that could not be scanned, such as one that fails to parse.
+    #[must_use]
+    pub fn incomplete(file_id: FileId) -> Self {
+        Self {
+            file_id,
+            is_complete: false,
+            variables: Vec::new(),
+            defined: HashSet::default(),
+            optional: HashSet::default(),
+            blocks: HashSet::default(),
+            parent: None,
+            includes: Vec::new(),
+        }
+    }
+}
+
+impl<'index> TemplateContext<'index> {
+    fn add(&mut self, template: &'index TemplateSummary, overridden_blocks: &HashSet<&str>) {
+        self.is_complete &= template.is_complete;
+        self.defined.extend(template.defined.iter().map(String::as_str));
+        self.optional.extend(template.optional.iter().map(String::as_str));
+        self.variables.extend(
+            template
+                .variables
+                .iter()
+                .filter(|variable| !variable.blocks.iter().any(|block| overridden_blocks.contains(block.as_str()))),
+        );
+    }
+
+    /// Returns `true` if `name` is provided by the templates themselves or by Twig.
+    #[must_use]
+    pub fn is_defined(&self, name: &str) -> bool {
+        self.defined.contains(name) || GLOBAL_VARIABLES.contains(&name)
+    }
+
+    /// Returns `true` if every template reading `name` also tolerates it being absent.
+    #[must_use]
+    pub fn is_optional(&self, name: &str) -> bool {
+        self.optional.contains(name)
+    }
+
+    /// Returns the first read of each variable that must be present in the render context.
+    #[must_use]
+    pub fn required_variables(&self) -> Vec<&'index TemplateVariable> {
+        let mut seen = HashSet::default();
+
+        self.variables
+            .iter()
+            .copied()
+            .filter(|variable| !self.is_defined(&variable.name) && !self.is_optional(&variable.name))
+            .filter(|variable| seen.insert(variable.name.as_str()))
+            .collect()
+    }
+
+    /// Returns the unguarded reads of context variables that access attributes.
+    pub fn attribute_accesses(&self) -> impl Iterator<Item = &'index TemplateVariable> + '_ {
+        self.variables.iter().copied().filter(|variable| {
+            !variable.is_optional && !variable.attributes.is_empty() && !self.is_defined(&variable.name)
+        })
+    }
+}
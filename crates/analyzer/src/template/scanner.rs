@@ -0,0 +1,318 @@
+use foldhash::HashMap;
+use foldhash::HashSet;
+
+use mago_database::file::FileId;
+use mago_span::Span;
+use mago_twig_syntax::cst::Argument;
+use mago_twig_syntax::cst::ArrowFunction;
+use mago_twig_syntax::cst::Binary;
+use mago_twig_syntax::cst::BinaryOperator;
+use mago_twig_syntax::cst::Block;
+use mago_twig_syntax::cst::Call;
+use mago_twig_syntax::cst::Embed;
+use mago_twig_syntax::cst::Expression;
+use mago_twig_syntax::cst::Extends;
+use mago_twig_syntax::cst::Filter;
+use mago_twig_syntax::cst::For;
+use mago_twig_syntax::cst::From;
+use mago_twig_syntax::cst::GetAttribute;
+use mago_twig_syntax::cst::Import;
+use mago_twig_syntax::cst::Include;
+use mago_twig_syntax::cst::Macro;
+use mago_twig_syntax::cst::MethodCall;
+use mago_twig_syntax::cst::Name;
+use mago_twig_syntax::cst::Set;
+use mago_twig_syntax::cst::Test;
+use mago_twig_syntax::cst::With;
+use mago_twig_syntax::walker::Walker;
+
+use crate::template::TemplateAttribute;
+use crate::template::TemplateSummary;
+use crate::template::TemplateVariable;
+
+/// Walks a template and records what it reads from its render context.
+pub(super) struct TemplateScanner;
+
+#[derive(Debug, Default)]
+pub(super) struct TemplateScannerState {
+    variables: Vec<TemplateVariable>,
+    defined: HashSet<String>,
+    optional: HashSet<String>,
+    blocks: HashSet<String>,
+    parent: Option<String>,
+    includes: Vec<String>,
+    /// The names of the blocks enclosing the current node, outermost first.
+    block_stack: Vec<String>,
+    /// Whether each enclosing `{% with %}` hides the outer render context.
+    with_stack: Vec<bool>,
+    /// How many enclosing nodes have a scope of their own, such as macros.
+    isolated_depth: usize,
+    /// The spans of names read inside `is defined`, `default`, or `??`.
+    optional_spans: HashSet<Span>,
+    /// The spans of names that are not variables, such as the names of called functions.
+    non_variable_spans: HashSet<Span>,
+    /// The attribute chains accessed on a name, keyed by the span of the name.
+    chains: HashMap<Span, Vec<TemplateAttribute>>,
+}
+
+impl TemplateScannerState {
+    pub(super) fn finish(self, file_id: FileId) -> TemplateSummary {
+        TemplateSummary {
+            file_id,
+            is_complete: true,
+            variables: self.variables,
+            defined: self.defined,
+            optional: self.optional,
+            blocks: self.blocks,
+            parent: self.parent,
+            includes: self.includes,
+        }
+    }
+
+    fn define(&mut self, name: &[u8]) {
+        self.defined.insert(String::from_utf8_lossy(name).into_owned());
+    }
+
+    fn mark_optional(&mut self, expression: &Expression<'_>) {
+        if let Some(name) = root_name(expression) {
+            self.optional_spans.insert(name.span);
+            self.optional.insert(String::from_utf8_lossy(name.name).into_owned());
+        }
+    }
+
+    fn record_chain(&mut self, chain: Option<(&Name<'_>, Vec<TemplateAttribute>)>) {
+        let Some((name, chain)) = chain else {
+            return;
+        };
+
+        // Nested accesses are visited outermost first, so the first chain seen for a name is the longest.
+        self.chains.entry(name.span).or_insert(chain);
+    }
+
+    fn is_isolated(&self) -> bool {
+        self.isolated_depth > 0 || self.with_stack.iter().any(|hides_context| *hides_context)
+    }
+}
+
+impl<'ast, 'arena> Walker<'ast, 'arena, TemplateScannerState> for TemplateScanner {
+    fn walk_in_name(&self, name: &'ast Name<'arena>, state: &mut TemplateScannerState) {
+        if state.is_isolated() || state.non_variable_spans.contains(&name.span) {
+            return;
+        }
+
+        let attributes = state.chains.remove(&name.span).unwrap_or_default();
+        state.variables.push(TemplateVariable {
+            name: String::from_utf8_lossy(name.name).into_owned(),
+            span: name.span,
+            is_optional: state.optional_spans.contains(&name.span),
+            blocks: state.block_stack.clone(),
+            attributes,
+        });
+    }
+
+    fn walk_in_get_attribute(&self, get_attribute: &'ast GetAttribute<'arena>, state: &mut TemplateScannerState) {
+        if let Expression::Name(attribute) = get_attribute.attribute {
+            state.non_variable_spans.insert(attribute.span);
+        }
+
+        state.record_chain(get_attribute_chain(get_attribute));
+    }
+
+    fn walk_in_method_call(&self, method_call: &'ast MethodCall<'arena>, state: &mut TemplateScannerState) {
+        state.record_chain(method_call_chain(method_call));
+    }
+
+    fn walk_in_call(&self, call: &'ast Call<'arena>, state: &mut TemplateScannerState) {
+        let Expression::Name(callee) = call.callee else {
+            return;
+        };
+
+        state.non_variable_spans.insert(callee.span);
+
+        // `{{ include('partial.html.twig') }}` passes the whole render context.
+        if callee.name == b"include"
+            && let [Argument::Positional(argument)] = call.argument_list.arguments.nodes
+            && let Some(template) = string_value(argument.value)
+        {
+            state.includes.push(template);
+        }
+    }
+
+    fn walk_in_test(&self, test: &'ast Test<'arena>, state: &mut TemplateScannerState) {
+        if test.name.value == b"defined" {
+            state.mark_optional(test.operand);
+        }
+    }
+
+    fn walk_in_filter(&self, filter: &'ast Filter<'arena>, state: &mut TemplateScannerState) {
+        if filter.name.value == b"default" {
+            state.mark_optional(filter.operand);
+        }
+    }
+
+    fn walk_in_binary(&self, binary: &'ast Binary<'arena>, state: &mut TemplateScannerState) {
+        if let BinaryOperator::NullCoalesce(_) = binary.operator {
+            state.mark_optional(binary.lhs);
+        }
+    }
+
+    fn walk_in_for(&self, r#for: &'ast For<'arena>, state: &mut TemplateScannerState) {
+        for target in r#for.targets.iter() {
+            state.define(target.value);
+        }
+    }
+
+    fn walk_in_set(&self, set: &'ast Set<'arena>, state: &mut TemplateScannerState) {
+        for name in set.names.iter() {
+            state.define(name.value);
+        }
+    }
+
+    fn walk_in_import(&self, import: &'ast Import<'arena>, state: &mut TemplateScannerState) {
+        state.define(import.alias.value);
+    }
+
+    fn walk_in_from(&self, from: &'ast From<'arena>, state: &mut TemplateScannerState) {
+        for imported in from.names.iter() {
+            state.define(imported.to.as_ref().unwrap_or(&imported.from).value);
+        }
+    }
+
+    fn walk_in_arrow_function(&self, arrow_function: &'ast ArrowFunction<'arena>, state: &mut TemplateScannerState) {
+        for parameter in arrow_function.parameters.iter() {
+            state.define(parameter.value);
+        }
+    }
+
+    fn walk_in_macro(&self, _macro: &'ast Macro<'arena>, state: &mut TemplateScannerState) {
+        state.isolated_depth += 1;
+    }
+
+    fn walk_out_macro(&self, _macro: &'ast Macro<'arena>, state: &mut TemplateScannerState) {
+        state.isolated_depth -= 1;
+    }
+
+    fn walk_in_with(&self, with: &'ast With<'arena>, state: &mut TemplateScannerState) {
+        let hides_context = match &with.variables {
+            _ if with.only_keyword.is_some() => true,
+            Some(Expression::HashMap(hash_map)) => {
+                for key in hash_map.entries.iter().filter_map(|entry| entry.key.as_ref()) {
+                    if let Some(key) = string_value(key) {
+                        state.defined.insert(key);
+                    }
+                }
+
+                false
+            }
+            Some(_) => true,
+            None => false,
+        };
+
+        state.with_stack.push(hides_context);
+    }
+
+    fn walk_out_with(&self, _with: &'ast With<'arena>, state: &mut TemplateScannerState) {
+        state.with_stack.pop();
+    }
+
+    fn walk_in_block(&self, block: &'ast Block<'arena>, state: &mut TemplateScannerState) {
+        let name = String::from_utf8_lossy(block.name.value).into_owned();
+
+        state.blocks.insert(name.clone());
+        state.block_stack.push(name);
+    }
+
+    fn walk_out_block(&self, _block: &'ast Block<'arena>, state: &mut TemplateScannerState) {
+        state.block_stack.pop();
+    }
+
+    fn walk_in_extends(&self, extends: &'ast Extends<'arena>, state: &mut TemplateScannerState) {
+        state.parent = string_value(&extends.template);
+    }
+
+    fn walk_in_include(&self, include: &'ast Include<'arena>, state: &mut TemplateScannerState) {
+        if include.with_clause.is_none()
+            && include.only_keyword.is_none()
+            && let Some(template) = string_value(&include.template)
+        {
+            state.includes.push(template);
+        }
+    }
+
+    fn walk_in_embed(&self, embed: &'ast Embed<'arena>, state: &mut TemplateScannerState) {
+        if embed.with_clause.is_none()
+            && embed.only_keyword.is_none()
+            && let Some(template) = string_value(&embed.template)
+        {
+            state.includes.push(template);
+        }
+    }
+}
+
+/// Returns the variable an attribute access, method call, or item access is rooted at.
+fn root_name<'ast, 'arena>(expression: &'ast Expression<'arena>) -> Option<&'ast Name<'arena>> {
+    match expression {
+        Expression::Name(name) => Some(name),
+        Expression::GetAttribute(get_attribute) => root_name(get_attribute.object),
+        Expression::MethodCall(method_call) => root_name(method_call.object),
+        Expression::GetItem(get_item) => root_name(get_item.object),
+        Expression::Parenthesized(parenthesized) => root_name(parenthesized.inner),
+        _ => None,
+    }
+}
+
+/// Returns the variable and the statically named attributes of an access like `user.address.city`.
+fn attribute_chain<'ast, 'arena>(
+    expression: &'ast Expression<'arena>,
+) -> Option<(&'ast Name<'arena>, Vec<TemplateAttribute>)> {
+    match expression {
+        Expression::Name(name) => Some((name, Vec::new())),
+        Expression::GetAttribute(get_attribute) => get_attribute_chain(get_attribute),
+        Expression::MethodCall(method_call) => method_call_chain(method_call),
+        _ => None,
+    }
+}
+
+fn get_attribute_chain<'ast, 'arena>(
+    get_attribute: &'ast GetAttribute<'arena>,
+) -> Option<(&'ast Name<'arena>, Vec<TemplateAttribute>)> {
+    let Expression::Name(attribute) = get_attribute.attribute else {
+        return None;
+    };
+
+    let (name, mut chain) = attribute_chain(get_attribute.object)?;
+    chain.push(TemplateAttribute {
+        name: String::from_utf8_lossy(attribute.name).into_owned(),
+        span: attribute.span,
+        is_method_call: false,
+    });
+
+    Some((name, chain))
+}
+
+fn method_call_chain<'ast, 'arena>(
+    method_call: &'ast MethodCall<'arena>,
+) -> Option<(&'ast Name<'arena>, Vec<TemplateAttribute>)> {
+    let (name, mut chain) = attribute_chain(method_call.object)?;
+    chain.push(TemplateAttribute {
+        name: String::from_utf8_lossy(method_call.method.value).into_owned(),
+        span: method_call.method.span,
+        is_method_call: true,
+    });
+
+    Some((name, chain))
+}
+
+/// Returns the value of a string literal expression.
+fn string_value(expression: &Expression<'_>) -> Option<String> {
+    let Expression::String(literal) = expression else {
+        return None;
+    };
+
+    let value = match literal.raw {
+        [b'\'' | b'"', inner @ .., _] => inner,
+        raw => raw,
+    };
+
+    Some(String::from_utf8_lossy(value).into_owned())
+}
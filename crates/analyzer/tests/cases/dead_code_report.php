@@ -0,0 +1,59 @@
+<?php
+
+declare(strict_types=1);
+
+namespace App\Service;
+
+interface Formatter
+{
+    public function format(string $value): string;
+}
+
+final class UpperFormatter implements Formatter
+{
+    public function format(string $value): string
+    {
+        return strtoupper($value);
+    }
+}
+
+final class Greeter
+{
+    public const string PREFIX = 'Hello, ';
+
+    /** @mago-expect analysis:unreachable-constant */
+    public const string SUFFIX = '!';
+
+    /** @mago-expect analysis:unreachable-property */
+    public int $count = 0;
+
+    public function __construct(
+        private Formatter $formatter,
+    ) {}
+
+    public function greet(string $name): string
+    {
+        return $this->formatter->format(self::PREFIX . $name);
+    }
+
+    /** @mago-expect analysis:unreachable-method */
+    public function shout(string $name): string
+    {
+        return $this->greet($name) . '!';
+    }
+}
+
+/** @mago-expect analysis:unreachable-class */
+final class Orphan
+{
+    public function run(): void {}
+}
+
+/** @api */
+final class PublicApi
+{
+    public function entry(): void {}
+}
+
+$greeter = new Greeter(new UpperFormatter());
+echo $greeter->greet('world');
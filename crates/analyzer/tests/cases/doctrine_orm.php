@@ -0,0 +1,341 @@
+<?php
+
+namespace Doctrine\Persistence {
+    /**
+     * @template-covariant T of object
+     */
+    interface ObjectRepository
+    {
+        public function find(mixed $id): null|object;
+
+        public function findAll(): array;
+
+        /**
+         * @param array<string, mixed> $criteria
+         * @param array<string, string>|null $orderBy
+         */
+        public function findBy(
+            array $criteria,
+            null|array $orderBy = null,
+            null|int $limit = null,
+            null|int $offset = null,
+        ): array;
+
+        /**
+         * @param array<string, mixed> $criteria
+         */
+        public function findOneBy(array $criteria): null|object;
+
+        /**
+         * @return class-string<T>
+         */
+        public function getClassName(): string;
+    }
+
+    interface ObjectManager
+    {
+        public function getRepository(string $className): ObjectRepository;
+    }
+}
+
+namespace Doctrine\ORM {
+    use Doctrine\Persistence\ObjectManager;
+    use Doctrine\Persistence\ObjectRepository;
+
+    final class Query
+    {
+        public function getResult(): mixed
+        {
+            return null;
+        }
+    }
+
+    interface EntityManagerInterface extends ObjectManager
+    {
+        public function createQuery(string $dql = ''): Query;
+    }
+
+    /**
+     * @template T of object
+     *
+     * @implements ObjectRepository<T>
+     */
+    class EntityRepository implements ObjectRepository
+    {
+        /**
+         * @param class-string<T> $entityName
+         */
+        public function __construct(
+            private string $entityName,
+        ) {}
+
+        public function find(mixed $id): null|object
+        {
+            return null;
+        }
+
+        public function findAll(): array
+        {
+            return [];
+        }
+
+        /**
+         * @param array<string, mixed> $criteria
+         * @param array<string, string>|null $orderBy
+         */
+        public function findBy(
+            array $criteria,
+            null|array $orderBy = null,
+            null|int $limit = null,
+            null|int $offset = null,
+        ): array {
+            return [];
+        }
+
+        /**
+         * @param array<string, mixed> $criteria
+         */
+        public function findOneBy(array $criteria): null|object
+        {
+            return null;
+        }
+
+        /**
+         * @param array<string, mixed> $criteria
+         */
+        public function count(array $criteria = []): int
+        {
+            return 0;
+        }
+
+        /**
+         * @return class-string<T>
+         */
+        public function getClassName(): string
+        {
+            return $this->entityName;
+        }
+    }
+}
+
+namespace Doctrine\Bundle\DoctrineBundle\Repository {
+    use Doctrine\ORM\EntityRepository;
+
+    /**
+     * @template T of object
+     *
+     * @extends EntityRepository<T>
+     */
+    class ServiceEntityRepository extends EntityRepository
+    {
+    }
+}
+
+namespace Doctrine\ORM\Mapping {
+    use Attribute;
+
+    #[Attribute(Attribute::TARGET_CLASS)]
+    final class Entity
+    {
+        public function __construct(
+            public null|string $repositoryClass = null,
+        ) {}
+    }
+
+    #[Attribute(Attribute::TARGET_CLASS)]
+    final class Embeddable
+    {
+    }
+
+    #[Attribute(Attribute::TARGET_PROPERTY)]
+    final class Id
+    {
+    }
+
+    #[Attribute(Attribute::TARGET_PROPERTY)]
+    final class Column
+    {
+        public function __construct(
+            public null|string $type = null,
+        ) {}
+    }
+
+    #[Attribute(Attribute::TARGET_PROPERTY)]
+    final class Embedded
+    {
+        public function __construct(
+            public null|string $class = null,
+        ) {}
+    }
+
+    #[Attribute(Attribute::TARGET_PROPERTY)]
+    final class ManyToOne
+    {
+        public function __construct(
+            public null|string $targetEntity = null,
+        ) {}
+    }
+
+    #[Attribute(Attribute::TARGET_PROPERTY)]
+    final class OneToMany
+    {
+        public function __construct(
+            public null|string $targetEntity = null,
+            public null|string $mappedBy = null,
+        ) {}
+    }
+}
+
+namespace App\Entity {
+    use App\Repository\UserRepository;
+    use Doctrine\ORM\Mapping as ORM;
+
+    #[ORM\Embeddable]
+    class Address
+    {
+        #[ORM\Column]
+        public string $city = '';
+    }
+
+    #[ORM\Entity(repositoryClass: UserRepository::class)]
+    class User
+    {
+        #[ORM\Id]
+        #[ORM\Column]
+        public int $id = 0;
+
+        #[ORM\Column]
+        public string $email = '';
+
+        #[ORM\Embedded(class: Address::class)]
+        public Address $address;
+
+        /**
+         * @var list<Post>
+         */
+        #[ORM\OneToMany(targetEntity: Post::class, mappedBy: 'author')]
+        public array $posts = [];
+
+        public string $unmapped = '';
+
+        public function __construct()
+        {
+            $this->address = new Address();
+        }
+    }
+
+    #[ORM\Entity]
+    class Post
+    {
+        #[ORM\Id]
+        #[ORM\Column]
+        public int $id = 0;
+
+        #[ORM\Column]
+        public string $title = '';
+
+        #[ORM\ManyToOne]
+        public null|User $author = null;
+    }
+}
+
+namespace App\Repository {
+    use App\Entity\User;
+    use Doctrine\Bundle\DoctrineBundle\Repository\ServiceEntityRepository;
+
+    /**
+     * @extends ServiceEntityRepository<User>
+     */
+    class UserRepository extends ServiceEntityRepository
+    {
+        public function findOneByEmail(string $email): null|User
+        {
+            return $this->findOneBy(['email' => $email]);
+        }
+    }
+}
+
+namespace App {
+    use App\Entity\Post;
+    use App\Entity\User;
+    use App\Repository\UserRepository;
+    use Doctrine\ORM\EntityManagerInterface;
+    use Doctrine\ORM\EntityRepository;
+
+    function users(EntityManagerInterface $entityManager): UserRepository
+    {
+        return $entityManager->getRepository(User::class);
+    }
+
+    /**
+     * @return EntityRepository<Post>
+     */
+    function posts(EntityManagerInterface $entityManager): EntityRepository
+    {
+        return $entityManager->getRepository(Post::class);
+    }
+
+    function find_user(UserRepository $users, int $id): null|User
+    {
+        return $users->find($id);
+    }
+
+    function find_post(EntityManagerInterface $entityManager): null|Post
+    {
+        return $entityManager->getRepository(Post::class)->findOneBy(['title' => 'Hello']);
+    }
+
+    /**
+     * @return list<User>
+     */
+    function users_by_email(UserRepository $users, string $email): array
+    {
+        return $users->findBy(['email' => $email, 'address.city' => 'Paris'], ['id' => 'ASC']);
+    }
+
+    /**
+     * @return list<Post>
+     */
+    function all_posts(EntityManagerInterface $entityManager): array
+    {
+        return $entityManager->getRepository(Post::class)->findAll();
+    }
+
+    /**
+     * @return int<0, max>
+     */
+    function count_users(UserRepository $users): int
+    {
+        return $users->count(['email' => 'user@example.com']);
+    }
+
+    function invalid_criteria(UserRepository $users): void
+    {
+        // @mago-expect analysis:non-existent-entity-field
+        $users->findBy(['emial' => 'user@example.com']);
+        // @mago-expect analysis:non-existent-entity-field
+        $users->findOneBy(['unmapped' => '']);
+        // @mago-expect analysis:non-existent-entity-field
+        $users->findBy([], ['address.zip' => 'ASC']);
+        // @mago-expect analysis:non-existent-entity-field
+        $users->count(['name' => 'user']);
+    }
+
+    function queries(EntityManagerInterface $entityManager): void
+    {
+        $entityManager
+            ->createQuery(
+                'SELECT u, p FROM App\Entity\User u LEFT JOIN u.posts p WHERE u.email = :email AND p.title LIKE :title',
+            )
+            ->getResult();
+
+        $entityManager->createQuery('DELETE FROM App\Entity\Post p WHERE p.author = :author')->getResult();
+
+        // @mago-expect analysis:non-existent-entity
+        $entityManager->createQuery('SELECT u FROM App\Entity\Usr u')->getResult();
+        // @mago-expect analysis:non-existent-entity-field
+        $entityManager->createQuery('SELECT u FROM App\Entity\User u WHERE u.name = :name')->getResult();
+        // @mago-expect analysis:non-existent-entity-field
+        $entityManager->createQuery('SELECT u FROM App\Entity\User u JOIN u.comments c')->getResult();
+    }
+}
@@ -0,0 +1,405 @@
+<?php
+
+namespace Illuminate\Support {
+    final class Carbon
+    {
+    }
+
+    /**
+     * @template TKey of array-key
+     * @template-covariant TValue
+     */
+    class Collection
+    {
+        /**
+         * @return array<TKey, TValue>
+         */
+        public function toArray(): array
+        {
+            return [];
+        }
+
+        public function map(callable $callback): mixed
+        {
+            return $callback;
+        }
+
+        public function filter(null|callable $callback = null): mixed
+        {
+            return $callback;
+        }
+
+        public function first(): mixed
+        {
+            return null;
+        }
+
+        public function keys(): mixed
+        {
+            return null;
+        }
+
+        public function all(): mixed
+        {
+            return null;
+        }
+    }
+}
+
+namespace Illuminate\Database\Eloquent {
+    abstract class Model
+    {
+        public function __get(string $key): mixed
+        {
+            return null;
+        }
+
+        public function __set(string $key, mixed $value): void
+        {
+        }
+
+        /**
+         * @param list<mixed> $parameters
+         */
+        public function __call(string $method, array $parameters): mixed
+        {
+            return null;
+        }
+
+        /**
+         * @param list<mixed> $parameters
+         */
+        public static function __callStatic(string $method, array $parameters): mixed
+        {
+            return null;
+        }
+
+        public function hasMany(string $related): mixed
+        {
+            return $related;
+        }
+
+        public function belongsTo(string $related): mixed
+        {
+            return $related;
+        }
+    }
+
+    /**
+     * @template TModel of Model
+     */
+    class Builder
+    {
+        /**
+         * @return list<TModel>
+         */
+        public function getModels(): array
+        {
+            return [];
+        }
+
+        /**
+         * @param list<mixed> $parameters
+         */
+        public function __call(string $method, array $parameters): mixed
+        {
+            return null;
+        }
+    }
+
+    /**
+     * @template TKey of array-key
+     * @template-covariant TModel of Model
+     *
+     * @extends \Illuminate\Support\Collection<TKey, TModel>
+     */
+    class Collection extends \Illuminate\Support\Collection
+    {
+    }
+}
+
+namespace Illuminate\Database\Eloquent\Relations {
+    /**
+     * @template TRelated of \Illuminate\Database\Eloquent\Model
+     * @template TDeclaring of \Illuminate\Database\Eloquent\Model
+     */
+    abstract class Relation
+    {
+        /**
+         * @return list<TRelated|TDeclaring>
+         */
+        public function getModels(): array
+        {
+            return [];
+        }
+
+        /**
+         * @param list<mixed> $parameters
+         */
+        public function __call(string $method, array $parameters): mixed
+        {
+            return null;
+        }
+    }
+
+    /**
+     * @template TRelated of \Illuminate\Database\Eloquent\Model
+     * @template TDeclaring of \Illuminate\Database\Eloquent\Model
+     *
+     * @extends Relation<TRelated, TDeclaring>
+     */
+    final class HasMany extends Relation
+    {
+    }
+
+    /**
+     * @template TRelated of \Illuminate\Database\Eloquent\Model
+     * @template TDeclaring of \Illuminate\Database\Eloquent\Model
+     *
+     * @extends Relation<TRelated, TDeclaring>
+     */
+    final class BelongsTo extends Relation
+    {
+    }
+}
+
+namespace Illuminate\Cache {
+    final class CacheManager
+    {
+        public function get(string $key): null|string
+        {
+            return $key;
+        }
+
+        public function driver(): static
+        {
+            return $this;
+        }
+    }
+}
+
+namespace Illuminate\Support\Facades {
+    abstract class Facade
+    {
+        /**
+         * @param list<mixed> $arguments
+         */
+        public static function __callStatic(string $method, array $arguments): mixed
+        {
+            return null;
+        }
+    }
+
+    final class Cache extends Facade
+    {
+    }
+}
+
+namespace App\Models {
+    use Illuminate\Database\Eloquent\Builder;
+    use Illuminate\Database\Eloquent\Model;
+    use Illuminate\Database\Eloquent\Relations\BelongsTo;
+    use Illuminate\Database\Eloquent\Relations\HasMany;
+
+    enum Status: string
+    {
+        case Active = 'active';
+        case Banned = 'banned';
+    }
+
+    class User extends Model
+    {
+        /**
+         * @mago-expect analysis:invalid-model-cast
+         */
+        protected $casts = [
+            'status' => Status::class,
+            'settings' => 'array',
+            'verified_at' => 'datetime',
+            'balance' => 'decimal:2',
+            'legacy' => 'not-a-cast',
+        ];
+
+        /**
+         * @return HasMany<Post, User>
+         */
+        public function posts(): HasMany
+        {
+            return $this->hasMany(Post::class);
+        }
+
+        public function getDisplayNameAttribute(): string
+        {
+            return 'user';
+        }
+
+        /**
+         * @param Builder<User> $query
+         *
+         * @return Builder<User>
+         */
+        public function scopeActive(Builder $query): Builder
+        {
+            return $query;
+        }
+    }
+
+    class Post extends Model
+    {
+        protected $table = 'articles';
+
+        /**
+         * @return BelongsTo<User, Post>
+         */
+        public function author(): BelongsTo
+        {
+            return $this->belongsTo(User::class);
+        }
+    }
+}
+
+namespace App {
+    use App\Models\Post;
+    use App\Models\Status;
+    use App\Models\User;
+    use Illuminate\Database\Eloquent\Builder;
+    use Illuminate\Database\Eloquent\Collection;
+    use Illuminate\Support\Carbon;
+    use Illuminate\Support\Facades\Cache;
+
+    function email(User $user): string
+    {
+        return $user->email;
+    }
+
+    function nickname(User $user): null|string
+    {
+        return $user->nickname;
+    }
+
+    function is_admin(User $user): bool
+    {
+        return $user->is_admin;
+    }
+
+    function created_at(User $user): null|Carbon
+    {
+        return $user->created_at;
+    }
+
+    function status(User $user): Status
+    {
+        return $user->status;
+    }
+
+    /**
+     * @return array<array-key, mixed>
+     */
+    function settings(User $user): array
+    {
+        return $user->settings;
+    }
+
+    function verified_at(User $user): null|Carbon
+    {
+        return $user->verified_at;
+    }
+
+    /**
+     * @return numeric-string
+     */
+    function balance(User $user): string
+    {
+        return $user->balance;
+    }
+
+    function display_name(User $user): string
+    {
+        return $user->display_name;
+    }
+
+    /**
+     * @return Collection<int, Post>
+     */
+    function posts(User $user): Collection
+    {
+        return $user->posts;
+    }
+
+    function author(Post $post): null|User
+    {
+        return $post->author;
+    }
+
+    function title(Post $post): string
+    {
+        return $post->title;
+    }
+
+    function rename(User $user): void
+    {
+        $user->email = 'user@example.com';
+    }
+
+    function find(int $id): null|User
+    {
+        return User::find($id);
+    }
+
+    function find_or_fail(int $id): User
+    {
+        return User::where('active', true)->orderBy('name')->findOrFail($id);
+    }
+
+    /**
+     * @return Collection<int, User>
+     */
+    function active(): Collection
+    {
+        return User::active()->whereEmail('user@example.com')->get();
+    }
+
+    /**
+     * @return Builder<User>
+     */
+    function query(): Builder
+    {
+        return User::query()->latest();
+    }
+
+    function latest_post(User $user): null|Post
+    {
+        return $user->posts()->latest()->first();
+    }
+
+    function create(): User
+    {
+        return User::create(['email' => 'user@example.com']);
+    }
+
+    /**
+     * @return \Illuminate\Support\Collection<int, string>
+     */
+    function emails(): \Illuminate\Support\Collection
+    {
+        return User::all()->map(fn(User $user): string => $user->email);
+    }
+
+    /**
+     * @return Collection<int, User>
+     */
+    function authors(): Collection
+    {
+        return Post::all()->map(fn(Post $post): User => $post->author ?? new User());
+    }
+
+    function first_user(): null|User
+    {
+        return User::all()->first();
+    }
+
+    function cached(): null|string
+    {
+        return Cache::get('key');
+    }
+}
@@ -0,0 +1,307 @@
+<?php
+
+namespace PHPUnit\Framework\MockObject\Builder {
+    final class InvocationMocker
+    {
+        public function method(string $constraint): self
+        {
+            return $this;
+        }
+
+        public function willReturn(mixed $value): self
+        {
+            return $this;
+        }
+    }
+}
+
+namespace PHPUnit\Framework\MockObject\Rule {
+    final class InvokedCount
+    {
+    }
+}
+
+namespace PHPUnit\Framework\MockObject {
+    use PHPUnit\Framework\MockObject\Builder\InvocationMocker;
+    use PHPUnit\Framework\MockObject\Rule\InvokedCount;
+
+    interface Stub
+    {
+        public function method(string $constraint): InvocationMocker;
+    }
+
+    interface MockObject extends Stub
+    {
+        public function expects(InvokedCount $invocationRule): InvocationMocker;
+    }
+}
+
+namespace PHPUnit\Framework\Attributes {
+    #[\Attribute(\Attribute::TARGET_METHOD | \Attribute::IS_REPEATABLE)]
+    final class DataProvider
+    {
+        public function __construct(
+            public readonly string $methodName,
+        ) {}
+    }
+
+    #[\Attribute(\Attribute::TARGET_METHOD | \Attribute::IS_REPEATABLE)]
+    final class DataProviderExternal
+    {
+        public function __construct(
+            public readonly string $className,
+            public readonly string $methodName,
+        ) {}
+    }
+}
+
+namespace PHPUnit\Framework {
+    use PHPUnit\Framework\MockObject\MockObject;
+    use PHPUnit\Framework\MockObject\Rule\InvokedCount;
+    use PHPUnit\Framework\MockObject\Stub;
+
+    abstract class Assert
+    {
+        public static function assertInstanceOf(string $expected, mixed $actual, string $message = ''): void
+        {
+        }
+
+        public static function assertNotNull(mixed $actual, string $message = ''): void
+        {
+        }
+
+        public static function assertIsArray(mixed $actual, string $message = ''): void
+        {
+        }
+    }
+
+    abstract class TestCase extends Assert
+    {
+        public static function once(): InvokedCount
+        {
+            return new InvokedCount();
+        }
+
+        protected function createMock(string $originalClassName): MockObject
+        {
+            exit();
+        }
+
+        protected function createStub(string $originalClassName): Stub
+        {
+            exit();
+        }
+    }
+}
+
+namespace App {
+    interface Mailer
+    {
+        public function send(string $to): bool;
+    }
+
+    /**
+     * @method int count()
+     */
+    class Inbox
+    {
+        /**
+         * @return list<string>
+         */
+        public function messages(): array
+        {
+            return [];
+        }
+
+        public function __call(string $name, array $arguments): mixed
+        {
+            return null;
+        }
+    }
+
+    final class Message
+    {
+        public function __construct(
+            public readonly string $subject,
+        ) {}
+    }
+}
+
+namespace App\Tests {
+    use App\Inbox;
+    use App\Mailer;
+    use App\Message;
+    use PHPUnit\Framework\Assert;
+    use PHPUnit\Framework\Attributes\DataProvider;
+    use PHPUnit\Framework\Attributes\DataProviderExternal;
+    use PHPUnit\Framework\MockObject\MockObject;
+    use PHPUnit\Framework\MockObject\Stub;
+    use PHPUnit\Framework\TestCase;
+
+    final class MessageProvider
+    {
+        /**
+         * @return iterable<string, array{Message}>
+         */
+        public static function messages(): iterable
+        {
+            yield 'hello' => [new Message('Hello')];
+        }
+    }
+
+    final class MailerTest extends TestCase
+    {
+        public function testMocks(): void
+        {
+            $mailer = $this->createMock(Mailer::class);
+            $mailer->method('send')->willReturn(true);
+            $mailer->expects(self::once())->method('send')->willReturn(false);
+
+            $this->sendWith($mailer);
+            $this->expectMock($mailer);
+
+            // @mago-expect analysis:non-existent-mocked-method
+            $mailer->method('sendMail');
+            // @mago-expect analysis:non-existent-mocked-method
+            $mailer->expects(self::once())->method('deliver');
+        }
+
+        public function testStubs(): void
+        {
+            $inbox = $this->createStub(Inbox::class);
+            $inbox->method('messages')->willReturn([]);
+            $inbox->method('count')->willReturn(1);
+
+            $this->expectStub($inbox);
+
+            // @mago-expect analysis:non-existent-mocked-method
+            $inbox->method('clear');
+        }
+
+        public function sendWith(Mailer $mailer): bool
+        {
+            return $mailer->send('user@example.com');
+        }
+
+        public function expectMock(MockObject $mock): MockObject
+        {
+            return $mock;
+        }
+
+        public function expectStub(Inbox&Stub $stub): Stub
+        {
+            return $stub;
+        }
+
+        public function message(object $value): Message
+        {
+            $this->assertInstanceOf(Message::class, $value);
+
+            return $value;
+        }
+
+        public function present(null|Message $message): Message
+        {
+            self::assertNotNull($message);
+
+            return $message;
+        }
+
+        /**
+         * @return array<array-key, mixed>
+         */
+        public function values(mixed $values): array
+        {
+            Assert::assertIsArray($values);
+
+            return $values;
+        }
+
+        /**
+         * @return iterable<string, array{string, int}>
+         */
+        public static function subjects(): iterable
+        {
+            yield 'hello' => ['Hello', 1];
+        }
+
+        /**
+         * @return iterable<string, array{subject: string, count: int}>
+         */
+        public static function namedSubjects(): iterable
+        {
+            yield 'hello' => ['subject' => 'Hello', 'count' => 1];
+        }
+
+        #[DataProvider('subjects')]
+        public function testSubjects(string $subject, int $count): void
+        {
+        }
+
+        #[DataProvider('namedSubjects')]
+        public function testNamedSubjects(int $count, string $subject): void
+        {
+        }
+
+        /**
+         * @dataProvider subjects
+         */
+        public function testDocblockProvider(string $subject, int $count): void
+        {
+        }
+
+        #[DataProviderExternal(MessageProvider::class, 'messages')]
+        public function testExternalProvider(Message $message): void
+        {
+        }
+
+        // @mago-expect analysis:invalid-data-provider
+        #[DataProvider('missing')]
+        public function testMissingProvider(string $subject): void
+        {
+        }
+
+        /**
+         * @dataProvider missing
+         *
+         * @mago-expect analysis:invalid-data-provider
+         */
+        public function testMissingDocblockProvider(string $subject): void
+        {
+        }
+
+        #[DataProvider('subjects')]
+        // @mago-expect analysis:invalid-data-provider
+        public function testMismatchedProvider(int $subject, int $count): void
+        {
+        }
+
+        #[DataProvider('namedSubjects')]
+        // @mago-expect analysis:invalid-data-provider
+        public function testMismatchedNamedProvider(int $count, Message $subject): void
+        {
+        }
+
+        #[DataProviderExternal(MessageProvider::class, 'messages')]
+        // @mago-expect analysis:invalid-data-provider
+        public function testMismatchedExternalProvider(string $message): void
+        {
+        }
+
+        // @mago-expect analysis:invalid-data-provider
+        #[DataProvider('hiddenSubjects')]
+        public function testHiddenProvider(string $subject, int $count): void
+        {
+        }
+
+        /**
+         * @return iterable<string, array{string, int}>
+         *
+         * @mago-expect analysis:unused-method
+         */
+        protected static function hiddenSubjects(): iterable
+        {
+            yield 'hello' => ['Hello', 1];
+        }
+    }
+}
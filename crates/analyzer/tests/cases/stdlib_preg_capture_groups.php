@@ -0,0 +1,143 @@
+<?php
+
+declare(strict_types=1);
+
+/**
+ * @return null|array{0: string, 1: string, 2: string, 3?: string, 'year': string}
+ */
+function preg_match_groups(string $subject): null|array
+{
+    $matches = [];
+
+    if (preg_match('/(\d+)-(?<year>\d{4})(x)?/', $subject, $matches)) {
+        return $matches;
+    }
+
+    return null;
+}
+
+function preg_match_negated(string $subject): string
+{
+    $matches = [];
+
+    if (!preg_match('~^(?P<scheme>\w+)://([^/]+)~i', $subject, $matches)) {
+        return '';
+    }
+
+    return $matches['scheme'] . $matches[2];
+}
+
+/**
+ * @return null|array{0: string, 1: string, 2: string}
+ */
+function preg_match_optional_before_required(string $subject): null|array
+{
+    $matches = [];
+
+    if (preg_match('/(a)?(b)/', $subject, $matches)) {
+        return $matches;
+    }
+
+    return null;
+}
+
+/**
+ * @return null|array{0: list{string, int}, 1: list{null|string, int}, 2: list{null|string, int}}
+ */
+function preg_match_flags(string $subject): null|array
+{
+    $matches = [];
+
+    if (preg_match('/(a)|(b)/x', $subject, $matches, PREG_UNMATCHED_AS_NULL | PREG_OFFSET_CAPTURE)) {
+        return $matches;
+    }
+
+    return null;
+}
+
+/**
+ * @return null|array{0: non-empty-list<string>, 1: non-empty-list<string>, 2: non-empty-list<string>, 'n': non-empty-list<string>}
+ */
+function preg_match_all_pattern_order(string $subject): null|array
+{
+    $matches = [];
+
+    if (preg_match_all('/(a)(?<n>b)?/', $subject, $matches)) {
+        return $matches;
+    }
+
+    return null;
+}
+
+/**
+ * @return null|non-empty-list<array{0: string, 1: string, 2?: string, 'n'?: string}>
+ */
+function preg_match_all_set_order(string $subject): null|array
+{
+    $matches = [];
+
+    if (preg_match_all('/(a)(?<n>b)?/', $subject, $matches, PREG_SET_ORDER)) {
+        return $matches;
+    }
+
+    return null;
+}
+
+function preg_replace_callback_groups(string $subject): null|string
+{
+    return preg_replace_callback(
+        '/(?<word>\w+)(\d)?/',
+        static fn(array $matches): string => $matches['word'] . ($matches[2] ?? ''),
+        $subject,
+    );
+}
+
+/**
+ * @mago-expect analysis:possibly-undefined-int-array-index
+ * @mago-expect analysis:nullable-return-statement
+ * @mago-expect analysis:invalid-return-statement
+ */
+function preg_match_possibly_unmatched_group(string $subject): string
+{
+    $matches = [];
+
+    if (preg_match('/(a)(b)?/', $subject, $matches)) {
+        return $matches[2];
+    }
+
+    return '';
+}
+
+function preg_match_required_group(string $input): null|string
+{
+    $matches = [];
+
+    if (preg_match('/v(\d+\.\d+\.\d+)/', $input, $matches)) {
+        return $matches[1];
+    }
+
+    return null;
+}
+
+function preg_match_required_group_coalesce(string $input): string
+{
+    $matches = [];
+
+    if (preg_match('/v(\d+)/', $input, $matches)) {
+        /** @mago-expect analysis:redundant-null-coalesce */
+        return $matches[1] ?? '0';
+    }
+
+    return '0';
+}
+
+function preg_match_optional_group_coalesce(string $input): string
+{
+    $matches = [];
+
+    if (preg_match('/v(\d+)(-\w+)?/', $input, $matches)) {
+        return $matches[2] ?? '';
+    }
+
+    return '';
+}
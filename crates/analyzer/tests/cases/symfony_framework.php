@@ -0,0 +1,321 @@
+<?php
+
+namespace Psr\Container {
+    interface ContainerInterface
+    {
+        public function get(string $id): mixed;
+
+        public function has(string $id): bool;
+    }
+}
+
+namespace Symfony\Component\DependencyInjection {
+    interface ContainerInterface extends \Psr\Container\ContainerInterface
+    {
+        public function getParameter(string $name): array|bool|string|int|float|null;
+    }
+}
+
+namespace Symfony\Component\DependencyInjection\ParameterBag {
+    interface ParameterBagInterface
+    {
+        public function get(string $name): array|bool|string|int|float|null;
+    }
+}
+
+namespace Symfony\Component\Form {
+    interface FormInterface
+    {
+        public function getData(): mixed;
+    }
+
+    interface FormFactoryInterface
+    {
+        public function create(string $type = '', mixed $data = null, array $options = []): FormInterface;
+
+        public function createNamed(
+            string $name,
+            string $type = '',
+            mixed $data = null,
+            array $options = [],
+        ): FormInterface;
+    }
+
+    abstract class AbstractType
+    {
+    }
+}
+
+namespace Symfony\Component\OptionsResolver {
+    final class OptionsResolver
+    {
+        /**
+         * @param array<string, mixed> $defaults
+         */
+        public function setDefaults(array $defaults): static
+        {
+            return $this;
+        }
+
+        public function setDefault(string $option, mixed $value): static
+        {
+            return $this;
+        }
+    }
+}
+
+namespace Symfony\Bundle\FrameworkBundle\Controller {
+    use Symfony\Component\Form\FormInterface;
+
+    abstract class AbstractController
+    {
+        protected function getParameter(string $name): array|bool|string|int|float|null
+        {
+            return null;
+        }
+
+        protected function createForm(string $type, mixed $data = null, array $options = []): FormInterface
+        {
+            exit();
+        }
+    }
+}
+
+namespace Symfony\Component\EventDispatcher\Attribute {
+    use Attribute;
+
+    #[Attribute(Attribute::TARGET_CLASS | Attribute::TARGET_METHOD | Attribute::IS_REPEATABLE)]
+    final class AsEventListener
+    {
+        public function __construct(
+            public null|string $event = null,
+            public null|string $method = null,
+            public int $priority = 0,
+        ) {}
+    }
+}
+
+namespace Symfony\Component\HttpKernel {
+    final class KernelEvents
+    {
+        public const string REQUEST = 'kernel.request';
+    }
+}
+
+namespace Symfony\Component\HttpKernel\Event {
+    class KernelEvent
+    {
+    }
+
+    final class RequestEvent extends KernelEvent
+    {
+    }
+
+    final class ResponseEvent extends KernelEvent
+    {
+    }
+}
+
+namespace App\Entity {
+    final class User
+    {
+        public string $email = '';
+    }
+
+    final class Address
+    {
+        public string $city = '';
+    }
+}
+
+namespace App\Service {
+    final class Mailer
+    {
+        public function send(string $to): void
+        {
+        }
+    }
+}
+
+namespace App\Form {
+    use App\Entity\Address;
+    use App\Entity\User;
+    use Symfony\Component\Form\AbstractType;
+    use Symfony\Component\OptionsResolver\OptionsResolver;
+
+    final class UserType extends AbstractType
+    {
+        public function configureOptions(OptionsResolver $resolver): void
+        {
+            $resolver->setDefaults([
+                'data_class' => User::class,
+            ]);
+        }
+    }
+
+    final class AddressType extends AbstractType
+    {
+        public function configureOptions(OptionsResolver $resolver): void
+        {
+            $resolver->setDefault('data_class', Address::class);
+        }
+    }
+}
+
+namespace App\Controller {
+    use App\Entity\User;
+    use App\Form\UserType;
+    use Symfony\Bundle\FrameworkBundle\Controller\AbstractController;
+
+    final class ProfileController extends AbstractController
+    {
+        public function isDebug(): bool
+        {
+            return $this->getParameter('kernel.debug');
+        }
+
+        public function projectDirectory(): string
+        {
+            return $this->getParameter('kernel.project_dir');
+        }
+
+        public function pageSize(): int
+        {
+            return $this->getParameter('app.page_size');
+        }
+
+        /**
+         * @return array<array-key, mixed>
+         */
+        public function bundles(): array
+        {
+            return $this->getParameter('kernel.bundles');
+        }
+
+        public function edit(User $user): User
+        {
+            return $this->createForm(UserType::class, $user)->getData();
+        }
+
+        public function create(): User
+        {
+            // @mago-expect analysis:nullable-return-statement,invalid-return-statement
+            return $this->createForm(UserType::class)->getData();
+        }
+    }
+}
+
+namespace App {
+    use App\Entity\Address;
+    use App\Form\AddressType;
+    use App\Service\Mailer;
+    use Symfony\Component\DependencyInjection\ContainerInterface;
+    use Symfony\Component\DependencyInjection\ParameterBag\ParameterBagInterface;
+    use Symfony\Component\Form\FormFactoryInterface;
+
+    function mailer(ContainerInterface $container): Mailer
+    {
+        return $container->get('mailer');
+    }
+
+    function mailer_by_class(ContainerInterface $container): Mailer
+    {
+        return $container->get(Mailer::class);
+    }
+
+    function send(ContainerInterface $container): void
+    {
+        $container->get('app.mailer')->send('user@example.com');
+    }
+
+    function ratio(ParameterBagInterface $parameters): float
+    {
+        return $parameters->get('app.ratio');
+    }
+
+    function address(FormFactoryInterface $forms, Address $address): Address
+    {
+        return $forms->createNamed('address', AddressType::class, $address)->getData();
+    }
+
+    function new_address(FormFactoryInterface $forms): null|Address
+    {
+        return $forms->create(AddressType::class)->getData();
+    }
+}
+
+namespace App\EventListener {
+    use Symfony\Component\EventDispatcher\Attribute\AsEventListener;
+    use Symfony\Component\HttpKernel\Event\KernelEvent;
+    use Symfony\Component\HttpKernel\Event\RequestEvent;
+    use Symfony\Component\HttpKernel\Event\ResponseEvent;
+    use Symfony\Component\HttpKernel\KernelEvents;
+
+    #[AsEventListener(event: 'kernel.request')]
+    final class LocaleListener
+    {
+        public function onKernelRequest(RequestEvent $event): void
+        {
+        }
+    }
+
+    #[AsEventListener]
+    final class InvokableListener
+    {
+        public function __invoke(ResponseEvent $event): void
+        {
+        }
+    }
+
+    #[AsEventListener(event: RequestEvent::class, method: 'handle')]
+    #[AsEventListener(event: 'app.custom', method: 'handle')]
+    final class NamedMethodListener
+    {
+        public function handle(KernelEvent $event): void
+        {
+        }
+    }
+
+    final class MethodListeners
+    {
+        #[AsEventListener(event: 'kernel.response')]
+        public function onResponse(ResponseEvent $event): void
+        {
+        }
+
+        #[AsEventListener]
+        public function onAnyKernelEvent(KernelEvent $event): void
+        {
+        }
+
+        #[AsEventListener(event: KernelEvents::REQUEST)]
+        public function onConstantEvent($event): void
+        {
+        }
+
+        // @mago-expect analysis:invalid-event-listener
+        #[AsEventListener(event: 'kernel.response')]
+        public function onWrongEvent(RequestEvent $event): void
+        {
+        }
+
+        // @mago-expect analysis:invalid-event-listener
+        #[AsEventListener(event: 'kernel.request')]
+        // @mago-expect analysis:unused-method
+        private function onPrivate(RequestEvent $event): void
+        {
+        }
+
+        // @mago-expect analysis:invalid-event-listener
+        #[AsEventListener]
+        public function onUntyped($event): void
+        {
+        }
+    }
+
+    // @mago-expect analysis:invalid-event-listener
+    #[AsEventListener(event: 'kernel.request', method: 'missing')]
+    final class MissingMethodListener
+    {
+    }
+}
@@ -0,0 +1,48 @@
+<?php
+
+declare(strict_types=1);
+
+final class Console
+{
+    /**
+     * @mago-taint-source input
+     */
+    public function readLine(): string
+    {
+        return '';
+    }
+}
+
+/**
+ * @mago-taint-escape html
+ */
+function escape_html(string $value): string
+{
+    return str_replace(['<', '>'], ['&lt;', '&gt;'], $value);
+}
+
+/**
+ * @mago-taint-sink sql $query
+ */
+function run_query(string $query): void
+{
+}
+
+/**
+ * @mago-taint-sink $command
+ */
+function run_anything(string $command, string $label): void
+{
+}
+
+function annotated(Console $console): void
+{
+    $line = $console->readLine();
+
+    echo escape_html($line);
+
+    /** @mago-expect analysis:tainted-sql */
+    run_query('SELECT ' . escape_html($line));
+
+    run_anything('true', $line);
+}
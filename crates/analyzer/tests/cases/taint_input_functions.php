@@ -0,0 +1,28 @@
+<?php
+
+declare(strict_types=1);
+
+function print_query(): void
+{
+    /** @mago-expect analysis:tainted-html,mixed-argument */
+    echo filter_input(INPUT_GET, 'q');
+}
+
+function search_products(mysqli $connection): void
+{
+    /** @var array<string, string> $filters */
+    $filters = filter_input_array(INPUT_POST);
+    $query = 'SELECT * FROM products WHERE name = \'' . $filters['name'] . '\'';
+
+    /** @mago-expect analysis:tainted-sql */
+    mysqli_query($connection, $query);
+}
+
+function print_headers(): void
+{
+    /** @var array<string, string> $headers */
+    $headers = getallheaders();
+
+    /** @mago-expect analysis:tainted-html */
+    echo $headers['X-Forwarded-For'];
+}
@@ -0,0 +1,66 @@
+<?php
+
+declare(strict_types=1);
+
+function read_search_term(): string
+{
+    /** @var string $term */
+    $term = $_GET['q'];
+
+    return $term;
+}
+
+function wrap_in_heading(string $text): string
+{
+    return '<h1>' . $text . '</h1>';
+}
+
+function output(string $html): void
+{
+    /** @mago-expect analysis:tainted-html */
+    echo $html;
+}
+
+function show_search_results(): void
+{
+    $term = read_search_term();
+
+    output(wrap_in_heading($term));
+}
+
+function show_static_heading(): void
+{
+    echo wrap_in_heading('Search');
+}
+
+/**
+ * @param array{term: string, page: int} $query
+ */
+function show_page(array $query): void
+{
+    echo $query['page'];
+}
+
+function show_shaped_query(): void
+{
+    /** @var string $term */
+    $term = $_GET['q'];
+    $query = ['term' => $term, 'title' => 'Search'];
+
+    echo $query['title'];
+
+    /** @mago-expect analysis:tainted-html */
+    echo $query['term'];
+}
+
+function show_branches(bool $raw): void
+{
+    $value = 'default';
+    if ($raw) {
+        /** @var string $value */
+        $value = $_GET['value'];
+    }
+
+    /** @mago-expect analysis:tainted-html */
+    echo $value;
+}
@@ -0,0 +1,60 @@
+<?php
+
+declare(strict_types=1);
+
+final class SearchQuery
+{
+    public function __construct(
+        private string $term,
+    ) {}
+
+    public function getTerm(): string
+    {
+        return $this->term;
+    }
+}
+
+final class QueryBuilder
+{
+    private string $sql = '';
+
+    public function where(string $condition): self
+    {
+        $this->sql .= ' WHERE ' . $condition;
+
+        return $this;
+    }
+
+    public function run(PDO $pdo): void
+    {
+        /** @mago-expect analysis:tainted-sql */
+        $pdo->query('SELECT * FROM posts' . $this->sql);
+    }
+}
+
+function search(PDO $pdo): void
+{
+    /** @var string $term */
+    $term = $_GET['term'];
+    $query = new SearchQuery($term);
+
+    $builder = new QueryBuilder();
+    $builder->where("title LIKE '%" . $query->getTerm() . "%'");
+    $builder->run($pdo);
+}
+
+function search_safely(PDO $pdo): void
+{
+    /** @var string $term */
+    $term = $_GET['term'];
+
+    $statement = $pdo->prepare('SELECT * FROM posts WHERE title LIKE ?');
+    if (false !== $statement) {
+        $statement->execute(['%' . $term . '%']);
+    }
+
+    $quoted = $pdo->quote($term);
+    if (false !== $quoted) {
+        $pdo->query('SELECT * FROM posts WHERE title = ' . $quoted);
+    }
+}
@@ -0,0 +1,59 @@
+<?php
+
+declare(strict_types=1);
+
+function render_escaped(): void
+{
+    /** @var string $name */
+    $name = $_GET['name'];
+    $name = htmlspecialchars($name);
+
+    echo 'Hello, ' . $name;
+}
+
+function run_escaped(): void
+{
+    /** @var string $file */
+    $file = $_GET['file'];
+
+    exec('cat ' . escapeshellarg($file));
+}
+
+function render_number(): void
+{
+    /** @var string $page */
+    $page = $_GET['page'];
+
+    echo (int) $page;
+}
+
+function render_filtered(): void
+{
+    $id = filter_input(INPUT_GET, 'id', FILTER_VALIDATE_INT);
+    $name = filter_input(INPUT_GET, 'name', FILTER_SANITIZE_SPECIAL_CHARS);
+
+    echo (string) $id;
+    echo $name;
+
+    /** @mago-expect analysis:tainted-shell */
+    exec('greet ' . $name);
+}
+
+function render_overwritten(): void
+{
+    /** @var string $name */
+    $name = $_GET['name'];
+    $name = 'anonymous';
+
+    echo $name;
+}
+
+function run_escaped_only_for_html(): void
+{
+    /** @var string $file */
+    $file = $_GET['file'];
+    $file = htmlspecialchars($file);
+
+    /** @mago-expect analysis:tainted-shell */
+    exec('cat ' . $file);
+}
@@ -0,0 +1,53 @@
+<?php
+
+declare(strict_types=1);
+
+function render_greeting(): void
+{
+    /** @var string $name */
+    $name = $_GET['name'];
+    $greeting = 'Hello, ' . $name;
+
+    /** @mago-expect analysis:tainted-html */
+    echo $greeting;
+}
+
+function run_report(): void
+{
+    /** @var string $report */
+    $report = $_POST['report'] ?? 'daily';
+
+    /** @mago-expect analysis:tainted-shell */
+    shell_exec('generate-report ' . $report);
+}
+
+function load_page(): void
+{
+    /** @var string $page */
+    $page = $_COOKIE['page'];
+
+    /** @mago-expect analysis:tainted-include */
+    require __DIR__ . '/pages/' . $page . '.php';
+}
+
+function restore_state(): mixed
+{
+    /** @var string $state */
+    $state = $_COOKIE['state'];
+
+    /** @mago-expect analysis:tainted-unserialize */
+    return unserialize($state);
+}
+
+function print_server_values(): void
+{
+    /** @var string $agent */
+    $agent = $_SERVER['HTTP_USER_AGENT'];
+    /** @var string $server */
+    $server = $_SERVER['SERVER_NAME'];
+
+    /** @mago-expect analysis:tainted-html */
+    echo $agent;
+
+    echo $server;
+}
@@ -0,0 +1,43 @@
+<?php
+
+namespace Twig {
+    final class Environment
+    {
+        /**
+         * @param array<string, mixed> $context
+         */
+        public function render(string $name, array $context = []): string
+        {
+            return $name . (string) count($context);
+        }
+    }
+}
+
+namespace App {
+    use Twig\Environment;
+
+    final class Mailer
+    {
+        public function __construct(
+            private Environment $twig,
+        ) {}
+
+        public function welcome(): string
+        {
+            return $this->twig->render('email/welcome.html.twig');
+        }
+
+        /**
+         * @mago-expect analysis:non-existent-template
+         */
+        public function goodbye(): string
+        {
+            return $this->twig->render('email/goodbye.html.twig');
+        }
+
+        public function bundle(): string
+        {
+            return $this->twig->render('@Acme/email/goodbye.html.twig');
+        }
+    }
+}
@@ -0,0 +1,104 @@
+<?php
+
+namespace Symfony\Bundle\FrameworkBundle\Controller {
+    abstract class AbstractController
+    {
+        /**
+         * @param array<string, mixed> $parameters
+         */
+        protected function render(string $view, array $parameters = []): string
+        {
+            return $view . (string) count($parameters);
+        }
+    }
+}
+
+namespace App\Entity {
+    enum Status: string
+    {
+        case Active = 'active';
+    }
+
+    final class Address
+    {
+        public function __construct(
+            public string $city,
+        ) {}
+    }
+
+    final class User
+    {
+        public function __construct(
+            private string $email,
+            private Address $address,
+            public Status $status,
+        ) {}
+
+        public function getEmail(): string
+        {
+            return $this->email;
+        }
+
+        public function isActive(): bool
+        {
+            return $this->status === Status::Active;
+        }
+
+        public function getAddress(): Address
+        {
+            return $this->address;
+        }
+
+        public function verify(): bool
+        {
+            return $this->getToken() !== '';
+        }
+
+        private function getToken(): string
+        {
+            return $this->email;
+        }
+    }
+}
+
+namespace App\Controller {
+    use App\Entity\User;
+    use Symfony\Bundle\FrameworkBundle\Controller\AbstractController;
+
+    final class UserController extends AbstractController
+    {
+        public function show(User $user): string
+        {
+            return $this->render('user/show.html.twig', ['user' => $user]);
+        }
+
+        /**
+         * @mago-expect analysis:non-existent-template-attribute
+         */
+        public function typo(User $user): string
+        {
+            return $this->render('user/typo.html.twig', ['user' => $user]);
+        }
+
+        /**
+         * @mago-expect analysis:non-existent-template-attribute
+         */
+        public function nested(User $user): string
+        {
+            return $this->render('user/nested.html.twig', ['user' => $user]);
+        }
+
+        /**
+         * @mago-expect analysis:non-existent-template-attribute
+         */
+        public function private(User $user): string
+        {
+            return $this->render('user/private.html.twig', ['user' => $user]);
+        }
+
+        public function scalar(string $user): string
+        {
+            return $this->render('user/typo.html.twig', ['user' => $user]);
+        }
+    }
+}
@@ -0,0 +1,60 @@
+<?php
+
+namespace Symfony\Bundle\FrameworkBundle\Controller {
+    abstract class AbstractController
+    {
+        /**
+         * @param array<string, mixed> $parameters
+         */
+        protected function render(string $view, array $parameters = []): string
+        {
+            return $view . (string) count($parameters);
+        }
+    }
+}
+
+namespace App\Controller {
+    use Symfony\Bundle\FrameworkBundle\Controller\AbstractController;
+
+    final class UserController extends AbstractController
+    {
+        /**
+         * @mago-expect analysis:undefined-template-variable
+         */
+        public function show(string $name): string
+        {
+            return $this->render('user/show.html.twig', ['name' => $name]);
+        }
+
+        /**
+         * @mago-expect analysis:undefined-template-variable(2)
+         */
+        public function empty(): string
+        {
+            return $this->render('user/show.html.twig');
+        }
+
+        public function complete(string $name): string
+        {
+            return $this->render('user/show.html.twig', ['name' => $name, 'title' => 'Profile']);
+        }
+
+        public function guarded(): string
+        {
+            return $this->render('user/guarded.html.twig', ['items' => []]);
+        }
+
+        public function page(string $name): string
+        {
+            return $this->render('user/page.html.twig', ['name' => $name]);
+        }
+
+        /**
+         * @param array<string, mixed> $parameters
+         */
+        public function unsealed(array $parameters): string
+        {
+            return $this->render('user/show.html.twig', $parameters);
+        }
+    }
+}
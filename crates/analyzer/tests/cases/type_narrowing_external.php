@@ -6,6 +6,7 @@ function extractVersion(string $input): null|string
 {
     $matches = [];
     if (preg_match('/v(\d+\.\d+\.\d+)/', $input, $matches)) {
+        /** @mago-expect analysis:redundant-null-coalesce */
         return $matches[1] ?? null;
     }
 
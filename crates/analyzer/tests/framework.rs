@@ -10,8 +10,13 @@ use foldhash::HashSet;
 use mago_allocator::LocalArena;
 use mago_analyzer::Analyzer;
 use mago_analyzer::analysis_result::AnalysisResult;
+use mago_analyzer::container::ServiceContainer;
+use mago_analyzer::dead_code::find_unreachable_symbols;
+use mago_analyzer::form::FormIndex;
 use mago_analyzer::plugin::PluginRegistry;
+use mago_analyzer::schema::DatabaseSchema;
 use mago_analyzer::settings::Settings;
+use mago_analyzer::template::TemplateIndex;
 use mago_codex::metadata::CodebaseMetadata;
 use mago_codex::populator::populate_codebase;
 use mago_codex::scanner::scan_program;
@@ -34,6 +39,9 @@ pub struct TestCase<'src> {
     settings: Option<Settings>,
     expected_property_reads: Vec<(&'src str, &'src str, usize)>,
     expected_symbol_references: Vec<(&'src str, &'src str, usize)>,
+    templates: Vec<(&'src str, &'src str)>,
+    migrations: Vec<(&'src str, &'src str)>,
+    container: Option<&'src str>,
 }
 
 impl<'src> TestCase<'src> {
@@ -45,6 +53,9 @@ impl<'src> TestCase<'src> {
             settings: None,
             expected_property_reads: Vec::new(),
             expected_symbol_references: Vec::new(),
+            templates: Vec::new(),
+            migrations: Vec::new(),
+            container: None,
         }
     }
 
@@ -66,6 +77,27 @@ impl<'src> TestCase<'src> {
         self
     }
 
+    /// Adds a Twig template, named relative to the `templates` directory.
+    #[must_use]
+    pub fn template(mut self, name: &'src str, content: &'src str) -> Self {
+        self.templates.push((name, content));
+        self
+    }
+
+    /// Adds a migration, named relative to the `database/migrations` directory.
+    #[must_use]
+    pub fn migration(mut self, name: &'src str, content: &'src str) -> Self {
+        self.migrations.push((name, content));
+        self
+    }
+
+    /// Sets the XML dump of the compiled service container.
+    #[must_use]
+    pub fn container(mut self, xml: &'src str) -> Self {
+        self.container = Some(xml);
+        self
+    }
+
     pub fn run(self) {
         run_test_case_inner(self);
     }
@@ -118,6 +150,16 @@ pub fn check_missing_type_hints_settings() -> Settings {
     Settings { check_missing_type_hints: true, ..Default::default() }
 }
 
+#[must_use]
+pub fn taint_analysis_settings() -> Settings {
+    Settings { check_taints: true, ..Default::default() }
+}
+
+#[must_use]
+pub fn dead_code_report_settings() -> Settings {
+    Settings { find_unreachable_symbols: true, ..Default::default() }
+}
+
 fn run_test_case_inner(config: TestCase) {
     let Prelude { mut database, mut metadata, mut symbol_references } = PRELUDE.clone();
 
@@ -138,8 +180,43 @@ fn run_test_case_inner(config: TestCase) {
 
     populate_codebase(&mut metadata, &mut symbol_references, WordSet::default(), HashSet::default());
 
+    let template_files: Vec<File> = config
+        .templates
+        .iter()
+        .map(|(name, content)| {
+            File::ephemeral(
+                Cow::Owned(format!("templates/{name}").into_bytes()),
+                Cow::Owned(content.as_bytes().to_vec()),
+            )
+        })
+        .collect();
+    let templates = TemplateIndex::from_files(&template_files, &settings.template_paths);
+
+    let migration_files: Vec<File> = config
+        .migrations
+        .iter()
+        .map(|(name, content)| {
+            File::ephemeral(
+                Cow::Owned(format!("database/migrations/{name}").into_bytes()),
+                Cow::Owned(content.as_bytes().to_vec()),
+            )
+        })
+        .collect();
+    let schema = DatabaseSchema::from_files(&migration_files, &settings.migration_paths);
+    let container = config.container.map(ServiceContainer::from_xml).unwrap_or_default();
+    let forms = FormIndex::from_files([source_file]);
+
+    let check_taints = settings.check_taints;
+    let entry_points = settings.find_unreachable_symbols.then(|| settings.entry_points.clone());
     let mut analysis_result = AnalysisResult::new(symbol_references);
-    let analyzer = Analyzer::new(&arena, source_file, &resolved_names, &metadata, &PLUGIN_REGISTRY, settings);
+    let mut analyzer = Analyzer::new(&arena, source_file, &resolved_names, &metadata, &PLUGIN_REGISTRY, settings)
+        .with_templates(&templates)
+        .with_schema(&schema)
+        .with_container(&container)
+        .with_forms(&forms);
+    if check_taints || entry_points.is_some() {
+        analyzer = analyzer.with_deferred_pragmas();
+    }
 
     let analysis_run_result = analyzer.analyze(program, &mut analysis_result);
 
@@ -147,6 +224,31 @@ fn run_test_case_inner(config: TestCase) {
         panic!("Test '{}': Expected analysis to succeed, but it failed with an error: {}", config.name, err);
     }
 
+    // Taint flows and unreachable symbols are only known once the whole program is analyzed,
+    // so `@mago-expect` pragmas are reconciled against them after the fact, as the orchestrator does.
+    if check_taints || entry_points.is_some() {
+        let mut issues = std::mem::take(&mut analysis_result.issues);
+        if check_taints {
+            issues.extend(analysis_result.taint_graph.find_flows());
+        }
+
+        if let Some(entry_points) = &entry_points {
+            issues.extend(find_unreachable_symbols(
+                &metadata,
+                &analysis_result.symbol_references,
+                entry_points,
+                [source_file],
+            ));
+        }
+
+        for mut pragmas in analysis_result.take_deferred_pragmas() {
+            issues = pragmas.reconcile(source_file, issues);
+            issues.extend(pragmas.finish(source_file));
+        }
+
+        analysis_result.issues = issues;
+    }
+
     for (class, property, expected) in config.expected_property_reads {
         let symbol = (ascii_lowercase_word(class.as_bytes()), word(property.as_bytes()));
         let actual = analysis_result.symbol_references.count_property_reads(&symbol);
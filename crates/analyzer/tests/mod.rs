@@ -6,12 +6,67 @@ use crate::framework::default_test_settings;
 
 mod framework;
 
+const TWIG_RENDER_VARIABLE_TEMPLATES: [(&str, &str); 5] = [
+    ("user/show.html.twig", "<h1>{{ title }}</h1><p>{{ name }}</p>"),
+    (
+        "user/guarded.html.twig",
+        "{% if title is defined %}{{ title }}{% endif %}{{ subtitle|default('') }}{% for item in items %}{{ item }}{{ loop.index }}{% endfor %}{% set total = 1 %}{{ total }}{{ app.request }}",
+    ),
+    ("base.html.twig", "<title>{% block title %}{{ title }}{% endblock %}</title>{% block body %}{% endblock %}"),
+    (
+        "user/page.html.twig",
+        "{% extends 'base.html.twig' %}{% block title %}{{ name }}{% endblock %}{% block body %}{% include 'user/partial.html.twig' %}{% endblock %}",
+    ),
+    ("user/partial.html.twig", "{{ name|upper }}"),
+];
+
+const LARAVEL_MIGRATIONS: [(&str, &str); 2] = [
+    (
+        "2024_01_01_000000_create_users_table.php",
+        "<?php return new class extends Migration { public function up(): void { Schema::create('users', function (Blueprint $table) { $table->id(); $table->string('email')->unique(); $table->string('nickname')->nullable(); $table->boolean('is_admin'); $table->string('status'); $table->json('settings'); $table->timestamp('verified_at')->nullable(); $table->decimal('balance', 8, 2); $table->timestamps(); }); } };",
+    ),
+    (
+        "2024_01_02_000000_create_articles_table.php",
+        "<?php return new class extends Migration { public function up(): void { Schema::create('articles', function (Blueprint $table) { $table->id(); $table->foreignIdFor(User::class); $table->string('title'); $table->timestamps(); }); } };",
+    ),
+];
+
+const SYMFONY_CONTAINER: &str = r#"<?xml version="1.0" encoding="utf-8"?>
+<container xmlns="http://symfony.com/schema/dic/services">
+  <parameters>
+    <parameter key="kernel.debug">true</parameter>
+    <parameter key="kernel.project_dir">/srv/app</parameter>
+    <parameter key="kernel.bundles" type="collection"/>
+    <parameter key="app.page_size">25</parameter>
+    <parameter key="app.ratio">0.5</parameter>
+  </parameters>
+  <services>
+    <service id="App\Service\Mailer" class="App\Service\Mailer" public="true"/>
+    <service id="app.mailer" alias="App\Service\Mailer" public="true"/>
+    <service id="mailer" alias="app.mailer" public="true"/>
+  </services>
+</container>
+"#;
+
+const TWIG_RENDER_ATTRIBUTE_TEMPLATES: [(&str, &str); 4] = [
+    (
+        "user/show.html.twig",
+        "{{ user.email }}{{ user.active }}{{ user.address.city }}{{ user.status.value }}{{ user.getEmail() }}",
+    ),
+    ("user/typo.html.twig", "{{ user.emial }}"),
+    ("user/nested.html.twig", "{{ user.address.town }}"),
+    ("user/private.html.twig", "{{ user.token }}"),
+];
+
 /// A macro to automatically generate a test case from a corresponding PHP file.
 ///
 /// # Variants
 ///
 /// - `test_case!(test_name)`: Creates a test using default settings.
 /// - `test_case!(test_name, settings_expression)`: Creates a test with custom settings.
+/// - `test_case!(test_name, templates = [(name, content), ...])`: Creates a test with Twig templates.
+/// - `test_case!(test_name, migrations = [(name, content), ...])`: Creates a test with database migrations.
+/// - `test_case!(test_name, container = xml)`: Creates a test with a compiled service container dump.
 ///
 /// For a given test name, e.g., `my_test`, this macro will:
 ///
@@ -37,6 +92,37 @@ macro_rules! test_case {
             test.run();
         }
     };
+    ($test_name:ident, templates = $templates:expr) => {
+        #[test]
+        fn $test_name() {
+            let content = include_bytes!(concat!("cases/", stringify!($test_name), ".php"));
+            let mut test = $crate::framework::TestCase::new(stringify!($test_name), content);
+            for (name, template) in $templates {
+                test = test.template(name, template);
+            }
+
+            test.run();
+        }
+    };
+    ($test_name:ident, migrations = $migrations:expr) => {
+        #[test]
+        fn $test_name() {
+            let content = include_bytes!(concat!("cases/", stringify!($test_name), ".php"));
+            let mut test = $crate::framework::TestCase::new(stringify!($test_name), content);
+            for (name, migration) in $migrations {
+                test = test.migration(name, migration);
+            }
+
+            test.run();
+        }
+    };
+    ($test_name:ident, container = $container:expr) => {
+        #[test]
+        fn $test_name() {
+            let content = include_bytes!(concat!("cases/", stringify!($test_name), ".php"));
+            $crate::framework::TestCase::new(stringify!($test_name), content).container($container).run();
+        }
+    };
     ($test_name:ident, $settings:expr) => {
         #[test]
         fn $test_name() {
@@ -1320,6 +1406,7 @@ test_case!(strings_urlencode_with_array);
 test_case!(strings_invalid_property_default_string_to_int);
 test_case!(strings_undefined_variable_in_interpolation);
 test_case!(strings_preg_replace_with_int_subject);
+test_case!(stdlib_preg_capture_groups);
 test_case!(strings_mb_strlen_with_int);
 test_case!(strings_mb_substr_with_int);
 test_case!(strings_mb_strtolower_with_int);
@@ -2708,6 +2795,20 @@ test_case!(issue_2229);
 test_case!(issue_2231);
 test_case!(issue_2232);
 test_case!(issue_2233);
+test_case!(taint_superglobal_sinks, crate::framework::taint_analysis_settings());
+test_case!(taint_sanitized, crate::framework::taint_analysis_settings());
+test_case!(taint_interprocedural, crate::framework::taint_analysis_settings());
+test_case!(taint_properties, crate::framework::taint_analysis_settings());
+test_case!(taint_annotations, crate::framework::taint_analysis_settings());
+test_case!(taint_input_functions, crate::framework::taint_analysis_settings());
+test_case!(dead_code_report, crate::framework::dead_code_report_settings());
+test_case!(twig_render_undefined_template_variable, templates = TWIG_RENDER_VARIABLE_TEMPLATES);
+test_case!(twig_render_non_existent_template_attribute, templates = TWIG_RENDER_ATTRIBUTE_TEMPLATES);
+test_case!(twig_render_non_existent_template, templates = [("email/welcome.html.twig", "Welcome!")]);
+test_case!(laravel_eloquent, migrations = LARAVEL_MIGRATIONS);
+test_case!(doctrine_orm);
+test_case!(symfony_framework, container = SYMFONY_CONTAINER);
+test_case!(phpunit_framework);
 
 #[test]
 #[cfg_attr(miri, ignore)]
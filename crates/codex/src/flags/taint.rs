@@ -0,0 +1,141 @@
+/// A set of taint kinds, each naming a class of sink that untrusted data must not reach.
+///
+/// Taint kinds are declared in docblocks with the `@mago-taint-source`, `@mago-taint-sink`,
+/// and `@mago-taint-escape` tags, using the names returned by [`TaintFlags::name`].
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[repr(transparent)]
+pub struct TaintFlags(u8);
+
+impl TaintFlags {
+    /// Data rendered as HTML, e.g. by `echo` or `print`.
+    pub const HTML: TaintFlags = TaintFlags(1 << 0);
+
+    /// Data used as (part of) an SQL query.
+    pub const SQL: TaintFlags = TaintFlags(1 << 1);
+
+    /// Data used as (part of) a shell command.
+    pub const SHELL: TaintFlags = TaintFlags(1 << 2);
+
+    /// Data used as the path of an `include` or `require`.
+    pub const INCLUDE: TaintFlags = TaintFlags(1 << 3);
+
+    /// Data passed to `unserialize()`.
+    pub const UNSERIALIZE: TaintFlags = TaintFlags(1 << 4);
+
+    /// Every taint kind; untrusted input carries all of them.
+    pub const ALL: TaintFlags = TaintFlags((1 << 5) - 1);
+
+    /// Every individual taint kind, in declaration order.
+    pub const KINDS: [TaintFlags; 5] = [Self::HTML, Self::SQL, Self::SHELL, Self::INCLUDE, Self::UNSERIALIZE];
+}
+
+impl TaintFlags {
+    #[inline]
+    #[must_use]
+    pub const fn empty() -> Self {
+        TaintFlags(0)
+    }
+
+    #[inline]
+    #[must_use]
+    pub const fn bits(self) -> u8 {
+        self.0
+    }
+
+    #[inline]
+    #[must_use]
+    pub const fn is_empty(self) -> bool {
+        self.0 == 0
+    }
+
+    #[inline]
+    pub const fn insert(&mut self, other: TaintFlags) {
+        self.0 |= other.0;
+    }
+
+    #[inline]
+    pub const fn remove(&mut self, other: TaintFlags) {
+        self.0 &= !other.0;
+    }
+
+    #[inline]
+    #[must_use]
+    pub const fn contains(self, other: TaintFlags) -> bool {
+        (self.0 & other.0) == other.0
+    }
+
+    #[inline]
+    #[must_use]
+    pub const fn intersects(self, other: TaintFlags) -> bool {
+        (self.0 & other.0) != 0
+    }
+
+    #[inline]
+    #[must_use]
+    pub const fn union(self, other: TaintFlags) -> Self {
+        TaintFlags(self.0 | other.0)
+    }
+
+    #[inline]
+    #[must_use]
+    pub const fn intersection(self, other: TaintFlags) -> Self {
+        TaintFlags(self.0 & other.0)
+    }
+
+    #[inline]
+    #[must_use]
+    pub const fn difference(self, other: TaintFlags) -> Self {
+        TaintFlags(self.0 & !other.0)
+    }
+
+    /// Parses a single taint kind name, as written in docblock tags.
+    ///
+    /// `input` stands for every kind.
+    #[must_use]
+    pub fn from_name(name: &[u8]) -> Option<Self> {
+        Some(match name.to_ascii_lowercase().as_slice() {
+            b"html" => Self::HTML,
+            b"sql" => Self::SQL,
+            b"shell" => Self::SHELL,
+            b"include" => Self::INCLUDE,
+            b"unserialize" => Self::UNSERIALIZE,
+            b"input" => Self::ALL,
+            _ => return None,
+        })
+    }
+
+    /// Returns the docblock name of a single taint kind, or `None` if `self` is not exactly one kind.
+    #[must_use]
+    pub const fn name(self) -> Option<&'static str> {
+        Some(match self {
+            Self::HTML => "html",
+            Self::SQL => "sql",
+            Self::SHELL => "shell",
+            Self::INCLUDE => "include",
+            Self::UNSERIALIZE => "unserialize",
+            _ => return None,
+        })
+    }
+
+    /// Iterates over the individual kinds contained in `self`.
+    pub fn iter(self) -> impl Iterator<Item = TaintFlags> {
+        Self::KINDS.into_iter().filter(move |kind| self.contains(*kind))
+    }
+}
+
+impl std::ops::BitOr for TaintFlags {
+    type Output = Self;
+
+    #[inline]
+    fn bitor(self, rhs: Self) -> Self::Output {
+        self.union(rhs)
+    }
+}
+
+impl std::ops::BitOrAssign for TaintFlags {
+    #[inline]
+    fn bitor_assign(&mut self, rhs: Self) {
+        self.insert(rhs);
+    }
+}
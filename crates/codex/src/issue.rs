@@ -24,6 +24,7 @@ pub(crate) enum ScanningIssueKind {
     InvalidRequireImplementsTag,
     InvalidInheritorsTag,
     InvalidMixinTag,
+    InvalidTaintTag,
     CircularTypeImport,
     PatchDuplicateTarget,
     PatchKindMismatch,
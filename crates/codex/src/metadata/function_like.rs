@@ -11,6 +11,7 @@ use mago_word::WordMap;
 use mago_word::WordSet;
 
 use crate::assertion::Assertion;
+use crate::flags::taint::TaintFlags;
 use crate::issue::ScanningIssueKind;
 use crate::metadata::attribute::AttributeMetadata;
 use crate::metadata::class_like::TemplateTypes;
@@ -167,6 +168,14 @@ pub struct FunctionLikeMetadata {
     /// from `Mago\AvailableSince` / `Mago\AvailableUntil` attributes during
     /// scanning.
     pub version_constraint: VersionConstraint,
+
+    /// Taint kinds carried by the value this function-like returns, declared with
+    /// `@mago-taint-source`. Empty unless the function is a source of untrusted data.
+    pub taint_source: TaintFlags,
+
+    /// Taint kinds removed from the value this function-like returns, declared with
+    /// `@mago-taint-escape`. Used to mark user-defined sanitizers.
+    pub taint_escape: TaintFlags,
 }
 
 impl FunctionLikeKind {
@@ -233,6 +242,8 @@ impl FunctionLikeMetadata {
             has_docblock: false,
             issues: vec![],
             version_constraint: VersionConstraint::unconstrained(),
+            taint_source: TaintFlags::empty(),
+            taint_escape: TaintFlags::empty(),
         }
     }
 
@@ -478,6 +489,7 @@ impl FunctionLikeMetadata {
             if let Some(default) = &replacement.default_type {
                 slot.default_type = Some(default.clone());
             }
+            slot.taint_sink |= replacement.taint_sink;
         }
     }
 
@@ -502,5 +514,8 @@ impl FunctionLikeMetadata {
         if !patch.if_false_assertions.is_empty() {
             self.if_false_assertions = patch.if_false_assertions.clone();
         }
+
+        self.taint_source |= patch.taint_source;
+        self.taint_escape |= patch.taint_escape;
     }
 }
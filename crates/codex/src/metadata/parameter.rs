@@ -1,6 +1,7 @@
 use mago_span::HasSpan;
 use mago_span::Span;
 
+use crate::flags::taint::TaintFlags;
 use crate::metadata::attribute::AttributeMetadata;
 use crate::metadata::flags::MetadataFlags;
 use crate::metadata::ttype::TypeMetadata;
@@ -58,6 +59,9 @@ pub struct FunctionLikeParameterMetadata {
 
     /// Flags indicating various properties of the parameter.
     pub flags: MetadataFlags,
+
+    /// Taint kinds that must not reach this parameter, declared with `@mago-taint-sink`.
+    pub taint_sink: TaintFlags,
 }
 
 /// Contains metadata associated with a single parameter within a function, method, or closure signature.
@@ -86,6 +90,7 @@ impl FunctionLikeParameterMetadata {
             out_type: None,
             closure_this_type: None,
             default_type: None,
+            taint_sink: TaintFlags::empty(),
         }
     }
 
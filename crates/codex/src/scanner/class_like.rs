@@ -33,6 +33,7 @@ use mago_word::ascii_lowercase_word;
 use mago_word::word;
 
 use crate::consts::MAX_ENUM_CASES_FOR_ANALYSIS;
+use crate::flags::taint::TaintFlags;
 use crate::get_anonymous_class_name;
 use crate::identifier::method::MethodIdentifier;
 use crate::issue::ScanningIssueKind;
@@ -1503,6 +1504,7 @@ fn create_enum_value_parameter(backing_type: TAtomic, enum_method_span: Span) ->
         span: enum_method_span,
         name_span: enum_method_span,
         flags: MetadataFlags::empty(),
+        taint_sink: TaintFlags::empty(),
     }
 }
 
@@ -1543,5 +1545,7 @@ fn create_enum_method(
         has_docblock: false,
         flags: MetadataFlags::POPULATED,
         version_constraint: crate::metadata::version_constraint::VersionConstraint::unconstrained(),
+        taint_source: TaintFlags::empty(),
+        taint_escape: TaintFlags::empty(),
     }
 }
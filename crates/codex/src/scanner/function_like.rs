@@ -5,6 +5,7 @@ use mago_phpdoc_syntax::cst::AssertPattern;
 use mago_phpdoc_syntax::cst::AssertTagValue;
 use mago_phpdoc_syntax::cst::Element;
 use mago_phpdoc_syntax::cst::TagValue;
+use mago_phpdoc_syntax::cst::Text;
 use mago_phpdoc_syntax::cst::TextSegment;
 use mago_phpdoc_syntax::cst::r#type::Type;
 use mago_reporting::Annotation;
@@ -34,6 +35,7 @@ use mago_word::ascii_lowercase_word;
 use mago_word::word;
 
 use crate::assertion::Assertion;
+use crate::flags::taint::TaintFlags;
 use crate::issue::ScanningIssueKind;
 use crate::metadata::class_like::ClassLikeMetadata;
 use crate::metadata::constant::ConstantMetadata;
@@ -784,6 +786,12 @@ fn scan_function_like_docblock<A>(
         }
     }
 
+    for tag in document.tags() {
+        if let TagValue::Generic(generic) = &tag.value {
+            scan_taint_tag(tag.name.value, &generic.value, tag.span(), metadata);
+        }
+    }
+
     metadata.type_resolution_context = Some(type_context);
 
     if ignore_nullable_return || ignore_falsable_return {
@@ -799,6 +807,86 @@ fn scan_function_like_docblock<A>(
     }
 }
 
+/// Applies a `@mago-taint-source`, `@mago-taint-sink`, or `@mago-taint-escape` tag to `metadata`.
+///
+/// The tag value lists taint kinds separated by `|`; sources and escapes without kinds cover
+/// every kind. Sinks name the receiving parameter after the kinds, e.g. `@mago-taint-sink sql $query`.
+fn scan_taint_tag(name: &[u8], text: &Text<'_>, span: Span, metadata: &mut FunctionLikeMetadata) {
+    if !matches!(name, b"mago-taint-source" | b"mago-taint-sink" | b"mago-taint-escape") {
+        return;
+    }
+
+    let mut content = Vec::new();
+    for segment in text.segments.iter() {
+        if let TextSegment::PlainText(plain) = segment {
+            content.extend_from_slice(plain.value);
+        }
+    }
+
+    let mut words = content.split(u8::is_ascii_whitespace).filter(|word| !word.is_empty());
+    let (kinds, parameter) = match words.next() {
+        Some(first) if first.starts_with(b"$") => (None, Some(first)),
+        Some(first) => (Some(first), words.next().filter(|word| word.starts_with(b"$"))),
+        None => (None, None),
+    };
+
+    let mut flags = TaintFlags::empty();
+    for kind in kinds.into_iter().flat_map(|kinds| kinds.split(|byte| *byte == b'|')) {
+        let Some(kind) = TaintFlags::from_name(kind) else {
+            metadata.issues.push(
+                Issue::error("Unknown taint kind in taint tag.")
+                    .with_code(ScanningIssueKind::InvalidTaintTag)
+                    .with_annotation(
+                        Annotation::primary(span)
+                            .with_message(format!("`{}` is not a taint kind", String::from_utf8_lossy(kind))),
+                    )
+                    .with_help("Use one or more of `html`, `sql`, `shell`, `include`, `unserialize`, or `input`, separated by `|`."),
+            );
+
+            return;
+        };
+
+        flags |= kind;
+    }
+
+    if flags.is_empty() {
+        flags = TaintFlags::ALL;
+    }
+
+    match name {
+        b"mago-taint-source" => metadata.taint_source |= flags,
+        b"mago-taint-escape" => metadata.taint_escape |= flags,
+        _ => {
+            let Some(parameter_name) = parameter else {
+                metadata.issues.push(
+                    Issue::error("The `@mago-taint-sink` tag must name a parameter.")
+                        .with_code(ScanningIssueKind::InvalidTaintTag)
+                        .with_annotation(Annotation::primary(span).with_message("Missing parameter name"))
+                        .with_help("Write the tag as `@mago-taint-sink <kinds> $parameter`, e.g. `@mago-taint-sink sql $query`."),
+                );
+
+                return;
+            };
+
+            let Some(parameter_metadata) = metadata.get_parameter_mut(word(parameter_name)) else {
+                metadata.issues.push(
+                    Issue::error("The `@mago-taint-sink` tag references an unknown parameter.")
+                        .with_code(ScanningIssueKind::InvalidTaintTag)
+                        .with_annotation(Annotation::primary(span).with_message(format!(
+                            "Parameter `{}` is not defined in this function",
+                            String::from_utf8_lossy(parameter_name)
+                        )))
+                        .with_help("Please check for typos or add the parameter to the function signature."),
+                );
+
+                return;
+            };
+
+            parameter_metadata.taint_sink |= flags;
+        }
+    }
+}
+
 fn parse_assertions_from_tag(
     assertion_tag: &AssertTagValue<'_>,
     classname: Option<Word>,
@@ -13,7 +13,10 @@ use mago_syntax::cst::Program;
 use mago_text_edit::TextEdit;
 use mago_text_edit::TextRange;
 
+use crate::pragma::GUARD_EXPECT_CATEGORIES;
 use crate::pragma::Pragma;
+use crate::pragma::PragmaDate;
+use crate::pragma::PragmaError;
 use crate::pragma::PragmaKind;
 use crate::walk::attach_pragma_scopes;
 
@@ -37,7 +40,10 @@ fn check_non_pragma_line(line: &[u8]) -> bool {
     if without_marker.is_empty() {
         return false;
     }
-    if without_marker.starts_with(b"@mago-ignore") || without_marker.starts_with(b"@mago-expect") {
+    if without_marker.starts_with(b"@mago-ignore")
+        || without_marker.starts_with(b"@mago-expect")
+        || without_marker.starts_with(b"@mago-guard-expect")
+    {
         return false;
     }
     true
@@ -48,7 +54,8 @@ fn check_non_pragma_line(line: &[u8]) -> bool {
 /// It is responsible for:
 ///
 /// - Collecting issues reported by various tools.
-/// - Filtering issues based on configuration or suppression pragmas (`@mago-ignore`, `@mago-expect`).
+/// - Filtering issues based on configuration or suppression pragmas (`@mago-ignore`, `@mago-expect`,
+///   `@mago-guard-expect`).
 /// - Reporting unused or unfulfilled pragmas.
 #[derive(Debug)]
 pub struct Collector<'ctx, 'arena, A>
@@ -108,6 +115,8 @@ struct OwnedPragma {
     expected_matches: u16,
     matches: u16,
     description: String,
+    until: Option<PragmaDate>,
+    error: Option<PragmaError>,
 }
 
 impl<'ctx, 'arena, A> Collector<'ctx, 'arena, A>
@@ -239,6 +248,19 @@ where
     /// Reports an issue, returning `true` if it was added or `false` if it was suppressed.
     #[inline]
     pub fn report(&mut self, issue: Issue) -> bool {
+        self.report_categorized(None, issue)
+    }
+
+    /// Reports an issue with a specific code in a category, returning `true` if it was added.
+    ///
+    /// Pragmas apply as they do in [`report`](Self::report), except that category-specific
+    /// pragmas, such as `@mago-guard-expect perimeter`, only apply to issues of their category.
+    #[inline]
+    pub fn report_in_category(&mut self, category: &str, code: impl Into<String>, issue: Issue) -> bool {
+        self.report_categorized(Some(category), issue.with_code(code))
+    }
+
+    fn report_categorized(&mut self, category: Option<&str>, issue: Issue) -> bool {
         let primary_span = issue.annotations.iter().find(|ann| ann.kind.is_primary()).map(|ann| ann.span);
 
         if let Some(code) = issue.code.as_deref() {
@@ -271,11 +293,11 @@ where
             && let Some(code) = &issue.code
             && !self.is_recording()
         {
-            if self.is_ignored(span, code) {
+            if self.is_ignored(span, code, category) {
                 return false;
             }
 
-            if self.is_expected(span, code) {
+            if self.is_expected(span, code, category) {
                 return false;
             }
         }
@@ -397,6 +419,8 @@ where
     ///
     /// - Unfulfilled `@mago-expect` pragmas.
     /// - Unused pragmas of any kind.
+    /// - Used pragmas whose `until` date has passed.
+    /// - Pragmas that could not be parsed.
     ///
     /// Each issue includes a suggested `TextEdit` fix to remove the unused pragma text.
     #[inline]
@@ -404,6 +428,21 @@ where
     pub fn finish(mut self) -> IssueCollection {
         let mut issues = std::mem::take(&mut self.issues);
 
+        // Expired pragmas keep suppressing their issues, but are reported as errors in their place.
+        if self.pragmas.iter().any(|pragma| pragma.until.is_some()) {
+            let today = PragmaDate::today();
+            for pragma in self.pragmas.iter().filter(|pragma| pragma.matches > 0 && pragma.is_expired(today)) {
+                issues.push(expired_pragma_issue(pragma));
+            }
+        }
+
+        // Invalid pragmas never match an issue, so they are reported instead of being unfulfilled.
+        for pragma in self.pragmas.iter().filter(|pragma| pragma.error.is_some()) {
+            issues.push(invalid_pragma_issue(pragma));
+        }
+
+        self.pragmas.retain(|pragma| pragma.error.is_none());
+
         if self.skip_unfulfilled_expect {
             self.pragmas.clear();
 
@@ -659,8 +698,10 @@ where
     ///
     /// Finds the nearest applicable pragma and increments its match counter.
     #[inline]
-    fn is_ignored(&mut self, issue_span: Span, issue_code: &str) -> bool {
-        if let Some(pragma) = self.find_best_applicable_pragma_mut(issue_span, PragmaKind::Ignore, issue_code) {
+    fn is_ignored(&mut self, issue_span: Span, issue_code: &str, issue_category: Option<&str>) -> bool {
+        if let Some(pragma) =
+            self.find_best_applicable_pragma_mut(issue_span, PragmaKind::Ignore, issue_code, issue_category)
+        {
             pragma.matches = pragma.matches.saturating_add(1);
             return true;
         }
@@ -671,8 +712,10 @@ where
     ///
     /// Finds the nearest applicable pragma and increments its match counter.
     #[inline]
-    fn is_expected(&mut self, issue_span: Span, issue_code: &str) -> bool {
-        if let Some(pragma) = self.find_best_applicable_pragma_mut(issue_span, PragmaKind::Expect, issue_code) {
+    fn is_expected(&mut self, issue_span: Span, issue_code: &str, issue_category: Option<&str>) -> bool {
+        if let Some(pragma) =
+            self.find_best_applicable_pragma_mut(issue_span, PragmaKind::Expect, issue_code, issue_category)
+        {
             pragma.matches = pragma.matches.saturating_add(1);
             return true;
         }
@@ -690,6 +733,7 @@ where
         issue_span: Span,
         kind: PragmaKind,
         issue_code: &str,
+        issue_category: Option<&str>,
     ) -> Option<&mut Pragma<'arena>> {
         if self.pragmas.is_empty() {
             return None;
@@ -700,7 +744,11 @@ where
         let mut best_match_index = None;
 
         for (i, pragma) in self.pragmas.iter().enumerate() {
-            if pragma.kind != kind {
+            if pragma.kind != kind || pragma.error.is_some() {
+                continue;
+            }
+
+            if pragma.is_category_specific() && issue_category != Some(pragma.category) {
                 continue;
             }
 
@@ -748,6 +796,60 @@ where
     }
 }
 
+/// Builds the error reported in place of the issues suppressed by an expired pragma.
+fn expired_pragma_issue(pragma: &Pragma<'_>) -> Issue {
+    let Some(until) = pragma.until else {
+        unreachable!("only pragmas with an expiry date can expire");
+    };
+
+    let mut issue = Issue::error(format!("This pragma expired on {until}."))
+        .with_code("expired-pragma")
+        .with_annotation(Annotation::primary(pragma.span).with_message(format!(
+            "{} issue{} still expected here",
+            pragma.matches,
+            if pragma.matches == 1 { " is" } else { "s are" }
+        )))
+        .with_annotation(Annotation::secondary(pragma.trivia_span).with_message("...within this comment."));
+
+    if !pragma.description.is_empty() {
+        issue = issue.with_note(pragma.description);
+    }
+
+    issue.with_help("Fix the expected issues, or move the `until` date if the debt is still accepted.")
+}
+
+/// Builds the warning reported in place of a pragma that could not be parsed.
+fn invalid_pragma_issue(pragma: &Pragma<'_>) -> Issue {
+    let (message, help) = match pragma.error {
+        Some(PragmaError::UnknownCategory) => (
+            format!("Unknown category `{}` in this pragma.", pragma.category),
+            format!(
+                "Use one of {}.",
+                GUARD_EXPECT_CATEGORIES
+                    .iter()
+                    .map(|category| format!("`{category}`"))
+                    .collect::<std::vec::Vec<_>>()
+                    .join(", ")
+            ),
+        ),
+        Some(PragmaError::MissingCode) => (
+            format!("Missing code after `{}:` in this pragma.", pragma.category),
+            "Add a code after the colon, or remove the colon to expect every issue of the category.".to_string(),
+        ),
+        Some(PragmaError::InvalidDate) => (
+            "Invalid `until` date in this pragma.".to_string(),
+            "Write the date as `YYYY-MM-DD`, for example `until=2027-01-01`.".to_string(),
+        ),
+        None => unreachable!("only pragmas with an error are invalid"),
+    };
+
+    Issue::warning(message)
+        .with_code("invalid-pragma")
+        .with_annotation(Annotation::primary(pragma.span).with_message("This pragma does not suppress any issue"))
+        .with_annotation(Annotation::secondary(pragma.trivia_span).with_message("...within this comment."))
+        .with_help(help)
+}
+
 impl DeferredPragmas {
     /// Returns the file whose analysis pragmas are represented by this state.
     #[inline]
@@ -760,11 +862,30 @@ impl DeferredPragmas {
     /// another project-wide phase.
     #[must_use]
     pub fn reconcile(&mut self, file: &File, issues: IssueCollection) -> IssueCollection {
+        self.reconcile_categorized(file, None, issues)
+    }
+
+    /// Reconciles late diagnostics of a category, as reported by
+    /// [`Collector::report_in_category`], while retaining the updated pragma state.
+    #[must_use]
+    pub fn reconcile_in_category(&mut self, file: &File, category: &str, issues: IssueCollection) -> IssueCollection {
+        self.reconcile_categorized(file, Some(category), issues)
+    }
+
+    fn reconcile_categorized(
+        &mut self,
+        file: &File,
+        category: Option<&str>,
+        issues: IssueCollection,
+    ) -> IssueCollection {
         debug_assert_eq!(file.id, self.file_id, "deferred pragmas must be reconciled against their source file");
 
         let arena = LocalArena::new();
         let mut collector = self.clone().into_collector(&arena, file);
-        collector.extend(issues);
+        for issue in issues {
+            collector.report_categorized(category, issue);
+        }
+
         let (issues, state) = collector.defer();
         if let Some(state) = state {
             *self = state;
@@ -825,6 +946,8 @@ impl From<&Pragma<'_>> for OwnedPragma {
             expected_matches: pragma.expected_matches,
             matches: pragma.matches,
             description: pragma.description.to_owned(),
+            until: pragma.until,
+            error: pragma.error,
         }
     }
 }
@@ -846,6 +969,8 @@ impl OwnedPragma {
             expected_matches: self.expected_matches,
             matches: self.matches,
             description: arena.alloc_str(&self.description),
+            until: self.until,
+            error: self.error,
         }
     }
 }
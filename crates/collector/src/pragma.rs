@@ -1,6 +1,11 @@
+use std::fmt;
+use std::time::SystemTime;
+use std::time::UNIX_EPOCH;
+
 use mago_allocator::prelude::*;
 
 use mago_database::file::File;
+use mago_span::Position;
 use mago_span::Span;
 use mago_syntax::comments::comment_lines;
 use mago_syntax::cst::Trivia;
@@ -35,6 +40,103 @@ fn contains_ascii_whitespace(s: &[u8]) -> bool {
     s.iter().any(|b| b.is_ascii_whitespace())
 }
 
+const GUARD_EXPECT_PREFIX: &[u8] = b"@mago-guard-expect";
+
+/// The category of the architectural guard, whose collectors also extract `@mago-guard-expect` pragmas.
+pub const GUARD_CATEGORY: &str = "guard";
+
+/// The parts of the architectural guard a `@mago-guard-expect` pragma can expect issues from.
+///
+/// Unlike other categories, which only select the pragmas a collector extracts, these only
+/// match issues reported in the same category through [`Collector::report_in_category`].
+///
+/// [`Collector::report_in_category`]: crate::Collector::report_in_category
+pub const GUARD_EXPECT_CATEGORIES: &[&str] = &[GUARD_PERIMETER_CATEGORY, GUARD_STRUCTURAL_CATEGORY];
+
+/// The category of the issues reported by the perimeter guard, such as boundary breaches and dependency cycles.
+pub const GUARD_PERIMETER_CATEGORY: &str = "perimeter";
+
+/// The category of the issues reported by the structural guard.
+pub const GUARD_STRUCTURAL_CATEGORY: &str = "structural";
+
+/// A calendar date, such as the `until=2027-01-01` expiry of a `@mago-guard-expect` pragma.
+#[derive(Debug, Clone, Copy, Eq, PartialEq, Hash, PartialOrd, Ord)]
+pub struct PragmaDate {
+    pub year: u16,
+    pub month: u8,
+    pub day: u8,
+}
+
+impl PragmaDate {
+    /// Parses a `YYYY-MM-DD` date, returning `None` if it is malformed or does not exist.
+    #[must_use]
+    pub fn parse(value: &[u8]) -> Option<Self> {
+        let [y1, y2, y3, y4, b'-', m1, m2, b'-', d1, d2] = *value else {
+            return None;
+        };
+
+        let digits = [y1, y2, y3, y4, m1, m2, d1, d2];
+        if !digits.iter().all(u8::is_ascii_digit) {
+            return None;
+        }
+
+        let number = |digits: &[u8]| digits.iter().fold(0u16, |number, digit| number * 10 + u16::from(digit - b'0'));
+        let year = number(&digits[..4]);
+        let month = number(&digits[4..6]) as u8;
+        let day = number(&digits[6..]) as u8;
+
+        let is_leap_year = year % 4 == 0 && (year % 100 != 0 || year % 400 == 0);
+        let days_in_month = match month {
+            1 | 3 | 5 | 7 | 8 | 10 | 12 => 31,
+            4 | 6 | 9 | 11 => 30,
+            2 if is_leap_year => 29,
+            2 => 28,
+            _ => return None,
+        };
+
+        (1..=days_in_month).contains(&day).then_some(Self { year, month, day })
+    }
+
+    /// Returns the current date in UTC.
+    #[must_use]
+    pub fn today() -> Self {
+        let seconds = SystemTime::now().duration_since(UNIX_EPOCH).map_or(0, |duration| duration.as_secs());
+
+        // Converts days since the Unix epoch to a civil date, see
+        // https://howardhinnant.github.io/date_algorithms.html#civil_from_days
+        let days = seconds / 86_400 + 719_468;
+        let era = days / 146_097;
+        let day_of_era = days % 146_097;
+        let year_of_era = (day_of_era - day_of_era / 1_460 + day_of_era / 36_524 - day_of_era / 146_096) / 365;
+        let day_of_year = day_of_era - (365 * year_of_era + year_of_era / 4 - year_of_era / 100);
+        let shifted_month = (5 * day_of_year + 2) / 153;
+        let day = day_of_year - (153 * shifted_month + 2) / 5 + 1;
+        let month = if shifted_month < 10 { shifted_month + 3 } else { shifted_month - 9 };
+        let year = year_of_era + era * 400 + u64::from(month <= 2);
+
+        Self { year: year as u16, month: month as u8, day: day as u8 }
+    }
+}
+
+impl fmt::Display for PragmaDate {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{:04}-{:02}-{:02}", self.year, self.month, self.day)
+    }
+}
+
+/// Why a pragma could not be parsed.
+///
+/// Invalid pragmas never suppress an issue, and are reported in place of their issues.
+#[derive(Debug, Clone, Copy, Eq, PartialEq, Hash, PartialOrd, Ord)]
+pub enum PragmaError {
+    /// The category is not one of [`GUARD_EXPECT_CATEGORIES`].
+    UnknownCategory,
+    /// The category is followed by a colon, but no code.
+    MissingCode,
+    /// The `until=` argument is not a valid `YYYY-MM-DD` date.
+    InvalidDate,
+}
+
 /// Represents the kind of collector pragma.
 #[derive(Debug, Clone, Copy, Eq, PartialEq, Hash, PartialOrd, Ord)]
 #[repr(u8)]
@@ -77,6 +179,10 @@ pub struct Pragma<'arena> {
     pub matches: u16,
     /// An optional description explaining why this pragma is present.
     pub description: &'arena str,
+    /// The date after which the pragma expires, from the `until=` argument of `@mago-guard-expect`.
+    pub until: Option<PragmaDate>,
+    /// Why the pragma could not be parsed, if it is invalid.
+    pub error: Option<PragmaError>,
 }
 
 impl Pragma<'_> {
@@ -102,6 +208,21 @@ impl Pragma<'_> {
 
         self.matches >= self.expected_matches
     }
+
+    /// Returns `true` if this pragma has an expiry date that is before `today`.
+    #[inline]
+    #[must_use]
+    pub fn is_expired(&self, today: PragmaDate) -> bool {
+        self.until.is_some_and(|until| until < today)
+    }
+
+    /// Returns `true` if this pragma only matches issues reported in its own category, as
+    /// `@mago-guard-expect` pragmas do.
+    #[inline]
+    #[must_use]
+    pub fn is_category_specific(&self) -> bool {
+        GUARD_EXPECT_CATEGORIES.contains(&self.category)
+    }
 }
 
 impl PragmaKind {
@@ -186,6 +307,8 @@ where
             (PragmaKind::Ignore, b"@mago-ignore".as_slice())
         } else if trimmed.starts_with(b"@mago-expect") {
             (PragmaKind::Expect, b"@mago-expect".as_slice())
+        } else if trimmed.starts_with(GUARD_EXPECT_PREFIX) && categories.contains(&GUARD_CATEGORY) {
+            (PragmaKind::Expect, GUARD_EXPECT_PREFIX)
         } else {
             continue;
         };
@@ -193,6 +316,15 @@ where
         let content_with_leading_space = &trimmed[prefix.len()..];
         let content = content_with_leading_space.trim_ascii_start();
 
+        if prefix == GUARD_EXPECT_PREFIX {
+            let pragma_end_offset = pragma_start_offset + prefix.len() as u32 + content_with_leading_space.len() as u32;
+            let span = Span::new(file.id, pragma_start_offset, pragma_end_offset);
+
+            pragmas.extend(parse_guard_expect(arena, file, trivia, span, line, absolute_line_start, content));
+
+            continue;
+        }
+
         let Some((category_bytes, rest)) = split_once_byte(content, b':') else {
             // Handle `@mago-ignore all` / `@mago-expect all` without a category prefix.
             let (code_part, rest) = splitn_whitespace_2(content);
@@ -217,13 +349,7 @@ where
             let pragma_end_offset = pragma_start_offset + prefix.len() as u32 + content_with_leading_space.len() as u32;
             let span = Span::new(file.id, pragma_start_offset, pragma_end_offset);
 
-            let start_line = file.line_number(trivia.span.start.offset);
-            let end_line = file.line_number(trivia.span.end.offset);
-            let line_start_offset = file.get_line_start_offset(start_line).unwrap_or(0);
-            let line_end_offset = file.get_line_end_offset(end_line).unwrap_or(file.contents.len() as u32);
-            let prefix_text = &file.contents[line_start_offset as usize..trivia.span.start.offset as usize];
-            let postfix_text = &file.contents[trivia.span.end.offset as usize..line_end_offset as usize];
-            let own_line = prefix_text.trim_ascii().is_empty() && postfix_text.trim_ascii().is_empty();
+            let (start_line, end_line, own_line) = comment_placement(file, trivia);
 
             pragmas.push(Pragma {
                 kind,
@@ -240,6 +366,8 @@ where
                 code,
                 description,
                 scope_span: None,
+                until: None,
+                error: None,
             });
 
             continue;
@@ -317,13 +445,7 @@ where
             let pragma_end_offset = pragma_start_offset + prefix.len() as u32 + content_with_leading_space.len() as u32;
             let span = Span::new(file.id, pragma_start_offset, pragma_end_offset);
 
-            let start_line = file.line_number(trivia.span.start.offset);
-            let end_line = file.line_number(trivia.span.end.offset);
-            let line_start_offset = file.get_line_start_offset(start_line).unwrap_or(0);
-            let line_end_offset = file.get_line_end_offset(end_line).unwrap_or(file.contents.len() as u32);
-            let prefix_text = &file.contents[line_start_offset as usize..trivia.span.start.offset as usize];
-            let postfix_text = &file.contents[trivia.span.end.offset as usize..line_end_offset as usize];
-            let own_line = prefix_text.trim_ascii().is_empty() && postfix_text.trim_ascii().is_empty();
+            let (start_line, end_line, own_line) = comment_placement(file, trivia);
 
             pragmas.push(Pragma {
                 kind,
@@ -340,6 +462,8 @@ where
                 code: base_code,
                 description,
                 scope_span: None,
+                until: None,
+                error: None,
             });
 
             if chunk_end == codes_part.len() {
@@ -352,6 +476,103 @@ where
     pragmas
 }
 
+/// Parses a `@mago-guard-expect <perimeter|structural>[:<code>] [until=YYYY-MM-DD] [description]`
+/// pragma, which expects every issue of its part of the guard when no code is given.
+///
+/// An unknown category, a missing code, or a malformed expiry date make the pragma invalid; it
+/// is still returned so the collector can report it. Returns `None` only for non-UTF-8 content.
+fn parse_guard_expect<'arena, A>(
+    arena: &'arena A,
+    file: &File,
+    trivia: &Trivia<'arena>,
+    span: Span,
+    line: &[u8],
+    absolute_line_start: Position,
+    content: &[u8],
+) -> Option<Pragma<'arena>>
+where
+    A: Arena,
+{
+    let (specification, rest) = splitn_whitespace_2(content);
+    let (category_bytes, code_bytes) = match split_once_byte(specification, b':') {
+        Some((category_bytes, code_bytes)) => (category_bytes, Some(code_bytes)),
+        None => (specification, None),
+    };
+
+    let mut error = None;
+    let category = match GUARD_EXPECT_CATEGORIES.iter().copied().find(|category| category.as_bytes() == category_bytes)
+    {
+        Some(category) => category,
+        None => {
+            error = Some(PragmaError::UnknownCategory);
+
+            alloc_utf8(arena, category_bytes)?
+        }
+    };
+
+    let code = match code_bytes {
+        None => "all",
+        Some([]) => {
+            error = error.or(Some(PragmaError::MissingCode));
+
+            "all"
+        }
+        Some(code_bytes) => alloc_utf8(arena, code_bytes)?,
+    };
+
+    let rest = rest.trim_ascii_start();
+    let (until, description_bytes) = match rest.strip_prefix(b"until=") {
+        Some(value) => {
+            let (date, description_bytes) = splitn_whitespace_2(value);
+            let until = PragmaDate::parse(date);
+            if until.is_none() {
+                error = error.or(Some(PragmaError::InvalidDate));
+            }
+
+            (until, description_bytes)
+        }
+        None => (None, rest),
+    };
+
+    let description = alloc_utf8(arena, description_bytes.trim_ascii())?;
+
+    let specification_start = absolute_line_start + (specification.as_ptr() as usize - line.as_ptr() as usize) as u32;
+    let code_span = Span::new(file.id, specification_start, specification_start + specification.len() as u32);
+    let (start_line, end_line, own_line) = comment_placement(file, trivia);
+
+    Some(Pragma {
+        kind: PragmaKind::Expect,
+        span,
+        trivia_span: trivia.span,
+        code_span,
+        count_span: None,
+        expected_matches: 1,
+        matches: 0,
+        start_line,
+        end_line,
+        own_line,
+        category,
+        code,
+        description,
+        scope_span: None,
+        until,
+        error,
+    })
+}
+
+/// Returns the first and last line of a comment, and whether it is the only thing on those lines.
+fn comment_placement(file: &File, trivia: &Trivia<'_>) -> (u32, u32, bool) {
+    let start_line = file.line_number(trivia.span.start.offset);
+    let end_line = file.line_number(trivia.span.end.offset);
+    let line_start_offset = file.get_line_start_offset(start_line).unwrap_or(0);
+    let line_end_offset = file.get_line_end_offset(end_line).unwrap_or(file.contents.len() as u32);
+    let prefix_text = &file.contents[line_start_offset as usize..trivia.span.start.offset as usize];
+    let postfix_text = &file.contents[trivia.span.end.offset as usize..line_end_offset as usize];
+    let own_line = prefix_text.trim_ascii().is_empty() && postfix_text.trim_ascii().is_empty();
+
+    (start_line, end_line, own_line)
+}
+
 /// Parses the optional `(N)` count suffix from a pragma code.
 ///
 /// Returns `Some((base_code, count, count_start_offset_in_code))` when the code ends with a
@@ -47,6 +47,21 @@ pub enum FileType {
     External,
 }
 
+/// The language of a file's contents, determined by its extension.
+///
+/// Files with the `.twig` extension are Twig templates; every other file is treated as PHP,
+/// whatever extension it was loaded with.
+#[derive(Debug, Clone, Copy, Eq, PartialEq, Hash, PartialOrd, Ord)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[repr(u8)]
+pub enum FileLanguage {
+    /// PHP source code.
+    Php,
+
+    /// A Twig template.
+    Twig,
+}
+
 /// A file that's either stored on the host system's file system or in the vendored file system.
 ///
 /// This struct encapsulates all the necessary information about a file, including its content,
@@ -135,6 +150,13 @@ impl File {
         Self::new(name, FileType::Host, None, contents)
     }
 
+    /// Returns the language of the file's contents, based on the extension of its name.
+    #[inline]
+    #[must_use]
+    pub fn language(&self) -> FileLanguage {
+        FileLanguage::from_file_name(&self.name)
+    }
+
     /// Retrieve the line number for the given byte offset.
     ///
     /// # Parameters
@@ -237,6 +259,39 @@ impl FileType {
     }
 }
 
+impl FileLanguage {
+    /// Returns the language of a file with the given name or path.
+    #[inline]
+    #[must_use]
+    pub fn from_file_name(name: &[u8]) -> Self {
+        match name.iter().rposition(|byte| *byte == b'.') {
+            Some(position) => Self::from_extension(&name[position + 1..]),
+            None => FileLanguage::Php,
+        }
+    }
+
+    /// Returns the language of files with the given extension, without the leading dot.
+    #[inline]
+    #[must_use]
+    pub fn from_extension(extension: &[u8]) -> Self {
+        if extension.eq_ignore_ascii_case(b"twig") { FileLanguage::Twig } else { FileLanguage::Php }
+    }
+
+    /// Returns `true` if the file contains PHP source code.
+    #[inline]
+    #[must_use]
+    pub const fn is_php(self) -> bool {
+        matches!(self, FileLanguage::Php)
+    }
+
+    /// Returns `true` if the file is a Twig template.
+    #[inline]
+    #[must_use]
+    pub const fn is_twig(self) -> bool {
+        matches!(self, FileLanguage::Twig)
+    }
+}
+
 impl FileId {
     #[inline]
     #[must_use]
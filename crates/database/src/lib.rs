@@ -326,6 +326,13 @@ impl<'config> Database<'config> {
         if let Some(name) = self.id_to_name.remove(&id) { self.files.remove(&name).is_some() } else { false }
     }
 
+    /// Returns the configuration the database was loaded with.
+    #[inline]
+    #[must_use]
+    pub fn configuration(&self) -> &DatabaseConfiguration<'config> {
+        &self.configuration
+    }
+
     /// Commits a [`ChangeLog`], applying all its recorded operations to the database
     /// and optionally writing them to the filesystem.
     ///
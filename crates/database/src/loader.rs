@@ -677,6 +677,7 @@ mod tests {
     use super::*;
     use crate::DatabaseReader;
     use crate::GlobSettings;
+    use crate::file::FileLanguage;
     use std::borrow::Cow;
     use tempfile::TempDir;
 
@@ -735,6 +736,28 @@ mod tests {
         assert_eq!(a_file.file_type, FileType::Vendored, "src/a.php should be Vendored");
     }
 
+    #[test]
+    fn test_twig_templates_are_loaded_when_their_extension_is_configured() {
+        let temp_dir = TempDir::new().unwrap();
+
+        create_test_file(&temp_dir, "src/a.php", "<?php");
+        create_test_file(&temp_dir, "templates/base.html.twig", "{{ title }}");
+
+        let mut config = create_test_config(&temp_dir, vec!["src/", "templates/"], vec![]);
+        let db = DatabaseLoader::new(config.clone()).load().unwrap();
+        assert!(db.files().all(|f| f.language() == FileLanguage::Php), "templates are only loaded on request");
+
+        config.extensions.push(Cow::Borrowed(b"twig"));
+        let db = DatabaseLoader::new(config).load().unwrap();
+
+        let template = db.files().find(|f| name_str(&f.name).contains("base.html.twig")).unwrap();
+        assert_eq!(template.language(), FileLanguage::Twig);
+        assert_eq!(template.file_type, FileType::Host);
+
+        let source = db.files().find(|f| name_str(&f.name).contains("a.php")).unwrap();
+        assert_eq!(source.language(), FileLanguage::Php);
+    }
+
     #[test]
     fn test_deeper_vs_shallower_directory() {
         let temp_dir = TempDir::new().unwrap();
@@ -0,0 +1,40 @@
+//! Error types for the formatter crate.
+
+use mago_syntax::error::ParseError;
+
+/// Errors that can occur while formatting only part of a file.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum RangeFormatError {
+    /// The file's content contains syntax errors.
+    ParseError(ParseError),
+    /// The range could not be formatted without touching code outside of it, because
+    /// formatting the file changes the statements around the range (e.g. by splitting or
+    /// sorting `use` statements).
+    RangeNotIsolated,
+}
+
+impl std::fmt::Display for RangeFormatError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::ParseError(error) => write!(f, "{error}"),
+            Self::RangeNotIsolated => {
+                write!(f, "the range cannot be formatted on its own, as formatting changes the statements around it")
+            }
+        }
+    }
+}
+
+impl std::error::Error for RangeFormatError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            Self::ParseError(error) => Some(error),
+            Self::RangeNotIsolated => None,
+        }
+    }
+}
+
+impl From<ParseError> for RangeFormatError {
+    fn from(error: ParseError) -> Self {
+        Self::ParseError(error)
+    }
+}
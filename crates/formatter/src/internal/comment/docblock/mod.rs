@@ -0,0 +1,667 @@
+use mago_allocator::Arena;
+use mago_allocator::CollectIn;
+use mago_allocator::vec::Vec;
+use mago_allocator::vec_in;
+
+use mago_phpdoc_syntax::PHPDocParser;
+use mago_phpdoc_syntax::cst::Element;
+use mago_phpdoc_syntax::cst::Tag;
+use mago_phpdoc_syntax::cst::TagValue;
+use mago_phpdoc_syntax::cst::Text;
+use mago_phpdoc_syntax::cst::Variable;
+use mago_phpdoc_syntax::cst::r#type::Type;
+use mago_span::HasSpan;
+use mago_span::Position;
+use mago_span::Span;
+
+use crate::document::Document;
+use crate::document::Group;
+use crate::document::IfBreak;
+use crate::document::Line;
+use crate::internal::FormatterState;
+use crate::internal::comment::Comment;
+use crate::internal::comment::docblock::types::flat_width;
+use crate::internal::utils::string_width;
+
+mod types;
+
+/// A line of a docblock with its ` * ` prefix removed.
+#[derive(Debug, Clone, Copy)]
+struct DocblockLine<'arena> {
+    /// The source offset of the first byte of `text`.
+    offset: u32,
+    text: &'arena [u8],
+}
+
+#[derive(Debug, Clone, Copy)]
+enum BlockKind<'arena> {
+    Text,
+    Code,
+    Tag(&'arena Tag<'arena>),
+}
+
+/// A run of consecutive docblock lines belonging to the same element.
+#[derive(Debug, Clone, Copy)]
+struct Block<'arena> {
+    kind: BlockKind<'arena>,
+    start: usize,
+    end: usize,
+    blank_before: bool,
+}
+
+/// The structured parts of a tag such as `@param int $value The value.`.
+struct TagParts<'arena, A>
+where
+    A: Arena,
+{
+    name: &'arena [u8],
+    r#type: Option<(Document<'arena, A>, usize)>,
+    variable: Option<&'arena [u8]>,
+    description: Option<&'arena Text<'arena>>,
+    /// The source offset where the part preceding the description ends.
+    head_end: u32,
+}
+
+/// The column widths shared by a run of aligned `@param` tags.
+#[derive(Debug, Clone, Copy)]
+struct Columns {
+    name: usize,
+    r#type: usize,
+    variable: usize,
+}
+
+/// Collects the printed lines of a docblock, collapsing runs of blank lines and
+/// dropping leading and trailing ones.
+struct DocblockPrinter<'arena, A>
+where
+    A: Arena,
+{
+    parts: Vec<'arena, Document<'arena, A>, A>,
+    has_lines: bool,
+    pending_blank: bool,
+}
+
+impl<'arena, A> DocblockPrinter<'arena, A>
+where
+    A: Arena,
+{
+    fn blank(&mut self) {
+        self.pending_blank = self.has_lines;
+    }
+
+    /// Prints an empty line as written, as inside a code block.
+    fn empty(&mut self) {
+        self.flush();
+        self.parts.push(Document::Line(Line::hard()));
+        self.parts.push(Document::String(b" *"));
+    }
+
+    fn line(&mut self, content: Document<'arena, A>) {
+        self.flush();
+        self.parts.push(Document::Line(Line::hard()));
+        self.parts.push(Document::String(b" * "));
+        self.parts.push(content);
+    }
+
+    fn flush(&mut self) {
+        if self.pending_blank {
+            self.parts.push(Document::Line(Line::hard()));
+            self.parts.push(Document::String(b" *"));
+            self.pending_blank = false;
+        }
+
+        self.has_lines = true;
+    }
+}
+
+impl<'arena, A> FormatterState<'_, 'arena, A>
+where
+    A: Arena,
+{
+    /// Prints a multi-line docblock from its parsed structure.
+    ///
+    /// Returns `None` if the docblock can not be parsed, in which case it is printed
+    /// like any other block comment.
+    pub(crate) fn print_docblock(&self, comment: Comment) -> Option<Document<'arena, A>> {
+        let content = &self.source_text[comment.start as usize..comment.end as usize];
+        if content.len() < 5 || !content.starts_with(b"/**") || !content.ends_with(b"*/") {
+            return None;
+        }
+
+        let span = Span::new(self.file.id, Position::new(comment.start), Position::new(comment.end));
+        let document = PHPDocParser::parse_with_span(self.arena, content, span);
+        if document.has_errors() {
+            return None;
+        }
+
+        let lines = self.docblock_lines(comment.start + 3, comment.end - 2);
+        let mut blocks = self.docblock_blocks(document.elements.nodes, &lines);
+        if self.settings.docblock_sort_tags {
+            self.sort_docblock_tags(&mut blocks);
+        }
+
+        let width = self.docblock_width(comment.start);
+        let mut tags = Vec::with_capacity_in(blocks.len(), self.arena);
+        for block in &blocks {
+            tags.push(match block.kind {
+                BlockKind::Tag(tag) => self.docblock_tag_parts(tag, &lines[block.start..block.end]),
+                _ => None,
+            });
+        }
+
+        let columns = self.docblock_columns(&blocks, &tags, width);
+        let mut printer = DocblockPrinter {
+            parts: vec_in![self.arena; Document::String(b"/**")],
+            has_lines: false,
+            pending_blank: false,
+        };
+        for ((block, parts), columns) in blocks.iter().zip(tags).zip(columns) {
+            if block.blank_before {
+                printer.blank();
+            }
+
+            let block_lines = &lines[block.start..block.end];
+            match (block.kind, parts) {
+                (BlockKind::Text, _) => {
+                    for line in block_lines {
+                        if line.text.is_empty() {
+                            printer.blank();
+                        } else {
+                            printer.line(self.print_docblock_prose(line.text));
+                        }
+                    }
+                }
+                (BlockKind::Tag(_), Some(parts)) => {
+                    self.print_docblock_tag(&mut printer, parts, block_lines, columns, width);
+                }
+                (BlockKind::Code, _) => {
+                    for line in block_lines {
+                        if line.text.is_empty() {
+                            printer.empty();
+                        } else {
+                            printer.line(Document::String(line.text));
+                        }
+                    }
+                }
+                (BlockKind::Tag(_), None) => {
+                    for line in block_lines {
+                        if line.text.is_empty() {
+                            printer.blank();
+                        } else {
+                            printer.line(Document::String(line.text));
+                        }
+                    }
+                }
+            }
+        }
+
+        let mut parts = printer.parts;
+        parts.push(Document::Line(Line::hard()));
+        parts.push(Document::String(b" */"));
+
+        Some(Document::Array(parts))
+    }
+
+    /// Splits the body of a docblock, between `/**` and `*/`, into lines without their
+    /// leading ` * ` and trailing whitespace.
+    fn docblock_lines(&self, start: u32, end: u32) -> Vec<'arena, DocblockLine<'arena>, A> {
+        let mut lines = Vec::new_in(self.arena);
+        let mut line_start = start as usize;
+        let end = end as usize;
+
+        loop {
+            let line_end =
+                self.source_text[line_start..end].iter().position(|&b| b == b'\n').map_or(end, |i| line_start + i);
+
+            let mut offset = line_start;
+            while offset < line_end && matches!(self.source_text[offset], b' ' | b'\t') {
+                offset += 1;
+            }
+
+            if line_start != start as usize && offset < line_end && self.source_text[offset] == b'*' {
+                offset += 1;
+                if offset < line_end && self.source_text[offset] == b' ' {
+                    offset += 1;
+                }
+            }
+
+            lines.push(DocblockLine {
+                offset: offset as u32,
+                text: self.source_text[offset..line_end].trim_ascii_end(),
+            });
+
+            if line_end >= end {
+                break;
+            }
+
+            line_start = line_end + 1;
+        }
+
+        lines
+    }
+
+    /// Groups docblock lines into blocks, one per parsed element.
+    ///
+    /// Blank lines inside an element stay in its block; blank lines between
+    /// elements are recorded on the block that follows them.
+    fn docblock_blocks(
+        &self,
+        elements: &'arena [Element<'arena>],
+        lines: &[DocblockLine<'arena>],
+    ) -> Vec<'arena, Block<'arena>, A> {
+        let mut blocks: Vec<'arena, Block<'arena>, A> = Vec::new_in(self.arena);
+        let mut current = None;
+        let mut blank = false;
+        let mut index = 0;
+
+        for (line_index, line) in lines.iter().enumerate() {
+            let position = line.offset + (line.text.len() - line.text.trim_ascii_start().len()) as u32;
+            while index < elements.len() && elements[index].span().end.offset <= position {
+                index += 1;
+            }
+
+            let element = elements.get(index).filter(|element| element.span().start.offset <= position);
+            let element_index = element.map(|_| index);
+
+            if line.text.is_empty() {
+                if element_index.is_some()
+                    && element_index == current
+                    && let Some(block) = blocks.last_mut()
+                {
+                    block.end = line_index + 1;
+                } else {
+                    blank = true;
+                }
+
+                continue;
+            }
+
+            if let Some(block) = blocks.last_mut()
+                && !blank
+                && current == element_index
+                && (element_index.is_some() || matches!(block.kind, BlockKind::Text))
+            {
+                block.end = line_index + 1;
+
+                continue;
+            }
+
+            let kind = match element {
+                Some(Element::Tag(tag)) => BlockKind::Tag(tag),
+                Some(Element::Code(_)) => BlockKind::Code,
+                _ => BlockKind::Text,
+            };
+
+            blocks.push(Block { kind, start: line_index, end: line_index + 1, blank_before: blank });
+            blank = false;
+            current = element_index;
+        }
+
+        blocks
+    }
+
+    /// Orders `@template`, `@param`, `@return` and `@throws` tags, keeping every other
+    /// tag in place.
+    ///
+    /// Tags are only reordered when nothing but tags follows the first one.
+    fn sort_docblock_tags(&self, blocks: &mut [Block<'arena>]) {
+        let Some(first_tag) = blocks.iter().position(|block| matches!(block.kind, BlockKind::Tag(_))) else {
+            return;
+        };
+
+        if blocks[first_tag..].iter().any(|block| !matches!(block.kind, BlockKind::Tag(_))) {
+            return;
+        }
+
+        let mut slots = Vec::new_in(self.arena);
+        for (index, block) in blocks.iter().enumerate().skip(first_tag) {
+            if tag_rank(block).is_some() {
+                slots.push(index);
+            }
+        }
+
+        let mut order = slots.clone();
+        order.sort_by_key(|&index| tag_rank(&blocks[index]));
+        if order == slots {
+            return;
+        }
+
+        let separated = slots.iter().skip(1).any(|&index| blocks[index].blank_before);
+        let original = blocks.iter().copied().collect_in::<Vec<'arena, _, A>>(self.arena);
+        let mut previous_rank = None;
+        for (position, (&slot, &source)) in slots.iter().zip(order.iter()).enumerate() {
+            let rank = tag_rank(&original[source]);
+            let blank_before =
+                if position == 0 { original[slot].blank_before } else { separated && rank != previous_rank };
+
+            blocks[slot] = Block { blank_before, ..original[source] };
+            previous_rank = rank;
+        }
+    }
+
+    fn docblock_tag_parts(
+        &self,
+        tag: &'arena Tag<'arena>,
+        lines: &[DocblockLine<'arena>],
+    ) -> Option<TagParts<'arena, A>> {
+        let (r#type, variable, description): (Option<&'arena Type<'arena>>, _, _) = match &tag.value {
+            TagValue::Param(value) => (
+                Some(value.r#type),
+                value.parameter.as_ref().map(|parameter| (value.ampersand, value.ellipsis, parameter)),
+                value.description.as_ref(),
+            ),
+            TagValue::TypelessParam(value) => {
+                (None, Some((value.ampersand, value.ellipsis, &value.parameter)), value.description.as_ref())
+            }
+            TagValue::ParamOut(value) => {
+                (Some(value.r#type), Some((None, None, &value.parameter)), value.description.as_ref())
+            }
+            TagValue::Return(value) | TagValue::RealReturn(value) => {
+                (Some(value.r#type), None, value.description.as_ref())
+            }
+            TagValue::Var(value) => (
+                Some(value.r#type),
+                value.variable.as_ref().map(|variable| (None, None, variable)),
+                value.description.as_ref(),
+            ),
+            TagValue::Throws(value) => (Some(value.r#type), None, value.description.as_ref()),
+            _ => return None,
+        };
+
+        let mut head_end = tag.name.span.end.offset;
+        let r#type = match r#type {
+            Some(r#type) => {
+                let document = self.print_docblock_type(r#type, 0)?;
+                let width = flat_width(&document);
+                head_end = r#type.span().end.offset;
+
+                Some((document, width))
+            }
+            None => None,
+        };
+
+        let variable = variable.map(|(ampersand, ellipsis, variable): (Option<Span>, Option<Span>, &Variable)| {
+            head_end = variable.span.end.offset;
+
+            let mut buffer = Vec::with_capacity_in(variable.value.len() + 4, self.arena);
+            if ampersand.is_some() {
+                buffer.push(b'&');
+            }
+
+            if ellipsis.is_some() {
+                buffer.extend_from_slice(b"...");
+            }
+
+            buffer.extend_from_slice(variable.value);
+            &*buffer.leak()
+        });
+
+        // The tag must end on the line holding its last structured part, unless a
+        // description follows; anything else is kept as written.
+        let head_line = docblock_line_index(lines, head_end)?;
+        if description.is_none() && head_line + 1 != lines.len() {
+            return None;
+        }
+
+        Some(TagParts {
+            name: &self.source_text[tag.at.start.offset as usize..tag.name.span.end.offset as usize],
+            r#type,
+            variable,
+            description,
+            head_end,
+        })
+    }
+
+    /// Computes the shared column widths of consecutive `@param` tags.
+    fn docblock_columns(
+        &self,
+        blocks: &[Block<'arena>],
+        tags: &[Option<TagParts<'arena, A>>],
+        width: usize,
+    ) -> Vec<'arena, Option<Columns>, A> {
+        let mut columns = Vec::with_capacity_in(blocks.len(), self.arena);
+        columns.resize(blocks.len(), None);
+        if !self.settings.docblock_align_tags {
+            return columns;
+        }
+
+        let is_alignable = |index: usize| {
+            let BlockKind::Tag(tag) = blocks[index].kind else {
+                return false;
+            };
+
+            if !matches!(tag.value, TagValue::Param(_) | TagValue::TypelessParam(_)) {
+                return false;
+            }
+
+            tags[index].as_ref().is_some_and(|parts| {
+                parts.variable.is_some_and(|variable| {
+                    let type_width = parts.r#type.as_ref().map_or(0, |(_, width)| *width);
+
+                    string_width(parts.name) + type_width + string_width(variable) + 2 <= width
+                })
+            })
+        };
+
+        let mut index = 0;
+        while index < blocks.len() {
+            if !is_alignable(index) {
+                index += 1;
+                continue;
+            }
+
+            let mut end = index + 1;
+            while end < blocks.len() && !blocks[end].blank_before && is_alignable(end) {
+                end += 1;
+            }
+
+            if end - index > 1 {
+                let mut run = Columns { name: 0, r#type: 0, variable: 0 };
+                for parts in tags[index..end].iter().flatten() {
+                    run.name = run.name.max(string_width(parts.name));
+                    run.r#type = run.r#type.max(parts.r#type.as_ref().map_or(0, |(_, width)| *width));
+                    run.variable = run.variable.max(parts.variable.map_or(0, string_width));
+                }
+
+                for column in &mut columns[index..end] {
+                    *column = Some(run);
+                }
+            }
+
+            index = end;
+        }
+
+        columns
+    }
+
+    fn print_docblock_tag(
+        &self,
+        printer: &mut DocblockPrinter<'arena, A>,
+        parts: TagParts<'arena, A>,
+        lines: &[DocblockLine<'arena>],
+        columns: Option<Columns>,
+        width: usize,
+    ) {
+        let name_width = string_width(parts.name);
+        let mut head = vec_in![self.arena; Document::String(parts.name)];
+        let mut padding = columns.map_or(0, |columns| columns.name - name_width);
+        if let Some((r#type, type_width)) = parts.r#type {
+            head.push(Document::String(self.docblock_padding(padding + 1)));
+            head.push(r#type);
+            padding = columns.map_or(0, |columns| columns.r#type - type_width);
+        } else if let Some(columns) = columns
+            && columns.r#type > 0
+        {
+            padding += columns.r#type + 1;
+        }
+
+        if let Some(variable) = parts.variable {
+            head.push(Document::String(self.docblock_padding(padding + 1)));
+            head.push(Document::String(variable));
+            padding = columns.map_or(0, |columns| columns.variable - string_width(variable));
+        }
+
+        let head_line = docblock_line_index(lines, parts.head_end).unwrap_or(0);
+        let Some(description) = parts.description else {
+            printer.line(Document::Array(head));
+
+            return;
+        };
+
+        let description_start = description.span.start.offset;
+        let description_line = docblock_line_index(lines, description_start).unwrap_or(head_line);
+        let mut rest = &lines[description_line..];
+
+        // A description starting on the tag's line continues at the description column;
+        // one starting on a later line keeps its own indentation.
+        let mut hang = None;
+        if description_line == head_line {
+            let line = &lines[description_line];
+            let text = &self.source_text[description_start as usize..line.offset as usize + line.text.len()];
+            let original_column = (description_start - line.offset) as usize;
+
+            let mut column = head.iter().map(flat_width).sum::<usize>() + padding + 1;
+            if column > width {
+                column = name_width + 1;
+            }
+
+            head.push(Document::String(self.docblock_padding(padding + 1)));
+            head.push(self.print_docblock_words(text, column));
+            hang = Some((column, original_column));
+            rest = &rest[1..];
+        }
+
+        printer.line(Document::Array(head));
+        for line in rest {
+            if line.text.is_empty() {
+                printer.blank();
+
+                continue;
+            }
+
+            let text = line.text.trim_ascii_start();
+            let indentation = line.text.len() - text.len();
+            let indentation = match hang {
+                Some((column, original_column)) => column + indentation.saturating_sub(original_column),
+                None => indentation,
+            };
+
+            printer.line(Document::Array(vec_in![
+                self.arena;
+                Document::String(self.docblock_padding(indentation)),
+                self.print_docblock_words(text, indentation),
+            ]));
+        }
+    }
+
+    /// Prints a line of free text, wrapping it at the print width with continuation
+    /// lines indented to match the line, or the content of a list item.
+    fn print_docblock_prose(&self, text: &'arena [u8]) -> Document<'arena, A> {
+        let content = text.trim_ascii_start();
+        let indentation = text.len() - content.len();
+
+        let marker = if let Some(rest) = content.strip_prefix(b"- ").or_else(|| content.strip_prefix(b"* ")) {
+            content.len() - rest.len()
+        } else {
+            let digits = content.iter().take_while(|b| b.is_ascii_digit()).count();
+            if digits > 0 && content[digits..].starts_with(b". ") { digits + 2 } else { 0 }
+        };
+
+        self.print_docblock_words(text, indentation + marker)
+    }
+
+    /// Prints a run of words that wraps at the print width, continuing on new docblock
+    /// lines indented by `hang` columns.
+    ///
+    /// Text containing tabs or repeated spaces is assumed to be laid out by hand and is
+    /// printed as written, as are words inside backticks or braces.
+    fn print_docblock_words(&self, text: &'arena [u8], hang: usize) -> Document<'arena, A> {
+        let content = text.trim_ascii_start();
+        if !self.settings.docblock_wrap_descriptions
+            || content.contains(&b'\t')
+            || content.windows(2).any(|w| w == b"  ")
+        {
+            return Document::String(text);
+        }
+
+        let mut words = Vec::new_in(self.arena);
+        let mut word_start = 0;
+        let mut in_code = false;
+        let mut braces = 0usize;
+        for (index, &byte) in text.iter().enumerate().skip(text.len() - content.len()) {
+            match byte {
+                b'`' => in_code = !in_code,
+                b'{' if !in_code => braces += 1,
+                b'}' if !in_code => braces = braces.saturating_sub(1),
+                b' ' if !in_code && braces == 0 => {
+                    words.push(&text[word_start..index]);
+                    word_start = index + 1;
+                }
+                _ => {}
+            }
+        }
+
+        words.push(&text[word_start..]);
+        if words.len() < 2 {
+            return Document::String(text);
+        }
+
+        let mut prefix = Vec::with_capacity_in(3 + hang, self.arena);
+        prefix.extend_from_slice(b" * ");
+        prefix.resize(3 + hang, b' ');
+        let prefix: &'arena [u8] = prefix.leak();
+
+        let mut parts = vec_in![self.arena; Document::String(words[0])];
+        for word in &words[1..] {
+            parts.push(Document::Group(Group::new(vec_in![
+                self.arena;
+                Document::IfBreak(IfBreak::new(
+                    self.arena,
+                    Document::Array(vec_in![self.arena; Document::Line(Line::hard()), Document::String(prefix)]),
+                    Document::String(b" "),
+                )),
+                Document::String(word),
+            ])));
+        }
+
+        Document::Array(parts)
+    }
+
+    fn docblock_padding(&self, width: usize) -> &'arena [u8] {
+        match width {
+            1 => b" ",
+            _ => crate::internal::utils::spaces(self.arena, width),
+        }
+    }
+
+    /// The width available to the content of a docblock line, after its ` * ` prefix.
+    fn docblock_width(&self, start: u32) -> usize {
+        let line_start =
+            self.source_text[..start as usize].iter().rposition(|&b| b == b'\n').map_or(0, |index| index + 1);
+
+        let column = string_width(&self.source_text[line_start..start as usize]);
+
+        self.settings.print_width.saturating_sub(column + 3)
+    }
+}
+
+/// The position of `tag` in the normalised order, if it has one.
+fn tag_rank(block: &Block<'_>) -> Option<u8> {
+    let BlockKind::Tag(tag) = block.kind else {
+        return None;
+    };
+
+    match tag.value {
+        TagValue::Template(_) => Some(0),
+        TagValue::Param(_) | TagValue::TypelessParam(_) => Some(1),
+        TagValue::Return(_) => Some(2),
+        TagValue::Throws(_) => Some(3),
+        _ => None,
+    }
+}
+
+/// The index of the line containing the source offset, treating the end of a line as
+/// part of it.
+fn docblock_line_index(lines: &[DocblockLine<'_>], offset: u32) -> Option<usize> {
+    lines.iter().rposition(|line| line.offset <= offset && offset <= line.offset + line.text.len() as u32)
+}
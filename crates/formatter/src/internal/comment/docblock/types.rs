@@ -0,0 +1,227 @@
+use mago_allocator::Arena;
+use mago_allocator::vec::Vec;
+use mago_allocator::vec_in;
+
+use mago_phpdoc_syntax::cst::r#type::ConditionalType;
+use mago_phpdoc_syntax::cst::r#type::GenericParameters;
+use mago_phpdoc_syntax::cst::r#type::ShapeType;
+use mago_phpdoc_syntax::cst::r#type::Type;
+use mago_span::HasSpan;
+use mago_span::Span;
+
+use crate::document::Document;
+use crate::document::Group;
+use crate::document::IfBreak;
+use crate::document::Line;
+use crate::internal::FormatterState;
+use crate::internal::utils::string_width;
+
+impl<'arena, A> FormatterState<'_, 'arena, A>
+where
+    A: Arena,
+{
+    /// Prints a docblock type, breaking array shapes and conditional types over multiple
+    /// lines when they do not fit.
+    ///
+    /// Returns `None` if the type spans multiple lines in a way that can not be rebuilt
+    /// from its structure, in which case the tag is kept as written.
+    pub(super) fn print_docblock_type(
+        &self,
+        r#type: &'arena Type<'arena>,
+        depth: usize,
+    ) -> Option<Document<'arena, A>> {
+        Some(match r#type {
+            Type::Parenthesized(parenthesized) => Document::Array(vec_in![
+                self.arena;
+                Document::String(b"("),
+                self.print_docblock_type(parenthesized.inner, depth)?,
+                Document::String(b")"),
+            ]),
+            Type::Union(union) => Document::Array(vec_in![
+                self.arena;
+                self.print_docblock_type(union.left, depth)?,
+                Document::String(b"|"),
+                self.print_docblock_type(union.right, depth)?,
+            ]),
+            Type::Intersection(intersection) => Document::Array(vec_in![
+                self.arena;
+                self.print_docblock_type(intersection.left, depth)?,
+                Document::String(b"&"),
+                self.print_docblock_type(intersection.right, depth)?,
+            ]),
+            Type::Nullable(nullable) => Document::Array(vec_in![
+                self.arena;
+                Document::String(b"?"),
+                self.print_docblock_type(nullable.inner, depth)?,
+            ]),
+            Type::Array(array) => {
+                self.print_docblock_generic(Some(array.keyword.span), array.parameters.as_ref(), depth)?
+            }
+            Type::NonEmptyArray(array) => {
+                self.print_docblock_generic(Some(array.keyword.span), array.parameters.as_ref(), depth)?
+            }
+            Type::AssociativeArray(array) => {
+                self.print_docblock_generic(Some(array.keyword.span), array.parameters.as_ref(), depth)?
+            }
+            Type::List(list) => {
+                self.print_docblock_generic(Some(list.keyword.span), list.parameters.as_ref(), depth)?
+            }
+            Type::NonEmptyList(list) => {
+                self.print_docblock_generic(Some(list.keyword.span), list.parameters.as_ref(), depth)?
+            }
+            Type::Iterable(iterable) => {
+                self.print_docblock_generic(Some(iterable.keyword.span), iterable.parameters.as_ref(), depth)?
+            }
+            Type::Reference(reference) => {
+                self.print_docblock_generic(Some(reference.kind.span()), reference.parameters.as_ref(), depth)?
+            }
+            Type::Shape(shape) => self.print_docblock_shape(shape, depth)?,
+            Type::Conditional(conditional) => self.print_docblock_conditional(conditional, depth)?,
+            _ => self.print_docblock_type_source(r#type.span())?,
+        })
+    }
+
+    fn print_docblock_generic(
+        &self,
+        name: Option<Span>,
+        parameters: Option<&'arena GenericParameters<'arena>>,
+        depth: usize,
+    ) -> Option<Document<'arena, A>> {
+        let mut parts = Vec::new_in(self.arena);
+        if let Some(name) = name {
+            parts.push(self.print_docblock_type_source(name)?);
+        }
+
+        if let Some(parameters) = parameters {
+            parts.push(Document::String(b"<"));
+            for (index, entry) in parameters.entries.iter().enumerate() {
+                if index > 0 {
+                    parts.push(Document::String(b", "));
+                }
+
+                if let Some(variance) = &entry.variance {
+                    parts.push(self.print_docblock_type_source(variance.span())?);
+                    parts.push(Document::String(b" "));
+                }
+
+                parts.push(self.print_docblock_type(&entry.inner, depth)?);
+            }
+
+            parts.push(Document::String(b">"));
+        }
+
+        Some(Document::Array(parts))
+    }
+
+    fn print_docblock_shape(&self, shape: &'arena ShapeType<'arena>, depth: usize) -> Option<Document<'arena, A>> {
+        let mut parts =
+            vec_in![self.arena; self.print_docblock_type_source(shape.keyword.span)?, Document::String(b"{")];
+        if shape.fields.is_empty() && shape.additional_fields.is_none() {
+            parts.push(Document::String(b"}"));
+
+            return Some(Document::Array(parts));
+        }
+
+        parts.push(self.docblock_type_line(depth + 1, b""));
+        for (index, field) in shape.fields.iter().enumerate() {
+            if index > 0 {
+                parts.push(Document::String(b","));
+                parts.push(self.docblock_type_line(depth + 1, b" "));
+            }
+
+            if let Some(key) = &field.key {
+                parts.push(self.print_docblock_type_source(key.key.span())?);
+                if key.question_mark.is_some() {
+                    parts.push(Document::String(b"?"));
+                }
+
+                parts.push(Document::String(b": "));
+            }
+
+            parts.push(self.print_docblock_type(field.value, depth + 1)?);
+        }
+
+        if let Some(additional_fields) = &shape.additional_fields {
+            if !shape.fields.is_empty() {
+                parts.push(Document::String(b","));
+                parts.push(self.docblock_type_line(depth + 1, b" "));
+            }
+
+            parts.push(Document::String(b"..."));
+            if let Some(parameters) = &additional_fields.parameters {
+                parts.push(self.print_docblock_generic(None, Some(parameters), depth + 1)?);
+            }
+        } else if self.settings.trailing_comma {
+            parts.push(Document::IfBreak(IfBreak::then(self.arena, Document::String(b","))));
+        }
+
+        parts.push(self.docblock_type_line(depth, b""));
+        parts.push(Document::String(b"}"));
+
+        Some(Document::Group(Group::new(parts)))
+    }
+
+    fn print_docblock_conditional(
+        &self,
+        conditional: &'arena ConditionalType<'arena>,
+        depth: usize,
+    ) -> Option<Document<'arena, A>> {
+        let mut parts = vec_in![
+            self.arena;
+            self.print_docblock_type(conditional.subject, depth)?,
+            Document::String(b" is "),
+        ];
+
+        if conditional.not.is_some() {
+            parts.push(Document::String(b"not "));
+        }
+
+        parts.push(self.print_docblock_type(conditional.target, depth)?);
+        parts.push(self.docblock_type_line(depth + 1, b" "));
+        parts.push(Document::String(b"? "));
+        parts.push(self.print_docblock_type(conditional.then, depth + 1)?);
+        parts.push(self.docblock_type_line(depth + 1, b" "));
+        parts.push(Document::String(b": "));
+        parts.push(self.print_docblock_type(conditional.r#else, depth + 1)?);
+
+        Some(Document::Group(Group::new(parts)))
+    }
+
+    /// A line inside a docblock type: `flat` when the enclosing group fits, otherwise a
+    /// new docblock line indented by `depth` levels.
+    fn docblock_type_line(&self, depth: usize, flat: &'static [u8]) -> Document<'arena, A> {
+        let indentation = depth * self.settings.tab_width;
+        let mut prefix = Vec::with_capacity_in(3 + indentation, self.arena);
+        prefix.extend_from_slice(b" * ");
+        prefix.resize(3 + indentation, b' ');
+
+        Document::IfBreak(IfBreak::new(
+            self.arena,
+            Document::Array(vec_in![self.arena; Document::Line(Line::hard()), Document::String(prefix.leak())]),
+            Document::String(flat),
+        ))
+    }
+
+    fn print_docblock_type_source(&self, span: Span) -> Option<Document<'arena, A>> {
+        let source = &self.source_text[span.start.offset as usize..span.end.offset as usize];
+        if source.contains(&b'\n') || source.contains(&b'\r') {
+            return None;
+        }
+
+        Some(Document::String(source))
+    }
+}
+
+/// The width of a document when printed on a single line.
+pub(super) fn flat_width<A>(document: &Document<'_, A>) -> usize
+where
+    A: Arena,
+{
+    match document {
+        Document::String(string) => string_width(string),
+        Document::Array(contents) | Document::Indent(contents) => contents.iter().map(flat_width).sum(),
+        Document::Group(group) => group.contents.iter().map(flat_width).sum(),
+        Document::IfBreak(if_break) => flat_width(if_break.flat_content),
+        _ => 0,
+    }
+}
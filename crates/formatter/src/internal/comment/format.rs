@@ -647,6 +647,13 @@ where
             return Document::String(content);
         }
 
+        if self.settings.format_docblocks
+            && content.starts_with(b"/**")
+            && let Some(docblock) = self.print_docblock(comment)
+        {
+            return docblock;
+        }
+
         let lines = self.split_lines(content);
         let mut contents = Vec::with_capacity_in(lines.len() * 2, self.arena);
 
@@ -2,6 +2,7 @@ use mago_database::file::File;
 use mago_syntax::cst::Trivia;
 use mago_syntax::cst::TriviaKind;
 
+pub mod docblock;
 pub mod format;
 pub mod placement;
 
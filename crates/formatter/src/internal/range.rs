@@ -0,0 +1,203 @@
+use std::ops::Range;
+
+use mago_span::HasSpan;
+use mago_span::Span;
+use mago_syntax::cst::Node;
+use mago_syntax::cst::NodeKind;
+use mago_syntax::cst::Program;
+use mago_syntax::cst::TriviaSequenceExt;
+
+/// A run of sibling statements or class-like members selected for range formatting.
+///
+/// The target is recorded as a path of child indices from the program root, so that the
+/// same nodes can be found again in the formatted output, whose offsets differ. The indices
+/// are only followed where both programs have the same structure, as formatting can split
+/// grouped `use` statements or reorder them.
+#[derive(Debug)]
+pub(crate) struct RangeTarget {
+    /// For every node above the targeted siblings, its index among its parent's children and its kind.
+    path: Vec<(usize, NodeKind)>,
+    /// The index and kind of the first targeted sibling.
+    first: (usize, NodeKind),
+    /// The index and kind of the last targeted sibling.
+    last: (usize, NodeKind),
+}
+
+impl RangeTarget {
+    /// Finds the smallest run of sibling statements or class-like members overlapping `range`.
+    ///
+    /// Returns `None` if `range` does not overlap any statement.
+    pub(crate) fn find(program: &Program<'_>, range: &Range<u32>) -> Option<Self> {
+        let mut target = None;
+        let mut path = Vec::new();
+        let mut node = Node::Program(program);
+        loop {
+            let children = node.children();
+            let mut overlapping =
+                children.iter().enumerate().filter(|(_, child)| overlaps(child.span(), range)).map(|(index, _)| index);
+
+            let Some(first) = overlapping.next() else {
+                break;
+            };
+
+            let last = overlapping.next_back().unwrap_or(first);
+            if children[first..=last].iter().all(is_target) {
+                target = Some(Self {
+                    path: path.clone(),
+                    first: (first, children[first].kind()),
+                    last: (last, children[last].kind()),
+                });
+            }
+
+            // Descend while a single child holds the whole range, looking for a tighter run.
+            let child = children[first];
+            if first != last || !contains(child.span(), range) {
+                break;
+            }
+
+            path.push((first, child.kind()));
+            node = child;
+        }
+
+        target
+    }
+
+    /// Extends this target to cover `other` as well, if both are runs of the same siblings that
+    /// overlap or are adjacent.
+    ///
+    /// Returns `false`, leaving this target unchanged, if they cannot be merged.
+    pub(crate) fn merge(&mut self, other: &Self) -> bool {
+        if self.path != other.path || other.first.0 > self.last.0 + 1 || self.first.0 > other.last.0 + 1 {
+            return false;
+        }
+
+        if other.first.0 < self.first.0 {
+            self.first = other.first;
+        }
+
+        if other.last.0 > self.last.0 {
+            self.last = other.last;
+        }
+
+        true
+    }
+
+    /// Returns the bytes the target covers in `original` and in `formatted`, the same program
+    /// after formatting, including their attached comments.
+    ///
+    /// Returns `None` unless the two programs have the same children at every level of the
+    /// target's path, as the indices could otherwise point at different nodes.
+    pub(crate) fn locate(&self, original: &Program<'_>, formatted: &Program<'_>) -> Option<(Range<u32>, Range<u32>)> {
+        let mut original_node = Node::Program(original);
+        let mut formatted_node = Node::Program(formatted);
+        for (index, kind) in &self.path {
+            if !same_children(original_node, original.source_text, formatted_node, formatted.source_text) {
+                return None;
+            }
+
+            original_node = child_of_kind(original_node, *index, *kind)?;
+            formatted_node = child_of_kind(formatted_node, *index, *kind)?;
+        }
+
+        if !same_children(original_node, original.source_text, formatted_node, formatted.source_text) {
+            return None;
+        }
+
+        Some((self.siblings_range(original, original_node)?, self.siblings_range(formatted, formatted_node)?))
+    }
+
+    fn siblings_range(&self, program: &Program<'_>, parent: Node<'_, '_>) -> Option<Range<u32>> {
+        let first = child_of_kind(parent, self.first.0, self.first.1)?;
+        let last = child_of_kind(parent, self.last.0, self.last.1)?;
+
+        Some(with_comments(program, first.span().start.offset..last.span().end.offset))
+    }
+}
+
+fn child_of_kind<'ast, 'arena>(node: Node<'ast, 'arena>, index: usize, kind: NodeKind) -> Option<Node<'ast, 'arena>> {
+    node.children().get(index).copied().filter(|child| child.kind() == kind)
+}
+
+/// Returns `true` if `original` and `formatted` have the same number of children, pairwise
+/// of the same kind.
+///
+/// Statements and class-like members are compared by their variant, and `use` statements by
+/// their text, so that sorted imports are not mistaken for the original ones.
+fn same_children(
+    original: Node<'_, '_>,
+    original_source: &[u8],
+    formatted: Node<'_, '_>,
+    formatted_source: &[u8],
+) -> bool {
+    let original_children = original.children();
+    let formatted_children = formatted.children();
+
+    original_children.len() == formatted_children.len()
+        && original_children.iter().zip(&formatted_children).all(|(original, formatted)| {
+            sibling_signature(*original, original_source) == sibling_signature(*formatted, formatted_source)
+        })
+}
+
+fn sibling_signature(node: Node<'_, '_>, source: &[u8]) -> (NodeKind, Option<NodeKind>, Vec<u8>) {
+    let inner = if is_target(&node) { node.children().first().map(Node::kind) } else { None };
+    let text = if inner == Some(NodeKind::Use) {
+        let span = node.span();
+
+        source[span.start.offset as usize..span.end.offset as usize]
+            .iter()
+            .copied()
+            .filter(|byte| !byte.is_ascii_whitespace())
+            .collect()
+    } else {
+        Vec::new()
+    };
+
+    (node.kind(), inner, text)
+}
+
+const fn is_target(node: &Node<'_, '_>) -> bool {
+    matches!(node, Node::Statement(_) | Node::ClassLikeMember(_))
+}
+
+/// Returns `true` if `span` overlaps `range`; an empty range overlaps the node under its offset.
+const fn overlaps(span: Span, range: &Range<u32>) -> bool {
+    let end = if range.end > range.start { range.end } else { range.start + 1 };
+
+    span.start.offset < end && range.start < span.end.offset
+}
+
+const fn contains(span: Span, range: &Range<u32>) -> bool {
+    span.start.offset <= range.start && range.end <= span.end.offset
+}
+
+/// Extends `range` to the comments on the lines directly above it, and a comment trailing its last line.
+fn with_comments(program: &Program<'_>, range: Range<u32>) -> Range<u32> {
+    let source = program.source_text;
+    let comments: Vec<Span> = program.trivia.comments().map(|comment| comment.span).collect();
+
+    let mut start = range.start;
+    for comment in comments.iter().rev().filter(|comment| comment.end.offset <= range.start) {
+        let gap = &source[comment.end.offset as usize..start as usize];
+        let line_start =
+            source[..comment.start.offset as usize].iter().rposition(|byte| *byte == b'\n').map_or(0, |p| p + 1);
+        let before = &source[line_start..comment.start.offset as usize];
+
+        if !gap.iter().all(u8::is_ascii_whitespace) || !before.iter().all(u8::is_ascii_whitespace) {
+            break;
+        }
+
+        start = comment.start.offset;
+    }
+
+    let mut end = range.end;
+    for comment in comments.iter().filter(|comment| comment.start.offset >= range.end) {
+        let gap = &source[end as usize..comment.start.offset as usize];
+        if !gap.iter().all(|byte| *byte == b' ' || *byte == b'\t') {
+            break;
+        }
+
+        end = comment.end.offset;
+    }
+
+    start..end
+}
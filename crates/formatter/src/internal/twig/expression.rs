@@ -0,0 +1,302 @@
+use mago_allocator::Arena;
+use mago_allocator::vec::Vec;
+use mago_allocator::vec_in;
+use mago_twig_syntax::cst::Argument;
+use mago_twig_syntax::cst::ArgumentList;
+use mago_twig_syntax::cst::ArrayElement;
+use mago_twig_syntax::cst::BinaryOperator;
+use mago_twig_syntax::cst::Expression;
+use mago_twig_syntax::cst::HashMapEntry;
+use mago_twig_syntax::cst::NamedArgumentSeparator;
+use mago_twig_syntax::cst::TestArguments;
+use mago_twig_syntax::cst::UnaryOperator;
+
+use crate::document::Document;
+use crate::document::Group;
+use crate::document::Line;
+use crate::document::Separator;
+use crate::internal::twig::TwigFormatterState;
+
+impl<'arena, A> TwigFormatterState<'arena, A>
+where
+    A: Arena,
+{
+    pub(super) fn expression(&self, expression: &'arena Expression<'arena>) -> Document<'arena, A> {
+        match expression {
+            Expression::Name(name) => Document::String(name.name),
+            Expression::Number(number) => Document::String(number.raw),
+            Expression::String(string) => Document::String(string.raw),
+            Expression::InterpolatedString(string) => {
+                self.verbatim(self.source(string.open_quote.join(string.close_quote)))
+            }
+            Expression::Bool(bool) => Document::String(self.source(bool.span)),
+            Expression::Null(null) => Document::String(self.source(null.span)),
+            Expression::Array(array) => {
+                let elements = array.elements.iter().map(|element| match element {
+                    ArrayElement::Value(element) => self.expression(element.value),
+                    ArrayElement::Variadic(element) => {
+                        Document::Array(vec_in![self.arena; Document::String(b"..."), self.expression(element.value)])
+                    }
+                    ArrayElement::Missing(_) => Document::empty(),
+                });
+
+                self.list(b"[", elements, b"]", false)
+            }
+            Expression::HashMap(hash_map) => {
+                let entries = hash_map.entries.iter().map(|entry| self.hash_map_entry(entry));
+
+                self.list(b"{", entries, b"}", true)
+            }
+            Expression::Unary(unary) => {
+                let operator: &'static [u8] = match unary.operator {
+                    UnaryOperator::MinusSign(_) => b"-",
+                    UnaryOperator::PlusSign(_) => b"+",
+                    UnaryOperator::Not(_) => b"not ",
+                };
+
+                Document::Array(vec_in![self.arena; Document::String(operator), self.expression(unary.operand)])
+            }
+            Expression::Binary(binary) => {
+                let operator = self.binary_operator(&binary.operator);
+                if matches!(binary.operator, BinaryOperator::Range(_)) {
+                    return Document::Array(vec_in![
+                        self.arena;
+                        self.expression(binary.lhs),
+                        Document::String(operator),
+                        self.expression(binary.rhs),
+                    ]);
+                }
+
+                Document::Array(vec_in![
+                    self.arena;
+                    self.expression(binary.lhs),
+                    Document::String(b" "),
+                    Document::String(operator),
+                    Document::String(b" "),
+                    self.expression(binary.rhs),
+                ])
+            }
+            Expression::Conditional(conditional) => {
+                let mut parts = vec_in![self.arena; self.expression(conditional.condition)];
+                match (conditional.then, conditional.r#else) {
+                    (Some(then), Some(r#else)) => {
+                        parts.push(Document::String(b" ? "));
+                        parts.push(self.expression(then));
+                        parts.push(Document::String(b" : "));
+                        parts.push(self.expression(r#else));
+                    }
+                    (Some(then), None) => {
+                        parts.push(Document::String(b" ? "));
+                        parts.push(self.expression(then));
+                    }
+                    (None, Some(r#else)) => {
+                        parts.push(Document::String(b" ?: "));
+                        parts.push(self.expression(r#else));
+                    }
+                    (None, None) => {
+                        parts.push(Document::String(b" ?"));
+                    }
+                }
+
+                Document::Array(parts)
+            }
+            Expression::GetAttribute(get_attribute) => Document::Array(vec_in![
+                self.arena;
+                self.expression(get_attribute.object),
+                Document::String(if get_attribute.null_safe { b"?." } else { b"." }),
+                self.expression(get_attribute.attribute),
+            ]),
+            Expression::GetItem(get_item) => Document::Array(vec_in![
+                self.arena;
+                self.expression(get_item.object),
+                Document::String(b"["),
+                self.expression(get_item.index),
+                Document::String(b"]"),
+            ]),
+            Expression::Slice(slice) => {
+                let mut parts = vec_in![self.arena; self.expression(slice.object), Document::String(b"[")];
+                if let Some(start) = slice.start {
+                    parts.push(self.expression(start));
+                }
+
+                parts.push(Document::String(b":"));
+                if let Some(length) = slice.length {
+                    parts.push(self.expression(length));
+                }
+
+                parts.push(Document::String(b"]"));
+
+                Document::Array(parts)
+            }
+            Expression::Call(call) => Document::Array(vec_in![
+                self.arena;
+                self.expression(call.callee),
+                self.argument_list(&call.argument_list),
+            ]),
+            Expression::MethodCall(method_call) => Document::Array(vec_in![
+                self.arena;
+                self.expression(method_call.object),
+                Document::String(if method_call.null_safe { b"?." } else { b"." }),
+                Document::String(method_call.method.value),
+                self.argument_list(&method_call.argument_list),
+            ]),
+            Expression::Filter(filter) => {
+                let mut parts = vec_in![
+                    self.arena;
+                    self.expression(filter.operand),
+                    Document::String(b"|"),
+                    Document::String(filter.name.value),
+                ];
+
+                if let Some(argument_list) = &filter.argument_list {
+                    parts.push(self.argument_list(argument_list));
+                }
+
+                Document::Array(parts)
+            }
+            Expression::Test(test) => {
+                let mut parts = vec_in![self.arena; self.expression(test.operand), Document::String(b" is ")];
+                if test.not_keyword.is_some() {
+                    parts.push(Document::String(b"not "));
+                }
+
+                parts.push(Document::String(test.name.value));
+                if let Some(second_word) = test.second_word {
+                    parts.push(Document::String(b" "));
+                    parts.push(Document::String(second_word.value));
+                }
+
+                match &test.arguments {
+                    TestArguments::None => {}
+                    TestArguments::Parenthesised(argument_list) => parts.push(self.argument_list(argument_list)),
+                    TestArguments::Bare(expression) => {
+                        parts.push(Document::String(b" "));
+                        parts.push(self.expression(expression));
+                    }
+                }
+
+                Document::Array(parts)
+            }
+            Expression::Parenthesized(parenthesized) => Document::Array(vec_in![
+                self.arena;
+                Document::String(b"("),
+                self.expression(parenthesized.inner),
+                Document::String(b")"),
+            ]),
+            Expression::ArrowFunction(arrow_function) => {
+                let parameters = arrow_function.parameters.iter().map(|parameter| Document::String(parameter.value));
+                let mut parts = vec_in![self.arena];
+                if arrow_function.left_parenthesis.is_some() {
+                    parts.push(Document::String(b"("));
+                    parts.extend(Document::join(self.arena, parameters, Separator::CommaSpace));
+                    parts.push(Document::String(b")"));
+                } else {
+                    parts.extend(parameters);
+                }
+
+                parts.push(Document::String(b" => "));
+                parts.push(self.expression(arrow_function.body));
+
+                Document::Array(parts)
+            }
+        }
+    }
+
+    pub(super) fn argument_list(&self, argument_list: &'arena ArgumentList<'arena>) -> Document<'arena, A> {
+        let arguments = argument_list.arguments.iter().map(|argument| match argument {
+            Argument::Positional(argument) => {
+                if argument.ellipsis.is_some() {
+                    Document::Array(vec_in![self.arena; Document::String(b"..."), self.expression(argument.value)])
+                } else {
+                    self.expression(argument.value)
+                }
+            }
+            Argument::Named(argument) => Document::Array(vec_in![
+                self.arena;
+                Document::String(argument.name.value),
+                Document::String(match argument.separator {
+                    NamedArgumentSeparator::Equal(_) => b"=",
+                    NamedArgumentSeparator::Colon(_) => b": ",
+                }),
+                self.expression(argument.value),
+            ]),
+        });
+
+        self.list(b"(", arguments, b")", false)
+    }
+
+    fn hash_map_entry(&self, entry: &'arena HashMapEntry<'arena>) -> Document<'arena, A> {
+        let mut parts = vec_in![self.arena];
+        if entry.ellipsis.is_some() {
+            parts.push(Document::String(b"..."));
+        }
+
+        // Shorthand entries, such as `{ name }`, have a key but no colon.
+        if let Some(key) = &entry.key
+            && entry.colon.is_some()
+        {
+            parts.push(self.expression(key));
+            parts.push(Document::String(b": "));
+        }
+
+        parts.push(self.expression(&entry.value));
+
+        Document::Array(parts)
+    }
+
+    fn binary_operator(&self, operator: &BinaryOperator<'arena>) -> &'arena [u8] {
+        match operator {
+            BinaryOperator::And(_) => b"and",
+            BinaryOperator::Or(_) => b"or",
+            BinaryOperator::Xor(_) => b"xor",
+            BinaryOperator::BitwiseAnd(_) => b"b-and",
+            BinaryOperator::BitwiseOr(_) => b"b-or",
+            BinaryOperator::BitwiseXor(_) => b"b-xor",
+            BinaryOperator::In(_) => b"in",
+            BinaryOperator::NotIn(_) => b"not in",
+            BinaryOperator::Matches(_) => b"matches",
+            BinaryOperator::StartsWith(_) => b"starts with",
+            BinaryOperator::EndsWith(_) => b"ends with",
+            BinaryOperator::HasSome(_) => b"has some",
+            BinaryOperator::HasEvery(_) => b"has every",
+            _ => self.source(mago_span::HasSpan::span(operator)),
+        }
+    }
+
+    /// Prints a bracketed, comma-separated list, breaking it one item per line when it does
+    /// not fit.
+    ///
+    /// When `padded` is set, the items are separated from the brackets by a space on one line.
+    pub(super) fn list(
+        &self,
+        open: &'static [u8],
+        items: impl IntoIterator<Item = Document<'arena, A>>,
+        close: &'static [u8],
+        padded: bool,
+    ) -> Document<'arena, A> {
+        let items = Document::join(self.arena, items, Separator::CommaLine);
+        if items.is_empty() {
+            return Document::Array(vec_in![self.arena; Document::String(open), Document::String(close)]);
+        }
+
+        let line = if padded { Line::default() } else { Line::soft() };
+
+        let mut contents = vec_in![self.arena; Document::Line(line)];
+        contents.extend(items);
+
+        Document::Group(Group::new(vec_in![
+            self.arena;
+            Document::String(open),
+            Document::Indent(contents),
+            Document::Line(line),
+            Document::String(close),
+        ]))
+    }
+
+    pub(super) fn join_with_comma(
+        &self,
+        items: impl IntoIterator<Item = Document<'arena, A>>,
+    ) -> Vec<'arena, Document<'arena, A>, A> {
+        Document::join(self.arena, items, Separator::CommaSpace)
+    }
+}
@@ -0,0 +1,148 @@
+//! Formatting of Twig templates.
+//!
+//! Only the inside of `{{ ... }}` and `{% ... %}` tags is reformatted: the text between tags,
+//! comments, `{% verbatim %}` blocks, and tags the parser does not understand are copied from
+//! the source as-is. This keeps the markup of the template, and its whitespace control, intact.
+
+use mago_allocator::Arena;
+use mago_allocator::CollectIn;
+use mago_allocator::vec::Vec;
+use mago_allocator::vec_in;
+use mago_span::Span;
+use mago_twig_syntax::cst::Template;
+use mago_twig_syntax::cst::Trivia;
+
+use crate::document::Align;
+use crate::document::Document;
+use crate::document::Group;
+use crate::document::Separator;
+use crate::internal::utils::split_any_newline;
+
+mod expression;
+mod statement;
+
+/// Markers that indicate a template should not be formatted.
+const FORMAT_IGNORE_MARKERS: [&[u8]; 2] = [b"@mago-format-ignore", b"@mago-formatter-ignore"];
+
+pub struct TwigFormatterState<'arena, A>
+where
+    A: Arena,
+{
+    arena: &'arena A,
+    template: &'arena Template<'arena>,
+    comments: Vec<'arena, Trivia<'arena>, A>,
+    /// The offset up to which the source has been printed.
+    cursor: u32,
+    parts: Vec<'arena, Document<'arena, A>, A>,
+}
+
+impl<'arena, A> TwigFormatterState<'arena, A>
+where
+    A: Arena,
+{
+    pub fn new(arena: &'arena A, template: &'arena Template<'arena>) -> Self {
+        let mut comments = vec_in![arena];
+        comments.extend(template.trivia.iter().filter(|trivia| trivia.kind.is_comment()).copied());
+
+        Self { arena, template, comments, cursor: 0, parts: vec_in![arena] }
+    }
+
+    /// Checks if any comment in the template contains a file-level format-ignore directive.
+    pub fn has_format_ignore_comment(&self) -> bool {
+        self.comments.iter().any(|comment| {
+            FORMAT_IGNORE_MARKERS.iter().any(|marker| {
+                if let Some(pos) = memchr::memmem::find(comment.value, marker) {
+                    !comment.value[pos + marker.len()..].starts_with(b"-")
+                } else {
+                    false
+                }
+            })
+        })
+    }
+
+    pub fn format(mut self) -> Document<'arena, A> {
+        for statement in self.template.statements.iter() {
+            self.statement(statement);
+        }
+
+        self.copy_source_until(self.template.source_text.len() as u32);
+
+        Document::Array(self.parts)
+    }
+
+    /// Prints a tag, from the start of `open` to the end of `close`, with the given contents
+    /// separated by spaces.
+    ///
+    /// Tags containing comments are copied from the source, as their comments could not be
+    /// placed back reliably.
+    fn tag(&mut self, open: Span, close: Span, contents: impl FnOnce(&Self) -> Vec<'arena, Document<'arena, A>, A>) {
+        let start = open.start.offset;
+        let end = close.end.offset;
+        if start < self.cursor
+            || self.comments.iter().any(|comment| comment.span.start.offset < end && comment.span.end.offset > start)
+        {
+            self.copy_source_until(end);
+
+            return;
+        }
+
+        self.copy_source_until(start);
+
+        let mut parts = vec_in![self.arena; Document::String(self.source(open)), Document::String(b" ")];
+        for (index, document) in contents(self).into_iter().enumerate() {
+            if index > 0 {
+                parts.push(Document::String(b" "));
+            }
+
+            parts.push(document);
+        }
+
+        parts.push(Document::String(b" "));
+        parts.push(Document::String(self.source(close)));
+
+        let alignment = self.line_indentation(start);
+        let group = Document::Group(Group::new(parts));
+
+        self.parts.push(if alignment.is_empty() {
+            group
+        } else {
+            Document::Align(Align { alignment, contents: vec_in![self.arena; group] })
+        });
+
+        self.cursor = end;
+    }
+
+    /// Copies the source from the current cursor up to `offset`, as-is.
+    fn copy_source_until(&mut self, offset: u32) {
+        if offset <= self.cursor {
+            return;
+        }
+
+        let source = &self.template.source_text[self.cursor as usize..offset as usize];
+        let document = self.verbatim(source);
+
+        self.parts.push(document);
+        self.cursor = offset;
+    }
+
+    /// Builds a document printing `source` as-is, splitting it on newlines so that the printer
+    /// keeps track of the current column.
+    fn verbatim(&self, source: &'arena [u8]) -> Document<'arena, A> {
+        let lines = split_any_newline(source).map(Document::String).collect_in::<Vec<'arena, _, A>>(self.arena);
+
+        Document::Array(Document::join(self.arena, lines, Separator::LiteralLine))
+    }
+
+    /// Returns the whitespace at the start of the line containing `offset`.
+    fn line_indentation(&self, offset: u32) -> &'arena [u8] {
+        let source = self.template.source_text;
+        let line_start = memchr::memrchr(b'\n', &source[..offset as usize]).map_or(0, |position| position + 1);
+        let length = source[line_start..].iter().take_while(|byte| matches!(byte, b' ' | b'\t')).count();
+
+        &source[line_start..line_start + length]
+    }
+
+    fn source(&self, span: Span) -> &'arena [u8] {
+        &self.template.source_text[span.start.offset as usize..span.end.offset as usize]
+    }
+}
@@ -0,0 +1,457 @@
+use mago_allocator::Arena;
+use mago_allocator::vec::Vec;
+use mago_allocator::vec_in;
+use mago_span::Span;
+use mago_twig_syntax::cst::BlockBody;
+use mago_twig_syntax::cst::CacheOption;
+use mago_twig_syntax::cst::ElseBranch;
+use mago_twig_syntax::cst::IgnoreMissingClause;
+use mago_twig_syntax::cst::Keyword;
+use mago_twig_syntax::cst::Sequence;
+use mago_twig_syntax::cst::SetBody;
+use mago_twig_syntax::cst::Statement;
+use mago_twig_syntax::cst::WithExpressionClause;
+
+use crate::document::Document;
+use crate::internal::twig::TwigFormatterState;
+
+impl<'arena, A> TwigFormatterState<'arena, A>
+where
+    A: Arena,
+{
+    pub(super) fn statement(&mut self, statement: &'arena Statement<'arena>) {
+        match statement {
+            // Text, verbatim blocks, and unknown tags are copied from the source as-is, and the
+            // `types` mapping keeps the author's layout.
+            Statement::Text(_) | Statement::Verbatim(_) | Statement::Unknown(_) | Statement::Types(_) => {}
+            Statement::Print(print) => {
+                self.tag(
+                    print.open_variable,
+                    print.close_variable,
+                    |f| vec_in![f.arena; f.expression(&print.expression)],
+                );
+            }
+            Statement::If(r#if) => {
+                for branch in r#if.branches.iter() {
+                    self.tag(
+                        branch.open_tag,
+                        branch.close_tag,
+                        |f| vec_in![f.arena; Document::String(branch.keyword.value), f.expression(&branch.condition)],
+                    );
+
+                    self.statements(&branch.body);
+                }
+
+                self.else_branch(r#if.else_branch.as_ref());
+                self.end_tag(r#if.end_open_tag, r#if.end_keyword, r#if.end_close_tag);
+            }
+            Statement::For(r#for) => {
+                self.tag(r#for.open_tag, r#for.close_tag, |f| {
+                    let targets = r#for.targets.iter().map(|target| Document::String(target.value));
+
+                    let mut parts = vec_in![
+                        f.arena;
+                        Document::String(r#for.keyword.value),
+                        Document::Array(f.join_with_comma(targets)),
+                        Document::String(r#for.in_keyword.value),
+                        f.expression(&r#for.sequence),
+                    ];
+
+                    if let Some(if_clause) = &r#for.if_clause {
+                        parts.push(Document::String(if_clause.keyword.value));
+                        parts.push(f.expression(&if_clause.condition));
+                    }
+
+                    parts
+                });
+
+                self.statements(&r#for.body);
+                self.else_branch(r#for.else_branch.as_ref());
+                self.end_tag(r#for.end_open_tag, r#for.end_keyword, r#for.end_close_tag);
+            }
+            Statement::Set(set) => {
+                let names =
+                    || Document::Array(self.join_with_comma(set.names.iter().map(|name| Document::String(name.value))));
+
+                match &set.body {
+                    SetBody::Inline(inline) => {
+                        let names = names();
+                        self.tag(set.open_tag, inline.close_tag, |f| {
+                            let values = inline.values.iter().map(|value| f.expression(value));
+
+                            vec_in![
+                                f.arena;
+                                Document::String(set.keyword.value),
+                                names,
+                                Document::String(b"="),
+                                Document::Array(f.join_with_comma(values)),
+                            ]
+                        });
+                    }
+                    SetBody::Capture(capture) => {
+                        let names = names();
+                        self.tag(
+                            set.open_tag,
+                            capture.close_tag,
+                            |f| vec_in![f.arena; Document::String(set.keyword.value), names],
+                        );
+
+                        self.statements(&capture.body);
+                        self.end_tag(capture.end_open_tag, capture.end_keyword, capture.end_close_tag);
+                    }
+                }
+            }
+            Statement::Block(block) => {
+                match &block.body {
+                    BlockBody::Short(short) => {
+                        self.tag(block.open_tag, short.close_tag, |f| {
+                            vec_in![
+                                f.arena;
+                                Document::String(block.keyword.value),
+                                Document::String(block.name.value),
+                                f.expression(&short.expression),
+                            ]
+                        });
+                    }
+                    BlockBody::Long(long) => {
+                        self.tag(block.open_tag, long.close_tag, |f| {
+                        vec_in![f.arena; Document::String(block.keyword.value), Document::String(block.name.value)]
+                    });
+
+                        self.statements(&long.body);
+                        self.tag(long.end_open_tag, long.end_close_tag, |f| {
+                            let mut parts = vec_in![f.arena; Document::String(long.end_keyword.value)];
+                            if let Some(end_name) = long.end_name {
+                                parts.push(Document::String(end_name.value));
+                            }
+
+                            parts
+                        });
+                    }
+                }
+            }
+            Statement::Extends(extends) => {
+                self.tag(
+                    extends.open_tag,
+                    extends.close_tag,
+                    |f| vec_in![f.arena; Document::String(extends.keyword.value), f.expression(&extends.template)],
+                );
+            }
+            Statement::Use(r#use) => {
+                self.tag(r#use.open_tag, r#use.close_tag, |f| {
+                    let mut parts =
+                        vec_in![f.arena; Document::String(r#use.keyword.value), f.expression(&r#use.template)];
+
+                    if let Some(with_keyword) = r#use.with_keyword {
+                        let aliases = r#use.aliases.iter().map(|alias| {
+                            f.alias(alias.from.value, alias.as_keyword.as_ref(), alias.to.map(|to| to.value))
+                        });
+
+                        parts.push(Document::String(with_keyword.value));
+                        parts.push(Document::Array(f.join_with_comma(aliases)));
+                    }
+
+                    parts
+                });
+            }
+            Statement::Include(include) => {
+                self.tag(include.open_tag, include.close_tag, |f| {
+                    let mut parts =
+                        vec_in![f.arena; Document::String(include.keyword.value), f.expression(&include.template)];
+
+                    f.inclusion_options(
+                        &mut parts,
+                        include.ignore_missing.as_ref(),
+                        include.with_clause.as_ref(),
+                        include.only_keyword.as_ref(),
+                    );
+
+                    parts
+                });
+            }
+            Statement::Embed(embed) => {
+                self.tag(embed.open_tag, embed.close_tag, |f| {
+                    let mut parts =
+                        vec_in![f.arena; Document::String(embed.keyword.value), f.expression(&embed.template)];
+
+                    f.inclusion_options(
+                        &mut parts,
+                        embed.ignore_missing.as_ref(),
+                        embed.with_clause.as_ref(),
+                        embed.only_keyword.as_ref(),
+                    );
+
+                    parts
+                });
+
+                self.statements(&embed.body);
+                self.end_tag(embed.end_open_tag, embed.end_keyword, embed.end_close_tag);
+            }
+            Statement::Import(import) => {
+                self.tag(import.open_tag, import.close_tag, |f| {
+                    vec_in![
+                        f.arena;
+                        Document::String(import.keyword.value),
+                        f.expression(&import.template),
+                        Document::String(import.as_keyword.value),
+                        Document::String(import.alias.value),
+                    ]
+                });
+            }
+            Statement::From(from) => {
+                self.tag(from.open_tag, from.close_tag, |f| {
+                    let names = from
+                        .names
+                        .iter()
+                        .map(|name| f.alias(name.from.value, name.as_keyword.as_ref(), name.to.map(|to| to.value)));
+
+                    vec_in![
+                        f.arena;
+                        Document::String(from.keyword.value),
+                        f.expression(&from.template),
+                        Document::String(from.import_keyword.value),
+                        Document::Array(f.join_with_comma(names)),
+                    ]
+                });
+            }
+            Statement::Macro(r#macro) => {
+                self.tag(r#macro.open_tag, r#macro.close_tag, |f| {
+                    let arguments = r#macro.arguments.iter().map(|argument| match &argument.default {
+                        Some(default) => Document::Array(vec_in![
+                            f.arena;
+                            Document::String(argument.name.value),
+                            Document::String(b" = "),
+                            f.expression(default),
+                        ]),
+                        None => Document::String(argument.name.value),
+                    });
+
+                    vec_in![
+                        f.arena;
+                        Document::String(r#macro.keyword.value),
+                        Document::Array(vec_in![
+                            f.arena;
+                            Document::String(r#macro.name.value),
+                            f.list(b"(", arguments, b")", false),
+                        ]),
+                    ]
+                });
+
+                self.statements(&r#macro.body);
+                self.tag(r#macro.end_open_tag, r#macro.end_close_tag, |f| {
+                    let mut parts = vec_in![f.arena; Document::String(r#macro.end_keyword.value)];
+                    if let Some(end_name) = r#macro.end_name {
+                        parts.push(Document::String(end_name.value));
+                    }
+
+                    parts
+                });
+            }
+            Statement::With(with) => {
+                self.tag(with.open_tag, with.close_tag, |f| {
+                    let mut parts = vec_in![f.arena; Document::String(with.keyword.value)];
+                    if let Some(variables) = &with.variables {
+                        parts.push(f.expression(variables));
+                    }
+
+                    if let Some(only_keyword) = with.only_keyword {
+                        parts.push(Document::String(only_keyword.value));
+                    }
+
+                    parts
+                });
+
+                self.statements(&with.body);
+                self.end_tag(with.end_open_tag, with.end_keyword, with.end_close_tag);
+            }
+            Statement::Apply(apply) => {
+                self.tag(apply.open_tag, apply.close_tag, |f| {
+                    let filters = apply.filters.iter().map(|filter| match &filter.argument_list {
+                        Some(argument_list) => Document::Array(vec_in![
+                            f.arena;
+                            Document::String(filter.name.value),
+                            f.argument_list(argument_list),
+                        ]),
+                        None => Document::String(filter.name.value),
+                    });
+
+                    let mut filter_parts = vec_in![f.arena];
+                    for (index, filter) in filters.enumerate() {
+                        if index > 0 {
+                            filter_parts.push(Document::String(b"|"));
+                        }
+
+                        filter_parts.push(filter);
+                    }
+
+                    vec_in![f.arena; Document::String(apply.keyword.value), Document::Array(filter_parts)]
+                });
+
+                self.statements(&apply.body);
+                self.end_tag(apply.end_open_tag, apply.end_keyword, apply.end_close_tag);
+            }
+            Statement::Autoescape(autoescape) => {
+                self.tag(autoescape.open_tag, autoescape.close_tag, |f| {
+                    let mut parts = vec_in![f.arena; Document::String(autoescape.keyword.value)];
+                    if let Some(strategy) = &autoescape.strategy {
+                        parts.push(f.expression(strategy));
+                    }
+
+                    parts
+                });
+
+                self.statements(&autoescape.body);
+                self.end_tag(autoescape.end_open_tag, autoescape.end_keyword, autoescape.end_close_tag);
+            }
+            Statement::Sandbox(sandbox) => {
+                self.tag(
+                    sandbox.open_tag,
+                    sandbox.close_tag,
+                    |f| vec_in![f.arena; Document::String(sandbox.keyword.value)],
+                );
+
+                self.statements(&sandbox.body);
+                self.end_tag(sandbox.end_open_tag, sandbox.end_keyword, sandbox.end_close_tag);
+            }
+            Statement::Deprecated(deprecated) => {
+                self.tag(deprecated.open_tag, deprecated.close_tag, |f| {
+                    let mut parts =
+                        vec_in![f.arena; Document::String(deprecated.keyword.value), f.expression(&deprecated.message)];
+
+                    for option in deprecated.options {
+                        parts.push(Document::Array(vec_in![
+                            f.arena;
+                            Document::String(option.name.value),
+                            Document::String(b"="),
+                            f.expression(&option.value),
+                        ]));
+                    }
+
+                    parts
+                });
+            }
+            Statement::Do(r#do) => {
+                self.tag(
+                    r#do.open_tag,
+                    r#do.close_tag,
+                    |f| vec_in![f.arena; Document::String(r#do.keyword.value), f.expression(&r#do.expression)],
+                );
+            }
+            Statement::Flush(flush) => {
+                self.tag(flush.open_tag, flush.close_tag, |f| vec_in![f.arena; Document::String(flush.keyword.value)]);
+            }
+            Statement::Guard(guard) => {
+                self.tag(guard.open_tag, guard.close_tag, |f| {
+                    let mut parts = vec_in![
+                        f.arena;
+                        Document::String(guard.keyword.value),
+                        Document::String(guard.kind_keyword.value),
+                        Document::String(guard.name.value),
+                    ];
+
+                    if let Some(second_word) = guard.second_word {
+                        parts.push(Document::String(second_word.value));
+                    }
+
+                    parts
+                });
+
+                self.statements(&guard.body);
+                self.else_branch(guard.else_branch.as_ref());
+                self.end_tag(guard.end_open_tag, guard.end_keyword, guard.end_close_tag);
+            }
+            Statement::Cache(cache) => {
+                self.tag(cache.open_tag, cache.close_tag, |f| {
+                    let mut parts = vec_in![f.arena; Document::String(cache.keyword.value), f.expression(&cache.key)];
+                    if let Some(ttl) = &cache.ttl {
+                        parts.push(f.cache_option(ttl));
+                    }
+
+                    if let Some(tags) = &cache.tags {
+                        parts.push(f.cache_option(tags));
+                    }
+
+                    parts
+                });
+
+                self.statements(&cache.body);
+                self.end_tag(cache.end_open_tag, cache.end_keyword, cache.end_close_tag);
+            }
+        }
+    }
+
+    fn statements(&mut self, statements: &'arena Sequence<'arena, Statement<'arena>>) {
+        for statement in statements.iter() {
+            self.statement(statement);
+        }
+    }
+
+    fn else_branch(&mut self, else_branch: Option<&'arena ElseBranch<'arena>>) {
+        let Some(else_branch) = else_branch else {
+            return;
+        };
+
+        self.tag(
+            else_branch.open_tag,
+            else_branch.close_tag,
+            |f| vec_in![f.arena; Document::String(else_branch.keyword.value)],
+        );
+
+        self.statements(&else_branch.body);
+    }
+
+    fn end_tag(&mut self, open: Span, keyword: Keyword<'arena>, close: Span) {
+        self.tag(open, close, |f| vec_in![f.arena; Document::String(keyword.value)]);
+    }
+
+    fn alias(
+        &self,
+        from: &'arena [u8],
+        as_keyword: Option<&Keyword<'arena>>,
+        to: Option<&'arena [u8]>,
+    ) -> Document<'arena, A> {
+        match (as_keyword, to) {
+            (Some(as_keyword), Some(to)) => Document::Array(vec_in![
+                self.arena;
+                Document::String(from),
+                Document::String(b" "),
+                Document::String(as_keyword.value),
+                Document::String(b" "),
+                Document::String(to),
+            ]),
+            _ => Document::String(from),
+        }
+    }
+
+    fn inclusion_options(
+        &self,
+        parts: &mut Vec<'arena, Document<'arena, A>, A>,
+        ignore_missing: Option<&'arena IgnoreMissingClause<'arena>>,
+        with_clause: Option<&'arena WithExpressionClause<'arena>>,
+        only_keyword: Option<&'arena Keyword<'arena>>,
+    ) {
+        if let Some(ignore_missing) = ignore_missing {
+            parts.push(Document::String(ignore_missing.ignore_keyword.value));
+            parts.push(Document::String(ignore_missing.missing_keyword.value));
+        }
+
+        if let Some(with_clause) = with_clause {
+            parts.push(Document::String(with_clause.with_keyword.value));
+            parts.push(self.expression(&with_clause.variables));
+        }
+
+        if let Some(only_keyword) = only_keyword {
+            parts.push(Document::String(only_keyword.value));
+        }
+    }
+
+    fn cache_option(&self, option: &'arena CacheOption<'arena>) -> Document<'arena, A> {
+        Document::Array(vec_in![
+            self.arena;
+            Document::String(option.keyword.value),
+            Document::String(b"("),
+            self.expression(&option.value),
+            Document::String(b")"),
+        ])
+    }
+}
@@ -11,8 +11,11 @@
 #![allow(clippy::float_arithmetic)]
 
 use std::borrow::Cow;
+use std::cmp::Reverse;
+use std::ops::Range;
 
 use mago_allocator::Arena;
+use mago_allocator::vec::Vec;
 
 use mago_database::file::File;
 use mago_php_version::PHPVersion;
@@ -21,14 +24,21 @@ use mago_syntax::cst::TriviaSequenceExt;
 use mago_syntax::error::ParseError;
 use mago_syntax::parser::parse_file_with_settings;
 use mago_syntax::settings::ParserSettings;
+use mago_twig_syntax::cst::Template;
+use mago_twig_syntax::error::ParseError as TemplateParseError;
+use mago_twig_syntax::parser::parse_file as parse_template_file;
 
 use crate::document::Document;
+use crate::error::RangeFormatError;
 use crate::internal::FormatterState;
 use crate::internal::format::Format;
 use crate::internal::printer::Printer;
+use crate::internal::range::RangeTarget;
+use crate::internal::twig::TwigFormatterState;
 use crate::settings::FormatSettings;
 
 pub mod document;
+pub mod error;
 pub mod presets;
 pub mod settings;
 
@@ -112,6 +122,104 @@ where
         Ok(self.format(file, program))
     }
 
+    /// Formats only the part of a [`File`] that overlaps `range`, a range of byte offsets.
+    ///
+    /// The smallest run of statements or class-like members enclosing the range is formatted,
+    /// together with the comments attached to it, and spliced back into the file; every byte
+    /// outside of it is kept as is. When the statements start their own lines, they are
+    /// re-indented from the start of their first line. If the range does not touch any
+    /// statement, the file is returned unchanged.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`RangeFormatError::ParseError`] with the first parse error if the file's content
+    /// contains syntax errors, or [`RangeFormatError::RangeNotIsolated`] if formatting the whole
+    /// file changes the statements around the range (e.g. by splitting or sorting `use`
+    /// statements), so that it cannot be formatted on its own.
+    pub fn format_range<'ctx>(&self, file: &'ctx File, range: Range<u32>) -> Result<&'arena [u8], RangeFormatError> {
+        self.format_ranges(file, &[range])
+    }
+
+    /// Formats only the parts of a [`File`] that overlap any of `ranges`, given as byte offsets.
+    ///
+    /// This behaves like [`Formatter::format_range`] for every range at once: runs of statements
+    /// selected by several ranges are merged, and runs nested in another selected run are
+    /// formatted as part of it.
+    ///
+    /// # Errors
+    ///
+    /// Returns the same errors as [`Formatter::format_range`]; the file is never partially
+    /// formatted when one of the ranges cannot be isolated.
+    pub fn format_ranges<'ctx>(
+        &self,
+        file: &'ctx File,
+        ranges: &[Range<u32>],
+    ) -> Result<&'arena [u8], RangeFormatError> {
+        let program = parse_file_with_settings(self.arena, file, self.parser_settings);
+        if let Some(error) = program.errors.first() {
+            return Err(RangeFormatError::ParseError(error.clone()));
+        }
+
+        let mut targets: Vec<RangeTarget, A> = Vec::new_in(self.arena);
+        for target in ranges.iter().filter_map(|range| RangeTarget::find(program, range)) {
+            if !targets.iter_mut().any(|existing| existing.merge(&target)) {
+                targets.push(target);
+            }
+        }
+
+        if targets.is_empty() {
+            return Ok(program.source_text);
+        }
+
+        let formatted = self.format(file, program);
+        let formatted_file = File::ephemeral(file.name.clone(), Cow::Owned(formatted.to_vec()));
+        let formatted_program = parse_file_with_settings(self.arena, &formatted_file, self.parser_settings);
+        if formatted_program.has_errors() {
+            return Err(RangeFormatError::RangeNotIsolated);
+        }
+
+        // Each target is found at the same path in the formatted program, unless formatting
+        // changed the structure around it, in which case it cannot be formatted on its own.
+        let mut replacements = Vec::with_capacity_in(targets.len(), self.arena);
+        for target in &targets {
+            let Some((original, replacement)) = target.locate(program, formatted_program) else {
+                return Err(RangeFormatError::RangeNotIsolated);
+            };
+
+            // Replace the indentation as well when the target starts its line, so that its first
+            // line is not left with the original indentation and the others with the new one.
+            match (
+                line_indentation_start(program.source_text, original.start),
+                line_indentation_start(formatted, replacement.start),
+            ) {
+                (Some(original_start), Some(replacement_start)) => {
+                    replacements.push((original_start..original.end, replacement_start..replacement.end));
+                }
+                _ => replacements.push((original, replacement)),
+            }
+        }
+
+        replacements.sort_by_key(|(original, _)| (original.start, Reverse(original.end)));
+
+        let source = program.source_text;
+        let mut output = Vec::with_capacity_in(source.len(), self.arena);
+        let mut position = 0u32;
+        for (original, replacement) in replacements {
+            // Skip targets nested in one that was already replaced.
+            if original.start < position {
+                continue;
+            }
+
+            output.extend_from_slice(&source[position as usize..original.start as usize]);
+            output.extend_from_slice(&formatted[replacement.start as usize..replacement.end as usize]);
+            position = original.end;
+        }
+
+        output.extend_from_slice(&source[position as usize..]);
+
+        Ok(output.leak())
+    }
+
     /// Formats a pre-parsed [`Program`] (AST).
     ///
     /// This is the lowest-level formatting method that operates directly on the AST.
@@ -140,6 +248,59 @@ where
         program.format(&mut FormatterState::new(self.arena, program, file, self.php_version, self.settings))
     }
 
+    /// Formats a string of Twig template code.
+    ///
+    /// This is the Twig counterpart of [`Formatter::format_code`].
+    ///
+    /// # Errors
+    ///
+    /// Returns a [`TemplateParseError`] if the template contains syntax errors.
+    pub fn format_template_code(
+        &self,
+        name: Cow<'static, [u8]>,
+        code: Cow<'static, [u8]>,
+    ) -> Result<&'arena [u8], TemplateParseError<'arena>> {
+        let file = File::ephemeral(name, code);
+
+        self.format_template_file(&file)
+    }
+
+    /// Formats the contents of a Twig template [`File`].
+    ///
+    /// Only the inside of `{{ ... }}` and `{% ... %}` tags is reformatted; the text between
+    /// tags, comments, and `{% verbatim %}` blocks are kept as they are in the source.
+    ///
+    /// # Errors
+    ///
+    /// Returns the first [`TemplateParseError`] if the template contains syntax errors.
+    pub fn format_template_file<'ctx>(&self, file: &'ctx File) -> Result<&'arena [u8], TemplateParseError<'arena>> {
+        let template = parse_template_file(self.arena, file);
+        if let Some(error) = template.errors.first() {
+            return Err(error.clone());
+        }
+
+        Ok(self.format_template(file, template))
+    }
+
+    /// Formats a pre-parsed Twig [`Template`].
+    #[must_use]
+    pub fn format_template<'ctx>(&self, file: &'ctx File, template: &'arena Template<'arena>) -> &'arena [u8] {
+        let document = self.build_template(template);
+
+        self.print(document, Some(file.size as usize))
+    }
+
+    /// Converts a Twig template into a structured [`Document`] model.
+    #[must_use]
+    pub fn build_template(&self, template: &'arena Template<'arena>) -> Document<'arena, A> {
+        let state = TwigFormatterState::new(self.arena, template);
+        if state.has_format_ignore_comment() {
+            return Document::String(template.source_text);
+        }
+
+        state.format()
+    }
+
     /// Renders a [`Document`] model into a formatted string.
     ///
     /// The printer traverses the document model and generates the final text output
@@ -159,6 +320,14 @@ where
     }
 }
 
+/// Returns the start of the line holding `offset`, if only spaces and tabs precede `offset` on it.
+fn line_indentation_start(source: &[u8], offset: u32) -> Option<u32> {
+    let before = &source[..offset as usize];
+    let line_start = before.iter().rposition(|byte| *byte == b'\n').map_or(0, |position| position + 1);
+
+    before[line_start..].iter().all(|byte| *byte == b' ' || *byte == b'\t').then_some(line_start as u32)
+}
+
 /// Checks if any comment in the program contains a file-level format-ignore directive.
 ///
 /// This only matches `@mago-format-ignore` and `@mago-formatter-ignore` (without `-start`, `-end`, or `-next` suffix).
@@ -214,6 +214,10 @@ const DEFAULT_PRESET: FormatSettings = FormatSettings {
     separate_trait_use: true,
     indent_heredoc: true,
     uppercase_literal_keyword: false,
+    format_docblocks: false,
+    docblock_align_tags: true,
+    docblock_sort_tags: true,
+    docblock_wrap_descriptions: true,
 };
 
 /// The PSR-12 formatter preset.
@@ -249,6 +253,7 @@ const PINT_PRESET: FormatSettings = FormatSettings {
     space_around_concatenation_binary_operator: false,
     empty_line_before_return: true,
     separate_trait_use: false,
+    docblock_align_tags: false,
     ..DEFAULT_PRESET
 };
 
@@ -318,5 +323,6 @@ const DRUPAL_PRESET: FormatSettings = FormatSettings {
     empty_line_before_class_like_close: true,
     separate_trait_use: false,
     uppercase_literal_keyword: true,
+    docblock_align_tags: false,
     ..DEFAULT_PRESET
 };
@@ -1247,6 +1247,45 @@ generate_formatter_settings! {
     ///
     /// Default: false
     uppercase_literal_keyword: bool => "default_false",
+
+    /// Whether to format the contents of docblocks (`/** ... */` comments spanning multiple lines).
+    ///
+    /// When enabled, docblocks are normalised to one ` * `-prefixed line per line of content,
+    /// and the `docblock_*` settings below apply. Docblocks that cannot be parsed are left as-is.
+    ///
+    /// Default: false
+    format_docblocks: bool => "default_false",
+
+    /// Whether to align the types, parameter names, and descriptions of consecutive `@param` tags.
+    ///
+    /// Only applies when `format_docblocks` is enabled.
+    ///
+    /// Example:
+    /// ```php
+    /// /**
+    ///  * @param int    $id   The identifier.
+    ///  * @param string $name The name.
+    ///  */
+    /// ```
+    ///
+    /// Default: true
+    docblock_align_tags: bool => "default_true",
+
+    /// Whether to order `@template`, `@param`, `@return`, and `@throws` tags in that order.
+    ///
+    /// Other tags keep their position, and tags of the same kind keep their relative order.
+    /// Only applies when `format_docblocks` is enabled.
+    ///
+    /// Default: true
+    docblock_sort_tags: bool => "default_true",
+
+    /// Whether to wrap docblock lines that exceed `print_width`.
+    ///
+    /// Lines are only ever split, never joined, and code blocks are left untouched.
+    /// Only applies when `format_docblocks` is enabled.
+    ///
+    /// Default: true
+    docblock_wrap_descriptions: bool => "default_true",
 }
 
 impl Default for FormatSettings {
@@ -0,0 +1,44 @@
+<?php
+
+/**
+ * Short summary on first line.
+ * Example:
+ *
+ * ```php
+ * $x   =   foo(1,    2);
+ *
+ *
+ * bar();
+ * ```
+ *
+ * @psalm-param non-empty-string $name  The name of the thing, with
+ *                                      {@see Foo::bar()} inline and
+ *                                      continued here on the next line with
+ *                                      extra indentation that is long.
+ * @param       int              $count
+ *   Description on the next line.
+ * @deprecated   use something else
+ * @return void
+ * @see https://example.com/a/very/long/url/that/cannot/be/wrapped/anywhere/at/all/really
+ */
+function foo(string $name, int $count): void {}
+
+/**
+ * @param array{a: int $broken
+ */
+function bar($broken) {}
+
+/**
+ * @param int $a
+ *
+ * @return int
+ */
+function baz($a)
+{
+    return $a;
+}
+
+/**
+ * @var array<int, string> $x
+ */
+$x = [];
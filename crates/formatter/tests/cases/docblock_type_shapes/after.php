@@ -0,0 +1,37 @@
+<?php
+
+/**
+ * @param array{
+ *     id: int,
+ *     name: string,
+ *     email: string,
+ *     roles: list<string>,
+ * } $user The user to save.
+ * @param list<array{
+ *     id: int,
+ *     name?: string,
+ *     ...<string, mixed>
+ * }> $rows
+ * @return array{}|array{
+ *     ok: bool,
+ *     error?: string,
+ *     details: array{code: int, message: string},
+ * }
+ */
+function save(array $user, array $rows): array {}
+
+interface Repository
+{
+    /**
+     * @template T
+     *
+     * @param class-string<T> $class
+     *
+     * @return (T is Countable
+     *     ? list<T>
+     *     : (T is Stringable
+     *         ? non-empty-string
+     *         : array<string, T>))
+     */
+    public function find(string $class): mixed;
+}
@@ -0,0 +1,27 @@
+<?php
+
+/**
+ * Summary line.
+ *
+ * This is a long description that goes on and on and on and definitely exceeds the configured print width of the formatter.
+ * - a list item that is also very long and should be wrapped with a hanging indent under the list marker text
+ *
+ * @throws RuntimeException when things go wrong
+ * @return array{id: int, name: string, email: string, roles: list<string>, meta: array<string, mixed>} the user
+ * @param int $id the id
+ * @param array<string, mixed> $options the options which is a long description that should wrap nicely at the print width
+ * @param bool $force
+ * @template T of object
+ */
+function foo(int $id, array $options, bool $force) {}
+
+class A
+{
+    /**
+     * @param T $value
+     * @return (T is string
+     *     ? non-empty-string
+     *     : (T is int ? positive-int : array{some: int, other: string, third: float}))
+     */
+    public function bar($value) {}
+}
@@ -0,0 +1,21 @@
+<?php
+
+final class  Foo
+{
+    private   $a=1;
+
+    public function  bar( ) {
+        return  $this->a;
+    }
+
+    public function baz($x)
+    {
+        return $x + $this->a;
+    }
+
+    public function qux()
+    {
+        return 1;
+    }
+
+}
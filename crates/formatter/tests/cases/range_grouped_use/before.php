@@ -0,0 +1,6 @@
+<?php
+
+use A\{B, C};
+
+$x   =   1;
+$y   =   2;
@@ -0,0 +1,13 @@
+<?php
+
+$a   = 1;
+
+/**
+ * Doc.
+ */
+function foo()
+{
+    return 1;
+} // trailing
+
+$b   = 2;
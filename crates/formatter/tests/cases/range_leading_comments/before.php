@@ -0,0 +1,10 @@
+<?php
+
+$a   = 1;
+
+/**
+ * Doc.
+ */
+function   foo( ) { return   1; }  // trailing
+
+$b   = 2;
@@ -0,0 +1,13 @@
+<?php
+
+$a=[1,2,3];
+$b   =   2;
+
+function foo(){
+    $x   =   1;
+    if($x){
+        return   $x+1;
+    }
+    $untouched   =   true;
+    return   $x;
+}
@@ -0,0 +1,11 @@
+<?php
+
+function  foo( $a,$b )
+{
+    $x=$a+$b;
+    if($x>10){
+        $x = $x * 2;
+        $y=$x;
+    }
+    return $x;
+}
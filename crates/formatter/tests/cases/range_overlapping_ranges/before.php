@@ -0,0 +1,8 @@
+<?php
+
+$untouched   =   0;
+$a=foo(1,
+    2);
+$b=bar(3,
+    4);
+$c   =   5;
@@ -0,0 +1,10 @@
+<?php
+
+if ($a) {
+    $x = [
+        'aaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaa',
+        'bbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbb',
+        'cccccccccccccccccccccccccccccccccc',
+    ];
+  $y=2;
+}
@@ -0,0 +1,6 @@
+<?php
+
+if ($a) {
+  $x   =   ['aaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaa', 'bbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbb', 'cccccccccccccccccccccccccccccccccc'];
+  $y=2;
+}
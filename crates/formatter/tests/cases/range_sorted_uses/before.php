@@ -0,0 +1,6 @@
+<?php
+
+use B\Two;
+use A\One;
+
+$x   =   1;
@@ -0,0 +1,8 @@
+<?php
+
+$a=[1,2,3];
+
+$b  =  array_map( fn($x)=>$x*2,$a );
+echo   $b[0];
+
+$c=   'untouched';
@@ -6,7 +6,9 @@ use mago_allocator::LocalArena;
 use std::borrow::Cow;
 use std::fs;
 
+use mago_database::file::File;
 use mago_formatter::Formatter;
+use mago_formatter::error::RangeFormatError;
 use mago_formatter::settings::BraceStyle;
 use mago_formatter::settings::FormatSettings;
 use mago_formatter::settings::MethodChainBreakingStyle;
@@ -43,6 +45,81 @@ macro_rules! test_case {
     };
 }
 
+#[macro_export]
+macro_rules! template_test_case {
+    ($name:ident) => {
+        #[test]
+        pub fn $name() {
+            let code = include_bytes!(concat!("cases/", stringify!($name), "/before.twig"));
+            let expected = include_bytes!(concat!("cases/", stringify!($name), "/after.twig"));
+            let settings = include!(concat!("cases/", stringify!($name), "/settings.inc"));
+
+            let arena = LocalArena::new();
+            let formatter = Formatter::new(&arena, PHPVersion::PHP84, settings);
+
+            let formatted_code =
+                formatter.format_template_code(Cow::Borrowed(b"template.twig"), Cow::Borrowed(code)).unwrap();
+
+            pretty_assertions::assert_eq!(expected, formatted_code, "Formatted template does not match expected",);
+
+            let reformatted_code = formatter
+                .format_template_code(Cow::Borrowed(b"formatted_template.twig"), Cow::Owned(formatted_code.to_vec()))
+                .unwrap();
+
+            pretty_assertions::assert_eq!(expected, reformatted_code, "Reformatted template does not match expected",);
+        }
+    };
+}
+
+#[macro_export]
+macro_rules! range_test_case {
+    ($name:ident, not_isolated, $($first_line:literal..=$last_line:literal),+) => {
+        #[test]
+        pub fn $name() {
+            let code = include_bytes!(concat!("cases/", stringify!($name), "/before.php"));
+            let settings = include!(concat!("cases/", stringify!($name), "/settings.inc"));
+
+            let arena = LocalArena::new();
+            let formatter = Formatter::new(&arena, PHPVersion::PHP84, settings);
+
+            let file = File::ephemeral(Cow::Borrowed(b"code.php"), Cow::Borrowed(code));
+            let ranges = [$(
+                file.get_line_start_offset($first_line - 1).unwrap()..file.get_line_end_offset($last_line - 1).unwrap()
+            ),+];
+
+            pretty_assertions::assert_eq!(
+                formatter.format_ranges(&file, &ranges),
+                Err(RangeFormatError::RangeNotIsolated),
+                "Range formatting should fail when the range cannot be isolated",
+            );
+        }
+    };
+    ($name:ident, $($first_line:literal..=$last_line:literal),+) => {
+        #[test]
+        pub fn $name() {
+            let code = include_bytes!(concat!("cases/", stringify!($name), "/before.php"));
+            let expected = include_bytes!(concat!("cases/", stringify!($name), "/after.php"));
+            let settings = include!(concat!("cases/", stringify!($name), "/settings.inc"));
+
+            let arena = LocalArena::new();
+            let formatter = Formatter::new(&arena, PHPVersion::PHP84, settings);
+
+            let file = File::ephemeral(Cow::Borrowed(b"code.php"), Cow::Borrowed(code));
+            let ranges = [$(
+                file.get_line_start_offset($first_line - 1).unwrap()..file.get_line_end_offset($last_line - 1).unwrap()
+            ),+];
+
+            let formatted_code = formatter.format_ranges(&file, &ranges).unwrap();
+
+            pretty_assertions::assert_eq!(
+                String::from_utf8_lossy(expected),
+                String::from_utf8_lossy(formatted_code),
+                "Range formatted code does not match expected",
+            );
+        }
+    };
+}
+
 // Test cases
 test_case!(leading_comment_with_missing_prefix);
 test_case!(docblock_before_function_declaration);
@@ -507,6 +584,25 @@ test_case!(idempotency_corpus_phparkitect_arch_rule);
 test_case!(idempotency_corpus_apiplatform_schema_property);
 test_case!(idempotency_corpus_apiplatform_type_factory);
 test_case!(idempotency_corpus_symfony_json_streamer);
+test_case!(docblock_formatting);
+test_case!(docblock_type_shapes);
+test_case!(docblock_without_alignment_and_sorting);
+test_case!(docblock_code_and_unknown_tags);
+template_test_case!(template_tags);
+template_test_case!(template_expressions);
+template_test_case!(template_whitespace_control_and_comments);
+template_test_case!(template_breaking_lists);
+template_test_case!(template_format_ignore);
+range_test_case!(range_top_level_statements, 5..=6);
+range_test_case!(range_nested_statement, 7..=7);
+range_test_case!(range_class_members, 11..=16);
+range_test_case!(range_leading_comments, 8..=8);
+range_test_case!(range_between_statements, 4..=4);
+range_test_case!(range_multiple_ranges, 3..=3, 9..=9, 12..=12);
+range_test_case!(range_overlapping_ranges, 4..=5, 5..=7);
+range_test_case!(range_grouped_use, not_isolated, 6..=6);
+range_test_case!(range_sorted_uses, not_isolated, 3..=3);
+range_test_case!(range_reindented_statement, 4..=4);
 
 /// PHP identifiers are allowed to contain any byte ≥ 0x80, so a class name
 /// like `Café` is valid even when the source file is Latin-1 (or any other
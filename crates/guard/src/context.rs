@@ -2,6 +2,7 @@ use mago_codex::metadata::CodebaseMetadata;
 use mago_names::ResolvedNames;
 use mago_span::HasPosition;
 
+use crate::graph::DependencyGraph;
 use crate::report::FortressReport;
 use crate::report::breach::BoundaryBreach;
 use crate::report::flaw::StructuralFlaw;
@@ -16,6 +17,8 @@ pub struct GuardContext<'ctx, 'arena> {
     pub boundary_breaches: Vec<BoundaryBreach>,
     pub structural_flaws: Vec<StructuralFlaw>,
     pub current_namespace: Option<&'arena [u8]>,
+    /// The graph every checked dependency is recorded into, when one is being built.
+    pub dependency_graph: Option<DependencyGraph>,
 }
 
 impl<'ctx, 'arena> GuardContext<'ctx, 'arena> {
@@ -32,6 +35,7 @@ impl<'ctx, 'arena> GuardContext<'ctx, 'arena> {
             boundary_breaches: vec![],
             structural_flaws: vec![],
             current_namespace: None,
+            dependency_graph: None,
         }
     }
 
@@ -67,6 +71,8 @@ impl<'ctx, 'arena> GuardContext<'ctx, 'arena> {
         FortressReport {
             boundary_breaches: self.boundary_breaches,
             structural_flaws: self.structural_flaws,
+            dependency_cycles: vec![],
+            dependency_graph: self.dependency_graph,
             missing_perimeter_configuration: false,
             missing_structural_configuration: false,
         }
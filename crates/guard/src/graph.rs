@@ -0,0 +1,556 @@
+use std::collections::BTreeMap;
+use std::collections::VecDeque;
+use std::collections::btree_map::Entry;
+use std::fmt::Write;
+
+#[cfg(feature = "serde")]
+use serde::ser::SerializeStruct;
+#[cfg(feature = "serde")]
+use serde::ser::Serializer;
+
+use mago_span::Span;
+
+use crate::perimeter::checker::get_layer_index;
+use crate::report::cycle::CycleEdge;
+use crate::report::cycle::DependencyCycle;
+use crate::settings::PermittedDependencyKind;
+use crate::settings::Settings;
+
+/// The name given to the global namespace in the graph.
+pub const GLOBAL_NAMESPACE: &str = "@global";
+
+/// A namespace in the dependency graph.
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+pub struct DependencyNode {
+    /// The namespace, cut to the depth of the graph.
+    pub namespace: String,
+    /// The entry of `layering` the namespace belongs to, if any.
+    pub layer: Option<String>,
+    /// The number of classes, interfaces, and enums declared in the namespace.
+    pub class_likes: usize,
+    /// The number of abstract classes and interfaces declared in the namespace.
+    pub abstractions: usize,
+    /// The location of the first class-like declared in the namespace.
+    #[cfg_attr(feature = "serde", serde(skip))]
+    pub span: Option<Span>,
+}
+
+/// The aggregated dependencies of one namespace on another.
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+pub struct DependencyEdge {
+    /// The namespace holding the dependencies.
+    pub source: String,
+    /// The namespace the dependencies point to.
+    pub target: String,
+    /// The number of dependencies.
+    pub count: usize,
+    /// The number of dependencies per kind of symbol, keyed by `class-like`, `function`, and so on.
+    pub kinds: BTreeMap<&'static str, usize>,
+    /// The number of dependencies breaching the architectural rules.
+    pub breaches: usize,
+    /// The source code locations of the dependencies.
+    #[cfg_attr(feature = "serde", serde(skip))]
+    pub spans: Vec<Span>,
+}
+
+/// The dependencies between the namespaces of a codebase, as seen by the perimeter guard.
+///
+/// Namespaces are cut to `depth` segments, so that `App\Domain\User` and `App\Domain\Order`
+/// both count as `App\Domain` at depth 2. Dependencies within a namespace, and on built-in
+/// symbols, are left out.
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+pub struct DependencyGraph {
+    depth: Option<usize>,
+    /// The nodes, keyed by their lowercase namespace, as PHP namespaces are case-insensitive.
+    nodes: BTreeMap<String, DependencyNode>,
+    /// The edges, keyed by the keys of their source and target nodes.
+    edges: BTreeMap<(String, String), DependencyEdge>,
+}
+
+impl DependencyGraph {
+    /// Creates an empty graph, cutting namespaces to `depth` segments, or keeping them whole
+    /// when `depth` is `None`.
+    #[must_use]
+    pub fn new(depth: Option<usize>) -> Self {
+        Self { depth, nodes: BTreeMap::new(), edges: BTreeMap::new() }
+    }
+
+    /// The depth namespaces are cut to.
+    #[must_use]
+    pub const fn depth(&self) -> Option<usize> {
+        self.depth
+    }
+
+    /// The namespaces of the graph, sorted case-insensitively by name.
+    pub fn nodes(&self) -> impl Iterator<Item = &DependencyNode> {
+        self.nodes.values()
+    }
+
+    /// The edges of the graph, sorted case-insensitively by source and target.
+    pub fn edges(&self) -> impl Iterator<Item = &DependencyEdge> {
+        self.edges.values()
+    }
+
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.edges.is_empty()
+    }
+
+    /// Returns the same graph with namespaces cut to `depth` segments instead.
+    ///
+    /// `depth` can only make the graph coarser: cutting a graph of depth 2 to depth 3 leaves
+    /// it at depth 2.
+    #[must_use]
+    pub fn at_depth(&self, settings: &Settings, depth: Option<usize>) -> DependencyGraph {
+        let mut graph = DependencyGraph::new(depth);
+        for node in self.nodes.values() {
+            let (_, merged) = graph.node_mut(settings, namespace_bytes(&node.namespace));
+            merge_node(merged, node.clone());
+        }
+
+        for edge in self.edges.values() {
+            if let Some(merged) = graph.edge_mut(settings, namespace_bytes(&edge.source), namespace_bytes(&edge.target))
+            {
+                merge_edge(merged, edge.clone());
+            }
+        }
+
+        graph
+    }
+
+    /// Records a dependency of `source_namespace` on the symbol `dependency_fqn`.
+    pub(crate) fn add(
+        &mut self,
+        settings: &Settings,
+        source_namespace: &[u8],
+        dependency_fqn: &[u8],
+        dependency_kind: PermittedDependencyKind,
+        is_breach: bool,
+        span: Span,
+    ) {
+        let target_namespace = match dependency_fqn.iter().rposition(|byte| *byte == b'\\') {
+            Some(separator) => &dependency_fqn[..separator],
+            None => b"",
+        };
+
+        let Some(edge) = self.edge_mut(settings, source_namespace, target_namespace) else {
+            return;
+        };
+
+        edge.count += 1;
+        edge.spans.push(span);
+        *edge.kinds.entry(dependency_kind.as_str()).or_default() += 1;
+        if is_breach {
+            edge.breaches += 1;
+        }
+    }
+
+    /// Records a class-like declared in `namespace`.
+    pub(crate) fn add_class_like(&mut self, settings: &Settings, namespace: &[u8], is_abstract: bool, span: Span) {
+        merge_node(
+            self.node_mut(settings, namespace).1,
+            DependencyNode {
+                namespace: String::new(),
+                layer: None,
+                class_likes: 1,
+                abstractions: usize::from(is_abstract),
+                span: Some(span),
+            },
+        );
+    }
+
+    /// Merges the nodes and edges of another graph into this one.
+    pub fn extend(&mut self, other: DependencyGraph) {
+        for (key, other_node) in other.nodes {
+            match self.nodes.get_mut(&key) {
+                Some(node) => merge_node(node, other_node),
+                None => {
+                    self.nodes.insert(key, other_node);
+                }
+            }
+        }
+
+        for (key, other_edge) in other.edges {
+            match self.edges.get_mut(&key) {
+                Some(edge) => merge_edge(edge, other_edge),
+                None => {
+                    self.edges.insert(key, other_edge);
+                }
+            }
+        }
+    }
+
+    /// Finds the sets of namespaces that depend on each other, directly or transitively.
+    ///
+    /// Each cycle is a strongly connected component of the graph holding more than one
+    /// namespace, reported with every edge between its namespaces.
+    #[must_use]
+    pub fn find_cycles(&self) -> Vec<DependencyCycle> {
+        let keys: Vec<&str> = self.nodes.keys().map(String::as_str).collect();
+        let namespaces: Vec<&str> = self.nodes.values().map(|node| node.namespace.as_str()).collect();
+        let mut successors: Vec<Vec<usize>> = vec![Vec::new(); keys.len()];
+        for (source, target) in self.edges.keys() {
+            if let (Ok(source), Ok(target)) =
+                (keys.binary_search(&source.as_str()), keys.binary_search(&target.as_str()))
+            {
+                successors[source].push(target);
+            }
+        }
+
+        let mut cycles = Vec::new();
+        for component in strongly_connected_components(&successors) {
+            if component.len() < 2 {
+                continue;
+            }
+
+            let path =
+                shortest_loop(&successors, &component).into_iter().map(|index| namespaces[index].to_string()).collect();
+
+            let mut members: Vec<String> = component.iter().map(|index| namespaces[*index].to_string()).collect();
+            members.sort();
+
+            let mut member_keys: Vec<&str> = component.iter().map(|index| keys[*index]).collect();
+            member_keys.sort_unstable();
+
+            let edges = self
+                .edges
+                .iter()
+                .filter(|((source, target), _)| {
+                    member_keys.binary_search(&source.as_str()).is_ok()
+                        && member_keys.binary_search(&target.as_str()).is_ok()
+                })
+                .map(|(_, edge)| CycleEdge {
+                    source: edge.source.clone(),
+                    target: edge.target.clone(),
+                    spans: edge.spans.clone(),
+                })
+                .collect();
+
+            cycles.push(DependencyCycle { namespaces: members, path, edges });
+        }
+
+        cycles.sort_by(|a, b| a.namespaces.cmp(&b.namespaces));
+        cycles
+    }
+
+    /// Renders the graph in the DOT language of Graphviz.
+    ///
+    /// Namespaces belonging to a layer are grouped in a cluster per layer, and edges holding
+    /// breaches are drawn in red.
+    #[must_use]
+    pub fn to_dot(&self) -> String {
+        let mut output = String::from("digraph dependencies {\n    rankdir=LR;\n    node [shape=box];\n");
+
+        for (index, (layer, nodes)) in self.layers().iter().enumerate() {
+            match layer {
+                Some(layer) => {
+                    let _ = writeln!(output, "    subgraph cluster_{index} {{");
+                    let _ = writeln!(output, "        label={};", dot_string(layer));
+                    for node in nodes {
+                        let _ = writeln!(output, "        {};", dot_string(&node.namespace));
+                    }
+                    output.push_str("    }\n");
+                }
+                None => {
+                    for node in nodes {
+                        let _ = writeln!(output, "    {};", dot_string(&node.namespace));
+                    }
+                }
+            }
+        }
+
+        for edge in self.edges.values() {
+            let mut attributes = format!("label={}", dot_string(&edge_label(edge)));
+            if edge.breaches > 0 {
+                attributes.push_str(", color=red, fontcolor=red, penwidth=2");
+            }
+
+            let _ =
+                writeln!(output, "    {} -> {} [{attributes}];", dot_string(&edge.source), dot_string(&edge.target));
+        }
+
+        output.push_str("}\n");
+        output
+    }
+
+    /// Renders the graph as a Mermaid flowchart.
+    ///
+    /// Namespaces belonging to a layer are grouped in a subgraph per layer, and edges holding
+    /// breaches are drawn in red.
+    #[must_use]
+    pub fn to_mermaid(&self) -> String {
+        let identifiers: BTreeMap<&str, String> =
+            self.nodes.keys().enumerate().map(|(index, namespace)| (namespace.as_str(), format!("n{index}"))).collect();
+
+        let mut output = String::from("flowchart LR\n");
+        for (index, (layer, nodes)) in self.layers().iter().enumerate() {
+            let indentation = if let Some(layer) = layer {
+                let _ = writeln!(output, "    subgraph layer{index}[{}]", mermaid_string(layer));
+                "        "
+            } else {
+                "    "
+            };
+
+            for node in nodes {
+                let _ = writeln!(
+                    output,
+                    "{indentation}{}[{}]",
+                    identifiers[node.namespace.as_str()],
+                    mermaid_string(&node.namespace)
+                );
+            }
+
+            if layer.is_some() {
+                output.push_str("    end\n");
+            }
+        }
+
+        let mut breaching_edges = Vec::new();
+        for (index, edge) in self.edges.values().enumerate() {
+            let _ = writeln!(
+                output,
+                "    {} -->|{}| {}",
+                identifiers[edge.source.as_str()],
+                mermaid_string(&edge_label(edge)),
+                identifiers[edge.target.as_str()]
+            );
+
+            if edge.breaches > 0 {
+                breaching_edges.push(index.to_string());
+            }
+        }
+
+        if !breaching_edges.is_empty() {
+            let _ =
+                writeln!(output, "    linkStyle {} stroke:#d00,stroke-width:2px,color:#d00", breaching_edges.join(","));
+        }
+
+        output
+    }
+
+    /// The key and node of `namespace`, created if missing.
+    fn node_mut(&mut self, settings: &Settings, namespace: &[u8]) -> (String, &mut DependencyNode) {
+        let namespace = match self.depth {
+            Some(depth) => namespace.split(|byte| *byte == b'\\').take(depth).collect::<Vec<_>>().join(&b'\\'),
+            None => namespace.to_vec(),
+        };
+
+        let name = if namespace.is_empty() {
+            GLOBAL_NAMESPACE.to_string()
+        } else {
+            String::from_utf8_lossy(&namespace).into_owned()
+        };
+
+        let key = name.to_ascii_lowercase();
+        let node = self.nodes.entry(key.clone()).or_insert_with(|| {
+            let layer =
+                get_layer_index(&namespace, settings).map(|index| settings.perimeter.layering[index].to_string());
+
+            DependencyNode { namespace: name, layer, class_likes: 0, abstractions: 0, span: None }
+        });
+
+        (key, node)
+    }
+
+    /// The edge from `source_namespace` to `target_namespace`, created if missing, or `None`
+    /// when both are the same namespace at the depth of the graph.
+    fn edge_mut(
+        &mut self,
+        settings: &Settings,
+        source_namespace: &[u8],
+        target_namespace: &[u8],
+    ) -> Option<&mut DependencyEdge> {
+        let (source_key, source) = self.node_mut(settings, source_namespace);
+        let source = source.namespace.clone();
+        let (target_key, target) = self.node_mut(settings, target_namespace);
+        let target = target.namespace.clone();
+        if source_key == target_key {
+            return None;
+        }
+
+        Some(self.edges.entry((source_key, target_key)).or_insert_with(|| DependencyEdge {
+            source,
+            target,
+            count: 0,
+            kinds: BTreeMap::new(),
+            breaches: 0,
+            spans: Vec::new(),
+        }))
+    }
+
+    /// The nodes grouped by layer, in the order of `layering`, followed by the nodes outside
+    /// any layer.
+    fn layers(&self) -> Vec<(Option<&str>, Vec<&DependencyNode>)> {
+        let mut layers: Vec<(Option<&str>, Vec<&DependencyNode>)> = Vec::new();
+        for node in self.nodes.values() {
+            let layer = node.layer.as_deref();
+            match layers.iter_mut().find(|(existing, _)| *existing == layer) {
+                Some((_, nodes)) => nodes.push(node),
+                None => layers.push((layer, vec![node])),
+            }
+        }
+
+        layers.sort_by_key(|(layer, _)| layer.is_none());
+        layers
+    }
+}
+
+#[cfg(feature = "serde")]
+impl serde::Serialize for DependencyGraph {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        let mut state = serializer.serialize_struct("DependencyGraph", 3)?;
+        state.serialize_field("depth", &self.depth)?;
+        state.serialize_field("nodes", &self.nodes.values().collect::<Vec<_>>())?;
+        state.serialize_field("edges", &self.edges.values().collect::<Vec<_>>())?;
+        state.end()
+    }
+}
+
+/// Tarjan's algorithm, returning the strongly connected components of a graph given as the
+/// successors of each node.
+fn strongly_connected_components(successors: &[Vec<usize>]) -> Vec<Vec<usize>> {
+    struct State<'graph> {
+        successors: &'graph [Vec<usize>],
+        next_index: usize,
+        indices: Vec<Option<usize>>,
+        low_links: Vec<usize>,
+        stack: Vec<usize>,
+        on_stack: Vec<bool>,
+        components: Vec<Vec<usize>>,
+    }
+
+    fn connect(state: &mut State<'_>, node: usize) {
+        state.indices[node] = Some(state.next_index);
+        state.low_links[node] = state.next_index;
+        state.next_index += 1;
+        state.stack.push(node);
+        state.on_stack[node] = true;
+
+        for &successor in &state.successors[node] {
+            match state.indices[successor] {
+                None => {
+                    connect(state, successor);
+                    state.low_links[node] = state.low_links[node].min(state.low_links[successor]);
+                }
+                Some(index) if state.on_stack[successor] => {
+                    state.low_links[node] = state.low_links[node].min(index);
+                }
+                Some(_) => {}
+            }
+        }
+
+        if Some(state.low_links[node]) == state.indices[node] {
+            let mut component = Vec::new();
+            while let Some(member) = state.stack.pop() {
+                state.on_stack[member] = false;
+                component.push(member);
+                if member == node {
+                    break;
+                }
+            }
+
+            state.components.push(component);
+        }
+    }
+
+    let mut state = State {
+        successors,
+        next_index: 0,
+        indices: vec![None; successors.len()],
+        low_links: vec![0; successors.len()],
+        stack: Vec::new(),
+        on_stack: vec![false; successors.len()],
+        components: Vec::new(),
+    };
+
+    for node in 0..successors.len() {
+        if state.indices[node].is_none() {
+            connect(&mut state, node);
+        }
+    }
+
+    state.components
+}
+
+/// The shortest loop from the smallest node of a strongly connected component back to itself.
+fn shortest_loop(successors: &[Vec<usize>], component: &[usize]) -> Vec<usize> {
+    let Some(&start) = component.iter().min() else {
+        return Vec::new();
+    };
+
+    let mut previous: BTreeMap<usize, usize> = BTreeMap::new();
+    let mut queue = VecDeque::from([start]);
+    while let Some(node) = queue.pop_front() {
+        for &successor in &successors[node] {
+            if !component.contains(&successor) {
+                continue;
+            }
+
+            if successor == start {
+                let mut path = vec![start, node];
+                let mut current = node;
+                while let Some(&before) = previous.get(&current) {
+                    path.push(before);
+                    current = before;
+                }
+
+                path.reverse();
+
+                return path;
+            }
+
+            if let Entry::Vacant(entry) = previous.entry(successor) {
+                entry.insert(node);
+                queue.push_back(successor);
+            }
+        }
+    }
+
+    vec![start]
+}
+
+fn merge_node(node: &mut DependencyNode, other: DependencyNode) {
+    node.class_likes += other.class_likes;
+    node.abstractions += other.abstractions;
+    node.span = match (node.span, other.span) {
+        (Some(span), Some(other_span)) => Some(span.min(other_span)),
+        (span, other_span) => span.or(other_span),
+    };
+}
+
+fn merge_edge(edge: &mut DependencyEdge, other: DependencyEdge) {
+    edge.count += other.count;
+    edge.breaches += other.breaches;
+    edge.spans.extend(other.spans);
+    for (kind, count) in other.kinds {
+        *edge.kinds.entry(kind).or_default() += count;
+    }
+}
+
+/// The bytes of a namespace as named in the graph.
+fn namespace_bytes(namespace: &str) -> &[u8] {
+    if namespace == GLOBAL_NAMESPACE { b"" } else { namespace.as_bytes() }
+}
+
+/// The label of an edge: its count per kind, followed by its breaches.
+fn edge_label(edge: &DependencyEdge) -> String {
+    let mut label = edge.kinds.iter().map(|(kind, count)| format!("{kind}: {count}")).collect::<Vec<_>>().join(", ");
+    if edge.breaches > 0 {
+        let _ = write!(label, " ({} breach{})", edge.breaches, if edge.breaches == 1 { "" } else { "es" });
+    }
+
+    label
+}
+
+fn dot_string(value: &str) -> String {
+    format!("\"{}\"", value.replace('\\', "\\\\").replace('"', "\\\""))
+}
+
+fn mermaid_string(value: &str) -> String {
+    format!("\"{}\"", value.replace('"', "#quot;"))
+}
@@ -4,11 +4,19 @@ use mago_syntax::cst::Program;
 use mago_syntax::walker::MutWalker;
 
 use crate::context::GuardContext;
+use crate::graph::DependencyGraph;
+use crate::metrics::PackageMetric;
+use crate::metrics::package_metrics;
 use crate::perimeter::DependenciesGuardWalker;
 use crate::report::FortressReport;
+use crate::report::flaw::FlawKind;
+use crate::report::flaw::StructuralFlaw;
 use crate::settings::Settings;
+use crate::settings::StructuralSymbolKind;
 use crate::structural::StructuralGuardWalker;
 
+pub mod graph;
+pub mod metrics;
 pub mod path;
 pub mod report;
 pub mod settings;
@@ -53,9 +61,14 @@ impl ArchitecturalGuard {
         resolved_names: &'ast ResolvedNames<'arena>,
     ) -> FortressReport {
         let mut context = GuardContext::new(resolved_names, &self.settings, codebase);
+        let records_dependency_graph = self.records_dependency_graph();
+        if records_dependency_graph {
+            context.dependency_graph = Some(DependencyGraph::new(None));
+        }
 
         let mut missing_perimeter_configuration = false;
         let mut missing_structural_configuration = false;
+        let mut dependency_graph = None;
 
         // Run perimeter guard based on settings
         match self.settings.should_run_perimeter() {
@@ -66,9 +79,18 @@ impl ArchitecturalGuard {
             }
         }
 
+        // The metrics need the dependency graph even when the perimeter guard does not run,
+        // so it is recorded in a separate walk that reports no breaches.
+        if records_dependency_graph && self.settings.should_run_perimeter() != Some(true) {
+            let mut graph_context = GuardContext::new(resolved_names, &self.settings, codebase);
+            graph_context.dependency_graph = context.dependency_graph.take();
+            DependenciesGuardWalker.walk_program(program, &mut graph_context);
+            dependency_graph = graph_context.dependency_graph;
+        }
+
         // Run structural guard based on settings
         match self.settings.should_run_structural() {
-            Some(true) => StructuralGuardWalker.walk_program(program, &mut context),
+            Some(true) => StructuralGuardWalker::default().walk_program(program, &mut context),
             Some(false) => missing_structural_configuration = true,
             None => {
                 // Mode doesn't allow structural
@@ -76,8 +98,137 @@ impl ArchitecturalGuard {
         }
 
         let mut report = context.report();
+        if dependency_graph.is_some() {
+            report.dependency_graph = dependency_graph;
+        }
+
         report.missing_perimeter_configuration = missing_perimeter_configuration;
         report.missing_structural_configuration = missing_structural_configuration;
         report
     }
+
+    /// Finds the dependency cycles between namespaces in the merged dependency graphs of all
+    /// programs, as recorded by [`check`](Self::check) when cycle detection is enabled.
+    ///
+    /// # Arguments
+    ///
+    /// * `graph` - The dependency graph of the whole codebase
+    ///
+    /// # Returns
+    ///
+    /// A `FortressReport` with one entry per dependency cycle.
+    #[must_use]
+    pub fn check_cycles(&self, graph: &DependencyGraph) -> FortressReport {
+        if !self.settings.cycles.enabled || !self.settings.mode.includes_perimeter() {
+            return FortressReport::default();
+        }
+
+        let graph = graph.at_depth(&self.settings, self.settings.cycles.depth);
+
+        FortressReport { dependency_cycles: graph.find_cycles(), ..FortressReport::default() }
+    }
+
+    /// Checks the coupling metrics of every package against the configured thresholds, in
+    /// the merged dependency graphs of all programs as recorded by [`check`](Self::check).
+    ///
+    /// # Arguments
+    ///
+    /// * `graph` - The dependency graph of the whole codebase
+    ///
+    /// # Returns
+    ///
+    /// A `FortressReport` with one structural flaw per threshold a package does not meet.
+    #[must_use]
+    pub fn check_metrics(&self, graph: &DependencyGraph) -> FortressReport {
+        let metrics_settings = &self.settings.structural.metrics;
+        if metrics_settings.thresholds.is_empty() || !self.settings.mode.includes_structural() {
+            return FortressReport::default();
+        }
+
+        let graph = graph.at_depth(&self.settings, metrics_settings.depth);
+        let mut structural_flaws = vec![];
+        for package in package_metrics(&graph) {
+            let Some(span) = package.span else {
+                continue;
+            };
+
+            for threshold in &metrics_settings.thresholds {
+                if !matcher::matches(package.namespace.as_bytes(), threshold.on.as_bytes(), false, false)
+                    || threshold.not_on.as_ref().is_some_and(|not_on| {
+                        matcher::matches(package.namespace.as_bytes(), not_on.as_bytes(), false, false)
+                    })
+                {
+                    continue;
+                }
+
+                #[allow(clippy::cast_precision_loss)]
+                let bounds = [
+                    (PackageMetric::AfferentCoupling, None, threshold.max_afferent_coupling.map(|limit| limit as f64)),
+                    (PackageMetric::EfferentCoupling, None, threshold.max_efferent_coupling.map(|limit| limit as f64)),
+                    (PackageMetric::Instability, threshold.min_instability, threshold.max_instability),
+                    (PackageMetric::Abstractness, threshold.min_abstractness, threshold.max_abstractness),
+                    (PackageMetric::Distance, None, threshold.max_distance),
+                ];
+
+                for (metric, minimum, maximum) in bounds {
+                    let value = package.value(metric);
+                    let out_of_bounds = match (minimum, maximum) {
+                        (_, Some(maximum)) if value > maximum => Some((maximum, true)),
+                        (Some(minimum), _) if value < minimum => Some((minimum, false)),
+                        _ => None,
+                    };
+
+                    if let Some((limit, is_maximum)) = out_of_bounds {
+                        structural_flaws.push(StructuralFlaw {
+                            symbol_fqn: package.namespace.as_bytes().to_vec(),
+                            symbol_kind: StructuralSymbolKind::ClassLike,
+                            span,
+                            kind: FlawKind::MetricOutOfBounds { metric, value, limit, is_maximum },
+                            reason: threshold.reason.clone(),
+                        });
+                    }
+                }
+            }
+        }
+
+        FortressReport { structural_flaws, ..FortressReport::default() }
+    }
+
+    /// Whether [`check`](Self::check) records the dependency graph of the program, for the
+    /// checks that need the whole codebase.
+    fn records_dependency_graph(&self) -> bool {
+        (self.settings.cycles.enabled && self.settings.mode.includes_perimeter())
+            || (!self.settings.structural.metrics.thresholds.is_empty() && self.settings.mode.includes_structural())
+    }
+
+    /// Builds the graph of the dependencies between the namespaces of a program.
+    ///
+    /// Every dependency the perimeter guard checks is recorded, whether or not the rules
+    /// allow it, and the ones they do not allow are counted as breaches on their edge.
+    ///
+    /// # Arguments
+    ///
+    /// * `codebase` - The codebase metadata for symbol lookups
+    /// * `program` - The AST of the program
+    /// * `resolved_names` - The resolved names for the program
+    /// * `depth` - The number of namespace segments to keep, or `None` to keep them all
+    ///
+    /// # Returns
+    ///
+    /// A `DependencyGraph` of the program, to be merged with those of other programs.
+    #[must_use]
+    pub fn graph<'ast, 'arena>(
+        &self,
+        codebase: &CodebaseMetadata,
+        program: &'ast Program<'arena>,
+        resolved_names: &'ast ResolvedNames<'arena>,
+        depth: Option<usize>,
+    ) -> DependencyGraph {
+        let mut context = GuardContext::new(resolved_names, &self.settings, codebase);
+        context.dependency_graph = Some(DependencyGraph::new(depth));
+
+        DependenciesGuardWalker.walk_program(program, &mut context);
+
+        context.dependency_graph.unwrap_or_default()
+    }
 }
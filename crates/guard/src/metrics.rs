@@ -0,0 +1,122 @@
+use std::fmt;
+
+use mago_span::Span;
+
+use crate::graph::DependencyGraph;
+
+/// One of Robert C. Martin's package coupling metrics.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PackageMetric {
+    /// The number of packages depending on the package (Ca).
+    AfferentCoupling,
+    /// The number of packages the package depends on (Ce).
+    EfferentCoupling,
+    /// `Ce / (Ca + Ce)`: how much the package is subject to change (I).
+    Instability,
+    /// The share of abstract classes and interfaces among the class-likes of the package (A).
+    Abstractness,
+    /// `|A + I - 1|`: how far the package is from the balance of stability and abstractness (D).
+    Distance,
+}
+
+/// The coupling metrics of one package, a namespace cut to the depth of the graph.
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+pub struct PackageMetrics {
+    /// The namespace of the package.
+    pub namespace: String,
+    /// The number of classes, interfaces, and enums declared in the package.
+    pub class_likes: usize,
+    /// The number of abstract classes and interfaces declared in the package.
+    pub abstractions: usize,
+    /// The number of packages depending on the package.
+    pub afferent_coupling: usize,
+    /// The number of packages the package depends on.
+    pub efferent_coupling: usize,
+    /// `Ce / (Ca + Ce)`, or `0.0` when the package has no coupling at all.
+    pub instability: f64,
+    /// `abstractions / class_likes`.
+    pub abstractness: f64,
+    /// `|A + I - 1|`.
+    pub distance: f64,
+    /// The location of the first class-like declared in the package.
+    #[cfg_attr(feature = "serde", serde(skip))]
+    pub span: Option<Span>,
+}
+
+impl PackageMetric {
+    /// Returns the name of the metric.
+    #[must_use]
+    pub const fn as_str(&self) -> &'static str {
+        match self {
+            Self::AfferentCoupling => "afferent coupling",
+            Self::EfferentCoupling => "efferent coupling",
+            Self::Instability => "instability",
+            Self::Abstractness => "abstractness",
+            Self::Distance => "distance from the main sequence",
+        }
+    }
+
+    /// Formats a value of the metric: coupling as a count, the others as a ratio.
+    #[must_use]
+    pub fn format_value(&self, value: f64) -> String {
+        match self {
+            Self::AfferentCoupling | Self::EfferentCoupling => format!("{value:.0}"),
+            Self::Instability | Self::Abstractness | Self::Distance => format!("{value:.2}"),
+        }
+    }
+}
+
+impl PackageMetrics {
+    /// Returns the value of a metric.
+    #[must_use]
+    #[allow(clippy::cast_precision_loss)]
+    pub fn value(&self, metric: PackageMetric) -> f64 {
+        match metric {
+            PackageMetric::AfferentCoupling => self.afferent_coupling as f64,
+            PackageMetric::EfferentCoupling => self.efferent_coupling as f64,
+            PackageMetric::Instability => self.instability,
+            PackageMetric::Abstractness => self.abstractness,
+            PackageMetric::Distance => self.distance,
+        }
+    }
+}
+
+impl fmt::Display for PackageMetric {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.as_str())
+    }
+}
+
+/// Computes the coupling metrics of every package of the graph declaring at least one class-like.
+///
+/// Coupling is counted in packages: a package depending on three classes of another package
+/// adds one to its efferent coupling, and one to the afferent coupling of the other.
+#[must_use]
+#[allow(clippy::cast_precision_loss, clippy::float_arithmetic)]
+pub fn package_metrics(graph: &DependencyGraph) -> Vec<PackageMetrics> {
+    graph
+        .nodes()
+        .filter(|node| node.class_likes > 0)
+        .map(|node| {
+            let afferent_coupling = graph.edges().filter(|edge| edge.target == node.namespace).count();
+            let efferent_coupling = graph.edges().filter(|edge| edge.source == node.namespace).count();
+            let coupling = afferent_coupling + efferent_coupling;
+
+            let instability = if coupling == 0 { 0.0 } else { efferent_coupling as f64 / coupling as f64 };
+            let abstractness = node.abstractions as f64 / node.class_likes as f64;
+
+            PackageMetrics {
+                namespace: node.namespace.clone(),
+                class_likes: node.class_likes,
+                abstractions: node.abstractions,
+                afferent_coupling,
+                efferent_coupling,
+                instability,
+                abstractness,
+                distance: (abstractness + instability - 1.0).abs(),
+                span: node.span,
+            }
+        })
+        .collect()
+}
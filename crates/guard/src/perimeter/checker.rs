@@ -30,7 +30,22 @@ pub fn check_usage(
     vector: BreachVector,
     span: Span,
 ) {
-    if let Some(reason) = check_allowed(ctx, dependency_fqn, dependency_kind) {
+    let breach_reason = check_allowed(ctx, dependency_fqn, dependency_kind);
+
+    if let Some(graph) = ctx.dependency_graph.as_mut()
+        && !is_native(ctx.codebase, dependency_fqn)
+    {
+        graph.add(
+            ctx.settings,
+            ctx.current_namespace.unwrap_or(b""),
+            dependency_fqn,
+            dependency_kind,
+            breach_reason.is_some(),
+            span,
+        );
+    }
+
+    if let Some(reason) = breach_reason {
         ctx.boundary_breaches.push(BoundaryBreach {
             source_namespace: ctx.get_current_namespace().to_vec(),
             dependency_fqn: dependency_fqn.to_vec(),
@@ -170,7 +185,7 @@ fn is_native(codebase: &CodebaseMetadata, fqn: &[u8]) -> bool {
         .is_some_and(|flags| flags.is_built_in())
 }
 
-fn get_layer_index(namespace: &[u8], settings: &Settings) -> Option<usize> {
+pub(crate) fn get_layer_index(namespace: &[u8], settings: &Settings) -> Option<usize> {
     for (i, layer_namespace) in settings.perimeter.layering.iter().enumerate() {
         match layer_namespace {
             NamespacePath::Global if namespace.is_empty() => {
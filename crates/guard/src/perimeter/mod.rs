@@ -1,9 +1,12 @@
-mod checker;
+pub(crate) mod checker;
 
 use mago_span::HasSpan;
+use mago_span::Span;
 use mago_syntax::cst::Attribute;
+use mago_syntax::cst::Class;
 use mago_syntax::cst::ClassConstantAccess;
 use mago_syntax::cst::ConstantAccess;
+use mago_syntax::cst::Enum;
 use mago_syntax::cst::Expression;
 use mago_syntax::cst::Extends;
 use mago_syntax::cst::FunctionCall;
@@ -13,6 +16,8 @@ use mago_syntax::cst::FunctionPartialApplication;
 use mago_syntax::cst::Hint;
 use mago_syntax::cst::Implements;
 use mago_syntax::cst::Instantiation;
+use mago_syntax::cst::Interface;
+use mago_syntax::cst::ModifierSequenceExt;
 use mago_syntax::cst::Namespace;
 use mago_syntax::cst::Property;
 use mago_syntax::cst::StaticMethodCall;
@@ -60,6 +65,15 @@ impl DependenciesGuardWalker {
             _ => {}
         }
     }
+
+    /// Records a class-like declared in the current namespace into the dependency graph, if
+    /// one is being built.
+    fn record_class_like(context: &mut GuardContext<'_, '_>, is_abstract: bool, span: Span) {
+        let namespace = context.get_current_namespace();
+        if let Some(graph) = context.dependency_graph.as_mut() {
+            graph.add_class_like(context.settings, namespace, is_abstract, span);
+        }
+    }
 }
 
 impl<'ast, 'ctx, 'arena> MutWalker<'ast, 'arena, GuardContext<'ctx, 'arena>> for DependenciesGuardWalker {
@@ -71,6 +85,18 @@ impl<'ast, 'ctx, 'arena> MutWalker<'ast, 'arena, GuardContext<'ctx, 'arena>> for
         context.set_current_namespace(None);
     }
 
+    fn walk_in_class(&mut self, class: &'ast Class<'arena>, context: &mut GuardContext<'ctx, 'arena>) {
+        Self::record_class_like(context, class.modifiers.contains_abstract(), class.name.span);
+    }
+
+    fn walk_in_interface(&mut self, interface: &'ast Interface<'arena>, context: &mut GuardContext<'ctx, 'arena>) {
+        Self::record_class_like(context, true, interface.name.span);
+    }
+
+    fn walk_in_enum(&mut self, r#enum: &'ast Enum<'arena>, context: &mut GuardContext<'ctx, 'arena>) {
+        Self::record_class_like(context, false, r#enum.name.span);
+    }
+
     // Check use statements
     fn walk_in_use(&mut self, r#use: &'ast Use<'arena>, context: &mut GuardContext<'ctx, 'arena>) {
         match &r#use.items {
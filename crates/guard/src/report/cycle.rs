@@ -0,0 +1,89 @@
+use std::fmt::Write;
+
+use mago_reporting::Annotation;
+use mago_reporting::Issue;
+use mago_span::Span;
+
+/// The number of dependencies annotated per edge of a cycle; the rest are only counted.
+const ANNOTATED_DEPENDENCIES_PER_EDGE: usize = 5;
+
+/// Represents a set of namespaces that depend on each other, directly or transitively.
+#[derive(Debug)]
+pub struct DependencyCycle {
+    /// The namespaces in the cycle, sorted by name.
+    pub namespaces: Vec<String>,
+    /// One loop through the cycle, starting and ending at the same namespace.
+    pub path: Vec<String>,
+    /// The edges between the namespaces of the cycle.
+    pub edges: Vec<CycleEdge>,
+}
+
+/// The dependencies of one namespace of a cycle on another.
+#[derive(Debug)]
+pub struct CycleEdge {
+    /// The namespace holding the dependencies.
+    pub source: String,
+    /// The namespace the dependencies point to.
+    pub target: String,
+    /// The source code locations of the dependencies.
+    pub spans: Vec<Span>,
+}
+
+impl DependencyCycle {
+    #[must_use]
+    pub const fn error_code(&self) -> &'static str {
+        "dependency-cycle"
+    }
+
+    /// The edge holding the fewest dependencies, which is the cheapest one to cut.
+    #[must_use]
+    pub fn weakest_edge(&self) -> Option<&CycleEdge> {
+        self.edges.iter().min_by_key(|edge| edge.spans.len())
+    }
+}
+
+impl From<DependencyCycle> for Issue {
+    fn from(cycle: DependencyCycle) -> Self {
+        let namespaces = cycle.namespaces.iter().map(|namespace| format!("`{namespace}`")).collect::<Vec<_>>();
+        let mut issue = Issue::error(format!("Dependency cycle between {}", namespaces.join(", ")));
+
+        let weakest = cycle.weakest_edge().map(|edge| (edge.source.clone(), edge.target.clone(), edge.spans.len()));
+        for edge in &cycle.edges {
+            let is_weakest =
+                weakest.as_ref().is_some_and(|(source, target, _)| *source == edge.source && *target == edge.target);
+
+            for (index, span) in edge.spans.iter().take(ANNOTATED_DEPENDENCIES_PER_EDGE).enumerate() {
+                let message = format!("`{}` depends on `{}` here", edge.source, edge.target);
+                issue = issue.with_annotation(if is_weakest && index == 0 {
+                    Annotation::primary(*span).with_message(message)
+                } else {
+                    Annotation::secondary(*span).with_message(message)
+                });
+            }
+
+            let count = edge.spans.len();
+            let mut note = format!(
+                "`{}` → `{}`: {count} dependenc{}.",
+                edge.source,
+                edge.target,
+                if count == 1 { "y" } else { "ies" }
+            );
+            if count > ANNOTATED_DEPENDENCIES_PER_EDGE {
+                let _ = write!(note, " Only the first {ANNOTATED_DEPENDENCIES_PER_EDGE} are shown.");
+            }
+
+            issue = issue.with_note(note);
+        }
+
+        let path = cycle.path.iter().map(|namespace| format!("`{namespace}`")).collect::<Vec<_>>().join(" → ");
+        issue = issue.with_note(format!("The namespaces depend on each other through {path}."));
+
+        match weakest {
+            Some((source, target, count)) => issue.with_help(format!(
+                "Start with the {count} dependenc{} of `{source}` on `{target}`, the fewest of any edge in the cycle, or move the shared code into a namespace both can depend on.",
+                if count == 1 { "y" } else { "ies" }
+            )),
+            None => issue,
+        }
+    }
+}
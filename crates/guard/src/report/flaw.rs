@@ -5,6 +5,7 @@ use mago_reporting::Annotation;
 use mago_reporting::Issue;
 use mago_span::Span;
 
+use crate::metrics::PackageMetric;
 use crate::settings::StructuralInheritanceConstraint;
 use crate::settings::StructuralSymbolKind;
 
@@ -39,6 +40,11 @@ pub enum FlawKind {
     MustUseAttribute { expected: StructuralInheritanceConstraint },
     MustBe { allowed: Vec<StructuralSymbolKind> },
     PublicMethodNotAllowed { method: Vec<u8>, allowed: Vec<String> },
+    CallNotAllowed { function: Vec<u8>, pattern: String },
+    InstantiationNotAllowed { class: Vec<u8>, pattern: String },
+    StaticAccessNotAllowed { class: Vec<u8>, pattern: String },
+    PropertyWriteNotAllowed,
+    MetricOutOfBounds { metric: PackageMetric, value: f64, limit: f64, is_maximum: bool },
 }
 
 impl From<StructuralFlaw> for Issue {
@@ -78,6 +84,30 @@ impl From<StructuralFlaw> for Issue {
                 "Make method `{}` non-public, remove it, or add it to `only-public-methods`.",
                 BytesDisplay(method)
             ),
+            FlawKind::CallNotAllowed { function, .. } => {
+                format!("Remove the call to `{}` from this {}.", BytesDisplay(function), flaw.symbol_kind)
+            }
+            FlawKind::InstantiationNotAllowed { class, .. } => {
+                format!(
+                    "Inject the `{}` instance into this {} instead of creating it.",
+                    BytesDisplay(class),
+                    flaw.symbol_kind
+                )
+            }
+            FlawKind::StaticAccessNotAllowed { class, .. } => {
+                format!(
+                    "Inject an instance into this {} instead of accessing `{}` statically.",
+                    flaw.symbol_kind,
+                    BytesDisplay(class)
+                )
+            }
+            FlawKind::PropertyWriteNotAllowed => {
+                format!("Return a new value instead of changing state from this {}.", flaw.symbol_kind)
+            }
+            FlawKind::MetricOutOfBounds { metric, .. } => format!(
+                "Rework the dependencies of the `{}` namespace to bring its {metric} within bounds, or adjust the threshold.",
+                BytesDisplay(&flaw.symbol_fqn)
+            ),
         };
 
         issue.with_help(help)
@@ -126,6 +156,26 @@ impl fmt::Display for FlawKind {
                 let allowed = allowed.iter().map(|name| format!("`{name}`")).collect::<Vec<_>>().join(", ");
                 write!(f, "Public method `{}` is not allowed; allowed methods: {allowed}", BytesDisplay(method))
             }
+            Self::CallNotAllowed { function, pattern } => {
+                write!(f, "Calling `{}` is not allowed; it matches `{pattern}`", BytesDisplay(function))
+            }
+            Self::InstantiationNotAllowed { class, pattern } => {
+                write!(f, "Instantiating `{}` is not allowed; it matches `{pattern}`", BytesDisplay(class))
+            }
+            Self::StaticAccessNotAllowed { class, pattern } => {
+                write!(f, "Static access to `{}` is not allowed; it matches `{pattern}`", BytesDisplay(class))
+            }
+            Self::PropertyWriteNotAllowed => write!(f, "Properties must not be written outside of a constructor"),
+            Self::MetricOutOfBounds { metric, value, limit, is_maximum } => {
+                let direction = if *is_maximum { "above the maximum" } else { "below the minimum" };
+
+                write!(
+                    f,
+                    "The namespace's {metric} of {} is {direction} of {}",
+                    metric.format_value(*value),
+                    metric.format_value(*limit)
+                )
+            }
         }
     }
 }
@@ -148,6 +198,11 @@ impl FlawKind {
             Self::MustUseAttribute { .. } => "must-use-attribute",
             Self::MustBe { .. } => "must-be",
             Self::PublicMethodNotAllowed { .. } => "only-public-methods",
+            Self::CallNotAllowed { .. } => "must-not-call",
+            Self::InstantiationNotAllowed { .. } => "must-not-instantiate",
+            Self::StaticAccessNotAllowed { .. } => "must-not-access-statically",
+            Self::PropertyWriteNotAllowed => "must-not-write-properties",
+            Self::MetricOutOfBounds { .. } => "metric-threshold",
         }
     }
 }
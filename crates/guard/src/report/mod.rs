@@ -1,22 +1,40 @@
+use std::sync::Arc;
+
+use foldhash::HashMap;
+
 use mago_allocator::Arena;
 
 use mago_collector::Collector;
+use mago_collector::DeferredPragmas;
+use mago_collector::pragma::GUARD_CATEGORY;
+use mago_collector::pragma::GUARD_PERIMETER_CATEGORY;
+use mago_collector::pragma::GUARD_STRUCTURAL_CATEGORY;
 use mago_database::file::File;
+use mago_database::file::FileId;
+use mago_reporting::Issue;
 use mago_reporting::IssueCollection;
 use mago_syntax::cst::Program;
 
+use crate::graph::DependencyGraph;
 use crate::report::breach::BoundaryBreach;
+use crate::report::cycle::DependencyCycle;
 use crate::report::flaw::StructuralFlaw;
 
 pub mod breach;
+pub mod cycle;
 pub mod flaw;
 
-const COLLECTOR_CATEGORIES: &[&str] = &["guard"];
+const COLLECTOR_CATEGORIES: &[&str] = &[GUARD_CATEGORY];
 
 #[derive(Debug, Default)]
 pub struct FortressReport {
     pub boundary_breaches: Vec<BoundaryBreach>,
     pub structural_flaws: Vec<StructuralFlaw>,
+    /// Sets of namespaces depending on each other, found across the whole codebase.
+    pub dependency_cycles: Vec<DependencyCycle>,
+    /// The dependencies of the program, recorded when cycle detection is enabled so they can
+    /// be merged with those of other programs.
+    pub dependency_graph: Option<DependencyGraph>,
     /// Whether perimeter guard was skipped due to missing configuration.
     pub missing_perimeter_configuration: bool,
     /// Whether structural guard was skipped due to missing configuration.
@@ -26,22 +44,103 @@ pub struct FortressReport {
 impl FortressReport {
     #[must_use]
     pub fn is_empty(&self) -> bool {
-        self.boundary_breaches.is_empty() && self.structural_flaws.is_empty()
+        self.boundary_breaches.is_empty() && self.structural_flaws.is_empty() && self.dependency_cycles.is_empty()
     }
 
     pub fn report_into_issues<A>(self, arena: &A, source_file: &File, program: &Program) -> IssueCollection
+    where
+        A: Arena,
+    {
+        self.report_into_collector(arena, source_file, program).finish()
+    }
+
+    /// Reports the issues of the program like [`report_into_issues`](Self::report_into_issues),
+    /// but keeps its pragmas open for the issues found later across the whole codebase.
+    ///
+    /// Those issues are then reported through [`reconcile_into_issues`](Self::reconcile_into_issues),
+    /// and the returned pragmas are finished once all of them have been reconciled.
+    pub fn report_into_deferred_issues<A>(
+        self,
+        arena: &A,
+        source_file: &File,
+        program: &Program,
+    ) -> (IssueCollection, Option<DeferredPragmas>)
+    where
+        A: Arena,
+    {
+        self.report_into_collector(arena, source_file, program).defer()
+    }
+
+    /// Reports the issues found across the whole codebase, such as dependency cycles, through
+    /// the deferred pragmas of the file each issue points to.
+    ///
+    /// Issues pointing to a file without pragmas are reported as they are.
+    #[must_use]
+    pub fn reconcile_into_issues(
+        self,
+        deferred: &mut HashMap<FileId, (Arc<File>, DeferredPragmas)>,
+    ) -> IssueCollection {
+        let structural_issues = self.structural_flaws.into_iter().map(|structural_flaw| {
+            (GUARD_STRUCTURAL_CATEGORY, structural_flaw.kind.error_code(), Issue::from(structural_flaw))
+        });
+        let cycle_issues = self.dependency_cycles.into_iter().map(|dependency_cycle| {
+            (GUARD_PERIMETER_CATEGORY, dependency_cycle.error_code(), Issue::from(dependency_cycle))
+        });
+
+        let mut issues = IssueCollection::new();
+        for (category, code, issue) in cycle_issues.chain(structural_issues) {
+            let issue = issue.with_code(code);
+            let file_id = issue
+                .annotations
+                .iter()
+                .find(|annotation| annotation.kind.is_primary())
+                .map(|annotation| annotation.span.file_id);
+
+            match file_id.and_then(|file_id| deferred.get_mut(&file_id)) {
+                Some((file, pragmas)) => {
+                    issues.extend(pragmas.reconcile_in_category(file, category, std::iter::once(issue).collect()));
+                }
+                None => issues.push(issue),
+            }
+        }
+
+        issues
+    }
+
+    fn report_into_collector<'ctx, 'arena, A>(
+        self,
+        arena: &'arena A,
+        source_file: &'ctx File,
+        program: &Program<'arena>,
+    ) -> Collector<'ctx, 'arena, A>
     where
         A: Arena,
     {
         let mut collector = Collector::new(arena, source_file, program, COLLECTOR_CATEGORIES);
         for boundary_breach in self.boundary_breaches {
-            collector.report_with_code(boundary_breach.vector.error_code(), boundary_breach.into());
+            collector.report_in_category(
+                GUARD_PERIMETER_CATEGORY,
+                boundary_breach.vector.error_code(),
+                boundary_breach.into(),
+            );
         }
 
         for structural_flaw in self.structural_flaws {
-            collector.report_with_code(structural_flaw.kind.error_code(), structural_flaw.into());
+            collector.report_in_category(
+                GUARD_STRUCTURAL_CATEGORY,
+                structural_flaw.kind.error_code(),
+                structural_flaw.into(),
+            );
+        }
+
+        for dependency_cycle in self.dependency_cycles {
+            collector.report_in_category(
+                GUARD_PERIMETER_CATEGORY,
+                dependency_cycle.error_code(),
+                dependency_cycle.into(),
+            );
         }
 
-        collector.finish()
+        collector
     }
 }
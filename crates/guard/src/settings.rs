@@ -31,6 +31,7 @@ pub struct Settings {
     pub mode: GuardMode,
     pub perimeter: PerimeterSettings,
     pub structural: StructuralSettings,
+    pub cycles: CycleSettings,
 }
 
 #[derive(Debug, Clone, PartialEq, Eq, Default, JsonSchema)]
@@ -44,6 +45,19 @@ pub struct PerimeterSettings {
     pub restrictions: Vec<DependencyRestriction>,
 }
 
+/// Detection of dependency cycles between namespaces, from `[guard.cycles]`.
+#[derive(Debug, Clone, PartialEq, Eq, Default, JsonSchema)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(default, rename_all = "kebab-case", deny_unknown_fields))]
+pub struct CycleSettings {
+    /// Whether to report namespaces that depend on each other, directly or transitively.
+    pub enabled: bool,
+    /// The number of namespace segments compared, so that at depth 2 a cycle between
+    /// `App\Billing\Invoice` and `App\Invoicing\Model` is reported as one between
+    /// `App\Billing` and `App\Invoicing`. Namespaces are compared whole when unset.
+    pub depth: Option<usize>,
+}
+
 #[derive(Debug, Clone, PartialEq, Eq, Hash, JsonSchema)]
 #[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 #[cfg_attr(feature = "serde", serde(deny_unknown_fields))]
@@ -94,8 +108,50 @@ pub enum PermittedDependencyKind {
 pub struct StructuralSettings {
     /// A list of structural rules to enforce across the codebase.
     pub rules: Vec<StructuralRule>,
+    /// Thresholds on the coupling metrics of namespaces.
+    pub metrics: MetricsSettings,
+}
+
+/// Package coupling metrics settings from `[guard.structural.metrics]`.
+#[derive(Debug, Clone, PartialEq, Eq, Default, JsonSchema)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(default, rename_all = "kebab-case", deny_unknown_fields))]
+pub struct MetricsSettings {
+    /// The number of namespace segments making up a package. Namespaces are used whole when unset.
+    pub depth: Option<usize>,
+    /// The thresholds the metrics of matching packages must stay within.
+    pub thresholds: Vec<MetricThreshold>,
+}
+
+/// A threshold on the metrics of packages, from `[[guard.structural.metrics.thresholds]]`.
+#[derive(Debug, Clone, PartialEq, Default, JsonSchema)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(default, rename_all = "kebab-case", deny_unknown_fields))]
+pub struct MetricThreshold {
+    /// The pattern of the packages this threshold applies to.
+    pub on: String,
+    /// An optional exclusion pattern; matching packages are skipped.
+    pub not_on: Option<String>,
+    /// The maximum number of packages depending on a matching package.
+    pub max_afferent_coupling: Option<usize>,
+    /// The maximum number of packages a matching package depends on.
+    pub max_efferent_coupling: Option<usize>,
+    /// The minimum instability, from `0.0` (stable) to `1.0` (unstable).
+    pub min_instability: Option<f64>,
+    /// The maximum instability, from `0.0` (stable) to `1.0` (unstable).
+    pub max_instability: Option<f64>,
+    /// The minimum abstractness, from `0.0` (concrete) to `1.0` (abstract).
+    pub min_abstractness: Option<f64>,
+    /// The maximum abstractness, from `0.0` (concrete) to `1.0` (abstract).
+    pub max_abstractness: Option<f64>,
+    /// The maximum distance from the main sequence, from `0.0` to `1.0`.
+    pub max_distance: Option<f64>,
+    /// A human-readable reason for this threshold.
+    pub reason: Option<String>,
 }
 
+impl Eq for MetricThreshold {}
+
 /// Represents a single structural enforcement rule from `[[guard.structural.rules]]`.
 #[derive(Debug, Clone, PartialEq, Eq, Default, JsonSchema)]
 #[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
@@ -127,6 +183,14 @@ pub struct StructuralRule {
     pub must_use_attribute: Option<StructuralInheritanceConstraint>,
     /// The public methods that matched classes may declare. Private and protected methods are unrestricted.
     pub only_public_methods: Option<Vec<String>>,
+    /// Patterns of the functions that matched symbols may not call.
+    pub must_not_call: Option<Vec<String>>,
+    /// Patterns of the classes that matched symbols may not instantiate.
+    pub must_not_instantiate: Option<Vec<String>>,
+    /// Patterns of the classes whose static methods and properties matched symbols may not access.
+    pub must_not_access_statically: Option<Vec<String>>,
+    /// If true, matched symbols may not write to properties outside of constructors.
+    pub must_not_write_properties: Option<bool>,
     /// A human-readable reason for this rule.
     pub reason: Option<String>,
 }
@@ -173,6 +237,18 @@ impl PermittedDependencyKind {
     }
 }
 
+impl StructuralRule {
+    /// Returns true if the rule constrains what the code of matched symbols does,
+    /// rather than how the symbols are declared.
+    #[must_use]
+    pub fn has_behavioural_constraints(&self) -> bool {
+        self.must_not_call.is_some()
+            || self.must_not_instantiate.is_some()
+            || self.must_not_access_statically.is_some()
+            || self.must_not_write_properties == Some(true)
+    }
+}
+
 impl StructuralSymbolKind {
     #[must_use]
     pub const fn is_constant(&self) -> bool {
@@ -356,15 +432,15 @@ impl StructuralSettings {
     /// Returns true if there are no structural rules configured.
     #[must_use]
     pub fn is_empty(&self) -> bool {
-        self.rules.is_empty()
+        self.rules.is_empty() && self.metrics.thresholds.is_empty()
     }
 }
 
 impl Settings {
-    /// Returns true if perimeter guard has configuration.
+    /// Returns true if perimeter guard has configuration, or cycle detection is enabled.
     #[must_use]
     pub fn has_perimeter_config(&self) -> bool {
-        !self.perimeter.is_empty()
+        !self.perimeter.is_empty() || self.cycles.enabled
     }
 
     /// Returns true if structural guard has configuration.
@@ -1,12 +1,26 @@
+use mago_span::HasSpan;
+use mago_span::Span;
+use mago_syntax::cst::Access;
+use mago_syntax::cst::Assignment;
 use mago_syntax::cst::Class;
 use mago_syntax::cst::ClassLikeMember;
 use mago_syntax::cst::Constant;
 use mago_syntax::cst::Enum;
+use mago_syntax::cst::Expression;
 use mago_syntax::cst::Function;
+use mago_syntax::cst::FunctionCall;
+use mago_syntax::cst::FunctionPartialApplication;
+use mago_syntax::cst::Instantiation;
 use mago_syntax::cst::Interface;
+use mago_syntax::cst::Method;
 use mago_syntax::cst::ModifierSequenceExt;
 use mago_syntax::cst::Namespace;
+use mago_syntax::cst::StaticMethodCall;
+use mago_syntax::cst::StaticMethodPartialApplication;
+use mago_syntax::cst::StaticPropertyAccess;
 use mago_syntax::cst::Trait;
+use mago_syntax::cst::UnaryPostfix;
+use mago_syntax::cst::UnaryPrefix;
 use mago_syntax::walker::MutWalker;
 
 use crate::context::GuardContext;
@@ -17,18 +31,114 @@ use crate::settings::StructuralInheritanceConstraint;
 use crate::settings::StructuralRule;
 use crate::settings::StructuralSymbolKind;
 
-#[derive(Debug, Clone, Copy)]
-pub struct StructuralGuardWalker;
+#[derive(Debug, Default)]
+pub struct StructuralGuardWalker<'ctx, 'arena> {
+    /// The symbols whose code is being walked, innermost last.
+    scopes: Vec<SymbolScope<'ctx, 'arena>>,
+    /// The names of the methods being walked, innermost last.
+    methods: Vec<&'arena [u8]>,
+}
+
+/// A symbol whose code is checked against the behavioural constraints of its rules.
+#[derive(Debug)]
+struct SymbolScope<'ctx, 'arena> {
+    fqn: &'arena [u8],
+    kind: StructuralSymbolKind,
+    rules: Vec<&'ctx StructuralRule>,
+}
 
-impl StructuralGuardWalker {
-    fn get_structural_rules<'ctx, 'arena>(
-        context: &'ctx GuardContext<'ctx, 'arena>,
+impl<'ctx, 'arena> StructuralGuardWalker<'ctx, 'arena> {
+    fn get_structural_rules(
+        context: &GuardContext<'ctx, 'arena>,
         fqn: &'arena [u8],
         kind: StructuralSymbolKind,
     ) -> Vec<&'ctx StructuralRule> {
         context.settings.structural.rules.iter().filter(|rule| Self::applies_to(rule, fqn, kind)).collect()
     }
 
+    /// Enters the code of a symbol, so that the behavioural constraints of its rules apply to it.
+    fn enter_scope(&mut self, context: &GuardContext<'ctx, 'arena>, fqn: &'arena [u8], kind: StructuralSymbolKind) {
+        let rules = Self::get_structural_rules(context, fqn, kind)
+            .into_iter()
+            .filter(|rule| rule.has_behavioural_constraints())
+            .collect();
+
+        self.scopes.push(SymbolScope { fqn, kind, rules });
+    }
+
+    fn exit_scope(&mut self) {
+        self.scopes.pop();
+    }
+
+    /// Reports a flaw for every rule of the innermost symbol that `check` finds violated at `span`.
+    fn check_behaviour(
+        &self,
+        context: &mut GuardContext<'ctx, 'arena>,
+        span: Span,
+        check: impl Fn(&StructuralRule) -> Option<FlawKind>,
+    ) {
+        let Some(scope) = self.scopes.last() else {
+            return;
+        };
+
+        for rule in &scope.rules {
+            if let Some(kind) = check(rule) {
+                context.structural_flaws.push(StructuralFlaw {
+                    symbol_fqn: fqn_to_owned(scope.fqn),
+                    symbol_kind: scope.kind,
+                    span,
+                    kind,
+                    reason: rule.reason.clone(),
+                });
+            }
+        }
+    }
+
+    fn check_function_call(&self, context: &mut GuardContext<'ctx, 'arena>, function: &Expression<'arena>) {
+        let Expression::Identifier(function_name) = function else {
+            return;
+        };
+
+        let Some(fqn) = context.try_lookup_name(function_name) else {
+            return;
+        };
+
+        // PHP falls back to the global function when an unqualified name is not declared in the
+        // current namespace, so both names are matched.
+        let fallback = (function_name.is_local() && !context.resolved_names.is_imported(function_name))
+            .then(|| function_name.value());
+
+        self.check_behaviour(context, function_name.span(), |rule| {
+            [Some(fqn), fallback].into_iter().flatten().find_map(|name| {
+                find_pattern(rule.must_not_call.as_deref(), name)
+                    .map(|pattern| FlawKind::CallNotAllowed { function: fqn_to_owned(name), pattern: pattern.clone() })
+            })
+        });
+    }
+
+    fn check_static_access(&self, context: &mut GuardContext<'ctx, 'arena>, class: &Expression<'arena>) {
+        let Expression::Identifier(class_name) = class else {
+            return;
+        };
+
+        let fqn = context.lookup_name(class_name);
+        self.check_behaviour(context, class_name.span(), |rule| {
+            find_pattern(rule.must_not_access_statically.as_deref(), fqn)
+                .map(|pattern| FlawKind::StaticAccessNotAllowed { class: fqn_to_owned(fqn), pattern: pattern.clone() })
+        });
+    }
+
+    fn check_property_write(&self, context: &mut GuardContext<'ctx, 'arena>, target: &Expression<'arena>) {
+        if !is_property(target) || self.methods.last().is_some_and(|method| method.eq_ignore_ascii_case(b"__construct"))
+        {
+            return;
+        }
+
+        self.check_behaviour(context, target.span(), |rule| {
+            (rule.must_not_write_properties == Some(true)).then_some(FlawKind::PropertyWriteNotAllowed)
+        });
+    }
+
     fn applies_to(rule: &StructuralRule, fqn: &[u8], kind: StructuralSymbolKind) -> bool {
         if let Some(rule_kind) = rule.target
             && rule_kind != kind
@@ -87,7 +197,23 @@ fn fqn_to_owned(fqn: &[u8]) -> Vec<u8> {
     fqn.to_vec()
 }
 
-impl<'ast, 'ctx, 'arena> MutWalker<'ast, 'arena, GuardContext<'ctx, 'arena>> for StructuralGuardWalker {
+/// Returns the first of the patterns matching the fully qualified name, if any.
+fn find_pattern<'rule>(patterns: Option<&'rule [String]>, fqn: &[u8]) -> Option<&'rule String> {
+    patterns?.iter().find(|pattern| matcher::matches(fqn, pattern.as_bytes(), false, false))
+}
+
+/// Returns true if writing to the expression changes a property, including writes to
+/// elements of array properties such as `$this->items[] = $item`.
+fn is_property(expression: &Expression<'_>) -> bool {
+    match expression {
+        Expression::Access(Access::Property(_) | Access::StaticProperty(_)) => true,
+        Expression::ArrayAccess(array_access) => is_property(array_access.array),
+        Expression::ArrayAppend(array_append) => is_property(array_append.array),
+        _ => false,
+    }
+}
+
+impl<'ast, 'ctx, 'arena> MutWalker<'ast, 'arena, GuardContext<'ctx, 'arena>> for StructuralGuardWalker<'ctx, 'arena> {
     fn walk_in_namespace(&mut self, namespace: &'ast Namespace<'arena>, context: &mut GuardContext<'ctx, 'arena>) {
         context.set_current_namespace(namespace.name.as_ref().map(mago_syntax::cst::Identifier::value));
     }
@@ -98,6 +224,8 @@ impl<'ast, 'ctx, 'arena> MutWalker<'ast, 'arena, GuardContext<'ctx, 'arena>> for
 
     fn walk_in_class(&mut self, class: &'ast Class<'arena>, context: &mut GuardContext<'ctx, 'arena>) {
         let fqn = context.lookup_name(&class.name);
+        self.enter_scope(context, fqn, StructuralSymbolKind::Class);
+
         let structural_rules = Self::get_structural_rules(context, fqn, StructuralSymbolKind::Class);
 
         let mut structural_flaws = vec![];
@@ -395,6 +523,8 @@ impl<'ast, 'ctx, 'arena> MutWalker<'ast, 'arena, GuardContext<'ctx, 'arena>> for
 
     fn walk_in_enum(&mut self, r#enum: &'ast Enum<'arena>, context: &mut GuardContext<'ctx, 'arena>) {
         let fqn = context.lookup_name(&r#enum.name);
+        self.enter_scope(context, fqn, StructuralSymbolKind::Enum);
+
         let structural_rules = Self::get_structural_rules(context, fqn, StructuralSymbolKind::Enum);
 
         let mut structural_flaws = vec![];
@@ -468,6 +598,8 @@ impl<'ast, 'ctx, 'arena> MutWalker<'ast, 'arena, GuardContext<'ctx, 'arena>> for
 
     fn walk_in_trait(&mut self, r#trait: &'ast Trait<'arena>, context: &mut GuardContext<'ctx, 'arena>) {
         let fqn = context.lookup_name(&r#trait.name);
+        self.enter_scope(context, fqn, StructuralSymbolKind::Trait);
+
         let structural_rules = Self::get_structural_rules(context, fqn, StructuralSymbolKind::Trait);
 
         let mut structural_flaws = vec![];
@@ -544,6 +676,8 @@ impl<'ast, 'ctx, 'arena> MutWalker<'ast, 'arena, GuardContext<'ctx, 'arena>> for
 
     fn walk_in_function(&mut self, function: &'ast Function<'arena>, context: &mut GuardContext<'ctx, 'arena>) {
         let fqn = context.lookup_name(&function.name);
+        self.enter_scope(context, fqn, StructuralSymbolKind::Function);
+
         let structural_rules = Self::get_structural_rules(context, fqn, StructuralSymbolKind::Function);
 
         let mut structural_flaws = vec![];
@@ -649,4 +783,106 @@ impl<'ast, 'ctx, 'arena> MutWalker<'ast, 'arena, GuardContext<'ctx, 'arena>> for
 
         context.structural_flaws.extend(structural_flaws);
     }
+
+    fn walk_out_class(&mut self, _class: &'ast Class<'arena>, _context: &mut GuardContext<'ctx, 'arena>) {
+        self.exit_scope();
+    }
+
+    fn walk_out_enum(&mut self, _enum: &'ast Enum<'arena>, _context: &mut GuardContext<'ctx, 'arena>) {
+        self.exit_scope();
+    }
+
+    fn walk_out_trait(&mut self, _trait: &'ast Trait<'arena>, _context: &mut GuardContext<'ctx, 'arena>) {
+        self.exit_scope();
+    }
+
+    fn walk_out_function(&mut self, _function: &'ast Function<'arena>, _context: &mut GuardContext<'ctx, 'arena>) {
+        self.exit_scope();
+    }
+
+    fn walk_in_method(&mut self, method: &'ast Method<'arena>, _context: &mut GuardContext<'ctx, 'arena>) {
+        self.methods.push(method.name.value);
+    }
+
+    fn walk_out_method(&mut self, _method: &'ast Method<'arena>, _context: &mut GuardContext<'ctx, 'arena>) {
+        self.methods.pop();
+    }
+
+    fn walk_in_function_call(
+        &mut self,
+        function_call: &'ast FunctionCall<'arena>,
+        context: &mut GuardContext<'ctx, 'arena>,
+    ) {
+        self.check_function_call(context, function_call.function);
+    }
+
+    fn walk_in_function_partial_application(
+        &mut self,
+        function_partial_application: &'ast FunctionPartialApplication<'arena>,
+        context: &mut GuardContext<'ctx, 'arena>,
+    ) {
+        self.check_function_call(context, function_partial_application.function);
+    }
+
+    fn walk_in_instantiation(
+        &mut self,
+        instantiation: &'ast Instantiation<'arena>,
+        context: &mut GuardContext<'ctx, 'arena>,
+    ) {
+        let Expression::Identifier(class_name) = instantiation.class else {
+            return;
+        };
+
+        let fqn = context.lookup_name(class_name);
+        self.check_behaviour(context, class_name.span(), |rule| {
+            find_pattern(rule.must_not_instantiate.as_deref(), fqn)
+                .map(|pattern| FlawKind::InstantiationNotAllowed { class: fqn_to_owned(fqn), pattern: pattern.clone() })
+        });
+    }
+
+    fn walk_in_static_method_call(
+        &mut self,
+        static_method_call: &'ast StaticMethodCall<'arena>,
+        context: &mut GuardContext<'ctx, 'arena>,
+    ) {
+        self.check_static_access(context, static_method_call.class);
+    }
+
+    fn walk_in_static_method_partial_application(
+        &mut self,
+        static_method_partial_application: &'ast StaticMethodPartialApplication<'arena>,
+        context: &mut GuardContext<'ctx, 'arena>,
+    ) {
+        self.check_static_access(context, static_method_partial_application.class);
+    }
+
+    fn walk_in_static_property_access(
+        &mut self,
+        static_property_access: &'ast StaticPropertyAccess<'arena>,
+        context: &mut GuardContext<'ctx, 'arena>,
+    ) {
+        self.check_static_access(context, static_property_access.class);
+    }
+
+    fn walk_in_assignment(&mut self, assignment: &'ast Assignment<'arena>, context: &mut GuardContext<'ctx, 'arena>) {
+        self.check_property_write(context, assignment.lhs);
+    }
+
+    fn walk_in_unary_prefix(
+        &mut self,
+        unary_prefix: &'ast UnaryPrefix<'arena>,
+        context: &mut GuardContext<'ctx, 'arena>,
+    ) {
+        if unary_prefix.operator.is_increment_or_decrement() {
+            self.check_property_write(context, unary_prefix.operand);
+        }
+    }
+
+    fn walk_in_unary_postfix(
+        &mut self,
+        unary_postfix: &'ast UnaryPostfix<'arena>,
+        context: &mut GuardContext<'ctx, 'arena>,
+    ) {
+        self.check_property_write(context, unary_postfix.operand);
+    }
 }
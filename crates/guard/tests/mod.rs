@@ -1,17 +1,23 @@
 #![allow(clippy::unwrap_used, clippy::expect_used, clippy::missing_panics_doc)]
 
 use std::borrow::Cow;
+use std::sync::Arc;
 use std::sync::LazyLock;
 
+use foldhash::HashMap;
 use foldhash::HashSet;
 use indoc::indoc;
 use mago_allocator::LocalArena;
 
+use mago_codex::metadata::CodebaseMetadata;
 use mago_codex::populator::populate_codebase;
 use mago_codex::scanner::scan_program;
 use mago_database::DatabaseReader;
 use mago_database::file::File;
 use mago_guard::ArchitecturalGuard;
+use mago_guard::graph::DependencyGraph;
+use mago_guard::metrics::PackageMetric;
+use mago_guard::metrics::package_metrics;
 use mago_guard::path::NamespacePath;
 use mago_guard::path::Path;
 use mago_guard::path::SymbolSelector;
@@ -19,7 +25,10 @@ use mago_guard::report::FortressReport;
 use mago_guard::report::breach::BreachReason;
 use mago_guard::report::breach::BreachVector;
 use mago_guard::report::flaw::FlawKind;
+use mago_guard::settings::CycleSettings;
 use mago_guard::settings::DependencyRestriction;
+use mago_guard::settings::MetricThreshold;
+use mago_guard::settings::MetricsSettings;
 use mago_guard::settings::PerimeterRule;
 use mago_guard::settings::PerimeterSettings;
 use mago_guard::settings::PermittedDependency;
@@ -28,8 +37,11 @@ use mago_guard::settings::Settings;
 use mago_guard::settings::StructuralRule;
 use mago_guard::settings::StructuralSettings;
 use mago_guard::settings::StructuralSymbolKind;
+use mago_names::ResolvedNames;
 use mago_names::resolver::NameResolver;
 use mago_prelude::Prelude;
+use mago_reporting::IssueCollection;
+use mago_syntax::cst::Program;
 use mago_syntax::parser::parse_file;
 use mago_word::WordSet;
 
@@ -51,6 +63,30 @@ fn deny_all_settings() -> Settings {
 }
 
 fn test_guard(name: &'static str, code: &'static str, settings: Settings) -> FortressReport {
+    with_program(name, code, |codebase, _, program, resolved_names| {
+        ArchitecturalGuard::new(settings).check(codebase, program, resolved_names)
+    })
+}
+
+fn test_guard_issues(name: &'static str, code: &'static str, settings: Settings) -> IssueCollection {
+    with_program(name, code, |codebase, source_file, program, resolved_names| {
+        let report = ArchitecturalGuard::new(settings).check(codebase, program, resolved_names);
+
+        report.report_into_issues(&LocalArena::new(), source_file, program)
+    })
+}
+
+fn test_graph(name: &'static str, code: &'static str, settings: Settings, depth: Option<usize>) -> DependencyGraph {
+    with_program(name, code, |codebase, _, program, resolved_names| {
+        ArchitecturalGuard::new(settings).graph(codebase, program, resolved_names, depth)
+    })
+}
+
+fn with_program<T>(
+    name: &'static str,
+    code: &'static str,
+    f: impl FnOnce(&CodebaseMetadata, &File, &Program<'_>, &ResolvedNames<'_>) -> T,
+) -> T {
     let Prelude { mut database, mut metadata, mut symbol_references } = PRELUDE.clone();
 
     let file = File::ephemeral(Cow::Borrowed(name.as_bytes()), Cow::Borrowed(code.as_bytes()));
@@ -70,8 +106,7 @@ fn test_guard(name: &'static str, code: &'static str, settings: Settings) -> For
 
     populate_codebase(&mut metadata, &mut symbol_references, WordSet::default(), HashSet::default());
 
-    let guard = ArchitecturalGuard::new(settings);
-    guard.check(&metadata, program, &resolved_names)
+    f(&metadata, source_file, program, &resolved_names)
 }
 
 #[test]
@@ -797,6 +832,7 @@ pub fn test_only_public_methods_restricts_declared_class_api() {
                 only_public_methods: Some(vec!["__construct".to_string(), "__INVOKE".to_string()]),
                 ..Default::default()
             }],
+            ..Default::default()
         },
         ..Default::default()
     };
@@ -815,6 +851,130 @@ pub fn test_only_public_methods_restricts_declared_class_api() {
     assert!(result.structural_flaws.iter().all(|flaw| flaw.kind.error_code() == "only-public-methods"));
 }
 
+#[test]
+pub fn test_behavioural_constraints() {
+    let code = indoc! {r"
+        <?php
+
+        namespace App\Infrastructure {
+            class Mailer {
+                public static function instance(): self { return new self(); }
+            }
+        }
+
+        namespace App\Domain {
+            use App\Infrastructure\Mailer;
+
+            final class Order {
+                private array $lines = [];
+                private int $total = 0;
+
+                public function __construct(int $total) {
+                    $this->total = $total;
+                }
+
+                public function add(string $line): void {
+                    $this->lines[] = $line;
+                    $this->total++;
+                    $mailer = new Mailer();
+                    Mailer::instance();
+                    var_dump($line);
+                    $local = $line;
+                }
+            }
+        }
+    "};
+
+    let settings = Settings {
+        structural: StructuralSettings {
+            rules: vec![StructuralRule {
+                on: "App\\Domain\\**".to_string(),
+                target: Some(StructuralSymbolKind::Class),
+                must_not_call: Some(vec!["var_dump".to_string()]),
+                must_not_instantiate: Some(vec!["App\\Infrastructure\\**".to_string()]),
+                must_not_access_statically: Some(vec!["**".to_string()]),
+                must_not_write_properties: Some(true),
+                ..Default::default()
+            }],
+            ..Default::default()
+        },
+        ..Default::default()
+    };
+
+    let result = test_guard("behavioural_constraints", code, settings);
+    let codes = result.structural_flaws.iter().map(|flaw| flaw.kind.error_code()).collect::<Vec<_>>();
+
+    assert_eq!(
+        codes,
+        [
+            "must-not-write-properties",
+            "must-not-write-properties",
+            "must-not-instantiate",
+            "must-not-access-statically",
+            "must-not-call",
+        ]
+    );
+    assert!(result.structural_flaws.iter().all(|flaw| flaw.symbol_fqn == b"App\\Domain\\Order"));
+    assert!(result.structural_flaws.iter().any(|flaw| {
+        matches!(&flaw.kind, FlawKind::InstantiationNotAllowed { class, .. } if class == b"App\\Infrastructure\\Mailer")
+    }));
+}
+
+#[test]
+pub fn test_guard_expect_pragmas() {
+    let code = indoc! {r"
+        <?php
+
+        namespace App\Module {
+            /** @mago-guard-expect perimeter until=2999-12-31 Moving to an interface. */
+            final class Accepted {
+                public function __construct(private \App\Other\Db $db) {}
+            }
+
+            /** @mago-guard-expect perimeter until=2000-01-01 */
+            final class Expired {
+                public function __construct(private \App\Other\Db $db) {}
+            }
+
+            /** @mago-guard-expect structural */
+            final class WrongCategory {
+                public function __construct(private \App\Other\Db $db) {}
+            }
+        }
+    "};
+
+    let issues = test_guard_issues("guard_expect_pragmas", code, deny_all_settings());
+    let mut codes = issues.iter().filter_map(|issue| issue.code.as_deref()).collect::<Vec<_>>();
+    codes.sort_unstable();
+
+    assert_eq!(codes, ["disallowed-parameter-type", "expired-pragma", "unfulfilled-expect"]);
+}
+
+#[test]
+pub fn test_invalid_guard_expect_pragmas() {
+    let code = indoc! {r"
+        <?php
+
+        namespace App\Module {
+            /** @mago-guard-expect perimeter until=2026-13-01 */
+            final class InvalidDate {
+                public function __construct(private \App\Other\Db $db) {}
+            }
+
+            /** @mago-guard-expect perimetre */
+            final class UnknownCategory {
+                public function __construct(private \App\Other\Db $db) {}
+            }
+        }
+    "};
+
+    let issues = test_guard_issues("invalid_guard_expect_pragmas", code, deny_all_settings());
+    let mut codes = issues.iter().filter_map(|issue| issue.code.as_deref()).collect::<Vec<_>>();
+    codes.sort_unstable();
+
+    assert_eq!(codes, ["disallowed-parameter-type", "disallowed-parameter-type", "invalid-pragma", "invalid-pragma"]);
+}
+
 #[test]
 pub fn test_is_final_annotation_counted() {
     let code = indoc! {r"
@@ -835,6 +995,7 @@ pub fn test_is_final_annotation_counted() {
                 must_be_final: Some(true),
                 ..Default::default()
             }],
+            ..Default::default()
         },
         ..Default::default()
     };
@@ -845,3 +1006,227 @@ pub fn test_is_final_annotation_counted() {
         "Expected non violations: Should allow declare final class with annotation"
     );
 }
+
+#[test]
+pub fn test_dependency_graph() {
+    let code = indoc! {r"
+        <?php
+
+        namespace App\Domain\Model {
+            class User extends \App\Infrastructure\Db\Record {}
+        }
+
+        namespace App\Application {
+            function register(\App\Domain\Model\User $user): \App\Domain\Model\User {
+                strlen('x');
+
+                return \App\Domain\Model\make_user();
+            }
+        }
+    "};
+
+    let settings = Settings {
+        perimeter: PerimeterSettings {
+            layering: vec![
+                NamespacePath::Specific("App\\Domain\\".to_string()),
+                NamespacePath::Specific("App\\Application\\".to_string()),
+                NamespacePath::Specific("App\\Infrastructure\\".to_string()),
+            ],
+            ..Default::default()
+        },
+        ..Default::default()
+    };
+
+    let graph = test_graph("dependency_graph", code, settings, Some(2));
+    let edges: Vec<_> = graph.edges().collect();
+    assert_eq!(edges.len(), 2);
+
+    assert_eq!(edges[0].source, "App\\Application");
+    assert_eq!(edges[0].target, "App\\Domain");
+    assert_eq!(edges[0].count, 3);
+    assert_eq!(edges[0].kinds.get("class-like"), Some(&2));
+    assert_eq!(edges[0].kinds.get("function"), Some(&1));
+    assert_eq!(edges[0].breaches, 0);
+
+    assert_eq!(edges[1].source, "App\\Domain");
+    assert_eq!(edges[1].target, "App\\Infrastructure");
+    assert_eq!(edges[1].breaches, 1);
+
+    let layers: Vec<_> = graph.nodes().map(|node| node.layer.as_deref()).collect();
+    assert_eq!(layers, [Some("App\\Application\\"), Some("App\\Domain\\"), Some("App\\Infrastructure\\")]);
+
+    assert!(
+        graph
+            .to_dot()
+            .contains("\"App\\\\Domain\" -> \"App\\\\Infrastructure\" [label=\"class-like: 1 (1 breach)\", color=red")
+    );
+}
+
+#[test]
+pub fn test_dependency_cycle() {
+    let code = indoc! {r"
+        <?php
+
+        namespace App\Billing\Model {
+            class Invoice extends \App\Invoicing\Document {}
+        }
+
+        namespace App\Invoicing {
+            class Document {}
+
+            function total(\App\Billing\Model\Invoice $invoice): int {
+                return 0;
+            }
+        }
+
+        namespace App\Payment {
+            class Payment extends \App\Billing\Model\Invoice {}
+        }
+    "};
+
+    let settings = Settings { cycles: CycleSettings { enabled: true, depth: Some(2) }, ..Default::default() };
+
+    let report = test_guard("dependency_cycle", code, settings.clone());
+    assert!(report.boundary_breaches.is_empty());
+
+    let graph = report.dependency_graph.expect("cycle detection records the dependency graph");
+    let cycles = ArchitecturalGuard::new(settings).check_cycles(&graph).dependency_cycles;
+    assert_eq!(cycles.len(), 1);
+    assert_eq!(cycles[0].namespaces, ["App\\Billing", "App\\Invoicing"]);
+    assert_eq!(cycles[0].path, ["App\\Billing", "App\\Invoicing", "App\\Billing"]);
+    assert_eq!(cycles[0].edges.len(), 2);
+    assert!(cycles[0].edges.iter().all(|edge| edge.spans.len() == 1));
+}
+
+#[test]
+pub fn test_dependency_cycle_ignores_namespace_case() {
+    let code = indoc! {r"
+        <?php
+
+        namespace App\Billing {
+            class Invoice extends \App\invoicing\Document {}
+        }
+
+        namespace App\Invoicing {
+            class Document {}
+
+            function total(\App\billing\Invoice $invoice): int {
+                return 0;
+            }
+        }
+    "};
+
+    let settings = Settings { cycles: CycleSettings { enabled: true, depth: Some(2) }, ..Default::default() };
+
+    let report = test_guard("dependency_cycle_ignores_namespace_case", code, settings.clone());
+    let graph = report.dependency_graph.expect("cycle detection records the dependency graph");
+    let cycles = ArchitecturalGuard::new(settings).check_cycles(&graph).dependency_cycles;
+    assert_eq!(cycles.len(), 1);
+    assert_eq!(cycles[0].edges.len(), 2);
+}
+
+#[test]
+pub fn test_dependency_cycle_guard_expect_pragma() {
+    let code = indoc! {r"
+        <?php
+
+        namespace App\Billing {
+            /** @mago-guard-expect perimeter */
+            class Invoice extends \App\Invoicing\Document {}
+        }
+
+        namespace App\Invoicing {
+            class Document {}
+
+            function total(\App\Billing\Invoice $invoice): int {
+                return 0;
+            }
+        }
+    "};
+
+    let settings = Settings { cycles: CycleSettings { enabled: true, depth: Some(2) }, ..Default::default() };
+
+    let name = "dependency_cycle_guard_expect_pragma";
+    let issues = with_program(name, code, |codebase, source_file, program, resolved_names| {
+        let guard = ArchitecturalGuard::new(settings);
+        let mut report = guard.check(codebase, program, resolved_names);
+        let graph = report.dependency_graph.take().expect("cycle detection records the dependency graph");
+
+        let (mut issues, pragmas) = report.report_into_deferred_issues(&LocalArena::new(), source_file, program);
+        let file = Arc::new(File::ephemeral(Cow::Borrowed(name.as_bytes()), Cow::Borrowed(code.as_bytes())));
+        let mut deferred = HashMap::default();
+        deferred.insert(file.id, (file, pragmas.expect("the file has a pragma")));
+
+        issues.extend(guard.check_cycles(&graph).reconcile_into_issues(&mut deferred));
+        for (file, pragmas) in deferred.into_values() {
+            issues.extend(pragmas.finish(&file));
+        }
+
+        issues
+    });
+
+    assert!(issues.is_empty(), "the cycle should be expected: {issues:?}");
+}
+
+#[test]
+pub fn test_package_metrics() {
+    let code = indoc! {r"
+        <?php
+
+        namespace App\Domain\Model {
+            interface UserRepository {}
+
+            abstract class Entity {}
+
+            final class User extends Entity {
+                public function save(\App\Infrastructure\Db\Connection $connection): void {}
+            }
+        }
+
+        namespace App\Application {
+            final class Register {
+                public function __construct(\App\Domain\Model\UserRepository $users) {}
+            }
+        }
+
+        namespace App\Infrastructure\Db {
+            final class Connection {}
+        }
+    "};
+
+    let settings = Settings {
+        structural: StructuralSettings {
+            metrics: MetricsSettings {
+                depth: Some(2),
+                thresholds: vec![MetricThreshold {
+                    on: "App\\Domain".to_string(),
+                    max_instability: Some(0.3),
+                    min_abstractness: Some(0.5),
+                    ..Default::default()
+                }],
+            },
+            ..Default::default()
+        },
+        ..Default::default()
+    };
+
+    let report = test_guard("package_metrics", code, settings.clone());
+    assert!(report.structural_flaws.is_empty());
+
+    let graph = report.dependency_graph.expect("metric thresholds record the dependency graph");
+    let metrics = package_metrics(&graph.at_depth(&settings, Some(2)));
+    let domain = metrics.iter().find(|package| package.namespace == "App\\Domain").unwrap();
+    assert_eq!(domain.class_likes, 3);
+    assert_eq!(domain.abstractions, 2);
+    assert_eq!(domain.afferent_coupling, 1);
+    assert_eq!(domain.efferent_coupling, 1);
+    assert!((domain.instability - 0.5).abs() < f64::EPSILON);
+
+    let flaws = ArchitecturalGuard::new(settings).check_metrics(&graph).structural_flaws;
+    assert_eq!(flaws.len(), 1);
+    assert_eq!(flaws[0].symbol_fqn, b"App\\Domain");
+    assert!(matches!(
+        flaws[0].kind,
+        FlawKind::MetricOutOfBounds { metric: PackageMetric::Instability, is_maximum: true, .. }
+    ));
+}
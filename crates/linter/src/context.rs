@@ -4,9 +4,12 @@ use mago_collector::Collector;
 use mago_database::file::File;
 use mago_names::ResolvedNames;
 use mago_php_version::PHPVersion;
+use mago_reporting::Issue;
+use mago_reporting::IssueCollection;
 use mago_span::HasPosition;
 use mago_syntax::cst::Node;
 use mago_syntax::cst::NodeKind;
+use mago_twig_syntax::cst::Template;
 
 use crate::import_tracker::ImportKind;
 use crate::import_tracker::ImportResolution;
@@ -169,3 +172,28 @@ where
         self.ancestors[..len - 1].iter().any(|n| n.kind() == kind)
     }
 }
+
+/// The context passed to template rules while linting a Twig template.
+#[derive(Debug)]
+#[allow(clippy::partial_pub_fields)]
+pub struct TemplateLintContext<'ctx, 'arena> {
+    pub source_file: &'ctx File,
+    pub template: &'arena Template<'arena>,
+    issues: IssueCollection,
+}
+
+impl<'ctx, 'arena> TemplateLintContext<'ctx, 'arena> {
+    #[must_use]
+    pub fn new(source_file: &'ctx File, template: &'arena Template<'arena>) -> Self {
+        Self { source_file, template, issues: IssueCollection::new() }
+    }
+
+    pub fn report(&mut self, issue: Issue) {
+        self.issues.push(issue);
+    }
+
+    #[must_use]
+    pub fn finish(self) -> IssueCollection {
+        self.issues
+    }
+}
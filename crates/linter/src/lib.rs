@@ -18,8 +18,10 @@ use mago_reporting::IssueCollection;
 use mago_syntax::cst::Node;
 use mago_syntax::cst::NodeKind;
 use mago_syntax::cst::Program;
+use mago_twig_syntax::cst::Template;
 
 use crate::context::LintContext;
+use crate::context::TemplateLintContext;
 use crate::external::ExternalLintError;
 use crate::external::ExternalLinter;
 use crate::external::LinterTransport;
@@ -120,6 +122,31 @@ where
         self.lint_internal(source_file, program, resolved_names, Some(external))
     }
 
+    /// Lints a Twig template with the rules that apply to templates.
+    ///
+    /// Template rules do not go through the pragma collector, as templates have no PHP comments
+    /// to carry `@mago-ignore` or `@mago-expect` pragmas.
+    #[must_use]
+    pub fn lint_template(&self, source_file: &File, template: &'arena Template<'arena>) -> IssueCollection {
+        let file_name = std::str::from_utf8(source_file.name.as_ref()).ok();
+
+        let mut context = TemplateLintContext::new(source_file, template);
+        for (rule_index, rule) in self.registry.rules().iter().enumerate() {
+            if !rule.is_template_rule() {
+                continue;
+            }
+
+            let matcher = self.registry.excludes_for(rule_index);
+            if file_name.is_some_and(|file_name| !matcher.is_empty() && matcher.is_match(file_name)) {
+                continue;
+            }
+
+            rule.check_template(&mut context);
+        }
+
+        context.finish()
+    }
+
     fn lint_internal<'ctx, 'ast, T>(
         &self,
         source_file: &'ctx File,
@@ -18,6 +18,8 @@ pub mod strict_behavior;
 pub mod strict_types;
 pub mod suspicious_explode_arguments;
 pub mod switch_continue_to_break;
+pub mod twig_unclosed_block;
+pub mod twig_unknown_filter;
 pub mod use_specific_assertions;
 
 pub use assert_description::*;
@@ -40,4 +42,6 @@ pub use strict_behavior::*;
 pub use strict_types::*;
 pub use suspicious_explode_arguments::*;
 pub use switch_continue_to_break::*;
+pub use twig_unclosed_block::*;
+pub use twig_unknown_filter::*;
 pub use use_specific_assertions::*;
@@ -0,0 +1,255 @@
+use indoc::indoc;
+use mago_allocator::Arena;
+use schemars::JsonSchema;
+
+use mago_database::file::FileId;
+use mago_reporting::Annotation;
+use mago_reporting::Issue;
+use mago_reporting::Level;
+use mago_span::Span;
+use mago_syntax::cst::Node;
+use mago_syntax::cst::NodeKind;
+use mago_syntax_core::input::Input;
+use mago_twig_syntax::lexer::TwigLexer;
+use mago_twig_syntax::settings::LexerSettings;
+use mago_twig_syntax::token::TwigTokenKind;
+
+use crate::category::Category;
+use crate::context::LintContext;
+use crate::context::TemplateLintContext;
+use crate::requirements::RuleRequirements;
+use crate::rule::Config;
+use crate::rule::LintRule;
+use crate::rule_meta::RuleMeta;
+use crate::settings::RuleSettings;
+
+/// Tags that must be closed by a matching `end` tag, such as `{% if %}` and `{% endif %}`.
+const PAIRED_TAGS: &[&[u8]] = &[
+    b"apply",
+    b"autoescape",
+    b"block",
+    b"cache",
+    b"embed",
+    b"filter",
+    b"for",
+    b"guard",
+    b"if",
+    b"macro",
+    b"raw",
+    b"sandbox",
+    b"set",
+    b"spaceless",
+    b"stopwatch",
+    b"trans",
+    b"verbatim",
+    b"with",
+];
+
+#[derive(Debug, Clone)]
+pub struct TwigUnclosedBlockRule {
+    meta: &'static RuleMeta,
+    cfg: TwigUnclosedBlockConfig,
+}
+
+#[derive(Debug, Clone, Copy, Eq, PartialEq, Hash, JsonSchema)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(default, rename_all = "kebab-case", deny_unknown_fields))]
+pub struct TwigUnclosedBlockConfig {
+    pub level: Level,
+}
+
+impl Default for TwigUnclosedBlockConfig {
+    fn default() -> Self {
+        Self { level: Level::Error }
+    }
+}
+
+impl Config for TwigUnclosedBlockConfig {
+    fn level(&self) -> Level {
+        self.level
+    }
+}
+
+impl LintRule for TwigUnclosedBlockRule {
+    type Config = TwigUnclosedBlockConfig;
+
+    fn meta() -> &'static RuleMeta {
+        const META: RuleMeta = RuleMeta {
+            name: "Twig Unclosed Block",
+            code: "twig-unclosed-block",
+            description: indoc! {r"
+                Flags Twig tags that are never closed, such as an `{% if %}` without `{% endif %}`,
+                and closing tags that do not match any open tag.
+
+                Unlike the parser, which stops at the first error, this rule reports every unbalanced
+                tag in the template, pointing at the tag that was left open.
+            "},
+            good_example: indoc! {r"
+                {% if user %}
+                    {% for role in user.roles %}
+                        <span>{{ role }}</span>
+                    {% endfor %}
+                {% endif %}
+            "},
+            bad_example: indoc! {r"
+                {% if user %}
+                    {% for role in user.roles %}
+                        <span>{{ role }}</span>
+                {% endif %}
+            "},
+            category: Category::Correctness,
+
+            requirements: RuleRequirements::None,
+        };
+        &META
+    }
+
+    fn targets() -> &'static [NodeKind] {
+        &[]
+    }
+
+    fn build(settings: &RuleSettings<Self::Config>) -> Self {
+        Self { meta: Self::meta(), cfg: settings.config }
+    }
+
+    fn check<'arena, A>(&self, _ctx: &mut LintContext<'_, 'arena, A>, _node: Node<'_, 'arena>)
+    where
+        A: Arena,
+    {
+    }
+
+    fn is_template_rule() -> bool {
+        true
+    }
+
+    fn check_template(&self, ctx: &mut TemplateLintContext<'_, '_>) {
+        let template = ctx.template;
+
+        let mut open_tags: Vec<ScannedTag<'_>> = Vec::new();
+        for tag in collect_tags(template.file_id, template.source_text) {
+            if let Some(opened) = tag.name.strip_prefix(b"end")
+                && PAIRED_TAGS.contains(&opened)
+            {
+                let Some(position) = open_tags.iter().rposition(|open_tag| open_tag.name == opened) else {
+                    self.report_unexpected_end_tag(ctx, &tag);
+
+                    continue;
+                };
+
+                for open_tag in open_tags.drain(position..).skip(1) {
+                    self.report_unclosed_tag(ctx, &open_tag, Some(&tag));
+                }
+
+                continue;
+            }
+
+            if tag.is_opening {
+                open_tags.push(tag);
+            }
+        }
+
+        for open_tag in open_tags.iter().rev() {
+            self.report_unclosed_tag(ctx, open_tag, None);
+        }
+    }
+}
+
+impl TwigUnclosedBlockRule {
+    fn report_unclosed_tag(
+        &self,
+        ctx: &mut TemplateLintContext<'_, '_>,
+        tag: &ScannedTag<'_>,
+        closed_by: Option<&ScannedTag<'_>>,
+    ) {
+        let name = String::from_utf8_lossy(tag.name);
+
+        let mut issue = Issue::new(self.cfg.level, format!("The `{name}` tag is never closed."))
+            .with_code(self.meta.code)
+            .with_annotation(Annotation::primary(tag.span).with_message(format!("`{name}` opened here")));
+
+        if let Some(closed_by) = closed_by {
+            let closing_name = String::from_utf8_lossy(closed_by.name);
+
+            issue = issue.with_annotation(
+                Annotation::secondary(closed_by.span)
+                    .with_message(format!("`{closing_name}` closes an outer tag before `end{name}`")),
+            );
+        }
+
+        ctx.report(issue.with_help(format!("Add a `{{% end{name} %}}` tag where the `{name}` block ends.")));
+    }
+
+    fn report_unexpected_end_tag(&self, ctx: &mut TemplateLintContext<'_, '_>, tag: &ScannedTag<'_>) {
+        let name = String::from_utf8_lossy(tag.name);
+
+        ctx.report(
+            Issue::new(self.cfg.level, format!("Unexpected `{name}` tag."))
+                .with_code(self.meta.code)
+                .with_annotation(
+                    Annotation::primary(tag.span).with_message(format!("`{name}` does not close any open tag")),
+                )
+                .with_help("Remove this tag, or add the opening tag it is meant to close."),
+        );
+    }
+}
+
+/// A `{% ... %}` tag found while scanning the template.
+struct ScannedTag<'input> {
+    name: &'input [u8],
+    span: Span,
+    /// Whether the tag opens a block that must be closed by a matching `end` tag.
+    is_opening: bool,
+}
+
+/// Scans the template source for `{% ... %}` tags.
+///
+/// The source is lexed rather than parsed, so that tags are still found after the first
+/// syntax error, which is where the parser gives up.
+fn collect_tags(file_id: FileId, source: &[u8]) -> Vec<ScannedTag<'_>> {
+    let mut lexer = TwigLexer::new(Input::new(file_id, source), LexerSettings);
+
+    let mut tags = Vec::new();
+    let mut open_span: Option<Span> = None;
+    let mut tokens = Vec::new();
+    while let Some(Ok(token)) = lexer.advance() {
+        if token.is_trivia() {
+            continue;
+        }
+
+        if token.kind.is_open_block() {
+            open_span = Some(token.span_for(file_id));
+            tokens.clear();
+
+            continue;
+        }
+
+        let Some(tag_start) = open_span else {
+            continue;
+        };
+
+        if !token.kind.is_close_block() {
+            tokens.push(token);
+
+            continue;
+        }
+
+        let span = tag_start.join(token.span_for(file_id));
+        if let Some(name) = tokens.first().filter(|token| token.kind == TwigTokenKind::Name).map(|token| token.value) {
+            let arguments = &tokens[1..];
+            let is_opening = PAIRED_TAGS.contains(&name)
+                && match name {
+                    // `{% block name %}` opens a block, while `{% block name value %}` does not.
+                    b"block" => arguments.len() <= 1,
+                    // `{% set name %}` captures its body, while `{% set name = value %}` does not.
+                    b"set" => !arguments.iter().any(|argument| argument.kind == TwigTokenKind::Equal),
+                    _ => true,
+                };
+
+            tags.push(ScannedTag { name, span, is_opening });
+        }
+
+        open_span = None;
+    }
+
+    tags
+}
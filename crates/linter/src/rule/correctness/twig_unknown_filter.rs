@@ -0,0 +1,218 @@
+use indoc::indoc;
+use mago_allocator::Arena;
+use schemars::JsonSchema;
+
+use mago_reporting::Annotation;
+use mago_reporting::Issue;
+use mago_reporting::Level;
+use mago_syntax::cst::Node;
+use mago_syntax::cst::NodeKind;
+use mago_twig_syntax::cst::Filter;
+use mago_twig_syntax::cst::FilterApplication;
+use mago_twig_syntax::cst::Identifier;
+use mago_twig_syntax::walker::Walker;
+
+use crate::category::Category;
+use crate::context::LintContext;
+use crate::context::TemplateLintContext;
+use crate::requirements::RuleRequirements;
+use crate::rule::Config;
+use crate::rule::LintRule;
+use crate::rule_meta::RuleMeta;
+use crate::settings::RuleSettings;
+
+/// Filters provided by Twig itself, the official Twig extra extensions, and the Symfony Twig bridge.
+const BUILTIN_FILTERS: &[&str] = &[
+    "abbr_class",
+    "abbr_method",
+    "abs",
+    "batch",
+    "capitalize",
+    "column",
+    "convert_encoding",
+    "country_name",
+    "currency_name",
+    "currency_symbol",
+    "data_uri",
+    "date",
+    "date_modify",
+    "default",
+    "e",
+    "emojify",
+    "escape",
+    "file_excerpt",
+    "file_link",
+    "file_relative",
+    "filter",
+    "find",
+    "first",
+    "format",
+    "format_args",
+    "format_args_as_text",
+    "format_currency",
+    "format_date",
+    "format_datetime",
+    "format_file",
+    "format_file_from_text",
+    "format_number",
+    "format_time",
+    "html_to_markdown",
+    "humanize",
+    "inky_to_html",
+    "inline_css",
+    "invoke",
+    "join",
+    "json_encode",
+    "keys",
+    "language_name",
+    "last",
+    "length",
+    "locale_name",
+    "lower",
+    "map",
+    "markdown_to_html",
+    "merge",
+    "nl2br",
+    "number_format",
+    "plural",
+    "raw",
+    "reduce",
+    "replace",
+    "reverse",
+    "round",
+    "sanitize_html",
+    "serialize",
+    "shuffle",
+    "singular",
+    "slice",
+    "slug",
+    "sort",
+    "spaceless",
+    "split",
+    "striptags",
+    "timezone_name",
+    "title",
+    "trans",
+    "trim",
+    "u",
+    "upper",
+    "url_encode",
+    "yaml_dump",
+    "yaml_encode",
+];
+
+#[derive(Debug, Clone)]
+pub struct TwigUnknownFilterRule {
+    meta: &'static RuleMeta,
+    cfg: TwigUnknownFilterConfig,
+}
+
+#[derive(Debug, Clone, Eq, PartialEq, JsonSchema)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(default, rename_all = "kebab-case", deny_unknown_fields))]
+pub struct TwigUnknownFilterConfig {
+    pub level: Level,
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub known_filters: Vec<String>,
+}
+
+impl Default for TwigUnknownFilterConfig {
+    fn default() -> Self {
+        Self { level: Level::Warning, known_filters: Vec::new() }
+    }
+}
+
+impl Config for TwigUnknownFilterConfig {
+    fn level(&self) -> Level {
+        self.level
+    }
+}
+
+impl LintRule for TwigUnknownFilterRule {
+    type Config = TwigUnknownFilterConfig;
+
+    fn meta() -> &'static RuleMeta {
+        const META: RuleMeta = RuleMeta {
+            name: "Twig Unknown Filter",
+            code: "twig-unknown-filter",
+            description: indoc! {r"
+                Flags filters that are not provided by Twig, the Twig extra extensions, or the Symfony Twig bridge.
+
+                An unknown filter is usually a typo, and fails when the template is compiled. Filters
+                registered by your own Twig extensions can be declared through the `known-filters` option:
+
+                ```toml
+                known-filters = ['price', 'markdown']
+                ```
+            "},
+            good_example: indoc! {r"
+                <h1>{{ title|upper }}</h1>
+            "},
+            bad_example: indoc! {r"
+                <h1>{{ title|uper }}</h1>
+            "},
+            category: Category::Correctness,
+
+            requirements: RuleRequirements::None,
+        };
+        &META
+    }
+
+    fn targets() -> &'static [NodeKind] {
+        &[]
+    }
+
+    fn build(settings: &RuleSettings<Self::Config>) -> Self {
+        Self { meta: Self::meta(), cfg: settings.config.clone() }
+    }
+
+    fn check<'arena, A>(&self, _ctx: &mut LintContext<'_, 'arena, A>, _node: Node<'_, 'arena>)
+    where
+        A: Arena,
+    {
+    }
+
+    fn is_template_rule() -> bool {
+        true
+    }
+
+    fn check_template(&self, ctx: &mut TemplateLintContext<'_, '_>) {
+        let template = ctx.template;
+
+        self.walk_template(template, ctx);
+    }
+}
+
+impl<'ast, 'arena> Walker<'ast, 'arena, TemplateLintContext<'_, 'arena>> for TwigUnknownFilterRule {
+    fn walk_in_filter(&self, filter: &'ast Filter<'arena>, ctx: &mut TemplateLintContext<'_, 'arena>) {
+        self.check_filter_name(ctx, &filter.name);
+    }
+
+    fn walk_in_filter_application(
+        &self,
+        filter_application: &'ast FilterApplication<'arena>,
+        ctx: &mut TemplateLintContext<'_, 'arena>,
+    ) {
+        self.check_filter_name(ctx, &filter_application.name);
+    }
+}
+
+impl TwigUnknownFilterRule {
+    fn check_filter_name(&self, ctx: &mut TemplateLintContext<'_, '_>, name: &Identifier<'_>) {
+        let is_known = |filter: &str| filter.as_bytes() == name.value;
+        if BUILTIN_FILTERS.iter().copied().any(is_known)
+            || self.cfg.known_filters.iter().map(String::as_str).any(is_known)
+        {
+            return;
+        }
+
+        let name_text = String::from_utf8_lossy(name.value);
+
+        ctx.report(
+            Issue::new(self.cfg.level, format!("Unknown filter `{name_text}`."))
+                .with_code(self.meta.code)
+                .with_annotation(Annotation::primary(name.span).with_message(format!("Filter `{name_text}` is not defined")))
+                .with_help("Check the filter name for typos, or add it to the `known-filters` option if it is provided by one of your extensions."),
+        );
+    }
+}
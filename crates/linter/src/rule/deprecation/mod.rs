@@ -5,6 +5,7 @@ pub mod explicit_nullable_param;
 pub mod no_underscore_class;
 pub mod no_void_reference_return;
 pub mod optional_param_order;
+pub mod twig_deprecated_spaceless;
 
 pub use deprecated_cast::*;
 pub use deprecated_shell_execute_string::*;
@@ -13,3 +14,4 @@ pub use explicit_nullable_param::*;
 pub use no_underscore_class::*;
 pub use no_void_reference_return::*;
 pub use optional_param_order::*;
+pub use twig_deprecated_spaceless::*;
@@ -0,0 +1,151 @@
+use indoc::indoc;
+use mago_allocator::Arena;
+use schemars::JsonSchema;
+
+use mago_reporting::Annotation;
+use mago_reporting::Issue;
+use mago_reporting::Level;
+use mago_span::HasSpan;
+use mago_span::Span;
+use mago_syntax::cst::Node;
+use mago_syntax::cst::NodeKind;
+use mago_twig_syntax::cst::Filter;
+use mago_twig_syntax::cst::FilterApplication;
+use mago_twig_syntax::cst::Unknown;
+use mago_twig_syntax::walker::Walker;
+
+use crate::category::Category;
+use crate::context::LintContext;
+use crate::context::TemplateLintContext;
+use crate::requirements::RuleRequirements;
+use crate::rule::Config;
+use crate::rule::LintRule;
+use crate::rule_meta::RuleMeta;
+use crate::settings::RuleSettings;
+
+#[derive(Debug, Clone)]
+pub struct TwigDeprecatedSpacelessRule {
+    meta: &'static RuleMeta,
+    cfg: TwigDeprecatedSpacelessConfig,
+}
+
+#[derive(Debug, Clone, Copy, Eq, PartialEq, Hash, JsonSchema)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(default, rename_all = "kebab-case", deny_unknown_fields))]
+pub struct TwigDeprecatedSpacelessConfig {
+    pub level: Level,
+}
+
+impl Default for TwigDeprecatedSpacelessConfig {
+    fn default() -> Self {
+        Self { level: Level::Warning }
+    }
+}
+
+impl Config for TwigDeprecatedSpacelessConfig {
+    fn level(&self) -> Level {
+        self.level
+    }
+}
+
+impl LintRule for TwigDeprecatedSpacelessRule {
+    type Config = TwigDeprecatedSpacelessConfig;
+
+    fn meta() -> &'static RuleMeta {
+        const META: RuleMeta = RuleMeta {
+            name: "Twig Deprecated Spaceless",
+            code: "twig-deprecated-spaceless",
+            description: indoc! {r"
+                Flags uses of `spaceless` in Twig templates.
+
+                The `{% spaceless %}` tag was removed in Twig 3, and the `spaceless` filter, including
+                `{% apply spaceless %}`, is deprecated since Twig 3.12. Whitespace between tags
+                can be removed with the whitespace control modifiers (`{%-`, `-%}`, `{{-`, `-}}`) instead.
+            "},
+            good_example: indoc! {r"
+                <ul>
+                    {%- for item in items -%}
+                        <li>{{ item }}</li>
+                    {%- endfor -%}
+                </ul>
+            "},
+            bad_example: indoc! {r"
+                {% apply spaceless %}
+                    <ul>
+                        {% for item in items %}
+                            <li>{{ item }}</li>
+                        {% endfor %}
+                    </ul>
+                {% endapply %}
+            "},
+            category: Category::Deprecation,
+
+            requirements: RuleRequirements::None,
+        };
+        &META
+    }
+
+    fn targets() -> &'static [NodeKind] {
+        &[]
+    }
+
+    fn build(settings: &RuleSettings<Self::Config>) -> Self {
+        Self { meta: Self::meta(), cfg: settings.config }
+    }
+
+    fn check<'arena, A>(&self, _ctx: &mut LintContext<'_, 'arena, A>, _node: Node<'_, 'arena>)
+    where
+        A: Arena,
+    {
+    }
+
+    fn is_template_rule() -> bool {
+        true
+    }
+
+    fn check_template(&self, ctx: &mut TemplateLintContext<'_, '_>) {
+        let template = ctx.template;
+
+        self.walk_template(template, ctx);
+    }
+}
+
+impl<'ast, 'arena> Walker<'ast, 'arena, TemplateLintContext<'_, 'arena>> for TwigDeprecatedSpacelessRule {
+    fn walk_in_filter(&self, filter: &'ast Filter<'arena>, ctx: &mut TemplateLintContext<'_, 'arena>) {
+        if filter.name.value == b"spaceless" {
+            self.report_filter(ctx, filter.name.span);
+        }
+    }
+
+    fn walk_in_filter_application(
+        &self,
+        filter_application: &'ast FilterApplication<'arena>,
+        ctx: &mut TemplateLintContext<'_, 'arena>,
+    ) {
+        if filter_application.name.value == b"spaceless" {
+            self.report_filter(ctx, filter_application.name.span);
+        }
+    }
+
+    fn walk_in_unknown(&self, unknown: &'ast Unknown<'arena>, ctx: &mut TemplateLintContext<'_, 'arena>) {
+        if unknown.name.value == b"spaceless" {
+            ctx.report(
+                Issue::new(self.cfg.level, "The `spaceless` tag was removed in Twig 3.")
+                    .with_code(self.meta.code)
+                    .with_annotation(Annotation::primary(unknown.span()).with_message("`spaceless` tag used here"))
+                    .with_help("Remove the tag, and use whitespace control modifiers such as `{%-` and `-%}` where whitespace must be trimmed."),
+            );
+        }
+    }
+}
+
+impl TwigDeprecatedSpacelessRule {
+    fn report_filter(&self, ctx: &mut TemplateLintContext<'_, '_>, span: Span) {
+        ctx.report(
+            Issue::new(self.cfg.level, "The `spaceless` filter is deprecated since Twig 3.12.")
+                .with_code(self.meta.code)
+                .with_annotation(Annotation::primary(span).with_message("`spaceless` filter used here"))
+                .with_help("Remove the filter, and use whitespace control modifiers such as `{%-` and `-%}` where whitespace must be trimmed."),
+        );
+    }
+}
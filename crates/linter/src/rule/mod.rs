@@ -6,6 +6,7 @@ use mago_syntax::cst::Node;
 use mago_syntax::cst::NodeKind;
 
 use crate::context::LintContext;
+use crate::context::TemplateLintContext;
 use crate::integration::IntegrationSet;
 use crate::rule_meta::RuleMeta;
 use crate::settings::RuleSettings;
@@ -85,6 +86,17 @@ pub trait LintRule {
     fn check<'arena, A>(&self, ctx: &mut LintContext<'_, 'arena, A>, node: Node<'_, 'arena>)
     where
         A: Arena;
+
+    /// Whether the rule checks Twig templates instead of PHP code.
+    #[must_use]
+    fn is_template_rule() -> bool {
+        false
+    }
+
+    /// Checks a Twig template.
+    ///
+    /// Only called for rules returning `true` from [`LintRule::is_template_rule`].
+    fn check_template(&self, _ctx: &mut TemplateLintContext<'_, '_>) {}
 }
 
 macro_rules! define_rules {
@@ -165,6 +177,20 @@ macro_rules! define_rules {
                 }
             }
 
+            #[inline]
+            pub fn is_template_rule(&self) -> bool {
+                match self {
+                    $( AnyRule::$variant(_) => $rule::is_template_rule(), )*
+                }
+            }
+
+            #[inline]
+            pub fn check_template(&self, ctx: &mut TemplateLintContext<'_, '_>) {
+                match self {
+                    $( AnyRule::$variant(r) => r.check_template(ctx), )*
+                }
+            }
+
             #[inline]
             pub fn check<'arena, A>(&self, ctx: &mut LintContext<'_, 'arena, A>, node: Node<'_, 'arena>)
             where
@@ -394,4 +420,8 @@ define_rules! {
     MissingDocs(missing_docs @ MissingDocsRule),
     NoNullPropertyInit(no_null_property_init @ NoNullPropertyInitRule),
     PreferExplodeOverPregSplit(prefer_explode_over_preg_split @ PreferExplodeOverPregSplitRule),
+    TwigDeprecatedSpaceless(twig_deprecated_spaceless @ TwigDeprecatedSpacelessRule),
+    TwigNoDump(twig_no_dump @ TwigNoDumpRule),
+    TwigUnclosedBlock(twig_unclosed_block @ TwigUnclosedBlockRule),
+    TwigUnknownFilter(twig_unknown_filter @ TwigUnknownFilterRule),
 }
@@ -12,6 +12,7 @@ pub mod prepared_sql;
 pub mod require_preg_quote_delimiter;
 pub mod sensitive_parameter;
 pub mod tainted_data_to_sink;
+pub mod twig_no_dump;
 pub mod validated_sanitized_input;
 
 pub use disallowed_functions::*;
@@ -28,4 +29,5 @@ pub use prepared_sql::*;
 pub use require_preg_quote_delimiter::*;
 pub use sensitive_parameter::*;
 pub use tainted_data_to_sink::*;
+pub use twig_no_dump::*;
 pub use validated_sanitized_input::*;
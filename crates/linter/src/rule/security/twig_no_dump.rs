@@ -0,0 +1,129 @@
+use indoc::indoc;
+use mago_allocator::Arena;
+use schemars::JsonSchema;
+
+use mago_reporting::Annotation;
+use mago_reporting::Issue;
+use mago_reporting::Level;
+use mago_span::HasSpan;
+use mago_span::Span;
+use mago_syntax::cst::Node;
+use mago_syntax::cst::NodeKind;
+use mago_twig_syntax::cst::Call;
+use mago_twig_syntax::cst::Expression;
+use mago_twig_syntax::cst::Unknown;
+use mago_twig_syntax::walker::Walker;
+
+use crate::category::Category;
+use crate::context::LintContext;
+use crate::context::TemplateLintContext;
+use crate::requirements::RuleRequirements;
+use crate::rule::Config;
+use crate::rule::LintRule;
+use crate::rule_meta::RuleMeta;
+use crate::settings::RuleSettings;
+
+#[derive(Debug, Clone)]
+pub struct TwigNoDumpRule {
+    meta: &'static RuleMeta,
+    cfg: TwigNoDumpConfig,
+}
+
+#[derive(Debug, Clone, Copy, Eq, PartialEq, Hash, JsonSchema)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(default, rename_all = "kebab-case", deny_unknown_fields))]
+pub struct TwigNoDumpConfig {
+    pub level: Level,
+}
+
+impl Default for TwigNoDumpConfig {
+    fn default() -> Self {
+        Self { level: Level::Note }
+    }
+}
+
+impl Config for TwigNoDumpConfig {
+    fn level(&self) -> Level {
+        self.level
+    }
+}
+
+impl LintRule for TwigNoDumpRule {
+    type Config = TwigNoDumpConfig;
+
+    fn meta() -> &'static RuleMeta {
+        const META: RuleMeta = RuleMeta {
+            name: "Twig No Dump",
+            code: "twig-no-dump",
+            description: indoc! {r"
+                Flags `dump()` calls and `{% dump %}` tags in Twig templates.
+
+                Dumping variables is useful while debugging a template, but it should not be
+                committed as it can expose sensitive information to the users of the application.
+            "},
+            good_example: indoc! {r"
+                <p>{{ user.name }}</p>
+            "},
+            bad_example: indoc! {r"
+                {{ dump(user) }}
+                <p>{{ user.name }}</p>
+            "},
+            category: Category::Security,
+
+            requirements: RuleRequirements::None,
+        };
+        &META
+    }
+
+    fn targets() -> &'static [NodeKind] {
+        &[]
+    }
+
+    fn build(settings: &RuleSettings<Self::Config>) -> Self {
+        Self { meta: Self::meta(), cfg: settings.config }
+    }
+
+    fn check<'arena, A>(&self, _ctx: &mut LintContext<'_, 'arena, A>, _node: Node<'_, 'arena>)
+    where
+        A: Arena,
+    {
+    }
+
+    fn is_template_rule() -> bool {
+        true
+    }
+
+    fn check_template(&self, ctx: &mut TemplateLintContext<'_, '_>) {
+        let template = ctx.template;
+
+        self.walk_template(template, ctx);
+    }
+}
+
+impl<'ast, 'arena> Walker<'ast, 'arena, TemplateLintContext<'_, 'arena>> for TwigNoDumpRule {
+    fn walk_in_call(&self, call: &'ast Call<'arena>, ctx: &mut TemplateLintContext<'_, 'arena>) {
+        if let Expression::Name(name) = call.callee
+            && name.name == b"dump"
+        {
+            self.report(ctx, call.span(), "Function `dump` should be removed before committing");
+        }
+    }
+
+    fn walk_in_unknown(&self, unknown: &'ast Unknown<'arena>, ctx: &mut TemplateLintContext<'_, 'arena>) {
+        if unknown.name.value == b"dump" {
+            self.report(ctx, unknown.span(), "Tag `dump` should be removed before committing");
+        }
+    }
+}
+
+impl TwigNoDumpRule {
+    fn report(&self, ctx: &mut TemplateLintContext<'_, '_>, span: Span, message: &str) {
+        ctx.report(
+            Issue::new(self.cfg.level, "Do not commit template dumps.")
+                .with_code(self.meta.code)
+                .with_annotation(Annotation::primary(span).with_message(message))
+                .with_note("Dumped variables can expose sensitive application data if left in production templates.")
+                .with_help("Remove this dump."),
+        );
+    }
+}
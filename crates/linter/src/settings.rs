@@ -185,6 +185,10 @@ use crate::rule::TooManyEnumCasesConfig;
 use crate::rule::TooManyMethodsConfig;
 use crate::rule::TooManyPropertiesConfig;
 use crate::rule::TraitNameConfig;
+use crate::rule::TwigDeprecatedSpacelessConfig;
+use crate::rule::TwigNoDumpConfig;
+use crate::rule::TwigUnclosedBlockConfig;
+use crate::rule::TwigUnknownFilterConfig;
 use crate::rule::UseCompoundAssignmentConfig;
 use crate::rule::UseDedicatedExpectationConfig;
 use crate::rule::UseSimplerExpectationConfig;
@@ -418,6 +422,10 @@ pub struct RulesSettings {
     pub no_roles_as_capabilities: RuleSettings<NoRolesAsCapabilitiesConfig>,
     pub missing_docs: RuleSettings<MissingDocsConfig>,
     pub no_literal_namespace_string: RuleSettings<NoLiteralNamespaceStringConfig>,
+    pub twig_deprecated_spaceless: RuleSettings<TwigDeprecatedSpacelessConfig>,
+    pub twig_no_dump: RuleSettings<TwigNoDumpConfig>,
+    pub twig_unclosed_block: RuleSettings<TwigUnclosedBlockConfig>,
+    pub twig_unknown_filter: RuleSettings<TwigUnknownFilterConfig>,
 }
 
 impl<C: Config> RuleSettings<C> {
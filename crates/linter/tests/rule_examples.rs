@@ -15,6 +15,7 @@ use mago_linter::settings::RulesSettings;
 use mago_linter::settings::Settings;
 use mago_names::resolver::NameResolver;
 use mago_syntax::parser::parse_file;
+use mago_twig_syntax::parser::parse_file as parse_template_file;
 
 #[test]
 fn test_all_rule_examples() {
@@ -28,12 +29,14 @@ fn test_all_rule_examples() {
         let rule_code = rule.code();
         let rule_meta = rule.meta();
 
-        let bad_result = test_code_snippet(rule_code, rule_meta.bad_example, true);
+        let test_snippet = if rule.is_template_rule() { test_template_snippet } else { test_code_snippet };
+
+        let bad_result = test_snippet(rule_code, rule_meta.bad_example, true);
         if let Err(e) = bad_result {
             failures.push(format!("Rule '{rule_code}': Bad example issue - {e}"));
         }
 
-        let good_result = test_code_snippet(rule_code, rule_meta.good_example, false);
+        let good_result = test_snippet(rule_code, rule_meta.good_example, false);
         if let Err(e) = good_result {
             failures.push(format!("Rule '{rule_code}': Good example issue - {e}"));
         }
@@ -91,14 +94,41 @@ fn test_code_snippet(rule_code: &str, code: &str, should_have_issues: bool) -> R
 
     let issues = linter.lint(&file, program, &resolved_names);
 
-    let has_issues = !issues.is_empty();
+    check_issues(issues.len(), should_have_issues)
+}
+
+/// Test a Twig template snippet and verify it produces (or doesn't produce) issues
+fn test_template_snippet(rule_code: &str, code: &str, should_have_issues: bool) -> Result<(), String> {
+    let arena = LocalArena::new();
+
+    let file = File::ephemeral(Cow::Owned(b"test.twig".to_vec()), Cow::Owned(code.as_bytes().to_vec()));
+
+    let template = parse_template_file(&arena, &file);
+    if !should_have_issues && !template.errors.is_empty() {
+        return Err("Failed to parse template snippet.".to_string());
+    }
+
+    let settings = Settings::default();
+    let php_version = settings.php_version;
+    let registry = RuleRegistry::build(&settings, Some(&[rule_code.to_string()]), true);
+    if registry.rules().is_empty() {
+        return Err(format!("No rules found for code '{rule_code}'"));
+    }
+
+    let linter = Linter::from_registry(&arena, std::sync::Arc::new(registry), php_version);
+
+    let issues = linter.lint_template(&file, template);
+
+    check_issues(issues.len(), should_have_issues)
+}
 
-    if should_have_issues && !has_issues {
+fn check_issues(issue_count: usize, should_have_issues: bool) -> Result<(), String> {
+    if should_have_issues && issue_count == 0 {
         return Err("Expected bad example to produce issues, but none were found.".to_string());
     }
 
-    if !should_have_issues && has_issues {
-        return Err(format!("Expected good example to NOT produce issues, but found {} issue(s).", issues.len(),));
+    if !should_have_issues && issue_count > 0 {
+        return Err(format!("Expected good example to NOT produce issues, but found {issue_count} issue(s)."));
     }
 
     Ok(())
@@ -0,0 +1,63 @@
+use std::borrow::Cow;
+
+use mago_allocator::LocalArena;
+use mago_database::file::File;
+use mago_linter::Linter;
+use mago_linter::registry::RuleRegistry;
+use mago_linter::settings::Settings;
+use mago_twig_syntax::parser::parse_file;
+
+/// Helper to lint a template, and return the messages of the reported issues.
+fn lint(code: &str) -> Vec<String> {
+    let arena = LocalArena::new();
+
+    let file = File::ephemeral(Cow::Owned(b"test.twig".to_vec()), Cow::Owned(code.as_bytes().to_vec()));
+
+    let template = parse_file(&arena, &file);
+
+    let settings = Settings::default();
+    let php_version = settings.php_version;
+    let registry = RuleRegistry::build(&settings, Some(&["twig-unclosed-block".to_string()]), true);
+
+    let linter = Linter::from_registry(&arena, std::sync::Arc::new(registry), php_version);
+
+    linter.lint_template(&file, template).into_iter().map(|issue| issue.message).collect()
+}
+
+#[test]
+fn test_balanced_tags_are_not_reported() {
+    let messages = lint(
+        "{% block content %}{% if a %}{% for b in c %}{{ b }}{% endfor %}{% endif %}{% endblock %}\n\
+         {% set x = 1 %}{% set y %}captured{% endset %}{% block title 'Title' %}",
+    );
+
+    assert!(messages.is_empty(), "unexpected issues: {messages:?}");
+}
+
+#[test]
+fn test_tag_closed_by_outer_end_tag_is_reported() {
+    let messages = lint("{% if a %}{% for b in c %}{{ b }}{% endif %}");
+
+    assert_eq!(messages, vec!["The `for` tag is never closed.".to_string()]);
+}
+
+#[test]
+fn test_every_tag_left_open_is_reported() {
+    let messages = lint("{% block content %}{% if a %}{% apply upper %}text");
+
+    assert_eq!(
+        messages,
+        vec![
+            "The `apply` tag is never closed.".to_string(),
+            "The `if` tag is never closed.".to_string(),
+            "The `block` tag is never closed.".to_string(),
+        ]
+    );
+}
+
+#[test]
+fn test_stray_end_tag_is_reported() {
+    let messages = lint("{% if a %}{% endif %}{% endfor %}");
+
+    assert_eq!(messages, vec!["Unexpected `endfor` tag.".to_string()]);
+}
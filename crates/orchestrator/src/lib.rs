@@ -33,6 +33,7 @@
 //! 4. Run the service to get results
 
 use std::borrow::Cow;
+use std::ops::Range;
 use std::path::Path;
 use std::path::PathBuf;
 use std::sync::Arc;
@@ -432,6 +433,7 @@ impl<'cfg> Orchestrator<'cfg> {
     /// - `Ok(FileFormatStatus::Unchanged)` if the file is already properly formatted
     /// - `Ok(FileFormatStatus::Changed(String))` if the file was formatted, containing the new content
     /// - `Ok(FileFormatStatus::FailedToParse(ParseError))` if the file couldn't be parsed
+    /// - `Ok(FileFormatStatus::FailedToParseTemplate(String))` if the file is a Twig template that couldn't be parsed
     /// - `Err(OrchestratorError)` if formatting failed for other reasons
     ///
     /// # Performance
@@ -450,6 +452,22 @@ impl<'cfg> Orchestrator<'cfg> {
         service.format_file(file)
     }
 
+    /// Formats only the statements of a single PHP file that overlap `range`, a range of byte offsets.
+    ///
+    /// The smallest run of statements or class-like members enclosing the range is formatted and
+    /// spliced back into the file; everything outside of it is kept byte for byte. When that is
+    /// not possible, because formatting changes the statements around the range, the result is
+    /// [`FileFormatStatus::FailedToIsolateRange`].
+    ///
+    /// # Errors
+    ///
+    /// Returns [`OrchestratorError`] if `file` is a Twig template, or when formatting cannot complete.
+    pub fn format_file_range(&self, file: &File, range: Range<u32>) -> Result<FileFormatStatus, OrchestratorError> {
+        let service = self.get_format_service(ReadDatabase::empty());
+
+        service.format_file_range(file, range)
+    }
+
     /// Formats a single file using a provided bump arena for allocations.
     ///
     /// This method is similar to [`format_file`](Self::format_file) but allows you to
@@ -466,6 +484,7 @@ impl<'cfg> Orchestrator<'cfg> {
     /// - `Ok(FileFormatStatus::Unchanged)` if the file is already properly formatted
     /// - `Ok(FileFormatStatus::Changed(String))` if the file was formatted, containing the new content
     /// - `Ok(FileFormatStatus::FailedToParse(ParseError))` if the file couldn't be parsed
+    /// - `Ok(FileFormatStatus::FailedToParseTemplate(String))` if the file is a Twig template that couldn't be parsed
     /// - `Err(OrchestratorError)` if formatting failed for other reasons
     ///
     /// # Performance
@@ -14,13 +14,21 @@ use mago_allocator::LocalArena;
 use mago_analyzer::Analyzer;
 use mago_analyzer::analysis_result::AnalysisResult;
 use mago_analyzer::analysis_result::LateSymbolReferenceIssueReconciler;
+use mago_analyzer::container::ServiceContainer;
+use mago_analyzer::dead_code::EntryPoints;
+use mago_analyzer::dead_code::find_unreachable_symbols;
 use mago_analyzer::error::AnalysisError;
 use mago_analyzer::external::AFTER_FILE_ANALYSIS_BATCH_SIZE;
 use mago_analyzer::external::FileAnalysisSnapshot;
+use mago_analyzer::form::FormIndex;
 use mago_analyzer::plugin::PluginRegistry;
+use mago_analyzer::plugin::libraries::LaravelPlugin;
+use mago_analyzer::plugin::libraries::SymfonyPlugin;
+use mago_analyzer::schema::DatabaseSchema;
 use mago_analyzer::settings::Settings;
 #[cfg(not(target_arch = "wasm32"))]
 use mago_analyzer::telemetry as analyzer_telemetry;
+use mago_analyzer::template::TemplateIndex;
 use mago_codex::metadata::CodebaseMetadata;
 use mago_codex::populator::populate_codebase;
 use mago_codex::reference::SymbolReferences;
@@ -160,10 +168,32 @@ impl AnalysisService {
             self.plugin_registry.external_node_analysis_requirements().map_err(AnalysisError::from)?;
 
         // Run the analyzer
+        let check_taints = self.settings.check_taints;
+        let symfony = self.plugin_registry.has_plugin(&SymfonyPlugin);
+        let templates = if symfony {
+            TemplateIndex::from_files(self.database.files(), &self.settings.template_paths)
+        } else {
+            TemplateIndex::new()
+        };
+        let schema = if self.plugin_registry.has_plugin(&LaravelPlugin) {
+            DatabaseSchema::from_files(self.database.files(), &self.settings.migration_paths)
+        } else {
+            DatabaseSchema::new()
+        };
+        let container = symfony
+            .then(|| read_container_dump(&self.settings))
+            .flatten()
+            .map(|xml| ServiceContainer::from_xml(&xml))
+            .unwrap_or_default();
+        let forms = if symfony { FormIndex::from_files(self.database.files()) } else { FormIndex::new() };
         let mut analysis_result = AnalysisResult::new(self.symbol_references);
         let mut analyzer =
-            Analyzer::new(&arena, &file, &resolved_names, &self.codebase, &self.plugin_registry, self.settings);
-        if after_file || after_analysis {
+            Analyzer::new(&arena, &file, &resolved_names, &self.codebase, &self.plugin_registry, self.settings)
+                .with_templates(&templates)
+                .with_schema(&schema)
+                .with_container(&container)
+                .with_forms(&forms);
+        if after_file || after_analysis || check_taints {
             analyzer = analyzer.with_deferred_pragmas();
         }
         if let Some(session) = external_session.as_ref() {
@@ -200,6 +230,7 @@ impl AnalysisService {
         let mut pragma_reconciler =
             DeferredIssueReconciler::new(analysis_result.take_deferred_pragmas(), self.database.files());
         analysis_result.issues = pragma_reconciler.reconcile(std::mem::take(&mut analysis_result.issues))?;
+        analysis_result.issues.extend(pragma_reconciler.reconcile(analysis_result.taint_graph.find_flows())?);
         issues.extend(analysis_result.issues.iter().cloned());
         issues.extend(self.codebase.take_issues(true));
         if after_analysis {
@@ -243,16 +274,37 @@ impl AnalysisService {
             self.plugin_registry.create_external_analysis_session(self.database.files()).map(Arc::new);
         let codebase_scan_plan =
             self.plugin_registry.external_codebase_scan_plan().map_err(AnalysisError::from)?.map(Arc::new);
+        let symfony = self.plugin_registry.has_plugin(&SymfonyPlugin);
+        let templates = Arc::new(if symfony {
+            TemplateIndex::from_files(self.database.files(), &self.settings.template_paths)
+        } else {
+            TemplateIndex::new()
+        });
+        let schema = Arc::new(if self.plugin_registry.has_plugin(&LaravelPlugin) {
+            DatabaseSchema::from_files(self.database.files(), &self.settings.migration_paths)
+        } else {
+            DatabaseSchema::new()
+        });
+        let container = Arc::new(
+            symfony
+                .then(|| read_container_dump(&self.settings))
+                .flatten()
+                .map(|xml| ServiceContainer::from_xml(&xml))
+                .unwrap_or_default(),
+        );
+        let forms = Arc::new(if symfony { FormIndex::from_files(self.database.files()) } else { FormIndex::new() });
         let lifecycle_capabilities = Arc::new(OnceLock::new());
         let additional_symbol_references = Arc::new(OnceLock::new());
         let reducer = AnalysisResultReducer {
             plugin_registry: Arc::clone(&self.plugin_registry),
             external_session: external_session.clone(),
-            files: if external_session.is_some() {
+            files: if external_session.is_some() || self.settings.check_taints || self.settings.find_unreachable_symbols
+            {
                 self.database.files().collect::<Vec<_>>().into()
             } else {
                 Arc::from([])
             },
+            entry_points: self.settings.find_unreachable_symbols.then(|| self.settings.entry_points.clone()),
         };
 
         let pipeline = ParallelPipeline::new(
@@ -333,6 +385,7 @@ impl AnalysisService {
             move |(settings, parser_settings), arena, source_file, codebase| {
                 let (after_file, after_analysis, node_analysis_requirements) =
                     map_capabilities.get().cloned().unwrap_or_default();
+                let defer_pragmas = settings.check_taints || settings.find_unreachable_symbols;
 
                 #[cfg(not(target_arch = "wasm32"))]
                 let per_file_start = trace_enabled.then(Instant::now);
@@ -363,8 +416,12 @@ impl AnalysisService {
                 #[cfg(not(target_arch = "wasm32"))]
                 let analyzer_new_start = trace_enabled.then(Instant::now);
                 let mut analyzer =
-                    Analyzer::new(arena, &source_file, &resolved_names, &codebase, &plugin_registry, settings);
-                if after_file || after_analysis {
+                    Analyzer::new(arena, &source_file, &resolved_names, &codebase, &plugin_registry, settings)
+                        .with_templates(&templates)
+                        .with_schema(&schema)
+                        .with_container(&container)
+                        .with_forms(&forms);
+                if after_file || after_analysis || defer_pragmas {
                     analyzer = analyzer.with_deferred_pragmas();
                 }
                 if let Some(session) = external_session.as_deref() {
@@ -451,6 +508,23 @@ impl AnalysisService {
     }
 }
 
+/// Reads the service container dump configured in the settings.
+///
+/// Returns `None` when no dump is configured or it cannot be read, in which case plugins fall
+/// back to their untyped behaviour.
+pub(crate) fn read_container_dump(settings: &Settings) -> Option<String> {
+    let path = settings.container_xml_path.as_ref()?;
+
+    match std::fs::read_to_string(path) {
+        Ok(xml) => Some(xml),
+        Err(error) => {
+            tracing::warn!("Failed to read the service container dump at {}: {error}", path.display());
+
+            None
+        }
+    }
+}
+
 /// The "reduce" step for the analysis pipeline.
 ///
 /// This struct aggregates the `AnalysisResult` from each parallel task into a single,
@@ -466,6 +540,7 @@ struct AnalysisResultReducer {
     plugin_registry: Arc<PluginRegistry>,
     external_session: Option<Arc<mago_analyzer::external::ExternalAnalysisSession>>,
     files: Arc<[Arc<File>]>,
+    entry_points: Option<EntryPoints>,
 }
 
 impl Reducer<AnalysisTaskResult, AnalysisResult> for AnalysisResultReducer {
@@ -529,6 +604,18 @@ impl Reducer<AnalysisTaskResult, AnalysisResult> for AnalysisResultReducer {
         let mut pragma_reconciler =
             DeferredIssueReconciler::new(aggregated_result.take_deferred_pragmas(), self.files.iter().cloned());
         aggregated_result.issues = pragma_reconciler.reconcile(std::mem::take(&mut aggregated_result.issues))?;
+        let taint_issues = aggregated_result.taint_graph.find_flows();
+        aggregated_result.issues.extend(pragma_reconciler.reconcile(taint_issues)?);
+        if let Some(entry_points) = &self.entry_points {
+            let unreachable_symbols = find_unreachable_symbols(
+                &codebase,
+                &aggregated_result.symbol_references,
+                entry_points,
+                self.files.iter().map(Arc::as_ref),
+            );
+            aggregated_result.issues.extend(pragma_reconciler.reconcile(unreachable_symbols)?);
+        }
+
         let after_issues = self
             .plugin_registry
             .run_external_after_analysis_hooks(
@@ -0,0 +1,186 @@
+//! Persistent on-disk cache for the incremental analysis service.
+//!
+//! The cache stores the state an [`IncrementalAnalysisService`] holds after a run: the
+//! populated codebase, the symbol reference graph, and, for every source file, its content
+//! hash, owned codebase entries, and issues. A later process restores that state and runs
+//! [`analyze_incremental()`](IncrementalAnalysisService::analyze_incremental), so only the
+//! files whose content hash changed are re-scanned, and only the symbols affected by those
+//! changes (and their dependents) are re-analyzed.
+//!
+//! Each cache file is keyed by the PHP version and a hash of the configuration supplied by
+//! the caller, and is tagged with the Mago version that wrote it. A cache written by another
+//! Mago version, or for a different key, is ignored.
+//!
+//! [`IncrementalAnalysisService`]: crate::service::incremental_analysis::IncrementalAnalysisService
+
+use std::borrow::Cow;
+use std::fs;
+use std::io::BufReader;
+use std::io::BufWriter;
+use std::io::Write;
+use std::path::Path;
+use std::path::PathBuf;
+
+use bincode::config::standard;
+use serde::Deserialize;
+use serde::Serialize;
+
+use mago_codex::metadata::CodebaseEntryKeys;
+use mago_codex::metadata::CodebaseMetadata;
+use mago_codex::reference::SymbolReferences;
+use mago_database::file::FileId;
+use mago_php_version::PHPVersion;
+use mago_reporting::IssueCollection;
+
+use crate::error::OrchestratorError;
+
+/// The Mago version recorded in, and required of, every cache file.
+const CACHE_VERSION: &str = env!("CARGO_PKG_VERSION");
+
+/// A directory holding persisted incremental analysis state.
+#[derive(Debug, Clone)]
+pub struct AnalysisCache {
+    path: PathBuf,
+    php_version: PHPVersion,
+    configuration_hash: u64,
+}
+
+/// The header written before the cached state, used to reject foreign cache files.
+#[derive(Serialize, Deserialize)]
+struct CacheHeader<'version> {
+    version: Cow<'version, str>,
+    php_version: PHPVersion,
+    configuration_hash: u64,
+}
+
+/// Persisted state of an incremental analysis service.
+///
+/// Fields borrow when writing and own when reading, so storing a large codebase does
+/// not require cloning it first.
+#[derive(Serialize, Deserialize)]
+pub(crate) struct CachedAnalysis<'state> {
+    pub(crate) codebase: Cow<'state, CodebaseMetadata>,
+    pub(crate) symbol_references: Cow<'state, SymbolReferences>,
+    pub(crate) native_symbol_references: Cow<'state, SymbolReferences>,
+    pub(crate) external_symbol_references: Cow<'state, SymbolReferences>,
+    pub(crate) codebase_issues: Cow<'state, IssueCollection>,
+    pub(crate) lifecycle_issues: Cow<'state, IssueCollection>,
+    /// Hash of the Twig templates the analysis was checked against.
+    pub(crate) templates_hash: u64,
+    /// Hash of the migrations the database schema was built from.
+    pub(crate) schema_hash: u64,
+    /// Hash of the service container dump the analysis was checked against.
+    pub(crate) container_hash: u64,
+    /// The project files that may declare the data class of a form type.
+    pub(crate) form_files: Vec<FileId>,
+    pub(crate) files: Vec<CachedFile<'state>>,
+}
+
+/// Persisted state of a single source file.
+#[derive(Serialize, Deserialize)]
+pub(crate) struct CachedFile<'state> {
+    pub(crate) file_id: FileId,
+    pub(crate) content_hash: u64,
+    pub(crate) entry_keys: Cow<'state, CodebaseEntryKeys>,
+    pub(crate) analysis_issues: Cow<'state, IssueCollection>,
+    pub(crate) codebase_issues: Cow<'state, IssueCollection>,
+}
+
+impl AnalysisCache {
+    /// Creates a cache stored in `directory`.
+    ///
+    /// # Arguments
+    ///
+    /// * `directory` - The directory holding cache files; created on first write
+    /// * `php_version` - The PHP version the analysis targets
+    /// * `configuration_hash` - A hash of every configuration value that affects analysis results
+    #[must_use]
+    pub fn new(directory: &Path, php_version: PHPVersion, configuration_hash: u64) -> Self {
+        let path = directory.join(format!("analysis-{php_version}-{configuration_hash:016x}.bin"));
+
+        Self { path, php_version, configuration_hash }
+    }
+
+    /// Returns the path of the cache file for this key.
+    #[must_use]
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+
+    /// Reads the cached state, returning `None` when the cache is missing, stale, or unreadable.
+    pub(crate) fn load(&self) -> Option<CachedAnalysis<'static>> {
+        let file = fs::File::open(&self.path).ok()?;
+        let mut reader = BufReader::new(file);
+
+        let header: CacheHeader<'static> = match bincode::serde::decode_from_std_read(&mut reader, standard()) {
+            Ok(header) => header,
+            Err(error) => {
+                tracing::debug!("Ignoring unreadable analysis cache '{}': {error}", self.path.display());
+
+                return None;
+            }
+        };
+
+        if header.version != CACHE_VERSION
+            || header.php_version != self.php_version
+            || header.configuration_hash != self.configuration_hash
+        {
+            tracing::debug!(
+                "Ignoring analysis cache '{}' written by Mago {} for another configuration.",
+                self.path.display(),
+                header.version
+            );
+
+            return None;
+        }
+
+        match bincode::serde::decode_from_std_read(&mut reader, standard()) {
+            Ok(state) => Some(state),
+            Err(error) => {
+                tracing::debug!("Ignoring corrupted analysis cache '{}': {error}", self.path.display());
+
+                None
+            }
+        }
+    }
+
+    /// Writes `state` to the cache, replacing any previous contents.
+    ///
+    /// The state is written to a temporary file first and then renamed into place, so
+    /// concurrent readers never observe a partially written cache.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`OrchestratorError::General`] if the cache directory or file cannot be written.
+    pub(crate) fn store(&self, state: &CachedAnalysis<'_>) -> Result<(), OrchestratorError> {
+        let error = |error: &dyn std::fmt::Display| {
+            OrchestratorError::General(format!("Failed to write analysis cache '{}': {error}", self.path.display()))
+        };
+
+        if let Some(directory) = self.path.parent() {
+            fs::create_dir_all(directory).map_err(|e| error(&e))?;
+        }
+
+        let temporary = self.path.with_extension(format!("{}.tmp", std::process::id()));
+        let header = CacheHeader {
+            version: Cow::Borrowed(CACHE_VERSION),
+            php_version: self.php_version,
+            configuration_hash: self.configuration_hash,
+        };
+
+        let written = fs::File::create(&temporary).map_err(|e| error(&e)).and_then(|file| {
+            let mut writer = BufWriter::new(file);
+            bincode::serde::encode_into_std_write(&header, &mut writer, standard()).map_err(|e| error(&e))?;
+            bincode::serde::encode_into_std_write(state, &mut writer, standard()).map_err(|e| error(&e))?;
+            writer.flush().map_err(|e| error(&e))
+        });
+
+        if let Err(err) = written.and_then(|()| fs::rename(&temporary, &self.path).map_err(|e| error(&e))) {
+            let _ = fs::remove_file(&temporary);
+
+            return Err(err);
+        }
+
+        Ok(())
+    }
+}
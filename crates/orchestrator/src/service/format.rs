@@ -1,3 +1,5 @@
+use std::ops::Range;
+
 use foldhash::HashMap;
 use foldhash::HashMapExt;
 
@@ -5,7 +7,9 @@ use mago_allocator::LocalArena;
 use mago_database::ReadDatabase;
 use mago_database::file::File;
 use mago_database::file::FileId;
+use mago_database::file::FileLanguage;
 use mago_formatter::Formatter;
+use mago_formatter::error::RangeFormatError;
 use mago_formatter::settings::FormatSettings;
 use mago_php_version::PHPVersion;
 use mago_syntax::error::ParseError;
@@ -20,6 +24,10 @@ pub enum FileFormatStatus {
     Unchanged,
     Changed(Vec<u8>),
     FailedToParse(ParseError),
+    /// A Twig template could not be parsed; holds the rendered parse error.
+    FailedToParseTemplate(String),
+    /// A range of the file could not be formatted without touching code outside of it.
+    FailedToIsolateRange,
 }
 
 #[derive(Debug)]
@@ -68,16 +76,46 @@ impl FormatService {
         let formatter =
             Formatter::new(arena, self.php_version, self.settings).with_parser_settings(self.parser_settings);
 
-        match formatter.format_file(file) {
-            Ok(formatted_content) => {
-                if file.contents == formatted_content {
-                    Ok(FileFormatStatus::Unchanged)
-                } else {
-                    Ok(FileFormatStatus::Changed(formatted_content.to_vec()))
-                }
-            }
-            Err(parse_error) => Ok(FileFormatStatus::FailedToParse(parse_error)),
+        Ok(format_with(&formatter, file))
+    }
+
+    /// Formats only the statements of a PHP file that overlap `range`, a range of byte offsets.
+    ///
+    /// See [`Formatter::format_range`] for how the formatted region is chosen.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`OrchestratorError::General`] if `file` is a Twig template, as range formatting
+    /// is only supported for PHP files.
+    pub fn format_file_range(self, file: &File, range: Range<u32>) -> Result<FileFormatStatus, OrchestratorError> {
+        self.format_file_ranges(file, &[range])
+    }
+
+    /// Formats only the statements of a PHP file that overlap any of `ranges`, given as byte offsets.
+    ///
+    /// See [`Formatter::format_ranges`] for how the formatted regions are chosen.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`OrchestratorError::General`] if `file` is a Twig template, as range formatting
+    /// is only supported for PHP files.
+    pub fn format_file_ranges(self, file: &File, ranges: &[Range<u32>]) -> Result<FileFormatStatus, OrchestratorError> {
+        if file.language().is_twig() {
+            return Err(OrchestratorError::General(
+                "Range formatting is not supported for Twig templates.".to_string(),
+            ));
         }
+
+        let arena = LocalArena::new();
+        let formatter =
+            Formatter::new(&arena, self.php_version, self.settings).with_parser_settings(self.parser_settings);
+
+        Ok(match formatter.format_ranges(file, ranges) {
+            Ok(formatted_content) if file.contents == formatted_content => FileFormatStatus::Unchanged,
+            Ok(formatted_content) => FileFormatStatus::Changed(formatted_content.to_vec()),
+            Err(RangeFormatError::ParseError(parse_error)) => FileFormatStatus::FailedToParse(parse_error),
+            Err(RangeFormatError::RangeNotIsolated) => FileFormatStatus::FailedToIsolateRange,
+        })
     }
 
     /// Runs the formatter pipeline over every file in the database.
@@ -103,18 +141,8 @@ impl FormatService {
         pipeline.run(|context, arena, file| {
             let formatter = Formatter::new(arena, context.php_version, context.settings)
                 .with_parser_settings(context.parser_settings);
-            let status = match formatter.format_file(&file) {
-                Ok(formatted_content) => {
-                    if file.contents == formatted_content {
-                        FileFormatStatus::Unchanged
-                    } else {
-                        FileFormatStatus::Changed(formatted_content.to_vec())
-                    }
-                }
-                Err(parse_error) => FileFormatStatus::FailedToParse(parse_error),
-            };
-
-            Ok((file.id, status))
+
+            Ok((file.id, format_with(&formatter, &file)))
         })
     }
 
@@ -156,18 +184,8 @@ impl FormatService {
         pipeline.run_on_files(file_ids, |context, arena, file| {
             let formatter = Formatter::new(arena, context.php_version, context.settings)
                 .with_parser_settings(context.parser_settings);
-            let status = match formatter.format_file(&file) {
-                Ok(formatted_content) => {
-                    if file.contents == formatted_content {
-                        FileFormatStatus::Unchanged
-                    } else {
-                        FileFormatStatus::Changed(formatted_content.to_vec())
-                    }
-                }
-                Err(parse_error) => FileFormatStatus::FailedToParse(parse_error),
-            };
-
-            Ok((file.id, status))
+
+            Ok((file.id, format_with(&formatter, &file)))
         })
     }
 }
@@ -191,12 +209,19 @@ impl FormatResult {
 
     #[must_use]
     pub fn is_successful(&self) -> bool {
-        self.changed_files.values().all(|status| !matches!(status, FileFormatStatus::FailedToParse(_)))
+        !self.is_failed()
     }
 
     #[must_use]
     pub fn is_failed(&self) -> bool {
-        self.changed_files.values().any(|status| matches!(status, FileFormatStatus::FailedToParse(_)))
+        self.changed_files.values().any(|status| {
+            matches!(
+                status,
+                FileFormatStatus::FailedToParse(_)
+                    | FileFormatStatus::FailedToParseTemplate(_)
+                    | FileFormatStatus::FailedToIsolateRange
+            )
+        })
     }
 
     #[must_use]
@@ -210,6 +235,16 @@ impl FormatResult {
         })
     }
 
+    pub fn template_parse_errors(&self) -> impl Iterator<Item = (&FileId, &str)> {
+        self.changed_files.iter().filter_map(|(file_id, status)| {
+            if let FileFormatStatus::FailedToParseTemplate(error) = status {
+                Some((file_id, error.as_str()))
+            } else {
+                None
+            }
+        })
+    }
+
     pub fn changed_files(&self) -> impl Iterator<Item = (&FileId, &Vec<u8>)> {
         self.changed_files.iter().filter_map(|(file_id, status)| {
             if let FileFormatStatus::Changed(content) = status { Some((file_id, content)) } else { None }
@@ -222,6 +257,22 @@ impl FormatResult {
     }
 }
 
+/// Formats `file` with the formatter matching its language.
+fn format_with(formatter: &Formatter<'_, LocalArena>, file: &File) -> FileFormatStatus {
+    let formatted_content = match file.language() {
+        FileLanguage::Php => formatter.format_file(file).map_err(FileFormatStatus::FailedToParse),
+        FileLanguage::Twig => formatter
+            .format_template_file(file)
+            .map_err(|parse_error| FileFormatStatus::FailedToParseTemplate(parse_error.to_string())),
+    };
+
+    match formatted_content {
+        Ok(formatted_content) if file.contents == formatted_content => FileFormatStatus::Unchanged,
+        Ok(formatted_content) => FileFormatStatus::Changed(formatted_content.to_vec()),
+        Err(status) => status,
+    }
+}
+
 /// Shared, read-only context provided to each parallel formatting task.
 #[derive(Clone, Copy)]
 struct FormatContext {
@@ -1,8 +1,15 @@
 use std::sync::Arc;
 
+use foldhash::HashMap;
+
+use mago_allocator::LocalArena;
 use mago_codex::metadata::CodebaseMetadata;
+use mago_collector::DeferredPragmas;
 use mago_database::ReadDatabase;
+use mago_database::file::File;
 use mago_guard::ArchitecturalGuard;
+use mago_guard::graph::DependencyGraph;
+use mago_guard::report::FortressReport;
 use mago_guard::settings::Settings;
 use mago_names::resolver::NameResolver;
 use mago_reporting::Issue;
@@ -69,6 +76,44 @@ impl GuardService {
         Self { database, codebase, settings, parser_settings, use_progress_bars }
     }
 
+    /// Guards a single file synchronously without using parallel processing.
+    ///
+    /// This performs the same checks as [`run`](Self::run) for one file, which makes it
+    /// suitable for editor integrations that re-check a buffer on every change.
+    ///
+    /// The file is checked against the given `codebase` rather than the one the service was
+    /// created with, so callers that keep an up-to-date codebase can share it without cloning.
+    ///
+    /// # Arguments
+    ///
+    /// * `file` - The file to guard.
+    /// * `codebase` - The codebase metadata to resolve the file's symbols against.
+    ///
+    /// # Returns
+    ///
+    /// An `IssueCollection` containing all issues found in the file.
+    #[must_use]
+    pub fn guard_file(&self, file: &File, codebase: &CodebaseMetadata) -> IssueCollection {
+        let arena = LocalArena::new();
+        let mut issues = IssueCollection::new();
+        if !file.language().is_php() {
+            return issues;
+        }
+
+        let program = parse_file_with_settings(&arena, file, self.parser_settings);
+        if program.has_errors() {
+            issues.extend(program.errors.iter().map(Issue::from));
+        }
+
+        let resolved_names = NameResolver::new(&arena).resolve(program);
+        let guard = ArchitecturalGuard::new(self.settings.clone());
+        let report = guard.check(codebase, program, &resolved_names);
+
+        issues.extend(report.report_into_issues(&arena, file, program));
+
+        issues
+    }
+
     /// Runs the guard pipeline on the codebase.
     ///
     /// # Returns
@@ -89,13 +134,16 @@ impl GuardService {
         let pipeline = StatelessParallelPipeline::new(
             GUARD_PROGRESS_PREFIX,
             self.database,
-            (Arc::new(self.codebase), self.settings, self.parser_settings),
-            Box::new(GuardResultReducer),
+            (Arc::new(self.codebase), self.settings.clone(), self.parser_settings),
+            Box::new(GuardResultReducer { settings: self.settings }),
             self.use_progress_bars,
         );
 
         let issues = pipeline.run(|(codebase, guard_settings, parser_settings), arena, source_file| {
             let mut issues = IssueCollection::new();
+            if !source_file.language().is_php() {
+                return Ok(GuardFileResult { issues, dependency_graph: None, deferred_pragmas: None });
+            }
 
             let program = parse_file_with_settings(arena, &source_file, parser_settings);
             if program.has_errors() {
@@ -104,14 +152,25 @@ impl GuardService {
 
             let resolved_names = NameResolver::new(arena).resolve(program);
             let guard = ArchitecturalGuard::new(guard_settings);
-            let report = guard.check(&codebase, program, &resolved_names);
+            let mut report = guard.check(&codebase, program, &resolved_names);
+            let dependency_graph = report.dependency_graph.take();
+
+            // Cycles and coupling metrics are only known once every file has been guarded, so
+            // the pragmas of the file are kept open until the reducer has reported them.
+            if dependency_graph.is_none() {
+                issues.extend(report.report_into_issues(arena, &source_file, program));
 
-            issues.extend(
-                // Report as issues
-                report.report_into_issues(arena, &source_file, program),
-            );
+                return Ok(GuardFileResult { issues, dependency_graph, deferred_pragmas: None });
+            }
 
-            Ok(issues)
+            let (report_issues, deferred_pragmas) = report.report_into_deferred_issues(arena, &source_file, program);
+            issues.extend(report_issues);
+
+            Ok(GuardFileResult {
+                issues,
+                dependency_graph,
+                deferred_pragmas: deferred_pragmas.map(|pragmas| (source_file, pragmas)),
+            })
         })?;
 
         Ok(GuardResult {
@@ -120,23 +179,118 @@ impl GuardService {
             missing_structural_configuration: skipped_structural,
         })
     }
+
+    /// Builds the namespace dependency graph of the codebase.
+    ///
+    /// # Arguments
+    ///
+    /// * `depth` - The number of namespace segments to keep, or `None` to keep them all.
+    ///
+    /// # Returns
+    ///
+    /// A `Result` containing the [`DependencyGraph`] of all host files, or an [`OrchestratorError`].
+    ///
+    /// # Errors
+    ///
+    /// Returns [`OrchestratorError`] when the pipeline fails.
+    pub fn graph(self, depth: Option<usize>) -> Result<DependencyGraph, OrchestratorError> {
+        const GRAPH_PROGRESS_PREFIX: &str = "🕸️  Mapping";
+
+        let pipeline = StatelessParallelPipeline::new(
+            GRAPH_PROGRESS_PREFIX,
+            self.database,
+            (Arc::new(self.codebase), self.settings, self.parser_settings),
+            Box::new(DependencyGraphReducer { depth }),
+            self.use_progress_bars,
+        );
+
+        pipeline.run(move |(codebase, guard_settings, parser_settings), arena, source_file| {
+            if !source_file.language().is_php() {
+                return Ok(DependencyGraph::new(depth));
+            }
+
+            let program = parse_file_with_settings(arena, &source_file, parser_settings);
+            let resolved_names = NameResolver::new(arena).resolve(program);
+            let guard = ArchitecturalGuard::new(guard_settings);
+
+            Ok(guard.graph(&codebase, program, &resolved_names, depth))
+        })
+    }
+}
+
+/// The result of guarding a single file.
+#[derive(Debug)]
+struct GuardFileResult {
+    issues: IssueCollection,
+    dependency_graph: Option<DependencyGraph>,
+    /// The pragmas of the file, kept open for the issues found across the whole codebase.
+    deferred_pragmas: Option<(Arc<File>, DeferredPragmas)>,
 }
 
 /// The "reduce" step for the guard pipeline.
 ///
 /// This struct aggregates the `IssueCollection` from each parallel task into a single,
-/// final `IssueCollection` for the entire project.
+/// final `IssueCollection` for the entire project, and merges the dependency graph of each
+/// task to report the dependency cycles and coupling metrics spanning several files.
 #[derive(Debug, Clone)]
-struct GuardResultReducer;
+struct GuardResultReducer {
+    settings: Settings,
+}
 
-impl StatelessReducer<IssueCollection, IssueCollection> for GuardResultReducer {
-    fn reduce(&self, results: Vec<IssueCollection>) -> Result<IssueCollection, OrchestratorError> {
+impl StatelessReducer<GuardFileResult, IssueCollection> for GuardResultReducer {
+    fn reduce(&self, results: Vec<GuardFileResult>) -> Result<IssueCollection, OrchestratorError> {
         let mut aggregated_issues = IssueCollection::new();
+        let mut aggregated_graph: Option<DependencyGraph> = None;
+        let mut deferred_pragmas = HashMap::default();
 
-        for result in results {
-            aggregated_issues.extend(result);
+        for GuardFileResult { issues, dependency_graph, deferred_pragmas: pragmas } in results {
+            aggregated_issues.extend(issues);
+
+            if let Some(graph) = dependency_graph {
+                match aggregated_graph.as_mut() {
+                    Some(aggregated_graph) => aggregated_graph.extend(graph),
+                    None => aggregated_graph = Some(graph),
+                }
+            }
+
+            if let Some((file, pragmas)) = pragmas {
+                deferred_pragmas.insert(file.id, (file, pragmas));
+            }
+        }
+
+        if let Some(graph) = aggregated_graph {
+            let guard = ArchitecturalGuard::new(self.settings.clone());
+            let report = FortressReport {
+                dependency_cycles: guard.check_cycles(&graph).dependency_cycles,
+                structural_flaws: guard.check_metrics(&graph).structural_flaws,
+                ..FortressReport::default()
+            };
+
+            aggregated_issues.extend(report.reconcile_into_issues(&mut deferred_pragmas));
+        }
+
+        for (file, pragmas) in deferred_pragmas.into_values() {
+            aggregated_issues.extend(pragmas.finish(&file));
         }
 
         Ok(aggregated_issues)
     }
 }
+
+/// The "reduce" step for the dependency graph pipeline, merging the graph of each file.
+#[derive(Debug, Clone)]
+struct DependencyGraphReducer {
+    depth: Option<usize>,
+}
+
+impl StatelessReducer<DependencyGraph, DependencyGraph> for DependencyGraphReducer {
+    fn reduce(&self, results: Vec<DependencyGraph>) -> Result<DependencyGraph, OrchestratorError> {
+        let mut graph = DependencyGraph::new(self.depth);
+
+        for result in results {
+            graph.extend(result);
+        }
+
+        Ok(graph)
+    }
+}
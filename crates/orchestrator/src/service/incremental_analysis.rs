@@ -1,3 +1,4 @@
+use std::borrow::Cow;
 use std::sync::Arc;
 #[cfg(not(target_arch = "wasm32"))]
 use std::sync::atomic::AtomicU64;
@@ -17,12 +18,20 @@ use mago_analyzer::Analyzer;
 use mago_analyzer::analysis_result::AnalysisResult;
 use mago_analyzer::analysis_result::LateSymbolReferenceIssueReconciler;
 use mago_analyzer::artifacts::AnalysisArtifacts;
+use mago_analyzer::container::ServiceContainer;
+use mago_analyzer::dead_code::find_unreachable_symbols;
 use mago_analyzer::external::AFTER_FILE_ANALYSIS_BATCH_SIZE;
 use mago_analyzer::external::CodebaseScanFile;
 use mago_analyzer::external::CodebaseScanPlan;
 use mago_analyzer::external::FileAnalysisSnapshot;
+use mago_analyzer::form::FormIndex;
 use mago_analyzer::plugin::PluginRegistry;
+use mago_analyzer::plugin::libraries::LaravelPlugin;
+use mago_analyzer::plugin::libraries::SymfonyPlugin;
+use mago_analyzer::schema::DatabaseSchema;
 use mago_analyzer::settings::Settings;
+use mago_analyzer::taint::TaintGraph;
+use mago_analyzer::template::TemplateIndex;
 use mago_codex::diff::CodebaseDiff;
 use mago_codex::metadata::CodebaseEntryKeys;
 use mago_codex::metadata::CodebaseMetadata;
@@ -45,6 +54,10 @@ use mago_syntax::settings::ParserSettings;
 use mago_word::WordSet;
 
 use crate::error::OrchestratorError;
+use crate::service::analysis::read_container_dump;
+use crate::service::analysis_cache::AnalysisCache;
+use crate::service::analysis_cache::CachedAnalysis;
+use crate::service::analysis_cache::CachedFile;
 use crate::service::issue_reconciliation::DeferredIssueReconciler;
 
 /// Per-file cached state for incremental analysis.
@@ -57,6 +70,7 @@ struct FileState {
     codebase_issues: IssueCollection,
     deferred_pragmas: Option<DeferredPragmas>,
     late_symbol_references: SymbolReferences,
+    taint_graph: TaintGraph,
 }
 
 struct SelectiveAnalysisOutput {
@@ -67,6 +81,7 @@ struct SelectiveAnalysisOutput {
     late_symbol_references_by_file: HashMap<FileId, SymbolReferences>,
     per_file_issues: HashMap<FileId, IssueCollection>,
     per_file_pragmas: HashMap<FileId, DeferredPragmas>,
+    per_file_taint_graphs: HashMap<FileId, TaintGraph>,
     snapshots: Vec<Arc<FileAnalysisSnapshot>>,
     codebase_issues: IssueCollection,
 }
@@ -104,6 +119,14 @@ pub struct IncrementalAnalysisService {
     analysis_snapshots: HashMap<FileId, Arc<FileAnalysisSnapshot>>,
     codebase_scan_plan: Option<Arc<CodebaseScanPlan>>,
     codebase_scan_files: HashMap<FileId, CodebaseScanFile>,
+    templates: TemplateIndex,
+    templates_hash: u64,
+    schema: DatabaseSchema,
+    schema_hash: u64,
+    container: ServiceContainer,
+    container_hash: u64,
+    forms: FormIndex,
+    form_files: HashSet<FileId>,
 }
 
 impl std::fmt::Debug for IncrementalAnalysisService {
@@ -127,6 +150,14 @@ impl std::fmt::Debug for IncrementalAnalysisService {
             .field("analysis_snapshots", &self.analysis_snapshots.len())
             .field("codebase_scan_plan", &self.codebase_scan_plan.is_some())
             .field("codebase_scan_files", &self.codebase_scan_files.len())
+            .field("templates", &self.templates.len())
+            .field("templates_hash", &self.templates_hash)
+            .field("schema", &self.schema.len())
+            .field("schema_hash", &self.schema_hash)
+            .field("container", &self.container.len())
+            .field("container_hash", &self.container_hash)
+            .field("forms", &self.forms.len())
+            .field("form_files", &self.form_files.len())
             .finish()
     }
 }
@@ -177,6 +208,14 @@ impl IncrementalAnalysisService {
             analysis_snapshots: HashMap::default(),
             codebase_scan_plan: None,
             codebase_scan_files: HashMap::default(),
+            templates: TemplateIndex::new(),
+            templates_hash: 0,
+            schema: DatabaseSchema::new(),
+            schema_hash: 0,
+            container: ServiceContainer::new(),
+            container_hash: 0,
+            forms: FormIndex::new(),
+            form_files: HashSet::default(),
         }
     }
 
@@ -212,6 +251,12 @@ impl IncrementalAnalysisService {
         &self.database
     }
 
+    /// Returns the parser settings used for every file this service analyzes.
+    #[must_use]
+    pub fn parser_settings(&self) -> ParserSettings {
+        self.parser_settings
+    }
+
     /// Reconstructs the full issue list from cached per-file issues and codebase-level issues.
     ///
     /// Returns `None` if no analysis has been run yet.
@@ -316,6 +361,41 @@ impl IncrementalAnalysisService {
         issues
     }
 
+    /// Rebuilds the template index from the Twig files in the database.
+    fn reload_templates(&mut self) {
+        if !self.plugin_registry.has_plugin(&SymfonyPlugin) {
+            return;
+        }
+
+        self.templates = TemplateIndex::from_files(self.database.files(), &self.settings.template_paths);
+        self.templates_hash = hash_templates(&self.database);
+    }
+
+    /// Rebuilds the database schema from the migrations in the database.
+    fn reload_schema(&mut self) {
+        if !self.plugin_registry.has_plugin(&LaravelPlugin) {
+            return;
+        }
+
+        self.schema = DatabaseSchema::from_files(self.database.files(), &self.settings.migration_paths);
+        self.schema_hash = hash_migrations(&self.database, &self.settings.migration_paths);
+    }
+
+    /// Re-reads the service container dump and rebuilds the form index from the database.
+    fn reload_symfony(&mut self) {
+        if !self.plugin_registry.has_plugin(&SymfonyPlugin) {
+            return;
+        }
+
+        let xml = read_container_dump(&self.settings).unwrap_or_default();
+        self.container = ServiceContainer::from_xml(&xml);
+        self.container_hash = xxhash_rust::xxh3::xxh3_64(xml.as_bytes());
+
+        let form_files: Vec<_> = self.database.files().filter(|file| FormIndex::may_declare_forms(file)).collect();
+        self.form_files = form_files.iter().map(|file| file.id).collect();
+        self.forms = FormIndex::from_files(form_files);
+    }
+
     fn refresh_late_reference_issues(&mut self) {
         if self.late_symbol_references.is_empty() {
             for state in self.file_states.values_mut() {
@@ -340,12 +420,16 @@ impl IncrementalAnalysisService {
     /// Returns [`OrchestratorError`] when source scanning, codebase population, or per-file
     /// analysis fails.
     pub fn analyze(&mut self) -> Result<AnalysisResult, OrchestratorError> {
+        self.reload_templates();
+        self.reload_schema();
+        self.reload_symfony();
         self.codebase_scan_plan = self
             .plugin_registry
             .external_codebase_scan_plan()
             .map_err(mago_analyzer::error::AnalysisError::from)?
             .map(Arc::new);
-        let source_files: Vec<_> = self.database.files().filter(|f| f.file_type != FileType::Builtin).collect();
+        let source_files: Vec<_> =
+            self.database.files().filter(|f| f.file_type != FileType::Builtin && f.language().is_php()).collect();
 
         if source_files.is_empty() {
             tracing::info!("No source files found for analysis.");
@@ -428,6 +512,7 @@ impl IncrementalAnalysisService {
                     codebase_issues: IssueCollection::default(),
                     deferred_pragmas: None,
                     late_symbol_references: SymbolReferences::new(),
+                    taint_graph: TaintGraph::new(),
                 },
             );
         }
@@ -440,6 +525,7 @@ impl IncrementalAnalysisService {
             late_symbol_references_by_file,
             per_file_issues,
             per_file_pragmas,
+            mut per_file_taint_graphs,
             snapshots,
             codebase_issues: all_codebase_issues,
         } =
@@ -462,6 +548,7 @@ impl IncrementalAnalysisService {
                 state.deferred_pragmas = per_file_pragmas.get(&file_id).cloned();
                 state.late_symbol_references =
                     late_symbol_references_by_file.get(&file_id).cloned().unwrap_or_default();
+                state.taint_graph = per_file_taint_graphs.remove(&file_id).unwrap_or_default();
             }
         }
 
@@ -479,6 +566,139 @@ impl IncrementalAnalysisService {
         Ok(analysis_result)
     }
 
+    /// Runs a full analysis, resuming from the state persisted in `cache` by a previous run.
+    ///
+    /// When the cache holds state for the same Mago version, PHP version, and configuration,
+    /// that state is restored and [`analyze_incremental()`](Self::analyze_incremental) runs
+    /// against it: unchanged files keep their scanned metadata and issues, while changed files
+    /// and the symbols depending on them are invalidated and re-analyzed. Otherwise, this falls
+    /// back to [`analyze()`](Self::analyze). Either way, the resulting state is written back.
+    ///
+    /// The cache is bypassed entirely when an external analyzer is attached, since its
+    /// lifecycle state cannot be persisted, when taint analysis is enabled, since taint
+    /// flows are solved over the graphs of every file, and when unreachable symbols are
+    /// reported, since reachability is decided over the references of every file.
+    ///
+    /// Failing to write the cache is logged and does not fail the analysis.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`OrchestratorError`] when source scanning, codebase population, or per-file
+    /// analysis fails.
+    pub fn analyze_with_cache(&mut self, cache: &AnalysisCache) -> Result<AnalysisResult, OrchestratorError> {
+        if self.plugin_registry.has_external_analyzer() {
+            tracing::debug!("Skipping the analysis cache because an external analyzer is attached.");
+
+            return self.analyze();
+        }
+
+        if self.settings.check_taints {
+            tracing::debug!("Skipping the analysis cache because taint analysis is enabled.");
+
+            return self.analyze();
+        }
+
+        if self.settings.find_unreachable_symbols {
+            tracing::debug!("Skipping the analysis cache because unreachable symbols are reported.");
+
+            return self.analyze();
+        }
+
+        let result = match cache.load() {
+            Some(state) => {
+                tracing::debug!("Resuming analysis from cache '{}'.", cache.path().display());
+
+                self.restore_cached_state(state);
+                self.analyze_incremental(None)?
+            }
+            None => self.analyze()?,
+        };
+
+        if let Err(error) = cache.store(&self.cached_state()) {
+            tracing::warn!("{error}");
+        }
+
+        Ok(result)
+    }
+
+    /// Borrows the service state in its persisted form.
+    fn cached_state(&self) -> CachedAnalysis<'_> {
+        CachedAnalysis {
+            codebase: Cow::Borrowed(&self.codebase),
+            symbol_references: Cow::Borrowed(&self.symbol_references),
+            native_symbol_references: Cow::Borrowed(&self.native_symbol_references),
+            external_symbol_references: Cow::Borrowed(&self.external_symbol_references),
+            codebase_issues: Cow::Borrowed(&self.codebase_issues),
+            lifecycle_issues: Cow::Borrowed(&self.lifecycle_issues),
+            templates_hash: self.templates_hash,
+            schema_hash: self.schema_hash,
+            container_hash: self.container_hash,
+            form_files: self.form_files.iter().copied().collect(),
+            files: self
+                .file_states
+                .iter()
+                .map(|(file_id, state)| CachedFile {
+                    file_id: *file_id,
+                    content_hash: state.content_hash,
+                    entry_keys: Cow::Borrowed(&state.entry_keys),
+                    analysis_issues: Cow::Borrowed(&state.unreconciled_analysis_issues),
+                    codebase_issues: Cow::Borrowed(&state.codebase_issues),
+                })
+                .collect(),
+        }
+    }
+
+    /// Replaces the service state with state restored from the cache and marks it initialized.
+    ///
+    /// Deferred pragmas, late symbol references, and analysis snapshots only exist when an
+    /// external analyzer is attached, so they start out empty.
+    fn restore_cached_state(&mut self, state: CachedAnalysis<'static>) {
+        self.codebase = state.codebase.into_owned();
+        self.symbol_references = state.symbol_references.into_owned();
+        self.native_symbol_references = state.native_symbol_references.into_owned();
+        self.external_symbol_references = state.external_symbol_references.into_owned();
+        self.late_symbol_references = SymbolReferences::new();
+        self.codebase_issues = state.codebase_issues.into_owned();
+        self.lifecycle_issues = state.lifecycle_issues.into_owned();
+        self.templates_hash = state.templates_hash;
+        self.schema_hash = state.schema_hash;
+        self.container_hash = state.container_hash;
+        self.form_files = state.form_files.into_iter().collect();
+        if self.plugin_registry.has_plugin(&LaravelPlugin) {
+            self.schema = DatabaseSchema::from_files(self.database.files(), &self.settings.migration_paths);
+        }
+
+        if self.plugin_registry.has_plugin(&SymfonyPlugin) {
+            self.container = ServiceContainer::from_xml(&read_container_dump(&self.settings).unwrap_or_default());
+            self.forms = FormIndex::from_files(self.database.files());
+        }
+        self.file_states = state
+            .files
+            .into_iter()
+            .map(|file| {
+                let analysis_issues = file.analysis_issues.into_owned();
+
+                (
+                    file.file_id,
+                    FileState {
+                        content_hash: file.content_hash,
+                        entry_keys: file.entry_keys.into_owned(),
+                        unreconciled_analysis_issues: analysis_issues.clone(),
+                        analysis_issues,
+                        codebase_issues: file.codebase_issues.into_owned(),
+                        deferred_pragmas: None,
+                        late_symbol_references: SymbolReferences::new(),
+                        taint_graph: TaintGraph::new(),
+                    },
+                )
+            })
+            .collect();
+        self.analysis_snapshots.clear();
+        self.codebase_scan_plan = None;
+        self.codebase_scan_files.clear();
+        self.initialized = true;
+    }
+
     /// Runs incremental analysis optimized for subsequent runs after file changes.
     ///
     /// # Arguments
@@ -502,13 +722,43 @@ impl IncrementalAnalysisService {
             ));
         }
 
-        let source_files: Vec<_> = self.database.files().filter(|f| f.file_type != FileType::Builtin).collect();
+        let source_files: Vec<_> =
+            self.database.files().filter(|f| f.file_type != FileType::Builtin && f.language().is_php()).collect();
 
         if source_files.is_empty() {
             tracing::info!("No source files found for analysis.");
             return Ok(AnalysisResult::new(SymbolReferences::new()));
         }
 
+        let symfony = self.plugin_registry.has_plugin(&SymfonyPlugin);
+
+        // Templates are read by the analysis of every file that renders them, so any change to
+        // them re-analyzes the whole project.
+        if symfony && hash_templates(&self.database) != self.templates_hash {
+            tracing::debug!("Templates changed since last run, running a full analysis.");
+
+            return self.analyze();
+        }
+
+        // Likewise, the schema built from the migrations types the attributes of every model.
+        if self.plugin_registry.has_plugin(&LaravelPlugin)
+            && hash_migrations(&self.database, &self.settings.migration_paths) != self.schema_hash
+        {
+            tracing::debug!("Migrations changed since last run, running a full analysis.");
+
+            return self.analyze();
+        }
+
+        // The service container dump is read outside the database, so it is re-read on every run.
+        if symfony
+            && xxhash_rust::xxh3::xxh3_64(read_container_dump(&self.settings).unwrap_or_default().as_bytes())
+                != self.container_hash
+        {
+            tracing::debug!("Service container changed since last run, running a full analysis.");
+
+            return self.analyze();
+        }
+
         let current_file_names: WordSet = source_files.iter().map(|file| mago_word::word(file.name.as_ref())).collect();
 
         let mut changed_files = Vec::new();
@@ -598,6 +848,19 @@ impl IncrementalAnalysisService {
             }
         }
 
+        // The data class of a form type types the data of every form created with it, so adding,
+        // changing or removing a form type re-analyzes the whole project.
+        if symfony
+            && (changed_files
+                .iter()
+                .any(|file| self.form_files.contains(&file.id) || FormIndex::may_declare_forms(file))
+                || self.form_files.iter().any(|file_id| !current_file_ids.contains(file_id)))
+        {
+            tracing::debug!("Form types changed since last run, running a full analysis.");
+
+            return self.analyze();
+        }
+
         if changed_files.is_empty() && deleted_count == 0 {
             tracing::debug!("No files changed, reconstructing cached issues");
             let mut result = AnalysisResult::new(SymbolReferences::new());
@@ -812,6 +1075,7 @@ impl IncrementalAnalysisService {
                 mut late_symbol_references_by_file,
                 mut per_file_issues,
                 per_file_pragmas,
+                mut per_file_taint_graphs,
                 snapshots,
                 codebase_issues: new_codebase_issues,
             } = self.run_analyzer_selective(&mut merged_codebase, symbol_references, &self.settings, &files_to_skip)?;
@@ -852,6 +1116,7 @@ impl IncrementalAnalysisService {
                     state.analysis_issues = issues;
                     state.deferred_pragmas = per_file_pragmas.get(&file_id).cloned();
                     state.late_symbol_references = late_symbol_references_by_file.remove(&file_id).unwrap_or_default();
+                    state.taint_graph = per_file_taint_graphs.remove(&file_id).unwrap_or_default();
                 }
             }
 
@@ -865,6 +1130,7 @@ impl IncrementalAnalysisService {
                     self.file_states.get(&file_id).map(|s| s.codebase_issues.clone()).unwrap_or_default();
                 let deferred_pragmas = per_file_pragmas.get(&file_id).cloned();
                 let late_symbol_references = late_symbol_references_by_file.remove(&file_id).unwrap_or_default();
+                let taint_graph = per_file_taint_graphs.remove(&file_id).unwrap_or_default();
                 self.file_states.insert(
                     file_id,
                     FileState {
@@ -875,6 +1141,7 @@ impl IncrementalAnalysisService {
                         codebase_issues,
                         deferred_pragmas,
                         late_symbol_references,
+                        taint_graph,
                     },
                 );
             }
@@ -1070,6 +1337,7 @@ impl IncrementalAnalysisService {
             mut late_symbol_references_by_file,
             mut per_file_issues,
             per_file_pragmas,
+            mut per_file_taint_graphs,
             snapshots,
             codebase_issues: new_codebase_issues,
         } = self.run_analyzer_selective(&mut merged_codebase, symbol_references, &self.settings, &files_to_skip)?;
@@ -1111,6 +1379,7 @@ impl IncrementalAnalysisService {
                 state.analysis_issues = issues;
                 state.deferred_pragmas = per_file_pragmas.get(&file_id).cloned();
                 state.late_symbol_references = late_symbol_references_by_file.remove(&file_id).unwrap_or_default();
+                state.taint_graph = per_file_taint_graphs.remove(&file_id).unwrap_or_default();
             }
         }
 
@@ -1123,6 +1392,7 @@ impl IncrementalAnalysisService {
             let codebase_issues = self.file_states.get(&file_id).map(|s| s.codebase_issues.clone()).unwrap_or_default();
             let deferred_pragmas = per_file_pragmas.get(&file_id).cloned();
             let late_symbol_references = late_symbol_references_by_file.remove(&file_id).unwrap_or_default();
+            let taint_graph = per_file_taint_graphs.remove(&file_id).unwrap_or_default();
             self.file_states.insert(
                 file_id,
                 FileState {
@@ -1133,6 +1403,7 @@ impl IncrementalAnalysisService {
                     codebase_issues,
                     deferred_pragmas,
                     late_symbol_references,
+                    taint_graph,
                 },
             );
         }
@@ -1173,7 +1444,11 @@ impl IncrementalAnalysisService {
 
         let mut analysis_result = AnalysisResult::new(SymbolReferences::new());
         let mut analyzer =
-            Analyzer::new(&arena, &file, &resolved_names, &self.codebase, &self.plugin_registry, self.settings.clone());
+            Analyzer::new(&arena, &file, &resolved_names, &self.codebase, &self.plugin_registry, self.settings.clone())
+                .with_templates(&self.templates)
+                .with_schema(&self.schema)
+                .with_container(&self.container)
+                .with_forms(&self.forms);
         if let Some(session) = external_session.as_ref() {
             analyzer = analyzer.with_external_analysis_session(session);
         }
@@ -1227,7 +1502,11 @@ impl IncrementalAnalysisService {
 
         let mut analysis_result = AnalysisResult::new(SymbolReferences::new());
         let mut analyzer =
-            Analyzer::new(&arena, &file, &resolved_names, &self.codebase, &self.plugin_registry, self.settings.clone());
+            Analyzer::new(&arena, &file, &resolved_names, &self.codebase, &self.plugin_registry, self.settings.clone())
+                .with_templates(&self.templates)
+                .with_schema(&self.schema)
+                .with_container(&self.container)
+                .with_forms(&self.forms);
         if let Some(session) = external_session.as_ref() {
             analyzer = analyzer.with_external_analysis_session(session);
         }
@@ -1265,6 +1544,10 @@ impl IncrementalAnalysisService {
         const ANALYSIS_DURATION_THRESHOLD: Duration = Duration::from_secs(5);
 
         let plugin_registry = &self.plugin_registry;
+        let templates = &self.templates;
+        let schema = &self.schema;
+        let container = &self.container;
+        let forms = &self.forms;
         #[cfg(not(target_arch = "wasm32"))]
         let trace_enabled = tracing::enabled!(tracing::Level::TRACE);
         #[cfg(not(target_arch = "wasm32"))]
@@ -1292,7 +1575,9 @@ impl IncrementalAnalysisService {
         let host_files: Vec<_> = self
             .database
             .files()
-            .filter(|file| file.file_type == FileType::Host && !effective_skip_files.contains(&file.id))
+            .filter(|file| {
+                file.file_type == FileType::Host && file.language().is_php() && !effective_skip_files.contains(&file.id)
+            })
             .map(|file| self.database.get(&file.id))
             .collect::<Result<Vec<_>, _>>()?;
         #[cfg(not(target_arch = "wasm32"))]
@@ -1328,8 +1613,12 @@ impl IncrementalAnalysisService {
 
                 let semantics_checker = SemanticsChecker::new(settings.version);
                 let mut analyzer =
-                    Analyzer::new(arena, &source_file, &resolved_names, codebase, plugin_registry, settings.clone());
-                if after_file || after_analysis {
+                    Analyzer::new(arena, &source_file, &resolved_names, codebase, plugin_registry, settings.clone())
+                        .with_templates(templates)
+                        .with_schema(schema)
+                        .with_container(container)
+                        .with_forms(forms);
+                if after_file || after_analysis || settings.check_taints || settings.find_unreachable_symbols {
                     analyzer = analyzer.with_deferred_pragmas();
                 }
                 if let Some(session) = external_session.as_deref() {
@@ -1396,10 +1685,14 @@ impl IncrementalAnalysisService {
         aggregated_result.issues = before.issues;
         let mut per_file_issues: HashMap<FileId, IssueCollection> = HashMap::default();
         let mut per_file_pragmas: HashMap<FileId, DeferredPragmas> = HashMap::default();
+        let mut per_file_taint_graphs: HashMap<FileId, TaintGraph> = HashMap::default();
         let mut snapshots = Vec::new();
 
         for (file_id, mut result, snapshot) in results {
             let mut deferred_pragmas = result.take_deferred_pragmas();
+            if !result.taint_graph.is_empty() {
+                per_file_taint_graphs.insert(file_id, std::mem::take(&mut result.taint_graph));
+            }
             aggregated_result.symbol_references.extend(result.symbol_references);
             per_file_issues.insert(file_id, result.issues);
             if let Some(pragmas) = deferred_pragmas.pop() {
@@ -1481,17 +1774,43 @@ impl IncrementalAnalysisService {
             }
         }
 
+        // Taint flows span files, so they are solved over the graphs of every file on each run
+        // and reported as project-level issues rather than cached with any single file.
+        if settings.check_taints {
+            let mut taint_graph = TaintGraph::new();
+            for file_id in &effective_skip_files {
+                if let Some(state) = self.file_states.get(file_id) {
+                    taint_graph.extend(state.taint_graph.clone());
+                }
+            }
+            for graph in per_file_taint_graphs.values() {
+                taint_graph.extend(graph.clone());
+            }
+
+            aggregated_result.issues.extend(pragma_reconciler.reconcile(taint_graph.find_flows())?);
+        }
+
         let native_symbol_references = aggregated_result.symbol_references.clone();
         aggregated_result.symbol_references.extend(external_symbol_references.clone());
         aggregated_result.symbol_references.extend(late_symbol_references.clone());
 
+        if settings.find_unreachable_symbols {
+            let unreachable_symbols = find_unreachable_symbols(
+                codebase,
+                &aggregated_result.symbol_references,
+                &settings.entry_points,
+                self.database.files(),
+            );
+            aggregated_result.issues.extend(pragma_reconciler.reconcile(unreachable_symbols)?);
+        }
+
         let codebase_issues = codebase.take_issues(true);
         if after_analysis {
             let analyzed = snapshots.iter().map(|snapshot| snapshot.file_id()).collect::<HashSet<_>>();
             let current = self
                 .database
                 .files()
-                .filter(|file| file.file_type == FileType::Host)
+                .filter(|file| file.file_type == FileType::Host && file.language().is_php())
                 .map(|file| file.id)
                 .collect::<HashSet<_>>();
             let mut project_files = self
@@ -1561,12 +1880,44 @@ impl IncrementalAnalysisService {
             late_symbol_references_by_file,
             per_file_issues,
             per_file_pragmas,
+            per_file_taint_graphs,
             snapshots,
             codebase_issues,
         })
     }
 }
 
+/// Hashes the names and contents of the Twig files in the database, in name order.
+fn hash_templates(database: &ReadDatabase) -> u64 {
+    let mut templates: Vec<_> = database.files().filter(|file| file.language().is_twig()).collect();
+    templates.sort_unstable_by(|a, b| a.name.cmp(&b.name));
+
+    let mut hasher = xxhash_rust::xxh3::Xxh3::new();
+    for template in &templates {
+        hasher.update(&template.name);
+        hasher.update(&[0]);
+        hasher.update(&template.contents);
+        hasher.update(&[0]);
+    }
+
+    hasher.digest()
+}
+
+/// Hashes the names and contents of the migrations in the database, in the order they are applied.
+fn hash_migrations(database: &ReadDatabase, paths: &[String]) -> u64 {
+    let migrations = DatabaseSchema::migration_files(database.files(), paths);
+
+    let mut hasher = xxhash_rust::xxh3::Xxh3::new();
+    for migration in &migrations {
+        hasher.update(&migration.name);
+        hasher.update(&[0]);
+        hasher.update(&migration.contents);
+        hasher.update(&[0]);
+    }
+
+    hasher.digest()
+}
+
 #[cfg(test)]
 #[allow(clippy::unwrap_used, clippy::expect_used, clippy::similar_names)]
 mod tests {
@@ -4777,4 +5128,141 @@ mod tests {
             result.issues.iter().map(|i| i.code.clone()).collect::<Vec<_>>()
         );
     }
+
+    fn make_cache(directory: &Path, configuration_hash: u64) -> AnalysisCache {
+        AnalysisCache::new(directory, Settings::default().version, configuration_hash)
+    }
+
+    #[test]
+    fn test_cache_resumes_in_a_new_service() {
+        let directory = tempfile::tempdir().unwrap();
+        let cache = make_cache(directory.path(), 1);
+        let db = make_database(vec![
+            ("src/a.php", "<?php\nfunction compute(): int { return 1; }\n"),
+            ("src/b.php", "<?php\nfunction caller(): string { return compute(); }\n"),
+        ]);
+
+        let mut first = make_service(&db);
+        let initial = first.analyze_with_cache(&cache).expect("Initial analysis failed.");
+        assert!(cache.path().exists(), "the cache must be written after the first run");
+        assert!(!initial.issues.is_empty());
+
+        let mut second = make_service(&db);
+        let resumed = second.analyze_with_cache(&cache).expect("Cached analysis failed.");
+        assert!(second.is_initialized());
+        assert_eq!(second.tracked_file_count(), 2);
+
+        let (only_resumed, only_initial) = diff_issues(&resumed.issues, &initial.issues);
+        assert!(
+            only_resumed.is_empty() && only_initial.is_empty(),
+            "Resumed and initial analysis produced different issues.\n  Only in resumed: {only_resumed:?}\n  Only in initial: {only_initial:?}"
+        );
+    }
+
+    #[test]
+    fn test_cache_invalidates_dependents_of_changed_files() {
+        let directory = tempfile::tempdir().unwrap();
+        let cache = make_cache(directory.path(), 1);
+        let mut db = make_database(vec![
+            ("src/a.php", "<?php\nfunction compute(): int { return 1; }\n"),
+            ("src/b.php", "<?php\nfunction caller(): int { return compute(); }\n"),
+            ("src/c.php", "<?php\nfunction unrelated(): string { return 'c'; }\n"),
+        ]);
+
+        make_service(&db).analyze_with_cache(&cache).expect("Initial analysis failed.");
+
+        db.update(
+            FileId::new(b"src/a.php"),
+            Cow::Owned(b"<?php\nfunction compute(): string { return 'hello'; }\n".to_vec()),
+        );
+        db.delete(FileId::new(b"src/c.php"));
+
+        let mut resumed = make_service(&db);
+        let result = resumed.analyze_with_cache(&cache).expect("Cached analysis failed.");
+        assert_eq!(resumed.tracked_file_count(), 2);
+
+        let full = make_service(&db).analyze().expect("Full analysis failed.");
+        let (only_resumed, only_full) = diff_issues(&result.issues, &full.issues);
+        assert!(
+            only_resumed.is_empty() && only_full.is_empty(),
+            "Resumed and full analysis produced different issues.\n  Only in resumed: {only_resumed:?}\n  Only in full: {only_full:?}"
+        );
+        assert!(!full.issues.is_empty(), "the changed return type must be reported in the dependent file");
+    }
+
+    #[test]
+    fn test_cache_is_keyed_by_configuration_hash() {
+        let directory = tempfile::tempdir().unwrap();
+        let db = make_database(vec![("src/a.php", "<?php\nfunction compute(): int { return 1; }\n")]);
+
+        make_service(&db).analyze_with_cache(&make_cache(directory.path(), 1)).expect("Initial analysis failed.");
+
+        let other = make_cache(directory.path(), 2);
+        assert!(other.load().is_none());
+
+        std::fs::copy(make_cache(directory.path(), 1).path(), other.path()).unwrap();
+        assert!(other.load().is_none(), "a cache written for another configuration must be ignored");
+    }
+
+    #[test]
+    fn test_cache_ignores_corrupted_files() {
+        let directory = tempfile::tempdir().unwrap();
+        let cache = make_cache(directory.path(), 1);
+        std::fs::write(cache.path(), b"not a cache").unwrap();
+
+        let db = make_database(vec![("src/a.php", "<?php\nfunction compute(): int { return 1; }\n")]);
+        let mut service = make_service(&db);
+        service.analyze_with_cache(&cache).expect("Analysis must fall back to a full run.");
+
+        assert!(service.is_initialized());
+        assert!(cache.load().is_some(), "the corrupted cache must be replaced");
+    }
+
+    #[test]
+    fn test_incremental_keeps_taint_flows_through_unchanged_files() {
+        let source = concat!(
+            "<?php\n",
+            "function read_name(): string {\n",
+            "    /** @var string $name */\n",
+            "    $name = $_GET['name'];\n",
+            "    return $name;\n",
+            "}\n",
+        );
+        let sink = "<?php\nfunction greet(): void { echo read_name(); }\n";
+        let unrelated = "<?php\nfunction compute(): int { return 1; }\n";
+
+        let mut db =
+            make_database(vec![("src/source.php", source), ("src/sink.php", sink), ("src/unrelated.php", unrelated)]);
+
+        let settings = Settings { check_taints: true, ..Settings::default() };
+        let make_taint_service = |db: &Database<'_>| {
+            IncrementalAnalysisService::new(
+                db.read_only(),
+                CodebaseMetadata::new(),
+                SymbolReferences::new(),
+                settings.clone(),
+                ParserSettings::default(),
+                Arc::clone(&PLUGIN_REGISTRY),
+            )
+        };
+
+        let mut service = make_taint_service(&db);
+        let initial = service.analyze().expect("Full analysis failed.");
+        let is_tainted_html = |issue: &Issue| issue.code.as_deref() == Some("tainted-html");
+        assert_eq!(initial.issues.iter().filter(|issue| is_tainted_html(issue)).count(), 1);
+
+        db.update(
+            FileId::new(b"src/unrelated.php"),
+            Cow::Owned(b"<?php\nfunction compute(): int { return 2; }\n".to_vec()),
+        );
+        service.update_database(db.read_only());
+        let incremental = service.analyze_incremental(None).expect("Incremental analysis failed.");
+
+        let full = make_taint_service(&db).analyze().expect("Full analysis failed.");
+        let (only_incr, only_full) = diff_issues(&incremental.issues, &full.issues);
+        assert!(
+            only_incr.is_empty() && only_full.is_empty(),
+            "Incremental analysis lost taint flows through unchanged files.\n  Only in incremental: {only_incr:?}\n  Only in full: {only_full:?}"
+        );
+    }
 }
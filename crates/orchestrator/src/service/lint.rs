@@ -19,6 +19,7 @@ use mago_reporting::IssueCollection;
 use mago_semantics::SemanticsChecker;
 use mago_syntax::parser::parse_file_with_settings;
 use mago_syntax::settings::ParserSettings;
+use mago_twig_syntax::parser::parse_file as parse_template_file;
 
 use crate::OrchestratorError;
 use crate::service::pipeline::StatelessParallelPipeline;
@@ -134,6 +135,12 @@ impl LintService {
         include_disabled: bool,
     ) -> IssueCollection {
         let arena = LocalArena::new();
+        if file.language().is_twig() {
+            let registry = Arc::new(self.create_registry(only, include_disabled));
+
+            return lint_template(&arena, file, mode, registry, self.settings.php_version);
+        }
+
         let program = parse_file_with_settings(&arena, file, self.parser_settings);
         let resolved_names = NameResolver::new(&arena).resolve(program);
 
@@ -195,6 +202,10 @@ impl LintService {
         let telemetry_for_closure = Arc::clone(&telemetry);
 
         let result = pipeline.run(move |context, arena, file| {
+            if file.language().is_twig() {
+                return Ok(lint_template(arena, &file, context.mode, context.registry, context.php_version));
+            }
+
             #[cfg(not(target_arch = "wasm32"))]
             let per_file_start = trace_enabled.then(Instant::now);
             #[cfg(not(target_arch = "wasm32"))]
@@ -261,6 +272,31 @@ impl LintService {
     }
 }
 
+/// Lints a Twig template, reporting its parse errors and, in full mode, the issues found by
+/// the template rules.
+///
+/// Semantic checks and PHP rules do not apply to templates.
+fn lint_template(
+    arena: &LocalArena,
+    file: &File,
+    mode: LintMode,
+    registry: Arc<RuleRegistry>,
+    php_version: PHPVersion,
+) -> IssueCollection {
+    let template = parse_template_file(arena, file);
+
+    let mut issues = IssueCollection::new();
+    issues.extend(template.errors.iter().map(Issue::from));
+
+    if mode == LintMode::Full {
+        let linter = Linter::from_registry(arena, registry, php_version);
+
+        issues.extend(linter.lint_template(file, template));
+    }
+
+    issues
+}
+
 /// Shared, read-only context provided to each parallel linting task.
 #[derive(Clone)]
 struct LintContext {
@@ -7,9 +7,12 @@
 //! # Available Services
 //!
 //! - [`analysis::AnalysisService`]: Static analysis with type checking and control flow
+//! - [`incremental_analysis::IncrementalAnalysisService`]: Incremental analysis, optionally
+//!   persisted between runs through an [`analysis_cache::AnalysisCache`]
 //! - [`format::FormatService`]: Code formatting to ensure consistent style
 //! - [`guard::GuardService`]: Architectural rule enforcement
 //! - [`lint::LintService`]: Linting for code quality and best practices
+//! - [`query::QueryService`]: Definition, reference, and hover queries over analysis results
 //!
 //! # Architecture
 //!
@@ -31,7 +34,9 @@ mod pipeline;
 mod telemetry;
 
 pub mod analysis;
+pub mod analysis_cache;
 pub mod format;
 pub mod guard;
 pub mod incremental_analysis;
 pub mod lint;
+pub mod query;
@@ -204,7 +204,7 @@ where
         let source_files: Vec<_> = measure!(
             trace_enabled,
             source_discover_duration,
-            self.database.files().filter(|f| f.file_type != FileType::Builtin).collect()
+            self.database.files().filter(|f| f.file_type != FileType::Builtin && f.language().is_php()).collect()
         );
 
         if source_files.is_empty() {
@@ -313,7 +313,10 @@ where
         let host_files = measure!(
             trace_enabled,
             host_discover_duration,
-            self.database.files().filter(|f| f.file_type == FileType::Host).collect::<Vec<_>>()
+            self.database
+                .files()
+                .filter(|f| f.file_type == FileType::Host && f.language().is_php())
+                .collect::<Vec<_>>()
         );
 
         let before_map_result = before_map(&mut merged_codex, &mut symbol_references, captures)?;
@@ -0,0 +1,799 @@
+//! Symbol queries over an analyzed codebase.
+//!
+//! The [`QueryService`] answers editor-style questions about a position in a source file:
+//! where the symbol under the cursor is declared, where it is used, and what it looks like.
+//! It reads the [`CodebaseMetadata`] and [`SymbolReferences`](mago_codex::reference::SymbolReferences)
+//! kept by an [`IncrementalAnalysisService`], so queries reuse the results of a completed
+//! analysis instead of recomputing them.
+//!
+//! Top-level symbols (class-likes, functions, constants) are located through name
+//! resolution. Class-like members (methods, properties, constants, enum cases) are
+//! located by walking the syntax tree and resolving each receiver with the types the
+//! analyzer inferred for the file.
+
+use std::collections::BTreeSet;
+use std::fmt::Display;
+use std::fmt::Formatter;
+
+use mago_allocator::LocalArena;
+use mago_analyzer::artifacts::AnalysisArtifacts;
+use mago_codex::metadata::CodebaseMetadata;
+use mago_codex::metadata::function_like::FunctionLikeMetadata;
+use mago_codex::reference::ReferenceOrigin;
+use mago_codex::ttype::TType;
+use mago_codex::ttype::union::TUnion;
+use mago_database::DatabaseReader;
+use mago_database::file::File;
+use mago_database::file::FileId;
+use mago_names::ResolvedNames;
+use mago_names::resolver::NameResolver;
+use mago_span::HasSpan;
+use mago_span::Position;
+use mago_span::Span;
+use mago_syntax::comments::docblock::get_docblock_before_position;
+use mago_syntax::cst::AnonymousClass;
+use mago_syntax::cst::Class;
+use mago_syntax::cst::ClassConstantAccess;
+use mago_syntax::cst::ClassLikeConstantItem;
+use mago_syntax::cst::ClassLikeConstantSelector;
+use mago_syntax::cst::ClassLikeMemberSelector;
+use mago_syntax::cst::Enum;
+use mago_syntax::cst::EnumCaseBackedItem;
+use mago_syntax::cst::EnumCaseUnitItem;
+use mago_syntax::cst::Expression;
+use mago_syntax::cst::Interface;
+use mago_syntax::cst::Method;
+use mago_syntax::cst::MethodCall;
+use mago_syntax::cst::NullSafeMethodCall;
+use mago_syntax::cst::NullSafePropertyAccess;
+use mago_syntax::cst::Program;
+use mago_syntax::cst::PropertyAbstractItem;
+use mago_syntax::cst::PropertyAccess;
+use mago_syntax::cst::PropertyConcreteItem;
+use mago_syntax::cst::StaticMethodCall;
+use mago_syntax::cst::StaticPropertyAccess;
+use mago_syntax::cst::Trait;
+use mago_syntax::cst::Variable;
+use mago_syntax::parser::parse_file_with_settings;
+use mago_syntax::walker::MutWalker;
+use mago_word::Word;
+use mago_word::word;
+
+use crate::service::incremental_analysis::IncrementalAnalysisService;
+
+/// A symbol that can be the subject of a query.
+///
+/// Names use their declared casing. Member symbols always refer to the class-like that
+/// declares the member, even when the member was reached through a subclass.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum QuerySymbol {
+    /// A class, interface, trait, or enum.
+    ClassLike(Word),
+    /// A global function.
+    Function(Word),
+    /// A global constant.
+    Constant(Word),
+    /// A method, as `(declaring class, method)`.
+    Method(Word, Word),
+    /// A property, as `(declaring class, property)`. The property name includes the `$`.
+    Property(Word, Word),
+    /// A class constant or enum case, as `(declaring class, constant)`.
+    ClassConstant(Word, Word),
+}
+
+impl QuerySymbol {
+    /// Returns a short, stable name for the kind of symbol.
+    #[must_use]
+    pub const fn kind(&self) -> &'static str {
+        match self {
+            QuerySymbol::ClassLike(_) => "class-like",
+            QuerySymbol::Function(_) => "function",
+            QuerySymbol::Constant(_) => "constant",
+            QuerySymbol::Method(..) => "method",
+            QuerySymbol::Property(..) => "property",
+            QuerySymbol::ClassConstant(..) => "class-constant",
+        }
+    }
+
+    /// Returns the member name for class-like members, or `None` for top-level symbols.
+    #[must_use]
+    pub const fn member(&self) -> Option<Word> {
+        match self {
+            QuerySymbol::ClassLike(_) | QuerySymbol::Function(_) | QuerySymbol::Constant(_) => None,
+            QuerySymbol::Method(_, member)
+            | QuerySymbol::Property(_, member)
+            | QuerySymbol::ClassConstant(_, member) => Some(*member),
+        }
+    }
+
+    /// Returns the top-level name: the symbol itself, or the declaring class-like of a member.
+    #[must_use]
+    pub const fn name(&self) -> Word {
+        match self {
+            QuerySymbol::ClassLike(name)
+            | QuerySymbol::Function(name)
+            | QuerySymbol::Constant(name)
+            | QuerySymbol::Method(name, _)
+            | QuerySymbol::Property(name, _)
+            | QuerySymbol::ClassConstant(name, _) => *name,
+        }
+    }
+}
+
+impl Display for QuerySymbol {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self.member() {
+            Some(member) => write!(f, "{}::{}", self.name(), member),
+            None => write!(f, "{}", self.name()),
+        }
+    }
+}
+
+/// The declaration of the symbol found at a queried position.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct SymbolDefinition {
+    /// The symbol under the cursor.
+    pub symbol: QuerySymbol,
+    /// The span of the symbol occurrence under the cursor.
+    pub span: Span,
+    /// The span of the symbol's declaration, usually its name.
+    pub definition: Span,
+}
+
+/// Every use site of the symbol found at a queried position.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SymbolUsages {
+    /// The symbol under the cursor.
+    pub symbol: QuerySymbol,
+    /// The span of the symbol's declaration.
+    pub definition: Span,
+    /// The use sites, excluding the declaration itself, ordered by file and offset.
+    pub references: Vec<Span>,
+}
+
+/// Hover information for a queried position.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SymbolHover {
+    /// The span the hover applies to.
+    pub span: Span,
+    /// The symbol under the cursor, if the position is on a symbol.
+    pub symbol: Option<QuerySymbol>,
+    /// A one-line rendering of the symbol's declaration.
+    pub signature: Option<String>,
+    /// The inferred type of the innermost expression at the position, or the declared
+    /// type of the symbol when the position is not inside an expression.
+    pub inferred_type: Option<String>,
+    /// The raw docblock attached to the symbol's declaration.
+    pub docblock: Option<String>,
+}
+
+/// Service answering definition, reference, and hover queries.
+///
+/// The service borrows an [`IncrementalAnalysisService`] that has completed at least one
+/// analysis run; before that, the codebase only contains prelude symbols.
+#[derive(Debug)]
+pub struct QueryService<'service> {
+    analysis: &'service IncrementalAnalysisService,
+}
+
+/// A symbol resolved at a position, with the occurrence and declaration spans.
+#[derive(Debug, Clone, Copy)]
+struct LocatedSymbol {
+    symbol: QuerySymbol,
+    site: Span,
+    declaration: Span,
+}
+
+impl<'service> QueryService<'service> {
+    /// Creates a new query service over the results of the given analysis service.
+    #[must_use]
+    pub fn new(analysis: &'service IncrementalAnalysisService) -> Self {
+        Self { analysis }
+    }
+
+    /// Resolves the symbol at `offset` in the given file and returns its declaration.
+    ///
+    /// Returns `None` if the file is unknown, or the offset is not on a symbol known to
+    /// the codebase.
+    #[must_use]
+    pub fn definition(&self, file_id: FileId, offset: u32) -> Option<SymbolDefinition> {
+        let file = self.analysis.database().get(&file_id).ok()?;
+        let artifacts = self.artifacts(file_id);
+
+        let arena = LocalArena::new();
+        let program = parse_file_with_settings(&arena, &file, self.analysis.parser_settings());
+        let resolved_names = NameResolver::new(&arena).resolve(program);
+
+        let located = self.locate(&file, program, &resolved_names, artifacts.as_ref(), offset)?;
+
+        Some(SymbolDefinition { symbol: located.symbol, span: located.site, definition: located.declaration })
+    }
+
+    /// Resolves the symbol at `offset` in the given file and lists every place it is used.
+    ///
+    /// Candidate files are taken from the symbol reference graph, so only files that the
+    /// analyzer saw referencing the symbol (plus the declaring and queried files) are
+    /// scanned. Only host files are searched.
+    #[must_use]
+    pub fn references(&self, file_id: FileId, offset: u32) -> Option<SymbolUsages> {
+        let definition = self.definition(file_id, offset)?;
+        let located =
+            LocatedSymbol { symbol: definition.symbol, site: definition.span, declaration: definition.definition };
+
+        let mut candidates = self.candidate_files(&located.symbol);
+        candidates.insert(file_id);
+        candidates.insert(located.declaration.file_id);
+
+        let mut references = Vec::new();
+        for candidate in candidates {
+            let Ok(file) = self.analysis.database().get(&candidate) else {
+                continue;
+            };
+
+            if !file.file_type.is_host() {
+                continue;
+            }
+
+            references.extend(self.references_in_file(&file, &located));
+        }
+
+        references.sort_by_key(|span| (span.file_id, span.start.offset));
+        references.dedup();
+
+        Some(SymbolUsages { symbol: located.symbol, definition: located.declaration, references })
+    }
+
+    /// Describes the symbol or expression at `offset` in the given file.
+    ///
+    /// Returns `None` if the offset is neither on a known symbol nor inside an expression
+    /// whose type the analyzer inferred.
+    #[must_use]
+    pub fn hover(&self, file_id: FileId, offset: u32) -> Option<SymbolHover> {
+        let file = self.analysis.database().get(&file_id).ok()?;
+        let artifacts = self.artifacts(file_id);
+
+        let arena = LocalArena::new();
+        let program = parse_file_with_settings(&arena, &file, self.analysis.parser_settings());
+        let resolved_names = NameResolver::new(&arena).resolve(program);
+
+        let located = self.locate(&file, program, &resolved_names, artifacts.as_ref(), offset);
+        let expression = artifacts.as_ref().and_then(|artifacts| innermost_expression_type(artifacts, offset));
+
+        let span = match (&located, &expression) {
+            (Some(located), _) => located.site,
+            (None, Some(((start, end), _))) => Span::new(file.id, Position::new(*start), Position::new(*end)),
+            (None, None) => return None,
+        };
+
+        let expression_type = expression.map(|(_, union)| union.get_id().to_string());
+        let Some(located) = located else {
+            return Some(SymbolHover {
+                span,
+                symbol: None,
+                signature: None,
+                inferred_type: expression_type,
+                docblock: None,
+            });
+        };
+
+        let codebase = self.analysis.codebase();
+        let (signature, declared_type, declaration_start) = match located.symbol {
+            QuerySymbol::ClassLike(name) => {
+                let metadata = codebase.get_class_like(name.as_bytes())?;
+
+                (format!("{} {}", metadata.kind.as_str(), metadata.original_name), None, Some(metadata.span))
+            }
+            QuerySymbol::Function(name) => {
+                let metadata = codebase.get_function(name.as_bytes())?;
+
+                (render_function_like(metadata, &name.to_string()), return_type(metadata), Some(metadata.span))
+            }
+            QuerySymbol::Method(class, method) => {
+                let metadata = codebase.get_method(class.as_bytes(), method.as_bytes())?;
+                let qualified = format!("{class}::{}", metadata.original_name);
+
+                (render_function_like(metadata, &qualified), return_type(metadata), Some(metadata.span))
+            }
+            QuerySymbol::Property(class, property) => {
+                let metadata = codebase.get_property(class.as_bytes(), property.as_bytes())?;
+                let declared_type = codebase.get_property_type(class.as_bytes(), property.as_bytes());
+
+                (
+                    format!("property {class}::{property}"),
+                    declared_type.map(|union| union.get_id().to_string()),
+                    metadata.span,
+                )
+            }
+            QuerySymbol::ClassConstant(class, constant) => {
+                let declared_type = codebase.get_class_constant_type(class.as_bytes(), constant.as_bytes());
+
+                (
+                    format!("const {class}::{constant}"),
+                    declared_type.map(|union| union.get_id().to_string()),
+                    Some(located.declaration),
+                )
+            }
+            QuerySymbol::Constant(name) => {
+                let metadata = codebase.get_constant(name.as_bytes())?;
+                let declared_type = metadata
+                    .type_metadata
+                    .as_ref()
+                    .map(|type_metadata| &type_metadata.type_union)
+                    .or(metadata.inferred_type.as_ref());
+
+                (format!("const {name}"), declared_type.map(|union| union.get_id().to_string()), Some(metadata.span))
+            }
+        };
+
+        Some(SymbolHover {
+            span,
+            symbol: Some(located.symbol),
+            signature: Some(signature),
+            inferred_type: expression_type.or(declared_type),
+            docblock: declaration_start.and_then(|declaration| self.docblock(declaration)),
+        })
+    }
+
+    /// Runs the analyzer over a single file to obtain per-expression types.
+    fn artifacts(&self, file_id: FileId) -> Option<AnalysisArtifacts> {
+        self.analysis.analyze_file_with_artifacts(file_id).map(|(_, artifacts)| artifacts)
+    }
+
+    /// Finds the symbol covering `offset`, preferring resolved names over member accesses.
+    fn locate<'arena>(
+        &self,
+        file: &File,
+        program: &'arena Program<'arena>,
+        resolved_names: &ResolvedNames<'arena>,
+        artifacts: Option<&AnalysisArtifacts>,
+        offset: u32,
+    ) -> Option<LocatedSymbol> {
+        let codebase = self.analysis.codebase();
+
+        if let Some((start, end, name, _)) = resolved_names.at_offset(offset) {
+            let (symbol, declaration) = resolve_name(codebase, name)?;
+
+            return Some(LocatedSymbol {
+                symbol,
+                site: Span::new(file.id, Position::new(start), Position::new(end)),
+                declaration,
+            });
+        }
+
+        collect_member_sites(codebase, program, resolved_names, artifacts)
+            .into_iter()
+            .filter(|site| site.span.start.offset <= offset && offset < site.span.end.offset)
+            .find_map(|site| {
+                let (symbol, declaration) = resolve_member(codebase, &site)?;
+
+                Some(LocatedSymbol { symbol, site: site.span, declaration })
+            })
+    }
+
+    /// Collects the files that the reference graph records as using `symbol`.
+    fn candidate_files(&self, symbol: &QuerySymbol) -> BTreeSet<FileId> {
+        let codebase = self.analysis.codebase();
+        let database = self.analysis.database();
+        let name = symbol.name();
+        let member = symbol.member().map(|member| word(trim_variable_sigil(member.as_bytes())));
+
+        let mut files = BTreeSet::new();
+        self.analysis.symbol_references().for_each_reference(|origin, (referenced, referenced_member), _| {
+            let matches = match member {
+                Some(member) => {
+                    trim_variable_sigil(referenced_member.as_bytes()).eq_ignore_ascii_case(member.as_bytes())
+                }
+                None => referenced.as_bytes().eq_ignore_ascii_case(name.as_bytes()),
+            };
+
+            if !matches {
+                return;
+            }
+
+            let file_id = match origin {
+                ReferenceOrigin::File(file_name) => database.get_id(file_name.as_bytes()),
+                ReferenceOrigin::Symbol((referencing, _)) => declaring_file(codebase, referencing),
+            };
+
+            files.extend(file_id);
+        });
+
+        files
+    }
+
+    /// Lists the use sites of `located` within a single file.
+    fn references_in_file(&self, file: &File, located: &LocatedSymbol) -> Vec<Span> {
+        let codebase = self.analysis.codebase();
+        let is_declaration = |span: &Span| {
+            span.file_id == located.declaration.file_id && span.start.offset == located.declaration.start.offset
+        };
+
+        let arena = LocalArena::new();
+        let program = parse_file_with_settings(&arena, file, self.analysis.parser_settings());
+        let resolved_names = NameResolver::new(&arena).resolve(program);
+
+        let Some(member) = located.symbol.member() else {
+            return resolved_names
+                .references_to(located.symbol.name().as_bytes(), None)
+                .into_iter()
+                .map(|(start, end)| Span::new(file.id, Position::new(start), Position::new(end)))
+                .filter(|span| !is_declaration(span))
+                .collect();
+        };
+
+        let artifacts = self.artifacts(file.id);
+
+        collect_member_sites(codebase, program, &resolved_names, artifacts.as_ref())
+            .into_iter()
+            .filter(|site| {
+                trim_variable_sigil(site.name.as_bytes()).eq_ignore_ascii_case(trim_variable_sigil(member.as_bytes()))
+            })
+            .filter(|site| resolve_member(codebase, site).is_some_and(|(symbol, _)| symbol == located.symbol))
+            .map(|site| site.span)
+            .filter(|span| !is_declaration(span))
+            .collect()
+    }
+
+    /// Returns the docblock immediately preceding the declaration starting at `declaration`.
+    fn docblock(&self, declaration: Span) -> Option<String> {
+        let file = self.analysis.database().get(&declaration.file_id).ok()?;
+
+        let arena = LocalArena::new();
+        let program = parse_file_with_settings(&arena, &file, self.analysis.parser_settings());
+        let docblock = get_docblock_before_position(program.trivia.as_slice(), declaration.start.offset)?;
+
+        Some(String::from_utf8_lossy(docblock.value).into_owned())
+    }
+}
+
+/// The kind of class-like member a [`MemberSite`] refers to.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum MemberKind {
+    Method,
+    Property,
+    Constant,
+}
+
+/// A member declaration or access, with the class-likes it may belong to.
+#[derive(Debug, Clone)]
+struct MemberSite {
+    span: Span,
+    kind: MemberKind,
+    classes: Vec<Word>,
+    name: Word,
+}
+
+/// Walks a program and records every class-like member declaration and access.
+///
+/// Instance receivers are recorded by expression range and resolved to class-likes once
+/// the walk is done, since the analyzer's expression types cannot be shared with a walker.
+struct MemberSiteCollector<'ctx, 'arena> {
+    codebase: &'ctx CodebaseMetadata,
+    resolved_names: &'ctx ResolvedNames<'arena>,
+    class_stack: Vec<Option<Word>>,
+    sites: Vec<(MemberSite, Option<(u32, u32)>)>,
+}
+
+impl MemberSiteCollector<'_, '_> {
+    fn current_class(&self) -> Option<Word> {
+        self.class_stack.last().copied().flatten()
+    }
+
+    fn declare(&mut self, span: Span, kind: MemberKind, name: &[u8]) {
+        if let Some(class) = self.current_class() {
+            self.sites.push((MemberSite { span, kind, classes: vec![class], name: word(name) }, None));
+        }
+    }
+
+    fn access(&mut self, span: Span, kind: MemberKind, receiver: &Expression<'_>, is_static: bool, name: Word) {
+        let classes = match receiver {
+            Expression::Identifier(identifier) if is_static => {
+                self.resolved_names.resolve(identifier).map(word).into_iter().collect()
+            }
+            Expression::Self_(_) | Expression::Static(_) if is_static => self.current_class().into_iter().collect(),
+            Expression::Variable(Variable::Direct(variable)) if !is_static && variable.name == b"$this" => {
+                self.current_class().into_iter().collect()
+            }
+            Expression::Parent(_) if is_static => self
+                .current_class()
+                .and_then(|class| self.codebase.get_class_like(class.as_bytes()))
+                .and_then(|metadata| metadata.direct_parent_class)
+                .into_iter()
+                .collect(),
+            _ => {
+                let receiver = receiver.span();
+                let site = MemberSite { span, kind, classes: vec![], name };
+
+                self.sites.push((site, Some((receiver.start.offset, receiver.end.offset))));
+                return;
+            }
+        };
+
+        self.sites.push((MemberSite { span, kind, classes, name }, None));
+    }
+
+    fn instance_member(&mut self, object: &Expression<'_>, selector: &ClassLikeMemberSelector<'_>, kind: MemberKind) {
+        let ClassLikeMemberSelector::Identifier(identifier) = selector else {
+            return;
+        };
+
+        let name = match kind {
+            MemberKind::Property => word([b"$", identifier.value].concat()),
+            MemberKind::Method | MemberKind::Constant => word(identifier.value),
+        };
+
+        self.access(identifier.span, kind, object, false, name);
+    }
+}
+
+impl<'ast, 'arena> MutWalker<'ast, 'arena, ()> for MemberSiteCollector<'_, 'arena> {
+    fn walk_in_class(&mut self, class: &'ast Class<'arena>, _: &mut ()) {
+        self.class_stack.push(self.resolved_names.resolve(&class.name).map(word));
+    }
+
+    fn walk_out_class(&mut self, _: &'ast Class<'arena>, _: &mut ()) {
+        self.class_stack.pop();
+    }
+
+    fn walk_in_interface(&mut self, interface: &'ast Interface<'arena>, _: &mut ()) {
+        self.class_stack.push(self.resolved_names.resolve(&interface.name).map(word));
+    }
+
+    fn walk_out_interface(&mut self, _: &'ast Interface<'arena>, _: &mut ()) {
+        self.class_stack.pop();
+    }
+
+    fn walk_in_trait(&mut self, r#trait: &'ast Trait<'arena>, _: &mut ()) {
+        self.class_stack.push(self.resolved_names.resolve(&r#trait.name).map(word));
+    }
+
+    fn walk_out_trait(&mut self, _: &'ast Trait<'arena>, _: &mut ()) {
+        self.class_stack.pop();
+    }
+
+    fn walk_in_enum(&mut self, r#enum: &'ast Enum<'arena>, _: &mut ()) {
+        self.class_stack.push(self.resolved_names.resolve(&r#enum.name).map(word));
+    }
+
+    fn walk_out_enum(&mut self, _: &'ast Enum<'arena>, _: &mut ()) {
+        self.class_stack.pop();
+    }
+
+    fn walk_in_anonymous_class(&mut self, _: &'ast AnonymousClass<'arena>, _: &mut ()) {
+        self.class_stack.push(None);
+    }
+
+    fn walk_out_anonymous_class(&mut self, _: &'ast AnonymousClass<'arena>, _: &mut ()) {
+        self.class_stack.pop();
+    }
+
+    fn walk_in_method(&mut self, method: &'ast Method<'arena>, _: &mut ()) {
+        self.declare(method.name.span, MemberKind::Method, method.name.value);
+    }
+
+    fn walk_in_property_abstract_item(&mut self, item: &'ast PropertyAbstractItem<'arena>, _: &mut ()) {
+        self.declare(item.variable.span, MemberKind::Property, item.variable.name);
+    }
+
+    fn walk_in_property_concrete_item(&mut self, item: &'ast PropertyConcreteItem<'arena>, _: &mut ()) {
+        self.declare(item.variable.span, MemberKind::Property, item.variable.name);
+    }
+
+    fn walk_in_class_like_constant_item(&mut self, item: &'ast ClassLikeConstantItem<'arena>, _: &mut ()) {
+        self.declare(item.name.span, MemberKind::Constant, item.name.value);
+    }
+
+    fn walk_in_enum_case_unit_item(&mut self, item: &'ast EnumCaseUnitItem<'arena>, _: &mut ()) {
+        self.declare(item.name.span, MemberKind::Constant, item.name.value);
+    }
+
+    fn walk_in_enum_case_backed_item(&mut self, item: &'ast EnumCaseBackedItem<'arena>, _: &mut ()) {
+        self.declare(item.name.span, MemberKind::Constant, item.name.value);
+    }
+
+    fn walk_in_method_call(&mut self, method_call: &'ast MethodCall<'arena>, _: &mut ()) {
+        self.instance_member(method_call.object, &method_call.method, MemberKind::Method);
+    }
+
+    fn walk_in_null_safe_method_call(&mut self, method_call: &'ast NullSafeMethodCall<'arena>, _: &mut ()) {
+        self.instance_member(method_call.object, &method_call.method, MemberKind::Method);
+    }
+
+    fn walk_in_static_method_call(&mut self, method_call: &'ast StaticMethodCall<'arena>, _: &mut ()) {
+        if let ClassLikeMemberSelector::Identifier(identifier) = &method_call.method {
+            self.access(identifier.span, MemberKind::Method, method_call.class, true, word(identifier.value));
+        }
+    }
+
+    fn walk_in_property_access(&mut self, property_access: &'ast PropertyAccess<'arena>, _: &mut ()) {
+        self.instance_member(property_access.object, &property_access.property, MemberKind::Property);
+    }
+
+    fn walk_in_null_safe_property_access(&mut self, property_access: &'ast NullSafePropertyAccess<'arena>, _: &mut ()) {
+        self.instance_member(property_access.object, &property_access.property, MemberKind::Property);
+    }
+
+    fn walk_in_static_property_access(&mut self, property_access: &'ast StaticPropertyAccess<'arena>, _: &mut ()) {
+        if let Variable::Direct(variable) = &property_access.property {
+            self.access(variable.span, MemberKind::Property, property_access.class, true, word(variable.name));
+        }
+    }
+
+    fn walk_in_class_constant_access(&mut self, constant_access: &'ast ClassConstantAccess<'arena>, _: &mut ()) {
+        if let ClassLikeConstantSelector::Identifier(identifier) = &constant_access.constant {
+            self.access(identifier.span, MemberKind::Constant, constant_access.class, true, word(identifier.value));
+        }
+    }
+}
+
+/// Collects every member declaration and access in `program`.
+fn collect_member_sites<'arena>(
+    codebase: &CodebaseMetadata,
+    program: &'arena Program<'arena>,
+    resolved_names: &ResolvedNames<'arena>,
+    artifacts: Option<&AnalysisArtifacts>,
+) -> Vec<MemberSite> {
+    let mut collector = MemberSiteCollector { codebase, resolved_names, class_stack: vec![], sites: vec![] };
+    collector.walk_program(program, &mut ());
+
+    collector
+        .sites
+        .into_iter()
+        .filter_map(|(mut site, receiver)| {
+            if let Some(receiver) = receiver {
+                let union = artifacts?.expression_types.get(&receiver)?;
+
+                site.classes = union.types.iter().flat_map(|atomic| atomic.get_all_object_names()).collect();
+            }
+
+            if site.classes.is_empty() { None } else { Some(site) }
+        })
+        .collect()
+}
+
+/// Resolves a fully qualified name to a top-level symbol and its declaration span.
+///
+/// Functions and constants fall back to the global namespace, mirroring PHP's runtime
+/// fallback for unqualified names.
+fn resolve_name(codebase: &CodebaseMetadata, name: &[u8]) -> Option<(QuerySymbol, Span)> {
+    if let Some(metadata) = codebase.get_class_like(name) {
+        return Some((QuerySymbol::ClassLike(metadata.original_name), metadata.name_span.unwrap_or(metadata.span)));
+    }
+
+    let global_name = name.rsplit(|byte| *byte == b'\\').next().unwrap_or(name);
+    for candidate in [name, global_name] {
+        if let Some(metadata) = codebase.get_function(candidate) {
+            return Some((QuerySymbol::Function(metadata.original_name), metadata.name_span.unwrap_or(metadata.span)));
+        }
+
+        if let Some(metadata) = codebase.get_constant(candidate) {
+            return Some((QuerySymbol::Constant(metadata.name), metadata.span));
+        }
+    }
+
+    None
+}
+
+/// Resolves a member site to the declaring member and its declaration span.
+fn resolve_member(codebase: &CodebaseMetadata, site: &MemberSite) -> Option<(QuerySymbol, Span)> {
+    let name = site.name.as_bytes();
+
+    site.classes.iter().find_map(|class| match site.kind {
+        MemberKind::Method => {
+            let declaring_class = codebase.get_declaring_method_class(class.as_bytes(), name)?;
+            let class_metadata = codebase.get_class_like(declaring_class.as_bytes())?;
+            let metadata = codebase.get_method(declaring_class.as_bytes(), name)?;
+
+            Some((
+                QuerySymbol::Method(class_metadata.original_name, metadata.original_name),
+                metadata.name_span.unwrap_or(metadata.span),
+            ))
+        }
+        MemberKind::Property => {
+            let declaring_class = codebase.get_declaring_property_class(class.as_bytes(), name)?;
+            let class_metadata = codebase.get_class_like(declaring_class.as_bytes())?;
+            let metadata = codebase.get_property(declaring_class.as_bytes(), name)?;
+
+            Some((
+                QuerySymbol::Property(class_metadata.original_name, metadata.name.0),
+                metadata.name_span.or(metadata.span)?,
+            ))
+        }
+        MemberKind::Constant => {
+            let class_metadata = codebase.get_class_like(class.as_bytes())?;
+            let (constant_name, declaration) = match codebase.get_enum_case(class.as_bytes(), name) {
+                Some(case) => (case.name, case.name_span),
+                None => {
+                    let constant = codebase.get_class_constant(class.as_bytes(), name)?;
+
+                    (constant.name, constant.span)
+                }
+            };
+
+            // Inherited constants are copied into every class-like with their original span,
+            // so the declaring class-like is the ancestor whose body contains that span.
+            let declaring_class = std::iter::once(&class_metadata.name)
+                .chain(class_metadata.all_parent_classes.iter())
+                .chain(class_metadata.all_parent_interfaces.iter())
+                .chain(class_metadata.used_traits.iter())
+                .filter_map(|ancestor| codebase.get_class_like(ancestor.as_bytes()))
+                .find(|ancestor| {
+                    ancestor.span.file_id == declaration.file_id
+                        && ancestor.span.start.offset <= declaration.start.offset
+                        && declaration.end.offset <= ancestor.span.end.offset
+                })
+                .map_or(class_metadata.original_name, |ancestor| ancestor.original_name);
+
+            Some((QuerySymbol::ClassConstant(declaring_class, constant_name), declaration))
+        }
+    })
+}
+
+/// Returns the file declaring a referencing symbol recorded in the reference graph.
+fn declaring_file(codebase: &CodebaseMetadata, symbol: Word) -> Option<FileId> {
+    if let Some(metadata) = codebase.get_class_like(symbol.as_bytes()) {
+        return Some(metadata.span.file_id);
+    }
+
+    if let Some(metadata) = codebase.get_function(symbol.as_bytes()).or_else(|| codebase.get_closure(&symbol)) {
+        return Some(metadata.span.file_id);
+    }
+
+    codebase.get_constant(symbol.as_bytes()).map(|metadata| metadata.span.file_id)
+}
+
+/// Returns the type of the smallest expression containing `offset`.
+fn innermost_expression_type(artifacts: &AnalysisArtifacts, offset: u32) -> Option<((u32, u32), &TUnion)> {
+    artifacts
+        .expression_types
+        .iter()
+        .filter(|((start, end), _)| *start <= offset && offset < *end)
+        .min_by_key(|((start, end), _)| (end - start, *start))
+        .map(|(range, union)| (*range, union.as_ref()))
+}
+
+/// Renders a function-like declaration as a single line, e.g. `function foo(int $a): string`.
+fn render_function_like(metadata: &FunctionLikeMetadata, name: &str) -> String {
+    let parameters = metadata
+        .parameters
+        .iter()
+        .map(|parameter| {
+            let mut rendered = String::new();
+            if let Some(type_metadata) = &parameter.type_metadata {
+                rendered.push_str(&type_metadata.type_union.get_id().to_string());
+                rendered.push(' ');
+            }
+
+            if parameter.flags.is_by_reference() {
+                rendered.push('&');
+            }
+
+            if parameter.flags.is_variadic() {
+                rendered.push_str("...");
+            }
+
+            rendered.push_str(&parameter.get_name().0.to_string());
+            if parameter.flags.has_default() {
+                rendered.push_str(" = ...");
+            }
+
+            rendered
+        })
+        .collect::<Vec<_>>()
+        .join(", ");
+
+    match return_type(metadata) {
+        Some(return_type) => format!("function {name}({parameters}): {return_type}"),
+        None => format!("function {name}({parameters})"),
+    }
+}
+
+/// Returns the rendered return type of a function-like, if it has one.
+fn return_type(metadata: &FunctionLikeMetadata) -> Option<String> {
+    metadata.return_type_metadata.as_ref().map(|type_metadata| type_metadata.type_union.get_id().to_string())
+}
+
+/// Strips the leading `$` from a property name, if present.
+fn trim_variable_sigil(name: &[u8]) -> &[u8] {
+    name.strip_prefix(b"$").unwrap_or(name)
+}
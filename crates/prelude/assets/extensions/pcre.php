@@ -40,14 +40,14 @@ const PCRE_VERSION_MINOR = 42;
 const PCRE_JIT_SUPPORT = 1;
 
 /**
- * @param-out array<string> $matches
+ * @param-out ($flags is 0 ? array<string> : array<string|null|list{string|null, int}>) $matches
  *
  * @return int|false
  */
 function preg_match(string $pattern, string $subject, &$matches = [], int $flags = 0, int $offset = 0): int|false {}
 
 /**
- * @param-out list<array<string>> $matches
+ * @param-out ($flags is 0|1 ? array<list<string>> : array<list<string|null|list{string|null, int}>|array<string|null|list{string|null, int}>>) $matches
  */
 function preg_match_all(string $pattern, string $subject, &$matches = null, int $flags = 0, int $offset = 0): int|false {}
 
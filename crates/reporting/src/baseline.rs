@@ -12,11 +12,23 @@
 //! - **Loose**: Stores issue counts per (file, code, message) tuple. More resilient to code
 //!   changes as line number shifts don't affect the baseline. This is the default.
 //!
+//! - **Fingerprint**: Stores issue counts per (code, symbol, fingerprint) tuple, where the symbol
+//!   is the class member or function enclosing the issue, and the fingerprint hashes the innermost
+//!   statement or member around it. Survives reformatting, reordering, and file renames. The
+//!   locations are computed by the caller and passed in as [`IssueLocations`].
+//!
+//! Entries of every variant can carry an optional owner, ticket, and date, which are preserved when
+//! the baseline is regenerated. A [`ProjectBaseline`] holds the baselines of the linter, the
+//! analyzer, and the guard in a single file, one section per tool.
+//!
 //! File paths in baselines are normalized to use forward slashes for cross-platform compatibility,
 //! ensuring baselines created on Windows work on Unix systems and vice versa.
 
 use std::borrow::Cow;
+use std::cmp::Ordering;
 use std::collections::BTreeMap;
+use std::hash::Hash;
+use std::hash::Hasher;
 
 use foldhash::HashMap;
 use foldhash::HashSet;
@@ -24,6 +36,7 @@ use schemars::JsonSchema;
 
 use mago_database::DatabaseReader;
 use mago_database::ReadDatabase;
+use mago_span::Span;
 
 use crate::Annotation;
 use crate::Issue;
@@ -56,17 +69,53 @@ pub enum BaselineVariant {
     /// More resilient to code changes as line shifts don't affect the baseline.
     #[default]
     Loose,
+    /// Fingerprint baseline with structure-based matching.
+    ///
+    /// Issues are grouped by (code, symbol, fingerprint) and stored with a count.
+    /// Resilient to reformatting, reordering members, and renaming files.
+    Fingerprint,
+}
+
+/// The tool a baseline, or a section of a [`ProjectBaseline`], belongs to.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord, JsonSchema)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "lowercase"))]
+pub enum BaselineTool {
+    Linter,
+    Analyzer,
+    Guard,
+}
+
+/// Optional information a team attaches to a baseline entry to keep track of the debt it records.
+///
+/// None of the fields take part in matching issues against the baseline, and all of them are
+/// carried over to the matching entries when the baseline is regenerated.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Hash, PartialOrd, Ord, JsonSchema)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct BaselineEntryMetadata {
+    /// The team or person responsible for the entry.
+    #[cfg_attr(feature = "serde", serde(default, skip_serializing_if = "Option::is_none"))]
+    pub owner: Option<String>,
+    /// The ticket tracking the removal of the entry.
+    #[cfg_attr(feature = "serde", serde(default, skip_serializing_if = "Option::is_none"))]
+    pub ticket: Option<String>,
+    /// The date the entry was added to the baseline, as `YYYY-MM-DD`.
+    #[cfg_attr(feature = "serde", serde(default, skip_serializing_if = "Option::is_none"))]
+    pub added: Option<String>,
 }
 
 /// Represents a single issue in the strict baseline format.
 ///
 /// This is a simplified representation of an issue for storage in the baseline file.
-#[derive(Debug, PartialEq, Eq, Hash, Clone, PartialOrd, Ord, JsonSchema)]
+/// Two issues are equal when their code and lines are; the metadata is ignored.
+#[derive(Debug, Clone, JsonSchema)]
 #[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct StrictBaselineIssue {
     pub code: String,
     pub start_line: u32,
     pub end_line: u32,
+    #[cfg_attr(feature = "serde", serde(flatten))]
+    pub metadata: BaselineEntryMetadata,
 }
 
 /// Represents a collection of issues for a specific file path in the strict baseline.
@@ -105,6 +154,9 @@ pub struct LooseBaselineIssue {
     pub message: String,
     /// The number of occurrences of this issue.
     pub count: u32,
+    /// The owner, ticket, and date attached to the entry.
+    #[cfg_attr(feature = "serde", serde(flatten))]
+    pub metadata: BaselineEntryMetadata,
 }
 
 /// The loose baseline structure with count-based issue tracking.
@@ -117,7 +169,39 @@ pub struct LooseBaseline {
     pub issues: Vec<LooseBaselineIssue>,
 }
 
-/// A baseline that can be either strict or loose.
+/// Represents a single issue entry in the fingerprint baseline format.
+///
+/// Issues are grouped by (code, symbol, fingerprint) tuple with a count. The file is kept
+/// for reference only, and takes no part in matching.
+#[derive(Debug, PartialEq, Eq, Hash, Clone, PartialOrd, Ord, JsonSchema)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct FingerprintBaselineIssue {
+    /// The normalized file path where the issues occurred when the entry was generated.
+    pub file: String,
+    /// The issue code (e.g., "missing-type-hint").
+    pub code: String,
+    /// The symbol enclosing the issues, e.g. `App\Service::handle`, or an empty string at the top level.
+    pub symbol: String,
+    /// The fingerprint of the innermost statement or member enclosing the issues, as 16 hex digits.
+    pub fingerprint: String,
+    /// The number of occurrences of this issue.
+    pub count: u32,
+    /// The owner, ticket, and date attached to the entry.
+    #[cfg_attr(feature = "serde", serde(flatten))]
+    pub metadata: BaselineEntryMetadata,
+}
+
+/// The fingerprint baseline structure with structure-based issue tracking.
+#[derive(Debug, Default, JsonSchema)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct FingerprintBaseline {
+    /// The baseline variant marker.
+    pub variant: BaselineVariant,
+    /// The list of issues with their counts.
+    pub issues: Vec<FingerprintBaselineIssue>,
+}
+
+/// A baseline that can be strict, loose, or fingerprint-based.
 #[derive(Debug, JsonSchema)]
 #[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 #[cfg_attr(feature = "serde", serde(untagged))]
@@ -126,6 +210,53 @@ pub enum Baseline {
     Strict(StrictBaseline),
     /// Loose baseline with count-based matching.
     Loose(LooseBaseline),
+    /// Fingerprint baseline with structure-based matching.
+    Fingerprint(FingerprintBaseline),
+}
+
+/// Where an issue sits in the code, as used by the fingerprint baseline.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct IssueLocation {
+    /// The symbol enclosing the issue, e.g. `App\Service::handle`, or an empty string at the top level.
+    pub symbol: String,
+    /// The fingerprint of the innermost statement or member enclosing the issue, or `0` when
+    /// the issue sits on the declaration of the symbol itself.
+    pub fingerprint: u64,
+}
+
+/// The locations of a set of issues, keyed by the span a baseline uses to identify each issue.
+///
+/// Only the fingerprint baseline reads them; issues without a location fall back to their file
+/// path as the symbol.
+#[derive(Debug, Clone, Default)]
+pub struct IssueLocations {
+    locations: HashMap<Span, IssueLocation>,
+}
+
+/// A baseline file holding the baselines of several tools, one section per tool.
+#[derive(Debug, Default, JsonSchema)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct ProjectBaseline {
+    /// The baseline of the linter.
+    #[cfg_attr(feature = "serde", serde(default, skip_serializing_if = "Option::is_none"))]
+    pub linter: Option<Baseline>,
+    /// The baseline of the analyzer.
+    #[cfg_attr(feature = "serde", serde(default, skip_serializing_if = "Option::is_none"))]
+    pub analyzer: Option<Baseline>,
+    /// The baseline of the guard.
+    #[cfg_attr(feature = "serde", serde(default, skip_serializing_if = "Option::is_none"))]
+    pub guard: Option<Baseline>,
+}
+
+/// The contents of a baseline file: either the baseline of a single tool, or a project baseline.
+#[derive(Debug, JsonSchema)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(untagged))]
+pub enum BaselineFile {
+    /// A baseline holding the issues of a single tool.
+    Tool(Baseline),
+    /// A baseline holding the issues of several tools, one section per tool.
+    Project(ProjectBaseline),
 }
 
 /// A specific issue that appeared or disappeared during a baseline comparison.
@@ -158,6 +289,95 @@ fn normalize_path(path: &[u8]) -> String {
     String::from_utf8_lossy(path).replace('\\', "/")
 }
 
+impl PartialEq for StrictBaselineIssue {
+    fn eq(&self, other: &Self) -> bool {
+        self.cmp(other) == Ordering::Equal
+    }
+}
+
+impl Eq for StrictBaselineIssue {}
+
+impl Hash for StrictBaselineIssue {
+    fn hash<H>(&self, state: &mut H)
+    where
+        H: Hasher,
+    {
+        (&self.code, self.start_line, self.end_line).hash(state);
+    }
+}
+
+impl PartialOrd for StrictBaselineIssue {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for StrictBaselineIssue {
+    fn cmp(&self, other: &Self) -> Ordering {
+        (&self.code, self.start_line, self.end_line).cmp(&(&other.code, other.start_line, other.end_line))
+    }
+}
+
+impl IssueLocations {
+    /// Creates an empty set of locations.
+    #[must_use]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Returns the span a baseline uses to identify the issue, if it has any annotation.
+    #[must_use]
+    pub fn span_of(issue: &Issue) -> Option<Span> {
+        baseline_annotation(issue).map(|annotation| annotation.span)
+    }
+
+    /// Records the location of the issue identified by `span`.
+    pub fn insert(&mut self, span: Span, location: IssueLocation) {
+        self.locations.insert(span, location);
+    }
+
+    /// Returns the location of the given issue, if it was recorded.
+    #[must_use]
+    pub fn get(&self, issue: &Issue) -> Option<&IssueLocation> {
+        Self::span_of(issue).and_then(|span| self.locations.get(&span))
+    }
+
+    /// Returns the number of recorded locations.
+    #[must_use]
+    pub fn len(&self) -> usize {
+        self.locations.len()
+    }
+
+    /// Returns `true` if no location was recorded.
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.locations.is_empty()
+    }
+}
+
+impl BaselineTool {
+    /// All tools, in the order their sections appear in a project baseline.
+    pub const ALL: [BaselineTool; 3] = [BaselineTool::Linter, BaselineTool::Analyzer, BaselineTool::Guard];
+
+    /// Returns the name of the tool, which is also the name of its section in a project baseline.
+    #[must_use]
+    pub const fn as_str(&self) -> &'static str {
+        match self {
+            BaselineTool::Linter => "linter",
+            BaselineTool::Analyzer => "analyzer",
+            BaselineTool::Guard => "guard",
+        }
+    }
+}
+
+impl BaselineEntryMetadata {
+    /// Returns `true` if no field is set.
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.owner.is_none() && self.ticket.is_none() && self.added.is_none()
+    }
+}
+
 impl StrictBaseline {
     /// Creates a new empty strict baseline.
     #[must_use]
@@ -192,6 +412,7 @@ impl StrictBaseline {
                 code: issue.code.as_ref().unwrap_or(&String::from("unknown")).clone(),
                 start_line,
                 end_line,
+                metadata: BaselineEntryMetadata::default(),
             };
 
             if !entry.issues.contains(&baseline_issue) {
@@ -239,6 +460,7 @@ impl StrictBaseline {
                 code: issue.code.as_ref().unwrap_or(&String::from("unknown")).clone(),
                 start_line,
                 end_line,
+                metadata: BaselineEntryMetadata::default(),
             };
 
             if !baseline_entry.issues.contains(&baseline_issue) {
@@ -407,7 +629,13 @@ impl LooseBaseline {
 
         let mut baseline_issues: Vec<LooseBaselineIssue> = issue_counts
             .into_iter()
-            .map(|((file, code, message), count)| LooseBaselineIssue { file, code, message, count })
+            .map(|((file, code, message), count)| LooseBaselineIssue {
+                file,
+                code,
+                message,
+                count,
+                metadata: BaselineEntryMetadata::default(),
+            })
             .collect();
 
         baseline_issues.sort();
@@ -547,6 +775,7 @@ impl LooseBaseline {
                     code: issue.code.clone(),
                     message: issue.message.clone(),
                     count: new_count,
+                    metadata: issue.metadata.clone(),
                 });
             }
         }
@@ -557,17 +786,240 @@ impl LooseBaseline {
     }
 }
 
+/// The key fingerprint baseline entries are matched on: `(code, symbol, fingerprint)`.
+type FingerprintKey = (String, String, String);
+
+impl FingerprintBaselineIssue {
+    fn key(&self) -> FingerprintKey {
+        (self.code.clone(), self.symbol.clone(), self.fingerprint.clone())
+    }
+}
+
+impl FingerprintBaseline {
+    /// Creates a new empty fingerprint baseline.
+    #[must_use]
+    pub fn new() -> Self {
+        Self { variant: BaselineVariant::Fingerprint, issues: Vec::new() }
+    }
+
+    /// Returns the normalized file path and the matching key of an issue.
+    ///
+    /// Issues without a recorded location are keyed on their file path as the symbol,
+    /// with a zero fingerprint.
+    fn issue_key(
+        issue: &Issue,
+        read_database: &ReadDatabase,
+        locations: &IssueLocations,
+    ) -> Option<(String, FingerprintKey)> {
+        let annotation = baseline_annotation(issue)?;
+        let file = read_database.get(&annotation.span.file_id).ok()?;
+
+        let normalized_path = normalize_path(&file.name);
+        let code = issue.code.as_ref().unwrap_or(&String::from("unknown")).clone();
+        let (symbol, fingerprint) = match locations.get(issue) {
+            Some(location) => (location.symbol.clone(), location.fingerprint),
+            None => (normalized_path.clone(), 0),
+        };
+
+        Some((normalized_path, (code, symbol, format!("{fingerprint:016x}"))))
+    }
+
+    /// Returns the total count of each key, along with the file of its first entry.
+    fn pooled_counts(&self) -> HashMap<FingerprintKey, (u32, &str)> {
+        let mut counts: HashMap<FingerprintKey, (u32, &str)> = HashMap::default();
+        for issue in &self.issues {
+            counts.entry(issue.key()).or_insert((0, issue.file.as_str())).0 += issue.count;
+        }
+
+        counts
+    }
+
+    /// Generates a fingerprint baseline from a collection of issues.
+    ///
+    /// Issues are grouped by (file, code, symbol, fingerprint) tuple and stored with a count.
+    /// File paths are normalized to ensure cross-platform compatibility.
+    #[must_use]
+    pub fn generate_from_issues(
+        issues: &IssueCollection,
+        read_database: &ReadDatabase,
+        locations: &IssueLocations,
+    ) -> Self {
+        let mut issue_counts: HashMap<(String, FingerprintKey), u32> = HashMap::default();
+
+        for issue in issues.iter() {
+            if let Some(key) = Self::issue_key(issue, read_database, locations) {
+                *issue_counts.entry(key).or_insert(0) += 1;
+            }
+        }
+
+        let mut baseline_issues: Vec<FingerprintBaselineIssue> = issue_counts
+            .into_iter()
+            .map(|((file, (code, symbol, fingerprint)), count)| FingerprintBaselineIssue {
+                file,
+                code,
+                symbol,
+                fingerprint,
+                count,
+                metadata: BaselineEntryMetadata::default(),
+            })
+            .collect();
+
+        baseline_issues.sort();
+
+        Self { variant: BaselineVariant::Fingerprint, issues: baseline_issues }
+    }
+
+    /// Filters an issue collection against this fingerprint baseline.
+    ///
+    /// Returns a new issue collection containing only issues that exceed the baseline counts.
+    /// For each (code, symbol, fingerprint) tuple, issues are filtered out up to the total count
+    /// in the baseline, regardless of the file they occur in.
+    #[must_use]
+    pub fn filter_issues(
+        &self,
+        issues: IssueCollection,
+        read_database: &ReadDatabase,
+        locations: &IssueLocations,
+    ) -> IssueCollection {
+        let mut remaining_counts: HashMap<FingerprintKey, u32> =
+            self.pooled_counts().into_iter().map(|(key, (count, _))| (key, count)).collect();
+
+        let mut filtered_issues = Vec::new();
+
+        for issue in issues {
+            let Some((_, key)) = Self::issue_key(&issue, read_database, locations) else {
+                filtered_issues.push(issue);
+                continue;
+            };
+
+            if let Some(count) = remaining_counts.get_mut(&key)
+                && *count > 0
+            {
+                *count -= 1;
+                continue;
+            }
+
+            filtered_issues.push(issue);
+        }
+
+        IssueCollection::from(filtered_issues)
+    }
+
+    /// Compares this fingerprint baseline with a collection of current issues.
+    ///
+    /// Returns a comparison result with statistics about differences between the baseline
+    /// and current issues. New issues are reported in the file they occur in, removed issues
+    /// in the file recorded in the baseline.
+    #[must_use]
+    pub fn compare_with_issues(
+        &self,
+        issues: &IssueCollection,
+        read_database: &ReadDatabase,
+        locations: &IssueLocations,
+    ) -> BaselineComparisonResult {
+        let current = Self::generate_from_issues(issues, read_database, locations);
+
+        let current_map = current.pooled_counts();
+        let baseline_map = self.pooled_counts();
+
+        let mut new_issues: Vec<BaselineChangeEntry> = vec![];
+        let mut removed_issues: Vec<BaselineChangeEntry> = vec![];
+        let mut files_with_changes: HashSet<&str> = HashSet::default();
+
+        let mut record = |changes: &mut Vec<BaselineChangeEntry>, file, code: &str, delta: u32| {
+            for _ in 0..delta {
+                changes.push(BaselineChangeEntry {
+                    file: String::from(file),
+                    code: code.to_string(),
+                    start_line: 0,
+                    end_line: 0,
+                });
+            }
+
+            files_with_changes.insert(file);
+        };
+
+        for (key, &(current_count, file)) in &current_map {
+            let baseline_count = baseline_map.get(key).map_or(0, |(count, _)| *count);
+            if current_count > baseline_count {
+                record(&mut new_issues, file, &key.0, current_count - baseline_count);
+            }
+        }
+
+        for (key, &(baseline_count, file)) in &baseline_map {
+            let current_count = current_map.get(key).map_or(0, |(count, _)| *count);
+            if baseline_count > current_count {
+                record(&mut removed_issues, file, &key.0, baseline_count - current_count);
+            }
+        }
+
+        BaselineComparisonResult {
+            is_up_to_date: new_issues.is_empty() && removed_issues.is_empty(),
+            new_issues,
+            removed_issues,
+            files_with_changes_count: files_with_changes.len(),
+        }
+    }
+
+    /// Removes baseline issues that no longer correspond to a current issue.
+    ///
+    /// Unlike [`generate_from_issues`](Self::generate_from_issues), this never adds new
+    /// issues and never raises a count. For each `(code, symbol, fingerprint)` tuple the
+    /// counts are capped at the current number of occurrences; entries with no remaining
+    /// occurrence are dropped. The second tuple element is the number of stale occurrences removed.
+    #[must_use]
+    pub fn prune_outdated_entries(
+        &self,
+        issues: &IssueCollection,
+        read_database: &ReadDatabase,
+        locations: &IssueLocations,
+    ) -> (Self, usize) {
+        let current = Self::generate_from_issues(issues, read_database, locations);
+        let mut remaining_counts: HashMap<FingerprintKey, u32> =
+            current.pooled_counts().into_iter().map(|(key, (count, _))| (key, count)).collect();
+
+        let mut kept: Vec<FingerprintBaselineIssue> = Vec::new();
+        let mut removed_count = 0;
+
+        for issue in &self.issues {
+            let remaining = remaining_counts.get_mut(&issue.key());
+            let new_count = remaining.map_or(0, |remaining| {
+                let new_count = issue.count.min(*remaining);
+                *remaining -= new_count;
+
+                new_count
+            });
+
+            removed_count += (issue.count - new_count) as usize;
+
+            if new_count > 0 {
+                kept.push(FingerprintBaselineIssue { count: new_count, ..issue.clone() });
+            }
+        }
+
+        kept.sort();
+
+        (Self { variant: BaselineVariant::Fingerprint, issues: kept }, removed_count)
+    }
+}
+
 impl Baseline {
     /// Generates a baseline from a collection of issues using the specified variant.
+    ///
+    /// The `locations` are only read by the fingerprint variant.
     #[must_use]
     pub fn generate_from_issues(
         issues: &IssueCollection,
         read_database: &ReadDatabase,
         variant: BaselineVariant,
+        locations: &IssueLocations,
     ) -> Self {
         match variant {
             BaselineVariant::Strict => Baseline::Strict(StrictBaseline::generate_from_issues(issues, read_database)),
             BaselineVariant::Loose => Baseline::Loose(LooseBaseline::generate_from_issues(issues, read_database)),
+            BaselineVariant::Fingerprint => {
+                Baseline::Fingerprint(FingerprintBaseline::generate_from_issues(issues, read_database, locations))
+            }
         }
     }
 
@@ -575,10 +1027,16 @@ impl Baseline {
     ///
     /// Returns a new issue collection containing only issues that are not in the baseline.
     #[must_use]
-    pub fn filter_issues(&self, issues: IssueCollection, read_database: &ReadDatabase) -> IssueCollection {
+    pub fn filter_issues(
+        &self,
+        issues: IssueCollection,
+        read_database: &ReadDatabase,
+        locations: &IssueLocations,
+    ) -> IssueCollection {
         match self {
             Baseline::Strict(strict) => strict.filter_issues(issues, read_database),
             Baseline::Loose(loose) => loose.filter_issues(issues, read_database),
+            Baseline::Fingerprint(fingerprint) => fingerprint.filter_issues(issues, read_database, locations),
         }
     }
 
@@ -591,10 +1049,12 @@ impl Baseline {
         &self,
         issues: &IssueCollection,
         read_database: &ReadDatabase,
+        locations: &IssueLocations,
     ) -> BaselineComparisonResult {
         match self {
             Baseline::Strict(strict) => strict.compare_with_issues(issues, read_database),
             Baseline::Loose(loose) => loose.compare_with_issues(issues, read_database),
+            Baseline::Fingerprint(fingerprint) => fingerprint.compare_with_issues(issues, read_database, locations),
         }
     }
 
@@ -603,7 +1063,12 @@ impl Baseline {
     /// This never adds new entries, so issues introduced since the baseline was created
     /// remain reportable. The second tuple element is the number of stale entries removed.
     #[must_use]
-    pub fn prune_outdated_entries(&self, issues: &IssueCollection, read_database: &ReadDatabase) -> (Self, usize) {
+    pub fn prune_outdated_entries(
+        &self,
+        issues: &IssueCollection,
+        read_database: &ReadDatabase,
+        locations: &IssueLocations,
+    ) -> (Self, usize) {
         match self {
             Baseline::Strict(strict) => {
                 let (pruned, removed) = strict.prune_outdated_entries(issues, read_database);
@@ -613,6 +1078,10 @@ impl Baseline {
                 let (pruned, removed) = loose.prune_outdated_entries(issues, read_database);
                 (Baseline::Loose(pruned), removed)
             }
+            Baseline::Fingerprint(fingerprint) => {
+                let (pruned, removed) = fingerprint.prune_outdated_entries(issues, read_database, locations);
+                (Baseline::Fingerprint(pruned), removed)
+            }
         }
     }
 
@@ -622,6 +1091,198 @@ impl Baseline {
         match self {
             Baseline::Strict(_) => BaselineVariant::Strict,
             Baseline::Loose(_) => BaselineVariant::Loose,
+            Baseline::Fingerprint(_) => BaselineVariant::Fingerprint,
+        }
+    }
+
+    /// Carries the metadata of the entries of `previous` over to the matching entries of this baseline.
+    ///
+    /// An entry inherits the metadata of the previous entry with the same file, issue code and
+    /// lines (strict), message (loose), or fingerprint. When the file holds a single entry with
+    /// that code on both sides, the two are paired even if they moved. Fingerprint entries first
+    /// look for a counterpart with the same symbol and code in any file, so their metadata
+    /// follows them across renames. Any other entry is new to the baseline, and gets `today`
+    /// as its `added` date.
+    pub fn inherit_metadata(&mut self, previous: Option<&Baseline>, today: &str) {
+        let mut candidates: HashMap<(&str, &str), Vec<(EntryLocator<'_>, &BaselineEntryMetadata)>> = HashMap::default();
+        let mut symbol_candidates: HashMap<(&str, &str), Vec<(EntryLocator<'_>, &BaselineEntryMetadata)>> =
+            HashMap::default();
+
+        match previous {
+            Some(Baseline::Strict(strict)) => {
+                for (file, entry) in &strict.entries {
+                    for issue in &entry.issues {
+                        candidates
+                            .entry((file, &issue.code))
+                            .or_default()
+                            .push((EntryLocator::Lines(issue.start_line, issue.end_line), &issue.metadata));
+                    }
+                }
+            }
+            Some(Baseline::Loose(loose)) => {
+                for issue in &loose.issues {
+                    candidates
+                        .entry((&issue.file, &issue.code))
+                        .or_default()
+                        .push((EntryLocator::Message(&issue.message), &issue.metadata));
+                }
+            }
+            Some(Baseline::Fingerprint(fingerprint)) => {
+                for issue in &fingerprint.issues {
+                    let locator = EntryLocator::Fingerprint(&issue.symbol, &issue.fingerprint);
+
+                    candidates.entry((&issue.file, &issue.code)).or_default().push((locator, &issue.metadata));
+                    symbol_candidates.entry((&issue.symbol, &issue.code)).or_default().push((locator, &issue.metadata));
+                }
+            }
+            None => {}
+        }
+
+        let mut entry_counts: HashMap<(String, String), usize> = HashMap::default();
+        let mut symbol_entry_counts: HashMap<(String, String), usize> = HashMap::default();
+        match &*self {
+            Baseline::Strict(strict) => {
+                for (file, entry) in &strict.entries {
+                    for issue in &entry.issues {
+                        *entry_counts.entry((file.to_string(), issue.code.clone())).or_default() += 1;
+                    }
+                }
+            }
+            Baseline::Loose(loose) => {
+                for issue in &loose.issues {
+                    *entry_counts.entry((issue.file.clone(), issue.code.clone())).or_default() += 1;
+                }
+            }
+            Baseline::Fingerprint(fingerprint) => {
+                for issue in &fingerprint.issues {
+                    *entry_counts.entry((issue.file.clone(), issue.code.clone())).or_default() += 1;
+                    *symbol_entry_counts.entry((issue.symbol.clone(), issue.code.clone())).or_default() += 1;
+                }
+            }
+        }
+
+        let inherit = |file: &str, code: &str, locator: EntryLocator<'_>| -> BaselineEntryMetadata {
+            let symbol_matches = match locator {
+                EntryLocator::Fingerprint(symbol, _) => symbol_candidates
+                    .get(&(symbol, code))
+                    .map(|matches| (matches, symbol_entry_counts.get(&(symbol.to_string(), code.to_string())))),
+                EntryLocator::Lines(..) | EntryLocator::Message(_) => None,
+            };
+
+            let matches = symbol_matches.or_else(|| {
+                candidates
+                    .get(&(file, code))
+                    .map(|matches| (matches, entry_counts.get(&(file.to_string(), code.to_string()))))
+            });
+
+            let Some((matches, entries)) = matches else {
+                return BaselineEntryMetadata { added: Some(today.to_string()), ..Default::default() };
+            };
+
+            if let Some((_, metadata)) = matches.iter().find(|(candidate, _)| *candidate == locator) {
+                return (*metadata).clone();
+            }
+
+            match matches.as_slice() {
+                [(_, metadata)] if entries == Some(&1) => (*metadata).clone(),
+                _ => BaselineEntryMetadata { added: Some(today.to_string()), ..Default::default() },
+            }
+        };
+
+        match self {
+            Baseline::Strict(strict) => {
+                for (file, entry) in &mut strict.entries {
+                    for issue in &mut entry.issues {
+                        issue.metadata =
+                            inherit(file, &issue.code, EntryLocator::Lines(issue.start_line, issue.end_line));
+                    }
+                }
+            }
+            Baseline::Loose(loose) => {
+                for issue in &mut loose.issues {
+                    issue.metadata = inherit(&issue.file, &issue.code, EntryLocator::Message(&issue.message));
+                }
+            }
+            Baseline::Fingerprint(fingerprint) => {
+                for issue in &mut fingerprint.issues {
+                    issue.metadata =
+                        inherit(&issue.file, &issue.code, EntryLocator::Fingerprint(&issue.symbol, &issue.fingerprint));
+                }
+            }
+        }
+    }
+}
+
+/// What sets an entry apart from the other entries of the same file and code.
+#[derive(Clone, Copy, PartialEq, Eq)]
+enum EntryLocator<'baseline> {
+    Lines(u32, u32),
+    Message(&'baseline str),
+    Fingerprint(&'baseline str, &'baseline str),
+}
+
+impl ProjectBaseline {
+    /// Returns the section of the given tool, if any.
+    #[must_use]
+    pub fn section(&self, tool: BaselineTool) -> Option<&Baseline> {
+        match tool {
+            BaselineTool::Linter => self.linter.as_ref(),
+            BaselineTool::Analyzer => self.analyzer.as_ref(),
+            BaselineTool::Guard => self.guard.as_ref(),
+        }
+    }
+
+    /// Replaces the section of the given tool.
+    pub fn set_section(&mut self, tool: BaselineTool, baseline: Baseline) {
+        let section = match tool {
+            BaselineTool::Linter => &mut self.linter,
+            BaselineTool::Analyzer => &mut self.analyzer,
+            BaselineTool::Guard => &mut self.guard,
+        };
+
+        *section = Some(baseline);
+    }
+
+    /// Returns the sections present in the project baseline.
+    pub fn sections(&self) -> impl Iterator<Item = (BaselineTool, &Baseline)> {
+        BaselineTool::ALL.into_iter().filter_map(|tool| self.section(tool).map(|baseline| (tool, baseline)))
+    }
+}
+
+impl BaselineFile {
+    /// Returns the baseline of the given tool: the whole file for a single-tool baseline,
+    /// or the tool's section for a project baseline.
+    #[must_use]
+    pub fn section(&self, tool: BaselineTool) -> Option<&Baseline> {
+        match self {
+            BaselineFile::Tool(baseline) => Some(baseline),
+            BaselineFile::Project(project) => project.section(tool),
+        }
+    }
+
+    /// Consumes the file, returning the baseline of the given tool.
+    #[must_use]
+    pub fn into_section(self, tool: BaselineTool) -> Option<Baseline> {
+        match self {
+            BaselineFile::Tool(baseline) => Some(baseline),
+            BaselineFile::Project(mut project) => match tool {
+                BaselineTool::Linter => project.linter.take(),
+                BaselineTool::Analyzer => project.analyzer.take(),
+                BaselineTool::Guard => project.guard.take(),
+            },
+        }
+    }
+
+    /// Replaces the baseline of the given tool, keeping the sections of the other tools.
+    #[must_use]
+    pub fn with_section(self, tool: BaselineTool, baseline: Baseline) -> Self {
+        match self {
+            BaselineFile::Tool(_) => BaselineFile::Tool(baseline),
+            BaselineFile::Project(mut project) => {
+                project.set_section(tool, baseline);
+
+                BaselineFile::Project(project)
+            }
         }
     }
 }
@@ -702,7 +1363,12 @@ mod tests {
 
         let mut baseline = StrictBaseline::new();
         let mut entry = StrictBaselineEntry::default();
-        entry.issues.push(StrictBaselineIssue { code: "E001".to_string(), start_line: 0, end_line: 0 });
+        entry.issues.push(StrictBaselineIssue {
+            code: "E001".to_string(),
+            start_line: 0,
+            end_line: 0,
+            metadata: BaselineEntryMetadata::default(),
+        });
         baseline.entries.insert(Cow::Borrowed("test.php"), entry);
 
         let mut issues = IssueCollection::new();
@@ -722,8 +1388,18 @@ mod tests {
 
         let mut baseline = StrictBaseline::new();
         let mut entry = StrictBaselineEntry::default();
-        entry.issues.push(StrictBaselineIssue { code: "E001".to_string(), start_line: 0, end_line: 0 });
-        entry.issues.push(StrictBaselineIssue { code: "E003".to_string(), start_line: 2, end_line: 2 });
+        entry.issues.push(StrictBaselineIssue {
+            code: "E001".to_string(),
+            start_line: 0,
+            end_line: 0,
+            metadata: BaselineEntryMetadata::default(),
+        });
+        entry.issues.push(StrictBaselineIssue {
+            code: "E003".to_string(),
+            start_line: 2,
+            end_line: 2,
+            metadata: BaselineEntryMetadata::default(),
+        });
         baseline.entries.insert(Cow::Borrowed("test.php"), entry);
 
         let mut issues = IssueCollection::new();
@@ -772,6 +1448,7 @@ mod tests {
                 code: "E001".to_string(),
                 message: "test error".to_string(),
                 count: 2,
+                metadata: BaselineEntryMetadata::default(),
             }],
         };
 
@@ -798,12 +1475,14 @@ mod tests {
                     code: "E001".to_string(),
                     message: "test error".to_string(),
                     count: 2,
+                    metadata: BaselineEntryMetadata::default(),
                 },
                 LooseBaselineIssue {
                     file: "test.php".to_string(),
                     code: "E003".to_string(),
                     message: "test error".to_string(),
                     count: 1,
+                    metadata: BaselineEntryMetadata::default(),
                 },
             ],
         };
@@ -828,7 +1507,8 @@ mod tests {
         let mut issues = IssueCollection::new();
         issues.push(create_test_issue(file_id, "E001", 0, 5));
 
-        let baseline = Baseline::generate_from_issues(&issues, &read_db, BaselineVariant::Strict);
+        let baseline =
+            Baseline::generate_from_issues(&issues, &read_db, BaselineVariant::Strict, &IssueLocations::default());
 
         assert!(matches!(baseline, Baseline::Strict(_)));
         assert_eq!(baseline.variant(), BaselineVariant::Strict);
@@ -842,7 +1522,8 @@ mod tests {
         let mut issues = IssueCollection::new();
         issues.push(create_test_issue(file_id, "E001", 0, 5));
 
-        let baseline = Baseline::generate_from_issues(&issues, &read_db, BaselineVariant::Loose);
+        let baseline =
+            Baseline::generate_from_issues(&issues, &read_db, BaselineVariant::Loose, &IssueLocations::default());
 
         assert!(matches!(baseline, Baseline::Loose(_)));
         assert_eq!(baseline.variant(), BaselineVariant::Loose);
@@ -855,8 +1536,18 @@ mod tests {
 
         let mut baseline = StrictBaseline::new();
         let mut entry = StrictBaselineEntry::default();
-        entry.issues.push(StrictBaselineIssue { code: "E001".to_string(), start_line: 0, end_line: 0 });
-        entry.issues.push(StrictBaselineIssue { code: "E999".to_string(), start_line: 2, end_line: 2 });
+        entry.issues.push(StrictBaselineIssue {
+            code: "E001".to_string(),
+            start_line: 0,
+            end_line: 0,
+            metadata: BaselineEntryMetadata::default(),
+        });
+        entry.issues.push(StrictBaselineIssue {
+            code: "E999".to_string(),
+            start_line: 2,
+            end_line: 2,
+            metadata: BaselineEntryMetadata::default(),
+        });
         baseline.entries.insert(Cow::Borrowed("test.php"), entry);
 
         let mut issues = IssueCollection::new();
@@ -880,7 +1571,12 @@ mod tests {
 
         let mut baseline = StrictBaseline::new();
         let mut entry = StrictBaselineEntry::default();
-        entry.issues.push(StrictBaselineIssue { code: "E999".to_string(), start_line: 2, end_line: 2 });
+        entry.issues.push(StrictBaselineIssue {
+            code: "E999".to_string(),
+            start_line: 2,
+            end_line: 2,
+            metadata: BaselineEntryMetadata::default(),
+        });
         baseline.entries.insert(Cow::Borrowed("test.php"), entry);
 
         let issues = IssueCollection::new();
@@ -904,12 +1600,14 @@ mod tests {
                     code: "E001".to_string(),
                     message: "test error".to_string(),
                     count: 3,
+                    metadata: BaselineEntryMetadata::default(),
                 },
                 LooseBaselineIssue {
                     file: "test.php".to_string(),
                     code: "E999".to_string(),
                     message: "test error".to_string(),
                     count: 1,
+                    metadata: BaselineEntryMetadata::default(),
                 },
             ],
         };
@@ -925,4 +1623,294 @@ mod tests {
         assert_eq!(pruned.issues[0].code, "E001");
         assert_eq!(pruned.issues[0].count, 1, "the count must be capped at the current occurrences");
     }
+
+    #[test]
+    fn test_strict_filter_ignores_metadata() {
+        let (db, file_id) = create_test_database();
+        let read_db = db.read_only();
+
+        let mut issues = IssueCollection::new();
+        issues.push(create_test_issue(file_id, "E001", 0, 5));
+
+        let mut baseline = StrictBaseline::generate_from_issues(&issues, &read_db);
+        for issue in &mut baseline.entries.get_mut("test.php").unwrap().issues {
+            issue.metadata.owner = Some("payments".to_string());
+        }
+
+        assert_eq!(baseline.filter_issues(issues.clone(), &read_db).len(), 0);
+        assert!(baseline.compare_with_issues(&issues, &read_db).is_up_to_date);
+    }
+
+    #[test]
+    fn test_inherit_metadata_strict() {
+        let (db, file_id) = create_test_database();
+        let read_db = db.read_only();
+
+        let mut issues = IssueCollection::new();
+        issues.push(create_test_issue(file_id, "E001", 0, 5));
+        issues.push(create_test_issue(file_id, "E002", 10, 15));
+
+        let mut previous =
+            Baseline::generate_from_issues(&issues, &read_db, BaselineVariant::Strict, &IssueLocations::default());
+        let Baseline::Strict(strict) = &mut previous else { unreachable!() };
+        let entry = strict.entries.get_mut("test.php").unwrap();
+        entry.issues[0].metadata = BaselineEntryMetadata {
+            owner: Some("payments".to_string()),
+            ticket: Some("PAY-12".to_string()),
+            added: Some("2026-01-05".to_string()),
+        };
+
+        // E001 moved to another line, E003 is new.
+        let mut issues = IssueCollection::new();
+        issues.push(create_test_issue(file_id, "E001", 20, 25));
+        issues.push(create_test_issue(file_id, "E002", 10, 15));
+        issues.push(create_test_issue(file_id, "E003", 20, 25));
+
+        let mut baseline =
+            Baseline::generate_from_issues(&issues, &read_db, BaselineVariant::Strict, &IssueLocations::default());
+        baseline.inherit_metadata(Some(&previous), "2026-10-17");
+
+        let Baseline::Strict(strict) = baseline else { unreachable!() };
+        let entry = strict.entries.get("test.php").unwrap();
+        let metadata = |code: &str| entry.issues.iter().find(|issue| issue.code == code).unwrap().metadata.clone();
+
+        assert_eq!(metadata("E001").owner.as_deref(), Some("payments"));
+        assert_eq!(metadata("E001").added.as_deref(), Some("2026-01-05"));
+        assert!(metadata("E002").is_empty());
+        assert_eq!(metadata("E003").added.as_deref(), Some("2026-10-17"));
+        assert_eq!(metadata("E003").owner, None);
+    }
+
+    #[test]
+    fn test_inherit_metadata_does_not_cross_entries() {
+        let (db, file_id) = create_test_database();
+        let read_db = db.read_only();
+
+        let mut issues = IssueCollection::new();
+        issues.push(create_test_issue(file_id, "E001", 0, 5));
+        issues.push(create_test_issue(file_id, "E001", 10, 15));
+
+        let mut previous =
+            Baseline::generate_from_issues(&issues, &read_db, BaselineVariant::Strict, &IssueLocations::default());
+        let Baseline::Strict(strict) = &mut previous else { unreachable!() };
+        let entry = strict.entries.get_mut("test.php").unwrap();
+        entry.issues[0].metadata = BaselineEntryMetadata {
+            owner: Some("payments".to_string()),
+            ticket: Some("PAY-12".to_string()),
+            added: Some("2026-01-05".to_string()),
+        };
+
+        // The first E001 stays put, the second is fixed, and an unrelated E001 appears.
+        let mut issues = IssueCollection::new();
+        issues.push(create_test_issue(file_id, "E001", 0, 5));
+        issues.push(create_test_issue(file_id, "E001", 30, 35));
+
+        let mut baseline =
+            Baseline::generate_from_issues(&issues, &read_db, BaselineVariant::Strict, &IssueLocations::default());
+        baseline.inherit_metadata(Some(&previous), "2026-10-17");
+
+        let Baseline::Strict(strict) = baseline else { unreachable!() };
+        let entry = strict.entries.get("test.php").unwrap();
+        let mut entries: Vec<_> = entry.issues.iter().collect();
+        entries.sort_by_key(|issue| issue.start_line);
+
+        assert_eq!(entries.len(), 2);
+        assert_eq!(entries[0].metadata.owner.as_deref(), Some("payments"));
+        assert_eq!(entries[0].metadata.added.as_deref(), Some("2026-01-05"));
+        assert_eq!(entries[1].metadata.owner, None);
+        assert_eq!(entries[1].metadata.ticket, None);
+        assert_eq!(entries[1].metadata.added.as_deref(), Some("2026-10-17"));
+    }
+
+    #[test]
+    fn test_inherit_metadata_loose() {
+        let (db, file_id) = create_test_database();
+        let read_db = db.read_only();
+
+        let mut issues = IssueCollection::new();
+        issues.push(create_test_issue_with_message(file_id, "E001", "first", 0, 5));
+
+        let mut previous =
+            Baseline::generate_from_issues(&issues, &read_db, BaselineVariant::Loose, &IssueLocations::default());
+        let Baseline::Loose(loose) = &mut previous else { unreachable!() };
+        loose.issues[0].metadata.ticket = Some("PAY-12".to_string());
+
+        issues.push(create_test_issue_with_message(file_id, "E001", "first", 10, 15));
+        issues.push(create_test_issue_with_message(file_id, "E002", "second", 10, 15));
+
+        let mut baseline =
+            Baseline::generate_from_issues(&issues, &read_db, BaselineVariant::Loose, &IssueLocations::default());
+        baseline.inherit_metadata(Some(&previous), "2026-10-17");
+
+        let Baseline::Loose(loose) = baseline else { unreachable!() };
+        assert_eq!(loose.issues[0].count, 2);
+        assert_eq!(loose.issues[0].metadata.ticket.as_deref(), Some("PAY-12"));
+        assert_eq!(loose.issues[1].metadata.added.as_deref(), Some("2026-10-17"));
+    }
+
+    #[test]
+    fn test_loose_prune_keeps_metadata() {
+        let (db, file_id) = create_test_database();
+        let read_db = db.read_only();
+
+        let mut issues = IssueCollection::new();
+        issues.push(create_test_issue(file_id, "E001", 0, 5));
+        issues.push(create_test_issue(file_id, "E001", 10, 15));
+
+        let mut baseline = LooseBaseline::generate_from_issues(&issues, &read_db);
+        baseline.issues[0].metadata.owner = Some("payments".to_string());
+
+        let mut current = IssueCollection::new();
+        current.push(create_test_issue(file_id, "E001", 0, 5));
+
+        let (pruned, removed) = baseline.prune_outdated_entries(&current, &read_db);
+        assert_eq!(removed, 1);
+        assert_eq!(pruned.issues[0].metadata.owner.as_deref(), Some("payments"));
+    }
+
+    fn locate(locations: &mut IssueLocations, issue: &Issue, symbol: &str, fingerprint: u64) {
+        let span = IssueLocations::span_of(issue).unwrap();
+        locations.insert(span, IssueLocation { symbol: symbol.to_string(), fingerprint });
+    }
+
+    #[test]
+    fn test_fingerprint_generate_baseline_from_issues() {
+        let (db, file_id) = create_test_database();
+        let read_db = db.read_only();
+
+        let mut issues = IssueCollection::new();
+        let mut locations = IssueLocations::new();
+        for (start, end) in [(0, 5), (10, 15)] {
+            let issue = create_test_issue(file_id, "E001", start, end);
+            locate(&mut locations, &issue, "App\\Service::handle", 0xabc);
+            issues.push(issue);
+        }
+        issues.push(create_test_issue(file_id, "E002", 20, 25));
+
+        let baseline = FingerprintBaseline::generate_from_issues(&issues, &read_db, &locations);
+
+        assert_eq!(baseline.variant, BaselineVariant::Fingerprint);
+        assert_eq!(baseline.issues.len(), 2);
+        assert_eq!(baseline.issues[0].symbol, "App\\Service::handle");
+        assert_eq!(baseline.issues[0].fingerprint, "0000000000000abc");
+        assert_eq!(baseline.issues[0].count, 2);
+        // Issues without a location fall back to their file path.
+        assert_eq!(baseline.issues[1].symbol, "test.php");
+        assert_eq!(baseline.issues[1].fingerprint, "0000000000000000");
+    }
+
+    #[test]
+    fn test_fingerprint_filter_ignores_lines_and_files() {
+        let (db, file_id) = create_test_database();
+        let read_db = db.read_only();
+
+        let issue = create_test_issue(file_id, "E001", 0, 5);
+        let mut locations = IssueLocations::new();
+        locate(&mut locations, &issue, "App\\Service::handle", 1);
+
+        let mut baseline =
+            FingerprintBaseline::generate_from_issues(&IssueCollection::from([issue]), &read_db, &locations);
+        baseline.issues[0].file = "src/OldService.php".to_string();
+
+        // The statement moved to another line, and the class to another file.
+        let moved = create_test_issue(file_id, "E001", 20, 25);
+        let mut locations = IssueLocations::new();
+        locate(&mut locations, &moved, "App\\Service::handle", 1);
+        let issues = IssueCollection::from([moved]);
+
+        assert_eq!(baseline.filter_issues(issues.clone(), &read_db, &locations).len(), 0);
+        assert!(baseline.compare_with_issues(&issues, &read_db, &locations).is_up_to_date);
+    }
+
+    #[test]
+    fn test_fingerprint_compare_changed_statement() {
+        let (db, file_id) = create_test_database();
+        let read_db = db.read_only();
+
+        let issue = create_test_issue(file_id, "E001", 0, 5);
+        let mut locations = IssueLocations::new();
+        locate(&mut locations, &issue, "App\\Service::handle", 1);
+
+        let baseline = FingerprintBaseline::generate_from_issues(&IssueCollection::from([issue]), &read_db, &locations);
+
+        // The enclosing statement was rewritten, changing its fingerprint.
+        let changed = create_test_issue(file_id, "E001", 0, 5);
+        let mut locations = IssueLocations::new();
+        locate(&mut locations, &changed, "App\\Service::handle", 2);
+        let issues = IssueCollection::from([changed]);
+
+        assert_eq!(baseline.filter_issues(issues.clone(), &read_db, &locations).len(), 1);
+
+        let result = baseline.compare_with_issues(&issues, &read_db, &locations);
+        assert!(!result.is_up_to_date);
+        assert_eq!(result.new_issues.len(), 1);
+        assert_eq!(result.removed_issues.len(), 1);
+        assert_eq!(result.files_with_changes_count, 1);
+    }
+
+    #[test]
+    fn test_fingerprint_prune_outdated_entries() {
+        let (db, file_id) = create_test_database();
+        let read_db = db.read_only();
+
+        let mut issues = IssueCollection::new();
+        let mut locations = IssueLocations::new();
+        for (start, end) in [(0, 5), (10, 15)] {
+            let issue = create_test_issue(file_id, "E001", start, end);
+            locate(&mut locations, &issue, "App\\Service::handle", 1);
+            issues.push(issue);
+        }
+
+        let mut baseline = FingerprintBaseline::generate_from_issues(&issues, &read_db, &locations);
+        baseline.issues[0].metadata.owner = Some("payments".to_string());
+
+        let current = IssueCollection::from([create_test_issue(file_id, "E001", 0, 5)]);
+        let (pruned, removed) = baseline.prune_outdated_entries(&current, &read_db, &locations);
+
+        assert_eq!(removed, 1);
+        assert_eq!(pruned.issues.len(), 1);
+        assert_eq!(pruned.issues[0].count, 1);
+        assert_eq!(pruned.issues[0].metadata.owner.as_deref(), Some("payments"));
+    }
+
+    #[test]
+    fn test_inherit_metadata_fingerprint_follows_renames() {
+        let (db, file_id) = create_test_database();
+        let read_db = db.read_only();
+
+        let issue = create_test_issue(file_id, "E001", 0, 5);
+        let mut locations = IssueLocations::new();
+        locate(&mut locations, &issue, "App\\Service::handle", 1);
+        let issues = IssueCollection::from([issue]);
+
+        let mut previous = Baseline::generate_from_issues(&issues, &read_db, BaselineVariant::Fingerprint, &locations);
+        let Baseline::Fingerprint(fingerprint) = &mut previous else { unreachable!() };
+        fingerprint.issues[0].file = "src/OldService.php".to_string();
+        fingerprint.issues[0].metadata.owner = Some("payments".to_string());
+
+        let mut baseline = Baseline::generate_from_issues(&issues, &read_db, BaselineVariant::Fingerprint, &locations);
+        baseline.inherit_metadata(Some(&previous), "2026-10-17");
+
+        let Baseline::Fingerprint(fingerprint) = baseline else { unreachable!() };
+        assert_eq!(fingerprint.issues[0].file, "test.php");
+        assert_eq!(fingerprint.issues[0].metadata.owner.as_deref(), Some("payments"));
+        assert_eq!(fingerprint.issues[0].metadata.added, None);
+    }
+
+    #[test]
+    fn test_project_baseline_sections() {
+        let mut project = ProjectBaseline::default();
+        project.set_section(BaselineTool::Guard, Baseline::Loose(LooseBaseline::new()));
+        project.set_section(BaselineTool::Linter, Baseline::Strict(StrictBaseline::new()));
+
+        let tools: Vec<BaselineTool> = project.sections().map(|(tool, _)| tool).collect();
+        assert_eq!(tools, vec![BaselineTool::Linter, BaselineTool::Guard]);
+
+        let file = BaselineFile::Project(project);
+        assert!(file.section(BaselineTool::Analyzer).is_none());
+
+        let file = file.with_section(BaselineTool::Analyzer, Baseline::Loose(LooseBaseline::new()));
+        assert_eq!(file.section(BaselineTool::Analyzer).map(Baseline::variant), Some(BaselineVariant::Loose));
+        assert!(matches!(file.into_section(BaselineTool::Linter), Some(Baseline::Strict(_))));
+    }
 }
@@ -0,0 +1,141 @@
+use std::io::Write;
+
+use mago_database::DatabaseReader;
+use mago_database::ReadDatabase;
+use mago_database::file::HasFileId;
+
+use crate::IssueCollection;
+use crate::Level;
+use crate::error::ReportingError;
+use crate::formatter::Formatter;
+use crate::formatter::FormatterConfig;
+use crate::formatter::utils::fingerprint;
+use crate::formatter::utils::issue_category;
+use crate::formatter::utils::long_message;
+
+/// The remediation points of a trivial change, as defined by the Code Climate specification.
+const BASE_REMEDIATION_POINTS: u32 = 50_000;
+
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+struct CodeClimateIssue<'issue> {
+    #[cfg_attr(feature = "serde", serde(rename = "type"))]
+    kind: &'static str,
+    check_name: &'issue str,
+    description: &'issue str,
+    #[cfg_attr(feature = "serde", serde(skip_serializing_if = "Option::is_none"))]
+    content: Option<Content>,
+    categories: [&'static str; 1],
+    location: Location,
+    remediation_points: u32,
+    severity: &'static str,
+    fingerprint: String,
+}
+
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+struct Content {
+    body: String,
+}
+
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+struct Location {
+    path: String,
+    positions: Positions,
+}
+
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+struct Positions {
+    begin: Position,
+    end: Position,
+}
+
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+struct Position {
+    line: u32,
+    column: u32,
+}
+
+/// Formatter that outputs issues in the Code Climate JSON format.
+///
+/// Categories and remediation points are derived from the category of the issue code,
+/// such as the category of a linter rule, and fall back to the issue level.
+pub(crate) struct CodeClimateFormatter;
+
+impl Formatter for CodeClimateFormatter {
+    fn format(
+        &self,
+        writer: &mut dyn Write,
+        issues: &IssueCollection,
+        database: &ReadDatabase,
+        config: &FormatterConfig,
+    ) -> Result<(), ReportingError> {
+        let code_climate_issues = crate::formatter::utils::filter_issues(issues, config, false)
+            .map(|issue| {
+                let severity = match &issue.level {
+                    Level::Note | Level::Help => "info",
+                    Level::Warning => "minor",
+                    Level::Error => "major",
+                };
+
+                let (path, positions) = match issue
+                    .primary_annotation()
+                    .and_then(|annotation| database.get(&annotation.span.file_id()).ok().map(|file| (annotation, file)))
+                {
+                    Some((annotation, file)) => {
+                        let begin = Position {
+                            line: file.line_number(annotation.span.start.offset) + 1,
+                            column: file.column_number(annotation.span.start.offset) + 1,
+                        };
+
+                        let end = Position {
+                            line: file.line_number(annotation.span.end.offset) + 1,
+                            column: file.column_number(annotation.span.end.offset) + 1,
+                        };
+
+                        (String::from_utf8_lossy(&file.name).into_owned(), Positions { begin, end })
+                    }
+                    None => (
+                        "<unknown>".to_string(),
+                        Positions { begin: Position { line: 1, column: 1 }, end: Position { line: 1, column: 1 } },
+                    ),
+                };
+
+                let check_name = issue.code.as_deref().unwrap_or("other");
+                let (category, remediation_factor) = match issue_category(issue, config) {
+                    Some("Clarity") => ("Clarity", 1),
+                    Some("Best Practices" | "Consistency") => ("Style", 1),
+                    Some("Redundancy") => ("Duplication", 1),
+                    Some("Deprecation") => ("Compatibility", 2),
+                    Some("Maintainability") => ("Complexity", 2),
+                    Some("Safety") => ("Bug Risk", 4),
+                    Some("Correctness") => ("Bug Risk", 6),
+                    Some("Security") => ("Security", 10),
+                    _ => match issue.level {
+                        Level::Error => ("Bug Risk", 4),
+                        Level::Warning | Level::Help | Level::Note => ("Style", 1),
+                    },
+                };
+
+                let body = long_message(issue, true);
+                let content = (body != issue.message).then_some(Content { body });
+
+                let fingerprint = fingerprint(issue, database);
+
+                CodeClimateIssue {
+                    kind: "issue",
+                    check_name,
+                    description: &issue.message,
+                    content,
+                    categories: [category],
+                    location: Location { path, positions },
+                    remediation_points: BASE_REMEDIATION_POINTS * remediation_factor,
+                    severity,
+                    fingerprint,
+                }
+            })
+            .collect::<Vec<_>>();
+
+        serde_json::to_writer_pretty(writer, &code_climate_issues)?;
+
+        Ok(())
+    }
+}
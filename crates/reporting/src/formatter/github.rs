@@ -144,6 +144,8 @@ mod tests {
             minimum_level: None,
             filter_fixable: false,
             editor_url: None,
+            issue_categories: std::collections::HashMap::new(),
+            source_highlighter: None,
         }
     }
 
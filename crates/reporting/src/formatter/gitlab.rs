@@ -9,6 +9,7 @@ use crate::Level;
 use crate::error::ReportingError;
 use crate::formatter::Formatter;
 use crate::formatter::FormatterConfig;
+use crate::formatter::utils::fingerprint;
 use crate::formatter::utils::long_message;
 
 #[cfg_attr(feature = "serde", derive(serde::Serialize))]
@@ -84,21 +85,7 @@ impl Formatter for GitlabFormatter {
 
                 let check_name = issue.code.as_deref().unwrap_or("other");
 
-                let fingerprint = {
-                    let mut hasher = blake3::Hasher::new();
-                    hasher.update(check_name.as_bytes());
-                    hasher.update(path.as_bytes());
-                    // GitLab Code Quality fingerprints must be stable across machines, so we
-                    // deliberately serialize line/column as little-endian regardless of host.
-                    #[allow(clippy::little_endian_bytes)]
-                    let line_bytes = positions.begin.line.to_le_bytes();
-                    #[allow(clippy::little_endian_bytes)]
-                    let col_bytes = positions.begin.column.to_le_bytes();
-                    hasher.update(&line_bytes);
-                    hasher.update(&col_bytes);
-                    hasher.update(description.as_bytes());
-                    hasher.finalize().to_hex()[..32].to_string()
-                };
+                let fingerprint = fingerprint(issue, database);
 
                 CodeQualityIssue {
                     description,
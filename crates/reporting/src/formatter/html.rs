@@ -0,0 +1,544 @@
+use std::collections::BTreeMap;
+use std::collections::BTreeSet;
+use std::io::Write;
+
+use mago_database::DatabaseReader;
+use mago_database::ReadDatabase;
+use mago_database::file::File;
+use mago_database::file::HasFileId;
+use mago_text_edit::ApplyResult;
+use mago_text_edit::Safety;
+use mago_text_edit::TextEditor;
+
+use crate::AnnotationKind;
+use crate::Issue;
+use crate::IssueCollection;
+use crate::Level;
+use crate::error::ReportingError;
+use crate::formatter::Formatter;
+use crate::formatter::FormatterConfig;
+use crate::formatter::utils::issue_category;
+use crate::formatter::utils::xml_encode;
+use crate::highlight::Highlight;
+
+/// The number of source lines shown before and after each annotated line.
+const CONTEXT_LINES: u32 = 3;
+
+const LEVELS: [Level; 4] = [Level::Error, Level::Warning, Level::Help, Level::Note];
+
+const STYLE: &str = r#"
+:root { color-scheme: light dark; --bg: #ffffff; --fg: #1f2328; --muted: #656d76; --border: #d0d7de; --panel: #f6f8fa; --mark: #fff8c5; --error: #cf222e; --warning: #9a6700; --help: #0969da; --note: #6e7781; --insert: #dafbe1; --delete: #ffebe9; --keyword: #cf222e; --variable: #953800; --string: #0a3069; --number: #0550ae; --constant: #0550ae; --comment: #6e7781; --name: #8250df; --operator: #1f2328; --tag: #116329; --attribute: #116329; }
+@media (prefers-color-scheme: dark) { :root { --bg: #0d1117; --fg: #e6edf3; --muted: #8d96a0; --border: #30363d; --panel: #161b22; --mark: #3b2e00; --error: #ff7b72; --warning: #d29922; --help: #58a6ff; --note: #8d96a0; --insert: #12261e; --delete: #25171c; --keyword: #ff7b72; --variable: #ffa657; --string: #a5d6ff; --number: #79c0ff; --constant: #79c0ff; --comment: #8d96a0; --name: #d2a8ff; --operator: #e6edf3; --tag: #7ee787; --attribute: #7ee787; } }
+* { box-sizing: border-box; }
+body { margin: 0 auto; max-width: 1200px; padding: 24px; background: var(--bg); color: var(--fg); font: 14px/1.5 -apple-system, BlinkMacSystemFont, "Segoe UI", Helvetica, Arial, sans-serif; }
+h1 { margin: 0 0 4px; font-size: 24px; }
+h2 { margin: 32px 0 12px; font-size: 18px; }
+code, pre, .source { font-family: ui-monospace, SFMono-Regular, Menlo, Consolas, monospace; font-size: 12px; }
+.muted { color: var(--muted); }
+.filters { display: flex; flex-wrap: wrap; gap: 16px; align-items: center; margin-top: 16px; padding: 12px; border: 1px solid var(--border); border-radius: 6px; background: var(--panel); }
+.filters select { max-width: 320px; }
+.chart { border-collapse: collapse; width: 100%; }
+.chart td { padding: 2px 8px; }
+.chart td:first-child { width: 1%; white-space: nowrap; }
+.chart td:last-child { width: 1%; text-align: right; }
+.chart button { border: 0; padding: 0; background: none; color: var(--help); font: inherit; cursor: pointer; }
+.bar { display: block; height: 12px; min-width: 2px; border-radius: 2px; background: var(--help); }
+.file { margin-bottom: 16px; border: 1px solid var(--border); border-radius: 6px; overflow: hidden; }
+.file > summary { padding: 8px 12px; background: var(--panel); cursor: pointer; font-weight: 600; }
+.source { border-collapse: collapse; width: 100%; tab-size: 4; }
+.source td { padding: 0 8px; vertical-align: top; }
+.source .line-number { width: 1%; color: var(--muted); text-align: right; user-select: none; }
+.source .code { white-space: pre; }
+.source .gap td { color: var(--muted); background: var(--panel); }
+.mark.on { background: var(--mark); text-decoration: underline wavy var(--warning); text-decoration-skip-ink: none; }
+.mark.primary.on { text-decoration-color: var(--error); }
+.caret.on::before { content: "\2038"; color: var(--error); }
+.issue { margin: 4px 0 8px; padding: 8px 12px; border-left: 4px solid var(--note); border-radius: 4px; background: var(--panel); white-space: normal; font: 14px/1.5 -apple-system, BlinkMacSystemFont, "Segoe UI", Helvetica, Arial, sans-serif; }
+.issue.error { border-color: var(--error); }
+.issue.warning { border-color: var(--warning); }
+.issue.help { border-color: var(--help); }
+.issue .message { font-weight: 600; white-space: pre-wrap; }
+.issue ul { margin: 4px 0; padding-left: 20px; }
+.level { display: inline-block; margin-right: 6px; padding: 0 6px; border-radius: 10px; color: var(--bg); font-size: 12px; font-weight: 600; }
+.level.error { background: var(--error); }
+.level.warning { background: var(--warning); }
+.level.help { background: var(--help); }
+.level.note { background: var(--note); }
+.annotation { color: var(--muted); white-space: pre-wrap; }
+.diff { margin: 4px 0; padding: 8px; overflow-x: auto; border: 1px solid var(--border); border-radius: 4px; background: var(--bg); }
+.diff .insert { display: block; background: var(--insert); }
+.diff .delete { display: block; background: var(--delete); }
+.diff .hunk { display: block; color: var(--muted); }
+.keyword { color: var(--keyword); }
+.variable { color: var(--variable); }
+.string { color: var(--string); }
+.number { color: var(--number); }
+.constant { color: var(--constant); }
+.comment { color: var(--comment); font-style: italic; }
+.name { color: var(--name); }
+.operator { color: var(--operator); }
+.tag { color: var(--tag); font-weight: 600; }
+.attribute { color: var(--attribute); }
+[hidden] { display: none !important; }
+"#;
+
+const SCRIPT: &str = "
+(function () {
+  const levels = Array.from(document.querySelectorAll('[data-filter-level]'));
+  const code = document.getElementById('filter-code');
+  const category = document.getElementById('filter-category');
+  const shown = document.getElementById('shown');
+
+  function apply() {
+    const enabled = new Set(levels.filter((input) => input.checked).map((input) => input.value));
+    const visible = new Set();
+    for (const issue of document.querySelectorAll('.issue')) {
+      const show = enabled.has(issue.dataset.level)
+        && (code.value === '' || issue.dataset.code === code.value)
+        && (category.value === '' || issue.dataset.category === category.value);
+      issue.hidden = !show;
+      if (show) visible.add(issue.dataset.issue);
+    }
+
+    for (const element of document.querySelectorAll('[data-of]')) {
+      const show = element.dataset.of.split(' ').some((issue) => visible.has(issue));
+      if (element.classList.contains('mark')) {
+        element.classList.toggle('on', show);
+      } else {
+        element.hidden = !show;
+      }
+    }
+
+    for (const group of document.querySelectorAll('[data-group]')) {
+      group.hidden = group.querySelector('.issue:not([hidden])') === null;
+    }
+
+    shown.textContent = String(visible.size);
+  }
+
+  for (const input of levels) input.addEventListener('change', apply);
+  code.addEventListener('change', apply);
+  category.addEventListener('change', apply);
+  for (const button of document.querySelectorAll('[data-select-code]')) {
+    button.addEventListener('click', () => {
+      code.value = button.dataset.selectCode;
+      apply();
+      document.getElementById('files').scrollIntoView();
+    });
+  }
+
+  apply();
+})();
+";
+
+/// An annotated span of source code, underlined while its issue is shown.
+struct Mark {
+    start: u32,
+    end: u32,
+    primary: bool,
+    issue: usize,
+}
+
+/// Formatter that outputs a self-contained HTML report.
+///
+/// Issues are listed per file, next to the highlighted source lines they annotate, along
+/// with a summary per rule, filters by level, code and category, and the diff of their fix.
+pub(crate) struct HtmlFormatter;
+
+impl Formatter for HtmlFormatter {
+    fn format(
+        &self,
+        writer: &mut dyn Write,
+        issues: &IssueCollection,
+        database: &ReadDatabase,
+        config: &FormatterConfig,
+    ) -> Result<(), ReportingError> {
+        let issues: Vec<&Issue> = crate::formatter::utils::filter_issues(issues, config, true).collect();
+
+        let mut files: BTreeMap<&[u8], (&File, Vec<usize>)> = BTreeMap::new();
+        let mut unlocated = Vec::new();
+        let mut codes: BTreeMap<&str, usize> = BTreeMap::new();
+        let mut categories: BTreeSet<&str> = BTreeSet::new();
+        for (index, issue) in issues.iter().enumerate() {
+            *codes.entry(issue.code.as_deref().unwrap_or("other")).or_default() += 1;
+            if let Some(category) = issue_category(issue, config) {
+                categories.insert(category);
+            }
+
+            match issue.primary_annotation().and_then(|annotation| database.get_ref(&annotation.span.file_id()).ok()) {
+                Some(file) => files.entry(file.name.as_ref()).or_insert_with(|| (file, Vec::new())).1.push(index),
+                None => unlocated.push(index),
+            }
+        }
+
+        writeln!(writer, "<!DOCTYPE html>")?;
+        writeln!(writer, "<html lang=\"en\">")?;
+        writeln!(writer, "<head>")?;
+        writeln!(writer, "<meta charset=\"utf-8\">")?;
+        writeln!(writer, "<meta name=\"viewport\" content=\"width=device-width, initial-scale=1\">")?;
+        writeln!(writer, "<title>Mago report</title>")?;
+        writeln!(writer, "<style>{STYLE}</style>")?;
+        writeln!(writer, "</head>")?;
+        writeln!(writer, "<body>")?;
+        writeln!(writer, "<h1>Mago report</h1>")?;
+
+        let total = issues.len();
+        let level_counts: Vec<String> = LEVELS
+            .iter()
+            .map(|level| (level, issues.iter().filter(|issue| issue.level == *level).count()))
+            .filter(|(_, count)| *count > 0)
+            .map(|(level, count)| format!("{count} {level}"))
+            .collect();
+
+        write!(writer, "<p class=\"muted\">Showing <span id=\"shown\">{total}</span> of {total} issues")?;
+        if !level_counts.is_empty() {
+            write!(writer, " ({})", level_counts.join(", "))?;
+        }
+
+        writeln!(writer, " in {} files.</p>", files.len())?;
+
+        writeln!(writer, "<div class=\"filters\">")?;
+        for level in LEVELS {
+            writeln!(
+                writer,
+                "<label><input type=\"checkbox\" data-filter-level value=\"{level}\" checked> {level}</label>"
+            )?;
+        }
+
+        writeln!(writer, "<label>Code <select id=\"filter-code\"><option value=\"\">All codes</option>")?;
+        for code in codes.keys() {
+            writeln!(writer, "<option value=\"{0}\">{0}</option>", xml_encode(code))?;
+        }
+
+        writeln!(writer, "</select></label>")?;
+        writeln!(writer, "<label>Category <select id=\"filter-category\"><option value=\"\">All categories</option>")?;
+        for category in &categories {
+            writeln!(writer, "<option value=\"{0}\">{0}</option>", xml_encode(category))?;
+        }
+
+        writeln!(writer, "</select></label>")?;
+        writeln!(writer, "</div>")?;
+
+        if total == 0 {
+            writeln!(writer, "<p>No issues found.</p>")?;
+        } else {
+            write_summary(writer, &codes)?;
+        }
+
+        writeln!(writer, "<section id=\"files\">")?;
+        for (file_index, (file, indices)) in files.values().enumerate() {
+            write_file(writer, file_index, file, &issues, indices, database, config)?;
+        }
+
+        if !unlocated.is_empty() {
+            writeln!(writer, "<section data-group>")?;
+            writeln!(writer, "<h2>Other issues</h2>")?;
+            for index in unlocated {
+                write_issue(writer, index, issues[index], database, config)?;
+            }
+
+            writeln!(writer, "</section>")?;
+        }
+
+        writeln!(writer, "</section>")?;
+        writeln!(writer, "<script>{SCRIPT}</script>")?;
+        writeln!(writer, "</body>")?;
+        writeln!(writer, "</html>")?;
+
+        Ok(())
+    }
+}
+
+/// Writes the chart of the number of issues per rule, largest first.
+fn write_summary(writer: &mut dyn Write, codes: &BTreeMap<&str, usize>) -> Result<(), ReportingError> {
+    let mut codes: Vec<(&str, usize)> = codes.iter().map(|(code, count)| (*code, *count)).collect();
+    codes.sort_by(|a, b| b.1.cmp(&a.1).then_with(|| a.0.cmp(b.0)));
+    let max = codes.first().map_or(1, |(_, count)| *count);
+
+    writeln!(writer, "<h2>Issues per rule</h2>")?;
+    writeln!(writer, "<table class=\"chart\">")?;
+    for (code, count) in codes {
+        let code = xml_encode(code);
+        let width = count * 100 / max;
+
+        writeln!(
+            writer,
+            "<tr><td><button type=\"button\" data-select-code=\"{code}\">{code}</button></td><td><span class=\"bar\" style=\"width: {width}%\"></span></td><td>{count}</td></tr>"
+        )?;
+    }
+
+    writeln!(writer, "</table>")?;
+
+    Ok(())
+}
+
+/// Writes a file: the lines around its annotations, each followed by the issues reported on it.
+fn write_file(
+    writer: &mut dyn Write,
+    file_index: usize,
+    file: &File,
+    issues: &[&Issue],
+    indices: &[usize],
+    database: &ReadDatabase,
+    config: &FormatterConfig,
+) -> Result<(), ReportingError> {
+    let mut marks = Vec::new();
+    let mut issues_by_line: BTreeMap<u32, Vec<usize>> = BTreeMap::new();
+    let mut annotations_by_line: BTreeMap<u32, Vec<(usize, &str)>> = BTreeMap::new();
+    let mut lines: BTreeSet<u32> = BTreeSet::new();
+    let last_line = (file.lines.len() as u32).saturating_sub(1);
+
+    for &index in indices {
+        let issue = issues[index];
+        for annotation in issue.annotations.iter().filter(|annotation| annotation.span.file_id == file.id) {
+            let start = annotation.span.start.offset.min(file.size);
+            let end = annotation.span.end.offset.clamp(start, file.size);
+            let start_line = file.line_number(start);
+            let end_line = file.line_number(end);
+
+            marks.push(Mark { start, end, primary: annotation.kind.is_primary(), issue: index });
+            lines.extend(start_line.saturating_sub(CONTEXT_LINES)..=(end_line + CONTEXT_LINES).min(last_line));
+
+            match annotation.kind {
+                AnnotationKind::Primary if issue.primary_annotation() == Some(annotation) => {
+                    issues_by_line.entry(start_line).or_default().push(index);
+                }
+                _ => {
+                    if let Some(message) = annotation.message.as_deref() {
+                        annotations_by_line.entry(start_line).or_default().push((index, message));
+                    }
+                }
+            }
+        }
+    }
+
+    let highlights = config.source_highlighter.map(|highlight| highlight(&file.contents)).unwrap_or_default();
+
+    writeln!(writer, "<details class=\"file\" id=\"file-{file_index}\" data-group open>")?;
+    writeln!(
+        writer,
+        "<summary>{} <span class=\"muted\">({} issues)</span></summary>",
+        xml_encode(String::from_utf8_lossy(&file.name)),
+        indices.len()
+    )?;
+    writeln!(writer, "<table class=\"source\">")?;
+
+    let mut previous_line = None;
+    for line in lines {
+        if previous_line.is_some_and(|previous| previous + 1 != line) || (previous_line.is_none() && line > 0) {
+            writeln!(writer, "<tr class=\"gap\"><td class=\"line-number\">&#8943;</td><td></td></tr>")?;
+        }
+
+        previous_line = Some(line);
+
+        write!(
+            writer,
+            "<tr id=\"file-{file_index}-L{0}\"><td class=\"line-number\">{0}</td><td class=\"code\">",
+            line + 1
+        )?;
+        write_line(writer, file, line, &highlights, &marks)?;
+        writeln!(writer, "</td></tr>")?;
+
+        for &index in issues_by_line.get(&line).into_iter().flatten() {
+            write!(writer, "<tr><td></td><td>")?;
+            write_issue(writer, index, issues[index], database, config)?;
+            writeln!(writer, "</td></tr>")?;
+        }
+
+        for (index, message) in annotations_by_line.get(&line).into_iter().flatten() {
+            writeln!(
+                writer,
+                "<tr data-of=\"{index}\"><td></td><td class=\"annotation\">&#8627; {}</td></tr>",
+                xml_encode(message)
+            )?;
+        }
+    }
+
+    if previous_line.is_some_and(|previous| previous < last_line) {
+        writeln!(writer, "<tr class=\"gap\"><td class=\"line-number\">&#8943;</td><td></td></tr>")?;
+    }
+
+    writeln!(writer, "</table>")?;
+    writeln!(writer, "</details>")?;
+
+    Ok(())
+}
+
+/// Writes a source line, split into highlighted fragments and underlined where annotated.
+fn write_line(
+    writer: &mut dyn Write,
+    file: &File,
+    line: u32,
+    highlights: &[Highlight],
+    marks: &[Mark],
+) -> Result<(), ReportingError> {
+    let Some(start) = file.get_line_start_offset(line) else {
+        return Ok(());
+    };
+
+    let mut end = file.get_line_end_offset(line).unwrap_or(file.size).max(start);
+    if end > start && file.contents[end as usize - 1] == b'\r' {
+        end -= 1;
+    }
+
+    let first_highlight = highlights.partition_point(|highlight| highlight.end <= start);
+    let line_highlights: Vec<&Highlight> =
+        highlights[first_highlight..].iter().take_while(|highlight| highlight.start < end).collect();
+
+    let mut boundaries = vec![start, end];
+    for highlight in &line_highlights {
+        boundaries.push(highlight.start.clamp(start, end));
+        boundaries.push(highlight.end.clamp(start, end));
+    }
+
+    for mark in marks.iter().filter(|mark| mark.start <= end && mark.end >= start) {
+        boundaries.push(mark.start.clamp(start, end));
+        boundaries.push(mark.end.clamp(start, end));
+    }
+
+    boundaries.sort_unstable();
+    boundaries.dedup();
+
+    for (position, &offset) in boundaries.iter().enumerate() {
+        for mark in marks.iter().filter(|mark| mark.start == offset && mark.end == offset) {
+            write!(writer, "<span class=\"mark caret on\" data-of=\"{}\"></span>", mark.issue)?;
+        }
+
+        let Some(&next) = boundaries.get(position + 1) else {
+            break;
+        };
+
+        let text = xml_encode(String::from_utf8_lossy(&file.contents[offset as usize..next as usize]));
+        let text = match line_highlights.iter().find(|highlight| highlight.start <= offset && offset < highlight.end) {
+            Some(highlight) => format!("<span class=\"{}\">{text}</span>", highlight.kind.as_str()),
+            None => text,
+        };
+
+        let covering: Vec<&Mark> = marks.iter().filter(|mark| mark.start <= offset && next <= mark.end).collect();
+        if covering.is_empty() {
+            write!(writer, "{text}")?;
+            continue;
+        }
+
+        let primary = if covering.iter().any(|mark| mark.primary) { " primary" } else { "" };
+        let of: BTreeSet<usize> = covering.iter().map(|mark| mark.issue).collect();
+        let of: Vec<String> = of.iter().map(ToString::to_string).collect();
+
+        write!(writer, "<span class=\"mark{primary} on\" data-of=\"{}\">{text}</span>", of.join(" "))?;
+    }
+
+    Ok(())
+}
+
+/// Writes an issue with its notes, help, link and the diff of its fix.
+fn write_issue(
+    writer: &mut dyn Write,
+    index: usize,
+    issue: &Issue,
+    database: &ReadDatabase,
+    config: &FormatterConfig,
+) -> Result<(), ReportingError> {
+    let level = issue.level;
+    let code = xml_encode(issue.code.as_deref().unwrap_or("other"));
+    let category = xml_encode(issue_category(issue, config).unwrap_or_default());
+
+    writeln!(
+        writer,
+        "<div class=\"issue {level}\" id=\"issue-{index}\" data-issue=\"{index}\" data-level=\"{level}\" data-code=\"{code}\" data-category=\"{category}\">"
+    )?;
+    writeln!(
+        writer,
+        "<div><span class=\"level {level}\">{level}</span><code>{code}</code> <a class=\"muted\" href=\"#issue-{index}\">#</a></div>"
+    )?;
+    writeln!(writer, "<div class=\"message\">{}</div>", xml_encode(&issue.message))?;
+
+    if let Some(message) = issue.primary_annotation().and_then(|annotation| annotation.message.as_deref()) {
+        writeln!(writer, "<div class=\"annotation\">{}</div>", xml_encode(message))?;
+    }
+
+    if !issue.notes.is_empty() {
+        writeln!(writer, "<ul>")?;
+        for note in &issue.notes {
+            writeln!(writer, "<li>{}</li>", xml_encode(note))?;
+        }
+
+        writeln!(writer, "</ul>")?;
+    }
+
+    if let Some(help) = &issue.help {
+        writeln!(writer, "<div><strong>Help:</strong> {}</div>", xml_encode(help))?;
+    }
+
+    // Only web links are rendered as links, so that a crafted issue cannot run script.
+    if let Some(link) = issue.link.as_deref().filter(|link| link.starts_with("https://") || link.starts_with("http://"))
+    {
+        writeln!(writer, "<div><a href=\"{0}\">{0}</a></div>", xml_encode(link))?;
+    }
+
+    write_fix(writer, issue, database)?;
+
+    writeln!(writer, "</div>")?;
+
+    Ok(())
+}
+
+/// Writes the diff of the issue's fix, per file.
+fn write_fix(writer: &mut dyn Write, issue: &Issue, database: &ReadDatabase) -> Result<(), ReportingError> {
+    let mut edits: Vec<_> = issue
+        .edits
+        .iter()
+        .filter(|(_, edits)| !edits.is_empty())
+        .filter_map(|(file_id, edits)| database.get_ref(file_id).ok().map(|file| (file, edits)))
+        .collect();
+
+    if edits.is_empty() {
+        return Ok(());
+    }
+
+    edits.sort_by(|a, b| a.0.name.cmp(&b.0.name));
+
+    let safety = match edits.iter().flat_map(|(_, edits)| edits.iter()).map(|edit| edit.safety).max() {
+        Some(Safety::Safe) | None => "",
+        Some(Safety::PotentiallyUnsafe) => " (potentially unsafe)",
+        Some(_) => " (unsafe)",
+    };
+
+    writeln!(writer, "<details><summary>Suggested fix{safety}</summary>")?;
+    for (file, edits) in edits {
+        let mut editor = TextEditor::new(&file.contents);
+        if editor.apply_batch(edits.clone(), None::<fn(&[u8]) -> bool>) != ApplyResult::Applied {
+            continue;
+        }
+
+        let original = String::from_utf8_lossy(&file.contents);
+        let modified = editor.finish();
+        let modified = String::from_utf8_lossy(&modified);
+        let patch = diffy::create_patch(&original, &modified);
+
+        writeln!(writer, "<pre class=\"diff\"><strong>{}</strong>", xml_encode(String::from_utf8_lossy(&file.name)))?;
+        for hunk in patch.hunks() {
+            write!(writer, "<span class=\"hunk\">@@ -{} +{} @@</span>", hunk.old_range(), hunk.new_range())?;
+            for line in hunk.lines() {
+                let (class, prefix, text) = match line {
+                    diffy::Line::Context(text) => ("context", ' ', text),
+                    diffy::Line::Delete(text) => ("delete", '-', text),
+                    diffy::Line::Insert(text) => ("insert", '+', text),
+                };
+
+                let text = text.strip_suffix('\n').unwrap_or(text);
+                let text = text.strip_suffix('\r').unwrap_or(text);
+                if class == "context" {
+                    writeln!(writer, "{prefix}{}", xml_encode(text))?;
+                } else {
+                    write!(writer, "<span class=\"{class}\">{prefix}{}</span>", xml_encode(text))?;
+                }
+            }
+        }
+
+        writeln!(writer, "</pre>")?;
+    }
+
+    writeln!(writer, "</details>")?;
+
+    Ok(())
+}
@@ -0,0 +1,88 @@
+use std::collections::BTreeMap;
+use std::io::Write;
+
+use mago_database::DatabaseReader;
+use mago_database::ReadDatabase;
+use mago_database::file::FileType;
+use mago_database::file::HasFileId;
+
+use crate::IssueCollection;
+use crate::error::ReportingError;
+use crate::formatter::Formatter;
+use crate::formatter::FormatterConfig;
+use crate::formatter::utils::long_message;
+use crate::formatter::utils::xml_encode;
+
+/// Formatter that outputs issues in JUnit XML format.
+///
+/// Each host file is a test case, and each issue in it is a failure of that test case,
+/// so that files without issues show up as passing tests in test-result dashboards.
+pub(crate) struct JunitFormatter;
+
+impl Formatter for JunitFormatter {
+    fn format(
+        &self,
+        writer: &mut dyn Write,
+        issues: &IssueCollection,
+        database: &ReadDatabase,
+        config: &FormatterConfig,
+    ) -> Result<(), ReportingError> {
+        let mut failures_by_file: BTreeMap<String, Vec<String>> = database
+            .files_with_type(FileType::Host)
+            .map(|file| (String::from_utf8_lossy(&file.name).into_owned(), Vec::new()))
+            .collect();
+
+        for issue in crate::formatter::utils::filter_issues(issues, config, false) {
+            let (filename, line, column) = match issue.primary_annotation() {
+                Some(annotation) => {
+                    let file = database.get(&annotation.span.file_id())?;
+
+                    let line = file.line_number(annotation.span.start.offset) + 1;
+                    let column = file.column_number(annotation.span.start.offset) + 1;
+
+                    (String::from_utf8_lossy(&file.name).into_owned(), line, column)
+                }
+                None => ("<unknown>".to_string(), 0, 0),
+            };
+
+            let code = issue.code.as_deref().unwrap_or("other");
+            let details = format!("{filename}:{line}:{column}: {}[{code}]: {}", issue.level, long_message(issue, true));
+
+            let failure_tag = format!(
+                "      <failure type=\"{}\" message=\"{}\">{}</failure>",
+                xml_encode(code),
+                xml_encode(&issue.message),
+                xml_encode(details),
+            );
+
+            failures_by_file.entry(filename).or_default().push(failure_tag);
+        }
+
+        let tests = failures_by_file.len();
+        let failures = failures_by_file.values().filter(|failures| !failures.is_empty()).count();
+
+        writeln!(writer, "<?xml version=\"1.0\" encoding=\"UTF-8\"?>")?;
+        writeln!(writer, "<testsuites name=\"mago\" tests=\"{tests}\" failures=\"{failures}\" errors=\"0\">")?;
+        writeln!(writer, "  <testsuite name=\"mago\" tests=\"{tests}\" failures=\"{failures}\" errors=\"0\">")?;
+
+        for (filename, failures) in failures_by_file {
+            let filename = xml_encode(&filename);
+            if failures.is_empty() {
+                writeln!(writer, "    <testcase name=\"{filename}\" classname=\"mago\" />")?;
+                continue;
+            }
+
+            writeln!(writer, "    <testcase name=\"{filename}\" classname=\"mago\">")?;
+            for failure in failures {
+                writeln!(writer, "{failure}")?;
+            }
+
+            writeln!(writer, "    </testcase>")?;
+        }
+
+        writeln!(writer, "  </testsuite>")?;
+        writeln!(writer, "</testsuites>")?;
+
+        Ok(())
+    }
+}
@@ -1,3 +1,4 @@
+use std::collections::HashMap;
 use std::io::Write;
 
 use mago_database::ReadDatabase;
@@ -6,22 +7,29 @@ use crate::IssueCollection;
 use crate::Level;
 use crate::color::ColorChoice;
 use crate::error::ReportingError;
+use crate::highlight::SourceHighlighter;
 
 pub mod ariadne;
 pub mod checkstyle;
+#[cfg(feature = "serde")]
+pub mod code_climate;
 pub mod code_count;
 pub mod count;
 pub mod emacs;
 pub mod github;
 #[cfg(feature = "serde")]
 pub mod gitlab;
+pub mod html;
 #[cfg(feature = "serde")]
 pub mod json;
+pub mod junit;
 pub mod medium;
 pub mod rich;
 #[cfg(feature = "serde")]
 pub mod sarif;
 pub mod short;
+#[cfg(feature = "serde")]
+pub mod sonarqube;
 pub mod utils;
 
 /// Configuration for formatters.
@@ -40,6 +48,15 @@ pub struct FormatterConfig {
     /// Supported placeholders: `%file%` (absolute path), `%line%`, `%column%`.
     /// Example: `"phpstorm://open?file=%file%&line=%line%"`
     pub editor_url: Option<String>,
+    /// The category of each issue code, such as the category of a linter rule.
+    ///
+    /// Used by formats that classify issues, such as `code-climate` and `sonarqube`.
+    /// Issues whose code has no category are classified by their level.
+    pub issue_categories: HashMap<String, String>,
+    /// Highlighter for formats that show source code as markup, such as `html`.
+    ///
+    /// Source code is shown without highlighting when not set.
+    pub source_highlighter: Option<SourceHighlighter>,
 }
 
 /// Trait for formatting issues to a writer.
@@ -97,15 +114,25 @@ pub enum ReportingFormat {
     /// SARIF format (Static Analysis Results Interchange Format).
     #[cfg(feature = "serde")]
     Sarif,
+    /// SonarQube generic issue import format.
+    #[cfg(feature = "serde")]
+    Sonarqube,
+    /// JUnit XML format, with a test case per file and a failure per issue.
+    Junit,
+    /// Code Climate JSON format.
+    #[cfg(feature = "serde")]
+    CodeClimate,
+    /// Self-contained HTML report, with the source code of each file.
+    Html,
 }
 
 impl ReportingFormat {
     #[must_use]
     pub const fn requires_output_when_empty(self) -> bool {
         match self {
-            Self::Checkstyle => true,
+            Self::Checkstyle | Self::Junit | Self::Html => true,
             #[cfg(feature = "serde")]
-            Self::Gitlab | Self::Json | Self::Sarif => true,
+            Self::Gitlab | Self::Json | Self::Sarif | Self::Sonarqube | Self::CodeClimate => true,
             _ => false,
         }
     }
@@ -137,5 +164,11 @@ pub(crate) fn dispatch_format(
         ReportingFormat::CodeCount => code_count::CodeCountFormatter.format(writer, issues, database, config),
         #[cfg(feature = "serde")]
         ReportingFormat::Sarif => sarif::SarifFormatter.format(writer, issues, database, config),
+        #[cfg(feature = "serde")]
+        ReportingFormat::Sonarqube => sonarqube::SonarQubeFormatter.format(writer, issues, database, config),
+        ReportingFormat::Junit => junit::JunitFormatter.format(writer, issues, database, config),
+        #[cfg(feature = "serde")]
+        ReportingFormat::CodeClimate => code_climate::CodeClimateFormatter.format(writer, issues, database, config),
+        ReportingFormat::Html => html::HtmlFormatter.format(writer, issues, database, config),
     }
 }
@@ -0,0 +1,138 @@
+use std::io::Write;
+
+use mago_database::DatabaseReader;
+use mago_database::ReadDatabase;
+use mago_database::file::HasFileId;
+
+use crate::Annotation;
+use crate::IssueCollection;
+use crate::Level;
+use crate::error::ReportingError;
+use crate::formatter::Formatter;
+use crate::formatter::FormatterConfig;
+use crate::formatter::utils::issue_category;
+
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+struct GenericIssueReport<'issue> {
+    issues: Vec<GenericIssue<'issue>>,
+}
+
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "camelCase"))]
+struct GenericIssue<'issue> {
+    engine_id: &'static str,
+    rule_id: &'issue str,
+    severity: &'static str,
+    #[cfg_attr(feature = "serde", serde(rename = "type"))]
+    kind: &'static str,
+    primary_location: Location,
+    #[cfg_attr(feature = "serde", serde(skip_serializing_if = "Vec::is_empty"))]
+    secondary_locations: Vec<Location>,
+}
+
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "camelCase"))]
+struct Location {
+    message: String,
+    file_path: String,
+    text_range: TextRange,
+}
+
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "camelCase"))]
+struct TextRange {
+    start_line: u32,
+    #[cfg_attr(feature = "serde", serde(skip_serializing_if = "Option::is_none"))]
+    end_line: Option<u32>,
+    #[cfg_attr(feature = "serde", serde(skip_serializing_if = "Option::is_none"))]
+    start_column: Option<u32>,
+    #[cfg_attr(feature = "serde", serde(skip_serializing_if = "Option::is_none"))]
+    end_column: Option<u32>,
+}
+
+/// Formatter that outputs issues in the SonarQube generic issue import format.
+///
+/// Issues are imported as external issues of the `mago` engine. Their type is derived
+/// from the category of the issue code, such as the category of a linter rule, and falls
+/// back to the issue level.
+pub(crate) struct SonarQubeFormatter;
+
+impl Formatter for SonarQubeFormatter {
+    fn format(
+        &self,
+        writer: &mut dyn Write,
+        issues: &IssueCollection,
+        database: &ReadDatabase,
+        config: &FormatterConfig,
+    ) -> Result<(), ReportingError> {
+        let mut generic_issues = Vec::new();
+
+        for issue in crate::formatter::utils::filter_issues(issues, config, false) {
+            let Some(primary_location) =
+                issue.primary_annotation().and_then(|annotation| location(database, annotation, issue.message.clone()))
+            else {
+                // SonarQube attaches every issue to a file, so issues without one cannot be imported.
+                continue;
+            };
+
+            let secondary_locations = issue
+                .annotations
+                .iter()
+                .filter(|annotation| !annotation.is_primary())
+                .filter_map(|annotation| location(database, annotation, annotation.message.clone().unwrap_or_default()))
+                .collect();
+
+            let severity = match issue.level {
+                Level::Error => "CRITICAL",
+                Level::Warning => "MAJOR",
+                Level::Help => "MINOR",
+                Level::Note => "INFO",
+            };
+
+            let kind = match issue_category(issue, config) {
+                Some("Security") => "VULNERABILITY",
+                Some("Correctness" | "Safety") => "BUG",
+                Some(_) => "CODE_SMELL",
+                None if issue.level == Level::Error => "BUG",
+                None => "CODE_SMELL",
+            };
+
+            generic_issues.push(GenericIssue {
+                engine_id: "mago",
+                rule_id: issue.code.as_deref().unwrap_or("other"),
+                severity,
+                kind,
+                primary_location,
+                secondary_locations,
+            });
+        }
+
+        serde_json::to_writer_pretty(writer, &GenericIssueReport { issues: generic_issues })?;
+
+        Ok(())
+    }
+}
+
+/// Builds the location of an annotation; SonarQube lines are 1-based and columns 0-based.
+fn location(database: &ReadDatabase, annotation: &Annotation, message: String) -> Option<Location> {
+    let file = database.get(&annotation.span.file_id()).ok()?;
+
+    let start_line = file.line_number(annotation.span.start.offset) + 1;
+    let end_line = file.line_number(annotation.span.end.offset) + 1;
+    let start_column = file.column_number(annotation.span.start.offset);
+    let end_column = file.column_number(annotation.span.end.offset);
+
+    // SonarQube rejects empty ranges, so those are reported on their whole line.
+    let text_range = if start_line == end_line && start_column == end_column {
+        TextRange { start_line, end_line: None, start_column: None, end_column: None }
+    } else {
+        TextRange {
+            start_line,
+            end_line: Some(end_line),
+            start_column: Some(start_column),
+            end_column: Some(end_column),
+        }
+    };
+
+    Some(Location { message, file_path: String::from_utf8_lossy(&file.name).into_owned(), text_range })
+}
@@ -1,6 +1,10 @@
 use std::borrow::Cow;
 use std::cmp::Ordering;
 
+use mago_database::DatabaseReader;
+use mago_database::ReadDatabase;
+use mago_database::file::HasFileId;
+
 use crate::Issue;
 use crate::IssueCollection;
 use crate::Level;
@@ -62,8 +66,8 @@ impl<'issues> Iterator for FilteredIssues<'issues> {
 ///   `--sort` should produce a stable, severity-ordered view of issues.
 /// * Pass `false` for formats that already aggregate (`count`, `code-count`)
 ///   or whose consumers do their own ordering (`github`, `gitlab`, `sarif`,
-///   `checkstyle`, `emacs`). For these, even if the user passed `--sort`,
-///   sorting is wasted work.
+///   `checkstyle`, `emacs`, `sonarqube`, `junit`, `code-climate`). For these,
+///   even if the user passed `--sort`, sorting is wasted work.
 #[inline]
 pub fn filter_issues<'issues>(
     issues: &'issues IssueCollection,
@@ -133,6 +137,36 @@ fn compare_issues(a: &&Issue, b: &&Issue) -> Ordering {
     }
 }
 
+/// Returns a stable fingerprint that code quality reports use to track an issue across runs.
+///
+/// The fingerprint hashes the issue code and message, the file, and the source covered by the
+/// primary annotation with its whitespace collapsed, so it survives edits that only move the
+/// issue to another line or re-indent it.
+pub(crate) fn fingerprint(issue: &Issue, database: &ReadDatabase) -> String {
+    let file = issue
+        .primary_annotation()
+        .and_then(|annotation| database.get(&annotation.span.file_id()).ok().map(|file| (file, annotation.span)));
+
+    let mut hasher = blake3::Hasher::new();
+    hasher.update(issue.code.as_deref().unwrap_or("other").as_bytes());
+    hasher.update(b"\0");
+    hasher.update(issue.message.as_bytes());
+    hasher.update(b"\0");
+
+    if let Some((file, span)) = file {
+        hasher.update(&file.name);
+        hasher.update(b"\0");
+
+        let snippet = file.contents.get(span.start.offset as usize..span.end.offset as usize).unwrap_or_default();
+        for word in snippet.split(u8::is_ascii_whitespace).filter(|word| !word.is_empty()) {
+            hasher.update(word);
+            hasher.update(b" ");
+        }
+    }
+
+    hasher.finalize().to_hex()[..32].to_string()
+}
+
 /// XML-encode a string by escaping special characters.
 pub fn xml_encode(input: impl AsRef<str>) -> String {
     let input = input.as_ref();
@@ -174,6 +208,12 @@ fn is_xml_1_0_character(character: char) -> bool {
     )
 }
 
+/// Returns the category of the issue's code, if it has one.
+#[must_use]
+pub fn issue_category<'config>(issue: &Issue, config: &'config FormatterConfig) -> Option<&'config str> {
+    config.issue_categories.get(issue.code.as_deref()?).map(String::as_str)
+}
+
 /// Build a long message from an issue including notes, help, and links.
 pub fn long_message(issue: &Issue, include_annotations: bool) -> String {
     let mut message = issue.message.clone();
@@ -244,10 +284,50 @@ fn strip_windows_verbatim_prefix(path: &str) -> std::borrow::Cow<'_, str> {
 
 #[cfg(test)]
 mod tests {
+    use std::borrow::Cow;
+
+    use mago_database::ReadDatabase;
+    use mago_database::file::File;
+    use mago_span::Span;
+
+    use crate::Annotation;
+    use crate::Issue;
+
+    use super::fingerprint;
     use super::strip_windows_verbatim_prefix;
     use super::utf8_preserving_byte_offsets;
     use super::xml_encode;
 
+    fn fingerprint_of(source: &'static [u8], needle: &[u8]) -> String {
+        let file = File::ephemeral(Cow::Borrowed(b"src/Foo.php"), Cow::Borrowed(source));
+        let Some(start) = source.windows(needle.len()).position(|window| window == needle) else {
+            panic!("the needle should be part of the source");
+        };
+
+        let span = Span::new(file.id, (start as u32).into(), ((start + needle.len()) as u32).into());
+        let issue = Issue::error("Undefined variable.")
+            .with_code("undefined-variable")
+            .with_annotation(Annotation::primary(span));
+
+        fingerprint(&issue, &ReadDatabase::single(file))
+    }
+
+    #[test]
+    fn fingerprint_survives_moved_and_reindented_code() {
+        let original = fingerprint_of(b"<?php\n\necho $foo;\n", b"echo $foo;");
+        let moved = fingerprint_of(b"<?php\n\n$bar = 1;\n\n    echo   $foo;\n", b"echo   $foo;");
+
+        assert_eq!(original, moved);
+    }
+
+    #[test]
+    fn fingerprint_changes_with_the_source() {
+        let original = fingerprint_of(b"<?php\n\necho $foo;\n", b"echo $foo;");
+        let changed = fingerprint_of(b"<?php\n\nprint $foo;\n", b"print $foo;");
+
+        assert_ne!(original, changed);
+    }
+
     #[test]
     fn preserves_byte_offsets_when_replacing_invalid_utf8() {
         let bytes = b"valid \xc2\xa9 invalid \xa9 truncated \xf0\x9f\nnext";
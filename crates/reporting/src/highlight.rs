@@ -0,0 +1,66 @@
+//! Syntax highlighting of source code shown in reports.
+//!
+//! This crate does not depend on a parser, so formats that show highlighted source code,
+//! such as `html`, are given a [`SourceHighlighter`] that splits source code into
+//! highlighted fragments.
+
+/// The kind of a highlighted fragment of source code.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum HighlightKind {
+    /// A keyword, such as `function`, `return` or `(int)`.
+    Keyword,
+    /// A variable, such as `$name`.
+    Variable,
+    /// A string literal, or a part of an interpolated string or heredoc.
+    String,
+    /// An integer or float literal.
+    Number,
+    /// A constant built into the language, such as `true`, `null` or `__DIR__`.
+    Constant,
+    /// A comment or docblock.
+    Comment,
+    /// A name, such as the name of a class, function or constant.
+    Name,
+    /// An operator or punctuation.
+    Operator,
+    /// An opening or closing tag, such as `<?php` or `?>`.
+    Tag,
+    /// The start of an attribute, `#[`.
+    Attribute,
+}
+
+/// A highlighted fragment of source code, as a range of byte offsets.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Highlight {
+    /// The byte offset the fragment starts at.
+    pub start: u32,
+    /// The byte offset the fragment ends at, exclusive.
+    pub end: u32,
+    /// The kind of the fragment.
+    pub kind: HighlightKind,
+}
+
+/// Splits source code into highlighted fragments.
+///
+/// Fragments are sorted and do not overlap; source code outside of any fragment is not
+/// highlighted.
+pub type SourceHighlighter = fn(&[u8]) -> Vec<Highlight>;
+
+impl HighlightKind {
+    /// Returns the short name used for this kind, such as in CSS classes.
+    #[must_use]
+    pub const fn as_str(self) -> &'static str {
+        match self {
+            Self::Keyword => "keyword",
+            Self::Variable => "variable",
+            Self::String => "string",
+            Self::Number => "number",
+            Self::Constant => "constant",
+            Self::Comment => "comment",
+            Self::Name => "name",
+            Self::Operator => "operator",
+            Self::Tag => "tag",
+            Self::Attribute => "attribute",
+        }
+    }
+}
@@ -36,6 +36,7 @@ mod internal;
 pub mod baseline;
 pub mod color;
 pub mod error;
+pub mod highlight;
 pub mod output;
 pub mod reporter;
 
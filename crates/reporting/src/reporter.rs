@@ -8,6 +8,7 @@
 //! The reporter can filter issues based on baseline files and severity levels,
 //! and can sort issues for better readability.
 
+use std::collections::HashMap;
 use std::io::Write;
 
 use mago_database::ReadDatabase;
@@ -15,11 +16,13 @@ use mago_database::ReadDatabase;
 use crate::IssueCollection;
 use crate::Level;
 use crate::baseline::Baseline;
+use crate::baseline::IssueLocations;
 use crate::color::ColorChoice;
 use crate::error::ReportingError;
 use crate::formatter::FormatterConfig;
 use crate::formatter::ReportingFormat;
 use crate::formatter::dispatch_format;
+use crate::highlight::SourceHighlighter;
 use crate::output::ReportingTarget;
 
 /// Configuration options for the reporter.
@@ -59,6 +62,14 @@ pub struct ReporterConfig {
     /// Supported placeholders: `%file%` (absolute path), `%line%`, `%column%`.
     /// Example: `"phpstorm://open?file=%file%&line=%line%"`
     pub editor_url: Option<String>,
+
+    /// The category of each issue code, such as the category of a linter rule.
+    ///
+    /// Used by formats that classify issues, such as `code-climate` and `sonarqube`.
+    pub issue_categories: HashMap<String, String>,
+
+    /// Highlighter for formats that show source code as markup, such as `html`.
+    pub source_highlighter: Option<SourceHighlighter>,
 }
 
 /// Status information returned after reporting issues.
@@ -93,13 +104,21 @@ pub struct ReportStatus {
 pub struct Reporter {
     database: ReadDatabase,
     config: ReporterConfig,
+    issue_locations: IssueLocations,
 }
 
 impl Reporter {
     /// Create a new reporter with the given database and configuration.
     #[must_use]
     pub fn new(database: ReadDatabase, config: ReporterConfig) -> Self {
-        Self { database, config }
+        Self { database, config, issue_locations: IssueLocations::default() }
+    }
+
+    /// Sets the locations of the issues, read when filtering against a fingerprint baseline.
+    #[must_use]
+    pub fn with_issue_locations(mut self, issue_locations: IssueLocations) -> Self {
+        self.issue_locations = issue_locations;
+        self
     }
 
     /// Report issues to the configured target.
@@ -122,8 +141,8 @@ impl Reporter {
         let mut baseline_filtered_issues = 0;
         if let Some(baseline) = baseline {
             let original_count = issues.len();
-            let comparison = baseline.compare_with_issues(&issues, &self.database);
-            let filtered_issues = baseline.filter_issues(issues, &self.database);
+            let comparison = baseline.compare_with_issues(&issues, &self.database, &self.issue_locations);
+            let filtered_issues = baseline.filter_issues(issues, &self.database, &self.issue_locations);
 
             baseline_filtered_issues = original_count - filtered_issues.len();
             baseline_dead_issues = comparison.removed_issues.len();
@@ -153,6 +172,8 @@ impl Reporter {
             minimum_level: self.config.minimum_report_level,
             filter_fixable: self.config.filter_fixable,
             editor_url: self.config.editor_url.clone(),
+            issue_categories: self.config.issue_categories.clone(),
+            source_highlighter: self.config.source_highlighter,
         };
 
         // Dispatch to the appropriate formatter
@@ -205,8 +226,8 @@ impl Reporter {
         let mut baseline_filtered_issues = 0;
         if let Some(baseline) = baseline {
             let original_count = issues.len();
-            let comparison = baseline.compare_with_issues(&issues, &self.database);
-            let filtered_issues = baseline.filter_issues(issues, &self.database);
+            let comparison = baseline.compare_with_issues(&issues, &self.database, &self.issue_locations);
+            let filtered_issues = baseline.filter_issues(issues, &self.database, &self.issue_locations);
 
             baseline_filtered_issues = original_count - filtered_issues.len();
             baseline_dead_issues = comparison.removed_issues.len();
@@ -236,6 +257,8 @@ impl Reporter {
             minimum_level: self.config.minimum_report_level,
             filter_fixable: self.config.filter_fixable,
             editor_url: self.config.editor_url.clone(),
+            issue_categories: self.config.issue_categories.clone(),
+            source_highlighter: self.config.source_highlighter,
         };
 
         // Dispatch to the appropriate formatter
@@ -259,6 +282,7 @@ mod tests {
     use mago_database::DatabaseConfiguration;
     use mago_database::file::File;
     use mago_span::Span;
+    use mago_text_edit::TextEdit;
 
     use crate::Annotation;
     use crate::Issue;
@@ -280,6 +304,8 @@ mod tests {
                 sort: false,
                 minimum_report_level: None,
                 editor_url: None,
+                issue_categories: HashMap::new(),
+                source_highlighter: None,
             },
         )
     }
@@ -456,4 +482,152 @@ mod tests {
 
         assert_eq!(value[0]["description"], "message\0\u{1}\r\n</error>");
     }
+
+    #[test]
+    fn junit_writes_passing_test_cases_when_empty() {
+        let output = String::from_utf8_lossy(&report_empty(ReportingFormat::Junit)).into_owned();
+
+        assert!(output.contains("<testsuites name=\"mago\" tests=\"1\" failures=\"0\" errors=\"0\">"));
+        assert!(output.contains("<testcase name=\"test.php\" classname=\"mago\" />"));
+    }
+
+    #[test]
+    fn junit_escapes_hostile_values_as_valid_xml_text() {
+        let Ok(output) = String::from_utf8(report_hostile_values(ReportingFormat::Junit)) else {
+            panic!("JUnit output should be UTF-8");
+        };
+
+        assert!(!output.contains('\0'));
+        assert!(output.contains("failures=\"1\""));
+        assert!(output.contains("<testcase name=\"src/&lt;evil&gt;&#13;&#10;name.php\" classname=\"mago\">"));
+        assert!(output.contains("type=\"code&#13;&#10;value\" message=\"message&#13;&#10;&lt;/error&gt;\""));
+    }
+
+    #[test]
+    fn html_writes_page_when_empty() {
+        let Ok(output) = String::from_utf8(report_empty(ReportingFormat::Html)) else {
+            panic!("HTML output should be UTF-8");
+        };
+
+        assert!(output.starts_with("<!DOCTYPE html>"));
+        assert!(output.contains("<p>No issues found.</p>"));
+        assert!(output.trim_end().ends_with("</html>"));
+    }
+
+    #[test]
+    fn html_escapes_hostile_values() {
+        let Ok(output) = String::from_utf8(report_hostile_values(ReportingFormat::Html)) else {
+            panic!("HTML output should be UTF-8");
+        };
+
+        assert!(!output.contains('\0'));
+        assert!(!output.contains("</error>"));
+        assert!(output.contains("<summary>src/&lt;evil&gt;&#13;&#10;name.php"));
+        assert!(output.contains("data-code=\"code&#13;&#10;value\""));
+        assert!(output.contains("message&#13;&#10;&lt;/error&gt;"));
+    }
+
+    #[test]
+    fn html_shows_annotated_source_and_fix_diff() {
+        let file = File::ephemeral(Cow::Borrowed(b"test.php"), Cow::Borrowed(b"<?php\n\n$a == 1;\n"));
+        let file_id = file.id;
+        let reporter = reporter_for_file(ReportingFormat::Html, file);
+        let issue = Issue::warning("Use identity comparison.")
+            .with_code("identity-comparison")
+            .with_annotation(Annotation::primary(Span::new(file_id, 7u32.into(), 14u32.into())))
+            .with_edit(file_id, TextEdit::replace(10..12, "==="));
+
+        let mut buffer = Vec::new();
+        let Ok(_) = reporter.report_to(IssueCollection::from([issue]), None, &mut buffer) else {
+            panic!("reporting should succeed");
+        };
+
+        let Ok(output) = String::from_utf8(buffer) else {
+            panic!("HTML output should be UTF-8");
+        };
+
+        assert!(output.contains("<td class=\"line-number\">3</td><td class=\"code\"><span class=\"mark primary on\" data-of=\"0\">$a == 1</span>;</td>"));
+        assert!(output.contains("<span class=\"delete\">-$a == 1;</span><span class=\"insert\">+$a === 1;</span>"));
+        assert!(output.contains("data-select-code=\"identity-comparison\""));
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn sonarqube_writes_empty_issue_list_when_empty() {
+        let output = report_empty(ReportingFormat::Sonarqube);
+
+        let Ok(value) = serde_json::from_slice::<serde_json::Value>(&output) else {
+            panic!("empty SonarQube report should be valid JSON");
+        };
+
+        assert!(value["issues"].as_array().is_some_and(Vec::is_empty));
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn sonarqube_serializes_hostile_values_as_json_data() {
+        let output = report_hostile_values(ReportingFormat::Sonarqube);
+        let Ok(value) = serde_json::from_slice::<serde_json::Value>(&output) else {
+            panic!("SonarQube report should be valid JSON");
+        };
+
+        let issue = &value["issues"][0];
+        assert_eq!(issue["ruleId"], "code\r\nvalue");
+        assert_eq!(issue["type"], "BUG");
+        assert_eq!(issue["primaryLocation"]["message"], "message\0\u{1}\r\n</error>");
+        assert_eq!(issue["primaryLocation"]["textRange"]["startColumn"], 0);
+        assert_eq!(issue["primaryLocation"]["textRange"]["endColumn"], 1);
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn code_climate_writes_empty_list_when_empty() {
+        assert_eq!(report_empty(ReportingFormat::CodeClimate), b"[]");
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn code_climate_derives_categories_and_remediation_points() {
+        let file = File::ephemeral(Cow::Borrowed(b"test.php"), Cow::Borrowed(b"<?php\n"));
+        let file_id = file.id;
+        let configuration =
+            DatabaseConfiguration::new(std::path::Path::new("/"), vec![], vec![], vec![], vec![]).into_static();
+        let reporter = Reporter::new(
+            Database::single(file, configuration).read_only(),
+            ReporterConfig {
+                target: ReportingTarget::Stdout,
+                format: ReportingFormat::CodeClimate,
+                color_choice: ColorChoice::Never,
+                filter_fixable: false,
+                sort: false,
+                minimum_report_level: None,
+                editor_url: None,
+                issue_categories: HashMap::from([("no-eval".to_string(), "Security".to_string())]),
+                source_highlighter: None,
+            },
+        );
+
+        let span = Span::new(file_id, 0u32.into(), 5u32.into());
+        let issues = IssueCollection::from([
+            Issue::error("eval").with_code("no-eval").with_annotation(Annotation::primary(span)),
+            Issue::warning("unknown").with_code("other-code").with_annotation(Annotation::primary(span)),
+        ]);
+
+        let mut buffer = Vec::new();
+        let Ok(_) = reporter.report_to(issues, None, &mut buffer) else {
+            panic!("reporting should succeed");
+        };
+
+        let Ok(value) = serde_json::from_slice::<serde_json::Value>(&buffer) else {
+            panic!("Code Climate report should be valid JSON");
+        };
+
+        assert_eq!(value[0]["type"], "issue");
+        assert_eq!(value[0]["categories"][0], "Security");
+        assert_eq!(value[0]["remediation_points"], 500_000);
+        assert_eq!(value[0]["severity"], "major");
+        assert_eq!(value[1]["categories"][0], "Style");
+        assert_eq!(value[1]["remediation_points"], 50_000);
+        assert_eq!(value[1]["location"]["positions"]["begin"]["line"], 1);
+    }
 }
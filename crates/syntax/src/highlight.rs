@@ -0,0 +1,104 @@
+//! Syntax highlighting of PHP source code for reports.
+
+use mago_database::file::FileId;
+use mago_reporting::highlight::Highlight;
+use mago_reporting::highlight::HighlightKind;
+use mago_syntax_core::input::Input;
+
+use crate::lexer::Lexer;
+use crate::settings::LexerSettings;
+use crate::token::TokenKind;
+
+/// Splits PHP source code into highlighted fragments, one per token.
+///
+/// Whitespace and inline text are not highlighted. Highlighting stops at the first
+/// lexing error, leaving the rest of the source code as plain text.
+///
+/// This function is a [`SourceHighlighter`](mago_reporting::highlight::SourceHighlighter).
+#[must_use]
+pub fn highlight(source: &[u8]) -> Vec<Highlight> {
+    let mut lexer = Lexer::new(Input::new(FileId::zero(), source), LexerSettings::default());
+    let mut highlights = Vec::new();
+
+    while let Some(Ok(token)) = lexer.advance() {
+        let Some(kind) = highlight_kind(token.kind) else {
+            continue;
+        };
+
+        let start = token.start.offset;
+        let end = start + token.value.len() as u32;
+
+        highlights.push(Highlight { start, end, kind });
+    }
+
+    highlights
+}
+
+const fn highlight_kind(kind: TokenKind) -> Option<HighlightKind> {
+    Some(match kind {
+        TokenKind::Whitespace | TokenKind::InlineText | TokenKind::InlineShebang => return None,
+        TokenKind::SingleLineComment
+        | TokenKind::HashComment
+        | TokenKind::MultiLineComment
+        | TokenKind::DocBlockComment => HighlightKind::Comment,
+        TokenKind::Variable | TokenKind::Dollar | TokenKind::DollarLeftBrace | TokenKind::StringVariableName => {
+            HighlightKind::Variable
+        }
+        TokenKind::LiteralString
+        | TokenKind::PartialLiteralString
+        | TokenKind::StringPart
+        | TokenKind::DoubleQuote
+        | TokenKind::Backtick
+        | TokenKind::DocumentStart(_)
+        | TokenKind::DocumentEnd => HighlightKind::String,
+        TokenKind::LiteralInteger | TokenKind::LiteralFloat | TokenKind::OffsetNumber => HighlightKind::Number,
+        TokenKind::OpenTag | TokenKind::EchoTag | TokenKind::ShortOpenTag | TokenKind::CloseTag => HighlightKind::Tag,
+        TokenKind::HashLeftBracket => HighlightKind::Attribute,
+        TokenKind::Identifier
+        | TokenKind::QualifiedIdentifier
+        | TokenKind::FullyQualifiedIdentifier
+        | TokenKind::OffsetString => HighlightKind::Name,
+        kind if kind.is_literal() || kind.is_magic_constant() => HighlightKind::Constant,
+        kind if kind.is_keyword()
+            || kind.is_cast()
+            || matches!(
+                kind,
+                TokenKind::Callable | TokenKind::ProtectedSet | TokenKind::PublicSet | TokenKind::Insteadof
+            ) =>
+        {
+            HighlightKind::Keyword
+        }
+        _ => HighlightKind::Operator,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn highlights_tokens() {
+        let source = b"<?php\n// note\necho $name . 'x' . 42 . null;\n";
+        let highlights: Vec<(&[u8], HighlightKind)> = highlight(source)
+            .into_iter()
+            .map(|highlight| (&source[highlight.start as usize..highlight.end as usize], highlight.kind))
+            .collect();
+
+        assert_eq!(
+            highlights,
+            vec![
+                (&b"<?php"[..], HighlightKind::Tag),
+                (b"// note", HighlightKind::Comment),
+                (b"echo", HighlightKind::Keyword),
+                (b"$name", HighlightKind::Variable),
+                (b".", HighlightKind::Operator),
+                (b"'x'", HighlightKind::String),
+                (b".", HighlightKind::Operator),
+                (b"42", HighlightKind::Number),
+                (b".", HighlightKind::Operator),
+                (b"null", HighlightKind::Constant),
+                (b";", HighlightKind::Operator),
+            ]
+        );
+    }
+}
@@ -3,6 +3,7 @@
 pub mod comments;
 pub mod cst;
 pub mod error;
+pub mod highlight;
 pub mod lexer;
 pub mod macros;
 pub mod parser;
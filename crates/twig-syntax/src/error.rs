@@ -1,10 +1,15 @@
 use std::fmt;
 
 use mago_database::file::FileId;
+use mago_reporting::Annotation;
+use mago_reporting::Issue;
 use mago_span::HasSpan;
 use mago_span::Position;
 use mago_span::Span;
 
+const SYNTAX_ERROR_CODE: &str = "syntax";
+const PARSE_ERROR_CODE: &str = "parse";
+
 #[derive(Debug, Clone, Copy, Eq, PartialEq, Hash, PartialOrd, Ord)]
 #[cfg_attr(feature = "serde", derive(serde::Serialize))]
 pub enum SyntaxError {
@@ -162,3 +167,27 @@ impl HasSpan for ParseError<'_> {
         }
     }
 }
+
+impl From<&SyntaxError> for Issue {
+    fn from(error: &SyntaxError) -> Issue {
+        Issue::error("Syntax error encountered while lexing the template")
+            .with_code(SYNTAX_ERROR_CODE)
+            .with_annotation(Annotation::primary(error.span()).with_message(error.to_string()))
+            .with_note("This error indicates that the lexer encountered a syntax issue.")
+            .with_help("Check the syntax of your template.")
+    }
+}
+
+impl From<&ParseError<'_>> for Issue {
+    fn from(error: &ParseError<'_>) -> Self {
+        if let ParseError::SyntaxError(syntax_error) = error {
+            syntax_error.into()
+        } else {
+            Issue::error("Parse error encountered while parsing the template")
+                .with_code(PARSE_ERROR_CODE)
+                .with_annotation(Annotation::primary(error.span()).with_message(error.to_string()))
+                .with_note("This error indicates that the parser encountered a parse issue.")
+                .with_help("Check the syntax of your template.")
+        }
+    }
+}
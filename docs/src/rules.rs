@@ -38,6 +38,7 @@ struct RuleEntry {
     description: String,
     good_example: String,
     bad_example: String,
+    example_language: &'static str,
     category: Category,
     requirements: RuleRequirements,
     level: Level,
@@ -57,6 +58,7 @@ pub fn generate(documentation_root: &Path) -> Result<()> {
                 description: meta.description.to_string(),
                 good_example: meta.good_example.to_string(),
                 bad_example: meta.bad_example.to_string(),
+                example_language: if rule.is_template_rule() { "twig" } else { "php" },
                 category: meta.category,
                 requirements: meta.requirements,
                 level: AnyRule::default_level(rule),
@@ -361,8 +363,9 @@ fn render_rule(strings: &I18nMap, rule: &RuleEntry, rules_default_config: &Value
         if !bad.is_empty() {
             out.push_str("<div class=\"rule-example rule-example--bad\">\n");
             out.push_str(&format!(
-                "<div class=\"rule-example__label\">{}</div>\n\n```php\n",
+                "<div class=\"rule-example__label\">{}</div>\n\n```{}\n",
                 t(strings, "rules_avoid_label", "Avoid"),
+                rule.example_language,
             ));
             out.push_str(bad);
             out.push_str("\n```\n\n</div>\n\n");
@@ -371,8 +374,9 @@ fn render_rule(strings: &I18nMap, rule: &RuleEntry, rules_default_config: &Value
         if !good.is_empty() {
             out.push_str("<div class=\"rule-example rule-example--good\">\n");
             out.push_str(&format!(
-                "<div class=\"rule-example__label\">{}</div>\n\n```php\n",
+                "<div class=\"rule-example__label\">{}</div>\n\n```{}\n",
                 t(strings, "rules_prefer_label", "Prefer"),
+                rule.example_language,
             ));
             out.push_str(good);
             out.push_str("\n```\n\n</div>\n\n");
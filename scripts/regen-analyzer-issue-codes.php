@@ -348,6 +348,24 @@ final class AnalyzerCodeModuleGenerator
         'unavailable-constant',
         'unavailable-class-constant',
         'unavailable-enum-case',
+        'tainted-html',
+        'tainted-sql',
+        'tainted-shell',
+        'tainted-include',
+        'tainted-unserialize',
+        'unreachable-class',
+        'unreachable-method',
+        'unreachable-property',
+        'unreachable-constant',
+        'non-existent-template',
+        'undefined-template-variable',
+        'non-existent-template-attribute',
+        'invalid-model-cast',
+        'non-existent-entity',
+        'non-existent-entity-field',
+        'invalid-event-listener',
+        'non-existent-mocked-method',
+        'invalid-data-provider',
     ];
 
     /**
@@ -0,0 +1,342 @@
+//! Locating issues for the fingerprint baseline.
+//!
+//! A fingerprint baseline keys each issue on the symbol enclosing it and on the fingerprint of the
+//! innermost statement or class-like member around it. This module parses the files holding the
+//! issues once each, and walks them to find both.
+
+use std::collections::BTreeMap;
+
+use mago_allocator::LocalArena;
+use mago_database::DatabaseReader;
+use mago_database::ReadDatabase;
+use mago_database::file::FileId;
+use mago_fingerprint::FingerprintOptions;
+use mago_fingerprint::Fingerprintable;
+use mago_names::ResolvedNames;
+use mago_names::resolver::NameResolver;
+use mago_reporting::IssueCollection;
+use mago_reporting::baseline::BaselineVariant;
+use mago_reporting::baseline::IssueLocation;
+use mago_reporting::baseline::IssueLocations;
+use mago_span::HasSpan;
+use mago_span::Span;
+use mago_syntax::cst::AnonymousClass;
+use mago_syntax::cst::Class;
+use mago_syntax::cst::ClassLikeMember;
+use mago_syntax::cst::Enum;
+use mago_syntax::cst::Function;
+use mago_syntax::cst::Interface;
+use mago_syntax::cst::Statement;
+use mago_syntax::cst::Trait;
+use mago_syntax::parser::parse_file_with_settings;
+use mago_syntax::settings::ParserSettings;
+use mago_syntax::walker::MutWalker;
+
+/// Computes the locations of the given issues when the baseline variant needs them.
+///
+/// Only the fingerprint variant reads locations; for the other variants, no file is parsed and
+/// the returned set is empty.
+pub fn locate_issues(
+    variant: BaselineVariant,
+    read_database: &ReadDatabase,
+    issues: &IssueCollection,
+    parser_settings: ParserSettings,
+) -> IssueLocations {
+    let mut locations = IssueLocations::new();
+    if variant != BaselineVariant::Fingerprint {
+        return locations;
+    }
+
+    let mut spans_by_file: BTreeMap<FileId, Vec<Span>> = BTreeMap::new();
+    for span in issues.iter().filter_map(IssueLocations::span_of) {
+        spans_by_file.entry(span.file_id).or_default().push(span);
+    }
+
+    for (file_id, spans) in spans_by_file {
+        let Ok(file) = read_database.get(&file_id) else {
+            continue;
+        };
+
+        let arena = LocalArena::new();
+        let program = parse_file_with_settings(&arena, &file, parser_settings);
+        let resolved_names = NameResolver::new(&arena).resolve(program);
+
+        let mut locator = IssueLocator::new(&resolved_names, FingerprintOptions::default(), spans);
+        locator.walk_program(program, &mut ());
+
+        for (span, location) in locator.spans.into_iter().zip(locator.locations) {
+            if let Some(location) = location {
+                locations.insert(span, location);
+            }
+        }
+    }
+
+    locations
+}
+
+/// Walks a program, recording for each issue span the innermost statement or member enclosing it.
+struct IssueLocator<'a, 'arena> {
+    resolved_names: &'a ResolvedNames<'arena>,
+    options: FingerprintOptions<'a>,
+    spans: Vec<Span>,
+    locations: Vec<Option<IssueLocation>>,
+    /// The symbols enclosing the node being walked, innermost last.
+    scopes: Vec<String>,
+}
+
+impl<'a, 'arena> IssueLocator<'a, 'arena> {
+    fn new(resolved_names: &'a ResolvedNames<'arena>, options: FingerprintOptions<'a>, spans: Vec<Span>) -> Self {
+        let locations = vec![None; spans.len()];
+
+        Self { resolved_names, options, spans, locations, scopes: Vec::new() }
+    }
+
+    fn symbol(&self) -> String {
+        self.scopes.last().cloned().unwrap_or_default()
+    }
+
+    fn member_symbol(&self, member: &[u8]) -> String {
+        let member = String::from_utf8_lossy(member);
+
+        match self.scopes.last() {
+            Some(class) => format!("{class}::{member}"),
+            None => member.into_owned(),
+        }
+    }
+
+    /// Records `node` as the location of the issues it encloses, replacing the locations recorded
+    /// for its ancestors. The fingerprint is only computed when the node encloses an issue.
+    fn locate(&mut self, node: Span, symbol: impl FnOnce(&Self) -> String, fingerprint: impl FnOnce(&Self) -> u64) {
+        let enclosed: Vec<usize> = self
+            .spans
+            .iter()
+            .enumerate()
+            .filter(|(_, span)| node.start.offset <= span.start.offset && span.end.offset <= node.end.offset)
+            .map(|(index, _)| index)
+            .collect();
+
+        if enclosed.is_empty() {
+            return;
+        }
+
+        let location = IssueLocation { symbol: symbol(self), fingerprint: fingerprint(self) };
+        for index in enclosed {
+            self.locations[index] = Some(location.clone());
+        }
+    }
+
+    /// Enters the declaration of a symbol: issues on the declaration itself are keyed on the
+    /// symbol alone, so that changes to its body do not invalidate them.
+    fn enter(&mut self, node: Span, symbol: String) {
+        self.scopes.push(symbol);
+        self.locate(node, Self::symbol, |_| 0);
+    }
+
+    fn resolved_symbol(&self, name: &impl HasSpan) -> String {
+        String::from_utf8_lossy(self.resolved_names.get(name)).into_owned()
+    }
+}
+
+impl<'ast, 'arena> MutWalker<'ast, 'arena, ()> for IssueLocator<'_, 'arena> {
+    fn walk_in_statement(&mut self, statement: &'ast Statement<'arena>, _context: &mut ()) {
+        if matches!(
+            statement,
+            Statement::Namespace(_)
+                | Statement::Class(_)
+                | Statement::Interface(_)
+                | Statement::Trait(_)
+                | Statement::Enum(_)
+                | Statement::Function(_)
+        ) {
+            return;
+        }
+
+        self.locate(statement.span(), Self::symbol, |locator| {
+            statement.fingerprint(locator.resolved_names, &locator.options)
+        });
+    }
+
+    fn walk_in_class(&mut self, class: &'ast Class<'arena>, _context: &mut ()) {
+        self.enter(class.span(), self.resolved_symbol(&class.name));
+    }
+
+    fn walk_out_class(&mut self, _class: &'ast Class<'arena>, _context: &mut ()) {
+        self.scopes.pop();
+    }
+
+    fn walk_in_interface(&mut self, interface: &'ast Interface<'arena>, _context: &mut ()) {
+        self.enter(interface.span(), self.resolved_symbol(&interface.name));
+    }
+
+    fn walk_out_interface(&mut self, _interface: &'ast Interface<'arena>, _context: &mut ()) {
+        self.scopes.pop();
+    }
+
+    fn walk_in_trait(&mut self, r#trait: &'ast Trait<'arena>, _context: &mut ()) {
+        self.enter(r#trait.span(), self.resolved_symbol(&r#trait.name));
+    }
+
+    fn walk_out_trait(&mut self, _trait: &'ast Trait<'arena>, _context: &mut ()) {
+        self.scopes.pop();
+    }
+
+    fn walk_in_enum(&mut self, r#enum: &'ast Enum<'arena>, _context: &mut ()) {
+        self.enter(r#enum.span(), self.resolved_symbol(&r#enum.name));
+    }
+
+    fn walk_out_enum(&mut self, _enum: &'ast Enum<'arena>, _context: &mut ()) {
+        self.scopes.pop();
+    }
+
+    fn walk_in_anonymous_class(&mut self, _anonymous_class: &'ast AnonymousClass<'arena>, _context: &mut ()) {
+        self.scopes.push(self.member_symbol(b"class@anonymous"));
+    }
+
+    fn walk_out_anonymous_class(&mut self, _anonymous_class: &'ast AnonymousClass<'arena>, _context: &mut ()) {
+        self.scopes.pop();
+    }
+
+    fn walk_in_function(&mut self, function: &'ast Function<'arena>, _context: &mut ()) {
+        self.enter(function.span(), self.resolved_symbol(&function.name));
+    }
+
+    fn walk_out_function(&mut self, _function: &'ast Function<'arena>, _context: &mut ()) {
+        self.scopes.pop();
+    }
+
+    fn walk_in_class_like_member(&mut self, member: &'ast ClassLikeMember<'arena>, _context: &mut ()) {
+        let symbol = match member {
+            ClassLikeMember::Method(method) => {
+                self.enter(member.span(), self.member_symbol(method.name.value));
+
+                return;
+            }
+            ClassLikeMember::Property(property) => self.member_symbol(property.first_variable().name),
+            ClassLikeMember::Constant(constant) => match constant.items.first() {
+                Some(item) => self.member_symbol(item.name.value),
+                None => self.symbol(),
+            },
+            ClassLikeMember::EnumCase(case) => self.member_symbol(case.item.name().value),
+            ClassLikeMember::TraitUse(_) => self.symbol(),
+        };
+
+        self.locate(member.span(), |_| symbol, |locator| member.fingerprint(locator.resolved_names, &locator.options));
+    }
+
+    fn walk_out_class_like_member(&mut self, member: &'ast ClassLikeMember<'arena>, _context: &mut ()) {
+        if let ClassLikeMember::Method(_) = member {
+            self.scopes.pop();
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::borrow::Cow;
+    use std::path::Path;
+
+    use mago_database::Database;
+    use mago_database::DatabaseConfiguration;
+    use mago_database::file::File;
+    use mago_reporting::Annotation;
+    use mago_reporting::Issue;
+    use mago_span::Position;
+
+    use super::*;
+
+    const CODE: &str = indoc::indoc! {r"
+        <?php
+
+        namespace App;
+
+        final class Service
+        {
+            public function handle($x)
+            {
+                $y = $x == 1;
+                return $y;
+            }
+        }
+
+        echo 'done';
+    "};
+
+    fn issue_at(file_id: FileId, code: &str, needle: &str) -> Issue {
+        let start = u32::try_from(code.find(needle).expect("needle not found")).expect("offset fits in u32");
+        let end = start + u32::try_from(needle.len()).expect("length fits in u32");
+
+        Issue::warning("test").with_annotation(Annotation::primary(Span::new(
+            file_id,
+            Position::new(start),
+            Position::new(end),
+        )))
+    }
+
+    fn locate(code: &str, needles: &[&str]) -> Vec<Option<IssueLocation>> {
+        let file = File::ephemeral(Cow::Borrowed(b"src/Service.php"), Cow::Owned(code.as_bytes().to_vec()));
+        let file_id = file.id;
+        let config = DatabaseConfiguration::new(Path::new("/"), vec![], vec![], vec![], vec![]).into_static();
+        let database = Database::single(file, config);
+        let read_database = database.read_only();
+
+        let issues: Vec<Issue> = needles.iter().map(|needle| issue_at(file_id, code, needle)).collect();
+        let locations = locate_issues(
+            BaselineVariant::Fingerprint,
+            &read_database,
+            &IssueCollection::from(issues.clone()),
+            ParserSettings::default(),
+        );
+
+        issues.iter().map(|issue| locations.get(issue).cloned()).collect()
+    }
+
+    #[test]
+    fn locates_statements_declarations_and_top_level_code() {
+        let locations = locate(CODE, &["$x == 1", "function handle", "Service", "echo 'done';"]);
+        let locations: Vec<IssueLocation> = locations.into_iter().map(Option::unwrap).collect();
+
+        assert_eq!(locations[0].symbol, "App\\Service::handle");
+        assert_ne!(locations[0].fingerprint, 0);
+        assert_eq!(locations[1], IssueLocation { symbol: "App\\Service::handle".to_string(), fingerprint: 0 });
+        assert_eq!(locations[2], IssueLocation { symbol: "App\\Service".to_string(), fingerprint: 0 });
+        assert_eq!(locations[3].symbol, "");
+        assert_ne!(locations[3].fingerprint, 0);
+    }
+
+    #[test]
+    fn fingerprints_ignore_formatting_and_position() {
+        let moved = indoc::indoc! {r"
+            <?php
+
+            namespace App;
+
+            final class Service
+            {
+                public function other() { return 1; }
+
+                public function handle($x) {
+                    $y   =   $x == 1;
+                    return $y;
+                }
+            }
+        "};
+
+        let original = locate(CODE, &["$x == 1"]);
+        let reformatted = locate(moved, &["$x == 1"]);
+
+        assert_eq!(original, reformatted);
+    }
+
+    #[test]
+    fn skips_other_variants() {
+        let file = File::ephemeral(Cow::Borrowed(b"src/Service.php"), Cow::Borrowed(CODE.as_bytes()));
+        let file_id = file.id;
+        let config = DatabaseConfiguration::new(Path::new("/"), vec![], vec![], vec![], vec![]).into_static();
+        let database = Database::single(file, config);
+
+        let issues = IssueCollection::from([issue_at(file_id, CODE, "$x == 1")]);
+        for variant in [BaselineVariant::Loose, BaselineVariant::Strict] {
+            assert!(locate_issues(variant, &database.read_only(), &issues, ParserSettings::default()).is_empty());
+        }
+    }
+}
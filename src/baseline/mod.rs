@@ -2,20 +2,54 @@ use std::fs;
 use std::path::Path;
 
 use serde::Deserialize;
+use serde::de::IgnoredAny;
 
 use crate::error::Error;
 
+pub mod locations;
+pub mod revision;
+
 // Re-export baseline types from the reporting crate
 pub use mago_reporting::baseline::Baseline;
+pub use mago_reporting::baseline::BaselineFile;
 pub use mago_reporting::baseline::BaselineVariant;
+pub use mago_reporting::baseline::FingerprintBaseline;
 pub use mago_reporting::baseline::LooseBaseline;
+pub use mago_reporting::baseline::ProjectBaseline;
 pub use mago_reporting::baseline::StrictBaseline;
 
-/// Intermediate struct for detecting variant from file header.
+/// Intermediate struct for detecting the shape of a baseline file from its header.
 #[derive(Deserialize)]
 struct BaselineHeader {
     #[serde(default)]
     variant: Option<BaselineVariant>,
+    #[serde(default)]
+    linter: Option<IgnoredAny>,
+    #[serde(default)]
+    analyzer: Option<IgnoredAny>,
+    #[serde(default)]
+    guard: Option<IgnoredAny>,
+}
+
+/// Intermediate struct for detecting the variant of each section of a project baseline.
+#[derive(Deserialize)]
+struct ProjectBaselineHeader {
+    #[serde(default)]
+    linter: Option<toml::Table>,
+    #[serde(default)]
+    analyzer: Option<toml::Table>,
+    #[serde(default)]
+    guard: Option<toml::Table>,
+}
+
+/// Where the baseline of a tool is configured.
+#[derive(Debug, Clone, Copy)]
+pub struct BaselineLocation<'a> {
+    /// The path to the baseline file.
+    pub path: &'a Path,
+    /// Whether the path is the project baseline, holding a section per tool, rather than
+    /// a baseline of the tool alone.
+    pub is_project: bool,
 }
 
 /// Serializes a `Baseline` to a TOML file.
@@ -28,6 +62,30 @@ struct BaselineHeader {
 /// * `baseline` - The `Baseline` object to serialize.
 /// * `backup` - If `true`, renames an existing baseline file to `[path].bkp`. If `false`, deletes it.
 pub fn serialize_baseline(path: &Path, baseline: &Baseline, backup: bool) -> Result<(), Error> {
+    let toml_string = match baseline {
+        Baseline::Strict(strict) => toml::to_string_pretty(strict).map_err(Error::SerializingToml)?,
+        Baseline::Loose(loose) => toml::to_string_pretty(loose).map_err(Error::SerializingToml)?,
+        Baseline::Fingerprint(fingerprint) => toml::to_string_pretty(fingerprint).map_err(Error::SerializingToml)?,
+    };
+
+    write_baseline(path, &toml_string, backup)
+}
+
+/// Serializes a `BaselineFile`, holding either a single tool or a section per tool, to a TOML file.
+///
+/// Existing files are handled like in [`serialize_baseline`].
+pub fn serialize_baseline_file(path: &Path, file: &BaselineFile, backup: bool) -> Result<(), Error> {
+    match file {
+        BaselineFile::Tool(baseline) => serialize_baseline(path, baseline, backup),
+        BaselineFile::Project(project) => {
+            let toml_string = toml::to_string_pretty(project).map_err(Error::SerializingToml)?;
+
+            write_baseline(path, &toml_string, backup)
+        }
+    }
+}
+
+fn write_baseline(path: &Path, toml_string: &str, backup: bool) -> Result<(), Error> {
     if path.exists() {
         if backup {
             let backup_path = path.with_extension("toml.bkp");
@@ -37,20 +95,17 @@ pub fn serialize_baseline(path: &Path, baseline: &Baseline, backup: bool) -> Res
         }
     }
 
-    let toml_string = match baseline {
-        Baseline::Strict(strict) => toml::to_string_pretty(strict).map_err(Error::SerializingToml)?,
-        Baseline::Loose(loose) => toml::to_string_pretty(loose).map_err(Error::SerializingToml)?,
-    };
-
     fs::write(path, toml_string).map_err(Error::CreatingBaselineFile)?;
     Ok(())
 }
 
-/// Deserializes a `Baseline` from a TOML file.
+/// Deserializes a `BaselineFile` from a TOML file, detecting whether it is the baseline of a
+/// single tool or a project baseline with a section per tool.
 ///
-/// Returns a tuple of `(Baseline, bool)` where the boolean indicates if a warning should be shown.
-/// The warning is shown when the baseline file does not have a `variant` header, indicating it was
-/// created with an older version of mago. In this case, the baseline is assumed to be strict.
+/// Returns a tuple of `(BaselineFile, bool)` where the boolean indicates if a warning should be shown.
+/// The warning is shown when a single-tool baseline does not have a `variant` header, indicating it
+/// was created with an older version of mago. In this case, the baseline is assumed to be strict.
+/// Sections of a project baseline always carry a `variant` header.
 ///
 /// # Arguments
 ///
@@ -58,26 +113,53 @@ pub fn serialize_baseline(path: &Path, baseline: &Baseline, backup: bool) -> Res
 ///
 /// # Returns
 ///
-/// * `Ok((baseline, needs_warning))` - The deserialized baseline and whether a warning is needed.
+/// * `Ok((file, needs_warning))` - The deserialized baseline file and whether a warning is needed.
 /// * `Err(_)` - If reading or parsing the file fails.
-pub fn unserialize_baseline(path: &Path) -> Result<(Baseline, bool), Error> {
+pub fn unserialize_baseline_file(path: &Path) -> Result<(BaselineFile, bool), Error> {
     let toml_string = fs::read_to_string(path).map_err(Error::ReadingBaselineFile)?;
 
-    // First pass: detect variant from header
     let header: BaselineHeader = toml::from_str(&toml_string).map_err(Error::DeserializingToml)?;
+    if header.variant.is_some() || (header.linter.is_none() && header.analyzer.is_none() && header.guard.is_none()) {
+        let (baseline, needs_warning) = parse_baseline(&toml_string)?;
+
+        return Ok((BaselineFile::Tool(baseline), needs_warning));
+    }
+
+    let sections: ProjectBaselineHeader = toml::from_str(&toml_string).map_err(Error::DeserializingToml)?;
+    let parse_section = |table: Option<toml::Table>| -> Result<Option<Baseline>, Error> {
+        table.map(|table| parse_baseline(&table.to_string()).map(|(baseline, _)| baseline)).transpose()
+    };
+
+    Ok((
+        BaselineFile::Project(ProjectBaseline {
+            linter: parse_section(sections.linter)?,
+            analyzer: parse_section(sections.analyzer)?,
+            guard: parse_section(sections.guard)?,
+        }),
+        false,
+    ))
+}
+
+fn parse_baseline(toml_string: &str) -> Result<(Baseline, bool), Error> {
+    // First pass: detect variant from header
+    let header: BaselineHeader = toml::from_str(toml_string).map_err(Error::DeserializingToml)?;
 
     match header.variant {
         Some(BaselineVariant::Loose) => {
-            let loose: LooseBaseline = toml::from_str(&toml_string).map_err(Error::DeserializingToml)?;
+            let loose: LooseBaseline = toml::from_str(toml_string).map_err(Error::DeserializingToml)?;
             Ok((Baseline::Loose(loose), false))
         }
+        Some(BaselineVariant::Fingerprint) => {
+            let fingerprint: FingerprintBaseline = toml::from_str(toml_string).map_err(Error::DeserializingToml)?;
+            Ok((Baseline::Fingerprint(fingerprint), false))
+        }
         Some(BaselineVariant::Strict) => {
-            let strict: StrictBaseline = toml::from_str(&toml_string).map_err(Error::DeserializingToml)?;
+            let strict: StrictBaseline = toml::from_str(toml_string).map_err(Error::DeserializingToml)?;
             Ok((Baseline::Strict(strict), false))
         }
         None => {
             // No variant header - assume strict for backward compatibility
-            let strict: StrictBaseline = toml::from_str(&toml_string).map_err(Error::DeserializingToml)?;
+            let strict: StrictBaseline = toml::from_str(toml_string).map_err(Error::DeserializingToml)?;
             Ok((Baseline::Strict(strict), true)) // needs_warning = true
         }
     }
@@ -86,12 +168,23 @@ pub fn unserialize_baseline(path: &Path) -> Result<(Baseline, bool), Error> {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use mago_reporting::baseline::BaselineEntryMetadata;
+    use mago_reporting::baseline::FingerprintBaselineIssue;
     use mago_reporting::baseline::LooseBaselineIssue;
     use mago_reporting::baseline::StrictBaselineEntry;
     use mago_reporting::baseline::StrictBaselineIssue;
     use std::borrow::Cow;
     use tempfile::NamedTempFile;
 
+    fn unserialize_baseline(path: &Path) -> Result<(Baseline, bool), Error> {
+        let (file, needs_warning) = unserialize_baseline_file(path)?;
+        let BaselineFile::Tool(baseline) = file else {
+            panic!("Expected a single-tool baseline");
+        };
+
+        Ok((baseline, needs_warning))
+    }
+
     #[test]
     fn test_serialize_strict_baseline_creates_backup() {
         let temp_file = NamedTempFile::new().expect("Failed to create temp file");
@@ -129,6 +222,7 @@ mod tests {
                 code: "null-argument".to_string(),
                 message: "Argument #1 of `process` cannot be `null`.".to_string(),
                 count: 2,
+                metadata: BaselineEntryMetadata::default(),
             }],
         });
 
@@ -154,6 +248,7 @@ mod tests {
                     code: "redundant-docblock-type".to_string(),
                     start_line: 29,
                     end_line: 29,
+                    metadata: BaselineEntryMetadata::default(),
                 }],
             },
         );
@@ -318,6 +413,7 @@ variant = "strict"
                     code: "invalid-argument".to_string(),
                     start_line: 68,
                     end_line: 71,
+                    metadata: BaselineEntryMetadata::default(),
                 }],
             },
         );
@@ -342,6 +438,7 @@ variant = "strict"
                 code: "possibly-invalid-argument".to_string(),
                 message: "Argument #2 of `findBy` expects `array<string, mixed>`, but `mixed` was given.".to_string(),
                 count: 5,
+                metadata: BaselineEntryMetadata::default(),
             }],
         };
         let original = Baseline::Loose(loose);
@@ -357,4 +454,128 @@ variant = "strict"
             assert_eq!(loose.issues[0].count, 5);
         }
     }
+
+    #[test]
+    fn test_roundtrip_project_baseline() {
+        let temp_dir = tempfile::tempdir().expect("Failed to create temp dir");
+        let temp_path = temp_dir.path().join("mago-baseline.toml");
+
+        let mut strict = StrictBaseline::new();
+        strict.entries.insert(
+            Cow::Borrowed("src/Service/PaymentProcessor.php"),
+            StrictBaselineEntry {
+                issues: vec![StrictBaselineIssue {
+                    code: "must-be-final".to_string(),
+                    start_line: 12,
+                    end_line: 12,
+                    metadata: BaselineEntryMetadata::default(),
+                }],
+            },
+        );
+
+        let project = ProjectBaseline {
+            linter: Some(Baseline::Loose(LooseBaseline {
+                variant: BaselineVariant::Loose,
+                issues: vec![LooseBaselineIssue {
+                    file: "src/Service/PaymentProcessor.php".to_string(),
+                    code: "no-shorthand-ternary".to_string(),
+                    message: "Use of the shorthand ternary operator.".to_string(),
+                    count: 2,
+                    metadata: BaselineEntryMetadata {
+                        owner: Some("payments".to_string()),
+                        ticket: Some("PAY-431".to_string()),
+                        added: Some("2026-03-02".to_string()),
+                    },
+                }],
+            })),
+            analyzer: None,
+            guard: Some(Baseline::Strict(strict)),
+        };
+
+        serialize_baseline_file(&temp_path, &BaselineFile::Project(project), false).expect("Failed to serialize");
+
+        let content = std::fs::read_to_string(&temp_path).expect("Failed to read content");
+        assert!(content.contains("[linter]"));
+        assert!(content.contains("owner = \"payments\""));
+        assert!(!content.contains("[analyzer]"));
+
+        let (loaded, needs_warning) = unserialize_baseline_file(&temp_path).expect("Failed to deserialize");
+        assert!(!needs_warning);
+
+        let BaselineFile::Project(project) = loaded else {
+            panic!("Expected a project baseline");
+        };
+
+        assert!(project.analyzer.is_none());
+        assert!(matches!(project.guard, Some(Baseline::Strict(_))));
+
+        let Some(Baseline::Loose(linter)) = project.linter else {
+            panic!("Expected a loose linter section");
+        };
+
+        assert_eq!(linter.issues[0].count, 2);
+        assert_eq!(linter.issues[0].metadata.ticket.as_deref(), Some("PAY-431"));
+        assert_eq!(linter.issues[0].metadata.added.as_deref(), Some("2026-03-02"));
+    }
+
+    #[test]
+    fn test_roundtrip_fingerprint_baseline() {
+        let temp_file = NamedTempFile::new().expect("Failed to create temp file");
+        let temp_path = temp_file.path();
+
+        let baseline = Baseline::Fingerprint(FingerprintBaseline {
+            variant: BaselineVariant::Fingerprint,
+            issues: vec![FingerprintBaselineIssue {
+                file: "src/Service/PaymentProcessor.php".to_string(),
+                code: "possibly-null-argument".to_string(),
+                symbol: "App\\Service\\PaymentProcessor::process".to_string(),
+                fingerprint: "7cb672657098d36e".to_string(),
+                count: 1,
+                metadata: BaselineEntryMetadata { owner: Some("payments".to_string()), ..Default::default() },
+            }],
+        });
+
+        serialize_baseline(temp_path, &baseline, false).expect("Failed to serialize baseline");
+
+        let content = std::fs::read_to_string(temp_path).expect("Failed to read content");
+        assert!(content.contains("variant = \"fingerprint\""));
+
+        let (loaded, needs_warning) = unserialize_baseline(temp_path).expect("Failed to deserialize baseline");
+        assert!(!needs_warning);
+
+        let Baseline::Fingerprint(fingerprint) = loaded else {
+            panic!("Expected a fingerprint baseline");
+        };
+
+        assert_eq!(fingerprint.issues[0].symbol, "App\\Service\\PaymentProcessor::process");
+        assert_eq!(fingerprint.issues[0].fingerprint, "7cb672657098d36e");
+        assert_eq!(fingerprint.issues[0].metadata.owner.as_deref(), Some("payments"));
+    }
+
+    #[test]
+    fn test_unserialize_strict_baseline_with_metadata() {
+        let temp_file = NamedTempFile::new().expect("Failed to create temp file");
+        let temp_path = temp_file.path();
+
+        let toml_content = r#"
+variant = "strict"
+
+[[entries."src/Service/PaymentProcessor.php".issues]]
+code = "possibly-null-argument"
+start_line = 42
+end_line = 42
+owner = "payments"
+"#;
+
+        std::fs::write(temp_path, toml_content).expect("Failed to write TOML content");
+
+        let (baseline, _) = unserialize_baseline(temp_path).expect("Failed to deserialize baseline");
+        let Baseline::Strict(strict) = baseline else {
+            panic!("Expected a strict baseline");
+        };
+
+        let entry = strict.entries.get("src/Service/PaymentProcessor.php").expect("entry not found");
+        assert_eq!(entry.issues[0].metadata.owner.as_deref(), Some("payments"));
+        assert_eq!(entry.issues[0].metadata.ticket, None);
+    }
 }
@@ -0,0 +1,232 @@
+//! Comparing issues against a git revision.
+//!
+//! With `--new-since`, the issues found at a base revision act as a baseline: the tool is run
+//! a second time on the project's files as they were at that revision, read from git without a
+//! checkout, and current issues are matched to those by code, enclosing symbol and fingerprint.
+//! Matching ignores lines and files, so moved, reformatted and renamed code keeps matching.
+
+use std::borrow::Cow;
+use std::collections::BTreeMap;
+use std::path::Path;
+
+use mago_database::Database;
+use mago_database::DatabaseReader;
+use mago_database::ReadDatabase;
+use mago_database::file::File;
+use mago_database::file::FileType;
+use mago_database::membership::WorkspaceMatcher;
+use mago_reporting::IssueCollection;
+use mago_reporting::baseline::BaselineVariant;
+use mago_reporting::baseline::FingerprintBaseline;
+use mago_syntax::settings::ParserSettings;
+
+use crate::baseline::locations::locate_issues;
+use crate::error::Error;
+use crate::utils::git;
+
+/// The issues found at a git revision, used to report only the issues introduced since.
+#[derive(Debug)]
+pub struct RevisionBaseline {
+    /// The revision the issues were found at, as given on the command line.
+    revision: String,
+    /// The issues found at the revision, keyed by code, symbol and fingerprint.
+    baseline: FingerprintBaseline,
+    /// The number of issues found at the revision, per code.
+    counts: BTreeMap<String, usize>,
+    /// Whether to fail when the number of issues of any code went up since the revision.
+    ratchet: bool,
+}
+
+impl RevisionBaseline {
+    /// Creates a revision baseline from the issues found in `read_database`, the database
+    /// holding the project's files at `revision`.
+    #[must_use]
+    pub fn new(
+        revision: &str,
+        issues: &IssueCollection,
+        read_database: &ReadDatabase,
+        parser_settings: ParserSettings,
+        ratchet: bool,
+    ) -> Self {
+        let locations = locate_issues(BaselineVariant::Fingerprint, read_database, issues, parser_settings);
+
+        Self {
+            revision: revision.to_string(),
+            baseline: FingerprintBaseline::generate_from_issues(issues, read_database, &locations),
+            counts: count_by_code(issues),
+            ratchet,
+        }
+    }
+
+    /// Removes the issues that were already present at the revision.
+    #[must_use]
+    pub fn filter_issues(
+        &self,
+        issues: IssueCollection,
+        read_database: &ReadDatabase,
+        parser_settings: ParserSettings,
+    ) -> IssueCollection {
+        let total = issues.len();
+        let locations = locate_issues(BaselineVariant::Fingerprint, read_database, &issues, parser_settings);
+        let issues = self.baseline.filter_issues(issues, read_database, &locations);
+
+        let filtered = total - issues.len();
+        if filtered > 0 {
+            tracing::info!("Filtered out {filtered} issues already present at `{}`.", self.revision);
+        }
+
+        issues
+    }
+
+    /// Checks the ratchet, if enabled: logs each code with more issues than at the revision,
+    /// and returns `true` if there is any.
+    #[must_use]
+    pub fn ratchet_failed(&self, issues: &IssueCollection) -> bool {
+        if !self.ratchet {
+            return false;
+        }
+
+        let increases = increased_codes(&self.counts, &count_by_code(issues));
+        for (code, before, after) in &increases {
+            tracing::error!(
+                "The number of `{code}` issues went up from {before} to {after} since `{}`.",
+                self.revision
+            );
+        }
+
+        !increases.is_empty()
+    }
+}
+
+/// Builds the database of the project at a git revision.
+///
+/// Host files are replaced by the files tracked at `revision` that the database configuration
+/// would have loaded as host files, including files that were since renamed or deleted. Other
+/// files, such as vendored files and stubs, are shared with `database` as they are.
+pub fn load_revision_database<'config>(
+    database: &Database<'config>,
+    workspace: &Path,
+    revision: &str,
+) -> Result<Database<'config>, Error> {
+    let matcher = WorkspaceMatcher::from_configuration(database.configuration())?;
+    let workspace = workspace.canonicalize().unwrap_or_else(|_| workspace.to_path_buf());
+
+    let paths: Vec<_> = git::get_file_paths_at_revision(&workspace, revision)?
+        .into_iter()
+        .filter(|path| matcher.classify(&workspace.join(path)) == Some(FileType::Host))
+        .collect();
+
+    let contents = git::get_files_at_revision(&workspace, revision, &paths)?;
+
+    let mut revision_database = database.clone();
+    for id in database.read_only().file_ids_with_type(FileType::Host) {
+        revision_database.delete(id);
+    }
+
+    for (path, contents) in paths.into_iter().zip(contents) {
+        let Some(contents) = contents else {
+            continue;
+        };
+
+        // Git lists paths with forward slashes, matching the names of loaded files.
+        let name = path.to_string_lossy().into_owned().into_bytes();
+        let path = workspace.join(path);
+
+        revision_database.add(File::new(Cow::Owned(name), FileType::Host, Some(path), Cow::Owned(contents)));
+    }
+
+    Ok(revision_database)
+}
+
+/// Counts issues per code; issues without a code are not counted.
+fn count_by_code(issues: &IssueCollection) -> BTreeMap<String, usize> {
+    let mut counts = BTreeMap::new();
+    for code in issues.iter().filter_map(|issue| issue.code.as_ref()) {
+        *counts.entry(code.clone()).or_insert(0) += 1;
+    }
+
+    counts
+}
+
+/// Returns the codes counted more often in `after` than in `before`, with both counts.
+fn increased_codes(before: &BTreeMap<String, usize>, after: &BTreeMap<String, usize>) -> Vec<(String, usize, usize)> {
+    after
+        .iter()
+        .filter_map(|(code, &count)| {
+            let previous = before.get(code).copied().unwrap_or(0);
+
+            (count > previous).then(|| (code.clone(), previous, count))
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use std::path::Path;
+
+    use mago_database::DatabaseConfiguration;
+    use mago_reporting::Annotation;
+    use mago_reporting::Issue;
+    use mago_span::Position;
+    use mago_span::Span;
+
+    use super::*;
+
+    fn database(name: &'static str, code: &'static str) -> Database<'static> {
+        let config = DatabaseConfiguration::new(Path::new("/"), vec![], vec![], vec![], vec![]).into_static();
+
+        Database::single(File::ephemeral(Cow::Borrowed(name.as_bytes()), Cow::Borrowed(code.as_bytes())), config)
+    }
+
+    fn issues(database: &Database<'_>, source: &str, needles: &[&str]) -> IssueCollection {
+        let file_id = database.read_only().file_ids().next().expect("database has a file");
+
+        needles
+            .iter()
+            .map(|needle| {
+                let start = u32::try_from(source.find(needle).expect("needle not found")).expect("offset fits in u32");
+                let end = start + u32::try_from(needle.len()).expect("length fits in u32");
+
+                Issue::warning("test").with_code("loose-comparison").with_annotation(Annotation::primary(Span::new(
+                    file_id,
+                    Position::new(start),
+                    Position::new(end),
+                )))
+            })
+            .collect()
+    }
+
+    #[test]
+    fn filters_issues_present_at_the_revision() {
+        let old = "<?php\n\nfunction a() {\n    return $x == 1;\n}\n";
+        let new = "<?php\n\nfunction b() { return 2; }\n\nfunction a() {\n    return $x   ==   1;\n}\n\nfunction c() {\n    return $y == 2;\n}\n";
+        let before = database("src/Old.php", old);
+        let after = database("src/New.php", new);
+
+        let baseline = RevisionBaseline::new(
+            "main",
+            &issues(&before, old, &["$x == 1"]),
+            &before.read_only(),
+            ParserSettings::default(),
+            false,
+        );
+
+        let remaining = baseline.filter_issues(
+            issues(&after, new, &["$x   ==   1", "$y == 2"]),
+            &after.read_only(),
+            ParserSettings::default(),
+        );
+
+        assert_eq!(remaining.len(), 1);
+        assert!(!baseline.ratchet_failed(&remaining));
+    }
+
+    #[test]
+    fn reports_codes_with_more_issues() {
+        let before = BTreeMap::from([("a".to_string(), 2), ("b".to_string(), 1)]);
+        let after = BTreeMap::from([("a".to_string(), 2), ("b".to_string(), 3), ("c".to_string(), 1)]);
+
+        assert_eq!(increased_codes(&before, &after), vec![("b".to_string(), 1, 3), ("c".to_string(), 0, 1)]);
+        assert!(increased_codes(&after, &before).is_empty());
+    }
+}
@@ -49,6 +49,7 @@ use notify::RecursiveMode;
 use notify::Watcher as NotifyWatcher;
 
 use mago_analyzer::code::IssueCode;
+use mago_analyzer::dead_code::UNREACHABLE_SYMBOL_CODES;
 use mago_codex::metadata::CodebaseMetadata;
 use mago_codex::reference::SymbolReferences;
 use mago_database::Database;
@@ -57,13 +58,20 @@ use mago_database::file::FileType;
 use mago_database::watcher::DatabaseWatcher;
 use mago_database::watcher::WatchOptions;
 use mago_orchestrator::Orchestrator;
+use mago_orchestrator::service::analysis_cache::AnalysisCache;
 use mago_prelude::Prelude;
 use mago_reporting::CompiledIgnoreSet;
+use mago_reporting::IssueCollection;
+use mago_reporting::baseline::BaselineTool;
 
+use crate::baseline::revision::RevisionBaseline;
+use crate::baseline::revision::load_revision_database;
 use crate::commands::args::baseline_reporting::BaselineReportingArgs;
+use crate::commands::args::new_since::NewSinceArgs;
 use crate::commands::args::substitution::SubstitutionArgs;
 use crate::commands::stdin_input;
 use crate::config::Configuration;
+use crate::config::analyzer::AnalyzerConfiguration;
 use crate::consts::PRELUDE_BYTES;
 use crate::error::Error;
 use crate::extensions::initialize_external_analyzer;
@@ -132,6 +140,25 @@ pub struct AnalyzeCommand {
     #[arg(long, default_value_t = false)]
     pub skip_ignores: bool,
 
+    /// Enable interprocedural taint analysis for this run.
+    ///
+    /// Tracks untrusted input, such as `$_GET` or request objects, through assignments,
+    /// calls, returns, and properties, and reports it reaching HTML output, SQL queries,
+    /// shell commands, includes, or `unserialize()` without being sanitized.
+    /// Equivalent to setting `check-taints = true` in the analyzer configuration.
+    #[arg(long, default_value_t = false)]
+    pub taint_analysis: bool,
+
+    /// Report only the classes, methods, constants, and properties unreachable from any entry point.
+    ///
+    /// Walks the references of the whole program from top-level code and the configured
+    /// entry points, such as controllers, console commands, event subscribers, `#[Route]`
+    /// methods, and the classes named in configuration files. Every symbol that is never
+    /// reached is reported, grouped by namespace, with an edit deleting it.
+    /// Equivalent to setting `find-unreachable-symbols = true` and retaining only its issues.
+    #[arg(long, default_value_t = false, conflicts_with_all = ["list_codes", "watch", "staged", "stdin_input"])]
+    pub dead_code_report: bool,
+
     /// Enable watch mode for continuous analysis (experimental).
     ///
     /// When enabled, the analyzer watches the workspace for file changes and
@@ -144,7 +171,7 @@ pub struct AnalyzeCommand {
     /// with the updated configuration.
     ///
     /// Press Ctrl+C to stop watching.
-    #[arg(long, default_value_t = false, conflicts_with = "substitutions")]
+    #[arg(long, default_value_t = false, conflicts_with_all = ["substitutions", "new_since"])]
     pub watch: bool,
 
     /// List all available analyzer issue codes in JSON format.
@@ -170,6 +197,21 @@ pub struct AnalyzeCommand {
     #[arg(long, conflicts_with_all = ["list_codes", "watch", "staged"])]
     pub stdin_input: bool,
 
+    /// Directory for the persistent analysis cache.
+    ///
+    /// Stores the codebase model and per-file issues between runs, so the next run
+    /// only re-scans files whose content changed and only re-analyzes code affected
+    /// by those changes. Overrides `cache-directory` from the analyzer configuration.
+    ///
+    /// The cache is only used when analyzing the whole project; it is ignored when
+    /// explicit paths, `--staged`, `--stdin-input`, or substitutions are used.
+    #[arg(long, value_name = "DIR", conflicts_with_all = ["list_codes", "watch"])]
+    pub cache_dir: Option<PathBuf>,
+
+    /// Disable the persistent analysis cache, even if one is configured.
+    #[arg(long, default_value_t = false, conflicts_with = "cache_dir")]
+    pub no_cache: bool,
+
     /// Hidden flag to catch `--only` usage and show a helpful error.
     #[arg(long, hide = true, num_args = 1..)]
     pub only: Vec<String>,
@@ -178,6 +220,10 @@ pub struct AnalyzeCommand {
     #[clap(flatten)]
     pub baseline_reporting: BaselineReportingArgs,
 
+    /// Arguments related to reporting only the issues introduced since a git revision.
+    #[clap(flatten)]
+    pub new_since: NewSinceArgs,
+
     /// File-content substitutions (`--substitute ORIG=TEMP`).
     #[clap(flatten)]
     pub substitution: SubstitutionArgs,
@@ -216,7 +262,7 @@ impl AnalyzeCommand {
     ///
     /// Only host files are analyzed for issues; external files only contribute to
     /// the symbol table and type graph.
-    pub fn execute(self, configuration: Configuration, color_choice: ColorChoice) -> Result<ExitCode, Error> {
+    pub fn execute(self, mut configuration: Configuration, color_choice: ColorChoice) -> Result<ExitCode, Error> {
         if !self.only.is_empty() {
             eprintln!("error: the `--only` flag is not available for the analyzer.");
             eprintln!();
@@ -241,6 +287,14 @@ impl AnalyzeCommand {
             return Ok(ExitCode::SUCCESS);
         }
 
+        if self.taint_analysis {
+            configuration.analyzer.check_taints = true;
+        }
+
+        if self.dead_code_report {
+            configuration.analyzer.find_unreachable_symbols = true;
+        }
+
         // Check if watch mode is enabled early, since it needs a restart loop
         if self.watch {
             return self.run_watch_loop(configuration, color_choice);
@@ -254,7 +308,8 @@ impl AnalyzeCommand {
         let substitution_excludes: Vec<String> =
             substitutions.iter().map(|s| s.original.to_string_lossy().into_owned()).collect();
 
-        let mut orchestrator = create_orchestrator(&configuration, color_choice, false, true, false);
+        let cache = self.resolve_analysis_cache(&configuration, substitutions.is_empty());
+        let mut orchestrator = create_orchestrator(&configuration, color_choice, false, true, cache.is_some());
         orchestrator.add_exclude_patterns(configuration.analyzer.excludes.iter());
         orchestrator.add_exclude_patterns(substitution_excludes.iter());
         for substitution in &substitutions {
@@ -329,28 +384,56 @@ impl AnalyzeCommand {
             return Ok(ExitCode::SUCCESS);
         }
 
+        let ignore_set = self.compile_ignore_set(&configuration);
+
+        let revision_baseline_start = trace_enabled.then(Instant::now);
+        let revision_baseline = match &self.new_since.new_since {
+            Some(revision) => {
+                let revision_database = load_revision_database(&database, &configuration.source.workspace, revision)?;
+                let mut revision_issues = orchestrator
+                    .get_analysis_service(revision_database.read_only(), metadata.clone(), symbol_references.clone())
+                    .run()?
+                    .issues;
+                self.filter_issues(&mut revision_issues, &ignore_set, &revision_database);
+
+                Some(RevisionBaseline::new(
+                    revision,
+                    &revision_issues,
+                    &revision_database.read_only(),
+                    orchestrator.config.parser_settings,
+                    self.new_since.ratchet,
+                ))
+            }
+            None => None,
+        };
+        let revision_baseline_duration = revision_baseline_start.map(|s| s.elapsed());
+
         let service_run_start = trace_enabled.then(Instant::now);
-        let service = orchestrator.get_analysis_service(database.read_only(), metadata, symbol_references);
-        let analysis_result = service.run()?;
+        let analysis_result = match &cache {
+            Some(cache) => orchestrator
+                .get_incremental_analysis_service(database.read_only(), metadata, symbol_references)
+                .analyze_with_cache(cache)?,
+            None => orchestrator.get_analysis_service(database.read_only(), metadata, symbol_references).run()?,
+        };
         let service_run_duration = service_run_start.map(|s| s.elapsed());
         let report_start = trace_enabled.then(Instant::now);
         let mut issues = analysis_result.issues;
-        let ignore_set = self.compile_ignore_set(&configuration);
-
-        issues.filter_out_ignored(&ignore_set, |file_id| {
-            database.get_ref(&file_id).ok().map(|f| String::from_utf8_lossy(&f.name).into_owned())
-        });
+        self.filter_issues(&mut issues, &ignore_set, &database);
 
-        let baseline = configuration.analyzer.baseline.as_deref();
+        let baseline = configuration.baseline_location(BaselineTool::Analyzer);
         let baseline_variant = configuration.analyzer.baseline_variant;
-        let processor = self.baseline_reporting.get_processor(
-            color_choice,
-            baseline,
-            baseline_variant,
-            configuration.editor_url.clone(),
-            configuration.analyzer.minimum_fail_level,
-            self.staged || !self.path.is_empty() || self.stdin_input,
-        );
+        let processor = self
+            .baseline_reporting
+            .get_processor(
+                color_choice,
+                BaselineTool::Analyzer,
+                baseline,
+                baseline_variant,
+                configuration.editor_url.clone(),
+                configuration.analyzer.minimum_fail_level,
+                self.staged || !self.path.is_empty() || self.stdin_input,
+            )
+            .with_revision_baseline(revision_baseline);
 
         let (exit_code, changed_file_ids) = processor.process_issues(&orchestrator, &mut database, issues)?;
         let report_duration = report_start.map(|s| s.elapsed());
@@ -372,6 +455,7 @@ impl AnalyzeCommand {
             tracing::trace!("Prelude decoded in {:?} (concurrent task).", prelude_duration.unwrap_or_default());
             tracing::trace!("Database loaded in {:?} (concurrent task).", load_database_duration.unwrap_or_default());
             tracing::trace!("Prelude and database loaded in {:?}.", load_inputs_duration.unwrap_or_default());
+            tracing::trace!("Revision analyzed in {:?}.", revision_baseline_duration.unwrap_or_default());
             tracing::trace!("Analysis service ran in {:?}.", service_run_duration.unwrap_or_default());
             tracing::trace!("Issues filtered and reported in {:?}.", report_duration.unwrap_or_default());
             tracing::trace!("Database dropped in {:?}.", drop_database_duration.unwrap_or_default());
@@ -382,6 +466,59 @@ impl AnalyzeCommand {
         Ok(exit_code)
     }
 
+    /// Retains the issues to report: only unreachable symbols with `--dead-code-report`,
+    /// and none of the issues ignored by configuration.
+    fn filter_issues(&self, issues: &mut IssueCollection, ignore_set: &CompiledIgnoreSet, database: &Database<'_>) {
+        if self.dead_code_report {
+            let codes = UNREACHABLE_SYMBOL_CODES.iter().map(|code| code.as_str().to_string()).collect::<Vec<_>>();
+            issues.filter_retain_codes(&codes);
+        }
+
+        issues.filter_out_ignored(ignore_set, |file_id| {
+            database.get_ref(&file_id).ok().map(|f| String::from_utf8_lossy(&f.name).into_owned())
+        });
+    }
+
+    /// Resolves the persistent analysis cache for this run, if one is enabled.
+    ///
+    /// The cache only applies to whole-project runs: analyzing a subset of the project
+    /// changes which files are analyzed, which the cached per-file state does not track.
+    fn resolve_analysis_cache(&self, configuration: &Configuration, whole_project: bool) -> Option<AnalysisCache> {
+        if self.no_cache {
+            return None;
+        }
+
+        let directory = self.cache_dir.as_deref().or(configuration.analyzer.cache_directory.as_deref())?;
+        if !whole_project || self.staged || self.stdin_input || !self.path.is_empty() {
+            tracing::debug!("Not using the analysis cache because only part of the project is analyzed.");
+
+            return None;
+        }
+
+        // Ignores, the baseline, and the fail level only filter the reported issues, so
+        // changing them must not throw the cached results away.
+        let defaults = AnalyzerConfiguration::default();
+        let analyzer = AnalyzerConfiguration {
+            ignore: defaults.ignore,
+            baseline: defaults.baseline,
+            baseline_variant: defaults.baseline_variant,
+            cache_directory: defaults.cache_directory,
+            minimum_fail_level: defaults.minimum_fail_level,
+            ..configuration.analyzer.clone()
+        };
+
+        let hashed = serde_json::to_vec(&(
+            configuration.php_version,
+            &configuration.source,
+            &configuration.parser,
+            &analyzer,
+            self.no_stubs,
+        ))
+        .ok()?;
+
+        Some(AnalysisCache::new(directory, configuration.php_version, xxhash_rust::xxh3::xxh3_64(&hashed)))
+    }
+
     /// Compiles the configured ignore entries, or an empty set with `--skip-ignores`.
     fn compile_ignore_set(&self, configuration: &Configuration) -> CompiledIgnoreSet {
         if self.skip_ignores {
@@ -449,6 +586,9 @@ impl AnalyzeCommand {
                     ) {
                         Ok(new_config) => {
                             configuration = new_config;
+                            if self.taint_analysis {
+                                configuration.analyzer.check_taints = true;
+                            }
                         }
                         Err(e) => {
                             tracing::error!("Failed to reload configuration: {e}");
@@ -502,11 +642,12 @@ impl AnalyzeCommand {
             read_db.get_ref(&file_id).ok().map(|f| String::from_utf8_lossy(&f.name).into_owned())
         });
 
-        let baseline = configuration.analyzer.baseline.as_deref();
+        let baseline = configuration.baseline_location(BaselineTool::Analyzer);
         let baseline_variant = configuration.analyzer.baseline_variant;
 
         let processor = self.baseline_reporting.get_processor(
             color_choice,
+            BaselineTool::Analyzer,
             baseline,
             baseline_variant,
             configuration.editor_url.clone(),
@@ -577,8 +718,9 @@ fn setup_restart_watcher(workspace: &Path, configuration: &Configuration) -> Res
         }
     }
 
-    if let Some(baseline) = &configuration.analyzer.baseline {
-        let path = if baseline.is_absolute() { baseline.clone() } else { workspace.join(baseline) };
+    if let Some(baseline) = configuration.baseline_location(BaselineTool::Analyzer) {
+        let path =
+            if baseline.path.is_absolute() { baseline.path.to_path_buf() } else { workspace.join(baseline.path) };
 
         watch_files.push((path, "baseline file"));
     }
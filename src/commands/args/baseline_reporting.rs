@@ -18,8 +18,10 @@ use clap::ArgGroup;
 use clap::ColorChoice;
 use clap::Parser;
 use mago_reporting::Level;
+use mago_reporting::baseline::BaselineTool;
 use mago_reporting::baseline::BaselineVariant;
 
+use crate::baseline::BaselineLocation;
 use crate::commands::args::reporting::ReportingArgs;
 use crate::service::BaselineIssueProcessor;
 
@@ -37,6 +39,7 @@ pub struct BaselineReportingArgs {
     /// A baseline file contains a list of known issues that should be ignored
     /// in future runs. This is useful for gradually improving code quality by
     /// focusing on new issues while suppressing existing ones.
+    /// When the file is a project baseline, the section of the current tool is used.
     /// Can be overridden by configuration in mago.toml.
     #[arg(long, value_name = "PATH")]
     pub baseline: Option<PathBuf>,
@@ -118,37 +121,45 @@ impl BaselineReportingArgs {
     /// # Arguments
     ///
     /// * `color_choice` - Whether to use colored output
-    /// * `baseline` - Optional baseline path from configuration (overridden by CLI arg)
+    /// * `baseline_tool` - The tool whose section of a project baseline is used
+    /// * `baseline` - Optional baseline location from configuration (overridden by CLI arg)
     /// * `baseline_variant` - The variant to use when generating new baselines
     ///
     /// # Returns
     ///
     /// A [`BaselineIssueProcessor`] configured with all the baseline and reporting
     /// options from this argument set.
+    #[allow(clippy::too_many_arguments)]
     pub fn get_processor(
         &self,
         color_choice: ColorChoice,
-        baseline: Option<&Path>,
+        baseline_tool: BaselineTool,
+        baseline: Option<BaselineLocation<'_>>,
         baseline_variant: BaselineVariant,
         editor_url: Option<String>,
         config_minimum_fail_level: Level,
         scoped_analysis: bool,
     ) -> BaselineIssueProcessor {
+        let baseline_path: Option<Cow<'static, Path>> = if self.ignore_baseline {
+            None
+        } else {
+            match &self.baseline {
+                Some(path) => Some(Cow::Owned(path.to_path_buf())),
+                None => baseline.map(|location| Cow::Owned(location.path.to_path_buf())),
+            }
+        };
+
         BaselineIssueProcessor {
-            baseline_path: if self.ignore_baseline {
-                None
-            } else {
-                match &self.baseline {
-                    Some(path) => Some(Cow::Owned(path.to_path_buf())),
-                    None => baseline.map(|p| Cow::Owned(p.to_path_buf())),
-                }
-            },
+            baseline_path,
+            baseline_tool,
+            project_baseline: self.baseline.is_none() && baseline.is_some_and(|location| location.is_project),
             generate_baseline: self.generate_baseline,
             backup_baseline: self.backup_baseline,
             verify_baseline: self.verify_baseline,
             remove_outdated_baseline_entries: self.remove_outdated_baseline_entries,
             fail_on_out_of_sync_baseline: self.fail_on_out_of_sync_baseline,
             baseline_variant,
+            revision_baseline: None,
             issue_processor: self.reporting.get_processor(
                 color_choice,
                 editor_url,
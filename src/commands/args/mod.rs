@@ -8,6 +8,7 @@
 //!
 //! - [`reporting::ReportingArgs`]: Core issue reporting and fixing options
 //! - [`baseline_reporting::BaselineReportingArgs`]: Baseline management combined with reporting
+//! - [`new_since::NewSinceArgs`]: Reporting only the issues introduced since a git revision
 //!
 //! # Design Pattern
 //!
@@ -27,5 +28,6 @@
 //! support reporting or baseline functionality.
 
 pub mod baseline_reporting;
+pub mod new_since;
 pub mod reporting;
 pub mod substitution;
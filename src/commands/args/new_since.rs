@@ -0,0 +1,44 @@
+//! Command-line arguments for reporting only the issues introduced since a git revision.
+//!
+//! These arguments offer baseline-less adoption for pull requests: instead of
+//! suppressing known issues through a baseline file, the tool is also run on
+//! the project as it was at a base revision, and only the issues the branch
+//! introduced are reported.
+
+use clap::Parser;
+
+/// Command-line arguments for comparing issues against a git revision.
+///
+/// This struct is designed to be flattened into commands that also flatten
+/// [`BaselineReportingArgs`](crate::commands::args::baseline_reporting::BaselineReportingArgs).
+#[derive(Parser, Debug, Clone)]
+pub struct NewSinceArgs {
+    /// Report only the issues introduced since a git revision.
+    ///
+    /// The project's files are also read as they were at the revision, straight
+    /// from git without a checkout, and the issues found there are matched to the
+    /// current ones by code, enclosing symbol and statement fingerprint. Moved,
+    /// reformatted and renamed code keeps matching. Replaces the baseline file.
+    #[arg(
+        long,
+        value_name = "REVISION",
+        conflicts_with_all = [
+            "baseline",
+            "generate_baseline",
+            "verify_baseline",
+            "remove_outdated_baseline_entries",
+            "fail_on_out_of_sync_baseline",
+            "staged",
+            "stdin_input",
+            "substitutions",
+        ]
+    )]
+    pub new_since: Option<String>,
+
+    /// Also fail when the number of issues of any code went up since the revision.
+    ///
+    /// Fixing an issue elsewhere does not make room for a new one of another code.
+    /// Requires --new-since.
+    #[arg(long, requires = "new_since")]
+    pub ratchet: bool,
+}
@@ -28,6 +28,8 @@
 //! The `minimum_fail_level` determines when the command exits with failure.
 //! This enables CI integration where certain issue severities should fail builds.
 
+use std::collections::HashMap;
+
 use clap::ColorChoice;
 use clap::Parser;
 
@@ -61,7 +63,8 @@ pub struct ReportingArgs {
     /// This option provides a more organized view for reviewing large numbers of issues.
     ///
     /// No-op for aggregate-formats (count, code-count) and formats where the consumer handles
-    /// ordering (github, gitlab, sarif, checkstyle, emacs), they are always unordered.
+    /// ordering (github, gitlab, sarif, checkstyle, emacs, sonarqube, junit, code-climate), they are
+    /// always unordered.
     #[arg(long)]
     pub sort: bool,
 
@@ -235,6 +238,7 @@ impl ReportingArgs {
             color_choice,
             editor_url,
             scoped_analysis,
+            issue_categories: HashMap::new(),
         }
     }
 }
@@ -32,7 +32,7 @@ use clap::Parser;
 use clap::ValueEnum;
 use schemars::schema_for;
 
-use mago_reporting::baseline::Baseline;
+use mago_reporting::baseline::BaselineFile;
 
 use crate::config::Configuration;
 use crate::config::analyzer::AnalyzerConfiguration;
@@ -164,7 +164,7 @@ impl ConfigCommand {
                         serde_json::to_string_pretty(&schema)?
                     }
                     ConfigSection::Baseline => {
-                        let schema = schema_for!(Baseline);
+                        let schema = schema_for!(BaselineFile);
                         serde_json::to_string_pretty(&schema)?
                     }
                 }
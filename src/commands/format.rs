@@ -13,6 +13,12 @@
 //! - **Dry Run** (`--dry-run`): Shows what would change via diff without modifying files
 //! - **STDIN Mode** (`--stdin-input`): Reads from stdin, writes formatted code to stdout
 //!
+//! Any of these modes, except `--staged`, can be limited to part of a single file with
+//! `--range` (lines) or `--byte-range` (byte offsets): only the statements or class members
+//! enclosing the range are reformatted, and the rest of the file is kept byte for byte.
+//! Similarly, `--changed-since <REF>` reformats only the statements touched by the lines
+//! changed since a git revision, so a codebase can adopt the formatter gradually.
+//!
 //! # Configuration
 //!
 //! Formatting style is configured in `mago.toml` under the `[formatter]` section:
@@ -38,6 +44,7 @@
 
 use std::borrow::Cow;
 use std::io::Read;
+use std::ops::Range;
 use std::path::Path;
 use std::path::PathBuf;
 use std::process::ExitCode;
@@ -135,6 +142,31 @@ pub struct FormatCommand {
     /// - Not in a git repository
     #[arg(long, short = 's', conflicts_with_all = ["dry_run", "check", "stdin_input", "path"])]
     pub staged: bool,
+
+    /// Format only the lines changed since the given git revision.
+    ///
+    /// Compares the working tree, including staged and unstaged changes, against
+    /// the revision (e.g. `main` or `HEAD~1`) and reformats only the statements or
+    /// class members the changed lines touch. This lets a codebase adopt the
+    /// formatter gradually, without one large commit that rewrites `git blame`.
+    ///
+    /// Untracked files are not considered. Twig templates are skipped.
+    #[arg(long, value_name = "REF", conflicts_with_all = ["stdin_input", "staged", "range", "byte_range"])]
+    pub changed_since: Option<String>,
+
+    /// Format only the given lines of a single file, as `START:END` (1-based, inclusive).
+    ///
+    /// Only the smallest run of statements or class members enclosing the range is
+    /// reformatted, together with the comments attached to it; every other byte of the
+    /// file is kept as is. Requires exactly one file path, or `--stdin-input`.
+    #[arg(long, value_name = "START:END", value_parser = parse_line_range, conflicts_with_all = ["staged", "byte_range"])]
+    pub range: Option<(u32, u32)>,
+
+    /// Format only the given bytes of a single file, as `START:END` (0-based, end exclusive).
+    ///
+    /// Behaves like `--range`, but takes byte offsets, as editors usually report selections.
+    #[arg(long, value_name = "START:END", value_parser = parse_byte_range, conflicts_with_all = ["staged", "range"])]
+    pub byte_range: Option<(u32, u32)>,
 }
 
 impl FormatCommand {
@@ -184,6 +216,14 @@ impl FormatCommand {
             return self.execute_stdin(orchestrator, &configuration);
         }
 
+        if self.range.is_some() || self.byte_range.is_some() {
+            return self.execute_range(&orchestrator, &configuration, color_choice);
+        }
+
+        if let Some(revision) = &self.changed_since {
+            return self.execute_changed_since(&orchestrator, &configuration, revision, color_choice);
+        }
+
         let mut database = orchestrator.load_database(&configuration.source.workspace, false, None, None)?;
         let service = orchestrator.get_format_service(database.read_only());
 
@@ -195,6 +235,12 @@ impl FormatCommand {
             tracing::error!("Failed to parse file '{}': {parse_error}", mago_bytes::BytesDisplay(&file.name));
         }
 
+        for (file_id, parse_error) in result.template_parse_errors() {
+            let file = database.get_ref(file_id)?;
+
+            tracing::error!("Failed to parse template '{}': {parse_error}", mago_bytes::BytesDisplay(&file.name));
+        }
+
         let changed_files_count = result.changed_files_count();
 
         if changed_files_count == 0 {
@@ -247,7 +293,12 @@ impl FormatCommand {
 
         let Some(filepath) = self.stdin_filepath.as_deref() else {
             let file = File::ephemeral(Cow::Borrowed(b"<stdin>"), Cow::Owned(content));
-            return Ok(emit_stdin_result(orchestrator.format_file(&file)?, &file));
+            let status = match self.byte_range_in(&file) {
+                Some(range) => orchestrator.format_file_range(&file, range)?,
+                None => orchestrator.format_file(&file)?,
+            };
+
+            return Ok(emit_stdin_result(status, &file));
         };
 
         let logical_name = stdin_logical_name(filepath, &configuration.source.workspace);
@@ -270,11 +321,210 @@ impl FormatCommand {
             }
         };
 
-        let status = orchestrator.get_format_service(database.read_only()).format_file(&file)?;
+        let service = orchestrator.get_format_service(database.read_only());
+        let status = match self.byte_range_in(&file) {
+            Some(range) => service.format_file_range(&file, range)?,
+            None => service.format_file(&file)?,
+        };
 
         Ok(emit_stdin_result(status, &file))
     }
 
+    /// Executes the `--range` and `--byte-range` flows for a single file on disk.
+    fn execute_range(
+        self,
+        orchestrator: &mago_orchestrator::Orchestrator<'_>,
+        configuration: &Configuration,
+        color_choice: ColorChoice,
+    ) -> Result<ExitCode, Error> {
+        let [path] = self.path.as_slice() else {
+            return Err(Error::InvalidArgument(
+                "Range formatting requires exactly one file path, or `--stdin-input`.".to_string(),
+            ));
+        };
+
+        if !path.is_file() {
+            return Err(Error::InvalidArgument(format!(
+                "Range formatting requires a file, but '{}' is not one.",
+                path.display()
+            )));
+        }
+
+        let mut database = orchestrator.load_database(&configuration.source.workspace, false, None, None)?;
+        let canonical_path = path.canonicalize().unwrap_or_else(|_| path.clone());
+        let Ok(file) = database.get_by_path(&canonical_path) else {
+            tracing::info!("File '{}' is excluded from formatting.", path.display());
+
+            return Ok(ExitCode::SUCCESS);
+        };
+
+        let Some(range) = self.byte_range_in(&file) else {
+            return Ok(ExitCode::SUCCESS);
+        };
+
+        let new_content = match orchestrator.get_format_service(database.read_only()).format_file_range(&file, range)? {
+            FileFormatStatus::Unchanged => {
+                tracing::info!("The range is already formatted.");
+
+                return Ok(ExitCode::SUCCESS);
+            }
+            FileFormatStatus::Changed(new_content) => new_content,
+            FileFormatStatus::FailedToParse(parse_error) => {
+                tracing::error!("Failed to parse file '{}': {parse_error}", mago_bytes::BytesDisplay(&file.name));
+
+                return Ok(ExitCode::from(EXIT_CODE_ERROR));
+            }
+            FileFormatStatus::FailedToParseTemplate(parse_error) => {
+                tracing::error!("Failed to parse template '{}': {parse_error}", mago_bytes::BytesDisplay(&file.name));
+
+                return Ok(ExitCode::from(EXIT_CODE_ERROR));
+            }
+            FileFormatStatus::FailedToIsolateRange => {
+                tracing::error!(
+                    "Cannot format the range of '{}' on its own, as formatting changes the statements around it. Format the whole file instead.",
+                    mago_bytes::BytesDisplay(&file.name)
+                );
+
+                return Ok(ExitCode::from(EXIT_CODE_ERROR));
+            }
+        };
+
+        if self.check {
+            tracing::info!("The range needs formatting. Run the command without '--check' to format it.");
+
+            return Ok(ExitCode::FAILURE);
+        }
+
+        let change_log = ChangeLog::new();
+        utils::apply_update(&change_log, &file, &new_content, self.dry_run, color_choice)?;
+        database.commit(change_log, true)?;
+
+        if self.dry_run {
+            tracing::info!("The range needs formatting.");
+
+            Ok(ExitCode::FAILURE)
+        } else {
+            tracing::info!("Formatted the range successfully.");
+
+            Ok(ExitCode::SUCCESS)
+        }
+    }
+
+    /// Executes formatting for the lines changed since a git revision.
+    ///
+    /// Every changed PHP file that is part of the source paths is formatted with
+    /// [`FormatService::format_file_ranges`], limited to its added or modified lines.
+    ///
+    /// [`FormatService::format_file_ranges`]: mago_orchestrator::service::format::FormatService::format_file_ranges
+    fn execute_changed_since(
+        &self,
+        orchestrator: &mago_orchestrator::Orchestrator<'_>,
+        configuration: &Configuration,
+        revision: &str,
+        color_choice: ColorChoice,
+    ) -> Result<ExitCode, Error> {
+        let workspace = &configuration.source.workspace;
+        let changed_files = git::get_changed_lines(workspace, revision)?;
+        if changed_files.is_empty() {
+            tracing::info!("No lines changed since `{revision}`.");
+            return Ok(ExitCode::SUCCESS);
+        }
+
+        let mut database = orchestrator.load_database(workspace, false, None, None)?;
+        let change_log = ChangeLog::new();
+        let mut changed_files_count = 0;
+        let mut has_errors = false;
+        for (path, lines) in changed_files {
+            let absolute_path = workspace.join(&path);
+            let canonical_path = absolute_path.canonicalize().unwrap_or(absolute_path);
+
+            let Ok(file) = database.get_by_path(&canonical_path) else {
+                continue;
+            };
+
+            if file.language().is_twig() {
+                tracing::debug!("Skipping template '{}': range formatting only supports PHP.", path.display());
+                continue;
+            }
+
+            let ranges: Vec<Range<u32>> = lines
+                .into_iter()
+                .map(|lines| {
+                    let start = file.get_line_start_offset(lines.start() - 1).unwrap_or(file.size);
+                    let end = file.get_line_end_offset(lines.end() - 1).unwrap_or(file.size);
+
+                    start..end.max(start)
+                })
+                .collect();
+
+            match orchestrator.get_format_service(database.read_only()).format_file_ranges(&file, &ranges)? {
+                FileFormatStatus::Unchanged => {}
+                FileFormatStatus::Changed(new_content) => {
+                    changed_files_count += 1;
+                    if !self.check {
+                        utils::apply_update(&change_log, &file, &new_content, self.dry_run, color_choice)?;
+                    }
+                }
+                FileFormatStatus::FailedToParse(parse_error) => {
+                    has_errors = true;
+                    tracing::error!("Failed to parse file '{}': {parse_error}", path.display());
+                }
+                FileFormatStatus::FailedToParseTemplate(parse_error) => {
+                    has_errors = true;
+                    tracing::error!("Failed to parse template '{}': {parse_error}", path.display());
+                }
+                FileFormatStatus::FailedToIsolateRange => {
+                    has_errors = true;
+                    tracing::error!(
+                        "Cannot format the changed lines of '{}' on their own, as formatting changes the statements around them. Format the whole file instead.",
+                        path.display()
+                    );
+                }
+            }
+        }
+
+        database.commit(change_log, true)?;
+
+        if changed_files_count == 0 {
+            tracing::info!("All lines changed since `{revision}` are already formatted.");
+
+            return Ok(if has_errors { ExitCode::from(EXIT_CODE_ERROR) } else { ExitCode::SUCCESS });
+        }
+
+        if self.check {
+            tracing::info!(
+                "Found {changed_files_count} file(s) with changed lines that need formatting. Run the command without '--check' to format them.",
+            );
+
+            return Ok(ExitCode::FAILURE);
+        }
+
+        let exit_code = if self.dry_run {
+            tracing::info!("Found {changed_files_count} file(s) with changed lines that need formatting.");
+
+            ExitCode::FAILURE
+        } else {
+            tracing::info!("Formatted the changed lines of {changed_files_count} file(s) successfully.");
+
+            ExitCode::SUCCESS
+        };
+
+        Ok(exit_code)
+    }
+
+    /// Resolves `--range` or `--byte-range` to byte offsets in `file`, clamped to its size.
+    fn byte_range_in(&self, file: &File) -> Option<Range<u32>> {
+        if let Some((start, end)) = self.byte_range {
+            return Some(start.min(file.size)..end.min(file.size));
+        }
+
+        let (first_line, last_line) = self.range?;
+        let start = file.get_line_start_offset(first_line - 1).unwrap_or(file.size);
+        let end = file.get_line_end_offset(last_line - 1).unwrap_or(file.size);
+
+        Some(start..end.max(start))
+    }
+
     /// Executes formatting for staged files.
     ///
     /// This method implements the `--staged` mode for git pre-commit hooks:
@@ -318,7 +568,8 @@ impl FormatCommand {
             }
             let staged_file = get_staged_file(workspace, &path)?;
             match orchestrator.format_file(&staged_file)? {
-                FileFormatStatus::Unchanged => continue,
+                // Staged files are formatted as a whole, so their range is never isolated.
+                FileFormatStatus::Unchanged | FileFormatStatus::FailedToIsolateRange => continue,
                 FileFormatStatus::Changed(new_content) => {
                     update_staged_file(workspace, &path, new_content)?;
                     changed_files_count += 1;
@@ -326,6 +577,9 @@ impl FormatCommand {
                 FileFormatStatus::FailedToParse(parse_error) => {
                     tracing::error!("Failed to parse staged file '{}': {}", path.display(), parse_error);
                 }
+                FileFormatStatus::FailedToParseTemplate(parse_error) => {
+                    tracing::error!("Failed to parse staged template '{}': {}", path.display(), parse_error);
+                }
             };
         }
 
@@ -370,9 +624,49 @@ fn emit_stdin_result(status: FileFormatStatus, file: &File) -> ExitCode {
             tracing::error!("Failed to parse {}: {parse_error}", mago_bytes::BytesDisplay(&file.name));
             ExitCode::from(EXIT_CODE_ERROR)
         }
+        FileFormatStatus::FailedToParseTemplate(parse_error) => {
+            tracing::error!("Failed to parse template {}: {parse_error}", mago_bytes::BytesDisplay(&file.name));
+            ExitCode::from(EXIT_CODE_ERROR)
+        }
+        FileFormatStatus::FailedToIsolateRange => {
+            tracing::error!(
+                "Cannot format the range of {} on its own, as formatting changes the statements around it.",
+                mago_bytes::BytesDisplay(&file.name)
+            );
+            ExitCode::from(EXIT_CODE_ERROR)
+        }
     }
 }
 
+/// Parses a `START:END` range of 1-based, inclusive line numbers.
+fn parse_line_range(value: &str) -> Result<(u32, u32), String> {
+    let (start, end) = parse_range(value)?;
+    if start == 0 {
+        return Err("line numbers start at 1".to_string());
+    }
+
+    Ok((start, end))
+}
+
+/// Parses a `START:END` range of byte offsets.
+fn parse_byte_range(value: &str) -> Result<(u32, u32), String> {
+    parse_range(value)
+}
+
+fn parse_range(value: &str) -> Result<(u32, u32), String> {
+    let Some((start, end)) = value.split_once(':') else {
+        return Err(format!("expected `START:END`, found `{value}`"));
+    };
+
+    let start = start.trim().parse::<u32>().map_err(|error| format!("invalid start `{start}`: {error}"))?;
+    let end = end.trim().parse::<u32>().map_err(|error| format!("invalid end `{end}`: {error}"))?;
+    if end < start {
+        return Err(format!("the end of the range ({end}) is before its start ({start})"));
+    }
+
+    Ok((start, end))
+}
+
 /// Computes a workspace-relative, forward-slash logical name for `filepath`.
 ///
 /// Falls back to the path as given when it isn't a descendant of the workspace.
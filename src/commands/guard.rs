@@ -42,14 +42,20 @@ use std::path::PathBuf;
 use std::process::ExitCode;
 use std::time::Instant;
 
+use clap::ArgGroup;
 use clap::ColorChoice;
 use clap::Parser;
+use clap::ValueEnum;
 
 use mago_database::DatabaseReader;
 use mago_database::file::FileType;
+use mago_guard::metrics::PackageMetrics;
+use mago_guard::metrics::package_metrics;
 use mago_guard::settings::GuardMode;
 use mago_prelude::Prelude;
 
+use mago_reporting::baseline::BaselineTool;
+
 use crate::commands::args::baseline_reporting::BaselineReportingArgs;
 use crate::commands::args::substitution::SubstitutionArgs;
 use crate::commands::stdin_input;
@@ -58,6 +64,26 @@ use crate::consts::PRELUDE_BYTES;
 use crate::error::Error;
 use crate::utils::create_orchestrator;
 
+/// The format of the namespace dependency graph printed by `--graph`.
+#[derive(ValueEnum, Debug, Clone, Copy, PartialEq, Eq)]
+pub enum GraphFormat {
+    /// The DOT language of Graphviz.
+    Dot,
+    /// A Mermaid flowchart.
+    Mermaid,
+    /// A JSON document listing the nodes and edges.
+    Json,
+}
+
+/// The format of the package metrics printed by `--metrics`.
+#[derive(ValueEnum, Debug, Clone, Copy, PartialEq, Eq)]
+pub enum MetricsFormat {
+    /// A table with one package per row.
+    Table,
+    /// A JSON array with one object per package.
+    Json,
+}
+
 /// Command for enforcing architectural rules and layer dependencies.
 ///
 /// The `guard` command performs architectural boundary checking on your PHP codebase.
@@ -75,6 +101,7 @@ use crate::utils::create_orchestrator;
 /// depend on others and what types of symbols are allowed.
 #[derive(Parser, Debug)]
 #[command(name = "guard")]
+#[command(group(ArgGroup::new("export").args(["graph", "metrics"])))]
 pub struct GuardCommand {
     /// Specific files or directories to check instead of using configuration.
     ///
@@ -108,6 +135,35 @@ pub struct GuardCommand {
     #[arg(long, conflicts_with = "structural")]
     pub perimeter: bool,
 
+    /// Print the namespace dependency graph instead of reporting issues.
+    ///
+    /// Every dependency between namespaces is aggregated into an edge labelled with its
+    /// count per kind of symbol. Namespaces are grouped by their `layering` entry, and edges
+    /// holding dependencies that breach the perimeter rules are highlighted.
+    #[arg(long, value_name = "FORMAT", conflicts_with_all = ["structural", "stdin_input"])]
+    pub graph: Option<GraphFormat>,
+
+    /// Print the coupling metrics of each namespace instead of reporting issues.
+    ///
+    /// For every namespace declaring classes, prints its afferent coupling (Ca, the number
+    /// of namespaces depending on it), efferent coupling (Ce, the number of namespaces it
+    /// depends on), instability (I), abstractness (A), and distance from the main sequence (D).
+    #[arg(
+        long,
+        value_name = "FORMAT",
+        num_args = 0..=1,
+        default_missing_value = "table",
+        conflicts_with_all = ["stdin_input"],
+    )]
+    pub metrics: Option<MetricsFormat>,
+
+    /// The number of namespace segments to keep in the graph or metrics.
+    ///
+    /// At depth 2, `App\Domain\User` and `App\Domain\Order` are both shown as `App\Domain`.
+    /// By default, namespaces are kept whole, or cut to `[guard.structural.metrics].depth` for `--metrics`.
+    #[arg(long, value_name = "N", requires = "export", value_parser = clap::value_parser!(u16).range(1..))]
+    pub depth: Option<u16>,
+
     /// Read the file content from stdin and use the given path for baseline and reporting.
     ///
     /// Intended for editor integrations: pipe unsaved buffer content and pass the real file path.
@@ -229,6 +285,32 @@ impl GuardCommand {
             return Ok(ExitCode::SUCCESS);
         }
 
+        if let Some(format) = self.graph {
+            let service = orchestrator.get_guard_service(database.read_only(), metadata);
+            let graph = service.graph(self.depth.map(usize::from))?;
+
+            match format {
+                GraphFormat::Dot => print!("{}", graph.to_dot()),
+                GraphFormat::Mermaid => print!("{}", graph.to_mermaid()),
+                GraphFormat::Json => println!("{}", serde_json::to_string_pretty(&graph)?),
+            }
+
+            return Ok(ExitCode::SUCCESS);
+        }
+
+        if let Some(format) = self.metrics {
+            let depth = self.depth.map(usize::from).or(configuration.guard.settings.structural.metrics.depth);
+            let service = orchestrator.get_guard_service(database.read_only(), metadata);
+            let metrics = package_metrics(&service.graph(depth)?);
+
+            match format {
+                MetricsFormat::Table => print_metrics_table(&metrics),
+                MetricsFormat::Json => println!("{}", serde_json::to_string_pretty(&metrics)?),
+            }
+
+            return Ok(ExitCode::SUCCESS);
+        }
+
         let guard_run_start = trace_enabled.then(Instant::now);
         let service = orchestrator.get_guard_service(database.read_only(), metadata);
         let result = service.run()?;
@@ -246,10 +328,11 @@ impl GuardCommand {
         }
 
         let report_start = trace_enabled.then(Instant::now);
-        let baseline = configuration.guard.baseline.as_deref();
+        let baseline = configuration.baseline_location(BaselineTool::Guard);
         let baseline_variant = configuration.guard.baseline_variant;
         let processor = self.baseline_reporting.get_processor(
             color_choice,
+            BaselineTool::Guard,
             baseline,
             baseline_variant,
             editor_url,
@@ -282,3 +365,21 @@ impl GuardCommand {
         Ok(exit_code)
     }
 }
+
+/// Prints the package metrics as a table aligned on the longest namespace.
+fn print_metrics_table(metrics: &[PackageMetrics]) {
+    let width = metrics.iter().map(|package| package.namespace.len()).max().unwrap_or_default().max("Namespace".len());
+
+    println!("{:<width$}  {:>5}  {:>5}  {:>5}  {:>5}  {:>5}", "Namespace", "Ca", "Ce", "I", "A", "D");
+    for package in metrics {
+        println!(
+            "{:<width$}  {:>5}  {:>5}  {:>5.2}  {:>5.2}  {:>5.2}",
+            package.namespace,
+            package.afferent_coupling,
+            package.efferent_coupling,
+            package.instability,
+            package.abstractness,
+            package.distance,
+        );
+    }
+}
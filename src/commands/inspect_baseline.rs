@@ -3,6 +3,8 @@
 //! Large baselines (thousands of entries across thousands of lines) are tedious to read by
 //! hand. This command summarizes a baseline so it is easy to see, at a glance, which issue
 //! codes and which files dominate it - and to drill into a single code or a single file.
+//! Entries can also be grouped and filtered by the owner, ticket, and date recorded on them,
+//! and by tool when the file is a project baseline.
 
 use std::borrow::Cow;
 use std::collections::BTreeMap;
@@ -10,13 +12,16 @@ use std::path::PathBuf;
 use std::process::ExitCode;
 
 use mago_reporting::baseline::Baseline;
+use mago_reporting::baseline::BaselineEntryMetadata;
+use mago_reporting::baseline::BaselineFile;
+use mago_reporting::baseline::BaselineTool;
 use mago_reporting::osc8_hyperlink;
 
 use clap::ColorChoice;
 use clap::Parser;
 use colored::Colorize;
 
-use crate::baseline::unserialize_baseline;
+use crate::baseline::unserialize_baseline_file;
 use crate::config::Configuration;
 use crate::error::Error;
 use crate::utils::configure_colors;
@@ -29,6 +34,14 @@ pub enum InspectionGrouping {
     File,
     /// Group by issue code, listing the files under each code.
     Code,
+    /// Group by owner, listing the issue codes under each owner.
+    Owner,
+    /// Group by ticket, listing the issue codes under each ticket.
+    Ticket,
+    /// Group by the date entries were added, listing the issue codes under each date.
+    Added,
+    /// Group by tool, listing the issue codes under each tool of a project baseline.
+    Tool,
 }
 
 /// Inspect and visualize the issues recorded in a baseline file.
@@ -54,12 +67,32 @@ pub struct InspectBaselineCommand {
     #[arg(value_name = "CODE_OR_FILE")]
     pub filter: Option<String>,
 
-    /// Group the whole baseline by `code` or by `file`.
+    /// Group the whole baseline by `code`, `file`, `owner`, `ticket`, `added`, or `tool`.
     ///
     /// Ignored when a `CODE_OR_FILE` argument is given (that drills into a single entry instead).
     #[arg(long, value_name = "GROUPING")]
     pub group: Option<InspectionGrouping>,
 
+    /// Only include entries owned by the given owner.
+    #[arg(long, value_name = "OWNER")]
+    pub owner: Option<String>,
+
+    /// Only include entries tracked by the given ticket.
+    #[arg(long, value_name = "TICKET")]
+    pub ticket: Option<String>,
+
+    /// Only include entries added before the given date (`YYYY-MM-DD`).
+    #[arg(long, value_name = "DATE")]
+    pub added_before: Option<String>,
+
+    /// Only include entries added on or after the given date (`YYYY-MM-DD`).
+    #[arg(long, value_name = "DATE")]
+    pub added_since: Option<String>,
+
+    /// Only include the section of the given tool of a project baseline.
+    #[arg(long, value_name = "TOOL", value_parser = ["linter", "analyzer", "guard"])]
+    pub tool: Option<String>,
+
     /// Limit how many entries are shown, both top-level groups and members within each group.
     #[arg(long, value_name = "N")]
     pub limit: Option<usize>,
@@ -92,11 +125,17 @@ impl InspectBaselineCommand {
             },
         };
 
-        let (baseline, _needs_warning) = unserialize_baseline(&baseline_path)?;
-        let entries = flatten_baseline(&baseline);
+        let (baseline, _needs_warning) = unserialize_baseline_file(&baseline_path)?;
+        let all_entries = flatten_baseline_file(&baseline);
+        if all_entries.is_empty() {
+            println!("The baseline at `{}` contains no issues.", baseline_path.display());
+
+            return Ok(ExitCode::SUCCESS);
+        }
 
+        let entries: Vec<FlatEntry<'_>> = all_entries.into_iter().filter(|entry| self.matches(entry)).collect();
         if entries.is_empty() {
-            println!("The baseline at `{}` contains no issues.", baseline_path.display());
+            println!("{}", "No baseline entries match the given filters.".yellow());
 
             return Ok(ExitCode::SUCCESS);
         }
@@ -117,12 +156,46 @@ impl InspectBaselineCommand {
             (None, Some(InspectionGrouping::Code)) => {
                 self.print_groups(group_by(&entries, |entry| entry.code, |entry| entry.file), "code", &links);
             }
+            (None, Some(InspectionGrouping::Owner)) => {
+                let groups = group_by(&entries, |entry| entry.owner.unwrap_or("(no owner)"), |entry| entry.code);
+                self.print_groups(groups, "owner", &links);
+            }
+            (None, Some(InspectionGrouping::Ticket)) => {
+                let groups = group_by(&entries, |entry| entry.ticket.unwrap_or("(no ticket)"), |entry| entry.code);
+                self.print_groups(groups, "ticket", &links);
+            }
+            (None, Some(InspectionGrouping::Added)) => {
+                let groups = group_by(&entries, |entry| entry.added.unwrap_or("(no date)"), |entry| entry.code);
+                self.print_groups(groups, "date", &links);
+            }
+            (None, Some(InspectionGrouping::Tool)) => {
+                let groups = group_by(
+                    &entries,
+                    |entry| entry.tool.map_or("(single tool)", |tool| tool.as_str()),
+                    |entry| entry.code,
+                );
+                self.print_groups(groups, "tool", &links);
+            }
             (None, None) => self.print_code_summary(&entries),
         }
 
         Ok(ExitCode::SUCCESS)
     }
 
+    /// Returns `true` if the entry passes the owner, ticket, date, and tool filters.
+    ///
+    /// Entries without a date never pass the date filters.
+    fn matches(&self, entry: &FlatEntry<'_>) -> bool {
+        let matches_field =
+            |filter: &Option<String>, value: Option<&str>| filter.as_deref().is_none_or(|filter| value == Some(filter));
+
+        matches_field(&self.owner, entry.owner)
+            && matches_field(&self.ticket, entry.ticket)
+            && matches_field(&self.tool, entry.tool.map(|tool| tool.as_str()))
+            && self.added_before.as_deref().is_none_or(|date| entry.added.is_some_and(|added| added < date))
+            && self.added_since.as_deref().is_none_or(|date| entry.added.is_some_and(|added| added >= date))
+    }
+
     /// Prints the default summary: a ranked bar chart of issue codes, most frequent first.
     fn print_code_summary(&self, entries: &[FlatEntry<'_>]) {
         let mut totals: BTreeMap<&str, u32> = BTreeMap::new();
@@ -270,13 +343,38 @@ fn print_bars(rows: &[(&str, u32)], limit: Option<usize>, file_links: Option<&Li
     }
 }
 
-/// A single (file, code) pair from the baseline with its occurrence count.
+/// A single (file, code) pair from the baseline with its occurrence count and metadata.
 ///
-/// Both names borrow from the owned [`Baseline`], so flattening allocates no strings.
+/// All names borrow from the owned [`Baseline`], so flattening allocates no strings.
 struct FlatEntry<'baseline> {
     file: &'baseline str,
     code: &'baseline str,
     count: u32,
+    /// The section the entry belongs to, for project baselines.
+    tool: Option<BaselineTool>,
+    owner: Option<&'baseline str>,
+    ticket: Option<&'baseline str>,
+    added: Option<&'baseline str>,
+}
+
+impl<'baseline> FlatEntry<'baseline> {
+    fn new(
+        file: &'baseline str,
+        code: &'baseline str,
+        count: u32,
+        tool: Option<BaselineTool>,
+        metadata: &'baseline BaselineEntryMetadata,
+    ) -> Self {
+        Self {
+            file,
+            code,
+            count,
+            tool,
+            owner: metadata.owner.as_deref(),
+            ticket: metadata.ticket.as_deref(),
+            added: metadata.added.as_deref(),
+        }
+    }
 }
 
 /// A top-level group (a code or a file) with the members nested under it. All names borrow
@@ -297,12 +395,18 @@ enum ConfiguredBaseline {
     Ambiguous(Vec<PathBuf>),
 }
 
-/// Collects the distinct baselines configured across the analyzer, linter, and guard sections.
+/// Collects the distinct baselines configured across the project, analyzer, linter, and guard
+/// sections.
 ///
 /// No section takes precedence over another: a single distinct path is used, but several
 /// different paths are reported as ambiguous so the user picks one explicitly.
 fn resolve_configured_baseline(configuration: &Configuration) -> ConfiguredBaseline {
-    let candidates = [&configuration.analyzer.baseline, &configuration.linter.baseline, &configuration.guard.baseline];
+    let candidates = [
+        &configuration.baseline,
+        &configuration.analyzer.baseline,
+        &configuration.linter.baseline,
+        &configuration.guard.baseline,
+    ];
 
     let mut distinct: Vec<PathBuf> = Vec::new();
     for path in candidates.into_iter().flatten() {
@@ -318,25 +422,40 @@ fn resolve_configured_baseline(configuration: &Configuration) -> ConfiguredBasel
     }
 }
 
-/// Flattens either baseline variant into a uniform list of (file, code, count) entries that
+/// Flattens a baseline file into a uniform list of entries that borrow their names from `file`.
+fn flatten_baseline_file(file: &BaselineFile) -> Vec<FlatEntry<'_>> {
+    match file {
+        BaselineFile::Tool(baseline) => flatten_baseline(baseline, None),
+        BaselineFile::Project(project) => {
+            project.sections().flat_map(|(tool, baseline)| flatten_baseline(baseline, Some(tool))).collect()
+        }
+    }
+}
+
+/// Flattens any baseline variant into a uniform list of (file, code, count) entries that
 /// borrow their names from `baseline`.
-fn flatten_baseline(baseline: &Baseline) -> Vec<FlatEntry<'_>> {
+fn flatten_baseline(baseline: &Baseline, tool: Option<BaselineTool>) -> Vec<FlatEntry<'_>> {
     match baseline {
         Baseline::Loose(loose) => loose
             .issues
             .iter()
-            .map(|issue| FlatEntry { file: &issue.file, code: &issue.code, count: issue.count })
+            .map(|issue| FlatEntry::new(&issue.file, &issue.code, issue.count, tool, &issue.metadata))
+            .collect(),
+        Baseline::Fingerprint(fingerprint) => fingerprint
+            .issues
+            .iter()
+            .map(|issue| FlatEntry::new(&issue.file, &issue.code, issue.count, tool, &issue.metadata))
             .collect(),
         Baseline::Strict(strict) => {
             let mut entries = Vec::new();
-            for (file, entry) in &strict.entries {
-                let mut per_code: BTreeMap<&str, u32> = BTreeMap::new();
-                for issue in &entry.issues {
-                    *per_code.entry(issue.code.as_str()).or_insert(0) += 1;
+            for (file, file_entry) in &strict.entries {
+                let mut per_code: BTreeMap<(&str, &BaselineEntryMetadata), u32> = BTreeMap::new();
+                for issue in &file_entry.issues {
+                    *per_code.entry((issue.code.as_str(), &issue.metadata)).or_insert(0) += 1;
                 }
 
-                for (code, count) in per_code {
-                    entries.push(FlatEntry { file, code, count });
+                for ((code, metadata), count) in per_code {
+                    entries.push(FlatEntry::new(file, code, count, tool, metadata));
                 }
             }
 
@@ -420,6 +539,7 @@ mod tests {
                     code: code.to_string(),
                     message: String::new(),
                     count,
+                    metadata: BaselineEntryMetadata::default(),
                 })
                 .collect(),
         })
@@ -428,7 +548,7 @@ mod tests {
     #[test]
     fn flatten_loose_keeps_counts() {
         let baseline = loose(vec![("a.php", "mixed-assignment", 3), ("b.php", "mixed-argument", 1)]);
-        let entries = flatten_baseline(&baseline);
+        let entries = flatten_baseline(&baseline, None);
 
         assert_eq!(entries.len(), 2);
         assert_eq!(entries.iter().map(|entry| entry.count).sum::<u32>(), 4);
@@ -441,15 +561,30 @@ mod tests {
             Cow::Borrowed("a.php"),
             StrictBaselineEntry {
                 issues: vec![
-                    StrictBaselineIssue { code: "mixed-assignment".to_string(), start_line: 1, end_line: 1 },
-                    StrictBaselineIssue { code: "mixed-assignment".to_string(), start_line: 5, end_line: 5 },
-                    StrictBaselineIssue { code: "mixed-argument".to_string(), start_line: 9, end_line: 9 },
+                    StrictBaselineIssue {
+                        code: "mixed-assignment".to_string(),
+                        start_line: 1,
+                        end_line: 1,
+                        metadata: BaselineEntryMetadata::default(),
+                    },
+                    StrictBaselineIssue {
+                        code: "mixed-assignment".to_string(),
+                        start_line: 5,
+                        end_line: 5,
+                        metadata: BaselineEntryMetadata::default(),
+                    },
+                    StrictBaselineIssue {
+                        code: "mixed-argument".to_string(),
+                        start_line: 9,
+                        end_line: 9,
+                        metadata: BaselineEntryMetadata::default(),
+                    },
                 ],
             },
         );
 
         let baseline = Baseline::Strict(strict);
-        let entries = flatten_baseline(&baseline);
+        let entries = flatten_baseline(&baseline, None);
 
         let assignment = entries.iter().find(|entry| entry.code == "mixed-assignment");
         assert_eq!(assignment.map(|entry| entry.count), Some(2));
@@ -463,7 +598,7 @@ mod tests {
             ("a.php", "mixed-assignment", 3),
             ("b.php", "mixed-assignment", 2),
         ]);
-        let entries = flatten_baseline(&baseline);
+        let entries = flatten_baseline(&baseline, None);
         let groups = group_by(&entries, |entry| entry.code, |entry| entry.file);
 
         assert_eq!(groups[0].name, "mixed-assignment");
@@ -471,4 +606,91 @@ mod tests {
         assert_eq!(groups[0].members[0].0, "a.php");
         assert_eq!(groups[1].name, "mixed-argument");
     }
+
+    fn command() -> InspectBaselineCommand {
+        InspectBaselineCommand {
+            baseline: None,
+            filter: None,
+            group: None,
+            owner: None,
+            ticket: None,
+            added_before: None,
+            added_since: None,
+            tool: None,
+            limit: None,
+        }
+    }
+
+    /// A loose baseline issue as `(file, code, count, owner, added)`.
+    type OwnedIssue<'a> = (&'a str, &'a str, u32, Option<&'a str>, Option<&'a str>);
+
+    fn owned_loose(issues: Vec<OwnedIssue<'_>>) -> Baseline {
+        let mut baseline = loose(issues.iter().map(|&(file, code, count, _, _)| (file, code, count)).collect());
+        if let Baseline::Loose(loose) = &mut baseline {
+            for (issue, &(_, _, _, owner, added)) in loose.issues.iter_mut().zip(&issues) {
+                issue.metadata.owner = owner.map(str::to_string);
+                issue.metadata.added = added.map(str::to_string);
+            }
+        }
+
+        baseline
+    }
+
+    #[test]
+    fn flatten_project_baseline_tags_tools() {
+        let file = BaselineFile::Project(mago_reporting::baseline::ProjectBaseline {
+            linter: Some(loose(vec![("a.php", "no-shorthand-ternary", 2)])),
+            analyzer: None,
+            guard: Some(loose(vec![("a.php", "must-be-final", 1)])),
+        });
+
+        let entries = flatten_baseline_file(&file);
+        let groups =
+            group_by(&entries, |entry| entry.tool.map_or("(single tool)", |tool| tool.as_str()), |entry| entry.code);
+
+        assert_eq!(groups[0].name, "linter");
+        assert_eq!(groups[0].total, 2);
+        assert_eq!(groups[1].name, "guard");
+    }
+
+    #[test]
+    fn group_by_owner_collects_unowned_entries() {
+        let baseline = owned_loose(vec![
+            ("a.php", "mixed-argument", 4, Some("payments"), None),
+            ("b.php", "mixed-argument", 1, Some("payments"), None),
+            ("c.php", "mixed-assignment", 2, None, None),
+        ]);
+        let entries = flatten_baseline(&baseline, None);
+        let groups = group_by(&entries, |entry| entry.owner.unwrap_or("(no owner)"), |entry| entry.code);
+
+        assert_eq!(groups[0].name, "payments");
+        assert_eq!(groups[0].total, 5);
+        assert_eq!(groups[1].name, "(no owner)");
+    }
+
+    #[test]
+    fn filters_by_owner_and_date() {
+        let baseline = owned_loose(vec![
+            ("a.php", "mixed-argument", 1, Some("payments"), Some("2026-01-10")),
+            ("b.php", "mixed-argument", 1, Some("payments"), Some("2026-06-01")),
+            ("c.php", "mixed-argument", 1, Some("search"), Some("2026-01-10")),
+            ("d.php", "mixed-argument", 1, Some("payments"), None),
+        ]);
+        let entries = flatten_baseline(&baseline, None);
+
+        let mut inspection = command();
+        inspection.owner = Some("payments".to_string());
+        inspection.added_before = Some("2026-03-01".to_string());
+
+        let files: Vec<&str> =
+            entries.iter().filter(|entry| inspection.matches(entry)).map(|entry| entry.file).collect();
+        assert_eq!(files, vec!["a.php"]);
+
+        inspection.added_before = None;
+        inspection.added_since = Some("2026-03-01".to_string());
+
+        let files: Vec<&str> =
+            entries.iter().filter(|entry| inspection.matches(entry)).map(|entry| entry.file).collect();
+        assert_eq!(files, vec!["b.php"]);
+    }
 }
@@ -27,6 +27,7 @@
 //! by ignoring pre-existing issues while catching new ones. See [`BaselineReportingArgs`]
 //! for baseline options.
 
+use std::collections::HashMap;
 use std::path::PathBuf;
 use std::process::ExitCode;
 use std::sync::Arc;
@@ -42,8 +43,12 @@ use mago_linter::rule::AnyRule;
 use mago_linter::rule_meta::RuleEntry;
 use mago_orchestrator::service::lint::LintMode;
 use mago_reporting::Level;
+use mago_reporting::baseline::BaselineTool;
 
+use crate::baseline::revision::RevisionBaseline;
+use crate::baseline::revision::load_revision_database;
 use crate::commands::args::baseline_reporting::BaselineReportingArgs;
+use crate::commands::args::new_since::NewSinceArgs;
 use crate::commands::args::substitution::SubstitutionArgs;
 use crate::commands::stdin_input;
 use crate::config::Configuration;
@@ -188,6 +193,9 @@ pub struct LintCommand {
     #[clap(flatten)]
     pub baseline_reporting: BaselineReportingArgs,
 
+    #[clap(flatten)]
+    pub new_since: NewSinceArgs,
+
     /// File-content substitutions (`--substitute ORIG=TEMP`).
     #[clap(flatten)]
     pub substitution: SubstitutionArgs,
@@ -293,9 +301,10 @@ impl LintCommand {
         })?;
         let load_database_duration = load_database_start.map(|s| s.elapsed());
 
+        let external_linter = external_linter.map(Arc::new);
         let mut service = orchestrator.get_lint_service(database.read_only());
-        if let Some(external_linter) = external_linter {
-            service = service.with_external_linter(Arc::new(external_linter));
+        if let Some(external_linter) = &external_linter {
+            service = service.with_external_linter(Arc::clone(external_linter));
         }
 
         if let Some(explain_code) = self.explain {
@@ -322,24 +331,57 @@ impl LintCommand {
             return Ok(ExitCode::SUCCESS);
         }
 
+        let issue_categories: HashMap<String, String> = service
+            .create_registry(None, true)
+            .rules()
+            .iter()
+            .map(|rule| (rule.meta().code.to_string(), rule.meta().category.as_str().to_string()))
+            .collect();
+
+        let lint_mode = if self.semantics { LintMode::SemanticsOnly } else { LintMode::Full };
+        let only = if self.only.is_empty() { None } else { Some(self.only.as_slice()) };
+
+        let revision_baseline_start = trace_enabled.then(Instant::now);
+        let revision_baseline = match &self.new_since.new_since {
+            Some(revision) => {
+                let revision_database = load_revision_database(&database, &configuration.source.workspace, revision)?;
+                let mut revision_service = orchestrator.get_lint_service(revision_database.read_only());
+                if let Some(external_linter) = &external_linter {
+                    revision_service = revision_service.with_external_linter(Arc::clone(external_linter));
+                }
+
+                Some(RevisionBaseline::new(
+                    revision,
+                    &revision_service.lint(lint_mode, only)?,
+                    &revision_database.read_only(),
+                    orchestrator.config.parser_settings,
+                    self.new_since.ratchet,
+                ))
+            }
+            None => None,
+        };
+        let revision_baseline_duration = revision_baseline_start.map(|s| s.elapsed());
+
         let lint_run_start = trace_enabled.then(Instant::now);
-        let issues = service.lint(
-            if self.semantics { LintMode::SemanticsOnly } else { LintMode::Full },
-            if self.only.is_empty() { None } else { Some(self.only.as_slice()) },
-        )?;
+        let issues = service.lint(lint_mode, only)?;
         let lint_run_duration = lint_run_start.map(|s| s.elapsed());
 
         let report_start = trace_enabled.then(Instant::now);
-        let baseline = configuration.linter.baseline.as_deref();
+        let baseline = configuration.baseline_location(BaselineTool::Linter);
         let baseline_variant = configuration.linter.baseline_variant;
-        let processor = self.baseline_reporting.get_processor(
-            color_choice,
-            baseline,
-            baseline_variant,
-            editor_url,
-            configuration.linter.minimum_fail_level,
-            self.staged || !self.path.is_empty() || self.stdin_input,
-        );
+        let processor = self
+            .baseline_reporting
+            .get_processor(
+                color_choice,
+                BaselineTool::Linter,
+                baseline,
+                baseline_variant,
+                editor_url,
+                configuration.linter.minimum_fail_level,
+                self.staged || !self.path.is_empty() || self.stdin_input,
+            )
+            .with_issue_categories(issue_categories)
+            .with_revision_baseline(revision_baseline);
 
         let (exit_code, changed_file_ids) = processor.process_issues(&orchestrator, &mut database, issues)?;
         let report_duration = report_start.map(|s| s.elapsed());
@@ -359,6 +401,7 @@ impl LintCommand {
         if let Some(start) = command_start {
             tracing::trace!("Orchestrator initialized in {:?}.", orchestrator_init_duration.unwrap_or_default());
             tracing::trace!("Database loaded in {:?}.", load_database_duration.unwrap_or_default());
+            tracing::trace!("Revision linted in {:?}.", revision_baseline_duration.unwrap_or_default());
             tracing::trace!("Lint service ran in {:?}.", lint_run_duration.unwrap_or_default());
             tracing::trace!("Issues filtered and reported in {:?}.", report_duration.unwrap_or_default());
             tracing::trace!("Database dropped in {:?}.", drop_database_duration.unwrap_or_default());
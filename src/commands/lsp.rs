@@ -0,0 +1,103 @@
+//! Language server command implementation.
+//!
+//! This module implements the `mago lsp` command, which runs a Language Server Protocol
+//! server over stdio. The server keeps the project loaded in memory between edits and
+//! re-analyzes incrementally, so editors get analyzer, linter, and guard diagnostics
+//! without spawning a new `mago` process on every keystroke.
+//!
+//! See [`crate::lsp`] for the protocol layer.
+
+use std::process::ExitCode;
+
+use clap::ColorChoice;
+use clap::Parser;
+
+use mago_codex::metadata::CodebaseMetadata;
+use mago_database::ReadDatabase;
+use mago_prelude::Prelude;
+use mago_reporting::CompiledIgnoreSet;
+
+use crate::config::Configuration;
+use crate::consts::PRELUDE_BYTES;
+use crate::error::Error;
+use crate::lsp::server::LanguageServer;
+use crate::utils::create_orchestrator;
+
+/// Command for running the Mago language server.
+///
+/// The server communicates over stdin and stdout using the Language Server Protocol.
+/// Logs are written to stderr, so they never interfere with the protocol stream.
+///
+/// Editors should start it as `mago lsp` from the project root (or pass `--workspace`).
+#[derive(Parser, Debug)]
+#[command(name = "lsp")]
+pub struct LspCommand {
+    /// Disable built-in PHP and library stubs for analysis.
+    ///
+    /// By default, the server uses stubs for built-in PHP functions and popular
+    /// libraries to provide accurate type information.
+    #[arg(long, default_value_t = false)]
+    pub no_stubs: bool,
+
+    /// Ignore the `ignore` list from the analyzer configuration.
+    #[arg(long, default_value_t = false)]
+    pub skip_ignores: bool,
+}
+
+impl LspCommand {
+    /// Runs the language server until the client disconnects.
+    ///
+    /// The project database is loaded once up front; the initial analysis runs when the
+    /// client sends `initialized`, and every subsequent buffer change is applied to the
+    /// database and analyzed incrementally.
+    ///
+    /// # Arguments
+    ///
+    /// * `configuration` - The loaded configuration containing analyzer, linter, guard,
+    ///   and formatter settings
+    ///
+    /// # Returns
+    ///
+    /// - `Ok(ExitCode::SUCCESS)` if the client shut the server down cleanly
+    /// - `Ok(ExitCode::FAILURE)` if the client exited without a `shutdown` request
+    /// - `Err(Error)` if loading the database, analysis, or client I/O failed
+    pub fn execute(self, configuration: Configuration) -> Result<ExitCode, Error> {
+        let Prelude { database: prelude_database, metadata, symbol_references } = if self.no_stubs {
+            Prelude::default()
+        } else {
+            Prelude::decode(PRELUDE_BYTES).expect("Failed to decode embedded prelude")
+        };
+
+        let mut orchestrator = create_orchestrator(&configuration, ColorChoice::Never, false, false, true);
+        orchestrator.add_exclude_patterns(configuration.analyzer.excludes.iter());
+
+        let database =
+            orchestrator.load_database(&configuration.source.workspace, true, Some(prelude_database), None)?;
+
+        let ignore_set = if self.skip_ignores {
+            CompiledIgnoreSet::default()
+        } else {
+            CompiledIgnoreSet::compile(&configuration.analyzer.ignore, configuration.source.glob.to_database_settings())
+        };
+
+        // Documents are guarded against the codebase of the analysis service, so the guard does
+        // not need one of its own.
+        let guard = orchestrator.get_guard_service(ReadDatabase::empty(), CodebaseMetadata::new());
+        let lint = orchestrator.get_lint_service(ReadDatabase::empty());
+        let analysis = orchestrator.get_incremental_analysis_service(database.read_only(), metadata, symbol_references);
+
+        tracing::info!("Mago language server listening on stdio.");
+
+        let server = LanguageServer::new(
+            &orchestrator,
+            &configuration.source.workspace,
+            database,
+            analysis,
+            lint,
+            guard,
+            ignore_set,
+        );
+
+        server.run(std::io::stdin().lock(), std::io::stdout().lock())
+    }
+}
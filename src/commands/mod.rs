@@ -33,6 +33,8 @@
 //! - **`guard`** ([`GuardCommand`]): Enforce architectural rules
 //! - **`inspect-baseline`** ([`InspectBaselineCommand`]): Visualize a baseline file
 //! - **`cst`** ([`CstCommand`]): Display the abstract syntax tree
+//! - **`lsp`** ([`LspCommand`]): Run the language server over stdio
+//! - **`query`** ([`QueryCommand`]): Query definitions, references, and types at a position
 //! - **`self-update`** ([`SelfUpdateCommand`]): Update Mago to the latest version
 //! - **`generate-completions`** ([`GenerateCompletionsCommand`]): Generate shell completions
 //!
@@ -68,6 +70,8 @@ use crate::commands::init::InitCommand;
 use crate::commands::inspect_baseline::InspectBaselineCommand;
 use crate::commands::lint::LintCommand;
 use crate::commands::list_files::ListFilesCommand;
+use crate::commands::lsp::LspCommand;
+use crate::commands::query::QueryCommand;
 use crate::commands::self_update::SelfUpdateCommand;
 use crate::error::Error;
 
@@ -84,6 +88,8 @@ pub mod init;
 pub mod inspect_baseline;
 pub mod lint;
 pub mod list_files;
+pub mod lsp;
+pub mod query;
 pub mod self_update;
 pub mod stdin_input;
 
@@ -230,6 +236,25 @@ pub enum MagoCommand {
     #[command(name = "format")]
     Format(FormatCommand),
 
+    /// Run the Mago language server.
+    ///
+    /// Starts a Language Server Protocol server over stdio that keeps the project
+    /// loaded between edits, publishing analyzer, linter, and guard diagnostics
+    /// and providing document formatting.
+    ///
+    /// **Usage**: `mago lsp`
+    #[command(name = "lsp")]
+    Lsp(LspCommand),
+
+    /// Query the symbol at a position.
+    ///
+    /// Analyzes the workspace, then prints the definition, references, or hover
+    /// information for the symbol at `<file>:<line>:<column>` as JSON.
+    ///
+    /// **Usage**: `mago query <definition|references|hover> <file>:<line>:<column>`
+    #[command(name = "query")]
+    Query(QueryCommand),
+
     /// Update Mago to the latest version.
     ///
     /// Downloads and installs the latest version of Mago from GitHub releases.
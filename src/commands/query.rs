@@ -0,0 +1,296 @@
+//! Symbol query command implementation.
+//!
+//! This module implements the `mago query` command, which answers editor-style questions
+//! about a position in a source file and prints the answer as JSON:
+//!
+//! - **`definition`**: Where the symbol under the cursor is declared
+//! - **`references`**: Every place the symbol under the cursor is used
+//! - **`hover`**: The symbol's signature, inferred type, and docblock
+//!
+//! The command runs a full analysis of the workspace first, then answers the query from
+//! the resulting codebase metadata and symbol reference graph.
+//!
+//! Positions are written as `<file>:<line>:<column>`, with one-based lines and columns.
+//! Columns count characters, not bytes.
+
+use std::path::Path;
+use std::path::PathBuf;
+use std::process::ExitCode;
+use std::str::FromStr;
+use std::sync::Arc;
+
+use clap::ColorChoice;
+use clap::Parser;
+use clap::ValueEnum;
+use serde_json::Value;
+use serde_json::json;
+
+use mago_database::DatabaseReader;
+use mago_database::ReadDatabase;
+use mago_database::file::File;
+use mago_orchestrator::service::query::QueryService;
+use mago_orchestrator::service::query::QuerySymbol;
+use mago_prelude::Prelude;
+use mago_span::Span;
+
+use crate::config::Configuration;
+use crate::consts::PRELUDE_BYTES;
+use crate::error::Error;
+use crate::utils::create_orchestrator;
+
+/// The kind of query to run.
+#[derive(ValueEnum, Debug, Clone, Copy, PartialEq, Eq)]
+pub enum QueryKind {
+    /// Find the declaration of the symbol at the position.
+    Definition,
+    /// List every use of the symbol at the position.
+    References,
+    /// Describe the symbol or expression at the position.
+    Hover,
+}
+
+/// A position in a source file, written as `<file>:<line>:<column>`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct QueryPosition {
+    /// The file path, as given on the command line.
+    pub path: PathBuf,
+    /// The one-based line number.
+    pub line: u32,
+    /// The one-based column, in characters.
+    pub column: u32,
+}
+
+impl FromStr for QueryPosition {
+    type Err = String;
+
+    fn from_str(value: &str) -> Result<Self, Self::Err> {
+        let invalid = || format!("expected `<file>:<line>:<column>`, found `{value}`");
+
+        // Split from the right so that paths containing `:` (e.g. Windows drive letters) work.
+        let mut parts = value.rsplitn(3, ':');
+        let column = parts.next().and_then(|column| column.parse::<u32>().ok()).ok_or_else(invalid)?;
+        let line = parts.next().and_then(|line| line.parse::<u32>().ok()).ok_or_else(invalid)?;
+        let path = parts.next().filter(|path| !path.is_empty()).ok_or_else(invalid)?;
+
+        if line == 0 || column == 0 {
+            return Err(format!("lines and columns are one-based, found `{value}`"));
+        }
+
+        Ok(Self { path: PathBuf::from(path), line, column })
+    }
+}
+
+/// Command for querying symbols at a position in the workspace.
+///
+/// Prints a JSON document describing the result. When the position is not on a symbol
+/// (or, for `hover`, inside an expression with an inferred type), prints `null` and
+/// exits with a failure code.
+#[derive(Parser, Debug)]
+#[command(
+    name = "query",
+    about = "Query the definition, references, or type of the symbol at a position.",
+    long_about = r#"
+Query the definition, references, or type of the symbol at a position.
+
+The workspace is analyzed first; the query is then answered from the analysis results
+and printed as JSON. Positions use one-based lines and columns, e.g. `src/Foo.php:12:9`.
+"#
+)]
+pub struct QueryCommand {
+    /// The kind of query to run.
+    #[arg(value_enum)]
+    pub kind: QueryKind,
+
+    /// The position to query, as `<file>:<line>:<column>`.
+    pub position: QueryPosition,
+
+    /// Disable built-in PHP and library stubs for analysis.
+    #[arg(long, default_value_t = false)]
+    pub no_stubs: bool,
+}
+
+impl QueryCommand {
+    /// Executes the query command.
+    ///
+    /// # Arguments
+    ///
+    /// * `configuration` - The loaded configuration containing analyzer and source settings
+    ///
+    /// # Returns
+    ///
+    /// - `Ok(ExitCode::SUCCESS)` if the query produced a result
+    /// - `Ok(ExitCode::FAILURE)` if nothing was found at the position
+    /// - `Err(Error)` if the file is not part of the workspace, the position is outside the
+    ///   file, or analysis failed
+    pub fn execute(self, configuration: Configuration) -> Result<ExitCode, Error> {
+        let Prelude { database: prelude_database, metadata, symbol_references } = if self.no_stubs {
+            Prelude::default()
+        } else {
+            Prelude::decode(PRELUDE_BYTES).expect("Failed to decode embedded prelude")
+        };
+
+        let mut orchestrator = create_orchestrator(&configuration, ColorChoice::Never, false, false, false);
+        orchestrator.add_exclude_patterns(configuration.analyzer.excludes.iter());
+
+        let database =
+            orchestrator.load_database(&configuration.source.workspace, true, Some(prelude_database), None)?;
+
+        let mut analysis =
+            orchestrator.get_incremental_analysis_service(database.read_only(), metadata, symbol_references);
+        analysis.analyze()?;
+
+        let file = find_file(analysis.database(), &configuration.source.workspace, &self.position.path)?;
+        let offset = position_to_offset(&file, self.position.line, self.position.column).ok_or_else(|| {
+            Error::InvalidArgument(format!(
+                "position {}:{} is outside of `{}`",
+                self.position.line,
+                self.position.column,
+                String::from_utf8_lossy(&file.name)
+            ))
+        })?;
+
+        let query = QueryService::new(&analysis);
+        let database = analysis.database();
+        let result = match self.kind {
+            QueryKind::Definition => query.definition(file.id, offset).map(|definition| {
+                json!({
+                    "symbol": symbol_to_json(&definition.symbol),
+                    "location": location(database, definition.span),
+                    "definition": location(database, definition.definition),
+                })
+            }),
+            QueryKind::References => query.references(file.id, offset).map(|usages| {
+                json!({
+                    "symbol": symbol_to_json(&usages.symbol),
+                    "definition": location(database, usages.definition),
+                    "references": usages.references.into_iter().map(|span| location(database, span)).collect::<Vec<_>>(),
+                })
+            }),
+            QueryKind::Hover => query.hover(file.id, offset).map(|hover| {
+                json!({
+                    "symbol": hover.symbol.as_ref().map(symbol_to_json),
+                    "location": location(database, hover.span),
+                    "signature": hover.signature,
+                    "type": hover.inferred_type,
+                    "docblock": hover.docblock,
+                })
+            }),
+        };
+
+        let found = result.is_some();
+        println!("{}", serde_json::to_string_pretty(&result.unwrap_or(Value::Null))?);
+
+        Ok(if found { ExitCode::SUCCESS } else { ExitCode::FAILURE })
+    }
+}
+
+/// Looks up a file given on the command line, by filesystem path or by logical name.
+fn find_file(database: &ReadDatabase, workspace: &Path, path: &Path) -> Result<Arc<File>, Error> {
+    let absolute = if path.is_absolute() { path.to_path_buf() } else { workspace.join(path) };
+    let canonical = absolute.canonicalize().ok();
+
+    let file = [Some(absolute.as_path()), canonical.as_deref()]
+        .into_iter()
+        .flatten()
+        .find_map(|candidate| database.get_by_path(candidate).ok())
+        .or_else(|| database.get_by_name(path.to_string_lossy().as_bytes()).ok());
+
+    match file {
+        Some(file) => Ok(file),
+        None => Err(Error::InvalidArgument(format!("`{}` is not part of the workspace", path.display()))),
+    }
+}
+
+/// Converts a one-based line and character column into a byte offset.
+///
+/// Columns are counted on the raw bytes of the line, where each byte of an invalid UTF-8
+/// sequence counts as a character of its own, so offsets never drift on lines that are not
+/// valid UTF-8.
+fn position_to_offset(file: &File, line: u32, column: u32) -> Option<u32> {
+    let line_start = file.get_line_start_offset(line - 1)?;
+    let line_text = &file.contents[line_start as usize..];
+    let line_text = line_text.split(|byte| *byte == b'\n').next().unwrap_or_default();
+
+    let mut remaining = (column - 1) as usize;
+    let mut offset = 0;
+    for chunk in line_text.utf8_chunks() {
+        let valid = chunk.valid();
+        if let Some((index, _)) = valid.char_indices().nth(remaining) {
+            return Some(line_start + (offset + index) as u32);
+        }
+
+        remaining -= valid.chars().count();
+        offset += valid.len();
+
+        let invalid = chunk.invalid().len();
+        if remaining < invalid {
+            return Some(line_start + (offset + remaining) as u32);
+        }
+
+        remaining -= invalid;
+        offset += invalid;
+    }
+
+    (remaining == 0).then_some(line_start + offset as u32)
+}
+
+/// Counts the characters in `bytes` the way [`position_to_offset`] does.
+fn character_count(bytes: &[u8]) -> usize {
+    bytes.utf8_chunks().map(|chunk| chunk.valid().chars().count() + chunk.invalid().len()).sum()
+}
+
+/// Renders a span as a JSON location with one-based lines and character columns.
+fn location(database: &ReadDatabase, span: Span) -> Value {
+    let Ok(file) = database.get(&span.file_id) else {
+        return Value::Null;
+    };
+
+    let position = |offset: u32| {
+        let line = file.line_number(offset);
+        let line_start = file.get_line_start_offset(line).unwrap_or(0).min(offset);
+        let column = character_count(&file.contents[line_start as usize..offset as usize]);
+
+        json!({ "line": line + 1, "column": column + 1, "offset": offset })
+    };
+
+    json!({
+        "file": String::from_utf8_lossy(&file.name),
+        "start": position(span.start.offset),
+        "end": position(span.end.offset),
+    })
+}
+
+/// Renders a query symbol as JSON.
+fn symbol_to_json(symbol: &QuerySymbol) -> Value {
+    json!({ "kind": symbol.kind(), "name": symbol.to_string() })
+}
+
+#[cfg(test)]
+mod tests {
+    use std::borrow::Cow;
+
+    use mago_database::file::File;
+
+    use super::character_count;
+    use super::position_to_offset;
+
+    #[test]
+    fn converts_columns_on_lines_with_invalid_utf8() {
+        let file = File::ephemeral(Cow::Borrowed(b"test.php"), Cow::Borrowed(b"<?php\n$a = '\xff\xfe\xc3\xa9'; $b;\n"));
+
+        // `$b` is preceded by `$a = '`, two invalid bytes, `\u{e9}`, and `'; `.
+        let offset = position_to_offset(&file, 2, 13);
+        assert_eq!(offset, Some(19));
+        assert_eq!(&file.contents[19..21], b"$b");
+        assert_eq!(character_count(&file.contents[6..19]), 12);
+    }
+
+    #[test]
+    fn converts_columns_at_the_end_of_a_line() {
+        let file = File::ephemeral(Cow::Borrowed(b"test.php"), Cow::Borrowed(b"<?php\n\xff\n"));
+
+        assert_eq!(position_to_offset(&file, 2, 1), Some(6));
+        assert_eq!(position_to_offset(&file, 2, 2), Some(7));
+        assert_eq!(position_to_offset(&file, 2, 3), None);
+    }
+}
@@ -1,3 +1,4 @@
+use std::path::Path;
 use std::path::PathBuf;
 
 use clap::ColorChoice;
@@ -5,6 +6,8 @@ use mago_algebra::DEFAULT_CONSENSUS_LIMIT;
 use mago_algebra::DEFAULT_DISJUNCTION_COMPLEXITY;
 use mago_algebra::DEFAULT_NEGATION_COMPLEXITY;
 use mago_algebra::DEFAULT_SATURATION_COMPLEXITY;
+use mago_analyzer::dead_code::EntryPoints;
+use mago_analyzer::dead_code::extract_symbol_names;
 use mago_analyzer::settings::ClassInitializer;
 use mago_analyzer::settings::DEFAULT_FORMULA_SIZE_THRESHOLD;
 use mago_analyzer::settings::DEFAULT_LOOP_ASSIGNMENT_DEPTH_THRESHOLD;
@@ -41,11 +44,23 @@ pub struct AnalyzerConfiguration {
     ///
     /// - `"strict"`: Exact line matching with start/end line numbers
     /// - `"loose"`: Count-based matching by (file, code, message) tuple (default)
+    /// - `"fingerprint"`: Count-based matching by (code, symbol, fingerprint) tuple
     ///
     /// The loose variant is more resilient to code changes as line number shifts
     /// don't affect the baseline.
     pub baseline_variant: BaselineVariant,
 
+    /// Directory for the persistent analysis cache.
+    ///
+    /// When set, `mago analyze` stores the codebase model and per-file issues in this
+    /// directory, and later runs only re-scan files whose content changed and re-analyze
+    /// the code affected by those changes. Relative paths are resolved against the workspace.
+    ///
+    /// Can be overridden by the `--cache-dir` CLI flag, or disabled with `--no-cache`.
+    ///
+    /// Defaults to unset (no cache).
+    pub cache_directory: Option<PathBuf>,
+
     /// Set the minimum issue severity that causes the command to fail.
     ///
     /// The command will exit with a non-zero status if any issues at or above
@@ -126,6 +141,26 @@ pub struct AnalyzerConfiguration {
     /// during `check_throws` analysis. Parent classes and subclasses are not affected.
     pub unchecked_exception_classes: Vec<String>,
 
+    /// Enable interprocedural taint analysis.
+    ///
+    /// When `true`, the analyzer tracks untrusted input, such as superglobals and HTTP request
+    /// accessors, through assignments, calls, returns, and properties, and reports it reaching
+    /// HTML output, SQL queries, shell commands, includes, or `unserialize()` unsanitized.
+    pub check_taints: bool,
+
+    /// Report classes, methods, class constants, and properties that can not be reached
+    /// from any entry point.
+    ///
+    /// When `true`, the references recorded across the whole program are walked from
+    /// top-level code, functions, `@api` symbols, and the configured `entry-points`, and
+    /// every user-defined symbol that is never reached is reported.
+    ///
+    /// Can be enabled for a single run with the `--dead-code-report` CLI flag.
+    pub find_unreachable_symbols: bool,
+
+    /// The entry points used by `find-unreachable-symbols`.
+    pub entry_points: EntryPointsConfiguration,
+
     /// Enforce strict checks when accessing list elements by index.
     ///
     /// When `true`, the analyzer requires that any integer used to access a `list`
@@ -246,6 +281,33 @@ pub struct AnalyzerConfiguration {
     /// Defaults to empty (no additional initializers).
     pub class_initializers: Vec<String>,
 
+    /// Directories holding Twig templates, relative to the workspace.
+    ///
+    /// Templates are named by their path relative to one of these directories, which is how
+    /// analyzer plugins such as `symfony` resolve the templates passed to `render()` calls.
+    /// Only templates that are part of the source paths are considered.
+    ///
+    /// Defaults to `["templates"]`.
+    pub template_paths: Vec<String>,
+
+    /// Directories holding database migrations, relative to the workspace.
+    ///
+    /// Migrations are replayed in file name order to build the database schema that analyzer
+    /// plugins such as `laravel` use to type model attributes. Only migrations that are part of
+    /// the source paths are considered.
+    ///
+    /// Defaults to `["database/migrations"]`.
+    pub migration_paths: Vec<String>,
+
+    /// The XML dump of the compiled Symfony service container, relative to the workspace.
+    ///
+    /// Symfony writes it in debug mode, for example to
+    /// `var/cache/dev/App_KernelDevDebugContainer.xml`. Analyzer plugins such as `symfony` use
+    /// it to type the services and parameters fetched from the container.
+    ///
+    /// Defaults to none.
+    pub container_xml_path: Option<PathBuf>,
+
     /// Enable property initialization checking (`missing-constructor`, `uninitialized-property`).
     ///
     /// When `false`, disables both `missing-constructor` and `uninitialized-property` issues
@@ -320,6 +382,67 @@ pub struct AnalyzerConfiguration {
     pub performance: PerformanceConfiguration,
 }
 
+/// The symbols a framework calls without the analyzed code referencing them.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, JsonSchema)]
+#[serde(default, rename_all = "kebab-case", deny_unknown_fields)]
+pub struct EntryPointsConfiguration {
+    /// Classes whose public methods are entry points.
+    ///
+    /// A class name also matches every class extending or implementing it, while a
+    /// pattern ending in `*` matches class names by prefix, e.g. `App\Controller\*`.
+    ///
+    /// Defaults to Symfony and Laravel controllers and console commands, Symfony event
+    /// subscribers, and `PHPUnit` test cases.
+    pub classes: Vec<String>,
+
+    /// Attributes marking the class or method they are applied to as an entry point.
+    ///
+    /// Defaults to Symfony's `#[Route]`, `#[AsController]`, `#[AsCommand]`,
+    /// `#[AsEventListener]`, and `#[AsMessageHandler]` attributes.
+    pub attributes: Vec<String>,
+
+    /// Individual `Class` or `Class::member` names that are entry points.
+    pub symbols: Vec<String>,
+
+    /// Configuration files, relative to the workspace, whose namespaced class names and
+    /// `Class::method` references are entry points.
+    ///
+    /// Example: `["config/services.yaml", "config/routes.yaml"]`
+    pub config_files: Vec<PathBuf>,
+}
+
+impl EntryPointsConfiguration {
+    /// Resolves the configured entry points, reading the names referenced from the
+    /// configured configuration files.
+    fn to_entry_points(&self, workspace: &Path) -> EntryPoints {
+        let mut symbols = self.symbols.clone();
+        for config_file in &self.config_files {
+            let path = workspace.join(config_file);
+            match std::fs::read(&path) {
+                Ok(contents) => symbols.extend(extract_symbol_names(&contents)),
+                Err(error) => {
+                    tracing::warn!("Failed to read entry point configuration file `{}`: {error}", path.display())
+                }
+            }
+        }
+
+        EntryPoints { classes: self.classes.clone(), attributes: self.attributes.clone(), symbols }
+    }
+}
+
+impl Default for EntryPointsConfiguration {
+    fn default() -> Self {
+        let defaults = EntryPoints::default();
+
+        Self {
+            classes: defaults.classes,
+            attributes: defaults.attributes,
+            symbols: defaults.symbols,
+            config_files: vec![],
+        }
+    }
+}
+
 /// Performance tuning settings for the analyzer.
 ///
 /// These thresholds control the complexity limits for logical formula operations.
@@ -435,7 +558,13 @@ impl Default for PerformanceConfiguration {
 }
 
 impl AnalyzerConfiguration {
-    pub fn to_settings(&self, php_version: PHPVersion, color_choice: ColorChoice, enable_diff: bool) -> Settings {
+    pub fn to_settings(
+        &self,
+        workspace: &Path,
+        php_version: PHPVersion,
+        color_choice: ColorChoice,
+        enable_diff: bool,
+    ) -> Settings {
         // Backwards compatibility: if perform_heuristic_checks is set, use it for both options
         let check_missing_override = self.perform_heuristic_checks.unwrap_or(self.check_missing_override);
         let find_unused_parameters = self.perform_heuristic_checks.unwrap_or(self.find_unused_parameters);
@@ -464,6 +593,13 @@ impl AnalyzerConfiguration {
                 .iter()
                 .map(|s| mago_word::word(s.as_bytes()))
                 .collect(),
+            check_taints: self.check_taints,
+            find_unreachable_symbols: self.find_unreachable_symbols,
+            entry_points: if self.find_unreachable_symbols {
+                self.entry_points.to_entry_points(workspace)
+            } else {
+                EntryPoints::default()
+            },
             check_missing_override,
             find_unused_parameters,
             strict_list_index_checks: self.strict_list_index_checks,
@@ -481,6 +617,9 @@ impl AnalyzerConfiguration {
             diff: enable_diff,
             trust_existence_checks: self.trust_existence_checks,
             class_initializers: self.class_initializers.iter().filter_map(|s| ClassInitializer::parse(s)).collect(),
+            template_paths: self.template_paths.clone(),
+            migration_paths: self.migration_paths.clone(),
+            container_xml_path: self.container_xml_path.clone(),
             check_property_initialization: self.check_property_initialization,
             check_use_statements: self.check_use_statements,
             check_experimental: self.check_experimental,
@@ -510,6 +649,7 @@ impl Default for AnalyzerConfiguration {
             ignore: vec![],
             baseline: None,
             baseline_variant: BaselineVariant::default(),
+            cache_directory: None,
             minimum_fail_level: Level::Error,
             find_unused_expressions: defaults.find_unused_expressions,
             find_unused_definitions: defaults.find_unused_definitions,
@@ -520,6 +660,9 @@ impl Default for AnalyzerConfiguration {
             check_throws: defaults.check_throws,
             unchecked_exceptions: vec![],
             unchecked_exception_classes: vec![],
+            check_taints: defaults.check_taints,
+            find_unreachable_symbols: defaults.find_unreachable_symbols,
+            entry_points: EntryPointsConfiguration::default(),
             check_missing_override: defaults.check_missing_override,
             find_unused_parameters: defaults.find_unused_parameters,
             strict_list_index_checks: defaults.strict_list_index_checks,
@@ -535,6 +678,9 @@ impl Default for AnalyzerConfiguration {
             register_super_globals: defaults.register_super_globals,
             trust_existence_checks: defaults.trust_existence_checks,
             class_initializers: vec![],
+            template_paths: defaults.template_paths,
+            migration_paths: defaults.migration_paths,
+            container_xml_path: defaults.container_xml_path,
             check_property_initialization: defaults.check_property_initialization,
             check_use_statements: defaults.check_use_statements,
             check_experimental: defaults.check_experimental,
@@ -23,6 +23,7 @@ pub struct GuardConfiguration {
     ///
     /// - `"strict"`: Exact line matching with start/end line numbers
     /// - `"loose"`: Count-based matching by (file, code, message) tuple (default)
+    /// - `"fingerprint"`: Count-based matching by (code, symbol, fingerprint) tuple
     ///
     /// The loose variant is more resilient to code changes as line number shifts
     /// don't affect the baseline.
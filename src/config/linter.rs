@@ -33,6 +33,7 @@ pub struct LinterConfiguration {
     ///
     /// - `"strict"`: Exact line matching with start/end line numbers
     /// - `"loose"`: Count-based matching by (file, code, message) tuple (default)
+    /// - `"fingerprint"`: Count-based matching by (code, symbol, fingerprint) tuple
     ///
     /// The loose variant is more resilient to code changes as line number shifts
     /// don't affect the baseline.
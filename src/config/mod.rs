@@ -95,9 +95,11 @@ use serde::Deserialize;
 use serde::Serialize;
 
 use mago_php_version::PHPVersion;
+use mago_reporting::baseline::BaselineTool;
 use serde::de::IgnoredAny;
 use serde_json::Value;
 
+use crate::baseline::BaselineLocation;
 use crate::config::analyzer::AnalyzerConfiguration;
 use crate::config::extension::ExtensionHostConfiguration;
 use crate::config::formatter::FormatterConfiguration;
@@ -316,6 +318,13 @@ pub struct Configuration {
     #[serde(default)]
     pub guard: GuardConfiguration,
 
+    /// Path to a project baseline file shared by the linter, the analyzer, and the guard.
+    ///
+    /// The file holds one section per tool. A tool uses its section unless its own
+    /// `baseline` setting points to a separate file.
+    #[serde(default)]
+    pub baseline: Option<PathBuf>,
+
     /// Editor URL template for OSC 8 terminal hyperlinks on file paths in diagnostics.
     ///
     /// When set, file paths in diagnostic output become clickable links in terminals
@@ -611,6 +620,7 @@ impl Configuration {
             formatter: FormatterConfiguration::default(),
             analyzer: AnalyzerConfiguration::default(),
             guard: GuardConfiguration::default(),
+            baseline: None,
             editor_url: None,
             config_file: None,
             config_file_is_explicit: false,
@@ -640,9 +650,27 @@ impl Configuration {
             "formatter": self.formatter.to_value(),
             "analyzer": self.analyzer,
             "guard": self.guard,
+            "baseline": self.baseline,
         })
     }
 
+    /// Returns where the baseline of the given tool is configured: the tool's own `baseline`
+    /// setting, or else the project `baseline`.
+    #[must_use]
+    pub fn baseline_location(&self, tool: BaselineTool) -> Option<BaselineLocation<'_>> {
+        let tool_baseline = match tool {
+            BaselineTool::Linter => &self.linter.baseline,
+            BaselineTool::Analyzer => &self.analyzer.baseline,
+            BaselineTool::Guard => &self.guard.baseline,
+        };
+
+        match (tool_baseline, &self.baseline) {
+            (Some(path), _) => Some(BaselineLocation { path, is_project: false }),
+            (None, Some(path)) => Some(BaselineLocation { path, is_project: true }),
+            (None, None) => None,
+        }
+    }
+
     /// Normalizes and validates configuration values.
     ///
     /// This method ensures that all configuration values are within acceptable ranges
@@ -737,6 +765,18 @@ impl Configuration {
             self.analyzer.baseline = Some(resolved);
         }
 
+        if let Some(directory) = self.analyzer.cache_directory.take() {
+            let resolved = if directory.is_relative() { self.source.workspace.join(&directory) } else { directory };
+            tracing::debug!("Analyzer cache directory configured at {}.", resolved.display());
+            self.analyzer.cache_directory = Some(resolved);
+        }
+
+        if let Some(path) = self.analyzer.container_xml_path.take() {
+            let resolved = if path.is_relative() { self.source.workspace.join(&path) } else { path };
+            tracing::debug!("Analyzer service container dump configured at {}.", resolved.display());
+            self.analyzer.container_xml_path = Some(resolved);
+        }
+
         if let Some(b) = self.linter.baseline.take() {
             let resolved = if b.is_relative() { self.source.workspace.join(&b) } else { b };
             tracing::debug!("Linter baseline configuration from {}.", resolved.display());
@@ -749,6 +789,12 @@ impl Configuration {
             self.guard.baseline = Some(resolved);
         }
 
+        if let Some(b) = self.baseline.take() {
+            let resolved = if b.is_relative() { self.source.workspace.join(&b) } else { b };
+            tracing::debug!("Project baseline configuration from {}.", resolved.display());
+            self.baseline = Some(resolved);
+        }
+
         Ok(())
     }
 }
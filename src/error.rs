@@ -244,6 +244,12 @@ pub enum Error {
     /// `--staged` formatting) outside of a git repository.
     NotAGitRepository,
 
+    /// A git revision could not be resolved to a commit.
+    ///
+    /// This error occurs when the revision given to a git-related feature (such as
+    /// `--changed-since` formatting) does not exist. The string contains the revision.
+    UnknownGitRevision(String),
+
     /// A staged file has unstaged changes.
     ///
     /// This error occurs when attempting to format staged files but a file has both
@@ -290,6 +296,12 @@ pub enum Error {
     /// such as `--substitute ORIG=TEMP`. Semantic validation that depends on filesystem state
     /// or orchestrator context surfaces through [`Orchestrator`](Self::Orchestrator) instead.
     InvalidArgument(String),
+
+    /// Communication with a language server client failed.
+    ///
+    /// This error occurs during `mago lsp` when reading a message from stdin or writing
+    /// a response to stdout fails, or when the client sends a malformed message.
+    LanguageServer(std::io::Error),
 }
 
 /// Formats the error for user-friendly display.
@@ -355,6 +367,7 @@ impl std::fmt::Display for Error {
             }
             Self::Orchestrator(error) => write!(f, "Orchestrator error: {error}"),
             Self::NotAGitRepository => write!(f, "Not inside a git repository"),
+            Self::UnknownGitRevision(revision) => write!(f, "Unknown git revision: `{revision}`"),
             Self::StagedFileHasUnstagedChanges(path) => {
                 write!(f, "Cannot format staged files: '{path}' has both staged and unstaged changes")
             }
@@ -362,6 +375,7 @@ impl std::fmt::Display for Error {
                 write!(f, "Unknown formatter preset: `{preset}`. Available presets are: laravel, psr12, default")
             }
             Self::InvalidArgument(message) => write!(f, "{message}"),
+            Self::LanguageServer(error) => write!(f, "Language server communication failed: {error}"),
             Self::InvalidProjectVersionPin(error) => write!(f, "{error}"),
             Self::ProjectMajorVersionMismatch(pinned, installed) => {
                 write!(
@@ -427,6 +441,7 @@ impl std::error::Error for Error {
             Self::ThreadPoolBuildError(error) => Some(error),
             Self::Orchestrator(error) => Some(error),
             Self::InvalidProjectVersionPin(error) => Some(error),
+            Self::LanguageServer(error) => Some(error),
             _ => None,
         }
     }
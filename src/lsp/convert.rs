@@ -0,0 +1,213 @@
+//! Conversions between Mago's data model and Language Server Protocol structures.
+//!
+//! Mago addresses source locations by byte offset, while LSP clients address them by
+//! zero-based line and UTF-16 code unit. File identity is a workspace-relative logical
+//! name in Mago, and a `file://` URI on the wire.
+
+use std::path::Path;
+use std::path::PathBuf;
+
+use serde_json::Value;
+use serde_json::json;
+
+use mago_database::file::File;
+use mago_reporting::Issue;
+use mago_reporting::Level;
+
+/// Converts a `file://` URI into a filesystem path.
+///
+/// Returns `None` for URIs using any other scheme (e.g. `untitled:` buffers).
+pub fn uri_to_path(uri: &str) -> Option<PathBuf> {
+    let path = uri.strip_prefix("file://")?;
+    // Skip an optional authority component (`file://localhost/...`).
+    let path = &path[path.find('/')?..];
+    let decoded = percent_decode(path)?;
+
+    #[cfg(windows)]
+    let decoded = decoded.strip_prefix('/').map(str::to_string).unwrap_or(decoded).replace('/', "\\");
+
+    Some(PathBuf::from(decoded))
+}
+
+/// Converts a filesystem path into a `file://` URI, percent-encoding reserved characters.
+pub fn path_to_uri(path: &Path) -> String {
+    let path = path.to_string_lossy();
+
+    #[cfg(windows)]
+    let path = format!("/{}", path.replace('\\', "/"));
+
+    let mut uri = String::with_capacity(path.len() + 7);
+    uri.push_str("file://");
+    for byte in path.bytes() {
+        if byte.is_ascii_alphanumeric() || matches!(byte, b'/' | b'-' | b'_' | b'.' | b'~' | b':') {
+            uri.push(byte as char);
+        } else {
+            uri.push_str(&format!("%{byte:02X}"));
+        }
+    }
+
+    uri
+}
+
+/// Computes the workspace-relative logical name used as the database key for a path.
+///
+/// This mirrors the naming used by `--stdin-input`, so a buffer opened in the editor maps
+/// onto the same [`FileId`](mago_database::file::FileId) as the file loaded from disk.
+pub fn logical_name(workspace: &Path, path: &Path) -> String {
+    let relative = path.strip_prefix(workspace).unwrap_or(path);
+
+    #[cfg(windows)]
+    let name = relative.to_string_lossy().replace('\\', "/");
+    #[cfg(not(windows))]
+    let name = relative.to_string_lossy().into_owned();
+
+    name
+}
+
+/// Decodes `%XX` escapes in a URI path component.
+///
+/// Returns `None` when an escape is malformed or the decoded bytes are not valid UTF-8.
+fn percent_decode(input: &str) -> Option<String> {
+    let bytes = input.as_bytes();
+    let mut decoded = Vec::with_capacity(bytes.len());
+    let mut index = 0;
+    while index < bytes.len() {
+        if bytes[index] == b'%' {
+            let hex = input.get(index + 1..index + 3)?;
+            decoded.push(u8::from_str_radix(hex, 16).ok()?);
+            index += 3;
+        } else {
+            decoded.push(bytes[index]);
+            index += 1;
+        }
+    }
+
+    String::from_utf8(decoded).ok()
+}
+
+/// Converts a byte offset into an LSP position (zero-based line and UTF-16 column).
+pub fn offset_to_position(file: &File, offset: u32) -> Value {
+    let offset = offset.min(file.size);
+    let line = file.line_number(offset);
+    let line_start = file.get_line_start_offset(line).unwrap_or(0).min(offset);
+    let prefix = String::from_utf8_lossy(&file.contents[line_start as usize..offset as usize]);
+    let character: usize = prefix.chars().map(char::len_utf16).sum();
+
+    json!({ "line": line, "character": character })
+}
+
+/// Converts a byte range into an LSP range.
+pub fn range(file: &File, start: u32, end: u32) -> Value {
+    json!({ "start": offset_to_position(file, start), "end": offset_to_position(file, end) })
+}
+
+/// Returns the LSP range covering the whole document.
+pub fn full_range(file: &File) -> Value {
+    range(file, 0, file.size)
+}
+
+/// Maps an issue level onto an LSP `DiagnosticSeverity`.
+pub const fn severity(level: Level) -> u8 {
+    match level {
+        Level::Error => 1,
+        Level::Warning => 2,
+        Level::Note => 3,
+        Level::Help => 4,
+    }
+}
+
+/// Converts an issue into an LSP `Diagnostic` for the given file.
+///
+/// The primary annotation located in `file` becomes the diagnostic range; secondary
+/// annotations that carry a message become `relatedInformation` entries. Notes and help
+/// text are appended to the message, since LSP has no dedicated fields for them.
+pub fn issue_to_diagnostic(issue: &Issue, file: &File, source: &str) -> Value {
+    let primary =
+        issue.annotations.iter().find(|annotation| annotation.is_primary() && annotation.span.file_id == file.id);
+    let diagnostic_range = match primary {
+        Some(annotation) => range(file, annotation.span.start.offset, annotation.span.end.offset),
+        None => range(file, 0, 0),
+    };
+
+    let mut message = issue.message.clone();
+    for note in &issue.notes {
+        message.push_str("\n\n");
+        message.push_str(note);
+    }
+
+    if let Some(help) = &issue.help {
+        message.push_str("\n\nHelp: ");
+        message.push_str(help);
+    }
+
+    let related: Vec<Value> = issue
+        .annotations
+        .iter()
+        .filter(|annotation| !annotation.is_primary() && annotation.span.file_id == file.id)
+        .filter_map(|annotation| {
+            let note = annotation.message.as_ref()?;
+            let location_uri = file.path.as_deref().map(path_to_uri)?;
+
+            Some(json!({
+                "location": {
+                    "uri": location_uri,
+                    "range": range(file, annotation.span.start.offset, annotation.span.end.offset),
+                },
+                "message": note,
+            }))
+        })
+        .collect();
+
+    let mut diagnostic = json!({
+        "range": diagnostic_range,
+        "severity": severity(issue.level),
+        "source": source,
+        "message": message,
+    });
+
+    if let Some(code) = &issue.code {
+        diagnostic["code"] = json!(code);
+    }
+
+    if let Some(link) = &issue.link {
+        diagnostic["codeDescription"] = json!({ "href": link });
+    }
+
+    if !related.is_empty() {
+        diagnostic["relatedInformation"] = Value::Array(related);
+    }
+
+    diagnostic
+}
+
+#[cfg(test)]
+mod tests {
+    use std::borrow::Cow;
+
+    use super::*;
+
+    fn file(contents: &'static str) -> File {
+        File::ephemeral(Cow::Borrowed(b"test.php"), Cow::Borrowed(contents.as_bytes()))
+    }
+
+    #[test]
+    fn converts_offsets_to_utf16_positions() {
+        let file = file("<?php\n$a = 'é😀';\n$b = 1;\n");
+
+        assert_eq!(offset_to_position(&file, 0), json!({ "line": 0, "character": 0 }));
+        assert_eq!(offset_to_position(&file, 6), json!({ "line": 1, "character": 0 }));
+        // `é` is two bytes and one UTF-16 unit; `😀` is four bytes and two UTF-16 units.
+        assert_eq!(offset_to_position(&file, 18), json!({ "line": 1, "character": 9 }));
+        assert_eq!(offset_to_position(&file, 21), json!({ "line": 2, "character": 0 }));
+    }
+
+    #[test]
+    fn round_trips_uris() {
+        let path = Path::new("/tmp/my project/src/Foo#1.php");
+        let uri = path_to_uri(path);
+
+        assert_eq!(uri, "file:///tmp/my%20project/src/Foo%231.php");
+        assert_eq!(uri_to_path(&uri).as_deref(), Some(path));
+        assert_eq!(uri_to_path("untitled:Untitled-1"), None);
+    }
+}
@@ -0,0 +1,23 @@
+//! Language Server Protocol support for Mago.
+//!
+//! This module implements the protocol layer behind `mago lsp`: a stdio JSON-RPC server
+//! that keeps an [`IncrementalAnalysisService`](mago_orchestrator::service::incremental_analysis::IncrementalAnalysisService)
+//! alive for the lifetime of the editor session.
+//!
+//! # Layout
+//!
+//! - [`transport`]: `Content-Length` framed message reading and writing
+//! - [`convert`]: Conversions between Mago's byte offsets and issues and LSP positions and diagnostics
+//! - [`server`]: The request dispatcher and document state
+//!
+//! # Capabilities
+//!
+//! The server currently supports:
+//!
+//! - Full document synchronization (`didOpen`, `didChange`, `didClose`)
+//! - Diagnostics from the analyzer, the linter, and the architectural guard
+//! - Whole-document formatting
+
+pub mod convert;
+pub mod server;
+pub mod transport;
@@ -0,0 +1,435 @@
+//! The language server state machine and request dispatcher.
+//!
+//! The server is single-threaded: it reads one message at a time from the client,
+//! applies it to the in-memory [`Database`], and re-runs the incremental analysis
+//! before publishing diagnostics for every open document. Heavy lifting (analysis,
+//! linting, guarding, formatting) is delegated to the orchestrator services.
+
+use std::borrow::Cow;
+use std::collections::BTreeMap;
+use std::collections::HashSet;
+use std::hash::Hash;
+use std::hash::Hasher;
+use std::io::BufRead;
+use std::io::Write;
+use std::path::Path;
+use std::path::PathBuf;
+use std::process::ExitCode;
+
+use serde_json::Value;
+use serde_json::json;
+
+use mago_database::Database;
+use mago_database::DatabaseReader;
+use mago_database::file::File;
+use mago_database::file::FileId;
+use mago_database::file::FileType;
+use mago_orchestrator::Orchestrator;
+use mago_orchestrator::service::format::FileFormatStatus;
+use mago_orchestrator::service::guard::GuardService;
+use mago_orchestrator::service::incremental_analysis::IncrementalAnalysisService;
+use mago_orchestrator::service::lint::LintMode;
+use mago_orchestrator::service::lint::LintService;
+use mago_reporting::CompiledIgnoreSet;
+use mago_reporting::Issue;
+
+use crate::consts::VERSION;
+use crate::error::Error;
+use crate::lsp::convert;
+use crate::lsp::transport;
+
+/// JSON-RPC error code for unknown methods.
+const METHOD_NOT_FOUND: i64 = -32601;
+
+/// JSON-RPC error code for malformed parameters.
+const INVALID_PARAMS: i64 = -32602;
+
+/// LSP error code for requests received before `initialize`.
+const SERVER_NOT_INITIALIZED: i64 = -32002;
+
+/// LSP error code for requests received after `shutdown`.
+const INVALID_REQUEST: i64 = -32600;
+
+/// `TextDocumentSyncKind.Full`: the client always sends the whole document.
+const TEXT_DOCUMENT_SYNC_FULL: u8 = 1;
+
+/// The lifecycle phase of the connection, as defined by the LSP specification.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Phase {
+    /// Waiting for the `initialize` request.
+    Uninitialized,
+    /// `initialize` has been answered; the session is live.
+    Running,
+    /// `shutdown` has been answered; only `exit` is accepted.
+    ShuttingDown,
+}
+
+/// A document currently open in the editor.
+#[derive(Debug)]
+struct OpenDocument {
+    /// The URI the client uses for this document.
+    uri: String,
+    /// The filesystem path behind the URI.
+    path: PathBuf,
+}
+
+/// An issue seen while collecting diagnostics, compared by value.
+///
+/// [`Issue`] does not implement [`Hash`], so only the fields that tell issues apart are hashed.
+#[derive(PartialEq, Eq)]
+struct SeenIssue<'issue>(&'issue Issue);
+
+impl Hash for SeenIssue<'_> {
+    fn hash<H>(&self, state: &mut H)
+    where
+        H: Hasher,
+    {
+        self.0.level.hash(state);
+        self.0.code.hash(state);
+        self.0.message.hash(state);
+        self.0.annotations.hash(state);
+    }
+}
+
+/// The Mago language server.
+///
+/// Holds the mutable [`Database`] that mirrors editor buffers on top of the files loaded
+/// from disk, and the long-lived [`IncrementalAnalysisService`] that re-analyzes only what
+/// changed between edits.
+pub struct LanguageServer<'cfg> {
+    orchestrator: &'cfg Orchestrator<'cfg>,
+    workspace: &'cfg Path,
+    database: Database<'cfg>,
+    analysis: IncrementalAnalysisService,
+    lint: LintService,
+    guard: GuardService,
+    ignore_set: CompiledIgnoreSet,
+    documents: BTreeMap<FileId, OpenDocument>,
+    phase: Phase,
+}
+
+impl<'cfg> LanguageServer<'cfg> {
+    /// Creates a new language server over an already loaded database.
+    ///
+    /// # Arguments
+    ///
+    /// * `orchestrator` - The orchestrator used for formatting and service configuration
+    /// * `workspace` - The workspace root, used to compute logical file names
+    /// * `database` - The database loaded from disk, including prelude files
+    /// * `analysis` - The incremental analysis service for `database`
+    /// * `lint` - The lint service used to lint individual documents
+    /// * `guard` - The guard service used to guard individual documents
+    /// * `ignore_set` - The analyzer `ignore` entries to filter analysis issues with
+    #[must_use]
+    pub fn new(
+        orchestrator: &'cfg Orchestrator<'cfg>,
+        workspace: &'cfg Path,
+        database: Database<'cfg>,
+        analysis: IncrementalAnalysisService,
+        lint: LintService,
+        guard: GuardService,
+        ignore_set: CompiledIgnoreSet,
+    ) -> Self {
+        Self {
+            orchestrator,
+            workspace,
+            database,
+            analysis,
+            lint,
+            guard,
+            ignore_set,
+            documents: BTreeMap::new(),
+            phase: Phase::Uninitialized,
+        }
+    }
+
+    /// Serves the client until it sends `exit` or closes the connection.
+    ///
+    /// # Returns
+    ///
+    /// - `ExitCode::SUCCESS` if the client sent `shutdown` before `exit`
+    /// - `ExitCode::FAILURE` if the client exited or disconnected without a `shutdown`
+    ///
+    /// # Errors
+    ///
+    /// Returns [`Error::LanguageServer`] when reading from or writing to the client fails.
+    pub fn run(mut self, mut reader: impl BufRead, mut writer: impl Write) -> Result<ExitCode, Error> {
+        while let Some(message) = transport::read_message(&mut reader).map_err(Error::LanguageServer)? {
+            let method = message.get("method").and_then(Value::as_str).unwrap_or_default().to_string();
+            let params = message.get("params").cloned().unwrap_or(Value::Null);
+
+            let Some(id) = message.get("id").cloned() else {
+                if method == "exit" {
+                    return Ok(if self.phase == Phase::ShuttingDown { ExitCode::SUCCESS } else { ExitCode::FAILURE });
+                }
+
+                if self.phase == Phase::Running {
+                    self.handle_notification(&method, &params, &mut writer)?;
+                }
+
+                continue;
+            };
+
+            if method.is_empty() {
+                // A response to a server-initiated request; the server sends none.
+                continue;
+            }
+
+            let response = match self.handle_request(&method, &params) {
+                Ok(result) => json!({ "jsonrpc": "2.0", "id": id, "result": result }),
+                Err((code, error_message)) => {
+                    json!({ "jsonrpc": "2.0", "id": id, "error": { "code": code, "message": error_message } })
+                }
+            };
+
+            transport::write_message(&mut writer, &response).map_err(Error::LanguageServer)?;
+        }
+
+        Ok(if self.phase == Phase::ShuttingDown { ExitCode::SUCCESS } else { ExitCode::FAILURE })
+    }
+
+    /// Handles a request and returns its result, or an error code and message.
+    fn handle_request(&mut self, method: &str, params: &Value) -> Result<Value, (i64, String)> {
+        match (self.phase, method) {
+            (Phase::Uninitialized, "initialize") => {
+                self.phase = Phase::Running;
+
+                Ok(json!({
+                    "capabilities": {
+                        "textDocumentSync": {
+                            "openClose": true,
+                            "change": TEXT_DOCUMENT_SYNC_FULL,
+                        },
+                        "documentFormattingProvider": true,
+                    },
+                    "serverInfo": { "name": "mago", "version": VERSION },
+                }))
+            }
+            (Phase::Uninitialized, _) => Err((SERVER_NOT_INITIALIZED, "Server has not been initialized.".to_string())),
+            (Phase::ShuttingDown, _) => Err((INVALID_REQUEST, "Server is shutting down.".to_string())),
+            (Phase::Running, "shutdown") => {
+                self.phase = Phase::ShuttingDown;
+
+                Ok(Value::Null)
+            }
+            (Phase::Running, "textDocument/formatting") => {
+                let uri = document_uri(params).ok_or((INVALID_PARAMS, "Missing `textDocument.uri`.".to_string()))?;
+
+                Ok(self.format_document(uri))
+            }
+            (Phase::Running, _) => Err((METHOD_NOT_FOUND, format!("Method `{method}` is not supported."))),
+        }
+    }
+
+    /// Handles a notification received while the session is running.
+    fn handle_notification(&mut self, method: &str, params: &Value, writer: &mut impl Write) -> Result<(), Error> {
+        match method {
+            "initialized" => {
+                tracing::info!("Running initial analysis...");
+                if self.run_analysis(None) {
+                    tracing::info!("Initial analysis complete.");
+                }
+            }
+            "textDocument/didOpen" => {
+                let document = &params["textDocument"];
+                let (Some(uri), Some(text)) = (document["uri"].as_str(), document["text"].as_str()) else {
+                    return Ok(());
+                };
+
+                if let Some(file_id) = self.open_document(uri, text) {
+                    self.run_analysis(Some(file_id));
+                    self.publish_all(writer)?;
+                }
+            }
+            "textDocument/didChange" => {
+                let Some(uri) = document_uri(params) else {
+                    return Ok(());
+                };
+
+                // With full synchronization, the last change carries the whole document.
+                let Some(text) =
+                    params["contentChanges"].as_array().and_then(|c| c.last()).and_then(|c| c["text"].as_str())
+                else {
+                    return Ok(());
+                };
+
+                if let Some(file_id) = self.open_document(uri, text) {
+                    self.run_analysis(Some(file_id));
+                    self.publish_all(writer)?;
+                }
+            }
+            "textDocument/didClose" => {
+                let Some(uri) = document_uri(params) else {
+                    return Ok(());
+                };
+
+                if let Some(file_id) = self.close_document(uri) {
+                    publish(writer, uri, &[])?;
+                    self.run_analysis(Some(file_id));
+                    self.publish_all(writer)?;
+                }
+            }
+            _ => {
+                tracing::debug!("Ignoring unsupported notification `{method}`.");
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Records an editor buffer in the database, returning the ID of the updated file.
+    ///
+    /// Files unknown to the database (e.g. created in the editor but not yet saved) are added
+    /// as host files so they are analyzed like the rest of the project.
+    fn open_document(&mut self, uri: &str, text: &str) -> Option<FileId> {
+        let path = convert::uri_to_path(uri)?;
+        let name = convert::logical_name(self.workspace, &path);
+        let file_id = FileId::new(name.as_bytes());
+        let contents: Cow<'static, [u8]> = Cow::Owned(text.as_bytes().to_vec());
+
+        if !self.database.update(file_id, contents.clone()) {
+            self.database.add(File::new(Cow::Owned(name.into_bytes()), FileType::Host, Some(path.clone()), contents));
+        }
+
+        self.documents.insert(file_id, OpenDocument { uri: uri.to_string(), path });
+
+        Some(file_id)
+    }
+
+    /// Forgets an editor buffer, restoring the on-disk contents of the file.
+    ///
+    /// Files that do not exist on disk are removed from the database altogether.
+    fn close_document(&mut self, uri: &str) -> Option<FileId> {
+        let file_id = *self.documents.iter().find(|(_, document)| document.uri == uri)?.0;
+        let document = self.documents.remove(&file_id)?;
+
+        match std::fs::read(&document.path) {
+            Ok(contents) => {
+                self.database.update(file_id, Cow::Owned(contents));
+            }
+            Err(_) => {
+                self.database.delete(file_id);
+            }
+        }
+
+        Some(file_id)
+    }
+
+    /// Runs the analysis, incrementally after `changed` when the project was already analyzed.
+    ///
+    /// A failed analysis is logged rather than ending the session, so the client keeps being
+    /// served with the results of the last successful run. Returns whether the analysis succeeded.
+    fn run_analysis(&mut self, changed: Option<FileId>) -> bool {
+        self.analysis.update_database(self.database.read_only());
+        let result = match changed {
+            Some(file_id) if self.analysis.is_initialized() => {
+                self.analysis.analyze_incremental(Some(&[file_id])).map(|_| ())
+            }
+            _ => self.analysis.analyze().map(|_| ()),
+        };
+
+        if let Err(error) = result {
+            tracing::error!("Analysis failed: {error}");
+
+            return false;
+        }
+
+        true
+    }
+
+    /// Publishes diagnostics for every open document.
+    ///
+    /// A change in one file can introduce or resolve issues in any file depending on it,
+    /// so all open documents are refreshed after each analysis run.
+    fn publish_all(&self, writer: &mut impl Write) -> Result<(), Error> {
+        for (file_id, document) in &self.documents {
+            let diagnostics = match self.database.get(file_id) {
+                Ok(file) if file.file_type == FileType::Host => self.collect_diagnostics(&file),
+                _ => Vec::new(),
+            };
+
+            publish(writer, &document.uri, &diagnostics)?;
+        }
+
+        Ok(())
+    }
+
+    /// Collects analyzer, linter, and guard diagnostics for a single file.
+    ///
+    /// Parse errors and semantic issues are reported by every tool; identical issues are
+    /// published only once, attributed to the first tool that reported them.
+    fn collect_diagnostics(&self, file: &File) -> Vec<Value> {
+        let mut analysis_issues = self.analysis.get_file_diagnostics(&file.id).cloned().unwrap_or_default();
+        analysis_issues.filter_out_ignored(&self.ignore_set, |file_id| {
+            self.database.get_ref(&file_id).ok().map(|f| String::from_utf8_lossy(&f.name).into_owned())
+        });
+
+        let lint_issues = self.lint.lint_file(file, LintMode::Full, None, false);
+        let guard_issues = self.guard.guard_file(file, self.analysis.codebase());
+
+        let mut seen: HashSet<SeenIssue<'_>> = HashSet::new();
+        let mut diagnostics = Vec::new();
+        for (source, issues) in
+            [("mago-analyzer", &analysis_issues), ("mago-linter", &lint_issues), ("mago-guard", &guard_issues)]
+        {
+            for issue in issues.iter() {
+                if !seen.insert(SeenIssue(issue)) {
+                    continue;
+                }
+
+                diagnostics.push(convert::issue_to_diagnostic(issue, file, source));
+            }
+        }
+
+        diagnostics
+    }
+
+    /// Formats an open document, returning the `TextEdit[]` result (or `null` on parse errors).
+    fn format_document(&self, uri: &str) -> Value {
+        let Some(file) = convert::uri_to_path(uri)
+            .map(|path| FileId::new(convert::logical_name(self.workspace, &path).as_bytes()))
+            .and_then(|file_id| self.database.get(&file_id).ok())
+        else {
+            return Value::Null;
+        };
+
+        match self.orchestrator.format_file(&file) {
+            Ok(FileFormatStatus::Unchanged | FileFormatStatus::FailedToIsolateRange) => json!([]),
+            Ok(FileFormatStatus::Changed(contents)) => json!([{
+                "range": convert::full_range(&file),
+                "newText": String::from_utf8_lossy(&contents),
+            }]),
+            Ok(FileFormatStatus::FailedToParse(error)) => {
+                tracing::debug!("Not formatting `{uri}`: {error}");
+
+                Value::Null
+            }
+            Ok(FileFormatStatus::FailedToParseTemplate(error)) => {
+                tracing::debug!("Not formatting template `{uri}`: {error}");
+
+                Value::Null
+            }
+            Err(error) => {
+                tracing::error!("Failed to format `{uri}`: {error}");
+
+                Value::Null
+            }
+        }
+    }
+}
+
+/// Extracts `params.textDocument.uri`.
+fn document_uri(params: &Value) -> Option<&str> {
+    params["textDocument"]["uri"].as_str()
+}
+
+/// Sends a `textDocument/publishDiagnostics` notification.
+fn publish(writer: &mut impl Write, uri: &str, diagnostics: &[Value]) -> Result<(), Error> {
+    let notification = json!({
+        "jsonrpc": "2.0",
+        "method": "textDocument/publishDiagnostics",
+        "params": { "uri": uri, "diagnostics": diagnostics },
+    });
+
+    transport::write_message(writer, &notification).map_err(Error::LanguageServer)
+}
@@ -0,0 +1,114 @@
+//! Message framing for the Language Server Protocol base protocol.
+//!
+//! Every message is a JSON-RPC payload preceded by a header section. The only header
+//! the server relies on is `Content-Length`; any other header is read and ignored.
+
+use std::io::BufRead;
+use std::io::Error as IoError;
+use std::io::ErrorKind;
+use std::io::Write;
+
+use serde_json::Value;
+
+/// Reads a single framed message from the reader.
+///
+/// Returns `Ok(None)` when the reader reaches end of input before a new message starts,
+/// which happens when the client closes the connection without sending `exit`.
+///
+/// # Errors
+///
+/// Returns an I/O error when the header section is malformed, the body is truncated,
+/// or the body is not valid JSON.
+pub fn read_message(reader: &mut impl BufRead) -> Result<Option<Value>, IoError> {
+    let mut content_length: Option<usize> = None;
+    let mut line = String::new();
+
+    loop {
+        line.clear();
+        if reader.read_line(&mut line)? == 0 {
+            return if content_length.is_none() {
+                Ok(None)
+            } else {
+                Err(IoError::new(ErrorKind::UnexpectedEof, "connection closed inside a message header"))
+            };
+        }
+
+        let header = line.trim_end_matches(['\r', '\n']);
+        if header.is_empty() {
+            if content_length.is_some() {
+                break;
+            }
+
+            continue;
+        }
+
+        if let Some((name, value)) = header.split_once(':')
+            && name.trim().eq_ignore_ascii_case("content-length")
+        {
+            let length = value
+                .trim()
+                .parse::<usize>()
+                .map_err(|_| IoError::new(ErrorKind::InvalidData, format!("invalid Content-Length `{value}`")))?;
+
+            content_length = Some(length);
+        }
+    }
+
+    let mut body = vec![0; content_length.unwrap_or_default()];
+    reader.read_exact(&mut body)?;
+
+    serde_json::from_slice(&body).map(Some).map_err(IoError::other)
+}
+
+/// Writes a single framed message to the writer and flushes it.
+///
+/// # Errors
+///
+/// Returns an I/O error if serialization or writing fails.
+pub fn write_message(writer: &mut impl Write, message: &Value) -> Result<(), IoError> {
+    let body = serde_json::to_vec(message).map_err(IoError::other)?;
+
+    write!(writer, "Content-Length: {}\r\n\r\n", body.len())?;
+    writer.write_all(&body)?;
+    writer.flush()
+}
+
+#[cfg(test)]
+mod tests {
+    use std::io::Cursor;
+
+    use serde_json::json;
+
+    use super::*;
+
+    #[test]
+    fn round_trips_a_message() {
+        let message = json!({ "jsonrpc": "2.0", "id": 1, "method": "initialize", "params": {} });
+
+        let mut buffer = Vec::new();
+        write_message(&mut buffer, &message).unwrap();
+
+        let mut reader = Cursor::new(buffer);
+        assert_eq!(read_message(&mut reader).unwrap(), Some(message));
+        assert_eq!(read_message(&mut reader).unwrap(), None);
+    }
+
+    #[test]
+    fn ignores_unknown_headers() {
+        let body = r#"{"jsonrpc":"2.0","method":"exit"}"#;
+        let input = format!(
+            "Content-Type: application/vscode-jsonrpc; charset=utf-8\r\nContent-Length: {}\r\n\r\n{body}",
+            body.len()
+        );
+
+        let message = read_message(&mut Cursor::new(input.into_bytes())).unwrap().unwrap();
+        assert_eq!(message["method"], "exit");
+    }
+
+    #[test]
+    fn rejects_truncated_bodies() {
+        let input = b"Content-Length: 20\r\n\r\n{}".to_vec();
+
+        assert!(read_message(&mut Cursor::new(input)).is_err());
+    }
+}
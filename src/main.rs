@@ -21,6 +21,8 @@
 //! - `mago analyze`: Perform static analysis
 //! - `mago format`: Format PHP code
 //! - `mago guard`: Enforce architectural rules
+//! - `mago lsp`: Run the language server
+//! - `mago query`: Query definitions, references, and types at a position
 //! - `mago ast`: Display the abstract syntax tree
 //! - `mago list-files`: List all files that would be processed
 //! - `mago self-update`: Update Mago to the latest version
@@ -70,6 +72,7 @@ mod config;
 mod consts;
 mod error;
 mod extensions;
+mod lsp;
 mod macros;
 mod service;
 mod updater;
@@ -284,6 +287,8 @@ pub fn run(main_start: Instant) -> Result<ExitCode, Error> {
         MagoCommand::Cst(cmd) => cmd.execute(configuration, arguments.colors),
         MagoCommand::Analyze(cmd) => cmd.execute(configuration, arguments.colors),
         MagoCommand::Guard(cmd) => cmd.execute(configuration, arguments.colors),
+        MagoCommand::Lsp(cmd) => cmd.execute(configuration),
+        MagoCommand::Query(cmd) => cmd.execute(configuration),
         MagoCommand::InspectBaseline(cmd) => cmd.execute(configuration, arguments.colors),
         MagoCommand::GenerateCompletions(cmd) => cmd.execute(),
         MagoCommand::SelfUpdate(_) => {
@@ -51,6 +51,7 @@
 //! starting with safe fixes and progressively enabling more aggressive transformations.
 
 use std::borrow::Cow;
+use std::collections::HashMap;
 use std::path::Path;
 use std::process::ExitCode;
 use std::sync::Arc;
@@ -60,7 +61,9 @@ use serde::Serialize;
 
 use clap::ColorChoice;
 use mago_allocator::LocalArena;
+use mago_collector::pragma::PragmaDate;
 use mago_database::file::FileId;
+use mago_syntax::highlight::highlight;
 use mago_syntax::parser::parse_file_content_with_settings;
 use mago_syntax::settings::ParserSettings;
 use rayon::iter::IntoParallelIterator;
@@ -79,7 +82,11 @@ use mago_reporting::Level;
 use mago_reporting::ReportingFormat;
 use mago_reporting::ReportingTarget;
 use mago_reporting::baseline::Baseline;
+use mago_reporting::baseline::BaselineFile;
+use mago_reporting::baseline::BaselineTool;
 use mago_reporting::baseline::BaselineVariant;
+use mago_reporting::baseline::IssueLocations;
+use mago_reporting::baseline::ProjectBaseline;
 use mago_reporting::reporter::Reporter;
 use mago_reporting::reporter::ReporterConfig;
 use mago_text_edit::ApplyResult;
@@ -87,7 +94,9 @@ use mago_text_edit::Safety;
 use mago_text_edit::TextEditor;
 
 use crate::baseline;
-use crate::baseline::unserialize_baseline;
+use crate::baseline::locations::locate_issues;
+use crate::baseline::revision::RevisionBaseline;
+use crate::baseline::unserialize_baseline_file;
 use crate::consts::ISSUE_URL;
 use crate::error::Error;
 use crate::utils;
@@ -210,6 +219,12 @@ pub struct IssueProcessor {
     pub editor_url: Option<String>,
 
     pub scoped_analysis: bool,
+
+    /// The category of each issue code, such as the category of a linter rule.
+    ///
+    /// Used by report formats that classify issues, such as `code-climate` and
+    /// `sonarqube`. Only applies in report mode.
+    pub issue_categories: HashMap<String, String>,
 }
 
 #[derive(Serialize)]
@@ -261,6 +276,18 @@ pub struct BaselineIssueProcessor {
     /// without filtering.
     pub baseline_path: Option<Cow<'static, Path>>,
 
+    /// The tool whose issues are processed.
+    ///
+    /// Selects the section to read and write when the baseline file is a project
+    /// baseline holding a section per tool.
+    pub baseline_tool: BaselineTool,
+
+    /// Whether `baseline_path` is the project baseline.
+    ///
+    /// When `true` and the file does not exist yet, generating a baseline creates a
+    /// project baseline with a section for `baseline_tool`. Existing files keep their shape.
+    pub project_baseline: bool,
+
     /// Generate a new baseline file from current issues.
     ///
     /// When `true`, instead of processing issues normally, generates a baseline
@@ -302,11 +329,18 @@ pub struct BaselineIssueProcessor {
     /// This determines the format of generated baseline files:
     /// - `Strict`: Exact line matching with start/end line numbers
     /// - `Loose`: Count-based matching by (file, code, message) tuple
+    /// - `Fingerprint`: Count-based matching by (code, symbol, fingerprint) tuple
     ///
     /// When loading existing baselines, the variant is determined by the file's
     /// `variant` header, not this setting.
     pub baseline_variant: BaselineVariant,
 
+    /// The issues found at a git revision, when reporting only the issues introduced since.
+    ///
+    /// When set, it replaces the baseline file: issues already present at the revision are
+    /// filtered out, and the command fails if the ratchet is enabled and went up.
+    pub revision_baseline: Option<RevisionBaseline>,
+
     /// Wrapped issue processor for actual issue processing.
     ///
     /// After baseline operations (loading, generation, or verification), this
@@ -358,8 +392,14 @@ impl IssueProcessor {
         if self.fix {
             self.handle_fix_mode(orchestrator, database, issues, baseline)
         } else {
-            self.handle_report_mode(database, issues, baseline, fail_on_out_of_sync_baseline)
-                .map(|code| (code, Vec::new()))
+            self.handle_report_mode(
+                database,
+                issues,
+                baseline,
+                fail_on_out_of_sync_baseline,
+                orchestrator.config.parser_settings,
+            )
+            .map(|code| (code, Vec::new()))
         }
     }
 
@@ -377,8 +417,15 @@ impl IssueProcessor {
         issues: IssueCollection,
         baseline: Option<Baseline>,
     ) -> Result<(ExitCode, Vec<FileId>), Error> {
-        let issues =
-            if let Some(baseline) = baseline { baseline.filter_issues(issues, &database.read_only()) } else { issues };
+        let issues = if let Some(baseline) = baseline {
+            let read_database = database.read_only();
+            let locations =
+                locate_issues(baseline.variant(), &read_database, &issues, orchestrator.config.parser_settings);
+
+            baseline.filter_issues(issues, &read_database, &locations)
+        } else {
+            issues
+        };
 
         let unfixable_count = (&issues).into_iter().filter(|i| i.edits.is_empty()).count();
 
@@ -457,8 +504,13 @@ impl IssueProcessor {
         issues: IssueCollection,
         baseline: Option<Baseline>,
         fail_on_out_of_sync_baseline: bool,
+        parser_settings: ParserSettings,
     ) -> Result<ExitCode, Error> {
         let read_database = database.read_only();
+        let locations = match &baseline {
+            Some(baseline) => locate_issues(baseline.variant(), &read_database, &issues, parser_settings),
+            None => IssueLocations::default(),
+        };
 
         let issues_to_report = issues;
 
@@ -474,9 +526,11 @@ impl IssueProcessor {
             sort: self.sort,
             minimum_report_level: self.minimum_report_level,
             editor_url: self.editor_url.clone(),
+            issue_categories: self.issue_categories.clone(),
+            source_highlighter: Some(highlight),
         };
 
-        let reporter = Reporter::new(read_database, reporter_configuration);
+        let reporter = Reporter::new(read_database, reporter_configuration).with_issue_locations(locations);
         let status = reporter.report(issues_to_report, baseline)?;
 
         if status.baseline_dead_issues > 0 {
@@ -623,7 +677,9 @@ impl IssueProcessor {
                     let format_status = orchestrator.format_file_in(&ephemeral_file, arena)?;
 
                     match format_status {
-                        FileFormatStatus::Unchanged => ephemeral_file.contents.into_owned(),
+                        FileFormatStatus::Unchanged | FileFormatStatus::FailedToIsolateRange => {
+                            ephemeral_file.contents.into_owned()
+                        }
                         FileFormatStatus::Changed(new_content) => new_content,
                         FileFormatStatus::FailedToParse(parse_error) => {
                             tracing::warn!(
@@ -632,6 +688,15 @@ impl IssueProcessor {
                                 parse_error
                             );
 
+                            ephemeral_file.contents.into_owned()
+                        }
+                        FileFormatStatus::FailedToParseTemplate(parse_error) => {
+                            tracing::warn!(
+                                "Failed to format template `{}` after applying fixes: {}",
+                                mago_bytes::BytesDisplay(&ephemeral_file.name),
+                                parse_error
+                            );
+
                             ephemeral_file.contents.into_owned()
                         }
                     }
@@ -673,6 +738,20 @@ impl IssueProcessor {
 }
 
 impl BaselineIssueProcessor {
+    /// Sets the category of each issue code, used by report formats that classify issues.
+    #[must_use]
+    pub fn with_issue_categories(mut self, issue_categories: HashMap<String, String>) -> Self {
+        self.issue_processor.issue_categories = issue_categories;
+        self
+    }
+
+    /// Sets the issues found at a git revision, replacing the baseline file.
+    #[must_use]
+    pub fn with_revision_baseline(mut self, revision_baseline: Option<RevisionBaseline>) -> Self {
+        self.revision_baseline = revision_baseline;
+        self
+    }
+
     /// Processes issues with baseline awareness.
     ///
     /// This method orchestrates the complete baseline-aware issue processing workflow.
@@ -683,11 +762,14 @@ impl BaselineIssueProcessor {
     ///
     /// The method follows this decision tree:
     ///
-    /// 1. **Baseline Path Provided**:
+    /// 1. **Revision Baseline Provided**:
+    ///    - Filter out issues present at the revision and pass the rest to wrapped processor
+    ///    - Fail if the ratchet is enabled and the count of any code went up
+    /// 2. **Baseline Path Provided**:
     ///    - If `generate_baseline` is `true`: Generate new baseline and exit with success
     ///    - If `verify_baseline` is `true`: Verify baseline and exit with success/failure
     ///    - Otherwise: Load baseline and pass to wrapped processor for filtering
-    /// 2. **No Baseline Path**:
+    /// 3. **No Baseline Path**:
     ///    - Validate that baseline-related flags are consistent
     ///    - Process issues normally without baseline filtering
     ///
@@ -709,26 +791,43 @@ impl BaselineIssueProcessor {
         database: &mut Database<'_>,
         issues: IssueCollection,
     ) -> Result<(ExitCode, Vec<FileId>), Error> {
+        if let Some(revision_baseline) = &self.revision_baseline {
+            let ratchet_failed = revision_baseline.ratchet_failed(&issues);
+            let issues =
+                revision_baseline.filter_issues(issues, &database.read_only(), orchestrator.config.parser_settings);
+
+            let (exit_code, changed_file_ids) =
+                self.issue_processor.process_issues(orchestrator, database, issues, None, false)?;
+
+            return Ok((if ratchet_failed { ExitCode::FAILURE } else { exit_code }, changed_file_ids));
+        }
+
         // Extract baseline_path before consuming self
         let baseline = if let Some(baseline_path_cow) = self.baseline_path.as_ref() {
             let baseline_path = baseline_path_cow.as_ref();
             if self.generate_baseline {
                 let read_database = database.read_only();
-                self.generate_baseline(baseline_path, &read_database, issues)?;
+                self.generate_baseline(baseline_path, &read_database, issues, orchestrator.config.parser_settings)?;
 
                 return Ok((ExitCode::SUCCESS, Vec::new()));
             }
 
             if self.verify_baseline {
                 let read_database = database.read_only();
-                let success = self.verify_baseline(baseline_path, &read_database, issues)?;
+                let success =
+                    self.verify_baseline(baseline_path, &read_database, issues, orchestrator.config.parser_settings)?;
 
                 return Ok((if success { ExitCode::SUCCESS } else { ExitCode::FAILURE }, Vec::new()));
             }
 
             if self.remove_outdated_baseline_entries {
                 let read_database = database.read_only();
-                self.remove_outdated_baseline_entries(baseline_path, &read_database, issues)?;
+                self.remove_outdated_baseline_entries(
+                    baseline_path,
+                    &read_database,
+                    issues,
+                    orchestrator.config.parser_settings,
+                )?;
 
                 return Ok((ExitCode::SUCCESS, Vec::new()));
             }
@@ -767,15 +866,18 @@ impl BaselineIssueProcessor {
             return None;
         }
 
-        match unserialize_baseline(path) {
-            Ok((baseline, needs_warning)) => {
-                if needs_warning {
+        match self.read_baseline_file(path) {
+            Ok(file) => {
+                let baseline = file.into_section(self.baseline_tool);
+                if baseline.is_none() {
                     tracing::warn!(
-                        "Baseline file does not specify a variant, assuming 'strict'. \
-                         Regenerate the baseline with `--generate-baseline` to update the format."
+                        "Baseline file `{}` has no `{}` section.",
+                        path.display(),
+                        self.baseline_tool.as_str()
                     );
                 }
-                Some(baseline)
+
+                baseline
             }
             Err(err) => {
                 tracing::error!("Failed to read baseline file at `{}`: {}", path.display(), err);
@@ -785,6 +887,19 @@ impl BaselineIssueProcessor {
         }
     }
 
+    /// Reads a baseline file, warning when it predates the `variant` header.
+    fn read_baseline_file(&self, path: &Path) -> Result<BaselineFile, Error> {
+        let (file, needs_warning) = unserialize_baseline_file(path)?;
+        if needs_warning {
+            tracing::warn!(
+                "Baseline file does not specify a variant, assuming 'strict'. \
+                 Regenerate the baseline with `--generate-baseline` to update the format."
+            );
+        }
+
+        Ok(file)
+    }
+
     /// Generates a new baseline file from the provided issues.
     ///
     /// This method creates a baseline containing all issues in the provided collection,
@@ -796,6 +911,7 @@ impl BaselineIssueProcessor {
     /// * `baseline_path` - Path where the baseline file should be written
     /// * `read_database` - Read-only database access for file metadata
     /// * `issues` - Collection of issues to include in the baseline
+    /// * `parser_settings` - Settings used to parse files when locating issues for a fingerprint baseline
     ///
     /// # Returns
     ///
@@ -813,10 +929,43 @@ impl BaselineIssueProcessor {
         baseline_path: &Path,
         read_database: &ReadDatabase,
         issues: IssueCollection,
+        parser_settings: ParserSettings,
     ) -> Result<(), Error> {
         tracing::info!("Generating {:?} baseline file...", self.baseline_variant);
-        let baseline = Baseline::generate_from_issues(&issues, read_database, self.baseline_variant);
-        baseline::serialize_baseline(baseline_path, &baseline, self.backup_baseline)?;
+
+        let existing = if baseline_path.exists() {
+            match self.read_baseline_file(baseline_path) {
+                Ok(file) => Some(file),
+                Err(err) => {
+                    tracing::warn!(
+                        "Failed to read the existing baseline file at `{}`, its owners, tickets, and dates are not preserved: {}",
+                        baseline_path.display(),
+                        err
+                    );
+
+                    None
+                }
+            }
+        } else {
+            None
+        };
+
+        let locations = locate_issues(self.baseline_variant, read_database, &issues, parser_settings);
+        let mut baseline = Baseline::generate_from_issues(&issues, read_database, self.baseline_variant, &locations);
+        baseline.inherit_metadata(
+            existing.as_ref().and_then(|file| file.section(self.baseline_tool)),
+            &PragmaDate::today().to_string(),
+        );
+
+        let file = match existing {
+            Some(file) => file.with_section(self.baseline_tool, baseline),
+            None if self.project_baseline => {
+                BaselineFile::Project(ProjectBaseline::default()).with_section(self.baseline_tool, baseline)
+            }
+            None => BaselineFile::Tool(baseline),
+        };
+
+        baseline::serialize_baseline_file(baseline_path, &file, self.backup_baseline)?;
         tracing::info!("Baseline file successfully generated at `{}`.", baseline_path.display());
 
         Ok(())
@@ -833,6 +982,7 @@ impl BaselineIssueProcessor {
     /// * `baseline_path` - Path to the baseline file to verify
     /// * `read_database` - Read-only database access for file metadata
     /// * `issues` - Current collection of issues to compare against the baseline
+    /// * `parser_settings` - Settings used to parse files when locating issues for a fingerprint baseline
     ///
     /// # Returns
     ///
@@ -851,6 +1001,7 @@ impl BaselineIssueProcessor {
         baseline_path: &Path,
         read_database: &ReadDatabase,
         issues: IssueCollection,
+        parser_settings: ParserSettings,
     ) -> Result<bool, Error> {
         if !baseline_path.exists() {
             tracing::info!("Baseline file `{}` does not exist.", baseline_path.display());
@@ -859,14 +1010,17 @@ impl BaselineIssueProcessor {
 
         tracing::info!("Verifying baseline file at `{}`...", baseline_path.display());
 
-        let (baseline, needs_warning) = unserialize_baseline(baseline_path)?;
-        if needs_warning {
-            tracing::warn!(
-                "Baseline file does not specify a variant, assuming 'strict'. \
-                 Regenerate the baseline with `--generate-baseline` to update the format."
+        let Some(baseline) = self.read_baseline_file(baseline_path)?.into_section(self.baseline_tool) else {
+            tracing::info!(
+                "Baseline file `{}` has no `{}` section.",
+                baseline_path.display(),
+                self.baseline_tool.as_str()
             );
-        }
-        let comparison = baseline.compare_with_issues(&issues, read_database);
+
+            return Ok(false);
+        };
+        let locations = locate_issues(baseline.variant(), read_database, &issues, parser_settings);
+        let comparison = baseline.compare_with_issues(&issues, read_database, &locations);
         let scoped_with_no_new_issues = self.issue_processor.scoped_analysis && comparison.new_issues.is_empty();
 
         if comparison.is_up_to_date {
@@ -935,6 +1089,7 @@ impl BaselineIssueProcessor {
         baseline_path: &Path,
         read_database: &ReadDatabase,
         issues: IssueCollection,
+        parser_settings: ParserSettings,
     ) -> Result<(), Error> {
         if !baseline_path.exists() {
             tracing::warn!(
@@ -947,15 +1102,19 @@ impl BaselineIssueProcessor {
 
         tracing::info!("Removing outdated entries from baseline file at `{}`...", baseline_path.display());
 
-        let (baseline, needs_warning) = unserialize_baseline(baseline_path)?;
-        if needs_warning {
+        let file = self.read_baseline_file(baseline_path)?;
+        let Some(baseline) = file.section(self.baseline_tool) else {
             tracing::warn!(
-                "Baseline file does not specify a variant, assuming 'strict'. \
-                 Regenerate the baseline with `--generate-baseline` to update the format."
+                "Baseline file `{}` has no `{}` section; there are no outdated entries to remove.",
+                baseline_path.display(),
+                self.baseline_tool.as_str()
             );
-        }
 
-        let (pruned_baseline, removed_count) = baseline.prune_outdated_entries(&issues, read_database);
+            return Ok(());
+        };
+
+        let locations = locate_issues(baseline.variant(), read_database, &issues, parser_settings);
+        let (pruned_baseline, removed_count) = baseline.prune_outdated_entries(&issues, read_database, &locations);
 
         if removed_count == 0 {
             tracing::info!("No outdated entries found; the baseline file is already up to date.");
@@ -963,7 +1122,8 @@ impl BaselineIssueProcessor {
             return Ok(());
         }
 
-        baseline::serialize_baseline(baseline_path, &pruned_baseline, self.backup_baseline)?;
+        let file = file.with_section(self.baseline_tool, pruned_baseline);
+        baseline::serialize_baseline_file(baseline_path, &file, self.backup_baseline)?;
 
         let noun = if removed_count == 1 { "entry" } else { "entries" };
         tracing::info!(
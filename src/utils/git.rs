@@ -2,12 +2,15 @@
 //!
 //! This module provides helper functions for interacting with git repositories,
 //! specifically for the `--staged` formatting feature that allows formatting
-//! staged files in pre-commit hooks.
+//! staged files in pre-commit hooks, the `--changed-since` formatting feature
+//! that formats only the lines changed relative to a revision, and the `--new-since`
+//! feature that reads the project's files at a revision without checking it out.
 
 use std::borrow::Cow;
 use std::collections::HashSet;
 use std::ffi::OsString;
 use std::io::Write as _;
+use std::ops::RangeInclusive;
 use std::path::Path;
 use std::path::PathBuf;
 use std::process::Command;
@@ -21,6 +24,9 @@ use mago_database::file::FileId;
 
 use crate::error::Error;
 
+/// The 1-based ranges of added or modified lines in a file, keyed by its path relative to the workspace.
+pub type ChangedLines = (PathBuf, Vec<RangeInclusive<u32>>);
+
 /// Get staged file paths relative to the workspace.
 ///
 /// This function is used by `--staged` flags in lint and analyze commands
@@ -42,6 +48,145 @@ pub fn get_staged_file_paths(workspace: &Path) -> Result<Vec<PathBuf>, Error> {
     get_staged_files(workspace)
 }
 
+/// Get the lines changed in the working tree relative to a git revision.
+///
+/// This function is used by the `--changed-since` formatting flag to format only
+/// the lines touched since `revision`, including staged and unstaged changes.
+/// Deleted files, and hunks that only remove lines, are not reported; untracked
+/// files are not reported either, as git does not diff them.
+///
+/// # Arguments
+///
+/// * `workspace` - The git repository root directory
+/// * `revision` - The revision to compare against, such as `main` or `HEAD~3`
+///
+/// # Returns
+///
+/// For each changed file (relative to workspace), the 1-based ranges of its added or
+/// modified lines, or an error if not in a git repository or `revision` is unknown.
+pub fn get_changed_lines(workspace: &Path, revision: &str) -> Result<Vec<ChangedLines>, Error> {
+    if !is_git_repository(workspace) {
+        return Err(Error::NotAGitRepository);
+    }
+
+    ensure_revision_exists(workspace, revision)?;
+
+    let output = Command::new("git")
+        .args([
+            "-c",
+            "core.quotePath=false",
+            "diff",
+            "--unified=0",
+            "--no-color",
+            "--no-ext-diff",
+            "--relative",
+            "--diff-filter=ACMR",
+            "--src-prefix=a/",
+            "--dst-prefix=b/",
+        ])
+        .arg(revision)
+        .arg("--")
+        .current_dir(workspace)
+        .output()
+        .map_err(|e| Error::Database(DatabaseError::IOError(e)))?;
+
+    if !output.status.success() {
+        return Err(Error::UnknownGitRevision(revision.to_string()));
+    }
+
+    Ok(parse_changed_lines(&String::from_utf8_lossy(&output.stdout)))
+}
+
+/// Get the paths of the files tracked at a git revision.
+///
+/// This function is used by the `--new-since` flag to find the files the project
+/// had at `revision`, including files that were since renamed or deleted.
+///
+/// # Arguments
+///
+/// * `workspace` - The git repository root directory
+/// * `revision` - The revision to list, such as `origin/main` or `HEAD~3`
+///
+/// # Returns
+///
+/// The paths (relative to workspace) of the files tracked at `revision` under the
+/// workspace, or an error if not in a git repository or `revision` is unknown.
+pub fn get_file_paths_at_revision(workspace: &Path, revision: &str) -> Result<Vec<PathBuf>, Error> {
+    if !is_git_repository(workspace) {
+        return Err(Error::NotAGitRepository);
+    }
+
+    ensure_revision_exists(workspace, revision)?;
+
+    let output = Command::new("git")
+        .args(["ls-tree", "-r", "-z", "--name-only", "--end-of-options"])
+        .arg(format!("{revision}^{{commit}}"))
+        .current_dir(workspace)
+        .output()
+        .map_err(|e| Error::Database(DatabaseError::IOError(e)))?;
+
+    if !output.status.success() {
+        return Err(Error::UnknownGitRevision(revision.to_string()));
+    }
+
+    Ok(output
+        .stdout
+        .split(|byte| *byte == 0)
+        .filter(|path| !path.is_empty())
+        .map(|path| PathBuf::from(String::from_utf8_lossy(path).into_owned()))
+        .collect())
+}
+
+/// Reads the contents of files at a git revision, without checking it out.
+///
+/// # Arguments
+///
+/// * `workspace` - The git repository root directory
+/// * `revision` - The revision to read from, such as `origin/main` or `HEAD~3`
+/// * `paths` - The paths (relative to workspace) of the files to read
+///
+/// # Returns
+///
+/// The contents of each file, in the order of `paths`; `None` for paths that are
+/// not a file at `revision`.
+pub fn get_files_at_revision(
+    workspace: &Path,
+    revision: &str,
+    paths: &[PathBuf],
+) -> Result<Vec<Option<Vec<u8>>>, Error> {
+    let mut input = Vec::new();
+    for path in paths {
+        // `./` makes the path relative to the working directory rather than the repository root.
+        input.extend_from_slice(revision.as_bytes());
+        input.extend_from_slice(b":./");
+        input.extend_from_slice(path.as_os_str().as_encoded_bytes());
+        input.push(b'\n');
+    }
+
+    let mut child = Command::new("git")
+        .stdin(Stdio::piped())
+        .stdout(Stdio::piped())
+        .args(["cat-file", "--batch"])
+        .current_dir(workspace)
+        .spawn()
+        .map_err(|e| Error::Database(DatabaseError::IOError(e)))?;
+
+    let mut stdin = child.stdin.take().expect("failed to get stdin");
+    std::thread::spawn(move || {
+        stdin.write_all(&input).expect("failed to write to stdin");
+    });
+
+    let output = child.wait_with_output().map_err(|e| Error::Database(DatabaseError::IOError(e)))?;
+    if !output.status.success() {
+        return Err(Error::UnknownGitRevision(revision.to_string()));
+    }
+
+    let mut contents = parse_batch_output(&output.stdout);
+    contents.resize(paths.len(), None);
+
+    Ok(contents)
+}
+
 /// Creates an ephemeral file with the contents of a staged file
 ///
 /// # Arguments
@@ -284,3 +429,250 @@ fn get_mode(workspace: &Path, path: &Path) -> Result<String, Error> {
 
     Ok(String::from_utf8_lossy(&output.stdout).trim().to_owned())
 }
+
+/// Fails with [`Error::UnknownGitRevision`] unless `revision` names a commit.
+fn ensure_revision_exists(workspace: &Path, revision: &str) -> Result<(), Error> {
+    let is_valid_revision = Command::new("git")
+        .args(["rev-parse", "--verify", "--quiet", "--end-of-options"])
+        .arg(format!("{revision}^{{commit}}"))
+        .current_dir(workspace)
+        .output()
+        .map(|o| o.status.success())
+        .unwrap_or(false);
+
+    if !is_valid_revision {
+        return Err(Error::UnknownGitRevision(revision.to_string()));
+    }
+
+    Ok(())
+}
+
+/// Parses the output of `git cat-file --batch` into the contents of each requested blob.
+///
+/// Each object is reported as a `<oid> <type> <size>` header followed by its contents and a
+/// newline; objects that cannot be found are reported as a single `<name> missing` line.
+fn parse_batch_output(output: &[u8]) -> Vec<Option<Vec<u8>>> {
+    let mut contents = Vec::new();
+    let mut rest = output;
+    while let Some(end) = rest.iter().position(|byte| *byte == b'\n') {
+        let header = String::from_utf8_lossy(&rest[..end]);
+        rest = &rest[end + 1..];
+
+        let mut parts = header.rsplitn(3, ' ');
+        let (Some(size), Some(kind), Some(_)) = (parts.next(), parts.next(), parts.next()) else {
+            contents.push(None);
+            continue;
+        };
+
+        let Ok(size) = size.parse::<usize>() else {
+            contents.push(None);
+            continue;
+        };
+
+        let Some(object) = rest.get(..size) else {
+            break;
+        };
+
+        contents.push((kind == "blob").then(|| object.to_vec()));
+        rest = rest.get(size + 1..).unwrap_or_default();
+    }
+
+    contents
+}
+
+/// Parses the output of `git diff --unified=0` into the added or modified lines of each file.
+///
+/// Hunk bodies are skipped by the line counts announced in their headers, so an added line that
+/// itself starts with `++ ` is never mistaken for the `+++ ` header of another file.
+fn parse_changed_lines(diff: &str) -> Vec<ChangedLines> {
+    let mut files: Vec<ChangedLines> = Vec::new();
+    let mut after_old_header = false;
+    let mut remaining_removed = 0u32;
+    let mut remaining_added = 0u32;
+    for line in diff.lines() {
+        if remaining_removed > 0 && line.starts_with('-') {
+            remaining_removed -= 1;
+            continue;
+        }
+
+        if remaining_added > 0 && line.starts_with('+') {
+            remaining_added -= 1;
+            continue;
+        }
+
+        if line.starts_with('\\') {
+            // `\ No newline at end of file` markers belong to the hunk body.
+            continue;
+        }
+
+        remaining_removed = 0;
+        remaining_added = 0;
+
+        if line.starts_with("--- ") {
+            after_old_header = true;
+            continue;
+        }
+
+        if let Some(path) = line.strip_prefix("+++ ")
+            && after_old_header
+        {
+            after_old_header = false;
+
+            // Git terminates paths that contain spaces with a tab.
+            let path = path.strip_suffix('\t').unwrap_or(path);
+            let path = path.strip_prefix("b/").unwrap_or(path);
+            files.push((PathBuf::from(path), Vec::new()));
+            continue;
+        }
+
+        after_old_header = false;
+
+        // A hunk header looks like `@@ -start[,count] +start[,count] @@`.
+        let Some(header) = line.strip_prefix("@@ ") else {
+            continue;
+        };
+
+        let mut parts = header.split(' ');
+        let (Some(removed), Some(added)) = (
+            parts.next().and_then(|part| part.strip_prefix('-')),
+            parts.next().and_then(|part| part.strip_prefix('+')),
+        ) else {
+            continue;
+        };
+
+        let (Some((_, removed_count)), Some((start, added_count))) =
+            (parse_hunk_range(removed), parse_hunk_range(added))
+        else {
+            continue;
+        };
+
+        remaining_removed = removed_count;
+        remaining_added = added_count;
+
+        if added_count > 0
+            && let Some((_, lines)) = files.last_mut()
+        {
+            lines.push(start..=start + added_count - 1);
+        }
+    }
+
+    files.retain(|(_, lines)| !lines.is_empty());
+    files
+}
+
+/// Parses a `start[,count]` hunk range, where an omitted count means a single line.
+fn parse_hunk_range(range: &str) -> Option<(u32, u32)> {
+    match range.split_once(',') {
+        Some((start, count)) => Some((start.parse().ok()?, count.parse().ok()?)),
+        None => Some((range.parse().ok()?, 1)),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_added_and_modified_lines() {
+        let diff = "\
+diff --git a/src/Foo.php b/src/Foo.php
+index f1ddabb..155bb39 100644
+--- a/src/Foo.php
++++ b/src/Foo.php
+@@ -5 +5 @@ function a(){}
+-function b(){}
++function b(){ }
+@@ -10,0 +11,3 @@ function c()
++$x = 1;
++$y = 2;
++$z = 3;
+";
+
+        let changed = parse_changed_lines(diff);
+        assert_eq!(changed, vec![(PathBuf::from("src/Foo.php"), vec![5..=5, 11..=13])]);
+    }
+
+    #[test]
+    fn ignores_removed_lines() {
+        let diff = "\
+--- a/src/Foo.php
++++ b/src/Foo.php
+@@ -3,2 +2,0 @@
+-$a = 1;
+-$b = 2;
+--- a/src/Bar.php
++++ b/src/Bar.php
+@@ -1,0 +2 @@
++$c = 3;
+";
+
+        let changed = parse_changed_lines(diff);
+        assert_eq!(changed, vec![(PathBuf::from("src/Bar.php"), vec![2..=2])]);
+    }
+
+    #[test]
+    fn does_not_mistake_added_lines_for_file_headers() {
+        let diff = "\
+diff --git a/src/Foo.php b/src/Foo.php
+--- a/src/Foo.php
++++ b/src/Foo.php
+@@ -1,0 +2,2 @@
++++ b/src/Bar.php
++$x = 1;
+@@ -7 +9 @@
+--- a/src/Baz.php
++++ b/src/Baz.php
+";
+
+        let changed = parse_changed_lines(diff);
+        assert_eq!(changed, vec![(PathBuf::from("src/Foo.php"), vec![2..=3, 9..=9])]);
+    }
+
+    #[test]
+    fn parses_paths_with_spaces() {
+        let diff = "\
+diff --git a/src/My Foo.php b/src/My Foo.php
+--- a/src/My Foo.php\t
++++ b/src/My Foo.php\t
+@@ -3 +3 @@
+-$a = 1;
++$a = 2;
+";
+
+        let changed = parse_changed_lines(diff);
+        assert_eq!(changed, vec![(PathBuf::from("src/My Foo.php"), vec![3..=3])]);
+    }
+
+    #[test]
+    fn parses_new_files() {
+        let diff = "\
+diff --git a/src/New.php b/src/New.php
+new file mode 100644
+--- /dev/null
++++ b/src/New.php
+@@ -0,0 +1,2 @@
++<?php
++echo 1;
+";
+
+        let changed = parse_changed_lines(diff);
+        assert_eq!(changed, vec![(PathBuf::from("src/New.php"), vec![1..=2])]);
+    }
+
+    #[test]
+    fn parses_batch_output() {
+        let output = b"\
+4b825dc642cb6eb9a060e54bf8d69288fbee4904 blob 12
+<?php echo;
+
+HEAD:./src/Gone.php missing
+d670460b4b4aece5915caf5c68d12f560a9fe3e4 tree 0
+
+557db03de997c86a4a028e1ebd3a1ceb225be238 blob 0
+
+";
+
+        let contents = parse_batch_output(output);
+        assert_eq!(contents, vec![Some(b"<?php echo;\n".to_vec()), None, None, Some(Vec::new())]);
+    }
+}
@@ -0,0 +1,268 @@
+//! Integration tests for `mago lsp`.
+//!
+//! These tests drive the language server over stdio with a scripted session and verify
+//! the lifecycle, diagnostics, and formatting responses.
+
+use std::io::Write;
+use std::path::Path;
+use std::path::PathBuf;
+use std::process::Command;
+use std::process::Stdio;
+
+use serde_json::Value;
+use serde_json::json;
+
+fn mago_bin() -> PathBuf {
+    let path = std::env::var("CARGO_BIN_EXE_mago")
+        .ok()
+        .or_else(|| option_env!("CARGO_BIN_EXE_mago").map(String::from))
+        .unwrap_or_else(|| "mago".to_string());
+
+    PathBuf::from(path)
+}
+
+/// Returns true if the mago binary can actually execute on this host.
+fn can_run_mago() -> bool {
+    Command::new(mago_bin())
+        .arg("--help")
+        .stdout(Stdio::null())
+        .stderr(Stdio::null())
+        .status()
+        .is_ok_and(|s| s.success())
+}
+
+fn frame(message: &Value) -> Vec<u8> {
+    let body = serde_json::to_vec(message).unwrap();
+    let mut framed = format!("Content-Length: {}\r\n\r\n", body.len()).into_bytes();
+    framed.extend(body);
+    framed
+}
+
+fn unframe(mut output: &[u8]) -> Vec<Value> {
+    let mut messages = Vec::new();
+    while let Some(header_end) = output.windows(4).position(|w| w == b"\r\n\r\n") {
+        let header = std::str::from_utf8(&output[..header_end]).unwrap();
+        let length: usize = header
+            .lines()
+            .find_map(|line| line.strip_prefix("Content-Length: "))
+            .and_then(|value| value.trim().parse().ok())
+            .expect("every message must carry a Content-Length header");
+
+        let body = &output[header_end + 4..header_end + 4 + length];
+        messages.push(serde_json::from_slice(body).unwrap());
+        output = &output[header_end + 4 + length..];
+    }
+
+    messages
+}
+
+fn run_session(workspace: &Path, messages: &[Value]) -> (std::process::Output, Vec<Value>) {
+    let mut child = Command::new(mago_bin())
+        .args(["--workspace", workspace.to_str().unwrap(), "--no-version-check", "lsp", "--no-stubs"])
+        .stdin(Stdio::piped())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .spawn()
+        .expect("failed to spawn mago");
+
+    let stdin = child.stdin.as_mut().unwrap();
+    for message in messages {
+        stdin.write_all(&frame(message)).unwrap();
+    }
+
+    let output = child.wait_with_output().expect("failed to run mago");
+    let messages = unframe(&output.stdout);
+
+    (output, messages)
+}
+
+fn create_workspace() -> (tempfile::TempDir, PathBuf) {
+    let temp_dir = tempfile::tempdir().unwrap();
+    let workspace = temp_dir.path().canonicalize().unwrap();
+
+    std::fs::create_dir(workspace.join("src")).unwrap();
+    std::fs::write(
+        workspace.join("mago.toml"),
+        r#"
+php-version = "8.4"
+[source]
+paths = ["src"]
+"#,
+    )
+    .unwrap();
+    std::fs::write(workspace.join("src").join("example.php"), "<?php\n").unwrap();
+
+    (temp_dir, workspace)
+}
+
+fn response(messages: &[Value], id: i64) -> &Value {
+    messages.iter().find(|m| m["id"] == id).unwrap_or_else(|| panic!("no response for request {id}: {messages:#?}"))
+}
+
+#[test]
+fn test_lsp_publishes_diagnostics_for_changed_buffers() {
+    if !can_run_mago() {
+        return;
+    }
+
+    let (_temp_dir, workspace) = create_workspace();
+    let uri = format!("file://{}", workspace.join("src").join("example.php").display());
+
+    let (output, messages) = run_session(
+        &workspace,
+        &[
+            json!({ "jsonrpc": "2.0", "id": 1, "method": "initialize", "params": { "capabilities": {} } }),
+            json!({ "jsonrpc": "2.0", "method": "initialized", "params": {} }),
+            json!({
+                "jsonrpc": "2.0",
+                "method": "textDocument/didOpen",
+                "params": { "textDocument": { "uri": uri, "languageId": "php", "version": 1, "text": "<?php\n" } },
+            }),
+            json!({
+                "jsonrpc": "2.0",
+                "method": "textDocument/didChange",
+                "params": {
+                    "textDocument": { "uri": uri, "version": 2 },
+                    "contentChanges": [{ "text": "<?php\n\nfunction f(): int { return \"not an int\"; }\n" }],
+                },
+            }),
+            json!({ "jsonrpc": "2.0", "id": 2, "method": "shutdown" }),
+            json!({ "jsonrpc": "2.0", "method": "exit" }),
+        ],
+    );
+
+    assert!(output.status.success(), "stderr: {}", String::from_utf8_lossy(&output.stderr));
+
+    let initialize = response(&messages, 1);
+    assert_eq!(initialize["result"]["capabilities"]["documentFormattingProvider"], true);
+    assert_eq!(initialize["result"]["serverInfo"]["name"], "mago");
+
+    let published: Vec<&Value> = messages
+        .iter()
+        .filter(|m| m["method"] == "textDocument/publishDiagnostics" && m["params"]["uri"] == uri)
+        .collect();
+    assert_eq!(published.len(), 2, "expected one publish per buffer update: {messages:#?}");
+    assert!(
+        published[0]["params"]["diagnostics"].as_array().unwrap().iter().all(|d| d["source"] != "mago-analyzer"),
+        "the initial buffer must not produce analyzer diagnostics: {:#?}",
+        published[0]
+    );
+
+    let diagnostics = published[1]["params"]["diagnostics"].as_array().unwrap();
+    let analyzer_diagnostic = diagnostics
+        .iter()
+        .find(|d| d["source"] == "mago-analyzer")
+        .unwrap_or_else(|| panic!("expected an analyzer diagnostic: {diagnostics:#?}"));
+    assert_eq!(analyzer_diagnostic["range"]["start"]["line"], 2);
+
+    assert_eq!(response(&messages, 2)["result"], Value::Null);
+}
+
+#[test]
+fn test_lsp_guards_against_project_codebase() {
+    if !can_run_mago() {
+        return;
+    }
+
+    let (_temp_dir, workspace) = create_workspace();
+    std::fs::write(
+        workspace.join("mago.toml"),
+        r#"
+php-version = "8.4"
+[source]
+paths = ["src"]
+
+[[guard.structural.rules]]
+on = "App\\**"
+target = "class"
+must-be-final = true
+"#,
+    )
+    .unwrap();
+
+    let uri = format!("file://{}", workspace.join("src").join("example.php").display());
+    let text = "<?php\n\nnamespace App;\n\n/** @final */\nclass Service {}\n";
+
+    let (output, messages) = run_session(
+        &workspace,
+        &[
+            json!({ "jsonrpc": "2.0", "id": 1, "method": "initialize", "params": { "capabilities": {} } }),
+            json!({ "jsonrpc": "2.0", "method": "initialized", "params": {} }),
+            json!({
+                "jsonrpc": "2.0",
+                "method": "textDocument/didOpen",
+                "params": { "textDocument": { "uri": uri, "languageId": "php", "version": 1, "text": text } },
+            }),
+            json!({ "jsonrpc": "2.0", "id": 2, "method": "shutdown" }),
+            json!({ "jsonrpc": "2.0", "method": "exit" }),
+        ],
+    );
+
+    assert!(output.status.success(), "stderr: {}", String::from_utf8_lossy(&output.stderr));
+
+    let published = messages
+        .iter()
+        .find(|m| m["method"] == "textDocument/publishDiagnostics" && m["params"]["uri"] == uri)
+        .unwrap_or_else(|| panic!("expected diagnostics for the document: {messages:#?}"));
+    assert!(
+        published["params"]["diagnostics"].as_array().unwrap().iter().all(|d| d["source"] != "mago-guard"),
+        "the `@final` docblock must be read from the project codebase: {published:#?}"
+    );
+}
+
+#[test]
+fn test_lsp_formats_documents() {
+    if !can_run_mago() {
+        return;
+    }
+
+    let (_temp_dir, workspace) = create_workspace();
+    let uri = format!("file://{}", workspace.join("src").join("example.php").display());
+
+    let (output, messages) = run_session(
+        &workspace,
+        &[
+            json!({ "jsonrpc": "2.0", "id": 1, "method": "initialize", "params": { "capabilities": {} } }),
+            json!({
+                "jsonrpc": "2.0",
+                "method": "textDocument/didOpen",
+                "params": { "textDocument": { "uri": uri, "languageId": "php", "version": 1, "text": "<?php\n$x=1;\n" } },
+            }),
+            json!({
+                "jsonrpc": "2.0",
+                "id": 2,
+                "method": "textDocument/formatting",
+                "params": { "textDocument": { "uri": uri }, "options": { "tabSize": 4, "insertSpaces": true } },
+            }),
+            json!({ "jsonrpc": "2.0", "id": 3, "method": "textDocument/hover", "params": {} }),
+            json!({ "jsonrpc": "2.0", "id": 4, "method": "shutdown" }),
+            json!({ "jsonrpc": "2.0", "method": "exit" }),
+        ],
+    );
+
+    assert!(output.status.success(), "stderr: {}", String::from_utf8_lossy(&output.stderr));
+
+    let edits = response(&messages, 2)["result"].as_array().unwrap();
+    assert_eq!(edits.len(), 1);
+    assert_eq!(edits[0]["newText"], "<?php\n\n$x = 1;\n");
+    assert_eq!(edits[0]["range"]["end"], json!({ "line": 2, "character": 0 }));
+
+    assert_eq!(response(&messages, 3)["error"]["code"], -32601);
+}
+
+#[test]
+fn test_lsp_exit_without_shutdown_fails() {
+    if !can_run_mago() {
+        return;
+    }
+
+    let (_temp_dir, workspace) = create_workspace();
+
+    let (output, messages) = run_session(
+        &workspace,
+        &[json!({ "jsonrpc": "2.0", "id": 1, "method": "shutdown" }), json!({ "jsonrpc": "2.0", "method": "exit" })],
+    );
+
+    assert!(!output.status.success());
+    assert_eq!(response(&messages, 1)["error"]["code"], -32002);
+}
@@ -0,0 +1,193 @@
+//! Integration tests for `mago query`.
+//!
+//! These tests run the mago binary against a temporary workspace with a small class and
+//! a script using it, and verify the JSON produced by the `definition`, `references`,
+//! and `hover` queries.
+
+use std::path::Path;
+use std::path::PathBuf;
+use std::process::Command;
+use std::process::Stdio;
+
+use serde_json::Value;
+
+fn mago_bin() -> PathBuf {
+    let path = std::env::var("CARGO_BIN_EXE_mago")
+        .ok()
+        .or_else(|| option_env!("CARGO_BIN_EXE_mago").map(String::from))
+        .unwrap_or_else(|| "mago".to_string());
+
+    PathBuf::from(path)
+}
+
+/// Returns true if the mago binary can actually execute on this host.
+fn can_run_mago() -> bool {
+    Command::new(mago_bin())
+        .arg("--help")
+        .stdout(Stdio::null())
+        .stderr(Stdio::null())
+        .status()
+        .is_ok_and(|s| s.success())
+}
+
+fn setup_workspace() -> tempfile::TempDir {
+    let temp_dir = tempfile::tempdir().unwrap();
+    let workspace = temp_dir.path();
+
+    std::fs::create_dir(workspace.join("src")).unwrap();
+    std::fs::write(
+        workspace.join("mago.toml"),
+        r#"
+php-version = "8.4"
+[source]
+paths = ["src"]
+"#,
+    )
+    .unwrap();
+
+    std::fs::write(
+        workspace.join("src").join("Greeter.php"),
+        r#"<?php
+
+namespace App;
+
+final class Greeter
+{
+    public string $name = 'world';
+
+    /**
+     * Builds a greeting.
+     */
+    public function greet(string $name): string
+    {
+        return 'Hello ' . $name . $this->name;
+    }
+}
+"#,
+    )
+    .unwrap();
+
+    std::fs::write(
+        workspace.join("src").join("main.php"),
+        r#"<?php
+
+namespace App;
+
+$greeter = new Greeter();
+$message = $greeter->greet('world');
+echo $greeter->name;
+"#,
+    )
+    .unwrap();
+
+    temp_dir
+}
+
+fn run_query(workspace: &Path, kind: &str, position: &str) -> (std::process::Output, Value) {
+    let output = Command::new(mago_bin())
+        .args(["--workspace", workspace.to_str().unwrap(), "--no-version-check", "query", kind, position, "--no-stubs"])
+        .current_dir(workspace)
+        .output()
+        .expect("failed to run mago");
+
+    let json = serde_json::from_slice(&output.stdout)
+        .unwrap_or_else(|_| panic!("invalid JSON output: {}", String::from_utf8_lossy(&output.stdout)));
+
+    (output, json)
+}
+
+fn start_line(location: &Value) -> (&str, u64) {
+    (location["file"].as_str().unwrap(), location["start"]["line"].as_u64().unwrap())
+}
+
+#[test]
+fn test_query_definition_of_method_call() {
+    if !can_run_mago() {
+        return;
+    }
+
+    let temp_dir = setup_workspace();
+    let (output, json) = run_query(temp_dir.path(), "definition", "src/main.php:6:22");
+
+    assert!(output.status.success(), "stderr: {}", String::from_utf8_lossy(&output.stderr));
+    assert_eq!(json["symbol"]["kind"], "method");
+    assert_eq!(json["symbol"]["name"], "App\\Greeter::greet");
+    assert_eq!(start_line(&json["definition"]), ("src/Greeter.php", 12));
+    assert_eq!(json["definition"]["start"]["column"], 21);
+}
+
+#[test]
+fn test_query_definition_of_class_name() {
+    if !can_run_mago() {
+        return;
+    }
+
+    let temp_dir = setup_workspace();
+    let (output, json) = run_query(temp_dir.path(), "definition", "src/main.php:5:16");
+
+    assert!(output.status.success(), "stderr: {}", String::from_utf8_lossy(&output.stderr));
+    assert_eq!(json["symbol"]["kind"], "class-like");
+    assert_eq!(json["symbol"]["name"], "App\\Greeter");
+    assert_eq!(start_line(&json["definition"]), ("src/Greeter.php", 5));
+}
+
+#[test]
+fn test_query_references_of_property() {
+    if !can_run_mago() {
+        return;
+    }
+
+    let temp_dir = setup_workspace();
+    let (output, json) = run_query(temp_dir.path(), "references", "src/Greeter.php:7:20");
+
+    assert!(output.status.success(), "stderr: {}", String::from_utf8_lossy(&output.stderr));
+    assert_eq!(json["symbol"]["name"], "App\\Greeter::$name");
+
+    let mut references: Vec<(&str, u64)> = json["references"].as_array().unwrap().iter().map(start_line).collect();
+    references.sort();
+    assert_eq!(references, vec![("src/Greeter.php", 14), ("src/main.php", 7)]);
+}
+
+#[test]
+fn test_query_hover_renders_type_and_docblock() {
+    if !can_run_mago() {
+        return;
+    }
+
+    let temp_dir = setup_workspace();
+    let (output, json) = run_query(temp_dir.path(), "hover", "src/main.php:6:24");
+
+    assert!(output.status.success(), "stderr: {}", String::from_utf8_lossy(&output.stderr));
+    assert_eq!(json["signature"], "function App\\Greeter::greet(string $name): string");
+    assert_eq!(json["type"], "string");
+    assert!(json["docblock"].as_str().unwrap().contains("Builds a greeting."));
+}
+
+#[test]
+fn test_query_without_symbol_prints_null() {
+    if !can_run_mago() {
+        return;
+    }
+
+    let temp_dir = setup_workspace();
+    let (output, json) = run_query(temp_dir.path(), "definition", "src/main.php:1:1");
+
+    assert!(!output.status.success());
+    assert_eq!(json, Value::Null);
+}
+
+#[test]
+fn test_query_rejects_malformed_positions() {
+    if !can_run_mago() {
+        return;
+    }
+
+    let temp_dir = setup_workspace();
+    let output = Command::new(mago_bin())
+        .args(["--workspace", temp_dir.path().to_str().unwrap(), "query", "hover", "src/main.php:0:1"])
+        .output()
+        .expect("failed to run mago");
+
+    assert!(!output.status.success());
+    assert!(String::from_utf8_lossy(&output.stderr).contains("one-based"));
+}